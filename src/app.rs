@@ -1,9 +1,23 @@
-use crate::engine::{DifficultyLevel, EngineActor, EngineCommand, EngineEvent};
-use crate::game::{GameOutcome, GameState, PlayerColor, MoveRecord};
-use crate::study::{Study, StudyManager};
-use crate::ui::{ChessBoard, ControlPanel, ControlAction, MoveList, PieceRenderer, Theme, AnalysisPanel, StudyPanel, StudyNavAction};
-use shakmaty::{Move, Square};
+use crate::broadcast::{catch_up, sync_game, Broadcast, BroadcastSettings, BroadcastUpdate, MIN_INTERVAL_SECS};
+use crate::tournament::{Entrant, Tournament, TournamentSettings};
+use crate::cli::LaunchOptions;
+use crate::backup;
+use crate::config::{data_dir, write_atomic, Config};
+use crate::events::{AppEvent, UiRequest};
+use crate::explorer::{append_my_games, load_my_games, new_games, BookExit, CoverageReport, ExplorerClient, OpeningTree};
+use crate::dgt::{match_board, BoardMatch, DgtConnection};
+use crate::engine::{clean_up_orphans, varied_move, DifficultyLevel, EngineActor, EngineManager, IdleEngine, Personality, EngineRole, EngineCommand, EngineEvent, EngineTimeouts, VARIETY_LINES};
+use crate::game::{spoken, critical_moments, eval_loss, GameClock, GameOutcome, GameReport, GameState, HeatmapKind, latex_diagram, latex_line, Nag, parse_pgn, pgn_clock, PgnFile, PgnGame, PlayerColor, TakebackPolicy, premove_position, PremoveQueue, PremoveStep, QualitySummary, MoveRecord, ReportFormat, StagedMove, TimeControl};
+use crate::i18n::{self, tr, tr_args};
+use crate::power::PowerMonitor;
+use crate::online::{analysis_url, Challenge, LichessClient, OnlineEvent, OnlineGame, OnlinePlayer, OnlineSettings};
+use crate::study::{DrillSession, DrillStep, GameAnnotation, LineMatch, NodeId, RepertoireAudit, RepertoireDrill, Study};
+use crate::training::{today, Activity, DailyPuzzle, TrainingPlan, EndgameSession, ENDGAMES, PuzzleSession, PuzzleSource, PuzzleStep, PuzzleStreak, VisionHistory};
+use crate::ui::{AnalysisAction, OnlineAction, OnlinePanel, BroadcastAction, BroadcastPanel, ChessBoard, CompareWindow, ControlPanel, ControlAction, CoverageWindow, CriticalMomentsPanel, DrillSummaryWindow, PhaseStrip, QualityStrip, EngineLog, EngineLogWindow, EvalGraph, LogLevel, ExplorerPanel, GamesList, MoveList, PieceRenderer, Theme, AnalysisPanel, AnalysisSettings, StudyPanel, StudyNavAction, EngineActivity, StatusBar, SettingsAction, TournamentAction, TournamentPanel, TrainingPlanPanel, SettingsWindow, UciConsole, UciConsoleWindow, ToastAction, ToastLevel, Toasts, EngineOnboarding, OnboardingAction, DailyPuzzleCard, PuzzleAction, PuzzleCardAction, PuzzlePanel, ImportAction, ImportDialog, MoveEntry, MoveTimeGraph, PlayerPlate, ResignDialog, ResignPrompt, ResultAction, ResultDialog, PLATE_HEIGHT, Preferences, speak, Sound, VisionTrainer};
+use shakmaty::{Move, Position, Square};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::ControlFlow;
 use std::sync::mpsc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,6 +25,10 @@ pub enum AppMode {
     Game,
     Analysis,
     Study,
+    /// Board vision drills on an empty board
+    Training,
+    /// Games on Lichess through the Bot or Board API
+    Online,
 }
 
 impl Default for AppMode {
@@ -23,26 +41,281 @@ impl Default for AppMode {
 #[serde(default)]
 pub struct AppState {
     difficulty: DifficultyLevel,
+    /// Playing style of the engine in Game mode
+    personality: Personality,
     theme: Theme,
     player_color: PlayerColor,
+    /// Time control for Game mode, `None` for untimed games
+    time_control: Option<TimeControl>,
+    takebacks: TakebackPolicy,
+    preferences: Preferences,
     flipped: bool,
     mode: AppMode,
+    /// Delay between autoplay steps in milliseconds
+    autoplay_interval_ms: u64,
+    /// Engine binary chosen by the user, tried before the default locations
+    engine_path: Option<String>,
+    /// Window inner size from the last session
+    window_size: Option<[f32; 2]>,
+    /// Window outer position from the last session
+    window_position: Option<[f32; 2]>,
+    window_maximized: bool,
+    sidebar_width: f32,
+    move_list_height: f32,
+    /// Fraction of the central panel the board fills, adjusted by pinch zoom
+    board_scale: f32,
+    vision_history: VisionHistory,
+    puzzle_streak: PuzzleStreak,
+    training_plan: TrainingPlan,
+    broadcast: BroadcastSettings,
+    online: OnlineSettings,
+    tournament: TournamentSettings,
+    analysis: AnalysisSettings,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             difficulty: DifficultyLevel::Casual,
+            personality: Personality::Standard,
             theme: Theme::Classic,
             player_color: PlayerColor::White,
+            time_control: None,
+            takebacks: TakebackPolicy::default(),
+            preferences: Preferences::default(),
             flipped: false,
             mode: AppMode::Game,
+            autoplay_interval_ms: 1000,
+            engine_path: None,
+            window_size: None,
+            window_position: None,
+            window_maximized: false,
+            sidebar_width: 240.0,
+            move_list_height: 120.0,
+            board_scale: 1.0,
+            vision_history: VisionHistory::default(),
+            puzzle_streak: PuzzleStreak::default(),
+            training_plan: TrainingPlan::default(),
+            broadcast: BroadcastSettings::default(),
+            online: OnlineSettings::default(),
+            tournament: TournamentSettings::default(),
+            analysis: AnalysisSettings::default(),
         }
     }
 }
 
+/// Remaining times at which the player's clock warns about low time, longest first
+const LOW_TIME_THRESHOLDS: [std::time::Duration; 2] = [
+    std::time::Duration::from_secs(30),
+    std::time::Duration::from_secs(10),
+];
+
+/// Smallest board size, as a fraction of the central panel, reachable by pinch zoom
+const MIN_BOARD_SCALE: f32 = 0.4;
+/// Largest board size reachable by pinch zoom; a board larger than the panel scrolls
+const MAX_BOARD_SCALE: f32 = 3.0;
+
+/// Mouse wheel distance (in points) that steps one move
+const WHEEL_STEP: f32 = 40.0;
+
+/// Pause in mouse wheel movement after which a partial step is forgotten
+const WHEEL_IDLE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Search time for each position of a game review
+const REVIEW_MOVETIME_MS: u64 = 500;
+
+/// Most moves of a hovered engine line drawn on the board
+const MAX_PV_ARROWS: usize = 8;
+
+/// Copy of the settings in the data directory, written atomically as eframe's file isn't
+const STATE_COPY_FILE: &str = "app_state.json";
+
+/// How long closing the app waits for the engine to quit
+const EXIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Most critical moments listed after a game review
+const CRITICAL_MOMENTS: usize = 8;
+
+/// Best move the engine found for the game's latest position while an earlier one was being viewed
+struct PendingEngineMove {
+    fen: String,
+    uci: String,
+    /// White-relative score of the search that found it
+    score: Option<i32>,
+}
+
+/// Games of a dropped file waiting for the user to pick a destination
+enum PendingImport {
+    Games(Vec<PgnGame>),
+    /// Compressed file, read again as it is imported rather than held in memory
+    Archive(PgnFile, std::path::PathBuf),
+}
+
+impl PendingImport {
+    /// Number of games, unknown for an archive until it is read
+    fn len(&self) -> Option<usize> {
+        match self {
+            PendingImport::Games(games) => Some(games.len()),
+            PendingImport::Archive(..) => None,
+        }
+    }
+
+    /// Pass the games to `visit` one at a time until it breaks
+    fn for_each(self, mut visit: impl FnMut(PgnGame) -> ControlFlow<()>) -> std::io::Result<()> {
+        match self {
+            PendingImport::Games(games) => {
+                for game in games {
+                    if visit(game).is_break() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+            PendingImport::Archive(file, path) => file.read_games(std::fs::File::open(path)?, visit),
+        }
+    }
+}
+
+/// Games an import hands over at a time, so a large archive is never held at once
+const IMPORT_BATCH: usize = 200;
+
+/// What a background import sends back
+enum ImportMessage {
+    Games(Vec<PgnGame>),
+    /// All games were read; `duplicates` were already in the games database, `error` cut the
+    /// import short
+    Done { duplicates: usize, error: Option<String> },
+}
+
+/// Games of a file being read into a study or the games database on a background thread,
+/// added as they arrive
+struct ImportJob {
+    name: String,
+    /// `ImportAction::Study` or `ImportAction::MyGames`
+    destination: ImportAction,
+    /// Study the chapters go into; batches arriving after it was switched are dropped
+    study_id: String,
+    messages: mpsc::Receiver<ImportMessage>,
+    added: usize,
+    failed: usize,
+}
+
+/// Read `pending` in batches onto `messages`. Games for the games database are checked with
+/// `is_new` and appended to its file before they are sent.
+fn read_import(
+    pending: PendingImport,
+    mut is_new: Option<impl FnMut(&PgnGame) -> bool>,
+    messages: mpsc::Sender<ImportMessage>,
+) {
+    let to_database = is_new.is_some();
+    let mut batch = Vec::new();
+    let mut duplicates = 0;
+    let mut error = None;
+    let flush = |batch: &mut Vec<PgnGame>, error: &mut Option<String>| {
+        if to_database {
+            if let Err(e) = append_my_games(batch) {
+                *error = Some(e.to_string());
+                return false;
+            }
+        }
+        messages.send(ImportMessage::Games(std::mem::take(batch))).is_ok()
+    };
+    let read = pending.for_each(|game| {
+        if is_new.as_mut().is_some_and(|is_new| !is_new(&game)) {
+            duplicates += 1;
+            return ControlFlow::Continue(());
+        }
+        batch.push(game);
+        if batch.len() >= IMPORT_BATCH && !flush(&mut batch, &mut error) {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    });
+    if error.is_none() && !batch.is_empty() {
+        flush(&mut batch, &mut error);
+    }
+    if let Err(e) = read {
+        error.get_or_insert(e.to_string());
+    }
+    let _ = messages.send(ImportMessage::Done { duplicates, error });
+}
+
+/// Whether two (start FEN, moves) lines are from the same game, one leading on from the other
+fn same_line(a: &(String, Vec<String>), b: &(String, Vec<String>)) -> bool {
+    a.0 == b.0 && (a.1.starts_with(&b.1) || b.1.starts_with(&a.1))
+}
+
+/// Enlarge buttons and spacing so widgets are comfortable to hit with a finger
+fn apply_touch_mode(ctx: &egui::Context, enabled: bool) {
+    let defaults = egui::style::Spacing::default();
+    ctx.all_styles_mut(|style| {
+        if enabled {
+            style.spacing.interact_size = egui::vec2(48.0, 36.0);
+            style.spacing.button_padding = egui::vec2(12.0, 8.0);
+            style.spacing.item_spacing = egui::vec2(10.0, 8.0);
+        } else {
+            style.spacing.interact_size = defaults.interact_size;
+            style.spacing.button_padding = defaults.button_padding;
+            style.spacing.item_spacing = defaults.item_spacing;
+        }
+    });
+}
+
+/// Find a Stockfish binary, trying the user's configured path first
+fn find_stockfish(custom_path: Option<&str>) -> Option<String> {
+    let default_paths = [
+        "./stockfish",
+        "/Users/rj/Desktop/stockfish/stockfish-macos-m1-apple-silicon",
+        "~/bin/stockfish",
+        "/usr/local/bin/stockfish",
+        "/opt/homebrew/bin/stockfish",
+        "stockfish",
+    ];
+
+    custom_path
+        .into_iter()
+        .chain(default_paths)
+        .find(|p| {
+            let expanded = shellexpand::tilde(p);
+            std::path::Path::new(expanded.as_ref()).exists()
+        })
+        .map(|s| shellexpand::tilde(s).to_string())
+}
+
+/// The engine role a mode plays or analyzes with
+fn mode_role(mode: AppMode) -> EngineRole {
+    match mode {
+        AppMode::Game | AppMode::Online => EngineRole::Game,
+        AppMode::Analysis | AppMode::Study | AppMode::Training => EngineRole::Analysis,
+    }
+}
+
+/// Settings as last saved, read when eframe's own copy is missing or damaged
+fn load_state_copy() -> Option<AppState> {
+    let json = std::fs::read_to_string(data_dir().join(STATE_COPY_FILE)).ok()?;
+    let state = serde_json::from_str(&json).ok();
+    if state.is_some() {
+        tracing::warn!("Settings restored from {}", STATE_COPY_FILE);
+    }
+    state
+}
+
+/// Spawn the engine actor and ask it to initialize
+fn spawn_engine(
+    stockfish_path: Option<String>,
+    timeouts: EngineTimeouts,
+) -> (mpsc::Sender<EngineCommand>, mpsc::Receiver<EngineEvent>) {
+    let (engine_cmd_tx, engine_event_rx) = EngineActor::spawn(stockfish_path, timeouts);
+    // Sent right away so it comes before any command the app sends next
+    let _ = engine_cmd_tx.send(EngineCommand::Init);
+    (engine_cmd_tx, engine_event_rx)
+}
+
 pub struct ChessApp {
     game: GameState,
+    clock: GameClock,
+    /// Number of low-time thresholds the player's clock has already warned about
+    low_time_warnings: usize,
     state: AppState,
     piece_renderer: PieceRenderer,
 
@@ -56,6 +329,29 @@ pub struct ChessApp {
     engine_ready: bool,
     engine_thinking: bool,
     engine_analyzing: bool,
+    engine_name: Option<String>,
+    engine_nps: Option<u64>,
+    /// Depth reached by the current Game mode search
+    search_depth: Option<u32>,
+    /// Root move being searched and its index, from `currmove`
+    search_current_move: Option<(String, Option<u32>)>,
+    /// Id of the latest `Go` sent; best moves from any other search are stale
+    search_id: u64,
+    /// Start position and moves of the last analysis, to tell whether the next one follows the same line
+    analysis_line: Option<(String, Vec<String>)>,
+    /// Engine move held back until the user returns to the position it was found for
+    pending_engine_move: Option<PendingEngineMove>,
+    /// Moves taken back in the current game, limited by the takeback policy
+    takebacks_used: u32,
+    /// Resignation waiting for the player's answer
+    resign_prompt: Option<ResignPrompt>,
+    /// The player chose to play on after being offered resignation this game
+    auto_resign_declined: bool,
+    /// (score, first move) of each line of a search whose move is picked for variety or by the
+    /// engine's personality
+    variety_lines: Option<BTreeMap<u32, (i32, String)>>,
+
+    move_entry: MoveEntry,
 
     // Analysis
     analysis_panel: AnalysisPanel,
@@ -67,42 +363,211 @@ pub struct ChessApp {
     // Study
     study: Study,
     study_panel: StudyPanel,
+
+    vision_trainer: VisionTrainer,
+    /// Square activity overlay shown while reviewing
+    heatmap: Option<HeatmapKind>,
+    /// White-relative evaluation of game positions by index, from the engine's own searches
+    game_evals: Vec<Option<i32>>,
+    /// Latest main-line score of the engine's current game search, White-relative
+    search_score: Option<i32>,
+    /// Positions searched and queued by the running game review
+    review_progress: Option<(usize, usize)>,
+    /// Where the reviewed game left opening theory
+    book_exit: Option<BookExit>,
+    /// The game-over dialog was closed for the current game
+    result_dismissed: bool,
+    /// Dropped PGN file waiting for the user to pick a destination: (file name, games)
+    pending_import: Option<(String, PendingImport)>,
+    /// Import running in the background
+    import: Option<ImportJob>,
+    config: Config,
+    explorer: ExplorerClient,
+    explorer_panel: ExplorerPanel,
+    /// Imported games of the user, the source of the personal opening tree
+    my_games: Vec<PgnGame>,
+    games_list: GamesList,
+    /// The games database and its opening tree, while they are read on a background thread
+    my_games_loading: Option<mpsc::Receiver<(Vec<PgnGame>, OpeningTree)>>,
+    /// Games added and duplicates skipped by the last import, announced once the tree is rebuilt
+    my_games_added: Option<(usize, usize)>,
+    /// Games played against the engine since the app started, oldest first
+    session_games: Vec<PgnGame>,
+    /// Imported games must contain this in a tag to be exported with the session's games
+    export_filter: String,
+    /// Export imported games along with the session's games
+    export_my_games: bool,
+    opening_tree: OpeningTree,
+    /// Repertoire coverage of the user's games, while its window is open
+    coverage: Option<CoverageReport>,
+    daily_puzzle: DailyPuzzle,
+    show_puzzle_card: bool,
+    /// Puzzle being solved on the board in Analysis mode
+    puzzle: Option<PuzzleSession>,
+    /// Theoretical endgame being played out in Game mode
+    endgame: Option<EndgameSession>,
+    /// Moves to answer the engine's replies with in Game mode
+    premoves: PremoveQueue,
+    /// Conditional premove line being typed in the Game sidebar
+    premove_line: String,
+    /// Move held back in Game mode while the engine works out its cost
+    staged: Option<StagedMove>,
+    /// Review index for the next staged move, so results for an abandoned one are ignored
+    staged_index: usize,
+    /// (chapter id, path) of the study line end last counted toward the training plan
+    counted_line_end: Option<(usize, Vec<usize>)>,
+    /// (chapter index, node) of the study move just added and where another chapter has its line
+    duplicate_line: Option<(usize, NodeId, LineMatch)>,
+    /// Repertoire drill of a study chapter being played on the board
+    drill: Option<RepertoireDrill>,
+    /// Score of the drill session just ended, while its window is open
+    drill_summary: Option<DrillSession>,
+    /// Engine check of a study chapter's moves under way
+    audit: Option<RepertoireAudit>,
+    /// Engine annotation of a study chapter's main line under way
+    annotation: Option<GameAnnotation>,
+    /// Live PGN followed in Analysis mode
+    broadcast: Option<Broadcast>,
+    /// Engine round robin running in the background
+    tournament: Option<Tournament>,
+    lichess: Option<LichessClient>,
+    online_game: Option<OnlineGame>,
+    challenges: Vec<Challenge>,
+    /// Electronic board used for input
+    dgt: Option<DgtConnection>,
+    /// Engine errors and stderr output
+    engine_log: EngineLog,
+    show_engine_log: bool,
+    uci_console: UciConsole,
+    show_uci_console: bool,
+    compare: CompareWindow,
+    /// Events queued this frame, handled in order by `process_events`. Engine events are
+    /// handled at the start of the frame; actions the panels queue while drawing are handled
+    /// at the end of it and show on the next frame.
+    events: VecDeque<AppEvent>,
+    /// What the handled events asked of the window, done at the end of `process_events`
+    ui_requests: Vec<UiRequest>,
+    /// Number of positions in the game when the electronic board was last checked
+    dgt_positions: usize,
+    /// A move made in the app that still has to be made on the electronic board
+    dgt_pending: Option<String>,
+    /// The electronic board shows the position on screen
+    dgt_in_sync: bool,
+    power: PowerMonitor,
+    /// Search threads last given to the engine, `None` while it runs with its own setting
+    engine_threads: Option<u32>,
+    /// Engine used by roles without one of their own
+    default_engine: Option<String>,
+    /// Binary the running engine was started from
+    running_engine: Option<String>,
+    /// Engines of the other roles, left running while this one is in use
+    engines: EngineManager,
+    /// Whether the engine runs with fewer threads to save power
+    power_saving: bool,
+    /// Whether the power-saving pause applied on the last frame, so it only pauses analysis on the way in
+    power_pause_active: bool,
+
+    show_settings: bool,
+    /// Engine path being edited in the settings window
+    engine_path_input: String,
+    onboarding: Option<EngineOnboarding>,
+    toasts: Toasts,
+
+    /// Mouse wheel movement not yet turned into a navigation step
+    scroll_accumulator: f32,
+    /// When the mouse wheel last moved
+    last_scroll: std::time::Instant,
+
+    /// The real game, set aside while exploring on a scratch board
+    scratch_saved_game: Option<GameState>,
+
+    // Autoplay replay
+    autoplay: bool,
+    last_autoplay_step: std::time::Instant,
 }
 
 impl ChessApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // Load persisted state
-        let state: AppState = cc
+    pub fn new(cc: &eframe::CreationContext<'_>, launch: LaunchOptions) -> Self {
+        // Load persisted state, then let the hand-edited config file override it
+        let mut state: AppState = cc
             .storage
             .and_then(|s| eframe::get_value(s, eframe::APP_KEY))
+            .or_else(load_state_copy)
             .unwrap_or_default();
+        let (config, config_error) = match Config::load() {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(e)),
+        };
+        if let Some(path) = &config.engine_path {
+            state.engine_path = Some(path.clone());
+        }
+        if let Some(difficulty) = config.difficulty {
+            state.difficulty = difficulty;
+        }
+        if let Some(theme) = config.theme {
+            state.theme = theme;
+        }
+        i18n::set_language(state.preferences.language);
+        apply_touch_mode(&cc.egui_ctx, state.preferences.touch_mode);
 
-        // Spawn engine actor - try common stockfish locations
-        let stockfish_path = [
-            "./stockfish",
-            "/Users/rj/Desktop/stockfish/stockfish-macos-m1-apple-silicon",
-            "~/bin/stockfish",
-            "/usr/local/bin/stockfish",
-            "/opt/homebrew/bin/stockfish",
-            "stockfish",
-        ]
-        .iter()
-        .find(|p| {
-            let expanded = shellexpand::tilde(p);
-            std::path::Path::new(expanded.as_ref()).exists()
-        })
-        .map(|s| shellexpand::tilde(s).to_string());
+        // Restore window geometry from the last session
+        if let Some([width, height]) = state.window_size {
+            cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(width, height)));
+        }
+        if let Some([x, y]) = state.window_position {
+            cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(x, y)));
+        }
+        if state.window_maximized {
+            cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(true));
+        }
 
-        let (engine_cmd_tx, engine_event_rx) = EngineActor::spawn(stockfish_path);
+        // Engines a crashed session left running would keep burning CPU
+        let orphans = clean_up_orphans();
 
-        // Send init command
-        let cmd_tx = engine_cmd_tx.clone();
-        std::thread::spawn(move || {
-            let _ = cmd_tx.send(EngineCommand::Init);
-        });
+        let mut app = Self::with_state(state, config, &launch);
+        if let Some(e) = config_error {
+            app.toasts.error(tr_args("toast.config_invalid", &[&Config::path().display(), &e]));
+        }
+        if orphans > 0 {
+            app.toasts.info(tr_args("toast.orphans_killed", &[&orphans]));
+        }
+        app.reload_my_games();
+        app.apply_launch_options(launch);
+        if app.state.mode == AppMode::Online {
+            app.connect_online();
+        }
+        app.clear_selection();
+        app
+    }
+
+    /// The app around loaded `state` and `config`, with its engine started but nothing read
+    /// from disk and no window to draw in yet
+    fn with_state(state: AppState, config: Config, launch: &LaunchOptions) -> Self {
+        // Spawn engine actor - try the configured path, then common stockfish locations
+        let stockfish_path = find_stockfish(launch.engine.as_deref().or(state.engine_path.as_deref()));
+        // Guide the user through installing an engine instead of starting a dead Game mode
+        let onboarding = stockfish_path.is_none().then(EngineOnboarding::default);
+        let engine_path_input = state.engine_path.clone().unwrap_or_default();
+        // An engine given on the command line is used for everything
+        let running_engine = match launch.engine {
+            Some(_) => stockfish_path.clone(),
+            None => state.preferences.engine_roles.path(mode_role(state.mode)).or_else(|| stockfish_path.clone()),
+        };
+        let (engine_cmd_tx, engine_event_rx) = spawn_engine(running_engine.clone(), config.engine_timeouts);
 
-        let mut app = Self {
+        // Offer the daily puzzle unless it's solved already or a position was asked for
+        let puzzle_source = state.preferences.daily_puzzle;
+        let show_puzzle_card = puzzle_source != PuzzleSource::Off
+            && !state.puzzle_streak.solved_on(today())
+            && launch.pgn.is_none()
+            && launch.fen.is_none();
+        let daily_puzzle = DailyPuzzle::load(if show_puzzle_card { puzzle_source } else { PuzzleSource::Off });
+        let analysis_panel = AnalysisPanel::with_settings(state.analysis.clone());
+
+        Self {
             game: GameState::new(),
+            clock: GameClock::new(state.time_control),
+            low_time_warnings: 0,
             state,
             piece_renderer: PieceRenderer::new(),
             selected_square: None,
@@ -112,15 +577,121 @@ impl ChessApp {
             engine_ready: false,
             engine_thinking: false,
             engine_analyzing: false,
-            analysis_panel: AnalysisPanel::default(),
+            engine_name: None,
+            engine_nps: None,
+            search_depth: None,
+            search_current_move: None,
+            search_id: 0,
+            analysis_line: None,
+            pending_engine_move: None,
+            takebacks_used: 0,
+            resign_prompt: None,
+            auto_resign_declined: false,
+            variety_lines: None,
+            move_entry: MoveEntry::default(),
+            analysis_panel,
             checking_draw_offer: false,
             draw_offer_score: None,
-            study: Study::new("Untitled Study".to_string()),
+            study: Study::default(),
             study_panel: StudyPanel::default(),
-        };
+            vision_trainer: VisionTrainer::default(),
+            heatmap: None,
+            game_evals: Vec::new(),
+            search_score: None,
+            review_progress: None,
+            book_exit: None,
+            result_dismissed: false,
+            pending_import: None,
+            import: None,
+            explorer: ExplorerClient::new(config.lichess_token.clone()),
+            explorer_panel: ExplorerPanel::default(),
+            my_games: Vec::new(),
+            games_list: GamesList::default(),
+            my_games_loading: None,
+            my_games_added: None,
+            session_games: Vec::new(),
+            export_filter: String::new(),
+            export_my_games: false,
+            opening_tree: OpeningTree::default(),
+            coverage: None,
+            daily_puzzle,
+            show_puzzle_card,
+            puzzle: None,
+            endgame: None,
+            premoves: PremoveQueue::default(),
+            premove_line: String::new(),
+            staged: None,
+            staged_index: 0,
+            counted_line_end: None,
+            duplicate_line: None,
+            drill: None,
+            drill_summary: None,
+            audit: None,
+            annotation: None,
+            broadcast: None,
+            tournament: None,
+            lichess: None,
+            online_game: None,
+            challenges: Vec::new(),
+            dgt: None,
+            engine_log: EngineLog::default(),
+            show_engine_log: false,
+            uci_console: UciConsole::default(),
+            show_uci_console: false,
+            compare: CompareWindow::default(),
+            events: VecDeque::new(),
+            ui_requests: Vec::new(),
+            dgt_positions: 0,
+            dgt_pending: None,
+            dgt_in_sync: true,
+            power: PowerMonitor::start(),
+            engine_threads: None,
+            default_engine: stockfish_path,
+            running_engine,
+            engines: EngineManager::default(),
+            power_saving: false,
+            power_pause_active: false,
+            config,
+            show_settings: false,
+            engine_path_input,
+            onboarding,
+            toasts: Toasts::default(),
+            scroll_accumulator: 0.0,
+            last_scroll: std::time::Instant::now(),
+            scratch_saved_game: None,
+            autoplay: false,
+            last_autoplay_step: std::time::Instant::now(),
+        }
+    }
 
-        app.clear_selection();
-        app
+    /// Open the position and mode asked for on the command line. A position without a mode opens
+    /// in Analysis so it isn't replaced by a new game.
+    fn apply_launch_options(&mut self, launch: LaunchOptions) {
+        let opens_position = launch.pgn.is_some() || launch.fen.is_some();
+        if let Some(mode) = launch.mode.or(opens_position.then_some(AppMode::Analysis)) {
+            self.state.mode = mode;
+        }
+
+        if let Some(path) = launch.pgn {
+            let game = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| {
+                    let games = parse_pgn(&text);
+                    let first = games.first().ok_or_else(|| tr("toast.pgn_empty").to_string())?;
+                    first.to_game().map_err(|e| e.to_string())
+                });
+            match game {
+                Ok(game) => self.game = game,
+                Err(e) => self.toasts.error(tr_args("toast.open_failed", &[&path.display(), &e])),
+            }
+        }
+        if let Some(fen) = launch.fen {
+            match GameState::from_fen(&fen) {
+                Ok(game) => self.game = game,
+                Err(e) => self.toasts.error(tr_args("toast.open_failed", &[&fen, &e])),
+            }
+        }
+        self.orient_board();
     }
 
     fn clear_selection(&mut self) {
@@ -136,25 +707,228 @@ impl ChessApp {
                 self.legal_moves_for_selected = self.game.legal_moves_for_square(square);
                 return;
             }
+            let player_color: shakmaty::Color = self.state.player_color.into();
+            if color == player_color && self.can_premove() {
+                if let Some(position) = premove_position(&self.game.fen()) {
+                    self.selected_square = Some(square);
+                    self.legal_moves_for_selected = position.legal_moves_for_square(square);
+                    return;
+                }
+            }
+        }
+        self.clear_selection();
+    }
+
+    /// Whether a move made on the board now is queued until the engine has replied
+    fn can_premove(&self) -> bool {
+        self.state.mode == AppMode::Game
+            && self.game.outcome() == GameOutcome::InProgress
+            && self.game.turn() != self.state.player_color
+            && !self.game.can_go_forward()
+    }
+
+    /// Queue `m`, picked in the position with the engine's move skipped, in place of any earlier premoves
+    fn queue_premove(&mut self, m: Move) {
+        self.clear_selection();
+        let Some(mut position) = premove_position(&self.game.fen()) else {
+            return;
+        };
+        if let Ok(record) = position.make_move(m) {
+            self.premoves.clear();
+            self.premoves.push(record.uci, record.san);
+        }
+    }
+
+    /// Queued premoves, with a field for a conditional line to answer the engine's expected replies
+    fn show_premoves(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("premove.heading")).on_hover_text(tr("premove.heading_hint"));
+        let engine_to_move = self.can_premove();
+        ui.horizontal(|ui| {
+            let field = ui.add_enabled(
+                engine_to_move,
+                egui::TextEdit::singleline(&mut self.premove_line)
+                    .hint_text(tr("premove.line_hint"))
+                    .desired_width(150.0),
+            );
+            let submitted = field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let add = ui.add_enabled(engine_to_move && !self.premove_line.trim().is_empty(), egui::Button::new(tr("premove.set")));
+            if submitted || add.clicked() {
+                match self.premoves.set_line(&self.game.fen(), &self.premove_line) {
+                    Ok(()) => self.premove_line.clear(),
+                    Err(e) => self.toasts.error(tr_args("toast.premove_invalid", &[&e])),
+                }
+            }
+        });
+        if self.premoves.is_empty() {
+            ui.weak(tr("premove.empty"));
+            return;
+        }
+        for premove in self.premoves.iter() {
+            match &premove.condition_san {
+                Some(reply) => ui.label(tr_args("premove.conditional", &[reply, &premove.san])),
+                None => ui.label(tr_args("premove.any_reply", &[&premove.san])),
+            };
         }
+        if ui.small_button(tr("premove.clear")).clicked() {
+            self.premoves.clear();
+        }
+    }
+
+    /// Whether a move made on the board is held back for the engine to check first
+    fn should_stage(&self) -> bool {
+        self.state.preferences.check_moves
+            && self.state.mode == AppMode::Game
+            && self.engine_ready
+            && self.review_progress.is_none()
+            && !self.study_review_running()
+    }
+
+    /// Hold `m` back and have the engine evaluate the position before and after it
+    fn stage_move(&mut self, m: Move) {
         self.clear_selection();
+        let fen = self.game.fen();
+        let Ok(mut after) = GameState::from_fen(&fen) else {
+            return;
+        };
+        let Ok(record) = after.make_move(m) else {
+            return;
+        };
+        // A move that ends the game has nothing left to evaluate
+        if after.outcome() != GameOutcome::InProgress {
+            self.make_move(m);
+            return;
+        }
+        self.cancel_staged();
+        let index = self.staged_index;
+        self.staged_index += 2;
+        let positions = vec![(index, fen), (index + 1, record.resulting_fen)];
+        let _ = self.engine_cmd_tx.send(EngineCommand::Review { positions, movetime_ms: REVIEW_MOVETIME_MS });
+        self.staged = Some(StagedMove::new(m, record.san, index));
+    }
+
+    /// Drop the staged move, stopping the engine if it is still evaluating it
+    fn cancel_staged(&mut self) {
+        if self.staged.take().is_some_and(|staged| staged.cost().is_none()) {
+            let _ = self.engine_cmd_tx.send(EngineCommand::Stop);
+        }
+    }
+
+    /// The staged move and its cost, with buttons to play it or pick another
+    fn show_staged(&mut self, ui: &mut egui::Ui) {
+        let Some(staged) = &self.staged else {
+            return;
+        };
+        let m = staged.m;
+        ui.horizontal(|ui| {
+            ui.strong(tr_args("staged.move", &[&staged.san]));
+            match staged.cost() {
+                None => {
+                    ui.spinner();
+                    ui.weak(tr("staged.checking"));
+                }
+                Some(0) => {
+                    ui.label(tr("staged.no_loss"));
+                }
+                Some(cost) => {
+                    let pawns = format!("{:.1}", cost as f32 / 100.0);
+                    ui.label(tr_args("staged.cost", &[&pawns]));
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(tr("staged.play")).clicked() {
+                self.cancel_staged();
+                self.make_move(m);
+            }
+            if ui.button(tr("staged.cancel")).clicked() {
+                self.cancel_staged();
+            }
+        });
+    }
+
+    /// Play the next queued premove once the engine has replied, dropping the queue when the
+    /// reply wasn't the expected one or the move is no longer legal
+    fn play_premove(&mut self) {
+        if self.premoves.is_empty() || self.state.mode != AppMode::Game || self.game.can_go_forward() || !self.can_interact() {
+            return;
+        }
+        let reply = self.game.last_move().map(|record| record.uci.clone()).unwrap_or_default();
+        match self.premoves.next(&reply) {
+            PremoveStep::Play(uci) => match self.game.parse_move(&uci) {
+                Ok(m) => {
+                    self.make_move(m);
+                }
+                Err(_) => {
+                    self.premoves.clear();
+                    self.toasts.info(tr("toast.premove_illegal"));
+                }
+            },
+            PremoveStep::Invalidated => self.toasts.info(tr("toast.premove_cancelled")),
+            PremoveStep::Empty => {}
+        }
+    }
+
+    /// Whether the user may play a move on the board right now
+    fn can_interact(&self) -> bool {
+        match self.state.mode {
+            AppMode::Game => {
+                self.game.outcome() == GameOutcome::InProgress
+                    && !self.engine_thinking
+                    && self.game.turn() == self.state.player_color
+            }
+            AppMode::Analysis | AppMode::Study => {
+                // An unsolved puzzle is only played from its latest position
+                let puzzle_ok = self.puzzle.as_ref().map_or(true, |p| p.solved || !self.game.can_go_forward());
+                // A followed broadcast is explored on the scratch board so updates don't overwrite moves
+                let broadcast_ok = self.broadcast.is_none() || self.scratch_saved_game.is_some();
+                self.game.outcome() == GameOutcome::InProgress && puzzle_ok && broadcast_ok
+            }
+            AppMode::Training => false,
+            AppMode::Online => {
+                self.state.online.player == OnlinePlayer::Me
+                    && self.online_game.as_ref().is_some_and(OnlineGame::our_turn)
+                    && !self.game.can_go_forward()
+            }
+        }
     }
 
     fn make_move(&mut self, m: Move) -> Option<MoveRecord> {
+        // A locked study can be followed along its moves but not given new ones
+        if self.follows_study() && self.study.locked {
+            let uci = shakmaty::uci::UciMove::from_move(m, shakmaty::CastlingMode::Standard).to_string();
+            if self.study.current_chapter().child_index(&uci).is_none() {
+                self.clear_selection();
+                self.study_panel.prompt_copy();
+                return None;
+            }
+        }
         if let Ok(record) = self.game.make_move(m) {
             self.clear_selection();
             
             // In study mode, add to study tree
-            if self.state.mode == AppMode::Study {
-                self.study.current_chapter_mut().add_move(record.clone(), self.game.fen());
+            if self.follows_study() {
+                if self.study.current_chapter_mut().add_move(record.clone(), self.game.fen()) {
+                    self.warn_duplicate_line();
+                }
                 self.study.update_timestamp();
             }
             
+            if self.puzzle.is_some() {
+                self.check_puzzle_move(&record);
+            }
+            if self.drill.is_some() {
+                self.check_drill_move(&record);
+            }
+
             // In analysis mode, restart analysis on new position
             if self.state.mode == AppMode::Analysis && self.engine_analyzing {
                 self.start_analysis();
             } else if self.state.mode == AppMode::Game {
                 self.check_engine_turn();
+            } else if self.state.mode == AppMode::Online {
+                if let (Some(client), Some(game)) = (&self.lichess, &self.online_game) {
+                    client.play_move(&game.id, &record.uci);
+                }
             }
             
             Some(record)
@@ -184,21 +958,120 @@ impl ChessApp {
 
     fn start_engine_search(&mut self) {
         self.engine_thinking = true;
+        self.search_depth = None;
+        self.search_current_move = None;
+        self.search_score = None;
 
-        let fen = self.game.fen();
-        let moves: Vec<String> = Vec::new();
-
-        let cmd_tx = self.engine_cmd_tx.clone();
-        std::thread::spawn(move || {
-            let _ = cmd_tx
-                .send(EngineCommand::Go {
-                    fen,
-                    moves,
-                    movetime_ms: Some(1000),
-                });
+        self.search_id += 1;
+        // The engine's first moves are picked among several near-equal lines if variety is on,
+        // and every move is if it plays with a personality
+        let engine_moves = self.moves_by(self.state.player_color.opposite());
+        let varied = self.endgame.is_none() && self.state.preferences.variety.applies(engine_moves);
+        let multi_line = varied || self.engine_personality().is_biased();
+        self.variety_lines = multi_line.then(BTreeMap::new);
+        // Sent right away so a later Stop can't overtake them
+        let _ = self.engine_cmd_tx.send(EngineCommand::SetMultiPV(if multi_line { VARIETY_LINES } else { 1 }));
+        let _ = self.engine_cmd_tx.send(EngineCommand::Go {
+            search_id: self.search_id,
+            fen: self.game.starting_fen(),
+            moves: self.game.uci_moves(),
+            movetime_ms: Some(1000),
         });
     }
 
+    /// Moves `color` has made in the game
+    fn moves_by(&self, color: PlayerColor) -> usize {
+        let plies = self.game.move_history().len();
+        if self.game.starting_turn() == color {
+            plies.div_ceil(2)
+        } else {
+            plies / 2
+        }
+    }
+
+    /// Whether playing `moves` from `fen` leads to the game's latest position
+    fn is_game_head(&self, fen: &str, moves: &[String]) -> bool {
+        fen == self.game.starting_fen()
+            && moves.len() == self.game.move_history().len()
+            && moves.iter().zip(self.game.move_history()).all(|(uci, record)| *uci == record.uci)
+    }
+
+    /// Play the held-back engine move if the user is viewing the game's latest position and
+    /// it is still the one the move was found for
+    fn play_pending_engine_move(&mut self) {
+        if self.game.can_go_forward() {
+            return;
+        }
+        let Some(pending) = self.pending_engine_move.take() else {
+            return;
+        };
+        // Moves entered over the old ones while browsing leave it for a position the game no longer has
+        if pending.fen != self.game.fen() || self.game.outcome() != GameOutcome::InProgress {
+            tracing::debug!("Dropping engine move {} for a position the game has left", pending.uci);
+            return;
+        }
+
+        let index = self.game.current_index();
+        if self.game_evals.len() <= index {
+            self.game_evals.resize(index + 1, None);
+        }
+        self.game_evals[index] = pending.score;
+
+        if let Err(e) = self.game.make_move_uci(&pending.uci) {
+            tracing::error!("Failed to apply engine move: {}", e);
+            self.toasts.error(tr_args("toast.engine_move_failed", &[&pending.uci, &e]));
+            return;
+        }
+        if self.state.preferences.engine_move_sound {
+            Sound::EngineMove.play();
+        }
+        self.check_auto_resign();
+    }
+
+    /// Offer resignation once the engine's evaluations have had the player lost for long enough
+    fn check_auto_resign(&mut self) {
+        if self.auto_resign_declined || self.resign_prompt.is_some() || self.endgame.is_some() {
+            return;
+        }
+        let engine = self.state.player_color.opposite();
+        let player = self.state.player_color;
+        let first_mover = self.game.starting_turn();
+        // The engine's score for each of its moves is kept with the position it moved from
+        let scores = (0..self.game.move_history().len())
+            .rev()
+            .filter(|i| (if i % 2 == 0 { first_mover } else { first_mover.opposite() }) == engine)
+            .map(|i| {
+                self.game_evals.get(i).copied().flatten().map(|score| match player {
+                    PlayerColor::White => score,
+                    PlayerColor::Black => -score,
+                })
+            });
+        if let Some(score) = self.state.preferences.auto_resign.lost(scores) {
+            self.resign_prompt = Some(ResignPrompt::Lost { score });
+        }
+    }
+
+    fn resign(&mut self) {
+        self.interrupt_search();
+        self.game.resign(self.state.player_color);
+    }
+
+    /// Abandon the engine's move search, e.g. after an undo or resignation. The engine
+    /// is stopped and any best move it still reports for the old position is ignored.
+    fn interrupt_search(&mut self) {
+        if self.engine_thinking || self.checking_draw_offer {
+            let _ = self.engine_cmd_tx.send(EngineCommand::Stop);
+        }
+        self.search_id += 1;
+        self.pending_engine_move = None;
+        self.variety_lines = None;
+        self.premoves.clear();
+        self.cancel_staged();
+        self.engine_thinking = false;
+        self.checking_draw_offer = false;
+        self.draw_offer_score = None;
+    }
+
     fn start_analysis(&mut self) {
         if !self.engine_ready || self.engine_analyzing {
             return;
@@ -206,19 +1079,39 @@ impl ChessApp {
 
         self.engine_analyzing = true;
         self.analysis_panel.is_analyzing = true;
-        self.analysis_panel.clear();
+        self.analysis_panel.power_paused = false;
         // Store the base position where analysis started - all engine lines are relative to this
-        self.analysis_panel.base_fen = Some(self.game.fen());
+        self.analysis_panel.start(self.game.fen());
 
-        let fen = self.game.fen();
-        let moves: Vec<String> = Vec::new();
+        let fen = self.game.starting_fen();
+        let moves = self.game.uci_moves();
         // Always calculate max (5) lines, just display fewer
         let max_lines = 5;
+        // Moves excluded in the panel are left out by listing every other legal move
+        let searchmoves = if self.analysis_panel.excluded.is_empty() {
+            Vec::new()
+        } else {
+            self.game
+                .legal_moves()
+                .iter()
+                .map(|m| m.to_uci(shakmaty::CastlingMode::Standard).to_string())
+                .filter(|uci| !self.analysis_panel.excluded.contains(uci))
+                .collect()
+        };
+
+        // The engine's hash is only worth keeping for positions of the same game or study line
+        let line = self.current_line();
+        let fresh = !self.state.preferences.keep_hash
+            || !self.analysis_line.as_ref().is_some_and(|last| same_line(last, &line));
+        self.analysis_line = Some(line);
 
         let cmd_tx = self.engine_cmd_tx.clone();
         std::thread::spawn(move || {
+            if fresh {
+                let _ = cmd_tx.send(EngineCommand::NewGame);
+            }
             let _ = cmd_tx.send(EngineCommand::SetMultiPV(max_lines));
-            let _ = cmd_tx.send(EngineCommand::Analyze { fen, moves });
+            let _ = cmd_tx.send(EngineCommand::Analyze { fen, moves, searchmoves });
         });
     }
 
@@ -226,11 +1119,72 @@ impl ChessApp {
         if self.engine_analyzing {
             self.engine_analyzing = false;
             self.analysis_panel.is_analyzing = false;
-            
-            let cmd_tx = self.engine_cmd_tx.clone();
-            std::thread::spawn(move || {
-                let _ = cmd_tx.send(EngineCommand::Stop);
-            });
+            // Sent right away so it reaches the engine ahead of whatever the caller sends next
+            let _ = self.engine_cmd_tx.send(EngineCommand::Stop);
+        }
+    }
+
+    /// Positions of the game without an evaluation yet, as (index, FEN)
+    fn unreviewed_positions(&self) -> Vec<(usize, String)> {
+        let fens = std::iter::once(self.game.starting_fen())
+            .chain(self.game.move_history().iter().map(|m| m.resulting_fen.clone()));
+        fens.enumerate()
+            .filter(|(i, _)| self.game_evals.get(*i).copied().flatten().is_none())
+            .collect()
+    }
+
+    /// Have the engine evaluate every position still missing one. Results land in
+    /// `game_evals` as they arrive, so cancelling keeps what was found and a later review resumes.
+    fn start_review(&mut self) {
+        let positions = self.unreviewed_positions();
+        if positions.is_empty() || self.review_progress.is_some() || self.study_review_running() {
+            return;
+        }
+        self.engine_analyzing = false;
+        self.analysis_panel.is_analyzing = false;
+        self.review_progress = Some((0, positions.len()));
+        // Ahead of the review, which the engine finishes before taking other commands
+        self.sync_engine_binary();
+        self.sync_engine_threads();
+        let _ = self.engine_cmd_tx.send(EngineCommand::Review { positions, movetime_ms: REVIEW_MOVETIME_MS });
+
+        // The review can then start from the first move out of theory
+        if self.book_exit.is_none() {
+            let mut fen = self.game.starting_fen();
+            let moves = self
+                .game
+                .move_history()
+                .iter()
+                .map(|record| (std::mem::replace(&mut fen, record.resulting_fen.clone()), record.san.clone()))
+                .collect();
+            self.book_exit = Some(BookExit::find(moves, self.config.lichess_token.clone()));
+        }
+    }
+
+    fn cancel_review(&mut self) {
+        if self.review_progress.take().is_some() {
+            let _ = self.engine_cmd_tx.send(EngineCommand::Stop);
+            self.sync_engine_binary();
+        }
+    }
+
+    /// Start position and moves leading to the position on the board, through the study tree in Study mode
+    fn current_line(&self) -> (String, Vec<String>) {
+        if self.follows_study() {
+            let chapter = self.study.current_chapter();
+            (chapter.root().fen.clone(), chapter.current_line_uci())
+        } else {
+            (self.game.starting_fen(), self.game.uci_moves())
+        }
+    }
+
+    /// Empty the engine's hash for a fresh evaluation, restarting analysis if it was running
+    fn clear_engine_hash(&mut self) {
+        let analyzing = self.engine_analyzing;
+        self.stop_analysis();
+        let _ = self.engine_cmd_tx.send(EngineCommand::ClearHash);
+        if analyzing {
+            self.start_analysis();
         }
     }
 
@@ -242,92 +1196,479 @@ impl ChessApp {
         }
     }
 
-    fn process_engine_events(&mut self, ctx: &egui::Context) {
+    /// Take all pending engine events, keeping only the newest `Info` per MultiPV line
+    /// between other events so a burst of output costs one panel update per line
+    fn drain_engine_events(&mut self) -> Vec<EngineEvent> {
+        let mut events: Vec<EngineEvent> = Vec::new();
         while let Ok(event) = self.engine_event_rx.try_recv() {
-            match event {
-                EngineEvent::Ready => {
-                    tracing::info!("Engine is ready");
-                    self.engine_ready = true;
-
-                    let cmd_tx = self.engine_cmd_tx.clone();
-                    let difficulty = self.state.difficulty;
-                    std::thread::spawn(move || {
-                        let _ = cmd_tx.send(EngineCommand::SetDifficulty(difficulty));
-                    });
-
-                    if self.state.mode == AppMode::Game {
-                        self.check_engine_turn();
-                    }
+            if let EngineEvent::Info { multipv, .. } = &event {
+                let line = multipv.unwrap_or(1);
+                let run_start = events
+                    .iter()
+                    .rposition(|e| !matches!(e, EngineEvent::Info { .. }))
+                    .map_or(0, |i| i + 1);
+                let superseded = events[run_start..].iter().position(|e| {
+                    matches!(e, EngineEvent::Info { multipv, .. } if multipv.unwrap_or(1) == line)
+                });
+                if let Some(i) = superseded {
+                    events.remove(run_start + i);
                 }
-                EngineEvent::BestMove { best_move, .. } => {
-                    tracing::info!("Engine best move: {}", best_move);
-                    self.engine_thinking = false;
-                    
-                    // Check if we're evaluating a draw offer
-                    if self.checking_draw_offer {
-                        self.checking_draw_offer = false;
-                        // Accept draw if white is ahead (positive score from white's perspective)
-                        let accept_draw = self.draw_offer_score.map_or(false, |score| score > 0);
-                        if accept_draw {
-                            self.game.agree_to_draw();
-                            tracing::info!("Draw accepted - white is ahead by {:?} cp", self.draw_offer_score);
-                        } else {
-                            tracing::info!("Draw declined - white is not ahead (score: {:?})", self.draw_offer_score);
-                        }
-                        self.draw_offer_score = None;
-                    } else {
-                        // Normal gameplay - apply engine move
-                        if let Err(e) = self.game.make_move_uci(&best_move) {
-                            tracing::error!("Failed to apply engine move: {}", e);
-                        }
-                    }
+            }
+            events.push(event);
+        }
+        events
+    }
+
+    /// Queue the engine's output, see `drain_engine_events`
+    fn poll_engine(&mut self) {
+        self.engines.poll();
+        for event in self.drain_engine_events() {
+            self.events.push_back(AppEvent::Engine(event));
+        }
+    }
 
-                    ctx.request_repaint();
+    fn handle_engine_event(&mut self, event: EngineEvent) {
+        match event {
+            EngineEvent::Identified { name, author } => {
+                tracing::info!("Engine identified as {} ({:?})", name, author);
+                self.engine_name = Some(name);
+            }
+            EngineEvent::Ready => {
+                tracing::info!("Engine is ready");
+                self.engine_ready = true;
+                self.engine_threads = None;
+                self.configure_engine();
+            }
+            EngineEvent::BestMove { search_id, fen, moves, best_move, .. } => {
+                if search_id != self.search_id {
+                    tracing::debug!("Ignoring best move {} of an abandoned search", best_move);
+                    return;
                 }
-                EngineEvent::Info { depth, score_cp, score_mate, pv, nodes, multipv, .. } => {
-                    let line_id = multipv.unwrap_or(1);
-                    self.analysis_panel.update_line(line_id, score_cp, score_mate, depth, pv);
-                    if let Some(n) = nodes {
-                        self.analysis_panel.total_nodes = n;
+                tracing::info!("Engine best move: {}", best_move);
+                self.engine_thinking = false;
+                
+                if self.state.mode == AppMode::Online {
+                    // make_move sends the move to Lichess
+                    if self.state.online.player == OnlinePlayer::Engine {
+                        self.game.go_to_end();
                     }
-                    
-                    // Capture score for draw offer evaluation
-                    if self.checking_draw_offer {
-                        if let Some(mate) = score_mate {
-                            // Convert mate score to a large centipawn value
-                            self.draw_offer_score = Some(if mate > 0 { 10000 } else { -10000 });
-                        } else if let Some(cp) = score_cp {
-                            self.draw_offer_score = Some(cp);
+                    if self.online_engine_to_move() {
+                        if let Ok(m) = self.game.parse_move(&best_move) {
+                            self.make_move(m);
                         }
                     }
+                } else if self.checking_draw_offer {
+                    // Evaluating a draw offer
+                    self.checking_draw_offer = false;
+                    // Accept draw if white is ahead (positive score from white's perspective)
+                    let accept_draw = self.draw_offer_score.map_or(false, |score| score > 0);
+                    if accept_draw {
+                        self.game.agree_to_draw();
+                        tracing::info!("Draw accepted - white is ahead by {:?} cp", self.draw_offer_score);
+                    } else {
+                        tracing::info!("Draw declined - white is not ahead (score: {:?})", self.draw_offer_score);
+                    }
+                    self.draw_offer_score = None;
+                } else if self.game.outcome() == GameOutcome::InProgress && self.is_game_head(&fen, &moves) {
+                    // Normal gameplay - apply engine move (not after a flag or resignation), once
+                    // the user is back at the position it was found for
+                    let margin = self.state.preferences.variety.margin_cp;
+                    let engine_moves = self.moves_by(self.state.player_color.opposite());
+                    let varied = self.endgame.is_none() && self.state.preferences.variety.applies(engine_moves);
+                    let personality = self.engine_personality();
+                    let uci = self
+                        .variety_lines
+                        .take()
+                        .and_then(|lines| {
+                            let lines: Vec<(i32, String)> = lines.into_values().collect();
+                            if varied {
+                                varied_move(&lines, margin, &mut fastrand::Rng::new()).map(str::to_string)
+                            } else {
+                                let head = GameState::from_fen(&self.game.head_fen()).ok()?;
+                                personality
+                                    .pick_move(head.current_position(), &lines, engine_moves)
+                                    .map(str::to_string)
+                            }
+                        })
+                        .unwrap_or(best_move);
+                    self.pending_engine_move = Some(PendingEngineMove {
+                        fen: self.game.head_fen(),
+                        uci,
+                        score: self.search_score.take(),
+                    });
+                    self.play_pending_engine_move();
+                } else {
+                    tracing::debug!("Ignoring best move {} for a position the game has left", best_move);
                 }
-                EngineEvent::Error(e) => {
-                    tracing::error!("Engine error: {}", e);
-                    self.engine_thinking = false;
-                    self.engine_analyzing = false;
-                    self.analysis_panel.is_analyzing = false;
+
+                self.ui_requests.push(UiRequest::Repaint);
+            }
+            EngineEvent::Info { depth, score_cp, score_mate, pv, nodes, nps, multipv, .. } => {
+                if nps.is_some() {
+                    self.engine_nps = nps;
+                }
+                if self.engine_thinking && depth.is_some() {
+                    self.search_depth = depth;
+                }
+                // Scores are relative to the engine, which is the side to move
+                let score = score_mate.map(|mate| if mate > 0 { 10000 } else { -10000 }).or(score_cp);
+                if let (true, Some(lines), Some(score), Some(first)) =
+                    (self.engine_thinking, self.variety_lines.as_mut(), score, pv.first())
+                {
+                    lines.insert(multipv.unwrap_or(1), (score, first.clone()));
+                }
+                if self.engine_thinking && multipv.unwrap_or(1) == 1 {
+                    if let Some(score) = score {
+                        self.search_score = Some(match self.game.turn() {
+                            PlayerColor::White => score,
+                            PlayerColor::Black => -score,
+                        });
+                    }
+                }
+                let line_id = multipv.unwrap_or(1);
+                self.analysis_panel.update_line(line_id, score_cp, score_mate, depth, pv);
+                if let Some(n) = nodes {
+                    self.analysis_panel.total_nodes = n;
+                }
+                
+                // Capture score for draw offer evaluation
+                if self.checking_draw_offer {
+                    if let Some(mate) = score_mate {
+                        // Convert mate score to a large centipawn value
+                        self.draw_offer_score = Some(if mate > 0 { 10000 } else { -10000 });
+                    } else if let Some(cp) = score_cp {
+                        self.draw_offer_score = Some(cp);
+                    }
+                }
+            }
+            EngineEvent::CurrentMove { depth, uci, number } => {
+                if self.engine_thinking {
+                    if depth.is_some() {
+                        self.search_depth = depth;
+                    }
+                    self.search_current_move = Some((uci, number));
+                    self.ui_requests.push(UiRequest::Repaint);
+                }
+            }
+            EngineEvent::ReviewProgress { index, score_cp, score_mate, depth, pv } => {
+                if let Some(annotation) = self.annotation.as_mut() {
+                    annotation.record(index, score_cp, score_mate, depth, pv);
+                    self.ui_requests.push(UiRequest::Repaint);
+                    return;
+                }
+                if let Some(audit) = self.audit.as_mut() {
+                    audit.record(index, score_mate.map(|mate| if mate > 0 { 10000 } else { -10000 }).or(score_cp));
+                    self.ui_requests.push(UiRequest::Repaint);
+                    return;
+                }
+                if self.review_progress.is_none() {
+                    if let Some(staged) = self.staged.as_mut() {
+                        let score = score_mate.map(|mate| if mate > 0 { 10000 } else { -10000 }).or(score_cp);
+                        if staged.record(index, score) {
+                            self.ui_requests.push(UiRequest::Repaint);
+                        }
+                    }
+                }
+                // Results of a review cancelled by a new game are dropped
+                let Some((done, _)) = self.review_progress.as_mut() else {
+                    return;
+                };
+                *done += 1;
+                let mover = if index % 2 == 0 {
+                    self.game.starting_turn()
+                } else {
+                    self.game.starting_turn().opposite()
+                };
+                let score = score_mate
+                    .map(|mate| if mate > 0 { 10000 } else { -10000 })
+                    .or(score_cp)
+                    .map(|score| match mover {
+                        PlayerColor::White => score,
+                        PlayerColor::Black => -score,
+                    });
+                if self.game_evals.len() <= index {
+                    self.game_evals.resize(index + 1, None);
+                }
+                self.game_evals[index] = score;
+                self.ui_requests.push(UiRequest::Repaint);
+            }
+            EngineEvent::ReviewDone { cancelled } => {
+                if let Some(annotation) = self.annotation.take() {
+                    if cancelled {
+                        self.toasts.info(tr("annotate.cancelled"));
+                    } else if let Some(chapter) = annotation.chapter_in(&mut self.study) {
+                        let engine = self.engine_name.as_deref().unwrap_or("Stockfish");
+                        let marked = annotation.apply(chapter, engine);
+                        self.study.update_timestamp();
+                        self.toasts.info(tr_args("annotate.done", &[&marked]));
+                    } else {
+                        self.toasts.warning(tr("audit.stale"));
+                    }
+                    self.ui_requests.push(UiRequest::Repaint);
+                    return;
+                }
+                if let Some(audit) = self.audit.take() {
+                    if cancelled {
+                        self.toasts.info(tr("audit.cancelled"));
+                    } else if let Some(chapter) = audit.chapter_in(&mut self.study) {
+                        let flagged = audit.apply(chapter);
+                        self.study.update_timestamp();
+                        self.toasts.info(tr_args("audit.done", &[&flagged]));
+                    } else {
+                        self.toasts.warning(tr("audit.stale"));
+                    }
+                    self.ui_requests.push(UiRequest::Repaint);
+                    return;
+                }
+                if self.review_progress.take().is_some() {
+                    if !cancelled {
+                        self.toasts.info(tr("toast.review_done"));
+                        self.record_activity(Activity::GameReviewed);
+                    }
+                    self.sync_engine_binary();
+                }
+                self.ui_requests.push(UiRequest::Repaint);
+            }
+            EngineEvent::Stderr(line) => {
+                self.engine_log.push(LogLevel::Output, line);
+            }
+            EngineEvent::Output(line) => {
+                self.uci_console.push_output(line);
+                self.ui_requests.push(UiRequest::Repaint);
+            }
+            EngineEvent::Warning(e) => {
+                tracing::warn!("Engine warning: {}", e);
+                self.engine_log.push(LogLevel::Error, e.to_string());
+                self.toasts.report_warning(&e, e.action());
+            }
+            EngineEvent::Error(e) => {
+                tracing::error!("Engine error: {}", e);
+                self.engine_log.push(LogLevel::Error, e.to_string());
+                // Before the engine is up, errors mean it couldn't be started at all
+                let action = if self.engine_ready { e.action() } else { Some(ToastAction::OpenSettings) };
+                self.toasts.report(&e, action);
+                self.engine_thinking = false;
+                self.engine_analyzing = false;
+                self.analysis_panel.is_analyzing = false;
+            }
+            EngineEvent::Terminated => {
+                tracing::warn!("Engine terminated");
+                self.toasts.warning(tr("toast.engine_terminated"));
+                self.engine_ready = false;
+                self.engine_thinking = false;
+                self.engine_analyzing = false;
+                self.analysis_panel.is_analyzing = false;
+            }
+        }
+    }
+
+    /// Handle the queued events, then do what they asked of the window
+    fn process_events(&mut self, ctx: &egui::Context) {
+        self.handle_events();
+        for request in self.ui_requests.drain(..) {
+            match request {
+                UiRequest::Repaint => ctx.request_repaint(),
+                UiRequest::CopyText(text) => ctx.copy_text(text),
+                UiRequest::TouchMode(enabled) => apply_touch_mode(ctx, enabled),
+            }
+        }
+    }
+
+    /// Handle the queued events in order, including any queued while handling them
+    fn handle_events(&mut self) {
+        while let Some(event) = self.events.pop_front() {
+            if event.is_user() {
+                tracing::debug!("Event: {:?}", event);
+            } else {
+                tracing::trace!("Event: {:?}", event);
+            }
+            self.dispatch(event);
+        }
+    }
+
+    fn dispatch(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Engine(event) => self.handle_engine_event(event),
+            AppEvent::Move(m) => {
+                self.make_move(m);
+            }
+            AppEvent::Control(action) => self.handle_control_action(action),
+            AppEvent::StudyNav(action) => self.handle_study_nav_action(action),
+            AppEvent::Settings(action) => self.handle_settings_action(action),
+            AppEvent::Toast(ToastAction::OpenSettings) => self.show_settings = true,
+            AppEvent::Toast(ToastAction::ShowEngineLog) => self.show_engine_log = true,
+            AppEvent::Toast(ToastAction::GoToDuplicateLine) => self.go_to_duplicate_line(),
+            AppEvent::Result(action) => self.handle_result_action(action),
+            AppEvent::Import(action) => self.handle_import_action(action),
+            AppEvent::Puzzle(action) => self.handle_puzzle_action(action),
+            AppEvent::PuzzleCard(action) => self.handle_puzzle_card_action(action),
+            AppEvent::Broadcast(action) => self.handle_broadcast_action(action),
+            AppEvent::Tournament(action) => self.handle_tournament_action(action),
+            AppEvent::Online(action) => self.handle_online_action(action),
+        }
+    }
+
+    /// Remember window geometry so the next session reopens in the same place
+    fn record_window_layout(&mut self, ctx: &egui::Context) {
+        ctx.input(|i| {
+            let viewport = i.viewport();
+            self.state.window_maximized = viewport.maximized.unwrap_or(false);
+            // Keep the restored (non-maximized) geometry while maximized
+            if !self.state.window_maximized {
+                if let Some(rect) = viewport.inner_rect {
+                    self.state.window_size = Some([rect.width(), rect.height()]);
                 }
-                EngineEvent::Terminated => {
-                    tracing::warn!("Engine terminated");
-                    self.engine_ready = false;
-                    self.engine_thinking = false;
-                    self.engine_analyzing = false;
-                    self.analysis_panel.is_analyzing = false;
+                if let Some(rect) = viewport.outer_rect {
+                    self.state.window_position = Some([rect.min.x, rect.min.y]);
                 }
             }
+        });
+    }
+
+    /// Copy the clock into moves not yet stamped, once the mover's increment has been credited
+    fn stamp_move_clocks(&mut self) {
+        let history = self.game.move_history();
+        let first = history.iter().rposition(|r| r.time_spent.is_some()).map_or(0, |i| i + 1);
+        let first_mover = self.game.starting_turn();
+        for i in first..history.len().min(self.clock.move_times().len()) {
+            let mover = if i % 2 == 0 { first_mover } else { first_mover.opposite() };
+            self.game.set_move_clock(i, self.clock.move_times()[i], self.clock.remaining(mover));
+        }
+    }
+
+    /// Run the clock of the side to move while a game is being played
+    fn update_clock(&mut self, ctx: &egui::Context) {
+        // The clock follows the game, not the move being looked at
+        let playing = self.state.mode == AppMode::Game && self.game.head_outcome() == GameOutcome::InProgress;
+        if self.state.mode == AppMode::Game {
+            // Credit new moves before the clock is handed to the next side
+            self.clock.sync_moves(self.game.move_history().len());
+        }
+        self.clock.set_running(playing.then(|| self.game.head_turn()));
+        if self.state.mode == AppMode::Game {
+            self.stamp_move_clocks();
+        }
+        if !playing {
+            return;
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+
+        if let Some(side) = self.clock.flagged() {
+            tracing::info!("{:?} lost on time", side);
+            self.game.lose_on_time(side);
+            self.clock.set_running(None);
+            self.interrupt_search();
+            if self.state.preferences.clock.flag_sound {
+                Sound::Flag.play();
+            }
+            return;
+        }
+
+        // Warn once per threshold as the player's own clock runs down
+        let Some(remaining) = self.clock.remaining(self.state.player_color) else {
+            return;
+        };
+        let crossed = LOW_TIME_THRESHOLDS.iter().filter(|t| remaining < **t).count();
+        if crossed > self.low_time_warnings {
+            self.low_time_warnings = crossed;
+            if self.state.preferences.clock.warning_sound {
+                Sound::LowTime.play();
+            }
+        }
+    }
+
+    /// Name plate for one side of the board
+    fn show_player_plate(&self, ui: &mut egui::Ui, side: PlayerColor) {
+        let opponent = match side {
+            PlayerColor::White => PlayerColor::Black,
+            PlayerColor::Black => PlayerColor::White,
+        };
+        let online = self.online_game.as_ref().filter(|_| self.state.mode == AppMode::Online);
+        let (name, elo, clock) = if let Some(game) = online {
+            let player = game.player(side);
+            (player.name.as_str(), player.rating, Some(game.remaining(side)))
+        } else if self.state.mode == AppMode::Game {
+            if side == self.state.player_color {
+                (tr("plate.you"), None, Some(self.clock.display(side)))
+            } else {
+                (
+                    self.engine_name.as_deref().unwrap_or("Stockfish"),
+                    Some(self.state.difficulty.approximate_elo()),
+                    Some(self.clock.display(side)),
+                )
+            }
+        } else {
+            let name = match side {
+                PlayerColor::White => tr("plate.white"),
+                PlayerColor::Black => tr("plate.black"),
+            };
+            (name, None, None)
+        };
+
+        let captured = self.game.captured_pieces(opponent);
+        PlayerPlate {
+            name,
+            elo,
+            clock,
+            clock_running: match online {
+                Some(game) => game.clock_running(side),
+                None => self.clock.running_side() == Some(side),
+            },
+            low_time: self.state.preferences.clock.flash
+                && self.clock.running_side() == Some(side)
+                && self.clock.remaining(side).is_some_and(|r| r < LOW_TIME_THRESHOLDS[0]),
+            captured: &captured,
+            captured_color: opponent.into(),
+            advantage: self.game.material_advantage(side),
+        }
+        .show(ui);
+    }
+
+    /// Depth and current move of the engine's search, for the thinking indicator
+    fn search_progress(&self) -> Option<String> {
+        let depth = self.search_depth?;
+        let mut text = tr_args("controls.search_depth", &[&depth]);
+        if let Some((uci, number)) = &self.search_current_move {
+            let san = self.game.uci_to_san(uci).unwrap_or_else(|| uci.clone());
+            text.push_str(" · ");
+            text.push_str(&san);
+            if let Some(number) = number {
+                text.push_str(&format!(" ({})", number));
+            }
+        }
+        Some(text)
+    }
+
+    fn engine_activity(&self) -> EngineActivity {
+        if !self.engine_ready {
+            EngineActivity::Offline
+        } else if self.engine_thinking || self.checking_draw_offer {
+            EngineActivity::Thinking
+        } else if self.engine_analyzing || self.review_progress.is_some() {
+            EngineActivity::Analyzing
+        } else {
+            EngineActivity::Idle
         }
     }
 
     fn new_game(&mut self) {
+        self.archive_game();
+        self.end_endgame();
         self.stop_analysis();
+        self.cancel_review();
         self.game.reset();
+        self.clock = GameClock::new(self.state.time_control);
+        self.game_evals.clear();
+        self.book_exit = None;
+        self.result_dismissed = false;
+        self.low_time_warnings = 0;
+        self.takebacks_used = 0;
+        self.resign_prompt = None;
+        self.auto_resign_declined = false;
         self.clear_selection();
-        self.engine_thinking = false;
-
-        let cmd_tx = self.engine_cmd_tx.clone();
-        std::thread::spawn(move || {
-            let _ = cmd_tx.send(EngineCommand::NewGame);
-        });
+        self.interrupt_search();
+        // Ahead of the first search of the new game, which it would otherwise cut short
+        let _ = self.engine_cmd_tx.send(EngineCommand::NewGame);
 
         if self.state.mode == AppMode::Game && self.state.player_color == PlayerColor::Black {
             self.check_engine_turn();
@@ -338,6 +1679,127 @@ impl ChessApp {
         }
     }
 
+    fn set_engine_path(&mut self, path: String) {
+        self.state.engine_path = Some(path.clone());
+        let saved = self.config.set_engine_path(&path);
+        self.report_config_save(saved);
+        self.default_engine = Some(path.clone());
+        // Idle engines may run the binary this replaces
+        self.engines.retain(&[]);
+        let path = self.state.preferences.engine_roles.path(self.engine_role()).unwrap_or(path);
+        self.restart_engine(path);
+    }
+
+    /// What the engine is currently used for
+    fn engine_role(&self) -> EngineRole {
+        if self.review_progress.is_some() {
+            EngineRole::Review
+        } else {
+            mode_role(self.state.mode)
+        }
+    }
+
+    /// Switch to the engine assigned to what the engine is used for if another one is running
+    fn sync_engine_binary(&mut self) {
+        let role = self.engine_role();
+        let wanted = self.state.preferences.engine_roles.path(role);
+        // Reviews stay on the engine in use unless one is assigned
+        let wanted = match role {
+            EngineRole::Review => wanted,
+            _ => wanted.or_else(|| self.default_engine.clone()),
+        };
+        if let Some(path) = wanted.filter(|path| self.running_engine.as_ref() != Some(path)) {
+            self.switch_engine(path);
+        }
+        self.engines.retain(&self.engines_in_use());
+    }
+
+    /// Binaries the roles are assigned, so idle engines running anything else can be quit
+    fn engines_in_use(&self) -> Vec<String> {
+        let roles = &self.state.preferences.engine_roles;
+        [EngineRole::Game, EngineRole::Analysis]
+            .into_iter()
+            .filter_map(|role| roles.path(role).or_else(|| self.default_engine.clone()))
+            .chain(roles.path(EngineRole::Review))
+            .collect()
+    }
+
+    /// Make the engine running `path` the one in use, leaving the current one running idle
+    /// for its role
+    fn switch_engine(&mut self, path: String) {
+        tracing::info!("Switching engine to: {}", path);
+        self.stop_analysis();
+        let next = self.engines.take(&path).unwrap_or_else(|| {
+            let (cmd_tx, event_rx) = spawn_engine(Some(path.clone()), self.config.engine_timeouts);
+            IdleEngine { path, cmd_tx, event_rx, ready: false, name: None, threads: None }
+        });
+        let IdleEngine { path, cmd_tx, event_rx, ready, name, threads } = next;
+        let current = IdleEngine {
+            path: self.running_engine.replace(path).unwrap_or_default(),
+            cmd_tx: std::mem::replace(&mut self.engine_cmd_tx, cmd_tx),
+            event_rx: std::mem::replace(&mut self.engine_event_rx, event_rx),
+            ready: std::mem::replace(&mut self.engine_ready, ready),
+            name: std::mem::replace(&mut self.engine_name, name),
+            threads: std::mem::replace(&mut self.engine_threads, threads),
+        };
+        // No role asks for an engine started without a binary of its own
+        if current.path.is_empty() {
+            let _ = current.cmd_tx.send(EngineCommand::Quit);
+        } else {
+            self.engines.park(current);
+        }
+        self.engine_thinking = false;
+        self.engine_nps = None;
+        // An engine started earlier sends no Ready to be set up by
+        if self.engine_ready {
+            self.configure_engine();
+        }
+    }
+
+    /// Give the engine the threads the current role and power situation allow, leaving its own
+    /// setting alone unless one of them sets a limit. Engines only take a new thread count
+    /// between searches, so analysis is restarted around it.
+    fn sync_engine_threads(&mut self) {
+        let cores = self.power.cores();
+        let roles = &self.state.preferences.engine_roles;
+        let threads = match self.state.preferences.power.threads(cores, self.power_saving) {
+            Some(threads) => Some(roles.threads(self.engine_role(), threads)),
+            None => Some(roles.threads(self.engine_role(), cores)).filter(|&threads| threads < cores),
+        };
+        if self.engine_ready && self.engine_threads != threads {
+            tracing::info!("Engine threads: {:?}", threads);
+            self.engine_threads = threads;
+            let analyzing = self.engine_analyzing;
+            self.stop_analysis();
+            let _ = self.engine_cmd_tx.send(EngineCommand::SetThreads(threads));
+            if analyzing {
+                self.start_analysis();
+            }
+        }
+    }
+
+    fn report_config_save(&mut self, result: std::io::Result<()>) {
+        if let Err(e) = result {
+            tracing::warn!("Could not write {}: {}", Config::path().display(), e);
+            self.toasts.error(tr_args("toast.config_save_failed", &[&e]));
+        }
+    }
+
+    fn handle_result_action(&mut self, action: ResultAction) {
+        match action {
+            ResultAction::Rematch => self.new_game(),
+            ResultAction::Analyze => {
+                self.result_dismissed = true;
+                self.set_mode(AppMode::Analysis);
+            }
+            ResultAction::Export => {
+                self.ui_requests.push(UiRequest::CopyText(self.export_game_pgn()));
+                self.toasts.info(tr("toast.pgn_copied"));
+            }
+            ResultAction::Close => self.result_dismissed = true,
+        }
+    }
+
     fn handle_control_action(&mut self, action: ControlAction) {
         match action {
             ControlAction::NewGame => {
@@ -348,47 +1810,133 @@ impl ChessApp {
             }
             ControlAction::SetDifficulty(level) => {
                 self.state.difficulty = level;
+                let saved = self.config.set_difficulty(level);
+                self.report_config_save(saved);
                 let cmd_tx = self.engine_cmd_tx.clone();
                 std::thread::spawn(move || {
                     let _ = cmd_tx.send(EngineCommand::SetDifficulty(level));
                 });
             }
+            ControlAction::SetPersonality(personality) => {
+                self.state.personality = personality;
+                self.send_engine_difficulty();
+            }
             ControlAction::SetTheme(theme) => {
                 tracing::info!("Setting theme to: {:?}", theme);
                 self.state.theme = theme;
+                let saved = self.config.set_theme(theme);
+                self.report_config_save(saved);
+            }
+            ControlAction::SetTimeControl(time_control) => {
+                self.state.time_control = time_control;
+                self.new_game();
             }
             ControlAction::SetPlayerColor(color) => {
                 self.state.player_color = color;
+                self.orient_board();
                 self.new_game();
             }
             ControlAction::Resign => {
-                self.game.resign(self.state.player_color);
+                self.resign_prompt = Some(ResignPrompt::Confirm);
             }
             ControlAction::OfferDraw => {
                 // Check position with engine - accept draw if white is ahead
                 self.check_draw_offer();
             }
             ControlAction::Undo => {
-                // Undo last two moves (player and engine)
-                self.undo_last_moves();
+                if self.state.takebacks.allows(self.takebacks_used) {
+                    self.undo_last_moves();
+                }
             }
         }
     }
     
+    /// Replace the running engine with the binary at `path`
+    fn restart_engine(&mut self, path: String) {
+        tracing::info!("Restarting engine with: {}", path);
+        self.stop_analysis();
+        let _ = self.engine_cmd_tx.send(EngineCommand::Quit);
+
+        let (engine_cmd_tx, engine_event_rx) = spawn_engine(Some(path.clone()), self.config.engine_timeouts);
+        self.running_engine = Some(path);
+        self.engine_cmd_tx = engine_cmd_tx;
+        self.engine_event_rx = engine_event_rx;
+        self.engine_ready = false;
+        self.engine_thinking = false;
+        self.engine_name = None;
+        self.engine_nps = None;
+    }
+
+    fn handle_settings_action(&mut self, action: SettingsAction) {
+        match action {
+            SettingsAction::Usernames => {
+                self.rebuild_opening_tree();
+            }
+            SettingsAction::EnginePath(path) => {
+                self.set_engine_path(path);
+            }
+            SettingsAction::EngineRoles => self.sync_engine_binary(),
+            SettingsAction::DgtConnect(port) => {
+                self.dgt = Some(DgtConnection::open(port));
+                self.dgt_positions = self.game.position_count();
+                self.dgt_pending = None;
+            }
+            SettingsAction::DgtDisconnect => {
+                self.dgt = None;
+            }
+            SettingsAction::ShowEngineLog => self.show_engine_log = true,
+            SettingsAction::ShowUciConsole => self.show_uci_console = true,
+            SettingsAction::Backup => self.create_backup(),
+            SettingsAction::Restore => self.restore_backup(),
+            SettingsAction::Language(language) => {
+                tracing::info!("Setting language to: {:?}", language);
+                self.state.preferences.language = language;
+                i18n::set_language(language);
+            }
+            SettingsAction::Notation(notation) => {
+                tracing::info!("Setting notation to: {:?}", notation);
+                self.state.preferences.notation = notation;
+            }
+            SettingsAction::OrientToPlayer(enabled) => {
+                self.state.preferences.orient_to_player = enabled;
+                self.orient_board();
+            }
+            SettingsAction::TouchMode(enabled) => {
+                self.state.preferences.touch_mode = enabled;
+                self.ui_requests.push(UiRequest::TouchMode(enabled));
+            }
+        }
+    }
+
+    /// Put the player's side (or the study chapter's side) at the bottom, if enabled.
+    /// The flip button still overrides this until the next color or chapter change.
+    fn orient_board(&mut self) {
+        if !self.state.preferences.orient_to_player {
+            return;
+        }
+        let side = match self.state.mode {
+            AppMode::Game => self.state.player_color,
+            AppMode::Study => self.study.current_chapter().orientation,
+            AppMode::Online => match &self.online_game {
+                Some(game) => game.color,
+                None => return,
+            },
+            AppMode::Analysis | AppMode::Training => return,
+        };
+        self.state.flipped = side == PlayerColor::Black;
+    }
+
     fn check_draw_offer(&mut self) {
         // Quick analysis: if white is ahead, accept draw
         // We'll use a simple evaluation - start a brief analysis
         if self.engine_ready && !self.engine_thinking && !self.engine_analyzing {
-            let fen = self.game.fen();
-            let cmd_tx = self.engine_cmd_tx.clone();
-            
             // Request a quick evaluation
-            std::thread::spawn(move || {
-                let _ = cmd_tx.send(EngineCommand::Go {
-                    fen,
-                    moves: Vec::new(),
-                    movetime_ms: Some(500), // 500ms quick eval
-                });
+            self.search_id += 1;
+            let _ = self.engine_cmd_tx.send(EngineCommand::Go {
+                search_id: self.search_id,
+                fen: self.game.starting_fen(),
+                moves: self.game.uci_moves(),
+                movetime_ms: Some(500), // 500ms quick eval
             });
             
             // Store that we're checking a draw offer
@@ -397,42 +1945,34 @@ impl ChessApp {
         }
     }
     
+    /// Take back the player's last move and the engine's reply to it, counting one takeback
     fn undo_last_moves(&mut self) {
-        // Undo the last two moves (player's move and engine's response)
-        // First, if engine is thinking, stop it
-        if self.engine_thinking {
-            let cmd_tx = self.engine_cmd_tx.clone();
-            std::thread::spawn(move || {
-                let _ = cmd_tx.send(EngineCommand::Stop);
-            });
-            self.engine_thinking = false;
+        if self.moves_by(self.state.player_color) == 0 {
+            return;
         }
-        
-        // Undo moves until it's the player's turn again
-        let target_turn = self.state.player_color;
+        // First, if engine is thinking, stop it
+        self.interrupt_search();
+
+        // Remove moves until the player is back at the position they last moved from
         let mut undone = 0;
-        
-        while self.game.turn() != target_turn && self.game.can_go_back() {
-            if self.game.go_back().is_ok() {
-                undone += 1;
-            } else {
+        while self.game.undo_last_move() {
+            undone += 1;
+            if self.game.turn() == self.state.player_color {
                 break;
             }
         }
-        
-        // Also remove the moves from history if we're at the end
-        if !self.game.can_go_forward() && undone > 0 {
-            // Truncate history
-            for _ in 0..undone {
-                self.game.undo_last_move();
-            }
-        }
-        
+        self.game_evals.truncate(self.game.position_count());
+        self.takebacks_used += 1;
+
         self.clear_selection();
         tracing::info!("Undid {} moves", undone);
     }
     
     fn handle_study_nav_action(&mut self, action: StudyNavAction) {
+        self.discard_scratch();
+        if !matches!(action, StudyNavAction::StartDrill | StudyNavAction::DrillHint | StudyNavAction::OrientationChanged | StudyNavAction::Audit(_) | StudyNavAction::Annotate) {
+            self.end_drill();
+        }
         match action {
             StudyNavAction::GoToPosition(path) => {
                 // Navigate study chapter to the specified path
@@ -446,172 +1986,1660 @@ impl ChessApp {
                     self.clear_selection();
                     tracing::info!("Navigated to study position: {:?}", path);
                 }
+
+                // Reaching the end of a line counts as reviewing it, once in a row
+                let chapter = self.study.current_chapter();
+                let line_end = (chapter.id, path);
+                if self.state.mode == AppMode::Study
+                    && !line_end.1.is_empty()
+                    && chapter.current_node().children.is_empty()
+                    && self.counted_line_end.as_ref() != Some(&line_end)
+                {
+                    self.counted_line_end = Some(line_end);
+                    self.record_activity(Activity::LineReviewed);
+                }
                 
                 // Restart analysis if active
                 if self.state.mode == AppMode::Analysis && self.engine_analyzing {
                     self.start_analysis();
                 }
             }
+            StudyNavAction::ChapterChanged => {
+                let path = self.study.current_chapter().current_path.clone();
+                self.handle_study_nav_action(StudyNavAction::GoToPosition(path));
+                self.orient_board();
+            }
+            StudyNavAction::OrientationChanged => {
+                self.study.update_timestamp();
+                self.orient_board();
+            }
+            StudyNavAction::StartDrill => self.start_drill(),
+            StudyNavAction::DrillHint => self.drill_hint(),
+            StudyNavAction::Audit(threshold_cp) => self.start_audit(threshold_cp),
+            StudyNavAction::Annotate => self.start_annotation(),
+            StudyNavAction::StopDrill => {
+                let path = self.study.current_chapter().current_path.clone();
+                self.handle_study_nav_action(StudyNavAction::GoToPosition(path));
+            }
         }
     }
 
-    fn go_to_previous_position(&mut self) {
-        if self.game.can_go_back() {
-            self.clear_selection();
-            let _ = self.game.go_back();
-            
-            if self.state.mode == AppMode::Study {
-                self.study.current_chapter_mut().go_back();
-            }
-            
-            if self.state.mode == AppMode::Analysis && self.engine_analyzing {
-                self.start_analysis();
-            }
+    /// Squares of the engine line under the pointer in the analysis panel, when it was found
+    /// for the position on the board
+    fn hovered_pv_arrows(&mut self) -> Vec<(Square, Square)> {
+        let Some(pv) = self.analysis_panel.hovered_pv.take() else {
+            return Vec::new();
+        };
+        if self.analysis_panel.base_fen.as_deref() != Some(self.game.fen().as_str()) {
+            return Vec::new();
         }
+        pv.iter()
+            .take(MAX_PV_ARROWS)
+            .map_while(|uci| Some((uci.get(0..2)?.parse().ok()?, uci.get(2..4)?.parse().ok()?)))
+            .collect()
     }
 
-    fn go_to_next_position(&mut self) {
-        if self.game.can_go_forward() {
-            self.clear_selection();
-            let _ = self.game.go_forward();
-            
-            if self.state.mode == AppMode::Study {
-                // In study mode, try to follow the main line
-                self.study.current_chapter_mut().go_to_child(0);
+    /// Have the engine check the current chapter's moves for its side
+    fn start_audit(&mut self, threshold_cp: i32) {
+        if !self.engine_ready {
+            self.toasts.error(tr("audit.no_engine"));
+            return;
+        }
+        if self.review_progress.is_some() || self.staged.is_some() || self.study_review_running() {
+            self.toasts.warning(tr("audit.busy"));
+            return;
+        }
+        let (audit, positions) = RepertoireAudit::new(&self.study, threshold_cp);
+        if positions.is_empty() {
+            self.toasts.info(tr_args("audit.done", &[&0]));
+            return;
+        }
+        self.stop_analysis();
+        self.sync_engine_threads();
+        let _ = self.engine_cmd_tx.send(EngineCommand::Review { positions, movetime_ms: REVIEW_MOVETIME_MS });
+        self.audit = Some(audit);
+    }
+
+    /// Whether the engine is auditing or annotating a study chapter
+    fn study_review_running(&self) -> bool {
+        self.audit.is_some() || self.annotation.is_some()
+    }
+
+    /// Have the engine annotate every move of the current chapter's main line
+    fn start_annotation(&mut self) {
+        if !self.engine_ready {
+            self.toasts.error(tr("audit.no_engine"));
+            return;
+        }
+        if self.review_progress.is_some() || self.staged.is_some() || self.study_review_running() {
+            self.toasts.warning(tr("audit.busy"));
+            return;
+        }
+        let (annotation, positions) = GameAnnotation::new(&self.study);
+        // The root alone has no moves to annotate
+        if positions.len() < 2 {
+            self.toasts.info(tr("annotate.nothing"));
+            return;
+        }
+        self.stop_analysis();
+        self.sync_engine_threads();
+        let _ = self.engine_cmd_tx.send(EngineCommand::Review { positions, movetime_ms: REVIEW_MOVETIME_MS });
+        self.annotation = Some(annotation);
+    }
+
+    /// Progress of the running engine audit or annotation, with a button to cancel it
+    fn show_audit_progress(&mut self, ui: &mut egui::Ui) {
+        let progress = match (&self.audit, &self.annotation) {
+            (Some(audit), _) => tr_args("audit.progress", &[&audit.done, &audit.total()]),
+            (None, Some(annotation)) => tr_args("annotate.progress", &[&annotation.done, &annotation.total()]),
+            (None, None) => return,
+        };
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(progress);
+            if ui.button(tr("audit.cancel")).clicked() {
+                let _ = self.engine_cmd_tx.send(EngineCommand::Stop);
             }
-            
-            if self.state.mode == AppMode::Analysis && self.engine_analyzing {
-                self.start_analysis();
+        });
+    }
+
+    /// Drill the current chapter from its side, line after line until stopped
+    fn start_drill(&mut self) {
+        let chapter = self.study.current_chapter();
+        if !RepertoireDrill::can_drill(chapter) {
+            self.toasts.warning(tr("drill.nothing"));
+            return;
+        }
+        let Ok(game) = GameState::from_fen(&chapter.root().fen) else {
+            return;
+        };
+        let (drill, reply) = RepertoireDrill::start(self.study.current_chapter, chapter, self.state.preferences.drill);
+        self.game = game;
+        self.clear_selection();
+        self.orient_board();
+        self.drill = Some(drill);
+        if let Some(reply) = reply {
+            self.play_drill_reply(reply);
+        }
+    }
+
+    /// End the drill, showing its score if any move was asked for
+    fn end_drill(&mut self) {
+        if let Some(drill) = self.drill.take() {
+            if drill.session.positions > 0 {
+                self.drill_summary = Some(drill.session);
             }
         }
     }
 
-    fn go_to_start(&mut self) {
+    /// Set the board up for another line of the drilled chapter
+    fn next_drill_line(&mut self) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        let chapter = &self.study.chapters[drill.chapter];
+        let Ok(game) = GameState::from_fen(&chapter.root().fen) else {
+            return;
+        };
+        let reply = drill.next_line(chapter);
+        self.game = game;
         self.clear_selection();
-        self.game.go_to_start();
-        
-        if self.state.mode == AppMode::Study {
-            self.study.current_chapter_mut().go_to_start();
+        if let Some(reply) = reply {
+            self.play_drill_reply(reply);
         }
-        
-        if self.state.mode == AppMode::Analysis && self.engine_analyzing {
-            self.start_analysis();
+    }
+
+    /// Play the opponent's move leading to `node` of the drilled chapter
+    fn play_drill_reply(&mut self, node: NodeId) {
+        let Some(drill) = &self.drill else {
+            return;
+        };
+        let chapter = &self.study.chapters[drill.chapter];
+        let Some(uci) = chapter.node(node).move_record.as_ref().map(|m| m.uci.clone()) else {
+            return;
+        };
+        if self.game.make_move_uci(&uci).is_err() {
+            tracing::warn!("Drill reply {} is illegal", uci);
         }
     }
 
-    fn go_to_end(&mut self) {
-        self.clear_selection();
+    fn check_drill_move(&mut self, record: &MoveRecord) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        let step = drill.play(&mut self.study.chapters[drill.chapter], &record.uci);
+        if matches!(step, DrillStep::Wrong | DrillStep::Failed) {
+            self.game.undo_last_move();
+        }
+        self.study.update_timestamp();
+        self.handle_drill_step(step);
+    }
+
+    /// Count the clock of a timed drill running out as a mistake
+    fn update_drill(&mut self, ctx: &egui::Context) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        let Some(left) = drill.time_left() else {
+            return;
+        };
+        if left.is_zero() {
+            let step = drill.time_out(&mut self.study.chapters[drill.chapter]);
+            self.toasts.warning(tr("drill.time_out"));
+            self.handle_drill_step(step);
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    fn handle_drill_step(&mut self, step: DrillStep) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        match step {
+            DrillStep::Wrong => {
+                let left = drill.settings.allowed_mistakes + 1 - drill.line_mistakes;
+                self.toasts.warning(tr_args("drill.wrong", &[&left]));
+            }
+            DrillStep::Failed => {
+                self.toasts.error(tr("drill.failed"));
+                self.next_drill_line();
+            }
+            DrillStep::Correct(reply) => {
+                if let Some(reply) = reply {
+                    self.play_drill_reply(reply);
+                }
+                let Some(drill) = &mut self.drill else {
+                    return;
+                };
+                if drill.finished(&self.study.chapters[drill.chapter]) {
+                    self.toasts.info(tr("drill.finished"));
+                    self.record_activity(Activity::LineReviewed);
+                    self.next_drill_line();
+                }
+            }
+        }
+    }
+
+    /// Show the repertoire move of the drilled position, if the drill allows hints
+    fn drill_hint(&mut self) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        if let Some(moves) = drill.hint(&mut self.study.chapters[drill.chapter]) {
+            self.toasts.info(tr_args("drill.hint", &[&moves.join(", ")]));
+        }
+    }
+
+    fn go_to_previous_position(&mut self) {
+        if self.game.can_go_back() {
+            self.clear_selection();
+            let _ = self.game.go_back();
+            
+            if self.follows_study() {
+                self.study.current_chapter_mut().go_back();
+            }
+            
+            if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+                self.start_analysis();
+            }
+        }
+    }
+
+    fn go_to_next_position(&mut self) {
+        if self.game.can_go_forward() {
+            self.clear_selection();
+            let _ = self.game.go_forward();
+            
+            if self.follows_study() {
+                // In study mode, try to follow the main line
+                self.study.current_chapter_mut().go_to_child(0);
+            }
+            
+            if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+                self.start_analysis();
+            }
+        }
+    }
+
+    /// Jump to the position after `index` moves of the game
+    fn go_to_position(&mut self, index: usize) {
+        if index == self.game.current_index() || self.game.go_to_position(index).is_err() {
+            return;
+        }
+        self.clear_selection();
+
+        if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+            self.start_analysis();
+        }
+    }
+
+    fn go_to_start(&mut self) {
+        self.clear_selection();
+        self.game.go_to_start();
+        
+        if self.follows_study() {
+            self.study.current_chapter_mut().go_to_start();
+        }
+        
+        if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+            self.start_analysis();
+        }
+    }
+
+    fn go_to_end(&mut self) {
+        self.clear_selection();
         self.game.go_to_end();
         
-        if self.state.mode == AppMode::Study {
+        if self.follows_study() {
             // Go to end of main line
             while self.study.current_chapter().can_go_forward(0) {
                 self.study.current_chapter_mut().go_to_child(0);
             }
         }
-        
-        if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+        
+        if self.state.mode == AppMode::Analysis && self.engine_analyzing {
+            self.start_analysis();
+        }
+    }
+
+    /// Whether there is a next position to step to (study main line in Study mode)
+    fn can_step_forward(&self) -> bool {
+        if self.follows_study() {
+            self.study.current_chapter().can_go_forward(0)
+        } else {
+            self.game.can_go_forward()
+        }
+    }
+
+    /// Step one move forward, following the study main line in Study mode
+    fn step_forward(&mut self) {
+        if self.follows_study() {
+            let mut path = self.study.current_chapter().current_path.clone();
+            path.push(0);
+            self.handle_study_nav_action(StudyNavAction::GoToPosition(path));
+        } else {
+            self.go_to_next_position();
+        }
+    }
+
+    /// Step one move back, following the study tree in Study mode
+    fn step_backward(&mut self) {
+        if self.follows_study() {
+            let mut path = self.study.current_chapter().current_path.clone();
+            if path.pop().is_some() {
+                self.handle_study_nav_action(StudyNavAction::GoToPosition(path));
+            }
+        } else {
+            self.go_to_previous_position();
+        }
+    }
+
+    /// Turn mouse wheel movement over the board and mouse back/forward buttons into navigation
+    fn handle_navigation_input(&mut self, ctx: &egui::Context, scroll_delta: f32) {
+        let keys = self.config.keybindings;
+        // Leave keys to text fields such as move entry while they have focus
+        let typing = ctx.wants_keyboard_input();
+        let pressed = |key| !typing && ctx.input(|i| i.key_pressed(key));
+        let (back, forward) = ctx.input(|i| {
+            (
+                i.pointer.button_pressed(egui::PointerButton::Extra1),
+                i.pointer.button_pressed(egui::PointerButton::Extra2),
+            )
+        });
+        if back || pressed(keys.previous) {
+            self.step_backward();
+        }
+        if forward || pressed(keys.next) {
+            self.step_forward();
+        }
+        if pressed(keys.start) {
+            self.go_to_start();
+        }
+        if pressed(keys.end) {
+            self.go_to_end();
+        }
+        if pressed(keys.flip) {
+            self.state.flipped = !self.state.flipped;
+        }
+
+        // Wheels and touchpads report movement on some frames only, so a partial step is kept
+        // until the wheel turns the other way or rests
+        let now = std::time::Instant::now();
+        if scroll_delta == 0.0 {
+            if now.duration_since(self.last_scroll) > WHEEL_IDLE {
+                self.scroll_accumulator = 0.0;
+            }
+            return;
+        }
+        if scroll_delta.signum() != self.scroll_accumulator.signum() {
+            self.scroll_accumulator = 0.0;
+        }
+        self.last_scroll = now;
+        // Scrolling down moves forward through the game, like Lichess
+        self.scroll_accumulator += scroll_delta;
+        while self.scroll_accumulator >= WHEEL_STEP {
+            self.scroll_accumulator -= WHEEL_STEP;
+            self.step_backward();
+        }
+        while self.scroll_accumulator <= -WHEEL_STEP {
+            self.scroll_accumulator += WHEEL_STEP;
+            self.step_forward();
+        }
+    }
+
+    fn toggle_autoplay(&mut self) {
+        self.autoplay = !self.autoplay;
+        if self.autoplay && !self.can_step_forward() {
+            // Restart from the beginning when already at the end
+            if self.follows_study() {
+                self.handle_study_nav_action(StudyNavAction::GoToPosition(Vec::new()));
+            } else {
+                self.go_to_start();
+            }
+        }
+        self.last_autoplay_step = std::time::Instant::now();
+    }
+
+    /// Advance autoplay if the step interval has elapsed, pausing on commented positions
+    fn update_autoplay(&mut self, ctx: &egui::Context) {
+        if !self.autoplay {
+            return;
+        }
+
+        let interval = std::time::Duration::from_millis(self.state.autoplay_interval_ms);
+        let elapsed = self.last_autoplay_step.elapsed();
+        if elapsed < interval {
+            ctx.request_repaint_after(interval - elapsed);
+            return;
+        }
+
+        if !self.can_step_forward() {
+            self.autoplay = false;
+            return;
+        }
+
+        self.step_forward();
+        self.last_autoplay_step = std::time::Instant::now();
+
+        let has_comments = self.follows_study()
+            && !self.study.current_chapter().current_node().comments.is_empty();
+        if has_comments || !self.can_step_forward() {
+            self.autoplay = false;
+        } else {
+            ctx.request_repaint_after(interval);
+        }
+    }
+
+    /// Whether moves and navigation should follow the study tree
+    /// Offer to go to another chapter of the study already holding the line just extended
+    fn warn_duplicate_line(&mut self) {
+        let Some(found) = self.study.find_duplicate_line() else {
+            self.duplicate_line = None;
+            return;
+        };
+        let name = &self.study.chapters[found.chapter].name;
+        let message = if found.exact {
+            tr_args("toast.duplicate_line", &[name])
+        } else {
+            tr_args("toast.transposed_line", &[name])
+        };
+        self.toasts.push(ToastLevel::Warning, message, Some(ToastAction::GoToDuplicateLine));
+        let chapter = self.study.current_chapter;
+        self.duplicate_line = Some((chapter, self.study.current_chapter().current_id(), found));
+    }
+
+    /// Take back the duplicate move and show the chapter that already has its line
+    fn go_to_duplicate_line(&mut self) {
+        let Some((chapter, node, found)) = self.duplicate_line.take() else {
+            return;
+        };
+        if let Some(added_to) = self.study.chapters.get_mut(chapter) {
+            added_to.remove_newest_node(node);
+        }
+        if !self.study.switch_chapter(found.chapter) {
+            return;
+        }
+        self.study.current_chapter_mut().current_path = found.path;
+        self.study.update_timestamp();
+        self.set_mode(AppMode::Study);
+        self.handle_study_nav_action(StudyNavAction::ChapterChanged);
+    }
+
+    fn follows_study(&self) -> bool {
+        self.state.mode == AppMode::Study && self.scratch_saved_game.is_none() && self.drill.is_none()
+    }
+
+    /// Explore moves on a copy of the current position without touching the game or study
+    fn start_scratch(&mut self) {
+        let Ok(scratch) = GameState::from_fen(&self.game.fen()) else {
+            return;
+        };
+        self.autoplay = false;
+        self.scratch_saved_game = Some(std::mem::replace(&mut self.game, scratch));
+        self.clear_selection();
+    }
+
+    /// Throw the scratch board away and return to the real game
+    fn discard_scratch(&mut self) {
+        if let Some(game) = self.scratch_saved_game.take() {
+            self.game = game;
+            self.clear_selection();
+            if self.engine_analyzing {
+                self.stop_analysis();
+                self.start_analysis();
+            }
+        }
+    }
+
+    /// Replay the explored line (up to the viewed move) on the real game, as a study variation in
+    /// Study mode. A game that goes on past the explored position keeps its moves, and the line
+    /// becomes a variation in a new chapter of the study instead.
+    fn commit_scratch(&mut self) {
+        let explored: Vec<String> = self.game.move_history()[..self.game.current_index()]
+            .iter()
+            .map(|record| record.uci.clone())
+            .collect();
+        let continues = self.scratch_saved_game.as_ref().is_some_and(GameState::can_go_forward);
+        if continues && self.state.mode != AppMode::Study {
+            if self.study.locked {
+                self.toasts.warning(tr("toast.keep_line_locked"));
+                return;
+            }
+            let line = self.game.move_history()[..self.game.current_index()].to_vec();
+            self.discard_scratch();
+            self.add_variation_chapter(line);
+            return;
+        }
+        self.discard_scratch();
+        for uci in explored {
+            match self.game.parse_move(&uci) {
+                Ok(m) => {
+                    self.make_move(m);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Copy the game into a new study chapter with `line` as a variation from the viewed move,
+    /// and show the end of the line there
+    fn add_variation_chapter(&mut self, line: Vec<MoveRecord>) {
+        let branch = self.game.current_index();
+        let number = self.study.chapters.len() + 1;
+        self.study.add_chapter(tr_args("study.chapter_n", &[&number]));
+        let chapter = self.study.current_chapter_mut();
+        chapter.set_root_fen(self.game.starting_fen());
+        for record in self.game.move_history() {
+            chapter.add_move(record.clone(), record.resulting_fen.clone());
+        }
+        chapter.current_path.truncate(branch);
+        for record in line {
+            let fen = record.resulting_fen.clone();
+            chapter.add_move(record, fen);
+        }
+        self.study.update_timestamp();
+        self.set_mode(AppMode::Study);
+        self.handle_study_nav_action(StudyNavAction::ChapterChanged);
+        self.toasts.info(tr_args("toast.line_added_as_chapter", &[&number]));
+    }
+
+    fn set_mode(&mut self, mode: AppMode) {
+        if self.state.mode != mode {
+            self.archive_game();
+            self.end_endgame();
+            self.book_exit = None;
+            self.discard_scratch();
+            self.puzzle = None;
+            self.end_drill();
+            self.broadcast = None;
+            self.cancel_staged();
+            self.state.mode = mode;
+            self.autoplay = false;
+            
+            self.stop_analysis();
+            self.analysis_panel.power_paused = false;
+            
+            match mode {
+                AppMode::Game => {
+                    // The position on the board came from another mode, not a game to keep
+                    self.game.reset();
+                    self.new_game();
+                }
+                AppMode::Analysis | AppMode::Training => {
+                    // Keep current position
+                }
+                AppMode::Online => {
+                    self.connect_online();
+                    self.sync_online_board();
+                }
+                AppMode::Study => {
+                    // Sync game with study position
+                    let fen = self.study.current_chapter().current_fen().to_string();
+                    if let Ok(new_game) = GameState::from_fen(&fen) {
+                        self.game = new_game;
+                    }
+                }
+            }
+            self.sync_engine_binary();
+            self.orient_board();
+        }
+    }
+
+    /// Play a clicked engine line from the position its analysis started at (`base_fen`). In
+    /// Study mode it becomes a variation of the chapter; otherwise it is explored on the scratch
+    /// board so the game's own moves stay intact.
+    fn play_engine_line(&mut self, base_fen: &str, path: Vec<String>) {
+        if !self.follows_study() && self.scratch_saved_game.is_none() {
+            self.start_scratch();
+        }
+        if !base_fen.is_empty() && base_fen != self.game.fen() {
+            if let Ok(new_game) = GameState::from_fen(base_fen) {
+                self.game = new_game;
+                tracing::info!("Reset to base position for analysis line");
+            }
+        }
+
+        tracing::info!("Playing engine path: {:?}", path);
+        for uci_move in path {
+            if !self.apply_engine_move(&uci_move) {
+                break;
+            }
+        }
+    }
+
+    /// Apply a move clicked from engine analysis (creates a fork/variation)
+    /// Returns true if move was successfully applied
+    fn apply_engine_move(&mut self, uci_move: &str) -> bool {
+        use shakmaty::uci::UciMove;
+
+        // Playing from the middle of the game would cut off its later moves
+        if self.game.can_go_forward() && !self.follows_study() && self.scratch_saved_game.is_none() {
+            self.start_scratch();
+        }
+        
+        // Parse the UCI move
+        if let Ok(uci) = uci_move.parse::<UciMove>() {
+            // Convert to Move
+            if let Ok(m) = uci.to_move(self.game.current_position()) {
+                // Check if move is legal
+                if self.game.legal_moves().contains(&m) {
+                    // Apply the move
+                    if let Some(record) = self.make_move(m) {
+                        // In Analysis mode, this creates a variation/fork
+                        tracing::info!("Applied engine move: {} (fork)", record.san);
+                        
+                        // Restart analysis at new position
+                        if self.engine_analyzing {
+                            self.stop_analysis();
+                            self.start_analysis();
+                        }
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Export current game as PGN
+    fn export_game_pgn(&self) -> String {
+        self.game_pgn("-").to_pgn()
+    }
+
+    /// The current game with its headers and the clock after each timed move, marking the mistakes
+    /// found by the engine's own searches if the user asked for them
+    fn game_pgn(&self, round: &str) -> PgnGame {
+        let engine = self.engine_name.as_deref().unwrap_or("Stockfish");
+        let (white, black) = match self.state.player_color {
+            PlayerColor::White => ("Player", engine),
+            PlayerColor::Black => (engine, "Player"),
+        };
+        let mut headers = vec![
+            ("Event".to_string(), "Stockfish Chess Game".to_string()),
+            ("Site".to_string(), "Local".to_string()),
+            ("Date".to_string(), chrono::Local::now().format("%Y.%m.%d").to_string()),
+            ("Round".to_string(), round.to_string()),
+            ("White".to_string(), white.to_string()),
+            ("Black".to_string(), black.to_string()),
+            ("Result".to_string(), self.game.outcome().result().to_string()),
+        ];
+        let start = self.game.starting_fen();
+        if start != GameState::new().fen() {
+            headers.push(("SetUp".to_string(), "1".to_string()));
+            headers.push(("FEN".to_string(), start));
+        }
+
+        let style = self.state.preferences.pgn_style;
+        let first_mover = self.game.starting_turn();
+        let moves = self
+            .game
+            .move_history()
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mover = if i % 2 == 0 { first_mover } else { first_mover.opposite() };
+                let nag = eval_loss(&self.game_evals, i, mover)
+                    .and_then(Nag::from_loss)
+                    .filter(|_| self.state.preferences.export_mistakes);
+                let text = style.move_text(&record.san, nag);
+                match record.clock_remaining {
+                    Some(left) => format!("{} {{[%clk {}]}}", text, pgn_clock(left)),
+                    None => text,
+                }
+            })
+            .collect();
+        PgnGame { headers, moves, ..Default::default() }
+    }
+
+    /// Keep the game played against the engine for the session export before the board is reset
+    fn archive_game(&mut self) {
+        if self.state.mode == AppMode::Game && !self.game.move_history().is_empty() {
+            let round = (self.session_games.len() + 1).to_string();
+            let game = self.game_pgn(&round);
+            self.session_games.push(game);
+        }
+    }
+
+    /// Ask where to save every game of the session, the one on the board included, and optionally
+    /// the imported games matching the filter, as one PGN file
+    fn export_all_games(&mut self) {
+        let mut games = self.session_games.clone();
+        if self.state.mode == AppMode::Game && !self.game.move_history().is_empty() {
+            games.push(self.game_pgn(&(games.len() + 1).to_string()));
+        }
+        if self.export_my_games {
+            games.extend(self.my_games.iter().filter(|g| g.matches(&self.export_filter)).cloned());
+        }
+        if games.is_empty() {
+            self.toasts.info(tr("toast.no_games_to_export"));
+            return;
+        }
+
+        let name = format!("stockfish-chess-games-{}.pgn", chrono::Local::now().format("%Y-%m-%d"));
+        let Some(path) = rfd::FileDialog::new().add_filter("PGN", &["pgn"]).set_file_name(name).save_file() else {
+            return;
+        };
+        let text = games.iter().map(PgnGame::to_pgn).collect::<Vec<_>>().join("\n");
+        match std::fs::write(&path, text) {
+            Ok(()) => self.toasts.info(tr_args("toast.games_exported", &[&games.len(), &path.display()])),
+            Err(e) => self.toasts.error(tr_args("toast.games_export_failed", &[&e])),
+        }
+    }
+
+    /// Cross-check the game on the Lichess analysis board, in the default browser
+    fn open_in_lichess(&self, ctx: &egui::Context) {
+        ctx.open_url(egui::OpenUrl::new_tab(self.lichess_url()));
+    }
+
+    /// Lichess analysis board URL for the game, at the move and orientation on the board
+    fn lichess_url(&self) -> String {
+        let orientation = if self.state.flipped { PlayerColor::Black } else { PlayerColor::White };
+        analysis_url(&self.game, orientation)
+    }
+
+    /// Buttons copying a link to the position and the annotated PGN, for sharing in chats
+    /// Position at the end of the engine's best line, when the analysis is of `fen`
+    fn engine_line_end(&self, fen: &str) -> Option<String> {
+        if self.analysis_panel.base_fen.as_deref() != Some(fen) {
+            return None;
+        }
+        let line = self.analysis_panel.all_lines.first()?;
+        line.records.last().map(|record| record.resulting_fen.clone())
+    }
+
+    fn show_share_buttons(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("share.copy_link")).on_hover_text(tr("share.copy_link_hint")).clicked() {
+                ui.ctx().copy_text(self.lichess_url());
+                self.toasts.info(tr("toast.link_copied"));
+            }
+            if ui.button(tr("share.copy_pgn")).on_hover_text(tr("share.copy_pgn_hint")).clicked() {
+                ui.ctx().copy_text(self.annotated_pgn());
+                self.toasts.info(tr("toast.pgn_copied"));
+            }
+        });
+        ui.horizontal(|ui| {
+            let flipped = self.state.flipped;
+            if ui.button(tr("share.latex_diagram")).on_hover_text(tr("share.latex_diagram_hint")).clicked() {
+                ui.ctx().copy_text(latex_diagram(&self.game.fen(), flipped));
+                self.toasts.info(tr("toast.latex_copied"));
+            }
+            let line = &self.game.move_history()[..self.game.current_index()];
+            if ui
+                .add_enabled(!line.is_empty(), egui::Button::new(tr("share.latex_line")))
+                .on_hover_text(tr("share.latex_line_hint"))
+                .clicked()
+            {
+                let sans: Vec<String> = line.iter().map(|record| record.san.clone()).collect();
+                ui.ctx().copy_text(latex_line(&self.game.starting_fen(), &sans, flipped));
+                self.toasts.info(tr("toast.latex_copied"));
+            }
+        });
+    }
+
+    /// The game as PGN with the engine's lines for the position on the board as a comment
+    fn annotated_pgn(&self) -> String {
+        let pgn = self.game_pgn("-");
+        let analyzed = self.analysis_panel.base_fen.as_deref() == Some(self.game.fen().as_str());
+        let engine = self.engine_name.as_deref().unwrap_or("Stockfish");
+        match self.analysis_panel.pgn_comment(engine).filter(|_| analyzed) {
+            Some(comment) => pgn.to_pgn_with_comment(self.game.current_index(), &comment),
+            None => pgn.to_pgn(),
+        }
+    }
+
+    /// Ask where to save a backup of the data directory and the persisted settings
+    fn create_backup(&mut self) {
+        let name = format!("stockfish-chess-backup-{}.zip", chrono::Local::now().format("%Y-%m-%d"));
+        let Some(path) = rfd::FileDialog::new().add_filter("ZIP", &["zip"]).set_file_name(name).save_file() else {
+            return;
+        };
+        let result = serde_json::to_string(&self.state)
+            .map_err(|e| e.to_string())
+            .and_then(|settings| backup::create(&path, &data_dir(), &settings));
+        match result {
+            Ok(_) => self.toasts.info(tr_args("toast.backup_saved", &[&path.display()])),
+            Err(e) => self.toasts.error(tr_args("toast.backup_failed", &[&e])),
+        }
+    }
+
+    /// Restore a backup over the current data, then reload everything read from it
+    fn restore_backup(&mut self) {
+        let Some(path) = rfd::FileDialog::new().add_filter("ZIP", &["zip"]).pick_file() else {
+            return;
+        };
+        let settings = match backup::restore(&path, &data_dir()) {
+            Ok(settings) => settings,
+            Err(e) => {
+                self.toasts.error(tr_args("toast.restore_failed", &[&e]));
+                return;
+            }
+        };
+        if let Some(state) = settings.and_then(|json| serde_json::from_str::<AppState>(&json).ok()) {
+            // Keep this machine's window layout
+            self.state = AppState {
+                window_size: self.state.window_size,
+                window_position: self.state.window_position,
+                window_maximized: self.state.window_maximized,
+                ..state
+            };
+            i18n::set_language(self.state.preferences.language);
+            self.ui_requests.push(UiRequest::TouchMode(self.state.preferences.touch_mode));
+        }
+        match Config::load() {
+            Ok(config) => self.config = config,
+            Err(e) => self.toasts.error(tr_args("toast.config_invalid", &[&Config::path().display(), &e])),
+        }
+        // As at startup, the config file overrides the saved settings
+        if let Some(path) = &self.config.engine_path {
+            self.state.engine_path = Some(path.clone());
+        }
+        if let Some(difficulty) = self.config.difficulty {
+            self.state.difficulty = difficulty;
+        }
+        if let Some(theme) = self.config.theme {
+            self.state.theme = theme;
+        }
+        self.engine_path_input = self.state.engine_path.clone().unwrap_or_default();
+
+        // The restored engine settings apply to the running engine, or the one they pick
+        if let Some(path) = find_stockfish(self.state.engine_path.as_deref()) {
+            self.default_engine = Some(path);
+        }
+        self.sync_engine_binary();
+        self.send_engine_difficulty();
+        self.sync_engine_threads();
+
+        if let Some(action) = self.study_panel.reload(&mut self.study) {
+            self.handle_study_nav_action(action);
+        }
+        self.reload_my_games();
+        self.toasts.info(tr("toast.backup_restored"));
+    }
+
+    /// Ask where to save a report of the reviewed game; the file extension picks Markdown or HTML.
+    /// A Markdown report gets its evaluation graph as an SVG file next to it.
+    fn save_report(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Markdown", &["md"])
+            .add_filter("HTML", &["html", "htm"])
+            .set_file_name("game-report.md")
+            .save_file()
+        else {
+            return;
+        };
+        let engine = self.engine_name.as_deref().unwrap_or("Stockfish");
+        let (white, black) = match self.state.player_color {
+            PlayerColor::White => (tr("plate.you"), engine),
+            PlayerColor::Black => (engine, tr("plate.you")),
+        };
+        let report = GameReport::new(&self.game, &self.game_evals, white, black);
+        let written = match ReportFormat::from_path(&path) {
+            ReportFormat::Html => std::fs::write(&path, report.to_html()),
+            ReportFormat::Markdown => {
+                let stem = path.file_stem().map_or_else(|| "report".into(), |s| s.to_string_lossy());
+                let graph = format!("{}-eval.svg", stem);
+                std::fs::write(path.with_file_name(&graph), report.eval_graph_svg())
+                    .and_then(|()| std::fs::write(&path, report.to_markdown(&graph)))
+            }
+        };
+        match written {
+            Ok(()) => self.toasts.info(tr_args("toast.report_saved", &[&path.display()])),
+            Err(e) => self.toasts.error(tr_args("toast.report_failed", &[&e])),
+        }
+    }
+
+    /// Open files dropped onto the window: PGN files and archives of them ask where to import,
+    /// other text files are read as a FEN position for Analysis mode
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        for file in dropped {
+            let name = file
+                .path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map_or_else(|| file.name.clone(), |n| n.to_string_lossy().into_owned());
+
+            if let Some(kind) = PgnFile::from_name(&name).filter(|k| k.is_archive()) {
+                let pending = match (&file.bytes, &file.path) {
+                    (_, Some(path)) => PendingImport::Archive(kind, path.clone()),
+                    (Some(bytes), None) => {
+                        let mut games = Vec::new();
+                        let read = kind.read_games(std::io::Cursor::new(bytes.as_ref()), |game| {
+                            games.push(game);
+                            ControlFlow::Continue(())
+                        });
+                        if let Err(e) = read {
+                            self.toasts.error(tr_args("toast.open_failed", &[&name, &e]));
+                            continue;
+                        }
+                        PendingImport::Games(games)
+                    }
+                    (None, None) => continue,
+                };
+                if pending.len() == Some(0) {
+                    self.toasts.error(tr_args("toast.open_failed", &[&name, &tr("toast.pgn_empty")]));
+                } else {
+                    self.pending_import = Some((name, pending));
+                }
+                continue;
+            }
+            let text = match (&file.bytes, &file.path) {
+                (Some(bytes), _) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+                (None, Some(path)) => std::fs::read_to_string(path).map_err(|e| e.to_string()),
+                (None, None) => continue,
+            };
+            let text = match text {
+                Ok(text) => text,
+                Err(e) => {
+                    self.toasts.error(tr_args("toast.open_failed", &[&name, &e]));
+                    continue;
+                }
+            };
+
+            if name.to_ascii_lowercase().ends_with(".pgn") {
+                let games = parse_pgn(&text);
+                if games.is_empty() {
+                    self.toasts.error(tr_args("toast.open_failed", &[&name, &tr("toast.pgn_empty")]));
+                } else {
+                    self.pending_import = Some((name, PendingImport::Games(games)));
+                }
+                continue;
+            }
+
+            let fen = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+            match GameState::from_fen(fen) {
+                Ok(game) => {
+                    self.set_mode(AppMode::Analysis);
+                    self.puzzle = None;
+                    self.game = game;
+                    self.clear_selection();
+                    self.orient_board();
+                }
+                Err(_) => self.toasts.error(tr_args("toast.not_pgn_or_fen", &[&name])),
+            }
+        }
+    }
+
+    /// Play `sans` from the starting position on the board in Analysis mode
+    fn open_line(&mut self, sans: &[String]) {
+        let mut game = GameState::new();
+        for san in sans {
+            if game.make_move_san(san).is_err() {
+                break;
+            }
+        }
+        self.set_mode(AppMode::Analysis);
+        self.puzzle = None;
+        self.game = game;
+        self.clear_selection();
+        self.orient_board();
+    }
+
+    /// Import the pending games where the user picked. Archives are read game by game, so a
+    /// database dump never has to fit in memory as text.
+    /// Open a game of the database in Analysis with the evaluations it was exported with
+    fn open_my_game(&mut self, index: usize) {
+        let Some(pgn) = self.my_games.get(index) else {
+            return;
+        };
+        match pgn.to_game() {
+            Ok(game) => {
+                let mut evals = pgn.evals.clone();
+                evals.resize(game.position_count(), None);
+                self.set_mode(AppMode::Analysis);
+                self.game = game;
+                self.game_evals = evals;
+                self.clear_selection();
+                self.orient_board();
+            }
+            Err(e) => {
+                let name = pgn.title().unwrap_or_else(|| tr("games.untitled").to_string());
+                self.toasts.error(tr_args("toast.open_failed", &[&name, &e]));
+            }
+        }
+    }
+
+    fn handle_import_action(&mut self, action: ImportAction) {
+        let Some((name, pending)) = self.pending_import.take() else {
+            return;
+        };
+        match action {
+            ImportAction::Game => {
+                let mut first = None;
+                let read = pending.for_each(|game| {
+                    first = Some(game);
+                    ControlFlow::Break(())
+                });
+                let game = match (read, first) {
+                    (Err(e), _) => Err(e.to_string()),
+                    (Ok(()), None) => Err(tr("toast.pgn_empty").to_string()),
+                    (Ok(()), Some(first)) => first.to_game().map_err(|e| e.to_string()),
+                };
+                match game {
+                    Ok(game) => {
+                        self.set_mode(AppMode::Analysis);
+                        self.game = game;
+                        self.clear_selection();
+                        self.orient_board();
+                    }
+                    Err(e) => self.toasts.error(tr_args("toast.open_failed", &[&name, &e])),
+                }
+            }
+            ImportAction::Study => {
+                // Kept open so the games can go into the personal copy once it is made
+                if self.study.locked {
+                    self.pending_import = Some((name, pending));
+                    self.set_mode(AppMode::Study);
+                    self.study_panel.prompt_copy();
+                    return;
+                }
+                self.set_mode(AppMode::Study);
+                self.start_import(name, pending, action);
+            }
+            ImportAction::MyGames => self.start_import(name, pending, action),
+            ImportAction::Cancel => {}
+        }
+    }
+
+    /// Read the games of `pending` into the study or the games database on a background thread
+    fn start_import(&mut self, name: String, pending: PendingImport, destination: ImportAction) {
+        // New games are told from the ones already in the database once it is loaded
+        if self.import.is_some() || (destination == ImportAction::MyGames && self.my_games_loading.is_some()) {
+            self.toasts.warning(tr("toast.import_busy"));
+            return;
+        }
+        // Re-importing an account's games only adds the ones played since
+        let is_new = (destination == ImportAction::MyGames).then(|| new_games(&self.my_games));
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || read_import(pending, is_new, tx));
+        self.toasts.info(tr_args("toast.importing", &[&name]));
+        self.import = Some(ImportJob {
+            name,
+            destination,
+            study_id: self.study.id.clone(),
+            messages: rx,
+            added: 0,
+            failed: 0,
+        });
+    }
+
+    /// Add the games the background import has read so far, and report once it is done
+    fn update_import(&mut self, ctx: &egui::Context) {
+        let Some(job) = &mut self.import else {
+            return;
+        };
+        let mut done = None;
+        let mut chapters_added = false;
+        while let Ok(message) = job.messages.try_recv() {
+            match message {
+                ImportMessage::Games(games) if job.destination == ImportAction::MyGames => {
+                    job.added += games.len();
+                    self.my_games.extend(games);
+                }
+                ImportMessage::Games(games) if self.study.id == job.study_id => {
+                    for game in &games {
+                        match self.study.add_pgn_chapter(game) {
+                            Ok(_) => job.added += 1,
+                            Err(_) => job.failed += 1,
+                        }
+                    }
+                    chapters_added = true;
+                }
+                ImportMessage::Games(games) => job.failed += games.len(),
+                ImportMessage::Done { duplicates, error } => {
+                    done = Some((duplicates, error));
+                    break;
+                }
+            }
+        }
+        if chapters_added {
+            self.study.update_timestamp();
+        }
+        let Some((duplicates, error)) = done else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return;
+        };
+        let Some(job) = self.import.take() else {
+            return;
+        };
+        if let Some(e) = error {
+            self.toasts.error(tr_args("toast.open_failed", &[&job.name, &e]));
+        }
+        if job.destination == ImportAction::MyGames {
+            if job.added == 0 {
+                self.toasts.info(tr_args("toast.my_games_none_new", &[&duplicates]));
+                return;
+            }
+            self.my_games_added = Some((job.added, duplicates));
+            self.rebuild_opening_tree();
+        } else {
+            if job.failed > 0 {
+                self.toasts.error(tr_args("toast.import_skipped", &[&job.failed]));
+            }
+            self.handle_study_nav_action(StudyNavAction::ChapterChanged);
+        }
+    }
+
+    /// Strength the engine plays at: full strength while an endgame is practised
+    fn engine_difficulty(&self) -> DifficultyLevel {
+        if self.endgame.is_some() {
+            DifficultyLevel::Maximum
+        } else {
+            self.state.difficulty
+        }
+    }
+
+    /// Playing style of the engine: none while an endgame is practised
+    fn engine_personality(&self) -> Personality {
+        if self.endgame.is_some() {
+            Personality::Standard
+        } else {
+            self.state.personality
+        }
+    }
+
+    /// Set the engine up for the current role once it takes commands
+    fn configure_engine(&mut self) {
+        self.send_engine_difficulty();
+        if self.state.mode == AppMode::Game {
+            self.check_engine_turn();
+        }
+    }
+
+    fn send_engine_difficulty(&self) {
+        let cmd_tx = self.engine_cmd_tx.clone();
+        let difficulty = self.engine_difficulty();
+        let personality = self.engine_personality();
+        std::thread::spawn(move || {
+            let _ = cmd_tx.send(EngineCommand::SetDifficulty(difficulty));
+            let _ = cmd_tx.send(EngineCommand::SetPersonality(personality));
+        });
+    }
+
+    /// Play a theoretical endgame from `ENDGAMES` against the engine at full strength
+    fn start_endgame(&mut self, index: usize) {
+        let (id, fen, _) = ENDGAMES[index];
+        let game = match GameState::from_fen(fen) {
+            Ok(game) => game,
+            Err(e) => {
+                self.toasts.error(tr_args("toast.open_failed", &[&id, &e]));
+                return;
+            }
+        };
+        if self.state.mode == AppMode::Game {
+            self.new_game();
+        } else {
+            self.set_mode(AppMode::Game);
+        }
+        self.interrupt_search();
+
+        let player = game.turn();
+        self.endgame = Some(EndgameSession::start(index, player, self.state.player_color));
+        self.state.player_color = player;
+        self.state.flipped = player == PlayerColor::Black;
+        self.game = game;
+        self.clock = GameClock::new(None);
+        self.clear_selection();
+        self.send_engine_difficulty();
+        self.check_engine_turn();
+    }
+
+    /// Leave endgame practice, giving back the player's own color and difficulty
+    fn end_endgame(&mut self) {
+        if let Some(session) = self.endgame.take() {
+            // Unless a color was picked for the next game meanwhile
+            if self.state.player_color == session.player {
+                self.state.player_color = session.previous_color;
+            }
+            self.send_engine_difficulty();
+        }
+    }
+
+    /// Compare a finished endgame with the tablebase outcome
+    fn update_endgame(&mut self) {
+        let outcome = self.game.outcome();
+        let Some(session) = &mut self.endgame else {
+            return;
+        };
+        if session.judged {
+            return;
+        }
+        let Some(success) = session.verdict(outcome) else {
+            return;
+        };
+        session.judged = true;
+        let name = session.name();
+        if success {
+            self.toasts.info(tr_args("toast.endgame_held", &[&name]));
+        } else {
+            self.toasts.warning(tr_args("toast.endgame_missed", &[&name]));
+        }
+    }
+
+    /// Count toward the training plan, congratulating once the day's goals are met
+    fn record_activity(&mut self, activity: Activity) {
+        if self.state.training_plan.record(activity, today()) {
+            self.toasts.info(tr("toast.plan_met"));
+        }
+    }
+
+    fn handle_puzzle_card_action(&mut self, action: PuzzleCardAction) {
+        self.show_puzzle_card = false;
+        if action != PuzzleCardAction::Solve {
+            return;
+        }
+        let Some(puzzle) = self.daily_puzzle.poll().cloned() else {
+            return;
+        };
+        match GameState::from_fen(&puzzle.fen) {
+            Ok(game) => {
+                self.set_mode(AppMode::Analysis);
+                self.stop_analysis();
+                self.state.flipped = game.turn() == PlayerColor::Black;
+                self.game = game;
+                self.clear_selection();
+                self.puzzle = Some(PuzzleSession::new(puzzle));
+            }
+            Err(e) => self.toasts.error(tr_args("toast.open_failed", &[&puzzle.id, &e])),
+        }
+    }
+
+    fn handle_puzzle_action(&mut self, action: PuzzleAction) {
+        match action {
+            PuzzleAction::ShowMove => {
+                let Some(session) = &mut self.puzzle else {
+                    return;
+                };
+                session.revealed = true;
+                let next = session.next_move().map(str::to_string);
+                if let Some(m) = next.and_then(|uci| self.game.parse_move(&uci).ok()) {
+                    self.make_move(m);
+                }
+            }
+            PuzzleAction::Close => self.puzzle = None,
+        }
+    }
+
+    /// Check a move played during a puzzle: answer correct moves, take back wrong ones
+    fn check_puzzle_move(&mut self, record: &MoveRecord) {
+        let Some(session) = &mut self.puzzle else {
+            return;
+        };
+        if session.solved {
+            return;
+        }
+        match session.play(&record.uci, &self.game) {
+            PuzzleStep::Continue(reply) => {
+                if self.game.make_move_uci(&reply).is_err() {
+                    tracing::warn!("Puzzle reply {} is illegal", reply);
+                }
+            }
+            PuzzleStep::Solved => {
+                if session.counts() {
+                    self.state.puzzle_streak.record_solved(today());
+                    self.toasts.info(tr("puzzle.solved"));
+                    self.record_activity(Activity::PuzzleSolved);
+                }
+            }
+            PuzzleStep::Wrong => {
+                let _ = self.game.go_back();
+                self.toasts.warning(tr("puzzle.wrong"));
+            }
+        }
+    }
+
+    fn handle_broadcast_action(&mut self, action: BroadcastAction) {
+        match action {
+            BroadcastAction::Follow => {
+                let settings = &self.state.broadcast;
+                // Saved settings may have been edited below the limit the panel enforces
+                let interval = std::time::Duration::from_secs(settings.interval_secs.max(MIN_INTERVAL_SECS));
+                self.broadcast = Some(Broadcast::follow(settings.url.trim().to_string(), interval));
+            }
+            BroadcastAction::Stop => self.broadcast = None,
+            BroadcastAction::SelectGame(index) => {
+                if let Some(broadcast) = &mut self.broadcast {
+                    broadcast.selected = index;
+                    self.sync_broadcast();
+                }
+            }
+        }
+    }
+
+    /// Apply finished broadcast polls to the game, or to the real game behind the scratch board
+    fn update_broadcast(&mut self, ctx: &egui::Context) {
+        let Some(broadcast) = &mut self.broadcast else {
+            return;
+        };
+        if broadcast.poll() {
+            self.sync_broadcast();
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    fn update_tournament(&mut self, ctx: &egui::Context) {
+        let Some(tournament) = &mut self.tournament else {
+            return;
+        };
+        if tournament.finished {
+            return;
+        }
+        if tournament.poll() && tournament.finished && tournament.error.is_none() {
+            self.toasts.info(tr_args("toast.tournament_finished", &[&tournament.games.len()]));
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(500));
+    }
+
+    fn handle_tournament_action(&mut self, action: TournamentAction) {
+        match action {
+            TournamentAction::Start => {
+                let Some(path) = find_stockfish(self.state.engine_path.as_deref()) else {
+                    self.toasts.error(tr("tournament.no_engine"));
+                    return;
+                };
+                let settings = &self.state.tournament;
+                let entrants = Entrant::field(settings, &path);
+                self.tournament = Some(Tournament::start(
+                    entrants,
+                    settings.double_round,
+                    settings.movetime_ms,
+                    self.config.engine_timeouts,
+                ));
+            }
+            TournamentAction::Stop => {
+                if let Some(tournament) = &mut self.tournament {
+                    tournament.stop();
+                }
+            }
+            TournamentAction::ExportPgn => {
+                let Some(tournament) = &self.tournament else {
+                    return;
+                };
+                let name = format!("stockfish-chess-tournament-{}.pgn", chrono::Local::now().format("%Y-%m-%d"));
+                let Some(path) =
+                    rfd::FileDialog::new().add_filter("PGN", &["pgn"]).set_file_name(name).save_file()
+                else {
+                    return;
+                };
+                let count = tournament.games.len();
+                match std::fs::write(&path, tournament.pgn()) {
+                    Ok(()) => self.toasts.info(tr_args("toast.games_exported", &[&count, &path.display()])),
+                    Err(e) => self.toasts.error(tr_args("toast.games_export_failed", &[&e])),
+                }
+            }
+        }
+    }
+
+    fn sync_broadcast(&mut self) {
+        let Some(pgn) = self.broadcast.as_ref().and_then(Broadcast::game) else {
+            return;
+        };
+        let on_scratch = self.scratch_saved_game.is_some();
+        let game = self.scratch_saved_game.as_mut().unwrap_or(&mut self.game);
+        let update = match sync_game(game, pgn) {
+            Ok(update) => update,
+            Err(e) => {
+                if let Some(broadcast) = &mut self.broadcast {
+                    broadcast.last_error = Some(e.to_string());
+                }
+                return;
+            }
+        };
+        if update == BroadcastUpdate::Unchanged || on_scratch {
+            return;
+        }
+        self.clear_selection();
+        if self.engine_analyzing {
+            self.stop_analysis();
+            self.start_analysis();
+        } else if self.state.broadcast.live_analysis {
+            self.start_analysis();
+        }
+    }
+
+    /// Log in to Lichess with the token from config.toml, once; the client reconnects by itself
+    fn connect_online(&mut self) {
+        if let (None, Some(token)) = (&self.lichess, &self.config.lichess_token) {
+            self.lichess = Some(LichessClient::connect(token.clone()));
+        }
+    }
+
+    fn handle_online_action(&mut self, action: OnlineAction) {
+        let Some(client) = &mut self.lichess else {
+            return;
+        };
+        match action {
+            OnlineAction::Connect => {
+                self.lichess = None;
+                self.connect_online();
+            }
+            OnlineAction::Accept(id) => client.accept(&id),
+            OnlineAction::Decline(id) => {
+                client.decline(&id);
+                self.challenges.retain(|c| c.id != id);
+            }
+            OnlineAction::Seek => client.seek(self.state.online.seek_minutes, self.state.online.seek_increment),
+            OnlineAction::Resign => {
+                if let Some(game) = &self.online_game {
+                    client.resign(&game.id);
+                }
+            }
+        }
+    }
+
+    /// Handle Lichess events; the board follows the online game while in Online mode
+    fn update_online(&mut self, ctx: &egui::Context) {
+        let Some(client) = &mut self.lichess else {
+            return;
+        };
+        let username = client.username.clone().unwrap_or_default();
+        for event in client.poll() {
+            match event {
+                OnlineEvent::Challenge(challenge) => {
+                    if !self.challenges.iter().any(|c| c.id == challenge.id) {
+                        self.toasts.info(tr_args("online.challenged", &[&challenge.challenger.name]));
+                        self.challenges.push(challenge);
+                    }
+                }
+                OnlineEvent::ChallengeGone(id) => self.challenges.retain(|c| c.id != id),
+                OnlineEvent::GameStart(id) => {
+                    self.challenges.retain(|c| c.id != id);
+                    if self.online_game.as_ref().map_or(true, OnlineGame::is_over) {
+                        if let Some(client) = &self.lichess {
+                            client.stream_game(&id);
+                        }
+                    }
+                }
+                OnlineEvent::GameFull { id, white, black, initial_fen, state } => {
+                    self.online_game = Some(OnlineGame::new(id, &username, (white, black), initial_fen, state));
+                    if self.state.mode == AppMode::Online {
+                        self.orient_board();
+                    }
+                    self.sync_online_board();
+                }
+                OnlineEvent::GameState(state) => {
+                    if let Some(game) = &mut self.online_game {
+                        game.update(state);
+                    }
+                    self.sync_online_board();
+                }
+                OnlineEvent::GameFinish(id) => {
+                    if self.online_game.as_ref().is_some_and(|g| g.id == id) {
+                        self.toasts.info(tr("online.game_over"));
+                    }
+                }
+                OnlineEvent::MoveRejected { game_id, reason } => {
+                    if self.online_game.as_ref().is_some_and(|g| g.id == game_id) {
+                        self.toasts.error(tr_args("online.move_rejected", &[&reason]));
+                        // The server's game stands; take the refused move off the board
+                        self.sync_online_board();
+                    }
+                }
+                OnlineEvent::Error(e) => self.toasts.error(tr_args("online.error", &[&e])),
+                OnlineEvent::Connected { .. } | OnlineEvent::Disconnected(_) => {}
+            }
+        }
+
+        // Keep the remote clocks ticking and pick up events
+        let running = self.online_game.as_ref().is_some_and(|g| !g.is_over());
+        ctx.request_repaint_after(std::time::Duration::from_millis(if running { 100 } else { 1000 }));
+    }
+
+    /// Show the server's moves on the board and let the engine answer when it plays for us
+    fn sync_online_board(&mut self) {
+        if self.state.mode != AppMode::Online {
+            return;
+        }
+        let Some(latest) = self.online_game.as_ref().and_then(|g| g.to_game().ok()) else {
+            return;
+        };
+        if let Ok(update) = catch_up(&mut self.game, latest) {
+            if update != BroadcastUpdate::Unchanged {
+                self.clear_selection();
+            }
+        }
+        // The engine always searches the latest position
+        if self.state.online.player == OnlinePlayer::Engine {
+            self.game.go_to_end();
+        }
+        if self.online_engine_to_move() && self.engine_ready && !self.engine_thinking {
+            self.start_engine_search();
+        }
+    }
+
+    fn online_engine_to_move(&self) -> bool {
+        self.state.online.player == OnlinePlayer::Engine
+            && self
+                .online_game
+                .as_ref()
+                .is_some_and(|game| game.our_turn() && self.game.turn() == game.color && !self.game.can_go_forward())
+    }
+
+    /// Use fewer engine threads while the window is in the background or on battery, and pause
+    /// analysis there if the preferences ask for it, back to full power once that's over
+    fn update_power(&mut self, ctx: &egui::Context) {
+        let focused = ctx.input(|i| i.focused);
+        let on_battery = self.power.on_battery();
+        let power = self.state.preferences.power;
+
+        // Searches started before the computer slept run on stale clocks
+        if self.power.take_resumed() {
+            tracing::info!("Restarting engine searches after sleep");
+            if self.engine_analyzing {
+                self.stop_analysis();
+                self.start_analysis();
+            }
+            if self.engine_thinking {
+                self.start_engine_search();
+            }
+        }
+
+        self.power_saving = !focused || on_battery;
+        self.sync_engine_threads();
+
+        let pause = (!focused && power.pause_unfocused) || (on_battery && power.pause_on_battery);
+        if pause && !self.power_pause_active && self.engine_analyzing {
+            self.stop_analysis();
+            self.analysis_panel.power_paused = true;
+        } else if !pause && self.analysis_panel.power_paused {
             self.start_analysis();
         }
+        self.power_pause_active = pause;
+
+        // Unplugging or plugging in the charger doesn't wake the window by itself
+        if self.analysis_panel.power_paused || on_battery {
+            ctx.request_repaint_after(std::time::Duration::from_secs(30));
+        }
     }
 
-    fn set_mode(&mut self, mode: AppMode) {
-        if self.state.mode != mode {
-            self.state.mode = mode;
-            
-            self.stop_analysis();
-            
-            match mode {
-                AppMode::Game => {
-                    self.new_game();
-                }
-                AppMode::Analysis => {
-                    // Keep current position
-                }
-                AppMode::Study => {
-                    // Sync game with study position
-                    let fen = self.study.current_chapter().current_fen().to_string();
-                    if let Ok(new_game) = GameState::from_fen(&fen) {
-                        self.game = new_game;
-                    }
+    /// Play moves made on the electronic board, and ask for moves made in the app to be copied to it
+    fn update_dgt(&mut self, ctx: &egui::Context) {
+        let Some(dgt) = &mut self.dgt else {
+            return;
+        };
+        ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        let rotated = self.state.preferences.dgt_rotated;
+        let changed = dgt.poll(rotated).is_some();
+        let Some(board) = dgt.board(rotated) else {
+            return;
+        };
+
+        if self.game.position_count() != self.dgt_positions {
+            self.dgt_positions = self.game.position_count();
+            let last = self.game.move_history().last().filter(|_| !self.game.can_go_forward());
+            if let (Some(last), true) = (last, self.game.current_position().board() != &board) {
+                self.dgt_pending = Some(last.san.clone());
+                if self.state.preferences.announce_moves {
+                    speak(&spoken(&last.san));
                 }
             }
         }
+
+        let matched = match_board(self.game.current_position(), &board);
+        self.dgt_in_sync = matched == BoardMatch::InSync;
+        match matched {
+            BoardMatch::InSync => self.dgt_pending = None,
+            BoardMatch::Move(m) if changed && self.dgt_pending.is_none() && self.can_interact() && !self.game.can_go_forward() => {
+                // Only the board's own move is expected; anything further, like a puzzle reply, is announced
+                self.dgt_positions = self.game.position_count() + 1;
+                self.make_move(m);
+            }
+            _ => {}
+        }
     }
 
-    /// Apply a move clicked from engine analysis (creates a fork/variation)
-    /// Returns true if move was successfully applied
-    fn apply_engine_move(&mut self, uci_move: &str) -> bool {
-        use shakmaty::uci::UciMove;
-        
-        // Parse the UCI move
-        if let Ok(uci) = uci_move.parse::<UciMove>() {
-            // Convert to Move
-            if let Ok(m) = uci.to_move(self.game.current_position()) {
-                // Check if move is legal
-                if self.game.legal_moves().contains(&m) {
-                    // Apply the move
-                    if let Some(record) = self.make_move(m) {
-                        // In Analysis mode, this creates a variation/fork
-                        tracing::info!("Applied engine move: {} (fork)", record.san);
-                        
-                        // Restart analysis at new position
-                        if self.engine_analyzing {
-                            self.stop_analysis();
-                            self.start_analysis();
-                        }
-                        return true;
-                    }
+    /// Read the games database and build its opening tree on a background thread
+    fn reload_my_games(&mut self) {
+        let names = self.state.preferences.usernames();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let games = load_my_games();
+            let tree = OpeningTree::build(&games, &names);
+            let _ = tx.send((games, tree));
+        });
+        self.my_games_loading = Some(rx);
+    }
+
+    /// Take the games database once the background load is done
+    fn update_my_games(&mut self, ctx: &egui::Context) {
+        let Some(loading) = &self.my_games_loading else {
+            return;
+        };
+        match loading.try_recv() {
+            Ok((games, tree)) => {
+                self.my_games = games;
+                self.opening_tree = tree;
+                self.my_games_loading = None;
+                if let Some((added, duplicates)) = self.my_games_added.take() {
+                    self.toasts.info(tr_args("toast.my_games_added", &[&added, &duplicates, &self.opening_tree.games]));
                 }
             }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint_after(std::time::Duration::from_millis(100)),
+            Err(mpsc::TryRecvError::Disconnected) => self.my_games_loading = None,
         }
-        false
     }
 
-    /// Export current game as PGN
-    fn export_game_pgn(&self) -> String {
-        use chrono::Local;
-        
-        let mut pgn = String::new();
-        
-        // Headers
-        pgn.push_str(&format!("[Event \"Stockfish Chess Game\"]\n"));
-        pgn.push_str(&format!("[Site \"Local\"]\n"));
-        pgn.push_str(&format!("[Date \"{}\"]\n", Local::now().format("%Y.%m.%d")));
-        pgn.push_str(&format!("[Round \"-\"]\n"));
-        pgn.push_str(&format!("[White \"Player\"]\n"));
-        pgn.push_str(&format!("[Black \"Stockfish\"]\n"));
-        
-        // Result
-        let result = match self.game.outcome() {
-            GameOutcome::Checkmate(PlayerColor::White) | GameOutcome::Resignation(PlayerColor::White) => "1-0",
-            GameOutcome::Checkmate(PlayerColor::Black) | GameOutcome::Resignation(PlayerColor::Black) => "0-1",
-            GameOutcome::Stalemate | GameOutcome::InsufficientMaterial | 
-            GameOutcome::ThreefoldRepetition | GameOutcome::FiftyMoveRule |
-            GameOutcome::DrawByAgreement => "1/2-1/2",
-            GameOutcome::InProgress => "*",
-        };
-        pgn.push_str(&format!("[Result \"{}\"]\n", result));
-        pgn.push('\n');
-        
-        // Moves
-        for (i, record) in self.game.move_history().iter().enumerate() {
-            if i % 2 == 0 {
-                pgn.push_str(&format!("{}. ", i / 2 + 1));
-            }
-            pgn.push_str(&record.san);
-            pgn.push(' ');
+    fn rebuild_opening_tree(&mut self) {
+        // The tree being built in the background used the old names
+        if self.my_games_loading.is_some() {
+            self.reload_my_games();
+            return;
         }
-        
-        pgn.push_str(result);
-        pgn.push('\n');
-        
-        pgn
+        let games = self.my_games.clone();
+        let names = self.state.preferences.usernames();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let tree = OpeningTree::build(&games, &names);
+            let _ = tx.send((games, tree));
+        });
+        self.my_games_loading = Some(rx);
     }
 
     /// Save current game to a new study
     fn save_game_to_study(&mut self) {
-        let mut new_study = Study::new(format!("Game {}", chrono::Local::now().format("%Y-%m-%d %H:%M")));
+        let name = format!("{} {}", i18n::format_date(&chrono::Local::now()), chrono::Local::now().format("%H:%M"));
+        let mut new_study = Study::new(tr_args("study.game_name", &[&name]));
         
         // Replay all moves into the study
         let moves: Vec<_> = self.game.move_history().iter().cloned().collect();
@@ -627,173 +3655,755 @@ impl ChessApp {
 
 impl eframe::App for ChessApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.process_engine_events(ctx);
+        self.poll_engine();
+        self.process_events(ctx);
+        self.play_pending_engine_move();
+        self.play_premove();
+        self.update_autoplay(ctx);
+        self.update_clock(ctx);
+        self.update_endgame();
+        self.update_broadcast(ctx);
+        self.update_tournament(ctx);
+        self.update_online(ctx);
+        self.update_dgt(ctx);
+        self.update_power(ctx);
+        self.record_window_layout(ctx);
+        self.handle_dropped_files(ctx);
+        self.update_drill(ctx);
+        self.update_import(ctx);
+        self.update_my_games(ctx);
+
+        if let Some((name, pending)) = &self.pending_import {
+            if let Some(action) = ImportDialog::show(ctx, name, pending.len()) {
+                self.events.push_back(AppEvent::Import(action));
+            }
+        }
+
+        // Hint while files are dragged over the window
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let screen = ctx.content_rect();
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_hint")));
+            painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                tr("import.drop_hint"),
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
 
         if self.engine_analyzing {
-            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            ctx.request_repaint_after(std::time::Duration::from_millis(self.state.preferences.analysis_refresh_ms));
         }
 
         // Side panel for controls, analysis, or study
-        egui::SidePanel::left("sidebar")
-            .default_width(240.0)
+        let sidebar = egui::SidePanel::left("sidebar")
+            .default_width(self.state.sidebar_width)
             .show(ctx, |ui| {
                 // Mode selector
                 ui.horizontal(|ui| {
-                    ui.label("Mode:");
-                    if ui.selectable_label(self.state.mode == AppMode::Game, "🎮").clicked() {
+                    ui.label(tr("mode.label"));
+                    if ui.selectable_label(self.state.mode == AppMode::Game, "🎮").on_hover_text(tr("mode.game")).clicked() {
                         self.set_mode(AppMode::Game);
                     }
-                    if ui.selectable_label(self.state.mode == AppMode::Analysis, "📊").clicked() {
+                    if ui.selectable_label(self.state.mode == AppMode::Analysis, "📊").on_hover_text(tr("mode.analysis")).clicked() {
                         self.set_mode(AppMode::Analysis);
                     }
-                    if ui.selectable_label(self.state.mode == AppMode::Study, "📚").clicked() {
+                    if ui.selectable_label(self.state.mode == AppMode::Study, "📚").on_hover_text(tr("mode.study")).clicked() {
                         self.set_mode(AppMode::Study);
                     }
+                    if ui.selectable_label(self.state.mode == AppMode::Training, "🎯").on_hover_text(tr("mode.training")).clicked() {
+                        self.set_mode(AppMode::Training);
+                    }
+                    if ui.selectable_label(self.state.mode == AppMode::Online, "🌐").on_hover_text(tr("mode.online")).clicked() {
+                        self.set_mode(AppMode::Online);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.selectable_label(self.show_settings, "⚙").on_hover_text(tr("settings.title")).clicked() {
+                            self.show_settings = !self.show_settings;
+                        }
+                    });
                 });
+                if self.dgt.as_ref().is_some_and(DgtConnection::is_connected) {
+                    if let Some(san) = &self.dgt_pending {
+                        ui.colored_label(egui::Color32::from_rgb(255, 170, 0), tr_args("dgt.make_move", &[san]));
+                    } else if !self.dgt_in_sync {
+                        ui.weak(tr("dgt.out_of_sync"));
+                    }
+                }
                 ui.separator();
 
                 // Navigation controls
-                if self.state.mode != AppMode::Game || self.game.can_go_back() || self.game.can_go_forward() {
-                    ui.label("Navigation:");
+                let has_navigation = match self.state.mode {
+                    AppMode::Game | AppMode::Online => self.game.can_go_back() || self.game.can_go_forward(),
+                    AppMode::Analysis | AppMode::Study => true,
+                    AppMode::Training => false,
+                };
+                if has_navigation {
+                    ui.label(tr("nav.label"));
                     ui.horizontal(|ui| {
-                        if ui.button("⏮").on_hover_text("Go to start").clicked() {
+                        if ui.button("⏮").on_hover_text(tr("nav.start")).clicked() {
                             self.go_to_start();
                         }
-                        if ui.button("◀").on_hover_text("Previous move").clicked() {
-                            self.go_to_previous_position();
+                        if ui.button("◀").on_hover_text(tr("nav.previous")).clicked() {
+                            self.step_backward();
                         }
-                        if ui.button("▶").on_hover_text("Next move").clicked() {
-                            self.go_to_next_position();
+                        if ui.button("▶").on_hover_text(tr("nav.next")).clicked() {
+                            self.step_forward();
                         }
-                        if ui.button("⏭").on_hover_text("Go to end").clicked() {
+                        if ui.button("⏭").on_hover_text(tr("nav.end")).clicked() {
                             self.go_to_end();
                         }
+                        let autoplay_icon = if self.autoplay { "⏸" } else { "▶" };
+                        if ui.selectable_label(self.autoplay, autoplay_icon)
+                            .on_hover_text(tr("nav.autoplay"))
+                            .clicked() {
+                            self.toggle_autoplay();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label(tr("nav.autoplay_speed"));
+                        let mut seconds = self.state.autoplay_interval_ms as f32 / 1000.0;
+                        if ui.add(egui::Slider::new(&mut seconds, 0.2..=5.0).suffix(" s")).changed() {
+                            self.state.autoplay_interval_ms = (seconds * 1000.0) as u64;
+                        }
                     });
                     
-                    ui.label(format!("Move: {} / {}", 
-                        self.game.current_index(), 
-                        self.game.position_count() - 1
-                    ));
+                    ui.label(tr_args("nav.move_counter", &[
+                        &self.game.current_index(),
+                        &(self.game.position_count() - 1),
+                    ]));
+
+                    ui.horizontal(|ui| {
+                        ui.label(tr("heatmap.label"));
+                        egui::ComboBox::from_id_salt("heatmap")
+                            .selected_text(self.heatmap.map_or(tr("heatmap.off"), |k| k.label()))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.heatmap, None, tr("heatmap.off"));
+                                for kind in HeatmapKind::all() {
+                                    ui.selectable_value(&mut self.heatmap, Some(*kind), kind.label());
+                                }
+                            });
+                    }).response.on_hover_text(tr("heatmap.hint"));
                     ui.separator();
                 }
 
                 // Mode-specific panels
                 match self.state.mode {
+                    AppMode::Analysis if self.puzzle.is_some() => {
+                        let today = today();
+                        let streak = &self.state.puzzle_streak;
+                        let (current, best) = (streak.current(today), streak.best);
+                        let action = self.puzzle.as_ref().and_then(|session| PuzzlePanel::show(ui, session, current, best));
+                        if let Some(action) = action {
+                            self.events.push_back(AppEvent::Puzzle(action));
+                        }
+                    }
                     AppMode::Analysis | AppMode::Study => {
                         // Combined Analysis + Study mode
                         ui.horizontal(|ui| {
-                            if ui.button(if self.engine_analyzing { "⏹ Stop" } else { "▶ Analyze" })
+                            if ui.button(if self.engine_analyzing { tr("analysis.stop") } else { tr("analysis.analyze") })
                                 .clicked() {
                                 self.toggle_analysis();
                             }
+                            if ui
+                                .add_enabled(self.engine_ready, egui::Button::new(tr("analysis.clear_hash")))
+                                .on_hover_text(tr("analysis.clear_hash_hint"))
+                                .clicked()
+                            {
+                                self.clear_engine_hash();
+                            }
+                            if self.scratch_saved_game.is_none() {
+                                if ui.button(tr("scratch.start")).on_hover_text(tr("scratch.start_hint")).clicked() {
+                                    self.start_scratch();
+                                }
+                            } else {
+                                if ui.button(tr("scratch.keep")).on_hover_text(tr("scratch.keep_hint")).clicked() {
+                                    self.commit_scratch();
+                                }
+                                if ui.button(tr("scratch.discard")).clicked() {
+                                    self.discard_scratch();
+                                }
+                            }
                         });
+                        if self.scratch_saved_game.is_some() {
+                            ui.colored_label(egui::Color32::from_rgb(255, 170, 0), tr("scratch.active"));
+                        }
+                        if ui.button(tr("analysis.open_lichess")).on_hover_text(tr("analysis.open_lichess_hint")).clicked() {
+                            self.open_in_lichess(ui.ctx());
+                        }
+                        self.show_share_buttons(ui);
+                        if ui.selectable_label(self.compare.open, tr("compare.open"))
+                            .on_hover_text(tr("compare.open_hint"))
+                            .clicked() {
+                            self.compare.open = !self.compare.open;
+                        }
                         ui.separator();
                         
                         // Show analysis panel and handle clicked moves
-                        if let Some((base_fen, path)) = self.analysis_panel.show(ui) {
-                            // User clicked a move in an engine line
-                            // Reset to base position first (where analysis started), then apply path
-                            if !base_fen.is_empty() {
-                                if let Ok(new_game) = GameState::from_fen(&base_fen) {
-                                    self.game = new_game;
-                                    tracing::info!("Reset to base position for analysis line");
-                                }
-                            }
-                            
-                            tracing::info!("Playing engine path: {:?}", path);
-                            
-                            // Play each move in the path sequentially
-                            for uci_move in path {
-                                if !self.apply_engine_move(&uci_move) {
-                                    break; // Stop if a move couldn't be applied
-                                }
-                            }
+                        let action = self.analysis_panel.show(
+                            ui,
+                            self.state.preferences.notation,
+                            self.state.theme,
+                            &mut self.piece_renderer,
+                            self.state.preferences.coach_hints,
+                        );
+                        if action == Some(AnalysisAction::Exclusions) && self.engine_analyzing {
+                            self.stop_analysis();
+                            self.start_analysis();
+                        }
+                        if let Some(AnalysisAction::Play { base_fen, path }) = action {
+                            self.play_engine_line(&base_fen, path);
                         }
                         
                         ui.separator();
+
+                        let mut explorer_move = None;
+                        egui::CollapsingHeader::new(tr("explorer.heading"))
+                            .id_salt("explorer")
+                            .show(ui, |ui| {
+                                let fen = self.game.fen();
+                                explorer_move = self.explorer_panel.show(
+                                    ui,
+                                    &mut self.explorer,
+                                    &self.opening_tree,
+                                    &self.study,
+                                    &fen,
+                                );
+                            });
+                        if let Some(uci) = explorer_move {
+                            self.apply_engine_move(&uci);
+                        }
+                        if std::mem::take(&mut self.explorer_panel.open_coverage) {
+                            let white = self.study.repertoire(PlayerColor::White);
+                            let black = self.study.repertoire(PlayerColor::Black);
+                            self.coverage = Some(CoverageReport::build(&self.opening_tree, &white, &black));
+                        }
+                        ui.separator();
+
+                        if self.state.mode == AppMode::Analysis {
+                            let mut broadcast_action = None;
+                            egui::CollapsingHeader::new(tr("broadcast.heading"))
+                                .id_salt("broadcast")
+                                .default_open(self.broadcast.is_some())
+                                .show(ui, |ui| {
+                                    broadcast_action =
+                                        BroadcastPanel::show(ui, &mut self.state.broadcast, self.broadcast.as_ref());
+                                });
+                            if let Some(action) = broadcast_action {
+                                self.events.push_back(AppEvent::Broadcast(action));
+                            }
+                            ui.separator();
+                        }
                         
                         // Also show study panel
                         if self.state.mode == AppMode::Study {
-                            if let Some(nav_action) = self.study_panel.show(ui, &mut self.study) {
-                                self.handle_study_nav_action(nav_action);
+                            if let Some(nav_action) = self.study_panel.show(
+                                ui,
+                                &mut self.study,
+                                self.state.theme,
+                                &self.state.preferences,
+                                &mut self.piece_renderer,
+                                self.drill.as_ref(),
+                            ) {
+                                self.events.push_back(AppEvent::StudyNav(nav_action));
                             }
+                            self.show_audit_progress(ui);
                         }
                     }
                     AppMode::Game => {
+                        let search_progress = self
+                            .engine_thinking
+                            .then(|| self.search_progress().unwrap_or_default());
                         if let Some(action) = ControlPanel::show(
                             ui,
                             &mut self.state.difficulty,
                             &mut self.state.theme,
                             &mut self.state.player_color,
+                            &mut self.state.time_control,
+                            self.game.outcome(),
+                            search_progress.as_deref(),
+                        ) {
+                            self.events.push_back(AppEvent::Control(action));
+                        }
+                        if let Some(action) = ControlPanel::show_personality(ui, &mut self.state.personality) {
+                            self.events.push_back(AppEvent::Control(action));
+                        }
+                        let can_undo = self.moves_by(self.state.player_color) > 0;
+                        if let Some(action) = ControlPanel::show_takebacks(
+                            ui,
+                            &mut self.state.takebacks,
+                            self.takebacks_used,
                             self.game.outcome(),
-                            self.engine_thinking,
+                            can_undo,
+                            !self.game.move_history().is_empty(),
                         ) {
-                            self.handle_control_action(action);
+                            self.events.push_back(AppEvent::Control(action));
+                        }
+
+                        if let Some(session) = &mut self.endgame {
+                            ui.separator();
+                            ui.label(egui::RichText::new(session.name()).strong());
+                            match session.expected() {
+                                Some(expected) => {
+                                    ui.label(tr_args("endgame.goal", &[&expected.label()]));
+                                }
+                                None if session.is_loading() => {
+                                    ui.horizontal(|ui| {
+                                        ui.spinner();
+                                        ui.weak(tr("endgame.looking_up"));
+                                    });
+                                }
+                                None => {
+                                    let error = session.fetch_error.as_deref().unwrap_or_default();
+                                    ui.weak(tr("endgame.fetch_failed")).on_hover_text(error);
+                                }
+                            }
+                            ui.weak(tr("endgame.full_strength"));
+                        }
+
+                        if self.game.outcome() == GameOutcome::InProgress {
+                            ui.separator();
+                            self.show_staged(ui);
+                            self.show_premoves(ui);
+                        }
+
+                        let time_graph = MoveTimeGraph {
+                            moves: self.game.move_history(),
+                            times: self.clock.move_times(),
+                            evals: &self.game_evals,
+                            first_mover: self.game.starting_turn(),
+                            current_index: self.game.current_index(),
+                        };
+                        let eval_graph = EvalGraph {
+                            moves: self.game.move_history(),
+                            evals: &self.game_evals,
+                            flags: &time_graph.flags(),
+                            current_index: self.game.current_index(),
+                            book_exit: self.book_exit.as_mut().and_then(BookExit::ply),
+                        };
+                        let mut clicked = None;
+                        if self.game_evals.iter().any(Option::is_some) {
+                            ui.separator();
+                            ui.label(tr("eval_graph.heading"));
+                            clicked = eval_graph.show(ui);
+                        }
+                        // Thinking time goes under the evaluation
+                        if !self.clock.move_times().is_empty() {
+                            ui.separator();
+                            ui.label(tr("move_time.heading"));
+                            clicked = time_graph.show(ui).or(clicked);
+                        }
+                        if let Some(index) = clicked {
+                            let _ = self.game.go_to_position(index);
+                            self.clear_selection();
                         }
                         
                         // Add PGN export button for finished games
                         if self.game.outcome() != GameOutcome::InProgress {
                             ui.separator();
-                            if ui.button("📄 Export PGN").clicked() {
+                            if ui.button(tr("controls.export_pgn")).clicked() {
                                 let pgn = self.export_game_pgn();
                                 ui.ctx().copy_text(pgn);
+                                self.toasts.info(tr("toast.pgn_copied"));
+                            }
+                            if ui.button(tr("analysis.open_lichess")).on_hover_text(tr("analysis.open_lichess_hint")).clicked() {
+                                self.open_in_lichess(ui.ctx());
+                            }
+                            self.show_share_buttons(ui);
+                            if let Some((done, total)) = self.review_progress {
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        egui::ProgressBar::new(done as f32 / total as f32)
+                                            .desired_width(160.0)
+                                            .text(tr_args("controls.review_progress", &[&done, &total])),
+                                    );
+                                    if ui.button(tr("controls.review_cancel")).clicked() {
+                                        self.cancel_review();
+                                    }
+                                });
+                            } else if ui
+                                .add_enabled(
+                                    self.engine_ready && !self.unreviewed_positions().is_empty(),
+                                    egui::Button::new(tr("controls.review_game")),
+                                )
+                                .on_hover_text(tr("controls.review_game_hint"))
+                                .clicked()
+                            {
+                                self.start_review();
+                            }
+                            let engine = self.engine_name.as_deref().unwrap_or("Stockfish");
+                            let sides = [
+                                (self.state.player_color, tr("plate.you")),
+                                (self.state.player_color.opposite(), engine),
+                            ];
+                            let move_phases = self.game.move_phases();
+                            for (side, name) in sides {
+                                if let Some(summary) = QualitySummary::of_game(&self.game, &self.game_evals, side) {
+                                    QualityStrip { name, summary: &summary }.show(ui);
+                                    let phases = QualitySummary::by_phase(&self.game_evals, &move_phases, self.game.starting_turn(), side);
+                                    PhaseStrip { phases: &phases }.show(ui);
+                                }
+                            }
+                            let moments = critical_moments(
+                                &self.game_evals,
+                                self.game.move_history().len(),
+                                self.game.starting_turn(),
+                                CRITICAL_MOMENTS,
+                            );
+                            if !moments.is_empty() {
+                                ui.separator();
+                                let panel = CriticalMomentsPanel {
+                                    moments: &moments,
+                                    game: &self.game,
+                                    notation: self.state.preferences.notation,
+                                };
+                                if let Some(ply) = panel.show(ui) {
+                                    self.go_to_position(ply);
+                                }
+                                ui.separator();
+                            }
+                            if ui.button(tr("controls.export_report")).clicked() {
+                                self.save_report();
                             }
-                            if ui.button("📚 Save to Study").clicked() {
+                            if ui.button(tr("controls.save_to_study")).clicked() {
                                 self.save_game_to_study();
                             }
                         }
+
+                        ui.separator();
+                        let mut open = None;
+                        ui.collapsing(tr("games.heading"), |ui| {
+                            open = self.games_list.show(ui, &self.my_games);
+                        });
+                        if let Some(index) = open {
+                            self.open_my_game(index);
+                        }
+                        ui.collapsing(tr("export.heading"), |ui| {
+                            ui.checkbox(&mut self.export_my_games, tr("export.include_my_games"))
+                                .on_hover_text(tr("export.include_my_games_hint"));
+                            ui.add_enabled(
+                                self.export_my_games,
+                                egui::TextEdit::singleline(&mut self.export_filter).hint_text(tr("export.filter_hint")),
+                            );
+                            if ui.button(tr("export.all_games")).clicked() {
+                                self.export_all_games();
+                            }
+                        });
+                    }
+                    AppMode::Online => {
+                        if let Some(action) = OnlinePanel::show(
+                            ui,
+                            &mut self.state.online,
+                            self.lichess.as_ref(),
+                            &self.challenges,
+                            self.online_game.as_ref(),
+                        ) {
+                            self.events.push_back(AppEvent::Online(action));
+                        }
+                    }
+                    AppMode::Training => {
+                        egui::CollapsingHeader::new(tr("plan.heading"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                TrainingPlanPanel::show(ui, &mut self.state.training_plan, today());
+                            });
+                        ui.separator();
+                        if self.state.preferences.daily_puzzle != PuzzleSource::Off
+                            && ui.button(tr("puzzle.daily")).clicked()
+                        {
+                            if !self.daily_puzzle.is_loading() && self.daily_puzzle.poll().is_none() {
+                                self.daily_puzzle = DailyPuzzle::load(self.state.preferences.daily_puzzle);
+                            }
+                            self.show_puzzle_card = true;
+                        }
+                        ui.separator();
+                        ui.label(egui::RichText::new(tr("endgame.heading")).strong());
+                        for (index, (_, _, name)) in ENDGAMES.iter().enumerate() {
+                            if ui.button(tr(name)).on_hover_text(tr("endgame.play_hint")).clicked() {
+                                self.start_endgame(index);
+                            }
+                        }
+                        ui.separator();
+                        ui.collapsing(tr("tournament.heading"), |ui| {
+                            if let Some(action) =
+                                TournamentPanel::show(ui, &mut self.state.tournament, self.tournament.as_ref())
+                            {
+                                self.events.push_back(AppEvent::Tournament(action));
+                            }
+                        });
+                        ui.separator();
+                        self.vision_trainer.show_panel(ui, &mut self.state.vision_history);
                     }
                 }
             });
+        self.state.sidebar_width = sidebar.response.rect.width();
 
-        // Bottom panel for move list
-        egui::TopBottomPanel::bottom("moves")
-            .default_height(120.0)
+        // Status bar along the bottom edge
+        egui::TopBottomPanel::bottom("status_bar")
+            .exact_height(22.0)
             .show(ctx, |ui| {
-                MoveList::show(ui, self.game.move_history());
+                StatusBar::show(
+                    ui,
+                    self.engine_name.as_deref(),
+                    self.engine_activity(),
+                    self.engine_nps,
+                    self.state.difficulty,
+                );
             });
 
-        // Central panel for the board
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let mut board = ChessBoard::new(
-                &self.game,
-                self.state.theme,
-                self.state.flipped,
-                &mut self.piece_renderer,
-            );
+        // Bottom panel for move list
+        if self.state.mode != AppMode::Training {
+            let move_list = egui::TopBottomPanel::bottom("moves")
+                .resizable(true)
+                .default_height(self.state.move_list_height)
+                .show(ctx, |ui| {
+                    let can_interact = self.can_interact();
+                    if let Some(m) = self.move_entry.show(ui, &self.game, can_interact) {
+                        self.events.push_back(AppEvent::Move(m));
+                    }
+                    let book_exit = self.book_exit.as_mut().and_then(BookExit::ply);
+                    MoveList::show(ui, self.game.move_history(), self.state.preferences.notation, book_exit);
+                });
+            self.state.move_list_height = move_list.response.rect.height();
+        }
 
-            let response = board.show(
-                ui,
-                &mut self.selected_square,
-                &self.legal_moves_for_selected,
-            );
+        for error in self.study_panel.take_errors() {
+            self.toasts.report(&error, error.action());
+        }
+        if let Some(action) = self.toasts.show(ctx) {
+            self.events.push_back(AppEvent::Toast(action));
+        }
+        EngineLogWindow::show(ctx, &mut self.show_engine_log, &mut self.engine_log);
+        if let Some(command) = UciConsoleWindow::show(ctx, &mut self.show_uci_console, &mut self.uci_console) {
+            let _ = self.engine_cmd_tx.send(EngineCommand::Raw(command));
+        }
+        if self.compare.open {
+            let fen = self.game.fen();
+            let engine_fen = self.engine_line_end(&fen);
+            self.compare.show(ctx, &fen, engine_fen.as_deref(), self.state.theme, &mut self.piece_renderer);
+        }
+        if let Some(session) = &self.drill_summary {
+            let mut open = true;
+            DrillSummaryWindow::show(ctx, &mut open, session);
+            if !open {
+                self.drill_summary = None;
+            }
+        }
+        if let Some(report) = &self.coverage {
+            let mut open = true;
+            let line = CoverageWindow::show(ctx, &mut open, report);
+            if !open {
+                self.coverage = None;
+            }
+            if let Some(line) = line {
+                self.open_line(&line);
+            }
+        }
 
-            // Handle board interaction
-            let can_interact = match self.state.mode {
-                AppMode::Game => {
-                    self.game.outcome() == GameOutcome::InProgress
-                        && !self.engine_thinking
-                        && self.game.turn() == self.state.player_color
+        if let Some(onboarding) = &mut self.onboarding {
+            match onboarding.show(ctx) {
+                Some(OnboardingAction::UseEnginePath(path)) => {
+                    self.onboarding = None;
+                    self.engine_path_input = path.clone();
+                    self.set_engine_path(path);
                 }
-                AppMode::Analysis | AppMode::Study => {
-                    self.game.outcome() == GameOutcome::InProgress
+                Some(OnboardingAction::Dismiss) => {
+                    self.onboarding = None;
                 }
+                None => {}
+            }
+        }
+
+        if self.show_puzzle_card && self.onboarding.is_none() {
+            let streak = self.state.puzzle_streak.current(today());
+            if let Some(action) = DailyPuzzleCard::show(
+                ctx,
+                &mut self.daily_puzzle,
+                streak,
+                self.state.theme,
+                &mut self.piece_renderer,
+            ) {
+                self.events.push_back(AppEvent::PuzzleCard(action));
+            }
+            if self.daily_puzzle.is_loading() {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+            }
+        }
+
+        if let Some(action) = SettingsWindow::show(
+            ctx,
+            &mut self.show_settings,
+            &mut self.state.preferences,
+            &mut self.engine_path_input,
+            self.dgt.as_ref(),
+        ) {
+            self.events.push_back(AppEvent::Settings(action));
+        }
+
+        // Central panel for the board
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.state.mode == AppMode::Training {
+                self.vision_trainer.show_board(
+                    ui,
+                    self.state.theme,
+                    &mut self.piece_renderer,
+                    self.state.flipped,
+                    &mut self.state.vision_history,
+                );
+                return;
+            }
+
+            // Name plates sit above and below the board and flip with it
+            let (top_side, bottom_side) = if self.state.flipped {
+                (PlayerColor::White, PlayerColor::Black)
+            } else {
+                (PlayerColor::Black, PlayerColor::White)
+            };
+            // The study tree has its own navigation, so only games get a slider
+            let show_slider = !self.follows_study() && !self.game.move_history().is_empty();
+            let slider_height = if show_slider {
+                ui.spacing().interact_size.y + ui.spacing().item_spacing.y
+            } else {
+                0.0
             };
+            let board_size = ui
+                .available_width()
+                .min(ui.available_height() - 2.0 * PLATE_HEIGHT - slider_height)
+                .max(0.0)
+                * self.state.board_scale;
+            let plate_size = egui::vec2(board_size, PLATE_HEIGHT);
+
+            let arrows = self.hovered_pv_arrows();
+            let engine_side = (self.state.mode == AppMode::Game && self.state.preferences.engine_move_highlight)
+                .then(|| self.state.player_color.opposite().into());
+            let movable_side = if self.can_premove() { self.state.player_color } else { self.game.turn() };
+            // A board zoomed past the panel is panned with the scroll bars; the wheel still steps moves
+            let scroll_source = egui::scroll_area::ScrollSource { mouse_wheel: false, ..Default::default() };
+            let board_area = egui::ScrollArea::both()
+                .id_salt("board_scroll")
+                .scroll_source(scroll_source)
+                .show(ui, |ui| {
+                    ui.allocate_ui(plate_size, |ui| self.show_player_plate(ui, top_side));
+
+                    let board_area = ui.allocate_ui(egui::vec2(board_size, board_size), |ui| {
+                        let heatmap = self.heatmap.map(|kind| self.game.heatmap(kind));
+                        let mut board = ChessBoard::new(
+                            &self.game,
+                            self.state.theme,
+                            self.state.flipped,
+                            &mut self.piece_renderer,
+                        )
+                        .with_heatmap(heatmap.as_ref())
+                        .with_arrows(arrows)
+                        .with_clicks(self.state.preferences.clicks)
+                        .with_engine_side(engine_side)
+                        .with_movable_side(movable_side.into())
+                        .with_auto_queen(self.state.preferences.auto_queen);
+                        board.show(ui, &mut self.selected_square, &self.legal_moves_for_selected)
+                    });
+
+                    ui.allocate_ui(plate_size, |ui| self.show_player_plate(ui, bottom_side));
+
+                    if show_slider {
+                        // Scrub through the game by half-moves
+                        let mut index = self.game.current_index();
+                        ui.spacing_mut().slider_width = board_size;
+                        let slider = egui::Slider::new(&mut index, 0..=self.game.move_history().len()).show_value(false);
+                        if ui.add(slider).on_hover_text(tr("board.move_slider_hint")).changed() {
+                            self.go_to_position(index);
+                        }
+                    }
+                    board_area
+                })
+                .inner;
+            let response = board_area.inner;
+
+            // Handle board interaction
+            let can_interact = self.can_interact();
+
+            self.handle_navigation_input(ui.ctx(), response.scroll_delta);
+            if response.zoom_delta != 1.0 {
+                self.state.board_scale = (self.state.board_scale * response.zoom_delta).clamp(MIN_BOARD_SCALE, MAX_BOARD_SCALE);
+            }
 
             if let Some(square) = response.square_clicked {
                 self.select_square(square);
             }
+            if response.deselected {
+                self.clear_selection();
+            }
             
             if let Some(m) = response.move_made {
-                if can_interact {
-                    self.make_move(m);
+                if can_interact && self.should_stage() {
+                    self.stage_move(m);
+                } else if can_interact {
+                    self.events.push_back(AppEvent::Move(m));
+                } else if self.can_premove() {
+                    self.queue_premove(m);
+                }
+            }
+
+            if self.state.mode == AppMode::Game
+                && self.game.outcome() != GameOutcome::InProgress
+                && !self.result_dismissed
+            {
+                let summary = QualitySummary::of_game(&self.game, &self.game_evals, self.state.player_color);
+                let opponent_summary =
+                    QualitySummary::of_game(&self.game, &self.game_evals, self.state.player_color.opposite());
+                let dialog = ResultDialog {
+                    outcome: self.game.outcome(),
+                    player_color: self.state.player_color,
+                    summary: summary.as_ref(),
+                    opponent: self.engine_name.as_deref().unwrap_or("Stockfish"),
+                    opponent_summary: opponent_summary.as_ref(),
+                };
+                if let Some(action) = dialog.show(ui.ctx(), board_area.response.rect) {
+                    self.events.push_back(AppEvent::Result(action));
+                }
+            }
+
+            if let Some(prompt) = self.resign_prompt {
+                let answer = if self.state.mode == AppMode::Game && self.game.outcome() == GameOutcome::InProgress {
+                    ResignDialog { prompt }.show(ui.ctx(), board_area.response.rect)
+                } else {
+                    Some(false)
+                };
+                match answer {
+                    Some(true) => {
+                        self.resign_prompt = None;
+                        self.resign();
+                    }
+                    Some(false) => {
+                        self.resign_prompt = None;
+                        self.auto_resign_declined |= matches!(prompt, ResignPrompt::Lost { .. });
+                    }
+                    None => {}
                 }
             }
         });
+
+        // What the panels queued while drawing takes effect on the next frame's paint
+        if !self.events.is_empty() {
+            self.process_events(ctx);
+            ctx.request_repaint();
+        }
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.state.analysis = self.analysis_panel.settings.clone();
         eframe::set_value(storage, eframe::APP_KEY, &self.state);
+        // eframe overwrites its file in place, which a crash can leave half-written
+        match serde_json::to_string(&self.state) {
+            Ok(json) => {
+                if let Err(e) = write_atomic(&data_dir().join(STATE_COPY_FILE), json) {
+                    tracing::warn!("Failed to save settings copy: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize settings: {}", e),
+        }
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
@@ -801,5 +4411,61 @@ impl eframe::App for ChessApp {
         
         let cmd_tx = self.engine_cmd_tx.clone();
         let _ = cmd_tx.send(EngineCommand::Quit);
+
+        // The engine processes have to be gone before the app is, or they are orphaned
+        let deadline = std::time::Instant::now() + EXIT_TIMEOUT;
+        self.engines.quit_all(deadline);
+        while let Some(left) = deadline.checked_duration_since(std::time::Instant::now()) {
+            match self.engine_event_rx.recv_timeout(left) {
+                Ok(EngineEvent::Terminated) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The app with default settings and no window
+    fn headless() -> ChessApp {
+        let mut state = AppState::default();
+        state.preferences.daily_puzzle = PuzzleSource::Off;
+        state.mode = AppMode::Analysis;
+        ChessApp::with_state(state, Config::default(), &LaunchOptions::default())
+    }
+
+    #[test]
+    fn test_handle_events() {
+        let mut app = headless();
+        let (_, e4) = app.game.legal_moves_san().into_iter().find(|(san, _)| san == "e4").unwrap();
+        let flipped = app.state.flipped;
+        app.events.extend([
+            AppEvent::Move(e4),
+            AppEvent::Control(ControlAction::FlipBoard),
+            AppEvent::Engine(EngineEvent::Output("readyok".to_string())),
+            AppEvent::Result(ResultAction::Export),
+        ]);
+        app.handle_events();
+
+        assert!(app.events.is_empty());
+        assert_eq!(app.game.move_history().len(), 1);
+        assert_eq!(app.state.flipped, !flipped);
+        let pgn = app.export_game_pgn();
+        assert!(pgn.contains("1. e4"));
+        assert_eq!(app.ui_requests, [UiRequest::Repaint, UiRequest::CopyText(pgn)]);
+    }
+
+    #[test]
+    fn test_export_mistakes_opt_in() {
+        let mut app = headless();
+        let (_, e4) = app.game.legal_moves_san().into_iter().find(|(san, _)| san == "e4").unwrap();
+        app.make_move(e4);
+        app.game_evals = vec![Some(0), Some(-500)];
+        assert!(!app.export_game_pgn().contains('$'));
+
+        app.state.preferences.export_mistakes = true;
+        assert!(app.export_game_pgn().contains("1. e4 $4"));
     }
 }
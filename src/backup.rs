@@ -0,0 +1,113 @@
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// Entry holding the app's persisted settings, next to the files of the data directory
+const SETTINGS_ENTRY: &str = "settings.json";
+
+/// Write a backup of every file under `data_dir` plus `settings` to `path`
+pub fn create(path: &Path, data_dir: &Path, settings: &str) -> Result<usize, String> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    write_backup(file, data_dir, settings)
+}
+
+/// Restore a backup into `data_dir`, returning the settings it holds, if any. The whole
+/// archive is read and checked before anything is written.
+pub fn restore(path: &Path, data_dir: &Path) -> Result<Option<String>, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    read_backup(file, data_dir)
+}
+
+/// Files under `dir`, relative to it, skipping half-written `.tmp` files
+fn data_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().map_or(true, |e| e != "tmp") {
+                files.push(path.strip_prefix(dir).unwrap_or(&path).to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn write_backup(writer: impl Write + Seek, data_dir: &Path, settings: &str) -> Result<usize, String> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default();
+    zip.start_file(SETTINGS_ENTRY, options).map_err(|e| e.to_string())?;
+    zip.write_all(settings.as_bytes()).map_err(|e| e.to_string())?;
+
+    let files = data_files(data_dir).map_err(|e| e.to_string())?;
+    for relative in &files {
+        let name = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        let contents = std::fs::read(data_dir.join(relative)).map_err(|e| format!("{}: {}", relative.display(), e))?;
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(&contents).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(files.len())
+}
+
+fn read_backup(reader: impl Read + Seek, data_dir: &Path) -> Result<Option<String>, String> {
+    let mut zip = ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    let mut settings = None;
+    let mut files = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        // Entries must stay inside the data directory
+        let relative = entry.enclosed_name().ok_or_else(|| format!("unsafe path in backup: {}", entry.name()))?;
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).map_err(|e| e.to_string())?;
+        if relative == Path::new(SETTINGS_ENTRY) {
+            settings = Some(String::from_utf8(contents).map_err(|e| e.to_string())?);
+        } else {
+            files.push((relative, contents));
+        }
+    }
+
+    for (relative, contents) in files {
+        let path = data_dir.join(relative);
+        crate::config::write_atomic(&path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let root = std::env::temp_dir().join(format!("stockfish-chess-backup-{}", std::process::id()));
+        let (source, target) = (root.join("source"), root.join("target"));
+        std::fs::create_dir_all(source.join("studies")).unwrap();
+        std::fs::write(source.join("config.toml"), "[engine]\n").unwrap();
+        std::fs::write(source.join("studies/abc.json"), "{}").unwrap();
+        std::fs::write(source.join("my_games.tmp"), "partial").unwrap();
+
+        let mut archive = std::io::Cursor::new(Vec::new());
+        assert_eq!(write_backup(&mut archive, &source, "{\"flipped\":true}").unwrap(), 2);
+        archive.set_position(0);
+        let settings = read_backup(archive, &target).unwrap();
+
+        assert_eq!(settings.as_deref(), Some("{\"flipped\":true}"));
+        assert_eq!(std::fs::read_to_string(target.join("studies/abc.json")).unwrap(), "{}");
+        assert!(target.join("config.toml").exists());
+        assert!(!target.join("my_games.tmp").exists());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::config::USER_AGENT;
+use crate::game::{parse_pgn, GameError, GameState, PgnGame};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Shortest polling interval, to stay polite to broadcast servers
+pub const MIN_INTERVAL_SECS: u64 = 5;
+
+/// What to follow and how often, remembered between sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BroadcastSettings {
+    /// PGN of the event: an http(s) URL, or a path to a file another program keeps updating
+    pub url: String,
+    pub interval_secs: u64,
+    /// Keep the engine analyzing the newest position
+    pub live_analysis: bool,
+}
+
+impl Default for BroadcastSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            interval_secs: 15,
+            live_analysis: true,
+        }
+    }
+}
+
+/// How the followed game changed with the latest poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastUpdate {
+    Unchanged,
+    /// This many new moves were added
+    Appended(usize),
+    /// The game no longer matches what was shown (another game, or moves were corrected)
+    Replaced,
+}
+
+fn fetch(url: &str) -> Result<Vec<PgnGame>, String> {
+    let text = if url.starts_with("http://") || url.starts_with("https://") {
+        ureq::get(url)
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())?
+    } else {
+        std::fs::read_to_string(shellexpand::tilde(url).as_ref()).map_err(|e| e.to_string())?
+    };
+    Ok(parse_pgn(&text))
+}
+
+/// Polls a PGN source on a background thread until dropped
+pub struct Broadcast {
+    /// Dropping the sender stops the polling thread
+    _stop_tx: mpsc::Sender<()>,
+    update_rx: mpsc::Receiver<Result<Vec<PgnGame>, String>>,
+    /// Games in the latest successful poll
+    pub games: Vec<PgnGame>,
+    /// Index of the followed game in `games`
+    pub selected: usize,
+    pub last_update: Option<Instant>,
+    pub last_error: Option<String>,
+}
+
+impl Broadcast {
+    pub fn follow(url: String, interval: Duration) -> Self {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (update_tx, update_rx) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            if update_tx.send(fetch(&url)).is_err() {
+                break;
+            }
+            match stop_rx.recv_timeout(interval) {
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+        Self {
+            _stop_tx: stop_tx,
+            update_rx,
+            games: Vec::new(),
+            selected: 0,
+            last_update: None,
+            last_error: None,
+        }
+    }
+
+    /// Take finished polls; true when new games arrived
+    pub fn poll(&mut self) -> bool {
+        let mut updated = false;
+        while let Ok(result) = self.update_rx.try_recv() {
+            match result {
+                Ok(games) => {
+                    self.games = games;
+                    self.selected = self.selected.min(self.games.len().saturating_sub(1));
+                    self.last_update = Some(Instant::now());
+                    self.last_error = None;
+                    updated = true;
+                }
+                Err(e) => self.last_error = Some(e),
+            }
+        }
+        updated
+    }
+
+    pub fn game(&self) -> Option<&PgnGame> {
+        self.games.get(self.selected)
+    }
+}
+
+/// Bring `game` up to date with the broadcast PGN, keeping the viewed move unless it was the latest
+pub fn sync_game(game: &mut GameState, pgn: &PgnGame) -> Result<BroadcastUpdate, GameError> {
+    catch_up(game, pgn.to_game()?)
+}
+
+/// Bring `game` up to date with `latest`, a newer copy of it such as a server's
+pub fn catch_up(game: &mut GameState, latest: GameState) -> Result<BroadcastUpdate, GameError> {
+    let known = game.move_history();
+    let extends = latest.starting_fen() == game.starting_fen()
+        && latest.move_history().len() >= known.len()
+        && latest.move_history().iter().zip(known).all(|(new, old)| new.uci == old.uci);
+    if !extends {
+        *game = latest;
+        return Ok(BroadcastUpdate::Replaced);
+    }
+
+    let new_moves = &latest.move_history()[known.len()..];
+    if new_moves.is_empty() {
+        return Ok(BroadcastUpdate::Unchanged);
+    }
+    let viewed = game.current_index();
+    let at_latest = !game.can_go_forward();
+    game.go_to_end();
+    for record in new_moves {
+        game.make_move_uci(&record.uci)?;
+    }
+    if !at_latest {
+        game.go_to_position(viewed)?;
+    }
+    Ok(BroadcastUpdate::Appended(new_moves.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_game() {
+        let pgn = |moves: &str| parse_pgn(&format!("[Event \"Live\"]\n\n{} *\n", moves)).remove(0);
+        let mut game = GameState::new();
+
+        assert_eq!(sync_game(&mut game, &pgn("1. e4 e5")).unwrap(), BroadcastUpdate::Appended(2));
+        assert_eq!(game.current_index(), 2);
+        assert_eq!(sync_game(&mut game, &pgn("1. e4 e5")).unwrap(), BroadcastUpdate::Unchanged);
+
+        // Browsing an earlier move stays put while new moves arrive
+        game.go_to_position(1).unwrap();
+        assert_eq!(sync_game(&mut game, &pgn("1. e4 e5 2. Nf3")).unwrap(), BroadcastUpdate::Appended(1));
+        assert_eq!((game.current_index(), game.move_history().len()), (1, 3));
+
+        // A corrected move replaces the game
+        assert_eq!(sync_game(&mut game, &pgn("1. e4 c5")).unwrap(), BroadcastUpdate::Replaced);
+        assert_eq!(game.move_history()[1].san, "c5");
+    }
+}
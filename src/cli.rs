@@ -0,0 +1,100 @@
+use crate::app::AppMode;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: stockfish-chess [OPTIONS] [FILE.pgn]
+
+Options:
+  --fen <FEN>        Start from this position
+  --engine <PATH>    UCI engine to use for this session
+  --mode <MODE>      game, analysis, study, training or online
+  --data-dir <DIR>   Keep studies, games and settings in this directory
+  --portable         Keep them in a data folder next to the executable
+                     (also on when a file named portable is there)
+  -h, --help         Print this help";
+
+/// Options given on the command line, applied once at startup
+#[derive(Debug, Default, PartialEq)]
+pub struct LaunchOptions {
+    /// PGN file whose first game is opened
+    pub pgn: Option<PathBuf>,
+    pub fen: Option<String>,
+    pub engine: Option<String>,
+    pub mode: Option<AppMode>,
+    /// Directory used instead of the user's data folder
+    pub data_dir: Option<PathBuf>,
+    /// Keep the data next to the executable
+    pub portable: bool,
+    pub help: bool,
+}
+
+impl LaunchOptions {
+    /// Parse the arguments after the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Accept both "--fen value" and "--fen=value"
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", flag))
+            };
+            match flag.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--fen" => options.fen = Some(value()?),
+                "--engine" => options.engine = Some(shellexpand::tilde(&value()?).to_string()),
+                "--data-dir" => options.data_dir = Some(PathBuf::from(shellexpand::tilde(&value()?).to_string())),
+                "--portable" => options.portable = true,
+                "--mode" => {
+                    let mode = value()?;
+                    options.mode = Some(match mode.to_ascii_lowercase().as_str() {
+                        "game" => AppMode::Game,
+                        "analysis" => AppMode::Analysis,
+                        "study" => AppMode::Study,
+                        "training" => AppMode::Training,
+                        "online" => AppMode::Online,
+                        _ => return Err(format!("unknown mode '{}'", mode)),
+                    });
+                }
+                _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+                _ if options.pgn.is_none() => options.pgn = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchOptions, String> {
+        LaunchOptions::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_launch_options() {
+        let options = parse(&["game.pgn", "--mode", "analysis", "--engine=/opt/sf"]).unwrap();
+        assert_eq!(options.pgn, Some(PathBuf::from("game.pgn")));
+        assert_eq!(options.mode, Some(AppMode::Analysis));
+        assert_eq!(options.engine.as_deref(), Some("/opt/sf"));
+
+        let fen = "8/8/8/8/8/8/8/K6k w - - 0 1";
+        assert_eq!(parse(&["--fen", fen]).unwrap().fen.as_deref(), Some(fen));
+        assert!(parse(&["--mode", "blitz"]).is_err());
+        assert!(parse(&["--fen"]).is_err());
+        assert!(parse(&["a.pgn", "b.pgn"]).is_err());
+
+        let options = parse(&["--data-dir=/media/usb/chess", "--portable"]).unwrap();
+        assert_eq!(options.data_dir, Some(PathBuf::from("/media/usb/chess")));
+        assert!(options.portable);
+        assert!(parse(&["--data-dir"]).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use crate::engine::{DifficultyLevel, EngineTimeouts};
+use crate::ui::Theme;
+use egui::Key;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use toml_edit::{value, DocumentMut, Item};
+
+/// User-Agent sent with requests to Lichess and other web services
+pub const USER_AGENT: &str = concat!("stockfish-chess/", env!("CARGO_PKG_VERSION"));
+
+/// File next to the executable that turns on portable mode
+const PORTABLE_MARKER: &str = "portable";
+
+/// Data directory chosen at startup instead of the user's data folder
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Tells apart the temporary files of writes running at the same time
+static WRITE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Directory holding studies and the config file
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = DATA_DIR_OVERRIDE.get() {
+        return dir.clone();
+    }
+    dirs::data_dir()
+        .unwrap_or_else(|| std::env::current_dir().unwrap())
+        .join("Stockfish-Chess")
+}
+
+/// Keep all data in `dir` for the rest of the session, the settings included. Only the first
+/// call counts.
+pub fn set_data_dir(dir: PathBuf) {
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("Could not create data directory {}: {}", dir.display(), e);
+    }
+    tracing::info!("Using data directory {}", dir.display());
+    let _ = DATA_DIR_OVERRIDE.set(dir);
+}
+
+/// The "data" folder next to the executable when running portable: asked for with `forced`,
+/// or by a `portable` file placed beside the executable
+pub fn portable_dir(forced: bool) -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let exe_dir = exe.parent()?;
+    (forced || exe_dir.join(PORTABLE_MARKER).exists()).then(|| exe_dir.join("data"))
+}
+
+/// File the window and app settings are saved to, `None` for eframe's own location. With a
+/// data directory chosen at startup they live in it, so nothing is written elsewhere.
+pub fn settings_file() -> Option<PathBuf> {
+    DATA_DIR_OVERRIDE.get().map(|dir| dir.join("app.ron"))
+}
+
+/// Replace `path` with `contents` so readers never see a half-written file, and a crash
+/// leaves either the old or the new contents on disk
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    use std::io::Write;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Unique per write, so two writers to the same file never share a temporary file
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let count = WRITE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp = path.with_file_name(format!(".{}.{}-{}.tmp", name, std::process::id(), count));
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(contents.as_ref())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)?;
+    // The rename itself only lasts once the directory is on disk; not possible on Windows
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Keys for moving through the game and flipping the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings {
+    pub previous: Key,
+    pub next: Key,
+    pub start: Key,
+    pub end: Key,
+    pub flip: Key,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            previous: Key::ArrowLeft,
+            next: Key::ArrowRight,
+            start: Key::Home,
+            end: Key::End,
+            flip: Key::F,
+        }
+    }
+}
+
+/// Settings read from an optional, hand-editable `config.toml` in the data directory.
+/// Values present in the file take precedence over the ones eframe persisted.
+///
+/// ```toml
+/// [engine]
+/// path = "/usr/local/bin/stockfish"
+/// difficulty = "Intermediate"
+/// # Seconds to wait for the engine before restarting it
+/// init_timeout = 30
+/// ready_timeout = 10
+/// move_timeout = 10
+///
+/// [appearance]
+/// theme = "Lichess"
+///
+/// [keybindings]
+/// previous = "ArrowLeft"
+/// flip = "F"
+///
+/// [lichess]
+/// token = "lip_..."
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    pub engine_path: Option<String>,
+    pub difficulty: Option<DifficultyLevel>,
+    pub engine_timeouts: EngineTimeouts,
+    pub theme: Option<Theme>,
+    pub keybindings: KeyBindings,
+    /// Personal API token for Lichess services that require a login
+    pub lichess_token: Option<String>,
+    /// The parsed file, kept so comments and layout survive when values are written back
+    document: DocumentMut,
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        data_dir().join("config.toml")
+    }
+
+    /// Read the config file; a missing file gives the defaults
+    pub fn load() -> Result<Self, String> {
+        match std::fs::read_to_string(Self::path()) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let document: DocumentMut = text.parse().map_err(|e: toml_edit::TomlError| e.to_string())?;
+        let get = |table: &str, key: &str| document.get(table).and_then(|t| t.get(key)).and_then(Item::as_str);
+
+        let mut keybindings = KeyBindings::default();
+        for (name, key) in [
+            ("previous", &mut keybindings.previous),
+            ("next", &mut keybindings.next),
+            ("start", &mut keybindings.start),
+            ("end", &mut keybindings.end),
+            ("flip", &mut keybindings.flip),
+        ] {
+            if let Some(binding) = get("keybindings", name) {
+                *key = Key::from_name(binding).ok_or_else(|| format!("unknown key '{}' for {}", binding, name))?;
+            }
+        }
+
+        let mut engine_timeouts = EngineTimeouts::default();
+        for (name, timeout) in [
+            ("init_timeout", &mut engine_timeouts.init),
+            ("ready_timeout", &mut engine_timeouts.ready),
+            ("move_timeout", &mut engine_timeouts.bestmove),
+        ] {
+            if let Some(item) = document.get("engine").and_then(|t| t.get(name)) {
+                let seconds = item
+                    .as_integer()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("{} must be a positive number of seconds", name))?;
+                *timeout = std::time::Duration::from_secs(seconds as u64);
+            }
+        }
+
+        Ok(Self {
+            engine_path: get("engine", "path").map(|p| shellexpand::tilde(p).to_string()),
+            difficulty: get("engine", "difficulty")
+                .map(|name| find_by_name(DifficultyLevel::all(), name, "difficulty"))
+                .transpose()?,
+            engine_timeouts,
+            theme: get("appearance", "theme")
+                .map(|name| find_by_name(Theme::all(), name, "theme"))
+                .transpose()?,
+            keybindings,
+            lichess_token: get("lichess", "token").filter(|t| !t.is_empty()).map(str::to_string),
+            document,
+        })
+    }
+
+    pub fn set_engine_path(&mut self, path: &str) -> std::io::Result<()> {
+        self.set_value("engine", "path", value(path))?;
+        self.engine_path = Some(path.to_string());
+        self.save()
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: DifficultyLevel) -> std::io::Result<()> {
+        self.set_value("engine", "difficulty", value(format!("{:?}", difficulty)))?;
+        self.difficulty = Some(difficulty);
+        self.save()
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) -> std::io::Result<()> {
+        self.set_value("appearance", "theme", value(format!("{:?}", theme)))?;
+        self.theme = Some(theme);
+        self.save()
+    }
+
+    /// Set `key` in the `[table]` of the document, adding the table if the file has none.
+    /// Fails when the file uses that name for something other than a table.
+    fn set_value(&mut self, table: &str, key: &str, item: Item) -> std::io::Result<()> {
+        let entry = self.document.entry(table).or_insert_with(toml_edit::table);
+        let Some(table_like) = entry.as_table_like_mut() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("'{}' in {} is not a table", table, Self::path().display()),
+            ));
+        };
+        table_like.insert(key, item);
+        Ok(())
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        write_atomic(&Self::path(), self.document.to_string())
+    }
+}
+
+/// The variant whose Debug name matches `name`, ignoring case
+fn find_by_name<T: Copy + std::fmt::Debug>(all: &[T], name: &str, what: &str) -> Result<T, String> {
+    all.iter()
+        .find(|v| format!("{:?}", v).eq_ignore_ascii_case(name))
+        .copied()
+        .ok_or_else(|| format!("unknown {} '{}'", what, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            "# my settings\n[engine]\ndifficulty = \"expert\"\n\n[keybindings]\nflip = \"R\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.difficulty, Some(DifficultyLevel::Expert));
+        assert_eq!(config.theme, None);
+        assert_eq!(config.keybindings.flip, Key::R);
+        assert_eq!(config.keybindings.next, Key::ArrowRight);
+        assert_eq!(config.engine_timeouts, EngineTimeouts::default());
+
+        let config = Config::parse("[engine]\nready_timeout = 3\n").unwrap();
+        assert_eq!(config.engine_timeouts.ready, std::time::Duration::from_secs(3));
+        assert!(Config::parse("[engine]\nmove_timeout = \"long\"\n").is_err());
+
+        assert!(Config::parse("[appearance]\ntheme = \"Neon\"\n").is_err());
+        assert!(Config::parse("[keybindings]\nnext = \"NoSuchKey\"\n").is_err());
+    }
+
+    #[test]
+    fn test_write_back_keeps_comments() {
+        let mut config = Config::parse("# keep me\n[engine]\ndifficulty = \"Novice\"\n").unwrap();
+        config.document["appearance"]["theme"] = value("Dark");
+        let text = config.document.to_string();
+        assert!(text.contains("# keep me"));
+        assert!(Config::parse(&text).unwrap().theme == Some(Theme::Dark));
+    }
+
+    #[test]
+    fn test_set_value_needs_a_table() {
+        let mut config = Config::parse("engine = \"stockfish\"\n").unwrap();
+        assert!(config.set_value("engine", "difficulty", value("Novice")).is_err());
+        assert!(config.set_value("appearance", "theme", value("Dark")).is_ok());
+
+        let mut config = Config::parse("engine = { path = \"stockfish\" }\n").unwrap();
+        assert!(config.set_value("engine", "difficulty", value("Novice")).is_ok());
+        assert_eq!(Config::parse(&config.document.to_string()).unwrap().difficulty, Some(DifficultyLevel::Novice));
+    }
+}
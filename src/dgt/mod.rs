@@ -0,0 +1,113 @@
+mod protocol;
+
+pub use protocol::{match_board, BoardMatch};
+
+use protocol::{Fields, MessageReader, SEND_BOARD, SEND_UPDATES};
+use shakmaty::Board;
+use std::io::{ErrorKind, Read, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+
+const BAUD_RATE: u32 = 9600;
+/// How long a read waits before checking whether the connection was closed
+const READ_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Serial ports that could have an electronic board attached
+pub fn available_ports() -> Vec<String> {
+    serialport::available_ports()
+        .map(|ports| ports.into_iter().map(|p| p.port_name).collect())
+        .unwrap_or_default()
+}
+
+enum DgtEvent {
+    Fields(Fields),
+    Error(String),
+}
+
+/// A DGT board on a serial or USB port, read on a background thread until dropped
+pub struct DgtConnection {
+    pub port: String,
+    /// Dropping the sender stops the reader thread
+    _stop_tx: mpsc::Sender<()>,
+    event_rx: mpsc::Receiver<DgtEvent>,
+    fields: Option<Fields>,
+    pub error: Option<String>,
+}
+
+impl DgtConnection {
+    pub fn open(port: String) -> Self {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (event_tx, event_rx) = mpsc::channel();
+        let path = port.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = read_board(&path, &stop_rx, &event_tx) {
+                let _ = event_tx.send(DgtEvent::Error(e));
+            }
+        });
+        Self {
+            port,
+            _stop_tx: stop_tx,
+            event_rx,
+            fields: None,
+            error: None,
+        }
+    }
+
+    /// The physical board when it changed since the last call
+    pub fn poll(&mut self, rotated: bool) -> Option<Board> {
+        let mut changed = false;
+        while let Ok(event) = self.event_rx.try_recv() {
+            match event {
+                DgtEvent::Fields(fields) => {
+                    self.fields = Some(fields);
+                    changed = true;
+                }
+                DgtEvent::Error(e) => self.error = Some(e),
+            }
+        }
+        changed.then(|| self.board(rotated)).flatten()
+    }
+
+    /// The physical board as last reported
+    pub fn board(&self, rotated: bool) -> Option<Board> {
+        self.fields.as_ref().map(|fields| fields.to_board(rotated))
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.fields.is_some() && self.error.is_none()
+    }
+}
+
+/// Ask for the whole board and then for every change, forwarding the fields after each change
+fn read_board(path: &str, stop_rx: &mpsc::Receiver<()>, events: &mpsc::Sender<DgtEvent>) -> Result<(), String> {
+    let mut port = serialport::new(path, BAUD_RATE)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .map_err(|e| e.to_string())?;
+    port.write_all(&[SEND_BOARD, SEND_UPDATES]).map_err(|e| e.to_string())?;
+
+    let mut reader = MessageReader::default();
+    let mut fields = Fields::default();
+    let mut buffer = [0u8; 256];
+    // The first dump is reported even if the board is empty, to show the board is there
+    let mut reported = false;
+    while let Err(mpsc::TryRecvError::Empty) = stop_rx.try_recv() {
+        let read = match port.read(&mut buffer) {
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::TimedOut => continue,
+            Err(e) => return Err(e.to_string()),
+        };
+        let messages = reader.push(&buffer[..read]);
+        let mut changed = false;
+        for message in &messages {
+            changed |= fields.apply(message);
+        }
+        if changed || (!reported && !messages.is_empty()) {
+            reported = true;
+            if events.send(DgtEvent::Fields(fields.clone())).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
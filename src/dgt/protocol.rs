@@ -0,0 +1,218 @@
+use shakmaty::{Board, Chess, Color, File, Move, Piece, Position, Rank, Role, Square};
+
+/// Commands sent to the board
+pub const SEND_BOARD: u8 = 0x42;
+pub const SEND_UPDATES: u8 = 0x44;
+
+/// Message ids sent by the board, without the high bit that marks a message start
+const BOARD_DUMP: u8 = 0x06;
+const FIELD_UPDATE: u8 = 0x0E;
+
+/// Header of every message: id byte and a 14-bit length that includes the header
+const HEADER_LEN: usize = 3;
+
+/// A complete message from the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Piece codes of all 64 fields, a8 first
+    BoardDump([u8; 64]),
+    /// A single field changed: (field index, piece code)
+    FieldUpdate(u8, u8),
+    /// Clock, version and other messages we don't use
+    Other,
+}
+
+/// Splits the board's byte stream into messages
+#[derive(Debug, Default)]
+pub struct MessageReader {
+    buffer: Vec<u8>,
+}
+
+impl MessageReader {
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<Message> {
+        self.buffer.extend_from_slice(bytes);
+        let mut messages = Vec::new();
+        loop {
+            // Drop noise before the next message start
+            match self.buffer.iter().position(|b| b & 0x80 != 0) {
+                Some(start) => {
+                    self.buffer.drain(..start);
+                }
+                None => {
+                    self.buffer.clear();
+                    break;
+                }
+            }
+            if self.buffer.len() < HEADER_LEN {
+                break;
+            }
+            let len = ((self.buffer[1] as usize) << 7) | self.buffer[2] as usize;
+            if len < HEADER_LEN {
+                self.buffer.remove(0);
+                continue;
+            }
+            if self.buffer.len() < len {
+                break;
+            }
+            let message: Vec<u8> = self.buffer.drain(..len).collect();
+            let body = &message[HEADER_LEN..];
+            messages.push(match message[0] & 0x7f {
+                BOARD_DUMP if body.len() == 64 => {
+                    let mut fields = [0; 64];
+                    fields.copy_from_slice(body);
+                    Message::BoardDump(fields)
+                }
+                FIELD_UPDATE if body.len() == 2 => Message::FieldUpdate(body[0], body[1]),
+                _ => Message::Other,
+            });
+        }
+        messages
+    }
+}
+
+fn piece_from_code(code: u8) -> Option<Piece> {
+    let (color, role) = match code {
+        1 => (Color::White, Role::Pawn),
+        2 => (Color::White, Role::Rook),
+        3 => (Color::White, Role::Knight),
+        4 => (Color::White, Role::Bishop),
+        5 => (Color::White, Role::King),
+        6 => (Color::White, Role::Queen),
+        7 => (Color::Black, Role::Pawn),
+        8 => (Color::Black, Role::Rook),
+        9 => (Color::Black, Role::Knight),
+        10 => (Color::Black, Role::Bishop),
+        11 => (Color::Black, Role::King),
+        12 => (Color::Black, Role::Queen),
+        _ => return None,
+    };
+    Some(Piece { color, role })
+}
+
+/// The square of a field index; fields run a8..h8, a7..h7 and so on, the other way round
+/// when the board is set up with Black at the cable end
+fn field_square(field: u8, rotated: bool) -> Square {
+    let field = if rotated { 63 - field } else { field } as u32;
+    Square::from_coords(File::new(field % 8), Rank::new(7 - field / 8))
+}
+
+/// Pieces on the physical board, kept up to date from dumps and field updates
+#[derive(Debug, Clone)]
+pub struct Fields([u8; 64]);
+
+impl Default for Fields {
+    fn default() -> Self {
+        Self([0; 64])
+    }
+}
+
+impl Fields {
+    /// Apply a message; true when the board changed
+    pub fn apply(&mut self, message: &Message) -> bool {
+        match message {
+            Message::BoardDump(fields) => {
+                let changed = self.0 != *fields;
+                self.0 = *fields;
+                changed
+            }
+            Message::FieldUpdate(field, code) if (*field as usize) < 64 => {
+                let changed = self.0[*field as usize] != *code;
+                self.0[*field as usize] = *code;
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    pub fn to_board(&self, rotated: bool) -> Board {
+        let mut board = Board::empty();
+        for (field, code) in self.0.iter().enumerate() {
+            if let Some(piece) = piece_from_code(*code) {
+                board.set_piece_at(field_square(field as u8, rotated), piece);
+            }
+        }
+        board
+    }
+}
+
+/// How the physical board relates to the game position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardMatch {
+    /// The board shows the position
+    InSync,
+    /// The board shows the position after this legal move
+    Move(Move),
+    /// Pieces are being moved, or the board was set up differently
+    Unknown,
+}
+
+/// Find the legal move, if any, that turns `position` into the physical board.
+/// Comparing whole boards handles castling, en passant and the promotion piece chosen.
+pub fn match_board(position: &Chess, physical: &Board) -> BoardMatch {
+    if position.board() == physical {
+        return BoardMatch::InSync;
+    }
+    position
+        .legal_moves()
+        .into_iter()
+        .find(|m| {
+            let mut after = position.clone();
+            after.play_unchecked(*m);
+            after.board() == physical
+        })
+        .map_or(BoardMatch::Unknown, BoardMatch::Move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Piece codes for the standard starting position, a8 first
+    fn start_fields() -> [u8; 64] {
+        let mut fields = [0; 64];
+        fields[..8].copy_from_slice(&[8, 9, 10, 12, 11, 10, 9, 8]);
+        fields[8..16].fill(7);
+        fields[48..56].fill(1);
+        fields[56..].copy_from_slice(&[2, 3, 4, 6, 5, 4, 3, 2]);
+        fields
+    }
+
+    #[test]
+    fn test_read_messages_and_match_moves() {
+        let mut reader = MessageReader::default();
+        let mut dump = vec![0x86, 0, 67];
+        dump.extend_from_slice(&start_fields());
+        // Noise, then a dump split across reads
+        assert!(reader.push(&[0x01, 0x02]).is_empty());
+        assert!(reader.push(&dump[..10]).is_empty());
+        let messages = reader.push(&dump[10..]);
+        assert_eq!(messages.len(), 1);
+
+        let mut fields = Fields::default();
+        assert!(fields.apply(&messages[0]));
+        let position = Chess::default();
+        assert_eq!(match_board(&position, &fields.to_board(false)), BoardMatch::InSync);
+
+        // e2 lifted, then put down on e4: fields 52 and 36
+        for message in reader.push(&[0x8e, 0, 5, 52, 0, 0x8e, 0, 5, 36, 1]) {
+            fields.apply(&message);
+        }
+        let BoardMatch::Move(m) = match_board(&position, &fields.to_board(false)) else {
+            panic!("e2e4 not recognized");
+        };
+        assert_eq!((m.from(), m.to()), (Some(Square::E2), Square::E4));
+
+        // Half way through a move
+        fields.apply(&Message::FieldUpdate(62, 0));
+        assert_eq!(match_board(&position, &fields.to_board(false)), BoardMatch::Unknown);
+    }
+
+    #[test]
+    fn test_rotated_board() {
+        let mut fields = Fields::default();
+        let mut rotated = start_fields();
+        rotated.reverse();
+        fields.apply(&Message::BoardDump(rotated));
+        assert_eq!(fields.to_board(true), *Chess::default().board());
+    }
+}
@@ -1,34 +1,111 @@
+use crate::engine::backend::{EngineBackend, ProcessBackend};
 use crate::engine::difficulty::DifficultyLevel;
-use anyhow::{Context, Result};
-use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use crate::engine::personality::Personality;
+use crate::error::AppError;
+use anyhow::Result;
 use std::sync::mpsc;
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long output is awaited between checks for new commands while analyzing
+const ANALYSIS_POLL: Duration = Duration::from_millis(20);
+
+/// Silence after which the answer to a console command is taken to be complete
+const CONSOLE_QUIET: Duration = Duration::from_millis(300);
+
+/// How long the actor waits for the engine before it gives up on it and restarts it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineTimeouts {
+    /// Startup, up to `uciok`
+    pub init: Duration,
+    /// `readyok` after `isready`
+    pub ready: Duration,
+    /// `bestmove` beyond the requested move time, or after `stop`
+    pub bestmove: Duration,
+}
+
+impl Default for EngineTimeouts {
+    fn default() -> Self {
+        Self {
+            init: Duration::from_secs(30),
+            ready: Duration::from_secs(10),
+            bestmove: Duration::from_secs(10),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum EngineCommand {
     Init,
     SetDifficulty(DifficultyLevel),
+    SetPersonality(Personality),
     SetMultiPV(u32),
+    /// Number of search threads, None for the engine's own default; only while the engine
+    /// isn't searching
+    SetThreads(Option<u32>),
     NewGame,
+    /// Empty the engine's hash table so the next search starts from scratch
+    ClearHash,
+    /// Search for a move to play in the position reached by playing the UCI `moves` from `fen`.
+    /// `search_id` comes back with the `BestMove`, so the caller can tell the answer to this
+    /// search from one it has since given up on.
     Go {
+        search_id: u64,
         fen: String,
         moves: Vec<String>,
         movetime_ms: Option<u64>,
     },
-    /// Start infinite analysis
+    /// Start infinite analysis of the position reached by playing `moves` from `fen`. Passing
+    /// the game's moves rather than the FEN of its latest position lets the engine reuse what
+    /// it learned about the earlier positions. A non-empty `searchmoves` limits the search to
+    /// those first moves.
     Analyze {
         fen: String,
         moves: Vec<String>,
+        searchmoves: Vec<String>,
+    },
+    /// Search each (position index, FEN) in turn for a game review, reporting every result
+    /// as it comes in. `Stop` cancels the rest.
+    Review {
+        positions: Vec<(usize, String)>,
+        movetime_ms: u64,
     },
     Stop,
     Quit,
+    /// A line typed in the UCI console, sent as is. Output up to the engine falling silent
+    /// comes back as `Output`.
+    Raw(String),
+}
+
+impl EngineCommand {
+    /// Commands that end a running `Go` search; others wait until it has finished
+    fn interrupts_search(&self) -> bool {
+        matches!(
+            self,
+            EngineCommand::NewGame
+                | EngineCommand::Go { .. }
+                | EngineCommand::Analyze { .. }
+                | EngineCommand::Review { .. }
+                | EngineCommand::Stop
+                | EngineCommand::Quit
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum EngineEvent {
+    /// Engine identity reported during the UCI handshake
+    Identified {
+        name: String,
+        author: Option<String>,
+    },
     Ready,
+    /// Answer to the `Go` with the same `search_id`, for the position `fen` and `moves` it was
+    /// asked about. Searches that were stopped don't report one.
     BestMove {
+        search_id: u64,
+        fen: String,
+        moves: Vec<String>,
         best_move: String,
         ponder: Option<String>,
     },
@@ -38,10 +115,36 @@ pub enum EngineEvent {
         score_mate: Option<i32>,
         pv: Vec<String>,
         nodes: Option<u64>,
+        nps: Option<u64>,
         time_ms: Option<u64>,
         multipv: Option<u32>, // 1-indexed line number
     },
-    Error(String),
+    /// Root move the engine is currently searching (from `currmove`)
+    CurrentMove {
+        depth: Option<u32>,
+        uci: String,
+        number: Option<u32>,
+    },
+    /// One position of a game review was searched; scores are relative to the side to move
+    ReviewProgress {
+        index: usize,
+        score_cp: Option<i32>,
+        score_mate: Option<i32>,
+        depth: Option<u32>,
+        /// The engine's line from the position, in UCI
+        pv: Vec<String>,
+    },
+    /// The review searched every position, or was cancelled partway
+    ReviewDone {
+        cancelled: bool,
+    },
+    /// A line the engine wrote to stderr that doesn't look like an error
+    Stderr(String),
+    /// A stderr line that looks like a failure. Reported, but the engine keeps searching
+    Warning(AppError),
+    /// Standard output the GUI has no use for, such as answers to console commands
+    Output(String),
+    Error(AppError),
     Terminated,
 }
 
@@ -52,55 +155,140 @@ enum EngineState {
     Idle,
     Thinking,
     Analyzing,
+    Reviewing,
     Terminated,
 }
 
+/// `Go` search in progress
+struct Search {
+    id: u64,
+    fen: String,
+    moves: Vec<String>,
+    /// When its `bestmove` is due
+    deadline: Instant,
+}
+
+/// Game review in progress: the positions still to search and the current search's score
+struct Review {
+    pending: std::collections::VecDeque<(usize, String)>,
+    movetime_ms: u64,
+    /// Position being searched and when its `bestmove` is due
+    current: Option<(usize, Instant)>,
+    score_cp: Option<i32>,
+    score_mate: Option<i32>,
+    depth: Option<u32>,
+    pv: Vec<String>,
+}
+
+/// UCI `position` command for the position reached by playing `moves` from `fen`
+fn position_command(fen: &str, moves: &[String]) -> String {
+    if moves.is_empty() {
+        format!("position fen {}", fen)
+    } else {
+        format!("position fen {} moves {}", fen, moves.join(" "))
+    }
+}
+
 pub struct EngineActor {
     cmd_rx: mpsc::Receiver<EngineCommand>,
     event_tx: mpsc::Sender<EngineEvent>,
     state: EngineState,
-    stdin: Option<BufWriter<ChildStdin>>,
-    stdout: Option<BufReader<ChildStdout>>,
-    child: Option<Child>,
+    backend: Box<dyn EngineBackend>,
+    /// The backend was started and answered the handshake
+    started: bool,
+    /// Thread count the engine starts with, from its `option name Threads` line
+    default_threads: Option<u32>,
+    /// Lines asked for with `SetMultiPV`, given again to a restarted engine
+    multipv: u32,
     difficulty: DifficultyLevel,
+    personality: Personality,
+    timeouts: EngineTimeouts,
+    review: Option<Review>,
+    search: Option<Search>,
+    /// Commands received during a `Go` search, run once it is over
+    deferred: std::collections::VecDeque<EngineCommand>,
 }
 
 impl EngineActor {
-    pub fn spawn(stockfish_path: Option<String>) -> (mpsc::Sender<EngineCommand>, mpsc::Receiver<EngineEvent>) {
-        let (cmd_tx, cmd_rx) = mpsc::channel::<EngineCommand>();
-        let (event_tx, event_rx) = mpsc::channel::<EngineEvent>();
-
+    pub fn spawn(
+        stockfish_path: Option<String>,
+        timeouts: EngineTimeouts,
+    ) -> (mpsc::Sender<EngineCommand>, mpsc::Receiver<EngineEvent>) {
         let path = stockfish_path.unwrap_or_else(|| "stockfish".to_string());
         tracing::info!("EngineActor spawn with path: {}", path);
+        Self::spawn_with(Box::new(ProcessBackend::new(path)), timeouts)
+    }
+
+    /// Run the actor on its own thread, talking UCI to `backend`
+    pub fn spawn_with(
+        backend: Box<dyn EngineBackend>,
+        timeouts: EngineTimeouts,
+    ) -> (mpsc::Sender<EngineCommand>, mpsc::Receiver<EngineEvent>) {
+        let (cmd_tx, cmd_rx) = mpsc::channel::<EngineCommand>();
+        let (event_tx, event_rx) = mpsc::channel::<EngineEvent>();
 
         thread::spawn(move || {
             let mut actor = EngineActor {
                 cmd_rx,
                 event_tx,
                 state: EngineState::Uninitialized,
-                stdin: None,
-                stdout: None,
-                child: None,
+                backend,
+                started: false,
+                default_threads: None,
+                multipv: 1,
                 difficulty: DifficultyLevel::default(),
+                personality: Personality::default(),
+                timeouts,
+                review: None,
+                search: None,
+                deferred: Default::default(),
             };
-            actor.run(path);
+            actor.run();
         });
 
         (cmd_tx, event_rx)
     }
 
-    fn run(&mut self, stockfish_path: String) {
-        tracing::info!("EngineActor run loop started for: {}", stockfish_path);
+    fn run(&mut self) {
+        tracing::info!("EngineActor run loop started");
         loop {
-            // If analyzing, check for commands without blocking
-            if self.state == EngineState::Analyzing {
+            if self.state == EngineState::Terminated {
+                break;
+            }
+            if self.state != EngineState::Thinking {
+                if let Some(cmd) = self.deferred.pop_front() {
+                    if let Err(e) = self.handle_command(cmd) {
+                        tracing::error!("Command failed: {}", e);
+                    }
+                    continue;
+                }
+            }
+
+            // While searching, check for commands without blocking
+            if matches!(self.state, EngineState::Thinking | EngineState::Analyzing | EngineState::Reviewing) {
                 match self.cmd_rx.try_recv() {
+                    Ok(cmd) if self.state == EngineState::Thinking && !cmd.interrupts_search() => {
+                        self.deferred.push_back(cmd);
+                        continue;
+                    }
                     Ok(cmd) => {
-                        if let Err(e) = self.handle_command(cmd, &stockfish_path) {
+                        if let Err(e) = self.handle_command(cmd) {
                             tracing::error!("Command failed: {}", e);
                         }
                         continue;
                     }
+                    Err(mpsc::TryRecvError::Empty) if self.state == EngineState::Thinking => {
+                        if let Err(e) = self.read_search_output() {
+                            self.report_error(e);
+                        }
+                        continue;
+                    }
+                    Err(mpsc::TryRecvError::Empty) if self.state == EngineState::Reviewing => {
+                        if let Err(e) = self.read_review_output() {
+                            self.report_error(e);
+                        }
+                        continue;
+                    }
                     Err(mpsc::TryRecvError::Empty) => {
                         // Continue reading engine output
                         if let Err(e) = self.read_analysis_output() {
@@ -128,7 +316,7 @@ impl EngineActor {
                 }
             };
 
-            if let Err(e) = self.handle_command(cmd, &stockfish_path) {
+            if let Err(e) = self.handle_command(cmd) {
                 tracing::error!("Command failed: {}", e);
             }
         }
@@ -137,89 +325,75 @@ impl EngineActor {
         let _ = self.event_tx.send(EngineEvent::Terminated);
     }
 
-    fn handle_command(&mut self, cmd: EngineCommand, stockfish_path: &str) -> Result<()> {
-        match cmd {
-            EngineCommand::Init => {
-                if let Err(e) = self.init(stockfish_path) {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
+    fn handle_command(&mut self, cmd: EngineCommand) -> Result<()> {
+        let result = match cmd {
+            EngineCommand::Init => self.init(),
             EngineCommand::SetDifficulty(level) => {
                 self.difficulty = level;
-                if let Err(e) = self.apply_difficulty() {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
-            EngineCommand::SetMultiPV(lines) => {
-                if let Err(e) = self.set_multipv(lines) {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
-            EngineCommand::NewGame => {
-                if let Err(e) = self.new_game() {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
-            EngineCommand::Go { fen, moves, movetime_ms } => {
-                if let Err(e) = self.go(&fen, &moves, movetime_ms) {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
-            EngineCommand::Analyze { fen, moves } => {
-                if let Err(e) = self.analyze(&fen, &moves) {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
-            }
-            EngineCommand::Stop => {
-                if let Err(e) = self.stop() {
-                    let _ = self.event_tx.send(EngineEvent::Error(e.to_string()));
-                }
+                self.apply_difficulty()
             }
-            EngineCommand::Quit => {
-                let _ = self.quit();
-                return Err(anyhow::anyhow!("Quit command received"));
+            EngineCommand::SetPersonality(personality) => {
+                self.personality = personality;
+                self.apply_difficulty()
             }
+            EngineCommand::SetMultiPV(lines) => self.set_multipv(lines),
+            EngineCommand::SetThreads(threads) => self.set_threads(threads),
+            EngineCommand::NewGame => self.new_game(),
+            EngineCommand::ClearHash => self.clear_hash(),
+            EngineCommand::Go { search_id, fen, moves, movetime_ms } => self.go(search_id, fen, moves, movetime_ms),
+            EngineCommand::Analyze { fen, moves, searchmoves } => self.analyze(&fen, &moves, &searchmoves),
+            EngineCommand::Review { positions, movetime_ms } => self.review(positions, movetime_ms),
+            EngineCommand::Stop => self.stop(),
+            EngineCommand::Raw(line) => self.raw(&line),
+            EngineCommand::Quit => self.quit(),
+        };
+        if let Err(e) = result {
+            self.report_error(e);
         }
         Ok(())
     }
 
-    fn init(&mut self, stockfish_path: &str) -> Result<()> {
-        tracing::info!("Initializing Stockfish at: {}", stockfish_path);
+    /// Report a failed command, ending any search or review and restarting an engine that timed out
+    fn report_error(&mut self, e: anyhow::Error) {
+        let error = AppError::from_engine(&e);
+        let timed_out = error.is_engine_timeout();
+        let _ = self.event_tx.send(EngineEvent::Error(error));
+        if self.search.take().is_some() {
+            self.state = EngineState::Idle;
+        }
+        if self.review.take().is_some() {
+            self.state = EngineState::Idle;
+            let _ = self.event_tx.send(EngineEvent::ReviewDone { cancelled: true });
+        }
+        if timed_out {
+            self.restart();
+        }
+    }
 
-        if !std::path::Path::new(stockfish_path).exists() {
-            anyhow::bail!("Stockfish binary not found at: {}", stockfish_path);
+    /// Replace an engine that stopped answering with a fresh process, which reports `Ready`
+    /// once it is up. A restart that times out as well is reported but not retried.
+    fn restart(&mut self) {
+        tracing::warn!("Restarting unresponsive engine");
+        self.backend.kill();
+        self.started = false;
+        self.state = EngineState::Uninitialized;
+        if let Err(e) = self.init() {
+            self.backend.kill();
+            self.started = false;
+            let _ = self.event_tx.send(EngineEvent::Error(AppError::from_engine(&e)));
         }
-        tracing::info!("Stockfish binary exists");
-
-        let stockfish_dir = std::path::Path::new(stockfish_path).parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| std::env::current_dir().unwrap());
-        tracing::info!("Setting working directory to: {:?}", stockfish_dir);
-
-        tracing::info!("Spawning Stockfish process...");
-        let mut child = Command::new(stockfish_path)
-            .current_dir(&stockfish_dir)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .context("Failed to spawn Stockfish process")?;
-        tracing::info!("Stockfish process spawned successfully with PID: {:?}", child.id());
-
-        let stdin = child.stdin.take().context("No stdin")?;
-        let stdout = child.stdout.take().context("No stdout")?;
-        tracing::info!("Got stdin and stdout handles");
-
-        self.stdin = Some(BufWriter::new(stdin));
-        self.stdout = Some(BufReader::new(stdout));
-        self.child = Some(child);
+    }
+
+    fn init(&mut self) -> Result<()> {
+        self.backend.start(&self.event_tx)?;
+        self.started = true;
 
         self.state = EngineState::Initializing;
         tracing::info!("Sending UCI command...");
 
         self.send_command("uci")?;
         tracing::info!("UCI command sent, waiting for uciok...");
-        self.wait_for_response("uciok")?;
+        self.read_uci_handshake()?;
         tracing::info!("Got uciok!");
 
         tracing::info!("Sending isready...");
@@ -230,6 +404,9 @@ impl EngineActor {
         self.state = EngineState::Idle;
 
         self.apply_difficulty()?;
+        if self.multipv > 1 {
+            self.set_multipv(self.multipv)?;
+        }
 
         let _ = self.event_tx.send(EngineEvent::Ready);
         tracing::info!("Stockfish initialized successfully");
@@ -238,11 +415,11 @@ impl EngineActor {
     }
 
     fn apply_difficulty(&mut self) -> Result<()> {
-        if self.stdin.is_none() {
+        if !self.started {
             return Ok(());
         }
 
-        for cmd in self.difficulty.uci_commands() {
+        for cmd in self.difficulty.uci_commands().into_iter().chain(self.personality.uci_commands()) {
             self.send_command(&cmd)?;
         }
 
@@ -253,11 +430,12 @@ impl EngineActor {
     }
 
     fn set_multipv(&mut self, lines: u32) -> Result<()> {
-        if self.stdin.is_none() {
+        let lines = lines.clamp(1, 5);
+        self.multipv = lines;
+        if !self.started {
             return Ok(());
         }
 
-        let lines = lines.clamp(1, 5);
         self.send_command(&format!("setoption name MultiPV value {}", lines))?;
         self.send_command("isready")?;
         self.wait_for_response("readyok")?;
@@ -265,60 +443,181 @@ impl EngineActor {
         Ok(())
     }
 
+    fn set_threads(&mut self, threads: Option<u32>) -> Result<()> {
+        let Some(threads) = threads.or(self.default_threads).filter(|_| self.started) else {
+            return Ok(());
+        };
+
+        self.send_command(&format!("setoption name Threads value {}", threads.max(1)))?;
+        self.send_command("isready")?;
+        self.wait_for_response("readyok")?;
+
+        Ok(())
+    }
+
     fn new_game(&mut self) -> Result<()> {
+        self.stop()?;
         self.send_command("ucinewgame")?;
         self.send_command("isready")?;
         self.wait_for_response("readyok")?;
         Ok(())
     }
 
-    fn go(&mut self, fen: &str, _moves: &[String], movetime_ms: Option<u64>) -> Result<()> {
-        let position_cmd = format!("position fen {}", fen);
-        self.send_command(&position_cmd)?;
+    fn clear_hash(&mut self) -> Result<()> {
+        if !self.started {
+            return Ok(());
+        }
 
-        let go_cmd = match movetime_ms {
-            Some(ms) => format!("go movetime {}", ms),
-            None => "go movetime 1000".to_string(),
-        };
+        self.stop()?;
+        self.send_command("setoption name Clear Hash")?;
+        self.send_command("isready")?;
+        self.wait_for_response("readyok")?;
+        Ok(())
+    }
 
-        self.state = EngineState::Thinking;
+    /// Start a search; its output is read by the run loop so a `Stop` can cut it short
+    fn go(&mut self, search_id: u64, fen: String, moves: Vec<String>, movetime_ms: Option<u64>) -> Result<()> {
+        self.stop()?;
+        self.send_command(&position_command(&fen, &moves))?;
+
+        let movetime_ms = movetime_ms.unwrap_or(1000);
+        let go_cmd = format!("go movetime {}", movetime_ms);
         self.send_command(&go_cmd)?;
 
-        self.read_until_bestmove()?;
+        let deadline = Instant::now() + Duration::from_millis(movetime_ms) + self.timeouts.bestmove;
+        self.search = Some(Search { id: search_id, fen, moves, deadline });
+        self.state = EngineState::Thinking;
+        Ok(())
+    }
 
-        self.state = EngineState::Idle;
+    fn analyze(&mut self, fen: &str, moves: &[String], searchmoves: &[String]) -> Result<()> {
+        // Stop any ongoing analysis or search first
+        self.stop()?;
+
+        self.send_command(&position_command(fen, moves))?;
+
+        self.state = EngineState::Analyzing;
+        if searchmoves.is_empty() {
+            self.send_command("go infinite")?;
+        } else {
+            self.send_command(&format!("go infinite searchmoves {}", searchmoves.join(" ")))?;
+        }
 
         Ok(())
     }
 
-    fn analyze(&mut self, fen: &str, _moves: &[String]) -> Result<()> {
-        // Stop any ongoing analysis first
-        if self.state == EngineState::Analyzing {
+    fn review(&mut self, positions: Vec<(usize, String)>, movetime_ms: u64) -> Result<()> {
+        self.stop()?;
+        self.review = Some(Review {
+            pending: positions.into(),
+            movetime_ms,
+            current: None,
+            score_cp: None,
+            score_mate: None,
+            depth: None,
+            pv: Vec::new(),
+        });
+        self.state = EngineState::Reviewing;
+        self.next_review_position()
+    }
+
+    /// Start searching the next review position, or report the review done
+    fn next_review_position(&mut self) -> Result<()> {
+        let Some(review) = self.review.as_mut() else {
+            return Ok(());
+        };
+        let Some((index, fen)) = review.pending.pop_front() else {
+            self.review = None;
+            self.state = EngineState::Idle;
+            let _ = self.event_tx.send(EngineEvent::ReviewDone { cancelled: false });
+            return Ok(());
+        };
+        let movetime_ms = review.movetime_ms;
+        review.current = Some((index, Instant::now() + Duration::from_millis(movetime_ms) + self.timeouts.bestmove));
+        review.score_cp = None;
+        review.score_mate = None;
+        review.depth = None;
+        review.pv.clear();
+
+        self.send_command(&format!("position fen {}", fen))?;
+        self.send_command(&format!("go movetime {}", movetime_ms))
+    }
+
+    /// Stop a running review, keeping the results already reported
+    fn cancel_review(&mut self) -> Result<()> {
+        let Some(review) = self.review.take() else {
+            return Ok(());
+        };
+        self.state = EngineState::Idle;
+        let _ = self.event_tx.send(EngineEvent::ReviewDone { cancelled: true });
+        if review.current.is_some() {
             self.send_command("stop")?;
-            // Drain any remaining output
             self.drain_output()?;
         }
+        Ok(())
+    }
 
-        let position_cmd = format!("position fen {}", fen);
-        self.send_command(&position_cmd)?;
+    fn read_review_output(&mut self) -> Result<()> {
+        let Some((index, deadline)) = self.review.as_ref().and_then(|r| r.current) else {
+            return self.next_review_position();
+        };
+        let Some(line) = self.backend.read_line(ANALYSIS_POLL)? else {
+            if Instant::now() >= deadline {
+                return Err(AppError::EngineTimeout {
+                    waiting_for: "bestmove".to_string(),
+                    after: Duration::from_millis(self.review.as_ref().map_or(0, |r| r.movetime_ms)) + self.timeouts.bestmove,
+                }
+                .into());
+            }
+            return Ok(());
+        };
 
-        self.state = EngineState::Analyzing;
-        self.send_command("go infinite")?;
+        let trimmed = line.trim();
+        if trimmed.starts_with("info ") {
+            // Only the main line's score is kept; review searches don't feed the analysis panel
+            if let Some(EngineEvent::Info { score_cp, score_mate, depth, pv, multipv, .. }) = Self::parse_info_line(trimmed) {
+                if let Some(review) = self.review.as_mut().filter(|_| multipv.unwrap_or(1) == 1) {
+                    if score_cp.is_some() || score_mate.is_some() {
+                        review.score_cp = score_cp;
+                        review.score_mate = score_mate;
+                        review.depth = depth.or(review.depth);
+                        if !pv.is_empty() {
+                            review.pv = pv;
+                        }
+                    }
+                }
+            }
+        } else if trimmed.starts_with("bestmove") {
+            if let Some(review) = self.review.as_mut() {
+                review.current = None;
+                let _ = self.event_tx.send(EngineEvent::ReviewProgress {
+                    index,
+                    score_cp: review.score_cp,
+                    score_mate: review.score_mate,
+                    depth: review.depth,
+                    pv: std::mem::take(&mut review.pv),
+                });
+            }
+            self.next_review_position()?;
+        }
 
         Ok(())
     }
 
     fn stop(&mut self) -> Result<()> {
+        self.cancel_review()?;
         match self.state {
             EngineState::Thinking => {
+                // The stopped search's best move is dropped, it was asked for a position the caller left
                 self.send_command("stop")?;
-                self.read_until_bestmove()?;
                 self.state = EngineState::Idle;
+                self.search = None;
+                self.drain_output()?;
             }
             EngineState::Analyzing => {
                 self.send_command("stop")?;
-                self.drain_output()?;
                 self.state = EngineState::Idle;
+                self.drain_output()?;
             }
             _ => {}
         }
@@ -326,36 +625,44 @@ impl EngineActor {
         Ok(())
     }
 
+    /// Send a console line and, unless a search is reading the output, pass on the answer
+    fn raw(&mut self, line: &str) -> Result<()> {
+        if !self.started {
+            return Err(anyhow::anyhow!("Engine is not running"));
+        }
+        self.send_command(line)?;
+        if self.state != EngineState::Idle {
+            return Ok(());
+        }
+        let deadline = Instant::now() + self.timeouts.ready;
+        while Instant::now() < deadline {
+            let Some(output) = self.backend.read_line(CONSOLE_QUIET)? else {
+                break;
+            };
+            let _ = self.event_tx.send(EngineEvent::Output(output.trim_end().to_string()));
+        }
+        Ok(())
+    }
+
+    /// Ask the engine to exit and end the actor once it has
     fn quit(&mut self) -> Result<()> {
         let _ = self.send_command("quit");
-
-        if let Some(mut child) = self.child.take() {
-            let _ = child.wait();
-        }
+        self.backend.shutdown();
+        self.state = EngineState::Terminated;
         Ok(())
     }
 
     fn send_command(&mut self, cmd: &str) -> Result<()> {
-        let stdin = self.stdin.as_mut().context("No stdin available")?;
         tracing::debug!("Sending to engine: {}", cmd);
-        writeln!(stdin, "{}", cmd)?;
-        stdin.flush()?;
-        Ok(())
+        self.backend.send(cmd)
     }
 
     fn wait_for_response(&mut self, expected: &str) -> Result<()> {
-        let stdout = self.stdout.as_mut().context("No stdout available")?;
-        let mut line = String::new();
         tracing::info!("Waiting for '{}'...", expected);
+        let deadline = Instant::now() + self.timeouts.ready;
 
         loop {
-            line.clear();
-            tracing::debug!("Reading line from engine...");
-            let n = stdout.read_line(&mut line)?;
-            tracing::debug!("Read {} bytes", n);
-            if n == 0 {
-                anyhow::bail!("Engine closed stdout unexpectedly (waiting for '{}')", expected);
-            }
+            let line = self.read_before(deadline, expected)?;
             let trimmed = line.trim();
             if !trimmed.is_empty() {
                 tracing::info!("Engine output: {}", trimmed);
@@ -368,49 +675,104 @@ impl EngineActor {
         }
     }
 
-    fn read_until_bestmove(&mut self) -> Result<()> {
-        let stdout = self.stdout.as_mut().context("No stdout available")?;
-        let mut line = String::new();
+    /// Read the response to `uci` up to `uciok`, reporting the engine's `id` lines and noting
+    /// its default thread count
+    fn read_uci_handshake(&mut self) -> Result<()> {
+        let mut name = None;
+        let mut author = None;
+        let deadline = Instant::now() + self.timeouts.init;
 
         loop {
-            line.clear();
-            let n = stdout.read_line(&mut line)?;
-            if n == 0 {
-                anyhow::bail!("Engine closed stdout unexpectedly");
-            }
+            let line = self.read_before(deadline, "uciok")?;
             let trimmed = line.trim();
             tracing::debug!("Engine: {}", trimmed);
 
-            if trimmed.starts_with("info ") {
-                if let Some(event) = Self::parse_info_line(trimmed) {
-                    let _ = self.event_tx.send(event);
+            if let Some(value) = trimmed.strip_prefix("id name ") {
+                name = Some(value.to_string());
+            } else if let Some(value) = trimmed.strip_prefix("id author ") {
+                author = Some(value.to_string());
+            } else if let Some(option) = trimmed.strip_prefix("option name Threads ") {
+                self.default_threads = option
+                    .split_whitespace()
+                    .skip_while(|&word| word != "default")
+                    .nth(1)
+                    .and_then(|value| value.parse().ok());
+            } else if trimmed.starts_with("uciok") {
+                break;
+            }
+        }
+
+        if let Some(name) = name {
+            let _ = self.event_tx.send(EngineEvent::Identified { name, author });
+        }
+        Ok(())
+    }
+
+    /// Next output line, failing with `AppError::EngineTimeout` once `deadline` passes without one
+    fn read_before(&mut self, deadline: Instant, waiting_for: &str) -> Result<String> {
+        let started = Instant::now();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if let Some(line) = self.backend.read_line(remaining)? {
+                return Ok(line);
+            }
+            if Instant::now() >= deadline {
+                return Err(AppError::EngineTimeout {
+                    waiting_for: waiting_for.to_string(),
+                    after: deadline.saturating_duration_since(started),
                 }
-            } else if trimmed.starts_with("bestmove ") {
-                let parts: Vec<&str> = trimmed.split_whitespace().collect();
-                let best_move = parts.get(1).unwrap_or(&"").to_string();
-                let ponder = if parts.len() >= 4 && parts[2] == "ponder" {
-                    Some(parts[3].to_string())
-                } else {
-                    None
-                };
-                let _ = self.event_tx.send(EngineEvent::BestMove { best_move, ponder });
-                return Ok(());
+                .into());
+            }
+        }
+    }
+
+    /// Forward the running search's output, up to its `bestmove`
+    fn read_search_output(&mut self) -> Result<()> {
+        let Some(deadline) = self.search.as_ref().map(|search| search.deadline) else {
+            self.state = EngineState::Idle;
+            return Ok(());
+        };
+        let Some(line) = self.backend.read_line(ANALYSIS_POLL)? else {
+            if Instant::now() >= deadline {
+                return Err(AppError::EngineTimeout {
+                    waiting_for: "bestmove".to_string(),
+                    after: self.timeouts.bestmove,
+                }
+                .into());
+            }
+            return Ok(());
+        };
+
+        let trimmed = line.trim();
+        tracing::debug!("Engine: {}", trimmed);
+        if trimmed.starts_with("info ") {
+            if let Some(event) = Self::parse_info_line(trimmed) {
+                let _ = self.event_tx.send(event);
+            }
+        } else if trimmed.starts_with("bestmove ") {
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            let best_move = parts.get(1).unwrap_or(&"").to_string();
+            let ponder = if parts.len() >= 4 && parts[2] == "ponder" {
+                Some(parts[3].to_string())
+            } else {
+                None
+            };
+            self.state = EngineState::Idle;
+            if let Some(Search { id, fen, moves, .. }) = self.search.take() {
+                let _ = self.event_tx.send(EngineEvent::BestMove { search_id: id, fen, moves, best_move, ponder });
             }
+        } else if !trimmed.is_empty() {
+            let _ = self.event_tx.send(EngineEvent::Output(trimmed.to_string()));
         }
+
+        Ok(())
     }
 
     fn read_analysis_output(&mut self) -> Result<()> {
-        let stdout = self.stdout.as_mut().context("No stdout available")?;
-        let mut line = String::new();
-
-        // Non-blocking read attempt - use a small timeout by reading what's available
-        // Since BufReader doesn't have non-blocking, we check if there's data
-        line.clear();
-        let n = stdout.read_line(&mut line)?;
-        
-        if n == 0 {
+        // Wait briefly for output, so new commands are still picked up promptly
+        let Some(line) = self.backend.read_line(ANALYSIS_POLL)? else {
             return Ok(()); // No data available
-        }
+        };
 
         let trimmed = line.trim();
         if trimmed.starts_with("info ") {
@@ -420,29 +782,21 @@ impl EngineActor {
         } else if trimmed.starts_with("bestmove ") {
             // Analysis was stopped
             self.state = EngineState::Idle;
+        } else if !trimmed.is_empty() {
+            let _ = self.event_tx.send(EngineEvent::Output(trimmed.to_string()));
         }
 
         Ok(())
     }
 
     fn drain_output(&mut self) -> Result<()> {
-        let stdout = self.stdout.as_mut().context("No stdout available")?;
-        let mut line = String::new();
-
-        // Read until we get bestmove or no more data
-        for _ in 0..100 { // Safety limit
-            line.clear();
-            match stdout.read_line(&mut line) {
-                Ok(0) => break,
-                Ok(_) => {
-                    if line.trim().starts_with("bestmove ") {
-                        break;
-                    }
-                }
-                Err(_) => break,
+        // Read until we get bestmove, which ends the stopped search
+        let deadline = Instant::now() + self.timeouts.bestmove;
+        loop {
+            if self.read_before(deadline, "bestmove")?.trim().starts_with("bestmove ") {
+                return Ok(());
             }
         }
-        Ok(())
     }
 
     fn parse_info_line(line: &str) -> Option<EngineEvent> {
@@ -453,55 +807,50 @@ impl EngineActor {
         let mut score_mate = None;
         let mut pv = Vec::new();
         let mut nodes = None;
+        let mut nps = None;
         let mut time_ms = None;
         let mut multipv = None;
+        let mut currmove = None;
+        let mut currmovenumber = None;
 
         let mut i = 1;
         while i < parts.len() {
             match parts[i] {
-                "depth" => {
-                    if i + 1 < parts.len() {
-                        depth = parts[i + 1].parse().ok();
-                        i += 2;
-                    } else {
-                        i += 1;
-                    }
+                "depth" if i + 1 < parts.len() => {
+                    depth = parts[i + 1].parse().ok();
+                    i += 2;
                 }
-                "multipv" => {
-                    if i + 1 < parts.len() {
-                        multipv = parts[i + 1].parse().ok();
-                        i += 2;
-                    } else {
-                        i += 1;
-                    }
+                "multipv" if i + 1 < parts.len() => {
+                    multipv = parts[i + 1].parse().ok();
+                    i += 2;
                 }
-                "score" => {
-                    if i + 2 < parts.len() {
-                        match parts[i + 1] {
-                            "cp" => score_cp = parts[i + 2].parse().ok(),
-                            "mate" => score_mate = parts[i + 2].parse().ok(),
-                            _ => {}
-                        }
-                        i += 3;
-                    } else {
-                        i += 1;
+                "score" if i + 2 < parts.len() => {
+                    match parts[i + 1] {
+                        "cp" => score_cp = parts[i + 2].parse().ok(),
+                        "mate" => score_mate = parts[i + 2].parse().ok(),
+                        _ => {}
                     }
+                    i += 3;
                 }
-                "nodes" => {
-                    if i + 1 < parts.len() {
-                        nodes = parts[i + 1].parse().ok();
-                        i += 2;
-                    } else {
-                        i += 1;
-                    }
+                "nodes" if i + 1 < parts.len() => {
+                    nodes = parts[i + 1].parse().ok();
+                    i += 2;
                 }
-                "time" => {
-                    if i + 1 < parts.len() {
-                        time_ms = parts[i + 1].parse().ok();
-                        i += 2;
-                    } else {
-                        i += 1;
-                    }
+                "nps" if i + 1 < parts.len() => {
+                    nps = parts[i + 1].parse().ok();
+                    i += 2;
+                }
+                "time" if i + 1 < parts.len() => {
+                    time_ms = parts[i + 1].parse().ok();
+                    i += 2;
+                }
+                "currmove" if i + 1 < parts.len() => {
+                    currmove = Some(parts[i + 1].to_string());
+                    i += 2;
+                }
+                "currmovenumber" if i + 1 < parts.len() => {
+                    currmovenumber = parts[i + 1].parse().ok();
+                    i += 2;
                 }
                 "pv" => {
                     i += 1;
@@ -516,6 +865,15 @@ impl EngineActor {
             }
         }
 
+        // Progress lines carry no score or PV, only the move being searched
+        if let Some(uci) = currmove.filter(|_| pv.is_empty() && score_cp.is_none() && score_mate.is_none()) {
+            return Some(EngineEvent::CurrentMove {
+                depth,
+                uci,
+                number: currmovenumber,
+            });
+        }
+
         if depth.is_some() || score_cp.is_some() || score_mate.is_some() || !pv.is_empty() {
             Some(EngineEvent::Info {
                 depth,
@@ -523,6 +881,7 @@ impl EngineActor {
                 score_mate,
                 pv,
                 nodes,
+                nps,
                 time_ms,
                 multipv,
             })
@@ -531,3 +890,212 @@ impl EngineActor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::backend::MockBackend;
+    use std::time::Duration;
+
+    const FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    /// Short timeouts so a silent mock fails fast
+    const TIMEOUTS: EngineTimeouts = EngineTimeouts {
+        init: Duration::from_millis(200),
+        ready: Duration::from_millis(200),
+        bestmove: Duration::from_millis(50),
+    };
+
+    /// Events until one matches `done`, failing the test if the actor goes quiet
+    fn events_until(rx: &mpsc::Receiver<EngineEvent>, done: impl Fn(&EngineEvent) -> bool) -> Vec<EngineEvent> {
+        let mut events = Vec::new();
+        loop {
+            let event = rx.recv_timeout(Duration::from_secs(5)).expect("engine went quiet");
+            let finished = done(&event);
+            events.push(event);
+            if finished {
+                return events;
+            }
+        }
+    }
+
+    fn go(movetime_ms: u64) -> EngineCommand {
+        EngineCommand::Go {
+            search_id: movetime_ms,
+            fen: FEN.to_string(),
+            moves: Vec::new(),
+            movetime_ms: Some(movetime_ms),
+        }
+    }
+
+    #[test]
+    fn test_init_and_play() {
+        let backend = MockBackend::new().on("go", &["info depth 12 score cp 31 time 15 pv e2e4 e7e5", "bestmove e2e4 ponder e7e5"]);
+        let sent = backend.sent.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+
+        tx.send(EngineCommand::Init).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+        assert!(matches!(&events[0], EngineEvent::Identified { name, .. } if name == "Mock"));
+
+        tx.send(go(500)).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::BestMove { .. }));
+        assert!(matches!(&events[0], EngineEvent::Info { depth: Some(12), score_cp: Some(31), time_ms: Some(15), .. }));
+        assert!(matches!(&events[1], EngineEvent::BestMove { search_id: 500, fen, moves, best_move, ponder: Some(_) } if best_move == "e2e4" && fen == FEN && moves.is_empty()));
+        assert!(sent.lock().unwrap().contains(&format!("position fen {}", FEN)));
+        assert!(sent.lock().unwrap().contains(&"go movetime 500".to_string()));
+    }
+
+    #[test]
+    fn test_console_command() {
+        let backend = MockBackend::new().on("eval", &["Final evaluation       +0.25 (white side)"]);
+        let sent = backend.sent.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        tx.send(EngineCommand::Raw("eval".to_string())).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Output(_)));
+        assert!(matches!(events.last(), Some(EngineEvent::Output(line)) if line.starts_with("Final evaluation")));
+        assert!(sent.lock().unwrap().contains(&"eval".to_string()));
+    }
+
+    #[test]
+    fn test_analysis_until_stopped() {
+        let backend = MockBackend::new()
+            .on("go infinite", &["info depth 1 multipv 1 score cp 20 pv d2d4", "info depth 1 multipv 2 score mate 3 pv e2e4"])
+            .on("stop", &["bestmove d2d4"]);
+        let sent = backend.sent.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        let moves = vec!["e2e4".to_string(), "c7c5".to_string()];
+        tx.send(EngineCommand::Analyze { fen: FEN.to_string(), moves, searchmoves: Vec::new() }).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Info { multipv: Some(2), .. }));
+        assert!(matches!(events.last(), Some(EngineEvent::Info { score_mate: Some(3), .. })));
+
+        // Stopping drains the final bestmove instead of reporting it as a move
+        tx.send(EngineCommand::Stop).unwrap();
+        tx.send(EngineCommand::NewGame).unwrap();
+        drop(tx);
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Terminated));
+        assert!(!events.iter().any(|e| matches!(e, EngineEvent::BestMove { .. })));
+        assert!(sent.lock().unwrap().contains(&"ucinewgame".to_string()));
+        assert!(sent.lock().unwrap().contains(&format!("position fen {} moves e2e4 c7c5", FEN)));
+    }
+
+    #[test]
+    fn test_quit_ends_actor() {
+        let backend = MockBackend::new();
+        let sent = backend.sent.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        // The actor ends without waiting for the app to hang up
+        tx.send(EngineCommand::Quit).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Terminated));
+        assert!(!events.iter().any(|e| matches!(e, EngineEvent::Error(_))));
+        assert_eq!(sent.lock().unwrap().last().map(String::as_str), Some("quit"));
+    }
+
+    #[test]
+    fn test_restarts_after_timeout() {
+        // The first search never finishes; the engine is restarted and answers afterwards
+        let backend = MockBackend::new().on("go movetime 7", &[]).on("go", &["bestmove g1f3"]);
+        let kills = backend.kills.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        tx.send(go(7)).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+        assert!(matches!(&events[0], EngineEvent::Error(AppError::EngineTimeout { waiting_for, .. }) if waiting_for == "bestmove"));
+        assert_eq!(kills.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        tx.send(go(500)).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::BestMove { .. } | EngineEvent::Error(_)));
+        assert!(matches!(events.last(), Some(EngineEvent::BestMove { best_move, .. }) if best_move == "g1f3"));
+    }
+
+    #[test]
+    fn test_restart_keeps_multipv() {
+        let backend = MockBackend::new().on("go movetime 7", &[]);
+        let sent = backend.sent.clone();
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+        tx.send(EngineCommand::SetMultiPV(3)).unwrap();
+
+        tx.send(go(7)).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+        let sent = sent.lock().unwrap();
+        let multipv: Vec<_> = sent.iter().filter(|c| c.as_str() == "setoption name MultiPV value 3").collect();
+        assert_eq!(multipv.len(), 2);
+        assert!(sent.iter().rposition(|c| c == "uci") < sent.iter().rposition(|c| c == "setoption name MultiPV value 3"));
+    }
+
+    #[test]
+    fn test_stopped_search_reports_no_move() {
+        // The first search only ends when stopped; its best move must not be reported
+        let backend = MockBackend::new()
+            .on("go movetime 60000", &["info depth 3 score cp 5 pv a2a3"])
+            .on("stop", &["bestmove a2a3"])
+            .on("go", &["bestmove d2d4"]);
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        tx.send(go(60_000)).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Info { .. }));
+        tx.send(EngineCommand::Stop).unwrap();
+        tx.send(go(500)).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::BestMove { .. } | EngineEvent::Error(_)));
+        assert!(matches!(events.last(), Some(EngineEvent::BestMove { search_id: 500, best_move, .. }) if best_move == "d2d4"));
+    }
+
+    #[test]
+    fn test_review_reports_each_position() {
+        let backend = MockBackend::new().on("go movetime 40", &["info depth 9 score cp -12 pv e7e5", "bestmove e7e5"]);
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        let positions = vec![(3, FEN.to_string()), (5, FEN.to_string())];
+        tx.send(EngineCommand::Review { positions, movetime_ms: 40 }).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::ReviewDone { .. }));
+        let indices: Vec<usize> = events
+            .iter()
+            .filter_map(|e| match e {
+                EngineEvent::ReviewProgress { index, score_cp: Some(-12), depth: Some(9), pv, .. } if pv == &["e7e5"] => Some(*index),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(indices, vec![3, 5]);
+        assert!(matches!(events.last(), Some(EngineEvent::ReviewDone { cancelled: false })));
+        assert!(!events.iter().any(|e| matches!(e, EngineEvent::Info { .. } | EngineEvent::BestMove { .. })));
+    }
+
+    #[test]
+    fn test_review_cancel_keeps_finished_positions() {
+        // The second position's search never finishes on its own
+        let second = "8/8/8/4k3/8/8/8/4K2R w K - 0 1";
+        let backend = MockBackend::new()
+            .on(&format!("position fen {}", FEN), &["info depth 5 score mate 2 pv e2e4", "bestmove e2e4"])
+            .on("stop", &["bestmove h1h8"]);
+        let (tx, rx) = EngineActor::spawn_with(Box::new(backend), TIMEOUTS);
+        tx.send(EngineCommand::Init).unwrap();
+        events_until(&rx, |e| matches!(e, EngineEvent::Ready));
+
+        let positions = vec![(0, FEN.to_string()), (1, second.to_string())];
+        tx.send(EngineCommand::Review { positions, movetime_ms: 60_000 }).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::ReviewProgress { .. }));
+        assert!(matches!(events.last(), Some(EngineEvent::ReviewProgress { index: 0, score_mate: Some(2), .. })));
+
+        tx.send(EngineCommand::Stop).unwrap();
+        let events = events_until(&rx, |e| matches!(e, EngineEvent::ReviewDone { .. }));
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], EngineEvent::ReviewDone { cancelled: true }));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::engine::EngineEvent;
+use crate::error::AppError;
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long an engine gets to exit after `quit` before it is killed
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Words that make a stderr line worth reporting as an error rather than just logging it
+const NOTABLE_STDERR: [&str; 10] = [
+    "error", "fatal", "panic", "exception", "abort", "failed", "cannot", "unable", "invalid", "not found",
+];
+
+/// The other end of the actor's UCI conversation: commands go in line by line and the
+/// engine's output comes back line by line
+pub trait EngineBackend: Send {
+    /// Start the engine so it accepts commands; anything it reports outside the UCI
+    /// conversation goes to `events`
+    fn start(&mut self, events: &mpsc::Sender<EngineEvent>) -> Result<()>;
+    fn send(&mut self, line: &str) -> Result<()>;
+    /// Next output line without its line ending, or `None` if there was none within
+    /// `timeout`. Fails once the engine has closed its output.
+    fn read_line(&mut self, timeout: Duration) -> Result<Option<String>>;
+    /// Wait for the engine to exit after `quit`, killing it if it doesn't
+    fn shutdown(&mut self);
+    /// End an engine that stopped answering
+    fn kill(&mut self);
+}
+
+/// A UCI engine running as a child process
+pub struct ProcessBackend {
+    path: String,
+    stdin: Option<BufWriter<ChildStdin>>,
+    /// Lines read from stdout on a separate thread, so reads can time out
+    stdout: Option<mpsc::Receiver<String>>,
+    child: Option<Child>,
+}
+
+impl ProcessBackend {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            stdin: None,
+            stdout: None,
+            child: None,
+        }
+    }
+}
+
+impl EngineBackend for ProcessBackend {
+    fn start(&mut self, events: &mpsc::Sender<EngineEvent>) -> Result<()> {
+        tracing::info!("Initializing Stockfish at: {}", self.path);
+
+        if !std::path::Path::new(&self.path).exists() {
+            return Err(AppError::EngineNotFound { path: self.path.clone() }.into());
+        }
+        tracing::info!("Stockfish binary exists");
+
+        let stockfish_dir = std::path::Path::new(&self.path).parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| std::env::current_dir().unwrap());
+        tracing::info!("Setting working directory to: {:?}", stockfish_dir);
+
+        tracing::info!("Spawning Stockfish process...");
+        let mut child = Command::new(&self.path)
+            .current_dir(&stockfish_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::EngineStart { path: self.path.clone(), reason: e.to_string() })?;
+        tracing::info!("Stockfish process spawned successfully with PID: {:?}", child.id());
+        super::processes::register(child.id(), &self.path);
+
+        let stdin = child.stdin.take().context("No stdin")?;
+        let stdout = child.stdout.take().context("No stdout")?;
+        let stderr = child.stderr.take().context("No stderr")?;
+        tracing::info!("Got stdin and stdout handles");
+
+        // Engines often explain on stderr why they are about to die
+        let events = events.clone();
+        std::thread::spawn(move || forward_stderr(BufReader::new(stderr), &events));
+
+        let (line_tx, line_rx) = mpsc::channel();
+        std::thread::spawn(move || read_stdout(BufReader::new(stdout), &line_tx));
+
+        self.stdin = Some(BufWriter::new(stdin));
+        self.stdout = Some(line_rx);
+        self.child = Some(child);
+        Ok(())
+    }
+
+    fn send(&mut self, line: &str) -> Result<()> {
+        let stdin = self.stdin.as_mut().context("No stdin available")?;
+        // Writing only fails once the engine has exited
+        writeln!(stdin, "{}", line).and_then(|()| stdin.flush()).map_err(|_| AppError::EngineClosed)?;
+        Ok(())
+    }
+
+    fn read_line(&mut self, timeout: Duration) -> Result<Option<String>> {
+        let stdout = self.stdout.as_ref().context("No stdout available")?;
+        match stdout.recv_timeout(timeout) {
+            Ok(line) => Ok(Some(line)),
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(None),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(AppError::EngineClosed.into()),
+        }
+    }
+
+    fn shutdown(&mut self) {
+        let Some(child) = self.child.as_mut() else {
+            return;
+        };
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while Instant::now() < deadline {
+            match child.try_wait() {
+                Ok(Some(_)) | Err(_) => {
+                    super::processes::unregister(child.id());
+                    self.child = None;
+                    return;
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            }
+        }
+        tracing::warn!("Engine did not exit within {} s of quit; killing it", SHUTDOWN_TIMEOUT.as_secs());
+        self.kill();
+    }
+
+    fn kill(&mut self) {
+        self.stdin = None;
+        self.stdout = None;
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+            super::processes::unregister(child.id());
+        }
+    }
+}
+
+/// An engine left running when its actor goes away would outlive the app
+impl Drop for ProcessBackend {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Forward stdout lines until the engine closes it, which disconnects the channel
+fn read_stdout(stdout: impl BufRead, lines: &mpsc::Sender<String>) {
+    for line in stdout.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if lines.send(line.trim_end().to_string()).is_err() {
+            break;
+        }
+    }
+}
+
+fn is_notable(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    NOTABLE_STDERR.iter().any(|word| line.contains(word))
+}
+
+/// Send stderr lines on as they come, notable ones as warnings and the rest for the log,
+/// until the engine closes stderr
+fn forward_stderr(stderr: impl BufRead, events: &mpsc::Sender<EngineEvent>) {
+    for line in stderr.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let line = line.trim_end().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        tracing::warn!("Engine stderr: {}", line);
+        let event = if is_notable(&line) { EngineEvent::Warning(AppError::EngineReported(line)) } else { EngineEvent::Stderr(line) };
+        if events.send(event).is_err() {
+            break;
+        }
+    }
+}
+
+/// An in-process engine that answers commands with scripted output, for tests. Each command
+/// is answered by the first rule whose prefix it starts with; `uci` and `isready` get a
+/// minimal handshake unless a rule says otherwise. Reads wait out their timeout once the
+/// replies are read.
+#[cfg(test)]
+pub struct MockBackend {
+    rules: Vec<(String, Vec<String>)>,
+    output: std::collections::VecDeque<String>,
+    /// Every command sent, for assertions
+    pub sent: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    /// Times the engine was killed
+    pub kills: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[cfg(test)]
+impl MockBackend {
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            output: Default::default(),
+            sent: Default::default(),
+            kills: Default::default(),
+        }
+    }
+
+    /// Answer commands starting with `prefix` with `replies`
+    pub fn on(mut self, prefix: &str, replies: &[&str]) -> Self {
+        self.rules.push((prefix.to_string(), replies.iter().map(|r| r.to_string()).collect()));
+        self
+    }
+}
+
+#[cfg(test)]
+impl EngineBackend for MockBackend {
+    fn start(&mut self, _events: &mpsc::Sender<EngineEvent>) -> Result<()> {
+        Ok(())
+    }
+
+    fn send(&mut self, line: &str) -> Result<()> {
+        self.sent.lock().unwrap().push(line.to_string());
+        let replies = match self.rules.iter().find(|(prefix, _)| line.starts_with(prefix.as_str())) {
+            Some((_, replies)) => replies.clone(),
+            None if line == "uci" => vec!["id name Mock".to_string(), "uciok".to_string()],
+            None if line == "isready" => vec!["readyok".to_string()],
+            None => Vec::new(),
+        };
+        self.output.extend(replies);
+        Ok(())
+    }
+
+    fn read_line(&mut self, timeout: Duration) -> Result<Option<String>> {
+        let line = self.output.pop_front();
+        if line.is_none() {
+            std::thread::sleep(timeout);
+        }
+        Ok(line)
+    }
+
+    fn shutdown(&mut self) {}
+
+    fn kill(&mut self) {
+        self.output.clear();
+        self.kills.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_stderr() {
+        let (tx, rx) = mpsc::channel();
+        let stderr = "NNUE file loaded\n\nError: cannot open weights.bin\n";
+        forward_stderr(std::io::Cursor::new(stderr), &tx);
+        let events: Vec<_> = rx.try_iter().collect();
+        assert!(matches!(&events[..], [EngineEvent::Stderr(info), EngineEvent::Warning(AppError::EngineReported(error))]
+            if info == "NNUE file loaded" && error.contains("weights.bin")));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::i18n::tr;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,13 +27,13 @@ impl DifficultyLevel {
 
     pub fn label(&self) -> &'static str {
         match self {
-            DifficultyLevel::Novice => "Novice (~1100)",
-            DifficultyLevel::Beginner => "Beginner (~1350)",
-            DifficultyLevel::Casual => "Casual (~1500)",
-            DifficultyLevel::Intermediate => "Intermediate (~1800)",
-            DifficultyLevel::Advanced => "Advanced (~2100)",
-            DifficultyLevel::Expert => "Expert (~2500)",
-            DifficultyLevel::Maximum => "Maximum Strength",
+            DifficultyLevel::Novice => tr("difficulty.novice"),
+            DifficultyLevel::Beginner => tr("difficulty.beginner"),
+            DifficultyLevel::Casual => tr("difficulty.casual"),
+            DifficultyLevel::Intermediate => tr("difficulty.intermediate"),
+            DifficultyLevel::Advanced => tr("difficulty.advanced"),
+            DifficultyLevel::Expert => tr("difficulty.expert"),
+            DifficultyLevel::Maximum => tr("difficulty.maximum"),
         }
     }
 
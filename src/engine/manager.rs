@@ -0,0 +1,126 @@
+use super::{EngineCommand, EngineEvent};
+use std::sync::mpsc;
+use std::time::Instant;
+
+/// An engine kept running for a role that isn't in use, with what it has reported since
+pub struct IdleEngine {
+    pub path: String,
+    pub cmd_tx: mpsc::Sender<EngineCommand>,
+    pub event_rx: mpsc::Receiver<EngineEvent>,
+    pub ready: bool,
+    pub name: Option<String>,
+    /// Thread count last sent to it, None for the engine's own default
+    pub threads: Option<u32>,
+}
+
+/// The engines of the roles not in use. Switching between Game mode, analysis and reviews
+/// picks up the engine left running for the role instead of starting it again.
+#[derive(Default)]
+pub struct EngineManager {
+    idle: Vec<IdleEngine>,
+}
+
+impl EngineManager {
+    /// Keep `engine` running idle, stopping whatever it was searching
+    pub fn park(&mut self, engine: IdleEngine) {
+        let _ = engine.cmd_tx.send(EngineCommand::Stop);
+        self.idle.push(engine);
+    }
+
+    /// Take back the idle engine running `path`, if there is one
+    pub fn take(&mut self, path: &str) -> Option<IdleEngine> {
+        self.poll();
+        let index = self.idle.iter().position(|engine| engine.path == path)?;
+        Some(self.idle.swap_remove(index))
+    }
+
+    /// Keep up with what the idle engines report: note when they are ready, drop what their
+    /// stopped searches still send, and forget the ones that have exited
+    pub fn poll(&mut self) {
+        self.idle.retain_mut(|engine| loop {
+            match engine.event_rx.try_recv() {
+                Ok(EngineEvent::Identified { name, .. }) => engine.name = Some(name),
+                Ok(EngineEvent::Ready) => {
+                    engine.ready = true;
+                    engine.threads = None;
+                }
+                Ok(EngineEvent::Terminated) | Err(mpsc::TryRecvError::Disconnected) => {
+                    tracing::warn!("Idle engine {} exited", engine.path);
+                    break false;
+                }
+                Ok(_) => {}
+                Err(mpsc::TryRecvError::Empty) => break true,
+            }
+        });
+    }
+
+    /// Quit the idle engines whose binaries no role uses any more
+    pub fn retain(&mut self, in_use: &[String]) {
+        self.idle.retain(|engine| {
+            let used = in_use.contains(&engine.path);
+            if !used {
+                tracing::info!("Quitting idle engine {}", engine.path);
+                let _ = engine.cmd_tx.send(EngineCommand::Quit);
+            }
+            used
+        });
+    }
+
+    /// Quit every idle engine, waiting until `deadline` for their processes to end
+    pub fn quit_all(&mut self, deadline: Instant) {
+        for engine in &self.idle {
+            let _ = engine.cmd_tx.send(EngineCommand::Quit);
+        }
+        for engine in self.idle.drain(..) {
+            while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                match engine.event_rx.recv_timeout(left) {
+                    Ok(EngineEvent::Terminated) | Err(_) => break,
+                    Ok(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::backend::MockBackend;
+    use crate::engine::{EngineActor, EngineTimeouts};
+    use std::time::Duration;
+
+    fn idle_mock(path: &str) -> IdleEngine {
+        let (cmd_tx, event_rx) = EngineActor::spawn_with(Box::new(MockBackend::new()), EngineTimeouts::default());
+        cmd_tx.send(EngineCommand::Init).unwrap();
+        IdleEngine {
+            path: path.to_string(),
+            cmd_tx,
+            event_rx,
+            ready: false,
+            name: None,
+            threads: Some(2),
+        }
+    }
+
+    #[test]
+    fn test_engine_manager() {
+        let mut engines = EngineManager::default();
+        engines.park(idle_mock("analysis"));
+        engines.park(idle_mock("review"));
+        assert!(engines.take("game").is_none());
+
+        // The idle engine keeps starting up while parked
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !engines.idle.iter().all(|engine| engine.ready) {
+            assert!(Instant::now() < deadline, "idle engines never became ready");
+            std::thread::sleep(Duration::from_millis(10));
+            engines.poll();
+        }
+        let analysis = engines.take("analysis").unwrap();
+        assert_eq!(analysis.name.as_deref(), Some("Mock"));
+        assert_eq!(analysis.threads, None);
+
+        engines.retain(&["analysis".to_string()]);
+        assert!(engines.idle.is_empty());
+    }
+}
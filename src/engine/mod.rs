@@ -1,5 +1,16 @@
 mod actor;
+mod backend;
 mod difficulty;
+mod manager;
+mod personality;
+mod processes;
+mod roles;
+mod variety;
 
-pub use actor::{EngineActor, EngineCommand, EngineEvent};
+pub use actor::{EngineActor, EngineCommand, EngineEvent, EngineTimeouts};
 pub use difficulty::DifficultyLevel;
+pub use manager::{EngineManager, IdleEngine};
+pub use personality::Personality;
+pub use processes::clean_up_orphans;
+pub use roles::{EngineRole, EngineRoles};
+pub use variety::{varied_move, VarietySettings, VARIETY_LINES};
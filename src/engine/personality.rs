@@ -0,0 +1,214 @@
+use crate::game::GamePhase;
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use shakmaty::{uci::UciMove, Chess, Color, Move, Position, Rank, Role};
+
+/// Engine moves during which a personality prefers the moves of its own opening book
+const BOOK_MOVES: usize = 4;
+/// Bonus, in centipawns, for a move from the personality's opening book
+const BOOK_BONUS: i32 = 30;
+
+/// Playing style of the Game mode engine: option presets plus a bias in how it picks among
+/// the near-equal lines of a multi-line search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Personality {
+    /// Plays the engine's best move
+    #[default]
+    Standard,
+    /// Seeks checks, captures and open games, and avoids draws
+    Aggressive,
+    /// Prefers quiet moves, castling and closed openings
+    Positional,
+    /// Trades pieces off and brings the king forward to win long endgames
+    EndgameGrinder,
+}
+
+impl Personality {
+    pub fn all() -> &'static [Personality] {
+        &[
+            Personality::Standard,
+            Personality::Aggressive,
+            Personality::Positional,
+            Personality::EndgameGrinder,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Personality::Standard => tr("personality.standard"),
+            Personality::Aggressive => tr("personality.aggressive"),
+            Personality::Positional => tr("personality.positional"),
+            Personality::EndgameGrinder => tr("personality.grinder"),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Personality::Standard => tr("personality.standard_description"),
+            Personality::Aggressive => tr("personality.aggressive_description"),
+            Personality::Positional => tr("personality.positional_description"),
+            Personality::EndgameGrinder => tr("personality.grinder_description"),
+        }
+    }
+
+    /// Face shown next to the personality's name
+    pub fn avatar(&self) -> &'static str {
+        match self {
+            Personality::Standard => "♞",
+            Personality::Aggressive => "🔥",
+            Personality::Positional => "🏰",
+            Personality::EndgameGrinder => "🐢",
+        }
+    }
+
+    /// Returns the UCI commands that set the engine's options for this personality. Engines
+    /// without a Contempt option ignore it.
+    pub fn uci_commands(&self) -> Vec<String> {
+        let contempt = match self {
+            Personality::Standard | Personality::Positional => 0,
+            Personality::Aggressive => 40,
+            Personality::EndgameGrinder => 20,
+        };
+        vec![format!("setoption name Contempt value {}", contempt)]
+    }
+
+    /// Whether the engine searches several lines to pick its move from
+    pub fn is_biased(&self) -> bool {
+        *self != Personality::Standard
+    }
+
+    /// How far below the best line, in centipawns, a move may score and still be picked
+    fn margin_cp(&self) -> i32 {
+        match self {
+            Personality::Standard => 0,
+            Personality::Aggressive => 40,
+            Personality::Positional => 25,
+            Personality::EndgameGrinder => 30,
+        }
+    }
+
+    /// First moves, for either side, the personality likes to open with
+    fn book(&self) -> &'static [&'static str] {
+        match self {
+            Personality::Standard => &[],
+            Personality::Aggressive => &["e2e4", "f2f4", "b1c3", "c7c5", "e7e5"],
+            Personality::Positional => &["d2d4", "c2c4", "g1f3", "g2g3", "d7d5", "e7e6", "c7c6"],
+            Personality::EndgameGrinder => &["d2d4", "g1f3", "c2c4", "e7e5", "c7c6"],
+        }
+    }
+
+    /// Bonus, in centipawns, the personality gives `m` in `position`
+    fn bias(&self, position: &Chess, m: &Move) -> i32 {
+        let gives_check = {
+            let mut after = position.clone();
+            after.play_unchecked(*m);
+            after.is_check()
+        };
+        match self {
+            Personality::Standard => 0,
+            Personality::Aggressive => {
+                let mut bonus = 0;
+                if gives_check {
+                    bonus += 25;
+                }
+                if m.is_capture() {
+                    bonus += 15;
+                }
+                // Pieces and pawns heading into the opponent's half
+                let forward = match position.turn() {
+                    Color::White => m.to().rank() >= Rank::Fifth,
+                    Color::Black => m.to().rank() <= Rank::Fourth,
+                };
+                if forward {
+                    bonus += 5;
+                }
+                bonus
+            }
+            Personality::Positional => {
+                if m.is_castle() {
+                    20
+                } else if !m.is_capture() && !gives_check {
+                    10
+                } else {
+                    0
+                }
+            }
+            Personality::EndgameGrinder => {
+                let mut bonus = 0;
+                // Trading pieces rather than pawns heads for the endgame
+                if m.capture().is_some_and(|role| role != Role::Pawn) {
+                    bonus += 20;
+                }
+                if m.role() == Role::King && GamePhase::of(position) == GamePhase::Endgame {
+                    bonus += 15;
+                }
+                bonus
+            }
+        }
+    }
+
+    /// The move the personality picks among `lines` (score for the side to move, first move)
+    /// of a search in `position`, after the engine has made `engine_moves`: the one scoring
+    /// best with its bias among those within the personality's margin of the best line
+    pub fn pick_move<'a>(&self, position: &Chess, lines: &'a [(i32, String)], engine_moves: usize) -> Option<&'a str> {
+        let best = lines.iter().map(|(score, _)| *score).max()?;
+        let in_book = engine_moves < BOOK_MOVES;
+        lines
+            .iter()
+            .filter(|(score, _)| *score >= best - self.margin_cp())
+            .filter_map(|(score, uci)| {
+                let m = uci.parse::<UciMove>().ok()?.to_move(position).ok()?;
+                let book = if in_book && self.book().contains(&uci.as_str()) { BOOK_BONUS } else { 0 };
+                Some((score + self.bias(position, &m) + book, uci.as_str()))
+            })
+            // The first of equally good moves, which is the engine's own order
+            .rev()
+            .max_by_key(|(value, _)| *value)
+            .map(|(_, uci)| uci)
+    }
+}
+
+impl std::fmt::Display for Personality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.avatar(), self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    fn scored(moves: &[(i32, &str)]) -> Vec<(i32, String)> {
+        moves.iter().map(|(score, uci)| (*score, uci.to_string())).collect()
+    }
+
+    #[test]
+    fn test_pick_move_book() {
+        let start = Chess::default();
+        let lines = scored(&[(30, "e2e4"), (28, "d2d4"), (20, "g1f3"), (-20, "f2f4")]);
+        assert_eq!(Personality::Standard.pick_move(&start, &lines, 0), Some("e2e4"));
+        assert_eq!(Personality::Positional.pick_move(&start, &lines, 0), Some("d2d4"));
+        assert_eq!(Personality::Aggressive.pick_move(&start, &lines, 0), Some("e2e4"));
+        // Out of the book the best line wins again
+        assert_eq!(Personality::Positional.pick_move(&start, &lines, BOOK_MOVES), Some("e2e4"));
+        assert_eq!(Personality::Aggressive.pick_move(&start, &[], 0), None);
+    }
+
+    #[test]
+    fn test_pick_move_style() {
+        // White can take on f7 with check or castle
+        let game = GameState::from_fen("r1bqk2r/pppp1ppp/5n2/2b1n3/2B1P3/2N5/PPPP1PPP/R1BQK2R w KQkq - 0 6").unwrap();
+        let position = game.current_position();
+        let lines = scored(&[(40, "c4f7"), (30, "e1g1"), (25, "d2d4")]);
+        assert_eq!(Personality::Positional.pick_move(position, &lines, BOOK_MOVES), Some("e1g1"));
+        assert_eq!(Personality::Aggressive.pick_move(position, &lines, BOOK_MOVES), Some("c4f7"));
+
+        // Rooks can be traded, or the king walks up
+        let game = GameState::from_fen("3r2k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 40").unwrap();
+        let position = game.current_position();
+        let lines = scored(&[(20, "g1f1"), (18, "d1d8")]);
+        assert_eq!(Personality::EndgameGrinder.pick_move(position, &lines, BOOK_MOVES), Some("d1d8"));
+        assert_eq!(Personality::Standard.pick_move(position, &lines, BOOK_MOVES), Some("g1f1"));
+    }
+}
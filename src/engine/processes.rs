@@ -0,0 +1,128 @@
+use crate::config::data_dir;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Engine processes started by running instances of the app, one file per process named
+/// "<app pid>-<engine pid>" holding the engine's path, so a later launch can end the engines
+/// of an instance that crashed
+fn registry_dir() -> PathBuf {
+    data_dir().join("engines")
+}
+
+/// The app and engine process ids of a registry file name
+fn parse_entry(name: &str) -> Option<(u32, u32)> {
+    let (app, engine) = name.split_once('-')?;
+    Some((app.parse().ok()?, engine.parse().ok()?))
+}
+
+/// Whether a running program, as the OS names it, is the engine binary at `path`
+fn same_program(path: &str, running: &str) -> bool {
+    let stem = |p: &str| Path::new(p.trim()).file_stem().map(|s| s.to_string_lossy().to_lowercase());
+    stem(path).is_some() && stem(path) == stem(running)
+}
+
+/// Remember an engine process started from `path` until it is unregistered
+pub fn register(engine_pid: u32, path: &str) {
+    let file = registry_dir().join(format!("{}-{}", std::process::id(), engine_pid));
+    if let Err(e) = crate::config::write_atomic(&file, path) {
+        tracing::warn!("Could not register engine process {}: {}", engine_pid, e);
+    }
+}
+
+pub fn unregister(engine_pid: u32) {
+    let _ = std::fs::remove_file(registry_dir().join(format!("{}-{}", std::process::id(), engine_pid)));
+}
+
+/// End the engines left running by instances of the app that are gone, returning how many
+/// were killed. Entries whose process has exited, or whose id now belongs to another program,
+/// are only forgotten.
+pub fn clean_up_orphans() -> usize {
+    let Ok(entries) = std::fs::read_dir(registry_dir()) else {
+        return 0;
+    };
+    let mut killed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some((app_pid, engine_pid)) = parse_entry(&name) else {
+            continue;
+        };
+        if app_pid == std::process::id() || process_name(app_pid).is_some() {
+            continue;
+        }
+        let path = std::fs::read_to_string(entry.path()).unwrap_or_default();
+        if process_name(engine_pid).is_some_and(|running| same_program(&path, &running)) {
+            tracing::warn!("Killing engine process {} left by a crashed session: {}", engine_pid, path);
+            kill_process(engine_pid);
+            killed += 1;
+        }
+        let _ = std::fs::remove_file(entry.path());
+    }
+    killed
+}
+
+/// Program running as `pid`, `None` if there is no such process
+#[cfg(target_os = "linux")]
+fn process_name(pid: u32) -> Option<String> {
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let program = cmdline.split(|&b| b == 0).next().unwrap_or_default();
+    Some(String::from_utf8_lossy(program).to_string())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_name(pid: u32) -> Option<String> {
+    let out = Command::new("ps").args(["-p", &pid.to_string(), "-o", "comm="]).output().ok()?;
+    let name = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(windows)]
+fn process_name(pid: u32) -> Option<String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let out = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    // "stockfish.exe","1234","Console","1","40,000 K"
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut fields = text.lines().next()?.split(',').map(|f| f.trim_matches('"'));
+    let name = fields.next()?.to_string();
+    (fields.next() == Some(pid.to_string().as_str())).then_some(name)
+}
+
+#[cfg(unix)]
+fn kill_process(pid: u32) {
+    let _ = Command::new("kill").args(["-9", &pid.to_string()]).status();
+}
+
+#[cfg(windows)]
+fn kill_process(pid: u32) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let _ = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .status();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(parse_entry("1200-1234"), Some((1200, 1234)));
+        assert_eq!(parse_entry("1200-1234.tmp"), None);
+        assert_eq!(parse_entry("notes"), None);
+    }
+
+    #[test]
+    fn test_same_program() {
+        assert!(same_program("/usr/games/stockfish", "stockfish"));
+        assert!(same_program("/opt/sf/Stockfish.exe", "stockfish.exe"));
+        assert!(same_program("/opt/sf/stockfish", "/opt/sf/stockfish"));
+        assert!(!same_program("/opt/sf/stockfish", "bash"));
+        assert!(!same_program("", "stockfish"));
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// What the engine is being used for, each with its own engine and thread limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineRole {
+    /// Playing against the user in Game mode, or for them online
+    Game,
+    Analysis,
+    /// Evaluating every position of a game, on the engine in use unless one is assigned
+    Review,
+}
+
+/// Engine binary and thread limit for one role
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoleEngine {
+    /// Engine binary, the configured engine when empty
+    pub path: String,
+    /// Most threads the engine may use, no limit when zero
+    pub max_threads: u32,
+}
+
+/// Engines assigned to Game mode, analysis and game reviews
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineRoles {
+    pub game: RoleEngine,
+    pub analysis: RoleEngine,
+    pub review: RoleEngine,
+}
+
+impl EngineRoles {
+    /// The engine binary assigned to `role`, None for the configured engine, or for reviews
+    /// the engine in use
+    pub fn path(&self, role: EngineRole) -> Option<String> {
+        let path = match role {
+            EngineRole::Game => &self.game.path,
+            EngineRole::Analysis => &self.analysis.path,
+            EngineRole::Review => &self.review.path,
+        };
+        let path = shellexpand::tilde(path.trim()).to_string();
+        (!path.is_empty()).then_some(path)
+    }
+
+    /// `threads` limited to what `role` may use
+    pub fn threads(&self, role: EngineRole, threads: u32) -> u32 {
+        let max = match role {
+            EngineRole::Game => self.game.max_threads,
+            EngineRole::Analysis => self.analysis.max_threads,
+            EngineRole::Review => self.review.max_threads,
+        };
+        if max == 0 {
+            threads
+        } else {
+            threads.min(max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_roles() {
+        let roles = EngineRoles {
+            game: RoleEngine {
+                path: "  ".to_string(),
+                max_threads: 1,
+            },
+            analysis: RoleEngine {
+                path: "/opt/engines/lc0".to_string(),
+                max_threads: 0,
+            },
+            review: RoleEngine {
+                path: String::new(),
+                max_threads: 2,
+            },
+        };
+        assert_eq!(roles.path(EngineRole::Game), None);
+        assert_eq!(roles.path(EngineRole::Analysis).as_deref(), Some("/opt/engines/lc0"));
+        assert_eq!(roles.threads(EngineRole::Game, 8), 1);
+        assert_eq!(roles.threads(EngineRole::Analysis, 8), 8);
+        assert_eq!(roles.threads(EngineRole::Review, 8), 2);
+        assert_eq!(roles.threads(EngineRole::Review, 1), 1);
+    }
+
+    #[test]
+    fn test_review_engine() {
+        let mut roles = EngineRoles::default();
+        assert_eq!(roles.path(EngineRole::Review), None);
+        roles.review.path = "~/engines/stockfish-review".to_string();
+        assert!(roles.path(EngineRole::Review).is_some_and(|path| !path.starts_with('~') && path.ends_with("stockfish-review")));
+    }
+}
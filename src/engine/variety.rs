@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// Lines searched while the engine picks a varied opening move
+pub const VARIETY_LINES: u32 = 4;
+
+/// Randomizes the engine's first moves among near-equal options so games don't all open alike
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VarietySettings {
+    pub enabled: bool,
+    /// How many of the engine's moves are varied
+    pub moves: u32,
+    /// How far below the best line, in centipawns, a move may score and still be picked
+    pub margin_cp: i32,
+}
+
+impl Default for VarietySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            moves: 4,
+            margin_cp: 30,
+        }
+    }
+}
+
+impl VarietySettings {
+    /// Whether the engine's move after it has made `engine_moves` is varied
+    pub fn applies(&self, engine_moves: usize) -> bool {
+        self.enabled && engine_moves < self.moves as usize
+    }
+}
+
+/// A random first move among `lines` (score for the side to move, first move) scoring within
+/// `margin_cp` of the best one
+pub fn varied_move<'a>(lines: &'a [(i32, String)], margin_cp: i32, rng: &mut fastrand::Rng) -> Option<&'a str> {
+    let best = lines.iter().map(|(score, _)| *score).max()?;
+    let options: Vec<&str> = lines
+        .iter()
+        .filter(|(score, _)| *score >= best - margin_cp)
+        .map(|(_, uci)| uci.as_str())
+        .collect();
+    Some(options[rng.usize(..options.len())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varied_move() {
+        let lines = vec![
+            (25, "e2e4".to_string()),
+            (20, "d2d4".to_string()),
+            (5, "g1f3".to_string()),
+            (-40, "g2g4".to_string()),
+        ];
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut seen: Vec<&str> = (0..50).filter_map(|_| varied_move(&lines, 30, &mut rng)).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen, vec!["d2d4", "e2e4", "g1f3"]);
+
+        assert_eq!(varied_move(&lines, 0, &mut rng), Some("e2e4"));
+        assert_eq!(varied_move(&[], 30, &mut rng), None);
+    }
+
+    #[test]
+    fn test_variety_applies() {
+        let settings = VarietySettings {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(settings.applies(3));
+        assert!(!settings.applies(4));
+        assert!(!VarietySettings::default().applies(0));
+    }
+}
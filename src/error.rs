@@ -0,0 +1,123 @@
+use crate::game::GameError;
+use crate::i18n::{tr, tr_args};
+use crate::ui::ToastAction;
+use std::time::Duration;
+
+/// Something that went wrong and is shown to the user, with enough context to say what to do
+/// about it. Texts are in the interface language.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    /// No engine binary at the path it was started from
+    EngineNotFound { path: String },
+    /// The engine binary exists but could not be run
+    EngineStart { path: String, reason: String },
+    /// The engine stopped answering; the actor restarts it
+    EngineTimeout { waiting_for: String, after: Duration },
+    /// The engine process went away mid-conversation
+    EngineClosed,
+    /// A line the engine wrote to stderr that looks like a failure
+    EngineReported(String),
+    /// Any other failed engine command
+    Engine(String),
+    StudyRead { id: String, reason: String },
+    /// The study file exists but doesn't decode, and there was no backup to fall back to
+    StudyDamaged { id: String, reason: String },
+    StudyWrite { name: String, reason: String },
+    /// A file the user asked to be written, such as an export
+    FileWrite { path: String, reason: String },
+    Pgn(GameError),
+    /// Pasted or dropped text held no game
+    NoPgn,
+}
+
+impl AppError {
+    /// The error behind a failed engine command
+    pub fn from_engine(e: &anyhow::Error) -> Self {
+        match e.downcast_ref::<AppError>() {
+            Some(error) => error.clone(),
+            None => AppError::Engine(e.to_string()),
+        }
+    }
+
+    pub fn is_engine_timeout(&self) -> bool {
+        matches!(self, AppError::EngineTimeout { .. })
+    }
+
+    /// What the user can do to fix it
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            AppError::EngineNotFound { .. } => tr("error.engine_not_found_fix"),
+            AppError::EngineStart { .. } => tr("error.engine_start_fix"),
+            AppError::EngineTimeout { .. } => tr("error.engine_timeout_fix"),
+            AppError::EngineClosed => tr("error.engine_closed_fix"),
+            AppError::EngineReported(_) => tr("error.engine_reported_fix"),
+            AppError::Engine(_) => tr("error.engine_fix"),
+            AppError::StudyRead { .. } => tr("error.study_read_fix"),
+            AppError::StudyDamaged { .. } => tr("error.study_damaged_fix"),
+            AppError::StudyWrite { .. } => tr("error.study_write_fix"),
+            AppError::FileWrite { .. } => tr("error.file_write_fix"),
+            AppError::Pgn(_) => tr("error.pgn_fix"),
+            AppError::NoPgn => tr("error.no_pgn_fix"),
+        }
+    }
+
+    /// Where the toast reporting it can take the user
+    pub fn action(&self) -> Option<ToastAction> {
+        match self {
+            AppError::EngineNotFound { .. } | AppError::EngineStart { .. } => Some(ToastAction::OpenSettings),
+            AppError::EngineTimeout { .. }
+            | AppError::EngineClosed
+            | AppError::EngineReported(_)
+            | AppError::Engine(_) => Some(ToastAction::ShowEngineLog),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            AppError::EngineNotFound { path } => tr_args("error.engine_not_found", &[path]),
+            AppError::EngineStart { path, reason } => tr_args("error.engine_start", &[path, reason]),
+            AppError::EngineTimeout { waiting_for, after } => {
+                tr_args("error.engine_timeout", &[waiting_for, &format!("{:.0}", after.as_secs_f32())])
+            }
+            AppError::EngineClosed => tr("error.engine_closed").to_string(),
+            AppError::EngineReported(line) => tr_args("error.engine_reported", &[line]),
+            AppError::Engine(reason) => tr_args("toast.engine_error", &[reason]),
+            AppError::StudyRead { id, reason } => tr_args("error.study_read", &[id, reason]),
+            AppError::StudyDamaged { id, reason } => tr_args("error.study_damaged", &[id, reason]),
+            AppError::StudyWrite { name, reason } => tr_args("error.study_write", &[name, reason]),
+            AppError::FileWrite { path, reason } => tr_args("error.file_write", &[path, reason]),
+            AppError::Pgn(e) => tr_args("error.pgn", &[e]),
+            AppError::NoPgn => tr("error.no_pgn").to_string(),
+        };
+        f.write_str(&text)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<GameError> for AppError {
+    fn from(e: GameError) -> Self {
+        AppError::Pgn(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_engine() {
+        let timeout = AppError::EngineTimeout { waiting_for: "readyok".to_string(), after: Duration::from_secs(10) };
+        let error = AppError::from_engine(&anyhow::Error::new(timeout));
+        assert!(error.is_engine_timeout());
+        assert!(error.to_string().contains("readyok"));
+        assert_eq!(error.action(), Some(ToastAction::ShowEngineLog));
+
+        let error = AppError::from_engine(&anyhow::anyhow!("broken pipe"));
+        assert!(matches!(&error, AppError::Engine(reason) if reason == "broken pipe"));
+        assert!(!error.remediation().is_empty());
+    }
+}
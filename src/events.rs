@@ -0,0 +1,56 @@
+use crate::engine::EngineEvent;
+use crate::ui::{
+    BroadcastAction, ControlAction, ImportAction, OnlineAction, PuzzleAction, PuzzleCardAction, ResultAction,
+    SettingsAction, StudyNavAction, ToastAction, TournamentAction,
+};
+use shakmaty::Move;
+
+/// Something that changes the app's state, from the user or the engine. Panels and the engine
+/// channel queue events as they happen and the app handles them in order in one place, so
+/// every change goes through the same path and can be logged or replayed.
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    Engine(EngineEvent),
+    /// A move made on the board or typed in
+    Move(Move),
+    Control(ControlAction),
+    StudyNav(StudyNavAction),
+    Settings(SettingsAction),
+    Toast(ToastAction),
+    Result(ResultAction),
+    Import(ImportAction),
+    Puzzle(PuzzleAction),
+    PuzzleCard(PuzzleCardAction),
+    Broadcast(BroadcastAction),
+    Tournament(TournamentAction),
+    Online(OnlineAction),
+}
+
+/// Something handling an event needs from the window. Handlers queue these instead of
+/// touching the `egui::Context`, so events can be handled without a window, and the app
+/// carries them out once the event queue is empty.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiRequest {
+    /// Draw again soon, because something on screen changed
+    Repaint,
+    CopyText(String),
+    TouchMode(bool),
+}
+
+impl AppEvent {
+    /// Whether the user caused it, rather than the engine
+    pub fn is_user(&self) -> bool {
+        !matches!(self, AppEvent::Engine(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_user() {
+        assert!(!AppEvent::Engine(EngineEvent::Ready).is_user());
+        assert!(AppEvent::Toast(ToastAction::ShowEngineLog).is_user());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::explorer::{OpeningTree, TreeMove};
+use crate::game::{numbered_line, position_key, GameState, PlayerColor};
+use crate::i18n::{tr, tr_args};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Most lines suggested for learning next
+const MAX_SUGGESTIONS: usize = 10;
+
+/// How a game left the repertoire
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GapKind {
+    /// The user played something else than the repertoire's `prepared` moves
+    Misplayed { prepared: Vec<String> },
+    /// The opponent played a move the repertoire has no answer to
+    Uncovered,
+}
+
+/// A move from the user's games that left their repertoire
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageGap {
+    pub kind: GapKind,
+    /// SAN moves from the starting position, the one that left the repertoire last
+    pub line: Vec<String>,
+    pub games: u32,
+    pub losses: u32,
+}
+
+impl CoverageGap {
+    fn new(kind: GapKind, line: &[String], m: &TreeMove) -> Self {
+        let mut line = line.to_vec();
+        line.push(m.san.clone());
+        Self {
+            kind,
+            line,
+            games: m.games,
+            losses: m.losses,
+        }
+    }
+
+    /// How much learning the line is worth: how often it came up, lost games counting twice
+    pub fn weight(&self) -> u32 {
+        self.games + self.losses
+    }
+
+    /// The line with move numbers, like "1. e4 c5 2. Nf3"
+    pub fn line_text(&self) -> String {
+        numbered_line(0, &self.line)
+    }
+
+    /// Games count and, for a misplayed line, the prepared moves
+    pub fn detail(&self) -> String {
+        let games = tr_args("coverage.games", &[&self.games, &self.losses]);
+        match &self.kind {
+            GapKind::Misplayed { prepared } => {
+                format!("{} · {}", games, tr_args("coverage.prepared", &[&prepared.join(", ")]))
+            }
+            GapKind::Uncovered => games,
+        }
+    }
+}
+
+/// Where the user's imported games left the lines of their repertoire studies
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    /// Lines where the user forgot their preparation, most played first
+    pub misplayed: Vec<CoverageGap>,
+    /// Opponent moves the repertoire doesn't answer, most played first
+    pub uncovered: Vec<CoverageGap>,
+}
+
+impl CoverageReport {
+    /// Follow the games of `tree` from the starting position for as long as they stay in
+    /// the repertoire of the side the user played, by position key as from `Study::repertoire`
+    pub fn build(tree: &OpeningTree, white: &HashMap<String, Vec<String>>, black: &HashMap<String, Vec<String>>) -> Self {
+        let mut report = Self::default();
+        let mut seen = HashSet::new();
+        let mut pending = vec![(GameState::new(), Vec::<String>::new())];
+
+        while let Some((game, line)) = pending.pop() {
+            let fen = game.fen();
+            let key = position_key(&fen);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            for m in tree.moves(&fen) {
+                let side = if m.mine { game.turn() } else { game.turn().opposite() };
+                let repertoire = match side {
+                    PlayerColor::White => white,
+                    PlayerColor::Black => black,
+                };
+                // Past the end of a repertoire line there is nothing to keep to
+                let Some(prepared) = repertoire.get(&key).filter(|p| !p.is_empty()) else {
+                    continue;
+                };
+                if prepared.contains(&m.san) {
+                    let mut next = GameState::from_fen(&fen).expect("FEN of a valid position");
+                    if next.make_move_uci(&m.uci).is_ok() {
+                        let mut line = line.clone();
+                        line.push(m.san.clone());
+                        pending.push((next, line));
+                    }
+                } else if m.mine {
+                    let kind = GapKind::Misplayed { prepared: prepared.clone() };
+                    report.misplayed.push(CoverageGap::new(kind, &line, m));
+                } else {
+                    report.uncovered.push(CoverageGap::new(GapKind::Uncovered, &line, m));
+                }
+            }
+        }
+
+        report.misplayed.sort_by_key(|g| std::cmp::Reverse(g.games));
+        report.uncovered.sort_by_key(|g| std::cmp::Reverse(g.games));
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.misplayed.is_empty() && self.uncovered.is_empty()
+    }
+
+    /// Lines most worth learning next, of either kind
+    pub fn suggestions(&self) -> Vec<&CoverageGap> {
+        let mut gaps: Vec<_> = self.misplayed.iter().chain(&self.uncovered).collect();
+        gaps.sort_by_key(|g| std::cmp::Reverse(g.weight()));
+        gaps.truncate(MAX_SUGGESTIONS);
+        gaps
+    }
+
+    /// Plain text version, for copying
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let sections = [
+            (tr("coverage.suggestions"), self.suggestions()),
+            (tr("coverage.misplayed"), self.misplayed.iter().collect()),
+            (tr("coverage.uncovered"), self.uncovered.iter().collect()),
+        ];
+        for (heading, gaps) in sections {
+            let _ = writeln!(text, "{}", heading);
+            for gap in gaps {
+                let _ = writeln!(text, "  {} ({})", gap.line_text(), gap.detail());
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_pgn;
+
+    #[test]
+    fn test_coverage_report() {
+        let games = parse_pgn(
+            "[White \"me\"]\n[Black \"x\"]\n[Result \"0-1\"]\n1. e4 e5 2. Bc4 0-1\n\n\
+             [White \"me\"]\n[Black \"y\"]\n[Result \"1-0\"]\n1. e4 e5 2. Nf3 Nc6 1-0\n\n\
+             [White \"me\"]\n[Black \"z\"]\n[Result \"1/2-1/2\"]\n1. e4 c6 1/2-1/2\n\n\
+             [White \"x\"]\n[Black \"me\"]\n[Result \"1-0\"]\n1. d4 d5 1-0\n",
+        );
+        let tree = OpeningTree::build(&games, &["me".to_string()]);
+
+        // White repertoire 1. e4 e5 2. Nf3, answering 1... e5 only
+        let mut white = HashMap::new();
+        let mut game = GameState::new();
+        for san in ["e4", "e5", "Nf3"] {
+            white.insert(position_key(&game.fen()), vec![san.to_string()]);
+            game.make_move_san(san).unwrap();
+        }
+        let report = CoverageReport::build(&tree, &white, &HashMap::new());
+
+        assert_eq!(report.misplayed.len(), 1);
+        assert_eq!(report.misplayed[0].line_text(), "1. e4 e5 2. Bc4");
+        assert_eq!(report.misplayed[0].kind, GapKind::Misplayed { prepared: vec!["Nf3".to_string()] });
+        assert_eq!(report.uncovered.len(), 1);
+        assert_eq!(report.uncovered[0].line, ["e4", "c6"]);
+        // Black has no repertoire, and 2... Nc6 is past the end of the White line
+        let suggestions = report.suggestions();
+        assert_eq!(suggestions[0].line_text(), "1. e4 e5 2. Bc4");
+    }
+}
@@ -0,0 +1,28 @@
+use crate::config::USER_AGENT;
+use crate::explorer::{ExplorerDatabase, ExplorerResult};
+
+/// Query the Lichess opening explorer for a position
+pub fn fetch(database: ExplorerDatabase, fen: &str, token: Option<&str>) -> Result<ExplorerResult, String> {
+    let url = match database {
+        ExplorerDatabase::Masters => "https://explorer.lichess.ovh/masters",
+        ExplorerDatabase::Lichess => "https://explorer.lichess.ovh/lichess",
+    };
+    let mut request = ureq::get(url)
+        .set("User-Agent", USER_AGENT)
+        .query("fen", fen)
+        .query("moves", "12")
+        .query("topGames", "0")
+        .query("recentGames", "0");
+    if database == ExplorerDatabase::Lichess {
+        request = request.query("speeds", "blitz,rapid,classical");
+    }
+    if let Some(token) = token {
+        request = request.set("Authorization", &format!("Bearer {}", token));
+    }
+
+    match request.call() {
+        Ok(response) => response.into_json().map_err(|e| e.to_string()),
+        Err(ureq::Error::Status(401, _)) => Err(crate::i18n::tr("explorer.unauthorized").to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -0,0 +1,163 @@
+mod coverage;
+mod lichess;
+mod personal;
+
+pub use coverage::{CoverageGap, CoverageReport};
+pub use personal::{append_my_games, load_my_games, new_games, OpeningTree, TreeMove};
+
+use crate::i18n::tr;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+/// Game collection the opening explorer searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExplorerDatabase {
+    /// Over-the-board games between titled players
+    Masters,
+    /// Rated blitz, rapid and classical games played on Lichess
+    Lichess,
+}
+
+impl ExplorerDatabase {
+    pub fn all() -> &'static [ExplorerDatabase] {
+        &[ExplorerDatabase::Masters, ExplorerDatabase::Lichess]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExplorerDatabase::Masters => tr("explorer.masters"),
+            ExplorerDatabase::Lichess => tr("explorer.lichess"),
+        }
+    }
+}
+
+/// Results of one continuation from the explored position
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExplorerMove {
+    pub uci: String,
+    pub san: String,
+    pub white: u64,
+    pub draws: u64,
+    pub black: u64,
+}
+
+impl ExplorerMove {
+    pub fn games(&self) -> u64 {
+        self.white + self.draws + self.black
+    }
+}
+
+/// Game counts for a position and its most played continuations
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExplorerResult {
+    pub white: u64,
+    pub draws: u64,
+    pub black: u64,
+    pub moves: Vec<ExplorerMove>,
+}
+
+type Request = (ExplorerDatabase, String);
+
+/// Runs explorer queries on a background thread and caches the answers by position
+pub struct ExplorerClient {
+    request_tx: mpsc::Sender<Request>,
+    response_rx: mpsc::Receiver<(Request, Result<ExplorerResult, String>)>,
+    cache: HashMap<Request, Result<ExplorerResult, String>>,
+    pending: Option<Request>,
+}
+
+impl ExplorerClient {
+    /// `token` is an optional Lichess API token sent with every query
+    pub fn new(token: Option<String>) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<Request>();
+        let (response_tx, response_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            while let Ok(mut request) = request_rx.recv() {
+                // Only the newest position matters when the user clicks through moves quickly
+                while let Ok(newer) = request_rx.try_recv() {
+                    request = newer;
+                }
+                let result = lichess::fetch(request.0, &request.1, token.as_deref());
+                if response_tx.send((request, result)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            request_tx,
+            response_rx,
+            cache: HashMap::new(),
+            pending: None,
+        }
+    }
+
+    /// The answer for a position if it has arrived, requesting it otherwise.
+    /// `None` means the query is still running.
+    pub fn lookup(&mut self, database: ExplorerDatabase, fen: &str) -> Option<&Result<ExplorerResult, String>> {
+        while let Ok((request, result)) = self.response_rx.try_recv() {
+            if self.pending.as_ref() == Some(&request) {
+                self.pending = None;
+            }
+            self.cache.insert(request, result);
+        }
+
+        let request = (database, fen.to_string());
+        if !self.cache.contains_key(&request) && self.pending.as_ref() != Some(&request) {
+            let _ = self.request_tx.send(request.clone());
+            self.pending = Some(request.clone());
+        }
+        self.cache.get(&request)
+    }
+
+    /// Forget a failed answer so the next lookup asks again
+    pub fn retry(&mut self, database: ExplorerDatabase, fen: &str) {
+        self.cache.remove(&(database, fen.to_string()));
+    }
+}
+
+/// Moves after which a game is no longer looked up in the masters database
+const BOOK_PLIES: usize = 30;
+
+/// Where a game left opening theory: its first move that no master played in that position
+pub struct BookExit {
+    response_rx: mpsc::Receiver<Option<usize>>,
+    ply: Option<usize>,
+}
+
+impl BookExit {
+    /// Look the moves up one position at a time on a background thread. `moves` pairs the FEN
+    /// each move was played from with the move in SAN.
+    pub fn find(moves: Vec<(String, String)>, token: Option<String>) -> Self {
+        let (response_tx, response_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut exit = None;
+            for (ply, (fen, san)) in moves.into_iter().take(BOOK_PLIES).enumerate() {
+                match lichess::fetch(ExplorerDatabase::Masters, &fen, token.as_deref()) {
+                    Ok(result) if result.moves.iter().any(|m| m.san == san) => {}
+                    Ok(_) => {
+                        exit = Some(ply);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Could not look up the opening: {}", e);
+                        break;
+                    }
+                }
+            }
+            let _ = response_tx.send(exit);
+        });
+        Self { response_rx, ply: None }
+    }
+
+    /// The ply of the first move out of theory, once found. `None` while the lookup runs, when the
+    /// opening stayed in theory or when the explorer couldn't be reached.
+    pub fn ply(&mut self) -> Option<usize> {
+        if let Ok(ply) = self.response_rx.try_recv() {
+            self.ply = ply;
+        }
+        self.ply
+    }
+}
@@ -0,0 +1,209 @@
+use crate::config::{data_dir, write_atomic};
+use crate::game::{parse_pgn, position_key, GameState, PgnGame, PlayerColor};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Only the opening phase of each game goes into the tree
+const OPENING_PLIES: usize = 30;
+
+/// How one move from a position turned out in the user's own games
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeMove {
+    pub san: String,
+    pub uci: String,
+    pub games: u32,
+    /// Results from the user's point of view
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    /// Whether the user played this move, rather than faced it
+    pub mine: bool,
+}
+
+impl TreeMove {
+    /// Score in percent, counting draws as half
+    pub fn score(&self) -> u32 {
+        (self.wins * 2 + self.draws) * 50 / self.games.max(1)
+    }
+}
+
+/// Opening tree of the user's imported games
+#[derive(Debug, Clone, Default)]
+pub struct OpeningTree {
+    positions: HashMap<String, Vec<TreeMove>>,
+    /// Games that went into the tree
+    pub games: usize,
+    /// Imported games in which none of the user's names played
+    pub skipped: usize,
+}
+
+impl OpeningTree {
+    /// Build the tree from games in which one of `names` (case-insensitive) played
+    pub fn build(games: &[PgnGame], names: &[String]) -> Self {
+        let mut tree = Self::default();
+        let start_fen = GameState::new().fen();
+        let is_me = |player: Option<&str>| {
+            player.is_some_and(|p| names.iter().any(|n| n.eq_ignore_ascii_case(p.trim())))
+        };
+
+        for pgn in games {
+            let side = if is_me(pgn.header("White")) {
+                PlayerColor::White
+            } else if is_me(pgn.header("Black")) {
+                PlayerColor::Black
+            } else {
+                tree.skipped += 1;
+                continue;
+            };
+            let Ok(game) = pgn.to_game() else {
+                tree.skipped += 1;
+                continue;
+            };
+            tree.games += 1;
+
+            let winner = match pgn.header("Result") {
+                Some("1-0") => Some(Some(PlayerColor::White)),
+                Some("0-1") => Some(Some(PlayerColor::Black)),
+                Some("1/2-1/2") => Some(None),
+                _ => None,
+            };
+            let mut fen = pgn.header("FEN").map_or_else(|| start_fen.clone(), str::to_string);
+            for record in game.move_history().iter().take(OPENING_PLIES) {
+                let mover = if fen.split_whitespace().nth(1) == Some("b") {
+                    PlayerColor::Black
+                } else {
+                    PlayerColor::White
+                };
+                let moves = tree.positions.entry(position_key(&fen)).or_default();
+                let index = match moves.iter().position(|m| m.uci == record.uci) {
+                    Some(index) => index,
+                    None => {
+                        moves.push(TreeMove {
+                            san: record.san.clone(),
+                            uci: record.uci.clone(),
+                            mine: mover == side,
+                            ..TreeMove::default()
+                        });
+                        moves.len() - 1
+                    }
+                };
+                let entry = &mut moves[index];
+                entry.games += 1;
+                match winner {
+                    Some(Some(w)) if w == side => entry.wins += 1,
+                    Some(Some(_)) => entry.losses += 1,
+                    Some(None) => entry.draws += 1,
+                    None => {}
+                }
+                fen = record.resulting_fen.clone();
+            }
+        }
+
+        for moves in tree.positions.values_mut() {
+            moves.sort_by_key(|m| std::cmp::Reverse(m.games));
+        }
+        tree
+    }
+
+    /// Moves played from a position, most frequent first
+    pub fn moves(&self, fen: &str) -> &[TreeMove] {
+        self.positions.get(&position_key(fen)).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// PGN file collecting the user's imported games
+fn my_games_path() -> PathBuf {
+    data_dir().join("my_games.pgn")
+}
+
+pub fn load_my_games() -> Vec<PgnGame> {
+    std::fs::read_to_string(my_games_path())
+        .map(|text| parse_pgn(&text))
+        .unwrap_or_default()
+}
+
+/// Filter passing the games not already in `existing`, each only once, so an import can be
+/// checked game by game as it is read
+pub fn new_games(existing: &[PgnGame]) -> impl FnMut(&PgnGame) -> bool {
+    let mut seen: HashSet<u64> = existing.iter().map(PgnGame::fingerprint).collect();
+    move |game| seen.insert(game.fingerprint())
+}
+
+/// Add `games` to the end of the file. The file is rewritten as a whole, so a crash leaves
+/// either the old games or all of them, never a game cut off halfway.
+pub fn append_my_games(games: &[PgnGame]) -> std::io::Result<()> {
+    let path = my_games_path();
+    let mut text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    write_games(&mut text, games);
+    write_atomic(&path, text)
+}
+
+/// Add the PGN of `games` to `text`, on a new line after what it already holds
+fn write_games(text: &mut String, games: &[PgnGame]) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    for game in games {
+        text.push_str(&game.to_pgn());
+        text.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tree() {
+        let games = parse_pgn(
+            "[White \"me\"]\n[Black \"x\"]\n[Result \"1-0\"]\n1. e4 e5 1-0\n\n\
+             [White \"y\"]\n[Black \"Me\"]\n[Result \"1/2-1/2\"]\n1. e4 c5 1/2-1/2\n\n\
+             [White \"a\"]\n[Black \"b\"]\n1. d4 *\n",
+        );
+        let tree = OpeningTree::build(&games, &["ME".to_string()]);
+        assert_eq!((tree.games, tree.skipped), (2, 1));
+
+        let start = GameState::new().fen();
+        let first = tree.moves(&start);
+        assert_eq!(first.len(), 1);
+        assert_eq!((first[0].games, first[0].wins, first[0].draws), (2, 1, 1));
+        assert_eq!(first[0].score(), 75);
+
+        let mut after_e4 = GameState::new();
+        after_e4.make_move_san("e4").unwrap();
+        let replies = tree.moves(&after_e4.fen());
+        assert_eq!(replies.len(), 2);
+        assert!(replies.iter().any(|m| m.san == "c5" && m.mine));
+        assert!(replies.iter().any(|m| m.san == "e5" && !m.mine));
+    }
+
+    #[test]
+    fn test_new_games() {
+        let existing = parse_pgn("[White \"me\"]\n[Date \"2024.01.01\"]\n1. e4 e5 1-0\n");
+        let incoming = parse_pgn(
+            "[White \"me\"]\n[Date \"2024.01.01\"]\n[Site \"elsewhere\"]\n1. e4 e5 1-0\n\n\
+             [White \"me\"]\n[Date \"2024.01.02\"]\n1. e4 e5 1-0\n\n\
+             [White \"me\"]\n[Date \"2024.01.02\"]\n1. e4 e5 1-0\n\n\
+             [White \"me\"]\n[Date \"2024.01.01\"]\n1. d4 *\n",
+        );
+        let mut is_new = new_games(&existing);
+        let added: Vec<_> = incoming.into_iter().filter(|game| is_new(game)).collect();
+        assert_eq!(added.len(), 2);
+        assert_eq!(added[0].header("Date"), Some("2024.01.02"));
+        assert_eq!(added[1].moves, ["d4"]);
+    }
+
+    #[test]
+    fn test_write_games() {
+        let games = parse_pgn("[White \"me\"]\n1. e4 e5 1-0\n\n[White \"me\"]\n1. d4 *\n");
+        let mut text = "[White \"old\"]\n1. c4 *".to_string();
+        write_games(&mut text, &games);
+        let read = parse_pgn(&text);
+        assert_eq!(read.len(), 3);
+        assert_eq!(read[1..], games[..]);
+    }
+}
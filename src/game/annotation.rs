@@ -0,0 +1,258 @@
+use crate::game::{GamePhase, GameState, PlayerColor};
+
+/// Evaluation loss, in centipawns for the side that moved, at which a move counts as dubious
+pub const INACCURACY_CP: i32 = 50;
+/// Evaluation loss at which a move counts as a mistake
+pub const MISTAKE_CP: i32 = 100;
+/// Evaluation loss at which a move counts as a blunder
+pub const BLUNDER_CP: i32 = 200;
+/// Evaluation, in centipawns for the side that moved, from which a position counts as won
+pub const WINNING_CP: i32 = 300;
+/// Material in pawns a move must give up, counting the reply, to count as a sacrifice
+const SACRIFICE_PAWNS: i32 = 2;
+
+/// PGN numeric annotation glyphs for move quality
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nag {
+    Mistake,
+    Blunder,
+    Dubious,
+}
+
+impl Nag {
+    /// Number written as `$n` in standard PGN
+    pub fn code(self) -> u8 {
+        match self {
+            Nag::Mistake => 2,
+            Nag::Blunder => 4,
+            Nag::Dubious => 6,
+        }
+    }
+
+    /// Traditional symbol for the glyph
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Nag::Mistake => "?",
+            Nag::Blunder => "??",
+            Nag::Dubious => "?!",
+        }
+    }
+
+    /// Glyph for a move that lost `loss` centipawns, if it lost enough to be worth marking
+    pub fn from_loss(loss: i32) -> Option<Nag> {
+        if loss >= BLUNDER_CP {
+            Some(Nag::Blunder)
+        } else if loss >= MISTAKE_CP {
+            Some(Nag::Mistake)
+        } else if loss >= INACCURACY_CP {
+            Some(Nag::Dubious)
+        } else {
+            None
+        }
+    }
+}
+
+/// Centipawns the move at `ply` lost for `mover`, from White-relative evaluations of the positions
+/// by index. The position before the move is often unevaluated, so the one a move earlier stands in.
+pub fn eval_loss(evals: &[Option<i32>], ply: usize, mover: PlayerColor) -> Option<i32> {
+    let (before, after) = evals_around(evals, ply, mover)?;
+    Some(before - after)
+}
+
+/// Evaluations before and after the move at `ply`, from the point of view of `mover`
+fn evals_around(evals: &[Option<i32>], ply: usize, mover: PlayerColor) -> Option<(i32, i32)> {
+    let before = evals.get(ply).copied().flatten().or_else(|| {
+        ply.checked_sub(1).and_then(|i| evals.get(i).copied().flatten())
+    })?;
+    let after = evals.get(ply + 1).copied().flatten()?;
+    Some(match mover {
+        PlayerColor::White => (before, after),
+        PlayerColor::Black => (-before, -after),
+    })
+}
+
+/// Why a move was a turning point of the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalKind {
+    /// The mover was winning before the move and no longer is
+    MissedWin,
+    /// The move lost at least a mistake's worth
+    Swing,
+}
+
+/// A move where the evaluation turned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CriticalMoment {
+    pub ply: usize,
+    pub kind: CriticalKind,
+    /// Centipawns the move lost for the side that played it
+    pub loss: i32,
+}
+
+/// The `max` moves of a game of `moves` moves that lost the most, from mistakes up, in game order
+pub fn critical_moments(evals: &[Option<i32>], moves: usize, first_mover: PlayerColor, max: usize) -> Vec<CriticalMoment> {
+    let mut critical: Vec<CriticalMoment> = (0..moves)
+        .filter_map(|ply| {
+            let mover = if ply % 2 == 0 { first_mover } else { first_mover.opposite() };
+            let (before, after) = evals_around(evals, ply, mover)?;
+            let loss = before - after;
+            if loss < MISTAKE_CP {
+                return None;
+            }
+            let kind = if before >= WINNING_CP && after < WINNING_CP {
+                CriticalKind::MissedWin
+            } else {
+                CriticalKind::Swing
+            };
+            Some(CriticalMoment { ply, kind, loss })
+        })
+        .collect();
+    critical.sort_by_key(|c| std::cmp::Reverse(c.loss));
+    critical.truncate(max);
+    critical.sort_by_key(|c| c.ply);
+    critical
+}
+
+/// Move quality of one side over a game whose positions the engine evaluated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QualitySummary {
+    /// Moves with a known evaluation loss
+    pub reviewed: usize,
+    /// Sound sacrifices that kept the evaluation, only counted by `of_game`
+    pub brilliant: usize,
+    /// Moves that lost less than an inaccuracy, brilliant ones aside
+    pub good: usize,
+    pub inaccuracies: usize,
+    pub mistakes: usize,
+    pub blunders: usize,
+    /// Average centipawn loss over the reviewed moves
+    pub average_loss: i32,
+}
+
+impl QualitySummary {
+    /// Summary for `side` over `moves` moves, or `None` if none of its moves were evaluated
+    pub fn of(evals: &[Option<i32>], moves: usize, first_mover: PlayerColor, side: PlayerColor) -> Option<Self> {
+        Self::of_plies(evals, 0..moves, first_mover, side)
+    }
+
+    /// Summaries for `side` in each phase of the game it has evaluated moves in, given the
+    /// phase each move was played in
+    pub fn by_phase(evals: &[Option<i32>], phases: &[GamePhase], first_mover: PlayerColor, side: PlayerColor) -> Vec<(GamePhase, Self)> {
+        GamePhase::ALL
+            .into_iter()
+            .filter_map(|phase| {
+                let plies = (0..phases.len()).filter(|&ply| phases[ply] == phase);
+                Some((phase, Self::of_plies(evals, plies, first_mover, side)?))
+            })
+            .collect()
+    }
+
+    fn of_plies(evals: &[Option<i32>], plies: impl Iterator<Item = usize>, first_mover: PlayerColor, side: PlayerColor) -> Option<Self> {
+        let mut summary = Self::default();
+        let mut total_loss = 0;
+        for ply in plies {
+            let mover = if ply % 2 == 0 { first_mover } else { first_mover.opposite() };
+            if mover != side {
+                continue;
+            }
+            let Some(loss) = eval_loss(evals, ply, mover) else {
+                continue;
+            };
+            summary.reviewed += 1;
+            total_loss += loss.max(0);
+            match Nag::from_loss(loss) {
+                Some(Nag::Dubious) => summary.inaccuracies += 1,
+                Some(Nag::Mistake) => summary.mistakes += 1,
+                Some(Nag::Blunder) => summary.blunders += 1,
+                None => summary.good += 1,
+            }
+        }
+        (summary.reviewed > 0).then(|| Self {
+            average_loss: total_loss / summary.reviewed as i32,
+            ..summary
+        })
+    }
+
+    /// Summary for `side` over a whole game, also picking out brilliant moves: sacrifices of
+    /// material that lost nothing and left the side at least level
+    pub fn of_game(game: &GameState, evals: &[Option<i32>], side: PlayerColor) -> Option<Self> {
+        let moves = game.move_history().len();
+        let first_mover = game.starting_turn();
+        let mut summary = Self::of(evals, moves, first_mover, side)?;
+        for ply in 0..moves {
+            let mover = if ply % 2 == 0 { first_mover } else { first_mover.opposite() };
+            if mover != side {
+                continue;
+            }
+            let Some((before, after)) = evals_around(evals, ply, mover) else {
+                continue;
+            };
+            // Count the reply, as a sacrifice is only given up once the opponent takes
+            let reply = (ply + 2).min(moves);
+            let given_up = game.material_advantage_at(ply, mover) - game.material_advantage_at(reply, mover);
+            if after >= before && after >= 0 && given_up >= SACRIFICE_PAWNS {
+                summary.brilliant += 1;
+                summary.good -= 1;
+            }
+        }
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_loss() {
+        // White-relative: +30 before Black's move, +280 after it
+        let evals = [Some(30), None, Some(280)];
+        assert_eq!(eval_loss(&evals, 1, PlayerColor::Black), Some(250));
+        assert_eq!(Nag::from_loss(250), Some(Nag::Blunder));
+        assert_eq!(eval_loss(&evals, 2, PlayerColor::White), None);
+
+        let summary = QualitySummary::of(&evals, 2, PlayerColor::White, PlayerColor::Black).unwrap();
+        assert_eq!(summary.blunders, 1);
+        assert_eq!(summary.average_loss, 250);
+        assert_eq!(QualitySummary::of(&evals, 2, PlayerColor::White, PlayerColor::White), None);
+    }
+
+    #[test]
+    fn test_quality_by_phase() {
+        let evals = [Some(0), Some(0), Some(10), Some(-290), Some(-290)];
+        let phases = [GamePhase::Opening, GamePhase::Opening, GamePhase::Endgame, GamePhase::Endgame];
+        let white = QualitySummary::by_phase(&evals, &phases, PlayerColor::White, PlayerColor::White);
+        assert_eq!(white.len(), 2);
+        assert_eq!((white[0].0, white[0].1.average_loss), (GamePhase::Opening, 0));
+        assert_eq!((white[1].0, white[1].1.blunders), (GamePhase::Endgame, 1));
+    }
+
+    #[test]
+    fn test_brilliant_sacrifice() {
+        let mut game = GameState::new();
+        // Bxf7+ gives a bishop for a pawn, the engine still likes it
+        for san in ["e4", "e5", "Bc4", "Nc6", "Bxf7+", "Kxf7"] {
+            game.make_move_san(san).unwrap();
+        }
+        let evals = [Some(30), Some(30), Some(40), Some(40), Some(40), Some(60), Some(60)];
+        let white = QualitySummary::of_game(&game, &evals, PlayerColor::White).unwrap();
+        assert_eq!((white.brilliant, white.good), (1, 2));
+        let black = QualitySummary::of_game(&game, &evals, PlayerColor::Black).unwrap();
+        assert_eq!((black.brilliant, black.good, black.inaccuracies), (0, 3, 0));
+    }
+
+    #[test]
+    fn test_critical_moments() {
+        // White throws away a winning position, Black blunders back, then Black slips slightly
+        let evals = [Some(500), Some(100), Some(400), Some(420), Some(470)];
+        let critical = critical_moments(&evals, 4, PlayerColor::White, 5);
+        assert_eq!(
+            critical,
+            [
+                CriticalMoment { ply: 0, kind: CriticalKind::MissedWin, loss: 400 },
+                CriticalMoment { ply: 1, kind: CriticalKind::Swing, loss: 300 },
+            ]
+        );
+        assert_eq!(critical_moments(&evals, 4, PlayerColor::White, 1)[0].ply, 0);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::game::{PgnGame, PgnReader};
+use flate2::read::MultiGzDecoder;
+use std::io::{self, BufRead, BufReader, Read, Seek};
+use std::ops::ControlFlow;
+
+/// Kinds of file PGN games are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgnFile {
+    Plain,
+    /// `.pgn.gz`
+    Gzip,
+    /// ZIP archive of PGN files, as database dumps often come
+    Zip,
+}
+
+impl PgnFile {
+    /// The kind of file going by its name, None when it holds no PGN
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name.ends_with(".pgn") {
+            Some(PgnFile::Plain)
+        } else if name.ends_with(".pgn.gz") {
+            Some(PgnFile::Gzip)
+        } else if name.ends_with(".zip") {
+            Some(PgnFile::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Whether games have to be decompressed, so the file is read as it goes rather than up front
+    pub fn is_archive(self) -> bool {
+        self != PgnFile::Plain
+    }
+
+    /// Pass the games in `file` to `visit` one at a time until it breaks, decompressing as they
+    /// are read so the whole file is never held in memory. Every `.pgn` and `.pgn.gz` inside a
+    /// ZIP archive is read, in the archive's order.
+    pub fn read_games<R: Read + Seek>(self, file: R, mut visit: impl FnMut(PgnGame) -> ControlFlow<()>) -> io::Result<()> {
+        match self {
+            PgnFile::Plain => visit_games(BufReader::new(file), &mut visit).map(drop),
+            PgnFile::Gzip => visit_games(BufReader::new(MultiGzDecoder::new(file)), &mut visit).map(drop),
+            PgnFile::Zip => {
+                let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+                for i in 0..archive.len() {
+                    let entry = archive.by_index(i).map_err(io::Error::other)?;
+                    let flow = match PgnFile::from_name(entry.name()) {
+                        Some(PgnFile::Plain) => visit_games(BufReader::new(entry), &mut visit)?,
+                        Some(PgnFile::Gzip) => visit_games(BufReader::new(MultiGzDecoder::new(entry)), &mut visit)?,
+                        Some(PgnFile::Zip) | None => continue,
+                    };
+                    if flow.is_break() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn visit_games(reader: impl BufRead, visit: &mut impl FnMut(PgnGame) -> ControlFlow<()>) -> io::Result<ControlFlow<()>> {
+    let mut games = PgnReader::new(reader);
+    for game in games.by_ref() {
+        if visit(game).is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+    }
+    match games.take_error() {
+        Some(e) => Err(e),
+        None => Ok(ControlFlow::Continue(())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    const PGN: &str = "[White \"a\"]\n1. e4 e5 1-0\n\n[White \"b\"]\n1. d4 *\n";
+
+    fn white_players(file: PgnFile, bytes: Vec<u8>) -> Vec<String> {
+        let mut players = Vec::new();
+        file.read_games(Cursor::new(bytes), |game| {
+            players.push(game.header("White").unwrap_or_default().to_string());
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        players
+    }
+
+    #[test]
+    fn test_read_compressed_games() {
+        assert_eq!(PgnFile::from_name("Lichess-2013-01.PGN.GZ"), Some(PgnFile::Gzip));
+        assert_eq!(PgnFile::from_name("notes.txt"), None);
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(PGN.as_bytes()).unwrap();
+        assert_eq!(white_players(PgnFile::Gzip, gz.finish().unwrap()), ["a", "b"]);
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("readme.txt", options).unwrap();
+        zip.write_all(b"[White \"not a game\"]\n").unwrap();
+        zip.start_file("2024/games.pgn", options).unwrap();
+        zip.write_all(PGN.as_bytes()).unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+        assert_eq!(white_players(PgnFile::Zip, bytes.clone()), ["a", "b"]);
+
+        // Stopping early reads no further
+        let mut count = 0;
+        PgnFile::Zip
+            .read_games(Cursor::new(bytes), |_| {
+                count += 1;
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_read_concatenated_gzip() {
+        // Files joined with `cat a.gz b.gz` hold one gzip member each
+        let mut bytes = Vec::new();
+        for text in ["[White \"a\"]\n1. e4 *\n\n", "[White \"b\"]\n1. d4 *\n"] {
+            let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+            gz.write_all(text.as_bytes()).unwrap();
+            bytes.extend(gz.finish().unwrap());
+        }
+        assert_eq!(white_players(PgnFile::Gzip, bytes), ["a", "b"]);
+    }
+}
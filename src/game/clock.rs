@@ -0,0 +1,240 @@
+use crate::game::PlayerColor;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Starting time and per-move increment for a timed game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub minutes: u32,
+    pub increment_secs: u32,
+}
+
+impl TimeControl {
+    pub const fn new(minutes: u32, increment_secs: u32) -> Self {
+        Self { minutes, increment_secs }
+    }
+
+    /// Common time controls offered in the game panel
+    pub fn presets() -> &'static [TimeControl] {
+        const PRESETS: [TimeControl; 6] = [
+            TimeControl::new(1, 0),
+            TimeControl::new(3, 2),
+            TimeControl::new(5, 0),
+            TimeControl::new(10, 0),
+            TimeControl::new(15, 10),
+            TimeControl::new(30, 0),
+        ];
+        &PRESETS
+    }
+
+    /// Short label such as "3+2"
+    pub fn label(&self) -> String {
+        format!("{}+{}", self.minutes, self.increment_secs)
+    }
+
+    fn initial(&self) -> Duration {
+        Duration::from_secs(u64::from(self.minutes) * 60)
+    }
+
+    fn increment(&self) -> Duration {
+        Duration::from_secs(u64::from(self.increment_secs))
+    }
+}
+
+/// Thinking time used by each side in the current game, counted against an optional time control
+#[derive(Debug, Clone, Default)]
+pub struct GameClock {
+    time_control: Option<TimeControl>,
+    white: Duration,
+    black: Duration,
+    /// Increments earned by each side
+    white_bonus: Duration,
+    black_bonus: Duration,
+    /// Side whose clock is running and when it was started
+    running: Option<(PlayerColor, Instant)>,
+    /// Thinking time of each move of the game, in order
+    move_times: Vec<Duration>,
+    /// Time used by each side when it last completed a move
+    white_at_last_move: Duration,
+    black_at_last_move: Duration,
+}
+
+impl GameClock {
+    pub fn new(time_control: Option<TimeControl>) -> Self {
+        Self {
+            time_control,
+            ..Self::default()
+        }
+    }
+
+    /// Run the clock for `side`, or stop both clocks with `None`.
+    /// Handing the clock to the other side credits the increment to the side that just moved.
+    pub fn set_running(&mut self, side: Option<PlayerColor>) {
+        if self.running.map(|(color, _)| color) == side {
+            return;
+        }
+        if let Some((color, started)) = self.running.take() {
+            *self.slot(color) += started.elapsed();
+            if let (Some(tc), Some(next)) = (self.time_control, side) {
+                if next != color {
+                    *self.bonus_slot(color) += tc.increment();
+                }
+            }
+        }
+        self.running = side.map(|color| (color, Instant::now()));
+    }
+
+    pub fn running_side(&self) -> Option<PlayerColor> {
+        self.running.map(|(color, _)| color)
+    }
+
+    /// Total time used by `side`, including the move in progress
+    pub fn elapsed(&self, side: PlayerColor) -> Duration {
+        let banked = match side {
+            PlayerColor::White => self.white,
+            PlayerColor::Black => self.black,
+        };
+        match self.running {
+            Some((color, started)) if color == side => banked + started.elapsed(),
+            _ => banked,
+        }
+    }
+
+    /// Time left for `side`, if the game has a time control
+    pub fn remaining(&self, side: PlayerColor) -> Option<Duration> {
+        let tc = self.time_control?;
+        let bonus = match side {
+            PlayerColor::White => self.white_bonus,
+            PlayerColor::Black => self.black_bonus,
+        };
+        Some((tc.initial() + bonus).saturating_sub(self.elapsed(side)))
+    }
+
+    /// Time to show on the clock: remaining time when timed, otherwise time used
+    pub fn display(&self, side: PlayerColor) -> Duration {
+        self.remaining(side).unwrap_or_else(|| self.elapsed(side))
+    }
+
+    /// The side whose time has run out, if any
+    pub fn flagged(&self) -> Option<PlayerColor> {
+        [PlayerColor::White, PlayerColor::Black]
+            .into_iter()
+            .find(|side| self.remaining(*side) == Some(Duration::ZERO))
+    }
+
+    /// Keep one thinking time per move of a game that has `moves` moves. New moves are credited to
+    /// the side whose clock is running; taken-back moves are forgotten.
+    pub fn sync_moves(&mut self, moves: usize) {
+        self.move_times.truncate(moves);
+        while self.move_times.len() < moves {
+            let spent = match self.running_side() {
+                Some(side) => {
+                    let used = self.elapsed(side);
+                    let last = match side {
+                        PlayerColor::White => &mut self.white_at_last_move,
+                        PlayerColor::Black => &mut self.black_at_last_move,
+                    };
+                    let spent = used.saturating_sub(*last);
+                    *last = used;
+                    spent
+                }
+                None => Duration::ZERO,
+            };
+            self.move_times.push(spent);
+        }
+    }
+
+    pub fn move_times(&self) -> &[Duration] {
+        &self.move_times
+    }
+
+    fn slot(&mut self, side: PlayerColor) -> &mut Duration {
+        match side {
+            PlayerColor::White => &mut self.white,
+            PlayerColor::Black => &mut self.black,
+        }
+    }
+
+    fn bonus_slot(&mut self, side: PlayerColor) -> &mut Duration {
+        match side {
+            PlayerColor::White => &mut self.white_bonus,
+            PlayerColor::Black => &mut self.black_bonus,
+        }
+    }
+}
+
+/// Format a duration as m:ss, or h:mm:ss past an hour
+pub fn format_clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Format a duration as h:mm:ss, as in a PGN `%clk` comment
+pub fn pgn_clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_switches_sides() {
+        let mut clock = GameClock::default();
+        clock.set_running(Some(PlayerColor::White));
+        std::thread::sleep(Duration::from_millis(20));
+        clock.set_running(Some(PlayerColor::Black));
+        let white = clock.elapsed(PlayerColor::White);
+        assert!(white >= Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.elapsed(PlayerColor::White), white);
+        clock.set_running(None);
+        assert!(clock.running_side().is_none());
+        assert!(clock.elapsed(PlayerColor::Black) >= Duration::from_millis(5));
+        assert_eq!(clock.remaining(PlayerColor::White), None);
+    }
+
+    #[test]
+    fn test_time_control_increment_and_flag() {
+        let mut clock = GameClock::new(Some(TimeControl::new(1, 2)));
+        clock.set_running(Some(PlayerColor::White));
+        clock.set_running(Some(PlayerColor::Black));
+        // White moved almost instantly and gained the increment
+        assert!(clock.remaining(PlayerColor::White) > Some(Duration::from_secs(61)));
+        assert_eq!(clock.flagged(), None);
+
+        clock.black = Duration::from_secs(60);
+        assert_eq!(clock.flagged(), Some(PlayerColor::Black));
+    }
+
+    #[test]
+    fn test_move_times() {
+        let mut clock = GameClock::default();
+        clock.set_running(Some(PlayerColor::White));
+        clock.white = Duration::from_secs(3);
+        clock.sync_moves(1);
+        clock.set_running(Some(PlayerColor::Black));
+        clock.black = Duration::from_secs(5);
+        clock.sync_moves(2);
+        clock.set_running(Some(PlayerColor::White));
+        clock.white = Duration::from_secs(4);
+        clock.sync_moves(3);
+        let secs: Vec<f32> = clock.move_times().iter().map(|d| d.as_secs_f32().round()).collect();
+        assert_eq!(secs, [3.0, 5.0, 1.0]);
+
+        clock.sync_moves(1);
+        assert_eq!(clock.move_times().len(), 1);
+    }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(Duration::from_secs(65)), "1:05");
+        assert_eq!(format_clock(Duration::from_secs(3725)), "1:02:05");
+        assert_eq!(pgn_clock(Duration::from_secs(65)), "0:01:05");
+    }
+}
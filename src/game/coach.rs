@@ -0,0 +1,176 @@
+use crate::game::GameState;
+use crate::i18n::{tr, tr_args};
+use shakmaty::{Bitboard, File, Position, Role, Square};
+
+/// Most pieces reported as in danger, so the hints stay readable
+const MAX_THREATS: usize = 2;
+
+/// A plain-language observation about a position, for players who don't read evaluations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    /// A piece of the side to move can be taken for nothing
+    Hanging { role: Role, square: Square },
+    /// A piece of the side to move is attacked by a cheaper one
+    AttackedByLesser { role: Role, square: Square, attacker: Role },
+    Castle,
+    Promote { square: Square },
+    Capture { role: Role, square: Square },
+    Check { role: Role },
+    /// The engine's move leaves a file without pawns
+    OpenFile { file: File },
+    PieceMove { role: Role, square: Square },
+    PawnPush { square: Square },
+}
+
+impl Hint {
+    pub fn text(&self) -> String {
+        match *self {
+            Hint::Hanging { role, square } => tr_args("coach.hanging", &[&role_name(role), &square]),
+            Hint::AttackedByLesser { role, square, attacker } => {
+                tr_args("coach.attacked_by_lesser", &[&role_name(role), &square, &role_name(attacker)])
+            }
+            Hint::Castle => tr("coach.castle").to_string(),
+            Hint::Promote { square } => tr_args("coach.promote", &[&square]),
+            Hint::Capture { role, square } => tr_args("coach.capture", &[&role_name(role), &square]),
+            Hint::Check { role } => tr_args("coach.check", &[&role_name(role)]),
+            Hint::OpenFile { file } => tr_args("coach.open_file", &[&file.char()]),
+            Hint::PieceMove { role, square } => tr_args("coach.piece_move", &[&role_name(role), &square]),
+            Hint::PawnPush { square } => tr_args("coach.pawn_push", &[&square]),
+        }
+    }
+}
+
+fn role_name(role: Role) -> &'static str {
+    match role {
+        Role::King => tr("speech.king"),
+        Role::Queen => tr("speech.queen"),
+        Role::Rook => tr("speech.rook"),
+        Role::Bishop => tr("speech.bishop"),
+        Role::Knight => tr("speech.knight"),
+        Role::Pawn => tr("speech.pawn"),
+    }
+}
+
+fn value(role: Role) -> u32 {
+    match role {
+        Role::Pawn => 1,
+        Role::Knight | Role::Bishop => 3,
+        Role::Rook => 5,
+        Role::Queen => 9,
+        Role::King => 100,
+    }
+}
+
+/// Files without a pawn of either color
+fn open_files(game: &GameState) -> Vec<File> {
+    let pawns = game.current_position().board().by_role(Role::Pawn);
+    File::ALL
+        .into_iter()
+        .filter(|&file| (pawns & Bitboard::from_file(file)).is_empty())
+        .collect()
+}
+
+/// Pieces of the side to move that are en prise or attacked by something cheaper, most valuable first
+fn threats(game: &GameState) -> Vec<Hint> {
+    let position = game.current_position();
+    let board = position.board();
+    let us = position.turn();
+    let occupied = board.occupied();
+    let mut threats: Vec<(u32, Hint)> = (board.by_color(us) & !board.by_role(Role::King))
+        .into_iter()
+        .filter_map(|square| {
+            let role = board.role_at(square)?;
+            let attackers = board.attacks_to(square, !us, occupied);
+            let cheapest = attackers.into_iter().filter_map(|sq| board.role_at(sq)).min_by_key(|r| value(*r))?;
+            let hint = if board.attacks_to(square, us, occupied).is_empty() {
+                Hint::Hanging { role, square }
+            } else if value(cheapest) < value(role) {
+                Hint::AttackedByLesser { role, square, attacker: cheapest }
+            } else {
+                return None;
+            };
+            Some((value(role), hint))
+        })
+        .collect();
+    threats.sort_by_key(|(value, _)| std::cmp::Reverse(*value));
+    threats.into_iter().take(MAX_THREATS).map(|(_, hint)| hint).collect()
+}
+
+/// What the engine's first move `uci` in the position `fen` is about
+fn plan(fen: &str, uci: &str) -> Vec<Hint> {
+    let Ok(game) = GameState::from_fen(fen) else {
+        return Vec::new();
+    };
+    let Ok(m) = game.parse_move(uci) else {
+        return Vec::new();
+    };
+    let Ok(mut after) = GameState::from_fen(fen) else {
+        return Vec::new();
+    };
+    if after.make_move(m).is_err() {
+        return Vec::new();
+    }
+
+    let mut hints = vec![if m.is_castle() {
+        Hint::Castle
+    } else if m.promotion().is_some() {
+        Hint::Promote { square: m.to() }
+    } else if let Some(role) = m.capture() {
+        Hint::Capture { role, square: m.to() }
+    } else if after.is_check() {
+        Hint::Check { role: m.role() }
+    } else if m.role() == Role::Pawn {
+        Hint::PawnPush { square: m.to() }
+    } else {
+        Hint::PieceMove { role: m.role(), square: m.to() }
+    }];
+    let before = open_files(&game);
+    hints.extend(open_files(&after).into_iter().filter(|f| !before.contains(f)).map(|file| Hint::OpenFile { file }));
+    hints
+}
+
+/// Hints for the position `fen`: pieces of the side to move in danger, then what the
+/// engine's best line `pv` (in UCI) sets out to do
+pub fn coach_hints(fen: &str, pv: &[String]) -> Vec<Hint> {
+    let Ok(game) = GameState::from_fen(fen) else {
+        return Vec::new();
+    };
+    let mut hints = threats(&game);
+    if let Some(first) = pv.first() {
+        hints.extend(plan(fen, first));
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn square(name: &str) -> Square {
+        Square::from_str(name).unwrap()
+    }
+
+    #[test]
+    fn test_coach_hints() {
+        // White's knight on b4 hangs to the queen on b6; the engine saves it
+        let fen = "4k3/8/1q6/8/1N6/8/8/4K3 w - - 0 1";
+        let hints = coach_hints(fen, &["b4d5".to_string()]);
+        assert_eq!(hints[0], Hint::Hanging { role: Role::Knight, square: square("b4") });
+        assert_eq!(hints[1], Hint::PieceMove { role: Role::Knight, square: square("d5") });
+
+        // A defended queen attacked by a pawn
+        let fen = "4k3/8/8/2p5/3Q4/4P3/8/4K3 w - - 0 1";
+        let hints = coach_hints(fen, &[]);
+        assert_eq!(
+            hints,
+            vec![Hint::AttackedByLesser { role: Role::Queen, square: square("d4"), attacker: Role::Pawn }]
+        );
+
+        // exd5 leaves the e-file without pawns
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+        let hints = coach_hints(fen, &["e4d5".to_string()]);
+        assert!(hints.contains(&Hint::Capture { role: Role::Pawn, square: square("d5") }));
+        assert!(hints.contains(&Hint::OpenFile { file: File::E }));
+    }
+}
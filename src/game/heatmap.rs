@@ -0,0 +1,84 @@
+use crate::i18n::tr;
+use shakmaty::{attacks, Bitboard, Board};
+
+/// What a square heatmap counts over the positions of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapKind {
+    /// Positions in which a piece stood on the square
+    Occupancy,
+    /// Positions in which any piece attacked the square
+    Attacks,
+}
+
+impl HeatmapKind {
+    pub fn all() -> &'static [HeatmapKind] {
+        &[HeatmapKind::Occupancy, HeatmapKind::Attacks]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeatmapKind::Occupancy => tr("heatmap.occupancy"),
+            HeatmapKind::Attacks => tr("heatmap.attacks"),
+        }
+    }
+}
+
+/// Per-square counts over a sequence of positions
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    counts: [u32; 64],
+}
+
+impl Heatmap {
+    pub fn compute<'a>(kind: HeatmapKind, boards: impl Iterator<Item = &'a Board>) -> Self {
+        let mut counts = [0u32; 64];
+        for board in boards {
+            let marked = match kind {
+                HeatmapKind::Occupancy => board.occupied(),
+                HeatmapKind::Attacks => board
+                    .clone()
+                    .into_iter()
+                    .map(|(square, piece)| attacks::attacks(square, piece, board.occupied()))
+                    .fold(Bitboard::EMPTY, |all, attacked| all | attacked),
+            };
+            for square in marked {
+                counts[usize::from(square)] += 1;
+            }
+        }
+        Self { counts }
+    }
+
+    /// Intensity of each square from 0 (never) to 1 (the busiest square)
+    pub fn intensities(&self) -> [f32; 64] {
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let mut intensities = [0.0; 64];
+        for (intensity, count) in intensities.iter_mut().zip(self.counts) {
+            *intensity = count as f32 / max;
+        }
+        intensities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shakmaty::Square;
+
+    #[test]
+    fn test_occupancy_counts_every_position() {
+        let start = Board::default();
+        let heatmap = Heatmap::compute(HeatmapKind::Occupancy, [&start, &start].into_iter());
+        let intensities = heatmap.intensities();
+        assert_eq!(intensities[usize::from(Square::E1)], 1.0);
+        assert_eq!(intensities[usize::from(Square::E4)], 0.0);
+    }
+
+    #[test]
+    fn test_attacks_from_start() {
+        let heatmap = Heatmap::compute(HeatmapKind::Attacks, std::iter::once(&Board::default()));
+        let intensities = heatmap.intensities();
+        // Third-rank squares are covered by pawns and knights, the centre is not yet attacked
+        assert!(intensities[usize::from(Square::C3)] > 0.0);
+        assert_eq!(intensities[usize::from(Square::E4)], 0.0);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::game::{fen_ply, numbered_line};
+
+/// Board options shared by every snippet
+fn board_options(flipped: bool) -> &'static str {
+    if flipped {
+        "showmover=true, inverse"
+    } else {
+        "showmover=true"
+    }
+}
+
+/// `chessboard` snippet drawing the position `fen`, Black at the bottom when `flipped`
+pub fn latex_diagram(fen: &str, flipped: bool) -> String {
+    format!("% \\usepackage{{chessboard}}\n\\chessboard[setfen={}, {}]\n", fen, board_options(flipped))
+}
+
+/// `xskak` snippet playing the SAN moves `sans` from `start_fen`, then drawing the position
+/// they lead to
+pub fn latex_line(start_fen: &str, sans: &[String], flipped: bool) -> String {
+    format!(
+        "% \\usepackage{{xskak}}\n\\newchessgame[setfen={}]\n\\mainline{{{}}}\n\n\\chessboard[{}]\n",
+        start_fen,
+        numbered_line(fen_ply(start_fen), sans),
+        board_options(flipped)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latex_snippets() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(
+            latex_diagram(start, true),
+            format!("% \\usepackage{{chessboard}}\n\\chessboard[setfen={}, showmover=true, inverse]\n", start)
+        );
+
+        let sans = ["e4", "e5", "Nf3"].map(String::from);
+        assert!(latex_line(start, &sans, false).contains("\\mainline{1. e4 e5 2. Nf3}\n"));
+        let black = "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3";
+        let sans = ["Nf6", "d3"].map(String::from);
+        assert!(latex_line(black, &sans, false).contains("\\mainline{3... Nf6 4. d3}"));
+    }
+}
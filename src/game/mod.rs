@@ -1,3 +1,31 @@
+mod annotation;
+mod archive;
+mod clock;
+mod coach;
+mod heatmap;
+mod latex;
+mod notation;
+mod pgn;
+mod phase;
+mod premove;
+mod report;
+mod staged;
 mod state;
+mod takeback;
 
-pub use state::{GameState, GameOutcome, PlayerColor, MoveRecord};
+pub use archive::PgnFile;
+pub use annotation::{critical_moments, eval_loss, CriticalKind, CriticalMoment, Nag, QualitySummary, MISTAKE_CP};
+pub use coach::coach_hints;
+pub use clock::{format_clock, pgn_clock, GameClock, TimeControl};
+pub use heatmap::{Heatmap, HeatmapKind};
+pub use latex::{latex_diagram, latex_line};
+pub use notation::{fen_ply, move_number, numbered_line, spoken, Notation, PgnStyle};
+pub use pgn::{parse_pgn, PgnGame, PgnReader};
+pub use phase::{game_phases, GamePhase};
+pub use premove::{premove_position, PremoveQueue, PremoveStep};
+pub use report::{GameReport, ReportFormat};
+pub(crate) use report::{escape_html, svg_diagram};
+pub use staged::StagedMove;
+pub use takeback::TakebackPolicy;
+
+pub use state::{position_key, GameError, GameState, GameOutcome, PlayerColor, MoveRecord};
@@ -0,0 +1,259 @@
+use crate::game::{MoveRecord, Nag};
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// How moves are rendered in the move list, analysis lines, and study tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Notation {
+    /// Standard algebraic notation (Nf3)
+    #[default]
+    San,
+    /// SAN with Unicode piece symbols (♘f3)
+    FigurineSan,
+    /// Long algebraic notation with origin square (Ng1-f3)
+    LongAlgebraic,
+    /// Raw UCI coordinates (g1f3)
+    Uci,
+}
+
+impl Notation {
+    pub fn all() -> &'static [Notation] {
+        &[
+            Notation::San,
+            Notation::FigurineSan,
+            Notation::LongAlgebraic,
+            Notation::Uci,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Notation::San => tr("notation.san"),
+            Notation::FigurineSan => tr("notation.figurine"),
+            Notation::LongAlgebraic => tr("notation.long"),
+            Notation::Uci => tr("notation.uci"),
+        }
+    }
+
+    /// Render a move in this notation
+    pub fn format(&self, record: &MoveRecord) -> String {
+        match self {
+            Notation::San => record.san.clone(),
+            Notation::FigurineSan => figurine(&record.san),
+            Notation::LongAlgebraic => long_algebraic(record),
+            Notation::Uci => record.uci.clone(),
+        }
+    }
+}
+
+/// How moves are written in exported PGN. The default is strict standard SAN with `$n`
+/// annotation glyphs, which every PGN reader understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PgnStyle {
+    /// Unicode piece symbols instead of piece letters
+    pub figurine: bool,
+    /// Symbols such as `?!` instead of numeric annotation glyphs
+    pub nag_glyphs: bool,
+}
+
+impl PgnStyle {
+    /// A SAN move with an optional annotation glyph
+    pub fn move_text(&self, san: &str, nag: Option<Nag>) -> String {
+        let mut text = if self.figurine { figurine(san) } else { san.to_string() };
+        if let Some(nag) = nag {
+            if self.nag_glyphs {
+                text.push_str(nag.glyph());
+            } else {
+                text.push_str(&format!(" ${}", nag.code()));
+            }
+        }
+        text
+    }
+}
+
+fn figurine_symbol(piece: char) -> Option<char> {
+    match piece {
+        'K' => Some('♔'),
+        'Q' => Some('♕'),
+        'R' => Some('♖'),
+        'B' => Some('♗'),
+        'N' => Some('♘'),
+        _ => None,
+    }
+}
+
+/// Replace SAN piece letters with figurines (files are lowercase, so only pieces are uppercase)
+fn figurine(san: &str) -> String {
+    san.chars().map(|c| figurine_symbol(c).unwrap_or(c)).collect()
+}
+
+/// A SAN move as words for text-to-speech, e.g. "Nxf3+" as "knight takes f3, check"
+pub fn spoken(san: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let body = san.trim_end_matches(['+', '#']);
+    if body.starts_with("O-O-O") {
+        words.push(tr("speech.castle_long").to_string());
+    } else if body.starts_with("O-O") {
+        words.push(tr("speech.castle_short").to_string());
+    } else {
+        let (body, promotion) = body.split_once('=').unwrap_or((body, ""));
+        let mut chars = body.chars().peekable();
+        if let Some(piece) = chars.next_if(|c| c.is_ascii_uppercase()) {
+            words.push(piece_name(piece).to_string());
+        }
+        let mut squares = String::new();
+        for c in chars {
+            if c == 'x' {
+                if !squares.is_empty() {
+                    words.push(std::mem::take(&mut squares));
+                }
+                words.push(tr("speech.takes").to_string());
+            } else {
+                squares.push(c);
+            }
+        }
+        words.push(squares);
+        if let Some(piece) = promotion.chars().next() {
+            words.push(tr("speech.promotes").to_string());
+            words.push(piece_name(piece).to_string());
+        }
+    }
+    let mut text = words.join(" ");
+    if san.ends_with('#') {
+        text.push_str(&format!(", {}", tr("speech.mate")));
+    } else if san.ends_with('+') {
+        text.push_str(&format!(", {}", tr("speech.check")));
+    }
+    text
+}
+
+fn piece_name(letter: char) -> &'static str {
+    match letter {
+        'K' => tr("speech.king"),
+        'Q' => tr("speech.queen"),
+        'R' => tr("speech.rook"),
+        'B' => tr("speech.bishop"),
+        _ => tr("speech.knight"),
+    }
+}
+
+/// Ply of the game the position `fen` is at, 0 before White's first move
+pub fn fen_ply(fen: &str) -> usize {
+    let mut fields = fen.split_whitespace().skip(1);
+    let black = fields.next() == Some("b");
+    let number: usize = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(1);
+    number.saturating_sub(1) * 2 + usize::from(black)
+}
+
+/// Number written before the move at `index` of a line whose first move is ply `first_ply` of
+/// the game: "12." before White's moves, "12..." before a first move by Black, none before
+/// Black's other moves
+pub fn move_number(first_ply: usize, index: usize) -> Option<String> {
+    let ply = first_ply + index;
+    let number = ply / 2 + 1;
+    if ply % 2 == 0 {
+        Some(format!("{}.", number))
+    } else if index == 0 {
+        Some(format!("{}...", number))
+    } else {
+        None
+    }
+}
+
+/// The SAN moves `sans` with their move numbers, like "1. e4 c5 2. Nf3", the first one being
+/// ply `first_ply` of the game
+pub fn numbered_line<S: AsRef<str>>(first_ply: usize, sans: &[S]) -> String {
+    let mut text = String::new();
+    for (i, san) in sans.iter().enumerate() {
+        if let Some(number) = move_number(first_ply, i) {
+            text.push_str(&number);
+            text.push(' ');
+        }
+        text.push_str(san.as_ref());
+        text.push(' ');
+    }
+    text.truncate(text.trim_end().len());
+    text
+}
+
+fn long_algebraic(record: &MoveRecord) -> String {
+    let san = &record.san;
+    let uci = &record.uci;
+    if san.starts_with("O-O") || uci.len() < 4 {
+        return san.clone();
+    }
+
+    let mut text = String::new();
+    if let Some(piece) = san.chars().next().filter(|c| "KQRBN".contains(*c)) {
+        text.push(piece);
+    }
+    text.push_str(&uci[0..2]);
+    text.push(if san.contains('x') { 'x' } else { '-' });
+    text.push_str(&uci[2..4]);
+    if let Some(promotion) = uci.chars().nth(4) {
+        text.push('=');
+        text.push(promotion.to_ascii_uppercase());
+    }
+    text.extend(san.chars().filter(|c| *c == '+' || *c == '#'));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(san: &str, uci: &str) -> MoveRecord {
+        MoveRecord {
+            san: san.to_string(),
+            uci: uci.to_string(),
+            resulting_fen: String::new(),
+            time_spent: None,
+            clock_remaining: None,
+        }
+    }
+
+    #[test]
+    fn test_figurine() {
+        assert_eq!(Notation::FigurineSan.format(&record("Nxf3+", "g1f3")), "♘xf3+");
+        assert_eq!(Notation::FigurineSan.format(&record("e8=Q", "e7e8q")), "e8=♕");
+        assert_eq!(Notation::FigurineSan.format(&record("O-O", "e1g1")), "O-O");
+    }
+
+    #[test]
+    fn test_pgn_style() {
+        let standard = PgnStyle::default();
+        assert_eq!(standard.move_text("Nf3", Some(Nag::Dubious)), "Nf3 $6");
+        let pretty = PgnStyle {
+            figurine: true,
+            nag_glyphs: true,
+        };
+        assert_eq!(pretty.move_text("Nf3", Some(Nag::Dubious)), "♘f3?!");
+        assert_eq!(pretty.move_text("e4", None), "e4");
+    }
+
+    #[test]
+    fn test_spoken() {
+        assert_eq!(spoken("Nxf3+"), "knight takes f3, check");
+        assert_eq!(spoken("exd8=Q#"), "e takes d8 promotes queen, checkmate");
+        assert_eq!(spoken("O-O-O"), "castles queenside");
+        assert_eq!(spoken("e4"), "e4");
+    }
+
+    #[test]
+    fn test_long_algebraic() {
+        assert_eq!(Notation::LongAlgebraic.format(&record("Nf3", "g1f3")), "Ng1-f3");
+        assert_eq!(Notation::LongAlgebraic.format(&record("exd5", "e4d5")), "e4xd5");
+        assert_eq!(Notation::LongAlgebraic.format(&record("bxa8=Q#", "b7a8q")), "b7xa8=Q#");
+        assert_eq!(Notation::LongAlgebraic.format(&record("O-O-O", "e1c1")), "O-O-O");
+    }
+
+    #[test]
+    fn test_numbered_line() {
+        assert_eq!(numbered_line(0, &["e4", "c5", "Nf3"]), "1. e4 c5 2. Nf3");
+        let black = "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3";
+        assert_eq!(fen_ply(black), 5);
+        assert_eq!(numbered_line(fen_ply(black), &["Nf6", "d3", "Bc5"]), "3... Nf6 4. d3 Bc5");
+        assert_eq!(numbered_line::<&str>(0, &[]), "");
+    }
+}
@@ -0,0 +1,403 @@
+use crate::game::{GameError, GameState};
+use std::io::BufRead;
+
+/// Tokens that end a game's movetext
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// One game read from a PGN file: its tag pairs and main-line moves in SAN.
+/// Comments, variations and annotation glyphs are skipped in `moves`, and kept in `movetext`.
+#[derive(Debug, Clone, Default)]
+pub struct PgnGame {
+    pub headers: Vec<(String, String)>,
+    pub moves: Vec<String>,
+    /// The game's movetext as read, empty for games made in the app
+    pub movetext: String,
+    /// White-relative centipawn evaluations by position index from the `[%eval]` comments on
+    /// main-line moves, as Lichess writes them into analysed games, mates as ±10000. Empty when
+    /// the game has none.
+    pub evals: Vec<Option<i32>>,
+}
+
+/// Games are the same when their tags and moves are, however their movetext was written
+impl PartialEq for PgnGame {
+    fn eq(&self, other: &Self) -> bool {
+        self.headers == other.headers && self.moves == other.moves
+    }
+}
+
+impl Eq for PgnGame {}
+
+impl PgnGame {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// "White - Black" when both players are known, otherwise the event
+    pub fn title(&self) -> Option<String> {
+        let known = |name| self.header(name).filter(|v| !v.is_empty() && *v != "?");
+        match (known("White"), known("Black")) {
+            (Some(white), Some(black)) => Some(format!("{} - {}", white, black)),
+            _ => known("Event").map(str::to_string),
+        }
+    }
+
+    /// Whether any tag value contains `filter`, ignoring case; an empty filter matches every game
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty() || self.headers.iter().any(|(_, value)| value.to_lowercase().contains(&filter))
+    }
+
+    /// Identifies the same game imported twice: a hash of the players, date and moves
+    pub fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for name in ["White", "Black", "Date"] {
+            self.header(name).map(str::trim).hash(&mut hasher);
+        }
+        self.moves.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Write the game back out as PGN text, with its movetext as read if it was
+    pub fn to_pgn(&self) -> String {
+        if self.movetext.trim().is_empty() {
+            return self.write_pgn(None);
+        }
+        let mut pgn = self.tags();
+        let movetext = self.movetext.trim();
+        pgn.push_str(movetext);
+        if !RESULTS.iter().any(|result| movetext.ends_with(result)) {
+            pgn.push(' ');
+            pgn.push_str(self.header("Result").unwrap_or("*"));
+        }
+        pgn.push('\n');
+        pgn
+    }
+
+    /// PGN text with `comment` after the first `ply` moves, before any move when `ply` is 0
+    pub fn to_pgn_with_comment(&self, ply: usize, comment: &str) -> String {
+        self.write_pgn(Some((ply, comment)))
+    }
+
+    fn write_pgn(&self, comment: Option<(usize, &str)>) -> String {
+        // A brace would end the comment early
+        let comment = comment.map(|(ply, text)| (ply, format!("{{{}}} ", text.replace('}', ")"))));
+        let mut pgn = self.tags();
+        let black_first = self.header("FEN").is_some_and(|fen| fen.split_whitespace().nth(1) == Some("b"));
+        for (i, san) in self.moves.iter().enumerate() {
+            let commented = match &comment {
+                Some((at, text)) if *at == i => {
+                    pgn.push_str(text);
+                    true
+                }
+                _ => false,
+            };
+            let ply = i + usize::from(black_first);
+            if ply % 2 == 0 {
+                pgn.push_str(&format!("{}. ", ply / 2 + 1));
+            } else if i == 0 || commented {
+                pgn.push_str(&format!("{}... ", ply / 2 + 1));
+            }
+            pgn.push_str(san);
+            pgn.push(' ');
+        }
+        if let Some((at, text)) = &comment {
+            if *at >= self.moves.len() {
+                pgn.push_str(text);
+            }
+        }
+        pgn.push_str(self.header("Result").unwrap_or("*"));
+        pgn.push('\n');
+        pgn
+    }
+
+    /// The tag pairs and the blank line before the movetext
+    fn tags(&self) -> String {
+        let mut pgn = String::new();
+        for (name, value) in &self.headers {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+        pgn.push('\n');
+        pgn
+    }
+
+    /// Replay the game from its `FEN` tag, or the standard start position
+    pub fn to_game(&self) -> Result<GameState, GameError> {
+        let mut game = match self.header("FEN") {
+            Some(fen) => GameState::from_fen(fen)?,
+            None => GameState::new(),
+        };
+        for san in &self.moves {
+            game.make_move_san(san)?;
+        }
+        Ok(game)
+    }
+}
+
+/// Split PGN text into games. Text without any moves or tags yields no games.
+pub fn parse_pgn(text: &str) -> Vec<PgnGame> {
+    let mut parser = PgnParser::default();
+    let mut games = Vec::new();
+    for line in text.lines() {
+        parser.feed_line(line, &mut games);
+    }
+    games.extend(parser.finish());
+    games
+}
+
+/// Reads games one at a time from PGN text too large to hold in memory at once
+pub struct PgnReader<R> {
+    reader: R,
+    parser: PgnParser,
+    /// Games finished by the last line read
+    ready: std::collections::VecDeque<PgnGame>,
+    line: Vec<u8>,
+    done: bool,
+    error: Option<std::io::Error>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: PgnParser::default(),
+            ready: std::collections::VecDeque::new(),
+            line: Vec::new(),
+            done: false,
+            error: None,
+        }
+    }
+
+    /// The read error that ended the games early, if any
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = PgnGame;
+
+    /// The next game; a read error ends the games like the end of the text
+    fn next(&mut self) -> Option<PgnGame> {
+        while self.ready.is_empty() && !self.done {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(n) if n > 0 => {
+                    // Old databases are often Latin-1, whose names would otherwise stop the import
+                    let line = String::from_utf8_lossy(&self.line);
+                    let mut games = Vec::new();
+                    self.parser.feed_line(&line, &mut games);
+                    self.ready.extend(games);
+                }
+                result => {
+                    self.error = result.err();
+                    self.done = true;
+                    self.ready.extend(std::mem::take(&mut self.parser).finish());
+                }
+            }
+        }
+        self.ready.pop_front()
+    }
+}
+
+/// State of PGN parsing between lines
+#[derive(Default)]
+struct PgnParser {
+    current: PgnGame,
+    in_comment: bool,
+    /// Text of the comment being read
+    comment: String,
+    variation_depth: usize,
+}
+
+impl PgnParser {
+    /// Parse one line, adding any games it finishes to `games`
+    fn feed_line(&mut self, line: &str, games: &mut Vec<PgnGame>) {
+        let trimmed = line.trim();
+        // An Event tag always opens a game, even after a comment or variation that was cut off
+        if trimmed.starts_with("[Event ") {
+            self.in_comment = false;
+            self.comment.clear();
+            self.variation_depth = 0;
+        }
+        if !self.in_comment && self.variation_depth == 0 && trimmed.starts_with('[') {
+            // A tag after movetext starts the next game
+            if !self.current.moves.is_empty() {
+                games.push(std::mem::take(&mut self.current));
+            }
+            if let Some(tag) = parse_tag(trimmed) {
+                self.current.headers.push(tag);
+            }
+            return;
+        }
+        if trimmed.starts_with('%') {
+            return;
+        }
+        self.current.movetext.push_str(line);
+        self.current.movetext.push('\n');
+
+        let mut token = String::new();
+        for c in line.chars() {
+            if self.in_comment {
+                self.in_comment = c != '}';
+                if self.in_comment {
+                    self.comment.push(c);
+                } else if self.variation_depth == 0 {
+                    record_eval(&std::mem::take(&mut self.comment), &mut self.current);
+                } else {
+                    self.comment.clear();
+                }
+                continue;
+            }
+            match c {
+                '{' => {
+                    finish_token(&mut token, self.variation_depth, &mut self.current, games);
+                    self.in_comment = true;
+                }
+                // Rest-of-line comment
+                ';' => break,
+                '(' => {
+                    finish_token(&mut token, self.variation_depth, &mut self.current, games);
+                    self.variation_depth += 1;
+                }
+                ')' => {
+                    finish_token(&mut token, self.variation_depth, &mut self.current, games);
+                    self.variation_depth = self.variation_depth.saturating_sub(1);
+                }
+                c if c.is_whitespace() => finish_token(&mut token, self.variation_depth, &mut self.current, games),
+                c => token.push(c),
+            }
+        }
+        finish_token(&mut token, self.variation_depth, &mut self.current, games);
+    }
+
+    /// The game left unfinished at the end of the text
+    fn finish(self) -> Option<PgnGame> {
+        (!self.current.moves.is_empty() || !self.current.headers.is_empty()).then_some(self.current)
+    }
+}
+
+/// Note the `[%eval]` in a main-line comment as the evaluation after the last move read
+fn record_eval(comment: &str, current: &mut PgnGame) {
+    let Some(value) = comment.split_once("[%eval ").and_then(|(_, rest)| rest.split(']').next()) else {
+        return;
+    };
+    let value = value.trim();
+    let eval = match value.strip_prefix('#') {
+        Some(mate) => mate.parse::<i32>().ok().map(|mate| if mate > 0 { 10000 } else { -10000 }),
+        None => value.parse::<f32>().ok().map(|pawns| (pawns * 100.0).round() as i32),
+    };
+    let index = current.moves.len();
+    if let (Some(eval), true) = (eval, index > 0) {
+        if current.evals.len() <= index {
+            current.evals.resize(index + 1, None);
+        }
+        current.evals[index] = Some(eval);
+    }
+}
+
+/// `[Name "Value"]` to (name, value)
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), value.replace("\\\"", "\"").replace("\\\\", "\\")))
+}
+
+/// Add a finished movetext token to the current game, ending the game on a result
+fn finish_token(token: &mut String, variation_depth: usize, current: &mut PgnGame, games: &mut Vec<PgnGame>) {
+    let text = std::mem::take(token);
+    if variation_depth > 0 || text.is_empty() || text.starts_with('$') {
+        return;
+    }
+    if RESULTS.contains(&text.as_str()) {
+        if current.header("Result").is_none() {
+            current.headers.push(("Result".to_string(), text));
+        }
+        games.push(std::mem::take(current));
+        return;
+    }
+
+    // Move numbers may be glued to the move ("12.Nf3", "12...Nf3")
+    let san = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let san = san.trim_end_matches(['!', '?']);
+    if !san.is_empty() {
+        current.moves.push(san.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pgn() {
+        let text = r#"[Event "Casual"]
+[White "Me"]
+
+1. e4 {best by test} e5 2.Nf3 (2. f4 exf4) Nc6?! $6 3... ; comment
+Bb5 1-0
+
+[Event "Second"]
+1. d4 d5 *
+"#;
+        let games = parse_pgn(text);
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].header("White"), Some("Me"));
+        assert_eq!(games[0].header("Result"), Some("1-0"));
+        assert_eq!(games[0].moves, ["e4", "e5", "Nf3", "Nc6", "Bb5"]);
+        assert_eq!(games[1].moves, ["d4", "d5"]);
+
+        let game = games[0].to_game().unwrap();
+        assert_eq!(game.move_history().len(), 5);
+
+        let round_trip = parse_pgn(&games[0].to_pgn());
+        assert_eq!(round_trip, vec![games[0].clone()]);
+
+        assert!(games[0].matches("me"));
+        assert!(games[0].matches(" "));
+        assert!(!games[1].matches("me"));
+    }
+
+    #[test]
+    fn test_pgn_with_comment() {
+        let game = PgnGame {
+            headers: vec![("Result".to_string(), "*".to_string())],
+            moves: vec!["e4".to_string(), "e5".to_string(), "Nf3".to_string()],
+            ..Default::default()
+        };
+        let pgn = game.to_pgn_with_comment(1, "SF: +0.30 1... e5 {sic}");
+        assert!(pgn.ends_with("1. e4 {SF: +0.30 1... e5 {sic)} 1... e5 2. Nf3 *\n"));
+        assert!(game.to_pgn_with_comment(0, "start").contains("\n{start} 1. e4"));
+        assert!(game.to_pgn_with_comment(3, "end").ends_with("Nf3 {end} *\n"));
+        assert_eq!(parse_pgn(&pgn)[0].moves, game.moves);
+    }
+
+    #[test]
+    fn test_evals() {
+        let text = "[Event \"Rated\"]\n\n1. e4 { [%eval 0.3] [%clk 0:03:00] } 1... c5 { [%eval 0.35] } 2. Nf3 ( 2. c3 { [%eval 0.1] } )\n2... d6 { [%eval #-3] } 1-0\n";
+        let game = &parse_pgn(text)[0];
+        assert_eq!(game.evals, [None, Some(30), Some(35), None, Some(-10000)]);
+
+        // Read games are written back as they were, keeping their evaluations
+        let round_trip = &parse_pgn(&game.to_pgn())[0];
+        assert_eq!(round_trip, game);
+        assert_eq!(round_trip.evals, game.evals);
+        assert!(PgnGame { moves: vec!["e4".to_string()], ..Default::default() }.to_pgn().ends_with("1. e4 *\n"));
+    }
+
+    #[test]
+    fn test_truncated_game() {
+        let text = "[Event \"One\"]\n\n1. e4 { [%clk 0:0\n[Event \"Two\"]\n\n1. d4 ( 1. c4\n[Event \"Three\"]\n\n1. Nf3 *\n";
+        let games = parse_pgn(text);
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].moves, ["e4"]);
+        assert_eq!(games[1].moves, ["d4"]);
+        assert_eq!(games[2].header("Event"), Some("Three"));
+        assert_eq!(games[2].moves, ["Nf3"]);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::i18n::tr;
+use shakmaty::{Chess, Position, Role};
+
+/// Moves, counted as full moves, after which a game is out of the opening
+const OPENING_MOVES: u32 = 12;
+/// Pieces other than kings and pawns, both sides together, that a game leaves the opening
+/// with at the latest
+const OPENING_PIECES: usize = 11;
+/// Pieces other than kings and pawns from which a position counts as an endgame
+const ENDGAME_PIECES: usize = 6;
+/// Material in pawns, pawns and kings aside, from which a position without queens counts as an endgame
+const ENDGAME_MATERIAL: u32 = 20;
+
+/// Stage of the game a position belongs to, in the order they come
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GamePhase {
+    Opening,
+    Middlegame,
+    Endgame,
+}
+
+impl GamePhase {
+    pub const ALL: [GamePhase; 3] = [GamePhase::Opening, GamePhase::Middlegame, GamePhase::Endgame];
+
+    /// Phase of `position` going by its material and move number alone
+    pub fn of(position: &Chess) -> Self {
+        let board = position.board();
+        let count = |role| board.by_role(role).count();
+        let pieces = count(Role::Knight) + count(Role::Bishop) + count(Role::Rook) + count(Role::Queen);
+        let material = 3 * (count(Role::Knight) + count(Role::Bishop)) + 5 * count(Role::Rook) + 9 * count(Role::Queen);
+        if pieces <= ENDGAME_PIECES || (count(Role::Queen) == 0 && material as u32 <= ENDGAME_MATERIAL) {
+            GamePhase::Endgame
+        } else if position.fullmoves().get() <= OPENING_MOVES && pieces >= OPENING_PIECES {
+            GamePhase::Opening
+        } else {
+            GamePhase::Middlegame
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GamePhase::Opening => tr("phase.opening"),
+            GamePhase::Middlegame => tr("phase.middlegame"),
+            GamePhase::Endgame => tr("phase.endgame"),
+        }
+    }
+}
+
+/// Phase of each of `positions` in game order. A game doesn't go back to an earlier phase,
+/// so a promotion late in an endgame doesn't make it a middlegame again.
+pub fn game_phases<'a>(positions: impl IntoIterator<Item = &'a Chess>) -> Vec<GamePhase> {
+    let mut reached = GamePhase::Opening;
+    positions
+        .into_iter()
+        .map(|position| {
+            reached = reached.max(GamePhase::of(position));
+            reached
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    fn phase(fen: &str) -> GamePhase {
+        GamePhase::of(GameState::from_fen(fen).unwrap().current_position())
+    }
+
+    #[test]
+    fn test_game_phase() {
+        assert_eq!(GamePhase::of(&Chess::default()), GamePhase::Opening);
+        // Move 20 with everything still on the board
+        assert_eq!(
+            phase("r1bq1rk1/pp2bppp/2n1pn2/3p4/3P4/2NBPN2/PP3PPP/R2Q1RK1 w - - 0 20"),
+            GamePhase::Middlegame
+        );
+        // Rook and knight each
+        assert_eq!(phase("6k1/5ppp/4n3/8/8/4N3/r4PPP/R5K1 w - - 0 30"), GamePhase::Endgame);
+        // Queens traded early, two rooks and a bishop each left
+        assert_eq!(phase("r3r1k1/ppp2ppp/3b4/8/8/3B4/PPP2PPP/R3R1K1 w - - 0 14"), GamePhase::Endgame);
+    }
+
+    #[test]
+    fn test_game_phases_keep_going_forward() {
+        let endgame = GameState::from_fen("8/P5k1/8/8/8/8/6K1/8 w - - 0 50").unwrap();
+        // A full board after an endgame is still counted as the endgame
+        let positions = [Chess::default(), endgame.current_position().clone(), Chess::default()];
+        assert_eq!(
+            game_phases(&positions),
+            [GamePhase::Opening, GamePhase::Endgame, GamePhase::Endgame]
+        );
+    }
+}
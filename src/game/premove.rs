@@ -0,0 +1,134 @@
+use crate::game::{GameError, GameState};
+use std::collections::VecDeque;
+
+/// A move of the player's queued while the engine thinks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Premove {
+    /// The engine reply (UCI) the move answers, or None to play it after any reply
+    pub condition: Option<String>,
+    pub condition_san: Option<String>,
+    pub uci: String,
+    pub san: String,
+}
+
+/// What to do once the engine has replied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PremoveStep {
+    /// Play this UCI move now
+    Play(String),
+    /// The engine played something the queue didn't expect; the queue was cleared
+    Invalidated,
+    Empty,
+}
+
+/// Premoves played one by one as long as the engine replies as expected
+#[derive(Debug, Clone, Default)]
+pub struct PremoveQueue {
+    moves: VecDeque<Premove>,
+}
+
+impl PremoveQueue {
+    /// Queue a conditional line typed in SAN from `fen`, where the engine is to move: its
+    /// expected reply, the player's answer, the next expected reply and so on. Move numbers
+    /// are skipped; a last engine move without an answer is ignored.
+    pub fn set_line(&mut self, fen: &str, text: &str) -> Result<(), GameError> {
+        let mut game = GameState::from_fen(fen)?;
+        let tokens: Vec<&str> = text
+            .split_whitespace()
+            .map(|t| t.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.'))
+            .filter(|t| !t.is_empty())
+            .collect();
+        let mut moves = VecDeque::new();
+        for pair in tokens.chunks_exact(2) {
+            let reply = game.make_move_san(pair[0])?;
+            let answer = game.make_move_san(pair[1])?;
+            moves.push_back(Premove {
+                condition: Some(reply.uci),
+                condition_san: Some(reply.san),
+                uci: answer.uci,
+                san: answer.san,
+            });
+        }
+        if moves.is_empty() {
+            return Err(GameError::InvalidMove(text.trim().to_string()));
+        }
+        self.moves = moves;
+        Ok(())
+    }
+
+    /// Queue a move to play after whatever the engine replies, if it is still legal then
+    pub fn push(&mut self, uci: String, san: String) {
+        self.moves.push_back(Premove {
+            condition: None,
+            condition_san: None,
+            uci,
+            san,
+        });
+    }
+
+    /// The player's next move after the engine played `reply`
+    pub fn next(&mut self, reply: &str) -> PremoveStep {
+        let Some(premove) = self.moves.pop_front() else {
+            return PremoveStep::Empty;
+        };
+        if premove.condition.as_deref().is_some_and(|expected| expected != reply) {
+            self.moves.clear();
+            return PremoveStep::Invalidated;
+        }
+        PremoveStep::Play(premove.uci)
+    }
+
+    pub fn clear(&mut self) {
+        self.moves.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Premove> {
+        self.moves.iter()
+    }
+}
+
+/// The position with the other side to move, so the player can pick premoves while the engine
+/// thinks. None when the side to move is in check, which the other side can't leave it in.
+pub fn premove_position(fen: &str) -> Option<GameState> {
+    let mut fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() < 4 {
+        return None;
+    }
+    fields[1] = if fields[1] == "w" { "b" } else { "w" };
+    fields[3] = "-";
+    GameState::from_fen(&fields.join(" ")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFTER_E4_E5_NF3: &str = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+
+    #[test]
+    fn test_conditional_line() {
+        let mut queue = PremoveQueue::default();
+        queue.set_line(AFTER_E4_E5_NF3, "2... Nc6 3. Bb5 a6 Ba4 Nf6").unwrap();
+        assert_eq!(queue.iter().count(), 2);
+        assert_eq!(queue.next("b8c6"), PremoveStep::Play("f1b5".to_string()));
+        assert_eq!(queue.next("d7d6"), PremoveStep::Invalidated);
+        assert!(queue.is_empty());
+        assert_eq!(queue.next("a7a6"), PremoveStep::Empty);
+
+        assert!(queue.set_line(AFTER_E4_E5_NF3, "Nc6 Bb9").is_err());
+        assert!(queue.set_line(AFTER_E4_E5_NF3, "Nc6").is_err());
+    }
+
+    #[test]
+    fn test_unconditional_premove() {
+        let game = premove_position(AFTER_E4_E5_NF3).unwrap();
+        let record = GameState::from_fen(&game.fen()).unwrap().make_move_san("Bc4").unwrap();
+        let mut queue = PremoveQueue::default();
+        queue.push(record.uci, record.san);
+        assert_eq!(queue.next("b8c6"), PremoveStep::Play("f1c4".to_string()));
+    }
+}
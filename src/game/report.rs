@@ -0,0 +1,421 @@
+use crate::game::{critical_moments, eval_loss, GamePhase, GameState, Nag, PgnStyle, PlayerColor, QualitySummary};
+use crate::i18n::{tr, tr_args};
+use shakmaty::{Board, Color, Position, Role, Square};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Most critical moments listed in a report
+const MAX_CRITICAL: usize = 5;
+/// Evaluations beyond this many centipawns are drawn at the edge of the graph
+const GRAPH_CLAMP_CP: i32 = 1000;
+const GRAPH_WIDTH: f32 = 600.0;
+const GRAPH_HEIGHT: f32 = 160.0;
+const DIAGRAM_SQUARE: u32 = 40;
+
+/// File format of an analysis report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// HTML for `.html` and `.htm` files, Markdown for anything else
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("html" | "htm") => ReportFormat::Html,
+            _ => ReportFormat::Markdown,
+        }
+    }
+}
+
+/// One move of the reviewed game
+struct ReportMove {
+    /// "12." for White, "12..." for Black
+    number: String,
+    san: String,
+    uci: String,
+    mover: PlayerColor,
+    loss: Option<i32>,
+    /// Position before the move
+    fen: String,
+}
+
+impl ReportMove {
+    fn nag(&self) -> Option<Nag> {
+        self.loss.and_then(Nag::from_loss)
+    }
+
+    fn squares(&self) -> Option<(Square, Square)> {
+        let from = self.uci.get(0..2)?.parse().ok()?;
+        let to = self.uci.get(2..4)?.parse().ok()?;
+        Some((from, to))
+    }
+}
+
+/// A reviewed game written up for sharing: the score, an evaluation graph, the critical
+/// moments with diagrams and both sides' accuracy
+pub struct GameReport {
+    white: String,
+    black: String,
+    result: String,
+    first_mover: PlayerColor,
+    moves: Vec<ReportMove>,
+    /// White-relative evaluations by position index
+    evals: Vec<Option<i32>>,
+    /// Phase each move was played in
+    phases: Vec<GamePhase>,
+}
+
+impl GameReport {
+    pub fn new(game: &GameState, evals: &[Option<i32>], white: &str, black: &str) -> Self {
+        let first_mover = game.starting_turn();
+        let first_number = game
+            .starting_fen()
+            .split_whitespace()
+            .nth(5)
+            .and_then(|n| n.parse::<usize>().ok())
+            .unwrap_or(1);
+        let offset = usize::from(first_mover == PlayerColor::Black);
+        let mut fen = game.starting_fen();
+        let moves = game
+            .move_history()
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mover = if i % 2 == 0 { first_mover } else { first_mover.opposite() };
+                let number = first_number + (i + offset) / 2;
+                ReportMove {
+                    number: match mover {
+                        PlayerColor::White => format!("{}.", number),
+                        PlayerColor::Black => format!("{}...", number),
+                    },
+                    san: record.san.clone(),
+                    uci: record.uci.clone(),
+                    mover,
+                    loss: eval_loss(evals, i, mover),
+                    fen: std::mem::replace(&mut fen, record.resulting_fen.clone()),
+                }
+            })
+            .collect();
+        Self {
+            white: white.to_string(),
+            black: black.to_string(),
+            result: game.outcome().result().to_string(),
+            first_mover,
+            moves,
+            evals: evals.to_vec(),
+            phases: game.move_phases(),
+        }
+    }
+
+    fn title(&self) -> String {
+        format!("{} - {} ({})", self.white, self.black, self.result)
+    }
+
+    /// The moves that lost the most, at most `MAX_CRITICAL` of them, in game order
+    fn critical_moments(&self) -> Vec<(usize, &ReportMove)> {
+        critical_moments(&self.evals, self.moves.len(), self.first_mover, MAX_CRITICAL)
+            .into_iter()
+            .map(|c| (c.ply, &self.moves[c.ply]))
+            .collect()
+    }
+
+    fn summaries(&self) -> [(PlayerColor, &str, Option<QualitySummary>); 2] {
+        let summary = |side| QualitySummary::of(&self.evals, self.moves.len(), self.first_mover, side);
+        [
+            (PlayerColor::White, &self.white, summary(PlayerColor::White)),
+            (PlayerColor::Black, &self.black, summary(PlayerColor::Black)),
+        ]
+    }
+
+    /// Average loss of each side in each phase, "–" where it has no evaluated moves
+    fn phase_losses(&self) -> [(&str, [String; 3]); 2] {
+        let losses = |side| {
+            let by_phase = QualitySummary::by_phase(&self.evals, &self.phases, self.first_mover, side);
+            GamePhase::ALL.map(|phase| {
+                by_phase
+                    .iter()
+                    .find(|(p, _)| *p == phase)
+                    .map_or("–".to_string(), |(_, s)| s.average_loss.to_string())
+            })
+        };
+        [(&self.white, losses(PlayerColor::White)), (&self.black, losses(PlayerColor::Black))]
+    }
+
+    /// Score text with annotation symbols
+    fn score(&self) -> String {
+        let style = PgnStyle { figurine: false, nag_glyphs: true };
+        let mut score = String::new();
+        for (i, m) in self.moves.iter().enumerate() {
+            if m.mover == PlayerColor::White || i == 0 {
+                score.push_str(&m.number);
+                score.push(' ');
+            }
+            score.push_str(&style.move_text(&m.san, m.nag()));
+            score.push(' ');
+        }
+        score.push_str(&self.result);
+        score
+    }
+
+    /// Evaluation over the game as an SVG line graph, White's advantage upwards, with the
+    /// critical moments marked
+    pub fn eval_graph_svg(&self) -> String {
+        let positions = self.moves.len() + 1;
+        let x = |index: usize| index as f32 * GRAPH_WIDTH / (positions.max(2) - 1) as f32;
+        let y = |cp: i32| {
+            let cp = cp.clamp(-GRAPH_CLAMP_CP, GRAPH_CLAMP_CP) as f32;
+            GRAPH_HEIGHT / 2.0 - cp / GRAPH_CLAMP_CP as f32 * GRAPH_HEIGHT / 2.0
+        };
+
+        let mut svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">
+<rect width="{w}" height="{half}" fill="#f4f4f4"/><rect y="{half}" width="{w}" height="{half}" fill="#404040"/>
+<line x1="0" y1="{half}" x2="{w}" y2="{half}" stroke="#888" stroke-width="1"/>
+"##,
+            w = GRAPH_WIDTH,
+            h = GRAPH_HEIGHT,
+            half = GRAPH_HEIGHT / 2.0
+        );
+        let points: Vec<String> = self
+            .evals
+            .iter()
+            .enumerate()
+            .take(positions)
+            .filter_map(|(i, eval)| eval.map(|cp| format!("{:.1},{:.1}", x(i), y(cp))))
+            .collect();
+        if !points.is_empty() {
+            let _ = writeln!(
+                svg,
+                r##"<polyline points="{}" fill="none" stroke="#3a7bd5" stroke-width="2"/>"##,
+                points.join(" ")
+            );
+        }
+        for (ply, _) in self.critical_moments() {
+            if let Some(cp) = self.evals.get(ply + 1).copied().flatten() {
+                let _ = writeln!(svg, r##"<circle cx="{:.1}" cy="{:.1}" r="4" fill="#d94040"/>"##, x(ply + 1), y(cp));
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn loss_text(m: &ReportMove) -> String {
+        let loss = m.loss.unwrap_or_default() as f32 / 100.0;
+        tr_args("report.loss", &[&format!("{:.2}", loss)])
+    }
+
+    /// Markdown report. Markdown can't hold images, so the graph is linked from `graph_file`,
+    /// which the caller writes next to the report; diagrams are drawn as text.
+    pub fn to_markdown(&self, graph_file: &str) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# {}\n", self.title());
+        let _ = writeln!(md, "## {}\n\n{}\n", tr("report.score"), self.score());
+        let _ = writeln!(md, "## {}\n\n![{}]({})\n", tr("report.evaluation"), tr("report.evaluation"), graph_file);
+
+        let _ = writeln!(md, "## {}\n", tr("report.accuracy"));
+        let _ = writeln!(
+            md,
+            "| | {} | {} | {} | {} |\n|---|---|---|---|---|",
+            tr("report.average_loss"),
+            tr("report.inaccuracies"),
+            tr("report.mistakes"),
+            tr("report.blunders")
+        );
+        for (_, name, summary) in self.summaries() {
+            match summary {
+                Some(s) => {
+                    let _ = writeln!(md, "| {} | {} | {} | {} | {} |", name, s.average_loss, s.inaccuracies, s.mistakes, s.blunders);
+                }
+                None => {
+                    let _ = writeln!(md, "| {} | – | – | – | – |", name);
+                }
+            }
+        }
+        md.push('\n');
+
+        let _ = writeln!(md, "### {}\n", tr("report.by_phase"));
+        let _ = writeln!(md, "| | {} |\n|---|---|---|---|", GamePhase::ALL.map(GamePhase::label).join(" | "));
+        for (name, losses) in self.phase_losses() {
+            let _ = writeln!(md, "| {} | {} |", name, losses.join(" | "));
+        }
+        md.push('\n');
+
+        let critical = self.critical_moments();
+        let _ = writeln!(md, "## {}\n", tr("report.critical"));
+        if critical.is_empty() {
+            let _ = writeln!(md, "{}\n", tr("report.no_critical"));
+        }
+        for (_, m) in critical {
+            let glyph = m.nag().map_or("", Nag::glyph);
+            let _ = writeln!(md, "### {} {}{}\n", m.number, m.san, glyph);
+            let _ = writeln!(md, "{}\n", Self::loss_text(m));
+            if let Ok(game) = GameState::from_fen(&m.fen) {
+                let _ = writeln!(md, "```\n{}```\n", text_diagram(game.current_position().board()));
+            }
+            let _ = writeln!(md, "`{}`\n", m.fen);
+        }
+        md
+    }
+
+    /// Self-contained HTML report with the graph and diagrams inline
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+             body {{ font-family: sans-serif; max-width: 800px; margin: 2em auto; }}\n\
+             table {{ border-collapse: collapse; }} td, th {{ border: 1px solid #ccc; padding: 4px 10px; }}\n\
+             </style>\n</head>\n<body>",
+            escape_html(&self.title())
+        );
+        let _ = writeln!(html, "<h1>{}</h1>", escape_html(&self.title()));
+        let _ = writeln!(html, "<h2>{}</h2>\n<p>{}</p>", tr("report.score"), escape_html(&self.score()));
+        let _ = writeln!(html, "<h2>{}</h2>\n{}", tr("report.evaluation"), self.eval_graph_svg());
+
+        let _ = writeln!(html, "<h2>{}</h2>\n<table>", tr("report.accuracy"));
+        let _ = writeln!(
+            html,
+            "<tr><th></th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+            tr("report.average_loss"),
+            tr("report.inaccuracies"),
+            tr("report.mistakes"),
+            tr("report.blunders")
+        );
+        for (_, name, summary) in self.summaries() {
+            let cells = match summary {
+                Some(s) => [s.average_loss, s.inaccuracies as i32, s.mistakes as i32, s.blunders as i32].map(|n| n.to_string()),
+                None => std::array::from_fn(|_| "–".to_string()),
+            };
+            let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", escape_html(name), cells.join("</td><td>"));
+        }
+        html.push_str("</table>\n");
+
+        let _ = writeln!(html, "<h3>{}</h3>\n<table>", tr("report.by_phase"));
+        let _ = writeln!(html, "<tr><th></th><th>{}</th></tr>", GamePhase::ALL.map(GamePhase::label).join("</th><th>"));
+        for (name, losses) in self.phase_losses() {
+            let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", escape_html(name), losses.join("</td><td>"));
+        }
+        html.push_str("</table>\n");
+
+        let critical = self.critical_moments();
+        let _ = writeln!(html, "<h2>{}</h2>", tr("report.critical"));
+        if critical.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", tr("report.no_critical"));
+        }
+        for (_, m) in critical {
+            let glyph = m.nag().map_or("", Nag::glyph);
+            let _ = writeln!(html, "<h3>{} {}{}</h3>\n<p>{}</p>", m.number, escape_html(&m.san), glyph, Self::loss_text(m));
+            if let Ok(game) = GameState::from_fen(&m.fen) {
+                html.push_str(&svg_diagram(game.current_position().board(), m.squares(), false));
+            }
+            let _ = writeln!(html, "<p><code>{}</code></p>", m.fen);
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn piece_char(color: Color, role: Role) -> char {
+    match (color, role) {
+        (Color::White, Role::King) => '♔',
+        (Color::White, Role::Queen) => '♕',
+        (Color::White, Role::Rook) => '♖',
+        (Color::White, Role::Bishop) => '♗',
+        (Color::White, Role::Knight) => '♘',
+        (Color::White, Role::Pawn) => '♙',
+        (Color::Black, Role::King) => '♚',
+        (Color::Black, Role::Queen) => '♛',
+        (Color::Black, Role::Rook) => '♜',
+        (Color::Black, Role::Bishop) => '♝',
+        (Color::Black, Role::Knight) => '♞',
+        (Color::Black, Role::Pawn) => '♟',
+    }
+}
+
+/// Squares from a8 across and down to h1, as they are drawn with White at the bottom
+fn diagram_squares() -> impl Iterator<Item = (u32, u32, Square)> {
+    (0..8u32).flat_map(|row| {
+        (0..8u32).map(move |col| (row, col, Square::from_coords(shakmaty::File::new(col), shakmaty::Rank::new(7 - row))))
+    })
+}
+
+/// The board as rows of piece symbols, White at the bottom
+fn text_diagram(board: &Board) -> String {
+    let mut text = String::new();
+    for (row, col, square) in diagram_squares() {
+        if col == 0 {
+            let _ = write!(text, "{} ", 8 - row);
+        }
+        text.push(board.piece_at(square).map_or('·', |p| piece_char(p.color, p.role)));
+        text.push(if col == 7 { '\n' } else { ' ' });
+    }
+    text.push_str("  a b c d e f g h\n");
+    text
+}
+
+/// The board as an SVG image, White at the bottom unless `flipped`, with the move's squares highlighted
+pub(crate) fn svg_diagram(board: &Board, highlight: Option<(Square, Square)>, flipped: bool) -> String {
+    let size = DIAGRAM_SQUARE * 8;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#, size);
+    svg.push('\n');
+    for (row, col, square) in diagram_squares() {
+        let square = if flipped { square.flip_vertical().flip_horizontal() } else { square };
+        let highlighted = highlight.is_some_and(|(from, to)| square == from || square == to);
+        let fill = match ((row + col) % 2 == 0, highlighted) {
+            (true, false) => "#f0d9b5",
+            (false, false) => "#b58863",
+            (true, true) => "#f6e27a",
+            (false, true) => "#d8b84a",
+        };
+        let (x, y) = (col * DIAGRAM_SQUARE, row * DIAGRAM_SQUARE);
+        let _ = writeln!(svg, r#"<rect x="{}" y="{}" width="{2}" height="{2}" fill="{3}"/>"#, x, y, DIAGRAM_SQUARE, fill);
+        if let Some(piece) = board.piece_at(square) {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                x + DIAGRAM_SQUARE / 2,
+                y + DIAGRAM_SQUARE / 2,
+                DIAGRAM_SQUARE * 4 / 5,
+                piece_char(piece.color, piece.role)
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_marks_critical_moments() {
+        let mut game = GameState::new();
+        for san in ["f3", "e5", "g4", "Qh4#"] {
+            game.make_move_san(san).unwrap();
+        }
+        // g4 walks into mate
+        let evals = [Some(20), Some(0), Some(-40), Some(-10000), None];
+        let report = GameReport::new(&game, &evals, "Player", "Stockfish");
+        assert_eq!(report.score(), "1. f3 e5 2. g4?? Qh4 0-1");
+        let critical = report.critical_moments();
+        assert_eq!(critical.len(), 1);
+        assert_eq!(critical[0].1.number, "2.");
+
+        let md = report.to_markdown("graph.svg");
+        assert!(md.contains("![") && md.contains("(graph.svg)"));
+        assert!(md.contains("### 2. g4??"));
+        assert!(md.contains("7 ♟ ♟ ♟ ♟ · ♟ ♟ ♟"));
+        assert!(md.contains("| Stockfish | 0 | – | – |"));
+
+        let html = report.to_html();
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert_eq!(ReportFormat::from_path(Path::new("game.HTML")), ReportFormat::Html);
+    }
+}
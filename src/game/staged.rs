@@ -0,0 +1,60 @@
+use shakmaty::Move;
+
+/// A move picked on the board but held back until the engine has said what it costs
+#[derive(Debug, Clone)]
+pub struct StagedMove {
+    pub m: Move,
+    pub san: String,
+    /// Review index of the position before the move; the position after it is the next one
+    pub index: usize,
+    /// Score of the position before the move, for the player
+    best: Option<i32>,
+    /// Score of the position after it, for the opponent
+    after: Option<i32>,
+}
+
+impl StagedMove {
+    pub fn new(m: Move, san: String, index: usize) -> Self {
+        Self { m, san, index, best: None, after: None }
+    }
+
+    /// Take a review result; false when it belongs to another staged move
+    pub fn record(&mut self, index: usize, score: Option<i32>) -> bool {
+        if index == self.index {
+            self.best = score;
+        } else if index == self.index + 1 {
+            self.after = score;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Centipawns the move gives away compared with the engine's best, once both
+    /// positions are evaluated
+    pub fn cost(&self) -> Option<i32> {
+        Some((self.best? + self.after?).max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    #[test]
+    fn test_staged_move_cost() {
+        let game = GameState::new();
+        let m = game.parse_move("e4").unwrap();
+        let mut staged = StagedMove::new(m, "e4".to_string(), 6);
+        assert!(!staged.record(4, Some(10)));
+        assert!(staged.record(6, Some(30)));
+        assert_eq!(staged.cost(), None);
+        // After the move the opponent stands at -10, so it cost 20
+        assert!(staged.record(7, Some(-10)));
+        assert_eq!(staged.cost(), Some(20));
+        // Search noise doesn't make a move better than best
+        staged.record(7, Some(-50));
+        assert_eq!(staged.cost(), Some(0));
+    }
+}
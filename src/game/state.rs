@@ -3,9 +3,12 @@ use shakmaty::{
     Position, Role, Square,
 };
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use crate::game::{game_phases, GamePhase, Heatmap, HeatmapKind};
+use crate::i18n::tr;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum GameError {
     #[error("Invalid move: {0}")]
     InvalidMove(String),
@@ -19,12 +22,22 @@ pub enum GameError {
     NoNextPosition,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum PlayerColor {
+    #[default]
     White,
     Black,
 }
 
+impl PlayerColor {
+    pub fn opposite(self) -> Self {
+        match self {
+            PlayerColor::White => PlayerColor::Black,
+            PlayerColor::Black => PlayerColor::White,
+        }
+    }
+}
+
 impl From<Color> for PlayerColor {
     fn from(c: Color) -> Self {
         match c {
@@ -43,6 +56,12 @@ impl From<PlayerColor> for Color {
     }
 }
 
+/// The placement, side to move, castling and en passant fields of a FEN, which identify a
+/// position regardless of the move counters
+pub fn position_key(fen: &str) -> String {
+    fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameOutcome {
     Checkmate(PlayerColor), // Winner
@@ -51,15 +70,64 @@ pub enum GameOutcome {
     ThreefoldRepetition,
     FiftyMoveRule,
     Resignation(PlayerColor), // Winner (the player who didn't resign)
+    Timeout(PlayerColor), // Winner (the player whose clock didn't run out)
     DrawByAgreement,
     InProgress,
 }
 
+impl GameOutcome {
+    /// Side that won, if the game was decided
+    pub fn winner(&self) -> Option<PlayerColor> {
+        match self {
+            GameOutcome::Checkmate(winner) | GameOutcome::Resignation(winner) | GameOutcome::Timeout(winner) => {
+                Some(*winner)
+            }
+            _ => None,
+        }
+    }
+
+    /// PGN result tag value
+    pub fn result(&self) -> &'static str {
+        match self {
+            GameOutcome::InProgress => "*",
+            _ => match self.winner() {
+                Some(PlayerColor::White) => "1-0",
+                Some(PlayerColor::Black) => "0-1",
+                None => "1/2-1/2",
+            },
+        }
+    }
+
+    /// Result and reason, e.g. "White wins by checkmate!"
+    pub fn message(&self) -> Option<&'static str> {
+        Some(match self {
+            GameOutcome::Checkmate(PlayerColor::White) => tr("outcome.white_checkmate"),
+            GameOutcome::Checkmate(PlayerColor::Black) => tr("outcome.black_checkmate"),
+            GameOutcome::Stalemate => tr("outcome.stalemate"),
+            GameOutcome::InsufficientMaterial => tr("outcome.insufficient_material"),
+            GameOutcome::ThreefoldRepetition => tr("outcome.threefold"),
+            GameOutcome::FiftyMoveRule => tr("outcome.fifty_move"),
+            GameOutcome::Resignation(PlayerColor::White) => tr("outcome.white_resignation"),
+            GameOutcome::Resignation(PlayerColor::Black) => tr("outcome.black_resignation"),
+            GameOutcome::Timeout(PlayerColor::White) => tr("outcome.white_timeout"),
+            GameOutcome::Timeout(PlayerColor::Black) => tr("outcome.black_timeout"),
+            GameOutcome::DrawByAgreement => tr("outcome.agreement"),
+            GameOutcome::InProgress => return None,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveRecord {
     pub san: String,
     pub uci: String,
     pub resulting_fen: String,
+    /// Thinking time for the move, when it was played against the clock
+    #[serde(default)]
+    pub time_spent: Option<Duration>,
+    /// The mover's time left after the move and its increment, when the game had a time control
+    #[serde(default)]
+    pub clock_remaining: Option<Duration>,
 }
 
 /// Represents a position in the game history
@@ -139,17 +207,56 @@ impl GameState {
         self.current_position().turn().into()
     }
 
+    /// Side to move in the latest position, whichever one is being viewed
+    pub fn head_turn(&self) -> PlayerColor {
+        self.positions[self.positions.len() - 1].position.turn().into()
+    }
+
+    /// FEN of the latest position, whichever one is being viewed
+    pub fn head_fen(&self) -> String {
+        Fen::from_position(&self.positions[self.positions.len() - 1].position, EnPassantMode::Legal).to_string()
+    }
+
+    /// UCI moves from the starting position to the one being viewed
+    pub fn uci_moves(&self) -> Vec<String> {
+        self.move_history[..self.current_index].iter().map(|m| m.uci.clone()).collect()
+    }
+
+    /// FEN of the position the game started from
+    pub fn starting_fen(&self) -> String {
+        Fen::from_position(&self.positions[0].position, EnPassantMode::Legal).to_string()
+    }
+
+    /// Side to move in the starting position
+    pub fn starting_turn(&self) -> PlayerColor {
+        self.positions[0].position.turn().into()
+    }
+
+    /// Full move number of the current position (increments after Black moves)
+    pub fn fullmove_number(&self) -> u32 {
+        self.current_position().fullmoves().get()
+    }
+
     pub fn is_check(&self) -> bool {
         self.current_position().is_check()
     }
 
     pub fn outcome(&self) -> GameOutcome {
+        self.outcome_at(self.current_index)
+    }
+
+    /// Outcome at the latest position, whichever one is being viewed
+    pub fn head_outcome(&self) -> GameOutcome {
+        self.outcome_at(self.positions.len() - 1)
+    }
+
+    fn outcome_at(&self, index: usize) -> GameOutcome {
         // Check for resignation or draw by agreement first
         if let Some(result) = self.game_result {
             return result;
         }
         
-        let pos = self.current_position();
+        let pos = &self.positions[index].position;
         
         if pos.is_checkmate() {
             let winner = match pos.turn() {
@@ -168,8 +275,8 @@ impl GameState {
         }
 
         // Check for threefold repetition using all positions up to current
-        let current_hash = self.positions[self.current_index].hash;
-        let repetitions = self.positions[..=self.current_index]
+        let current_hash = self.positions[index].hash;
+        let repetitions = self.positions[..=index]
             .iter()
             .filter(|p| p.hash == current_hash)
             .count();
@@ -195,6 +302,35 @@ impl GameState {
             .collect()
     }
 
+    /// Parse a move typed by the user, accepting SAN (`Nf3`, `exd5`) or UCI (`g1f3`)
+    pub fn parse_move(&self, text: &str) -> Result<Move, GameError> {
+        let text = text.trim();
+        let position = self.current_position();
+
+        if let Ok(san) = text.parse::<San>() {
+            if let Ok(m) = san.to_move(position) {
+                return Ok(m);
+            }
+        }
+
+        if let Ok(uci) = text.parse::<UciMove>() {
+            if let Ok(m) = uci.to_move(position) {
+                return Ok(m);
+            }
+        }
+
+        Err(GameError::InvalidMove(text.to_string()))
+    }
+
+    /// All legal moves in the current position with their SAN
+    pub fn legal_moves_san(&self) -> Vec<(String, Move)> {
+        let position = self.current_position();
+        self.legal_moves()
+            .into_iter()
+            .map(|m| (San::from_move(position, m).to_string(), m))
+            .collect()
+    }
+
     pub fn make_move_san(&mut self, san_str: &str) -> Result<MoveRecord, GameError> {
         if self.outcome() != GameOutcome::InProgress {
             return Err(GameError::GameOver);
@@ -265,6 +401,8 @@ impl GameState {
             san: san.to_string(),
             uci: uci.to_string(),
             resulting_fen,
+            time_spent: None,
+            clock_remaining: None,
         };
         self.move_history.push(record.clone());
 
@@ -328,10 +466,29 @@ impl GameState {
         self.positions.len()
     }
 
+    /// Record the clock for the move at `index` of the history
+    /// Phase of the position before each move, in game order
+    pub fn move_phases(&self) -> Vec<GamePhase> {
+        let moves = self.move_history.len();
+        game_phases(self.positions.iter().take(moves).map(|state| &state.position))
+    }
+
+    pub fn set_move_clock(&mut self, index: usize, time_spent: Duration, clock_remaining: Option<Duration>) {
+        if let Some(record) = self.move_history.get_mut(index) {
+            record.time_spent = Some(time_spent);
+            record.clock_remaining = clock_remaining;
+        }
+    }
+
     pub fn move_history(&self) -> &[MoveRecord] {
         &self.move_history
     }
 
+    /// Per-square heatmap over every position of the game, not just up to the viewed move
+    pub fn heatmap(&self, kind: HeatmapKind) -> Heatmap {
+        Heatmap::compute(kind, self.positions.iter().map(|p| p.position.board()))
+    }
+
     pub fn piece_at(&self, square: Square) -> Option<(Role, Color)> {
         let piece = self.current_position().board().piece_at(square)?;
         Some((piece.role, piece.color))
@@ -352,6 +509,15 @@ impl GameState {
         self.game_result = Some(GameOutcome::Resignation(winner));
     }
     
+    /// Lose on time - opponent wins
+    pub fn lose_on_time(&mut self, color: PlayerColor) {
+        let winner = match color {
+            PlayerColor::White => PlayerColor::Black,
+            PlayerColor::Black => PlayerColor::White,
+        };
+        self.game_result = Some(GameOutcome::Timeout(winner));
+    }
+
     /// Agree to a draw
     pub fn agree_to_draw(&mut self) {
         self.game_result = Some(GameOutcome::DrawByAgreement);
@@ -398,6 +564,63 @@ impl GameState {
         let c: Color = color.into();
         self.current_position().board().king_of(c)
     }
+
+    /// SAN for a UCI move in the current position
+    pub fn uci_to_san(&self, uci: &str) -> Option<String> {
+        let uci: UciMove = uci.parse().ok()?;
+        let m = uci.to_move(self.current_position()).ok()?;
+        Some(San::from_move(self.current_position(), m).to_string())
+    }
+
+    /// Pieces of `color` captured in the moves up to the current position, most valuable first
+    pub fn captured_pieces(&self, color: PlayerColor) -> Vec<Role> {
+        let color: Color = color.into();
+        let mut captured: Vec<Role> = self
+            .move_history
+            .iter()
+            .zip(&self.positions)
+            .take(self.current_index)
+            .filter(|(_, state)| state.position.turn() != color)
+            .filter_map(|(record, state)| {
+                let uci: UciMove = record.uci.parse().ok()?;
+                uci.to_move(&state.position).ok()?.capture()
+            })
+            .collect();
+        captured.sort_by_key(|&role| std::cmp::Reverse(piece_value(role)));
+        captured
+    }
+
+    /// Material balance in pawns from `color`'s point of view
+    pub fn material_advantage(&self, color: PlayerColor) -> i32 {
+        self.material_advantage_at(self.current_index, color)
+    }
+
+    /// Material lead of `color` in pawns in the position after `index` moves
+    pub fn material_advantage_at(&self, index: usize, color: PlayerColor) -> i32 {
+        let Some(state) = self.positions.get(index) else {
+            return 0;
+        };
+        let board = state.position.board();
+        let material = |c: Color| -> i32 {
+            Role::ALL
+                .iter()
+                .map(|&role| (board.by_color(c) & board.by_role(role)).count() as i32 * piece_value(role))
+                .sum()
+        };
+        let color: Color = color.into();
+        material(color) - material(color.other())
+    }
+}
+
+/// Conventional piece values in pawns
+fn piece_value(role: Role) -> i32 {
+    match role {
+        Role::Pawn => 1,
+        Role::Knight | Role::Bishop => 3,
+        Role::Rook => 5,
+        Role::Queen => 9,
+        Role::King => 0,
+    }
 }
 
 #[cfg(test)]
@@ -448,6 +671,50 @@ mod tests {
         assert_eq!(game.current_index(), 3);
     }
 
+    #[test]
+    fn test_parse_move() {
+        let game = GameState::new();
+        let san = game.parse_move("Nf3").unwrap();
+        let uci = game.parse_move(" g1f3 ").unwrap();
+        assert_eq!(san, uci);
+        assert!(game.parse_move("Nf6").is_err());
+        assert!(game.parse_move("e2e5").is_err());
+        assert_eq!(game.legal_moves_san().len(), 20);
+    }
+
+    #[test]
+    fn test_captured_pieces() {
+        let mut game = GameState::new();
+        for san in ["e4", "d5", "exd5", "Qxd5"] {
+            game.make_move_san(san).unwrap();
+        }
+        assert_eq!(game.captured_pieces(PlayerColor::White), vec![Role::Pawn]);
+        assert_eq!(game.captured_pieces(PlayerColor::Black), vec![Role::Pawn]);
+        assert_eq!(game.material_advantage(PlayerColor::White), 0);
+    }
+
+    #[test]
+    fn test_captured_after_promotion() {
+        let mut game = GameState::from_fen("8/1P6/6k1/8/8/8/K5p1/7R w - - 0 1").unwrap();
+        for uci in ["b7b8q", "g2h1q", "b8b5", "h1h5", "b5h5"] {
+            game.make_move_uci(uci).unwrap();
+        }
+        assert_eq!(game.captured_pieces(PlayerColor::White), vec![Role::Rook]);
+        assert_eq!(game.captured_pieces(PlayerColor::Black), vec![Role::Queen]);
+    }
+
+    #[test]
+    fn test_captured_from_fen() {
+        let mut game = GameState::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+        assert!(game.captured_pieces(PlayerColor::White).is_empty());
+        assert!(game.captured_pieces(PlayerColor::Black).is_empty());
+        game.make_move_san("exd5").unwrap();
+        assert_eq!(game.captured_pieces(PlayerColor::Black), vec![Role::Pawn]);
+        assert!(game.captured_pieces(PlayerColor::White).is_empty());
+        game.go_to_start();
+        assert!(game.captured_pieces(PlayerColor::Black).is_empty());
+    }
+
     #[test]
     fn test_scholars_mate() {
         let mut game = GameState::new();
@@ -461,4 +728,28 @@ mod tests {
 
         assert_eq!(game.outcome(), GameOutcome::Checkmate(PlayerColor::White));
     }
+
+    #[test]
+    fn test_head_fen() {
+        let mut game = GameState::new();
+        game.make_move_san("e4").unwrap();
+        game.make_move_san("e5").unwrap();
+        let head = game.fen();
+        game.go_to_start();
+        assert_eq!(game.head_fen(), head);
+        assert_eq!(game.fen(), game.starting_fen());
+    }
+
+    #[test]
+    fn test_head_while_browsing() {
+        let mut game = GameState::new();
+        for san in ["f3", "e5", "g4", "Qh4#"] {
+            game.make_move_san(san).unwrap();
+        }
+        game.go_back().unwrap();
+        assert_eq!(game.turn(), PlayerColor::Black);
+        assert_eq!(game.outcome(), GameOutcome::InProgress);
+        assert_eq!(game.head_turn(), PlayerColor::White);
+        assert_eq!(game.head_outcome(), GameOutcome::Checkmate(PlayerColor::Black));
+    }
 }
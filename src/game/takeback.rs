@@ -0,0 +1,63 @@
+use crate::i18n::{tr, tr_args};
+use serde::{Deserialize, Serialize};
+
+/// How many moves the player may take back in a game against the engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TakebackPolicy {
+    #[default]
+    Unlimited,
+    PerGame(u32),
+    /// No takebacks, for games played seriously
+    Off,
+}
+
+impl TakebackPolicy {
+    /// Policies offered in the game panel
+    pub fn presets() -> &'static [TakebackPolicy] {
+        const PRESETS: [TakebackPolicy; 5] = [
+            TakebackPolicy::Unlimited,
+            TakebackPolicy::PerGame(1),
+            TakebackPolicy::PerGame(3),
+            TakebackPolicy::PerGame(5),
+            TakebackPolicy::Off,
+        ];
+        &PRESETS
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            TakebackPolicy::Unlimited => tr("takeback.unlimited").to_string(),
+            TakebackPolicy::PerGame(n) => tr_args("takeback.per_game", &[n]),
+            TakebackPolicy::Off => tr("takeback.off").to_string(),
+        }
+    }
+
+    /// Takebacks left after `used` of them, None when there is no limit
+    pub fn remaining(&self, used: u32) -> Option<u32> {
+        match self {
+            TakebackPolicy::Unlimited => None,
+            TakebackPolicy::PerGame(n) => Some(n.saturating_sub(used)),
+            TakebackPolicy::Off => Some(0),
+        }
+    }
+
+    pub fn allows(&self, used: u32) -> bool {
+        self.remaining(used) != Some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_takeback_policy() {
+        assert!(TakebackPolicy::Unlimited.allows(100));
+        assert_eq!(TakebackPolicy::Unlimited.remaining(3), None);
+        assert_eq!(TakebackPolicy::PerGame(3).remaining(1), Some(2));
+        assert!(!TakebackPolicy::PerGame(3).allows(3));
+        // Lowering the limit mid-game leaves none rather than underflowing
+        assert_eq!(TakebackPolicy::PerGame(1).remaining(3), Some(0));
+        assert!(!TakebackPolicy::Off.allows(0));
+    }
+}
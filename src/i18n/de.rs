@@ -0,0 +1,726 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    // Formatting
+    ("format.move_number", "{0}."),
+    // Mode and navigation
+    ("mode.label", "Modus:"),
+    ("mode.game", "Gegen die Engine spielen"),
+    ("mode.analysis", "Analyse"),
+    ("mode.study", "Studie"),
+    ("nav.label", "Navigation:"),
+    ("nav.start", "Zum Anfang"),
+    ("nav.previous", "Vorheriger Zug"),
+    ("nav.next", "Nächster Zug"),
+    ("nav.end", "Zum Ende"),
+    ("nav.move_counter", "Zug: {0} / {1}"),
+    // Game controls
+    ("controls.engine_thinking", "Engine rechnet..."),
+    ("controls.new_game", "Neue Partie"),
+    ("controls.flip_board", "Brett drehen"),
+    ("controls.play_as", "Spielen als:"),
+    ("controls.white", "Weiß"),
+    ("controls.black", "Schwarz"),
+    ("controls.difficulty", "Spielstärke:"),
+    ("controls.personality", "Persönlichkeit:"),
+    ("controls.theme", "Design:"),
+    ("controls.language", "Sprache:"),
+    ("controls.resign", "🏳 Aufgeben"),
+    ("controls.offer_draw", "🤝 Remis anbieten"),
+    ("controls.undo", "↩ Zug zurücknehmen"),
+    ("controls.export_pgn", "📄 PGN exportieren"),
+    ("controls.save_to_study", "📚 In Studie speichern"),
+    // Outcomes
+    ("outcome.white_checkmate", "Weiß gewinnt durch Schachmatt!"),
+    ("outcome.black_checkmate", "Schwarz gewinnt durch Schachmatt!"),
+    ("outcome.stalemate", "Remis durch Patt"),
+    ("outcome.insufficient_material", "Remis durch ungenügendes Material"),
+    ("outcome.threefold", "Remis durch dreifache Stellungswiederholung"),
+    ("outcome.fifty_move", "Remis durch die 50-Züge-Regel"),
+    ("outcome.white_resignation", "Weiß gewinnt durch Aufgabe!"),
+    ("outcome.black_resignation", "Schwarz gewinnt durch Aufgabe!"),
+    ("outcome.agreement", "Remis durch Einigung"),
+    // Difficulty levels
+    ("difficulty.novice", "Neuling (~1100)"),
+    ("difficulty.beginner", "Anfänger (~1350)"),
+    ("difficulty.casual", "Gelegenheitsspieler (~1500)"),
+    ("difficulty.intermediate", "Fortgeschritten (~1800)"),
+    ("difficulty.advanced", "Stark (~2100)"),
+    ("difficulty.expert", "Experte (~2500)"),
+    ("difficulty.maximum", "Maximale Stärke"),
+    // Engine personalities
+    ("personality.standard", "Standard"),
+    ("personality.aggressive", "Angreifer"),
+    ("personality.positional", "Stratege"),
+    ("personality.grinder", "Endspiel-Kämpfer"),
+    ("personality.standard_description", "Spielt den besten Zug der Engine."),
+    ("personality.aggressive_description", "Sucht Schachs, Schlagzüge und scharfe Eröffnungen und meidet Remis."),
+    ("personality.positional_description", "Bevorzugt ruhige Züge, frühe Rochade und geschlossene Eröffnungen."),
+    ("personality.grinder_description", "Tauscht Figuren, aktiviert den König und gewinnt lange Endspiele."),
+    // Themes
+    ("theme.classic", "Klassisch"),
+    ("theme.dark", "Dunkel"),
+    // Analysis panel
+    ("analysis.heading", "Analyse"),
+    ("analysis.analyze", "▶ Analysieren"),
+    ("analysis.stop", "⏹ Stopp"),
+    ("analysis.analyzing", "Analysiere..."),
+    ("analysis.paused", "⏸ Pausiert"),
+    ("analysis.lines", "Varianten:"),
+    ("analysis.eval_bar", "Bewertungsbalken"),
+    ("analysis.calculating", "/ {0} werden berechnet"),
+    ("analysis.empty", "Noch keine Analyse..."),
+    // Move list
+    ("moves.heading", "Züge"),
+    // Study panel
+    ("study.heading", "Studie"),
+    ("study.untitled", "Unbenannte Studie"),
+    ("study.game_name", "Partie {0}"),
+    ("study.name", "Name:"),
+    ("study.chapter", "Kapitel:"),
+    ("study.chapter_n", "Kapitel {0}"),
+    ("study.comments", "Kommentare:"),
+    ("study.no_comments", "Noch keine Kommentare..."),
+    ("study.add", "Hinzufügen"),
+    ("study.variations", "Varianten:"),
+    ("study.save", "💾 Speichern"),
+    ("study.load", "📂 Öffnen"),
+    ("study.new", "🆕 Neu"),
+    ("study.export_pgn", "📄 PGN exportieren"),
+    ("study.new_study", "Neue Studie"),
+    ("study.study_name", "Name der Studie:"),
+    ("study.create", "Erstellen"),
+    ("study.cancel", "Abbrechen"),
+    ("study.load_study", "Studie öffnen"),
+    ("study.none_saved", "Keine gespeicherten Studien gefunden."),
+    ("study.close", "Schließen"),
+    ("study.start", "Anfang"),
+    ("study.alternatives", "Alternativen:"),
+    // Autoplay
+    ("nav.autoplay", "Automatisch abspielen / pausieren (hält bei Kommentaren)"),
+    ("nav.autoplay_speed", "Tempo:"),
+    // Status bar
+    ("status.no_engine", "Keine Engine"),
+    ("status.offline", "Offline"),
+    ("status.idle", "Bereit"),
+    ("status.thinking", "Rechnet"),
+    ("status.analyzing", "Analysiert"),
+    ("status.difficulty", "Spielstärke: {0}"),
+    // Settings
+    ("settings.title", "Einstellungen"),
+    ("settings.notation", "Notation:"),
+    ("notation.san", "Algebraisch (SAN)"),
+    ("notation.figurine", "Figurinen"),
+    ("notation.long", "Lange Notation"),
+    ("notation.uci", "UCI"),
+    // Toasts
+    ("toast.dismiss", "Schließen"),
+    ("toast.open_settings", "Einstellungen öffnen"),
+    ("toast.engine_error", "Engine-Fehler: {0}"),
+    ("error.engine_not_found", "Keine Engine unter {0} gefunden"),
+    ("error.engine_not_found_fix", "Installiere Stockfish oder wähle den Pfad der Engine in den Einstellungen."),
+    ("error.engine_start", "Die Engine {0} konnte nicht gestartet werden: {1}"),
+    ("error.engine_start_fix", "Prüfe, ob die Datei eine UCI-Engine für diesen Computer ist und ausgeführt werden darf."),
+    ("error.engine_timeout", "Die Engine hat innerhalb von {1} s nicht auf '{0}' geantwortet und wird neu gestartet"),
+    ("error.engine_timeout_fix", "Verringere Threads oder Hash der Engine, wenn der Computer ausgelastet ist, oder erhöhe die Zeitlimits in config.toml."),
+    ("error.engine_closed", "Die Engine wurde unerwartet beendet"),
+    ("error.engine_closed_fix", "Das Engine-Protokoll nennt vielleicht den Grund; eine Engine-Version für ältere Prozessoren kann helfen."),
+    ("error.engine_reported", "Die Engine meldet: {0}"),
+    ("error.engine_reported_fix", "Prüfe die Dateien, die die Engine braucht, etwa ihre Netzwerkdatei neben dem Programm."),
+    ("error.engine_fix", "Details stehen im Engine-Protokoll."),
+    ("error.study_read", "Studie {0} konnte nicht gelesen werden: {1}"),
+    ("error.study_read_fix", "Prüfe, ob der Studienordner im Datenverzeichnis lesbar ist."),
+    ("error.study_damaged", "Studie {0} ist beschädigt: {1}"),
+    ("error.study_damaged_fix", "Stelle die Studie aus einer Sicherung deiner Daten wieder her."),
+    ("error.study_write", "Studie {0} konnte nicht gespeichert werden: {1}"),
+    ("error.study_write_fix", "Prüfe den freien Speicherplatz und ob das Datenverzeichnis beschreibbar ist."),
+    ("error.file_write", "{0} konnte nicht geschrieben werden: {1}"),
+    ("error.file_write_fix", "Wähle einen anderen Ordner oder prüfe, ob dort Dateien geschrieben werden dürfen."),
+    ("error.pgn", "Die PGN konnte nicht gelesen werden: {0}"),
+    ("error.pgn_fix", "Prüfe die Züge um den genannten herum; Varianten müssen ab ihrer Abzweigung legal sein."),
+    ("error.no_pgn", "Die Zwischenablage enthält keine PGN-Partie"),
+    ("error.no_pgn_fix", "Kopiere eine Partie im PGN-Format und füge sie erneut ein."),
+    ("toast.engine_terminated", "Die Engine wurde beendet"),
+    ("toast.engine_move_failed", "Engine-Zug {0} konnte nicht ausgeführt werden: {1}"),
+    ("toast.pgn_copied", "PGN in die Zwischenablage kopiert"),
+    // Engine setup
+    ("settings.engine_path", "Engine:"),
+    ("settings.apply", "Übernehmen"),
+    ("settings.engine_missing", "Unter diesem Pfad gibt es keine Datei"),
+    ("onboarding.title", "Stockfish einrichten"),
+    ("onboarding.intro", "Stockfish ist eine freie Open-Source-Schachengine. Diese App nutzt sie zum Spielen und Analysieren, sie ist aber nicht enthalten und wurde auf diesem Computer nicht gefunden."),
+    ("onboarding.step_download", "1. Stockfish herunterladen"),
+    ("onboarding.download", "⬇ Für diesen Computer herunterladen"),
+    ("onboarding.all_downloads", "Alle Downloads"),
+    ("onboarding.step_locate", "2. Entpacken und den Pfad zur Stockfish-Datei angeben"),
+    ("onboarding.path_hint", "z. B. ~/Downloads/stockfish/stockfish-ubuntu-x86-64-avx2"),
+    ("onboarding.browse", "Durchsuchen…"),
+    ("onboarding.use_engine", "Diese Engine verwenden"),
+    ("onboarding.not_found", "Keine Datei unter {0} gefunden"),
+    ("onboarding.skip", "Ohne Engine fortfahren"),
+    // Move entry
+    ("entry.label", "Zug:"),
+    ("entry.hint", "e4, Nf3, g1f3…"),
+    // Name plates
+    ("plate.you", "Du"),
+    ("plate.white", "Weiß"),
+    ("plate.black", "Schwarz"),
+    // Search progress
+    ("controls.search_depth", "Tiefe {0}"),
+    // Line pinning
+    ("analysis.pin", "Variante anheften"),
+    ("analysis.unpin", "Lösen"),
+    // Eval gap
+    ("analysis.eval_gap", "Δ {0} zur 2. Variante"),
+    ("analysis.eval_gap_hint", "Unterschied in Bauern zwischen bestem und zweitbestem Zug"),
+    ("analysis.only_move", "⚠ Einziger Zug"),
+    ("analysis.only_move_hint", "Jeder andere Zug verliert deutlich"),
+    // Scratch board
+    ("scratch.start", "🔬 Was wäre, wenn?"),
+    ("scratch.start_hint", "Züge auf einer Wegwerfkopie dieser Stellung ausprobieren"),
+    ("scratch.keep", "✔ Variante behalten"),
+    ("scratch.keep_hint", "Die erkundeten Züge bis zum aktuellen in die Partie übernehmen"),
+    ("scratch.discard", "✖ Verwerfen"),
+    ("scratch.active", "Testbrett: Züge werden nicht gespeichert"),
+    // Board orientation
+    ("study.orientation", "Seite:"),
+    ("settings.board", "Brett:"),
+    ("settings.orient_to_player", "Brett nach meiner Farbe ausrichten"),
+    ("settings.orient_to_player_hint", "Automatisch drehen, wenn Farbe oder Kapitel wechseln; die Drehen-Taste funktioniert weiterhin"),
+    // Analysis refresh
+    ("settings.analysis_refresh", "Analyse-Aktualisierung:"),
+    ("settings.analysis_refresh_hint", "Wie oft die Varianten während der Analyse neu gezeichnet werden; längere Intervalle sparen CPU"),
+    // Time control and low-time warnings
+    ("outcome.white_timeout", "Weiß gewinnt auf Zeit!"),
+    ("outcome.black_timeout", "Schwarz gewinnt auf Zeit!"),
+    ("controls.time_control", "Bedenkzeit:"),
+    ("controls.untimed", "Ohne Uhr"),
+    ("settings.clock", "Uhr:"),
+    ("settings.clock_flash", "Uhr bei wenig Zeit blinken lassen"),
+    ("settings.clock_warning_sound", "Signalton bei 30 und 10 Sekunden"),
+    ("settings.clock_flag_sound", "Ton bei Zeitüberschreitung"),
+    // Touch mode
+    ("settings.touch", "Touch:"),
+    ("settings.touch_mode", "Touch-freundliches Layout"),
+    ("settings.touch_mode_hint", "Größere Schaltflächen und Abstände. Figuren mit dem Finger ziehen und das Brett mit zwei Fingern skalieren."),
+    // Board vision trainer
+    ("mode.training", "Training"),
+    ("vision.heading", "Brettvorstellung"),
+    ("vision.knight_path", "Springerwege"),
+    ("vision.attacks", "Angegriffene Felder"),
+    ("vision.knight_path_prompt", "Bringe den Springer auf dem kürzesten Weg von {0} nach {1}"),
+    ("vision.attacks_prompt", "Markiere alle Felder, die die Figur ({0}) auf {1} angreift"),
+    ("vision.moves_so_far", "Bisherige Züge: {0}"),
+    ("vision.check", "Prüfen"),
+    ("vision.clear", "Zurücksetzen"),
+    ("vision.reveal", "Lösung zeigen"),
+    ("vision.next", "Weiter ▶"),
+    ("vision.correct", "✔ Richtig!"),
+    ("vision.wrong", "✖ Nicht ganz, die Lösung ist markiert"),
+    ("vision.streak", "Serie: {0}"),
+    ("vision.stats", "{0}: {1}/{2} richtig, Ø {3} s"),
+    ("vision.pawn", "Bauer"),
+    ("vision.knight", "Springer"),
+    ("vision.bishop", "Läufer"),
+    ("vision.rook", "Turm"),
+    ("vision.queen", "Dame"),
+    ("vision.king", "König"),
+    ("heatmap.label", "Heatmap:"),
+    ("heatmap.off", "Aus"),
+    ("heatmap.occupancy", "Besetzte Felder"),
+    ("heatmap.attacks", "Angegriffene Felder"),
+    ("heatmap.hint", "Färbt Felder danach, wie oft sie während der ganzen Partie besetzt oder angegriffen waren"),
+    ("eval_graph.heading", "Bewertung"),
+    ("eval_graph.tooltip", "{0}. {1} — {2}"),
+    ("move_time.heading", "Zeit pro Zug"),
+    ("move_time.tooltip", "{0}. {1} — {2}"),
+    ("move_time.long", "Viel länger als üblich nachgedacht"),
+    ("move_time.instant_blunder", "Sofort gespielt – ein grober Fehler"),
+    ("settings.export", "PGN-Export"),
+    ("settings.export_mistakes", "Von der Engine gefundene Fehler markieren (?, ??)"),
+    ("settings.export_figurine", "Figurinennotation (♘f3)"),
+    ("settings.export_glyphs", "Kommentarsymbole (?!) statt $-Codes"),
+    ("settings.export_hint", "Beide aus lassen für Standard-PGN, das jedes Programm lesen kann"),
+    ("result.won", "Du hast gewonnen!"),
+    ("result.lost", "Du hast verloren"),
+    ("result.draw", "Remis"),
+    ("result.average_loss", "Durchschnittlicher Centipawn-Verlust: {0}"),
+    ("result.rematch", "Revanche"),
+    ("result.analyze", "Analysieren"),
+    ("result.export", "PGN kopieren"),
+    ("result.close", "Schließen"),
+    ("toast.pgn_empty", "Keine Partien gefunden"),
+    ("toast.open_failed", "{0} konnte nicht geöffnet werden: {1}"),
+    ("import.title", "PGN importieren"),
+    ("import.summary", "{0} enthält {1} Partie(n)."),
+    ("import.game", "In der Analyse öffnen"),
+    ("import.first_game", "Erste Partie in der Analyse öffnen"),
+    ("import.study", "Als Kapitel zur aktuellen Studie hinzufügen"),
+    ("import.cancel", "Abbrechen"),
+    ("import.drop_hint", "PGN-Datei (auch .pgn.gz oder .zip) oder FEN-Datei zum Öffnen ablegen"),
+    ("toast.not_pgn_or_fen", "{0} ist weder eine PGN-Datei noch eine FEN-Stellung"),
+    ("toast.import_skipped", "{0} Partie(n) konnten nicht importiert werden"),
+    ("toast.importing", "Partien aus {0} werden importiert…"),
+    ("toast.import_busy", "Warte, bis der laufende Import fertig ist"),
+    ("toast.keep_line_locked", "Die Studie ist gesperrt; erstelle eine eigene Kopie, um diese Variante zu behalten"),
+    ("toast.line_added_as_chapter", "Die Variante wurde als Abzweigung der Partie in Kapitel {0} gespeichert"),
+    ("toast.config_invalid", "{0} wird ignoriert: {1}"),
+    ("toast.config_save_failed", "config.toml konnte nicht gespeichert werden: {0}"),
+    ("toast.orphans_killed", "{0} Engine-Prozess(e) einer früheren Sitzung beendet"),
+    ("explorer.heading", "Eröffnungsbuch"),
+    ("explorer.masters", "Meister"),
+    ("explorer.lichess", "Lichess"),
+    ("explorer.loading", "Wird geladen…"),
+    ("explorer.retry", "Erneut versuchen"),
+    ("explorer.no_games", "Keine Partie erreichte diese Stellung"),
+    ("explorer.score", "Weiß {0}% · Remis {1}% · Schwarz {2}%"),
+    ("explorer.total", "{0} Partien"),
+    ("explorer.unauthorized", "Lichess verlangt für das Eröffnungsbuch eine Anmeldung: Token unter [lichess] in config.toml eintragen"),
+    ("import.my_games", "Zu meinen Partien hinzufügen"),
+    ("import.my_games_hint", "Partien für den persönlichen Eröffnungsbaum im Eröffnungsbuch behalten"),
+    ("explorer.personal", "Meine Partien"),
+    ("explorer.personal_empty", "Lege ein PGN deiner Partien ab, wähle „Zu meinen Partien hinzufügen“ und trage deine Benutzernamen in den Einstellungen ein"),
+    ("explorer.personal_total", "{0} deiner Partien ({1} übersprungen)"),
+    ("explorer.personal_score", "+{0} ={1} -{2} · {3}%"),
+    ("explorer.faced", "Von deinen Gegnern gespielt"),
+    ("explorer.deviation", "Weicht von deinem Repertoire ab, das {0} spielt"),
+    ("settings.usernames", "Deine Benutzernamen"),
+    ("settings.usernames_hint", "Kommagetrennte Namen, unter denen du spielst, um deine Seite in importierten Partien zu erkennen"),
+    ("toast.my_games_added", "{0} neue Partien hinzugefügt, {1} bereits importierte übersprungen; Eröffnungsbaum umfasst jetzt {2} deiner Partien"),
+    ("toast.my_games_none_new", "Alle {0} Partien waren bereits importiert"),
+    ("puzzle.daily", "Tagesaufgabe"),
+    ("puzzle.source_lichess", "Lichess (lokale Sammlung als Ersatz)"),
+    ("puzzle.source_bundled", "Lokale Sammlung"),
+    ("puzzle.source_off", "Aus"),
+    ("puzzle.fetch_failed", "Lichess nicht erreichbar"),
+    ("puzzle.white_to_move", "Weiß am Zug"),
+    ("puzzle.black_to_move", "Schwarz am Zug"),
+    ("puzzle.rating", "Wertung {0}"),
+    ("puzzle.offline", "Lokale Aufgabe, Lichess nicht erreichbar"),
+    ("puzzle.loading", "Heutige Aufgabe wird geladen…"),
+    ("puzzle.streak", "Serie: {0} Tage"),
+    ("puzzle.streak_best", "Serie: {0} Tage (Rekord {1})"),
+    ("puzzle.solve", "Lösen"),
+    ("puzzle.later", "Später"),
+    ("puzzle.solved", "Gelöst!"),
+    ("puzzle.solved_revealed", "Mit Hilfe gelöst, die Serie bleibt unverändert"),
+    ("puzzle.find_best", "Finde den besten Zug"),
+    ("puzzle.mistakes", "Fehlversuche: {0}"),
+    ("puzzle.open_lichess", "Auf Lichess öffnen"),
+    ("puzzle.show_move", "Zug zeigen"),
+    ("puzzle.show_move_hint", "Spielt den nächsten Zug der Lösung; die heutige Aufgabe zählt dann nicht mehr für die Serie"),
+    ("puzzle.close", "Schließen"),
+    ("puzzle.wrong", "Nicht der richtige Zug, versuch es noch einmal"),
+    ("settings.daily_puzzle", "Tagesaufgabe"),
+    ("settings.daily_puzzle_hint", "Wird beim Start angeboten, bis sie gelöst ist; Änderungen gelten ab dem nächsten Start"),
+    ("broadcast.heading", "Übertragung verfolgen"),
+    ("broadcast.url_hint", "PGN-URL oder Dateipfad"),
+    ("broadcast.interval", "Prüfen alle"),
+    ("broadcast.live_analysis", "Neue Züge mit der Engine analysieren"),
+    ("broadcast.follow", "Verfolgen"),
+    ("broadcast.stop", "Nicht mehr verfolgen"),
+    ("broadcast.game", "Partie {0}"),
+    ("broadcast.error", "Aktualisierung fehlgeschlagen: {0}"),
+    ("broadcast.updated", "Vor {0} s aktualisiert"),
+    ("broadcast.waiting", "Warte auf die erste Aktualisierung…"),
+    ("mode.online", "Online"),
+    ("online.player_engine", "Engine"),
+    ("online.player_me", "Ich"),
+    ("online.player", "Züge von"),
+    ("online.unauthorized", "Lichess hat das Token abgelehnt; es braucht den Bereich board:play oder bot:play"),
+    ("online.no_token", "Trage ein Lichess-API-Token mit dem Bereich board:play (oder bot:play) in config.toml unter [lichess] token ein, um online zu spielen"),
+    ("online.bot_account", "Bot"),
+    ("online.board_account", "Spieler"),
+    ("online.connected", "Verbunden als {0} ({1})"),
+    ("online.connecting", "Verbinde mit Lichess…"),
+    ("online.reconnect", "Neu verbinden"),
+    ("online.disconnected", "Verbindung verloren: {0}. Neuer Versuch…"),
+    ("online.stream_closed", "Lichess hat die Verbindung geschlossen"),
+    ("online.move_rejected", "Lichess hat den Zug abgelehnt: {0}"),
+    ("online.playing", "Partie gegen {0}"),
+    ("online.your_turn", "Du bist am Zug"),
+    ("online.their_turn", "Warte auf den Gegner"),
+    ("online.resign", "Aufgeben"),
+    ("online.finished", "Letzte Partie: {0}"),
+    ("online.seek", "Gegner suchen"),
+    ("online.seek_hint", "Lichess paart Suchen vom Brett nur für Schnell- und klassische Partien (ab 8 Minuten)"),
+    ("online.seeking", "Suche einen Gegner…"),
+    ("online.challenges", "Herausforderungen"),
+    ("online.no_challenges", "Keine offenen Herausforderungen"),
+    ("online.rated", "gewertet"),
+    ("online.casual", "ungewertet"),
+    ("online.accept", "Annehmen"),
+    ("online.decline", "Ablehnen"),
+    ("online.variant_unsupported", "Hier kann nur Standardschach gespielt werden"),
+    ("online.challenged", "{0} fordert dich heraus"),
+    ("online.game_over", "Die Online-Partie ist beendet"),
+    ("online.error", "Lichess: {0}"),
+    ("settings.dgt", "Elektronisches Brett"),
+    ("settings.dgt_no_port", "Port wählen"),
+    ("settings.dgt_connect", "Verbinden"),
+    ("settings.dgt_disconnect", "Trennen"),
+    ("settings.dgt_connected", "Brett an {0} verbunden"),
+    ("settings.dgt_waiting", "Warte auf das Brett…"),
+    ("settings.dgt_rotated", "Schwarz sitzt auf der Kabelseite"),
+    ("settings.announce_moves", "Auf dem Brett auszuführende Züge vorlesen"),
+    ("settings.announce_moves_hint", "Verwendet die Sprachausgabe des Systems (say, spd-say oder espeak)"),
+    ("dgt.make_move", "Führe {0} auf dem Brett aus"),
+    ("dgt.out_of_sync", "Das Brett stimmt nicht mit der Stellung auf dem Bildschirm überein"),
+    ("speech.king", "König"),
+    ("speech.queen", "Dame"),
+    ("speech.rook", "Turm"),
+    ("speech.bishop", "Läufer"),
+    ("speech.knight", "Springer"),
+    ("speech.takes", "schlägt"),
+    ("speech.promotes", "wandelt um in"),
+    ("speech.check", "Schach"),
+    ("speech.mate", "schachmatt"),
+    ("speech.castle_short", "kurze Rochade"),
+    ("speech.castle_long", "lange Rochade"),
+    ("report.score", "Partie"),
+    ("report.evaluation", "Bewertung"),
+    ("report.accuracy", "Genauigkeit"),
+    ("report.average_loss", "Durchschnittlicher Verlust (cp)"),
+    ("report.inaccuracies", "Ungenauigkeiten"),
+    ("report.mistakes", "Fehler"),
+    ("report.blunders", "Patzer"),
+    ("report.critical", "Kritische Momente"),
+    ("report.no_critical", "Es wurden keine Fehler gefunden."),
+    ("report.loss", "Verlor {0} Bauern"),
+    ("controls.export_report", "Bericht speichern…"),
+    ("toast.report_saved", "Bericht gespeichert unter {0}"),
+    ("toast.report_failed", "Bericht konnte nicht gespeichert werden: {0}"),
+    ("settings.backup", "Sicherung"),
+    ("settings.backup_create", "Sichern…"),
+    ("settings.backup_restore", "Wiederherstellen…"),
+    ("settings.backup_hint", "Speichert Studien, importierte Partien, die Konfigurationsdatei und die Einstellungen in einer ZIP-Datei"),
+    ("toast.backup_saved", "Sicherung gespeichert unter {0}"),
+    ("toast.backup_failed", "Sicherung konnte nicht gespeichert werden: {0}"),
+    ("toast.restore_failed", "Sicherung konnte nicht wiederhergestellt werden: {0}"),
+    ("toast.backup_restored", "Sicherung wiederhergestellt"),
+    ("analysis.open_lichess", "In Lichess öffnen"),
+    ("analysis.open_lichess_hint", "Öffnet die Partie auf dem Lichess-Analysebrett im Browser, für die Cloud-Analyse"),
+    ("engine_log.title", "Engine-Protokoll"),
+    ("engine_log.copy", "Kopieren"),
+    ("engine_log.clear", "Leeren"),
+    ("engine_log.empty", "Die Engine hat keine Fehler oder Ausgaben auf stderr gemeldet."),
+    ("settings.engine_log", "Protokoll"),
+    ("toast.show_engine_log", "Protokoll anzeigen"),
+    ("settings.power", "Energiesparen"),
+    ("settings.power_pause_unfocused", "Analyse im Hintergrund pausieren"),
+    ("settings.power_pause_on_battery", "Analyse im Akkubetrieb pausieren"),
+    ("settings.power_cpu_limit", "CPU-Limit"),
+    ("settings.power_cpu_limit_hint", "Anteil der CPU-Kerne, den die Engine im Hintergrund oder im Akkubetrieb nutzt"),
+    ("analysis.power_paused", "Zum Energiesparen pausiert, läuft weiter sobald das Fenster aktiv ist oder das Gerät lädt"),
+    ("settings.study_format", "Studiendateien"),
+    ("settings.study_format_hint", "Kompakte Dateien sind bei großen Repertoires deutlich kleiner und schneller; Studien in beiden Formaten lassen sich immer öffnen"),
+    ("study.format_json", "JSON (lesbar)"),
+    ("study.format_compact", "Kompakt (binär)"),
+    ("controls.review_game", "🔍 Partie auswerten"),
+    ("controls.review_game_hint", "Die Engine bewertet jeden Zug, für den noch keine Bewertung vorliegt"),
+    ("controls.review_progress", "Auswertung {0}/{1}"),
+    ("controls.review_cancel", "Abbrechen"),
+    ("toast.review_done", "Partieauswertung abgeschlossen"),
+    ("settings.engine_hash", "Engine-Hash"),
+    ("settings.keep_hash", "Zwischen Stellungen derselben Partie behalten"),
+    ("settings.keep_hash_hint", "Die Engine nutzt Ergebnisse benachbarter Stellungen weiter, so geht das Durchgehen einer Partie schneller; andere Stellungen beginnen weiterhin neu"),
+    ("analysis.clear_hash", "Hash leeren"),
+    ("analysis.clear_hash_hint", "Frühere Suchen vergessen und diese Stellung neu bewerten"),
+    ("games.heading", "Meine Partien"),
+    ("games.empty", "Noch keine Partien importiert"),
+    ("games.untitled", "Partie ohne Titel"),
+    ("games.open_hint", "In der Analyse öffnen"),
+    ("games.more", "{0} weitere Partien, Filter eingrenzen, um sie zu sehen"),
+    ("export.heading", "Partien exportieren"),
+    ("export.all_games", "📄 Alle Partien exportieren"),
+    ("export.include_my_games", "Meine importierten Partien einschließen"),
+    ("export.include_my_games_hint", "Importierte Partien hinzufügen, deren Spieler, Turnier, Datum oder anderer Tag den Filter enthält"),
+    ("export.filter_hint", "Filter, z. B. ein Spieler oder 2024"),
+    ("toast.no_games_to_export", "Noch keine Partien zum Exportieren"),
+    ("toast.games_exported", "{0} Partien nach {1} exportiert"),
+    ("toast.games_export_failed", "Partien konnten nicht exportiert werden: {0}"),
+    ("board.move_slider_hint", "Ziehen, um Zug für Zug durch die Partie zu blättern"),
+    ("analysis.stable", "stabil"),
+    ("analysis.settling", "pendelt sich ein"),
+    ("analysis.swinging", "schwankt"),
+    ("analysis.stability_hint", "Wie stark sich die Bewertung über die letzten Tiefen verändert hat"),
+    ("critical.heading", "Kritische Momente"),
+    ("critical.missed_win", "verpasster Gewinn"),
+    ("critical.swing", "Wendepunkt"),
+    ("critical.jump_hint", "Zur Stellung vor diesem Zug springen"),
+    ("study.snippet_idea", "mit der Idee…"),
+    ("study.snippet_only_move", "der einzige Zug"),
+    ("study.snippet_better_was", "besser war…"),
+    ("study.snippet_threatening", "droht…"),
+    ("study.snippet_plan", "der Plan ist…"),
+    ("glyph.good", "Guter Zug"),
+    ("glyph.mistake", "Fehler"),
+    ("glyph.brilliant", "Brillanter Zug"),
+    ("glyph.blunder", "Grober Fehler"),
+    ("glyph.interesting", "Interessanter Zug"),
+    ("glyph.dubious", "Zweifelhafter Zug"),
+    ("glyph.equal", "Ausgeglichene Stellung"),
+    ("glyph.unclear", "Unklare Stellung"),
+    ("glyph.white_slightly", "Weiß steht etwas besser"),
+    ("glyph.black_slightly", "Schwarz steht etwas besser"),
+    ("glyph.white_better", "Weiß steht besser"),
+    ("glyph.black_better", "Schwarz steht besser"),
+    ("glyph.white_winning", "Weiß steht auf Gewinn"),
+    ("glyph.black_winning", "Schwarz steht auf Gewinn"),
+    ("glyph.only_move", "Einziger Zug"),
+    ("glyph.zugzwang", "Zugzwang"),
+    ("glyph.development", "Entwicklungsvorsprung"),
+    ("glyph.initiative", "Initiative"),
+    ("glyph.attack", "Angriff"),
+    ("glyph.compensation", "Kompensation für das Material"),
+    ("glyph.counterplay", "Gegenspiel"),
+    ("glyph.time_trouble", "Zeitnot"),
+    ("glyph.with_idea", "Mit der Idee"),
+    ("glyph.novelty", "Neuerung"),
+    ("moves.book_exit", "Erster Zug außerhalb der Eröffnungstheorie"),
+    ("quality.brilliant", "Brillant: Opfer, die die Bewertung hielten"),
+    ("quality.good", "Gute Züge"),
+    ("quality.inaccuracies", "Ungenauigkeiten"),
+    ("quality.mistakes", "Fehler"),
+    ("quality.blunders", "Grobe Fehler"),
+    ("endgame.heading", "Endspieltraining"),
+    ("endgame.play_hint", "Diese Stellung gegen die Engine in voller Stärke spielen"),
+    ("endgame.kq_k", "Dame gegen König"),
+    ("endgame.kr_k", "Turm gegen König"),
+    ("endgame.kp_k", "König und Bauer gegen König"),
+    ("endgame.lucena", "Lucena-Stellung"),
+    ("endgame.philidor", "Philidor-Verteidigung"),
+    ("endgame.krb_kr", "Turm und Läufer gegen Turm (verteidigen)"),
+    ("endgame.kbn_k", "Läufer-Springer-Matt"),
+    ("endgame.goal", "Tablebase: {0}"),
+    ("endgame.goal_win", "gewinnen"),
+    ("endgame.goal_draw", "Remis halten"),
+    ("endgame.goal_loss", "bei bestem Spiel verloren, so zäh wie möglich"),
+    ("endgame.looking_up", "Tablebase wird abgefragt..."),
+    ("endgame.fetch_failed", "Tablebase nicht erreichbar, das Ergebnis kann nicht geprüft werden"),
+    ("endgame.full_strength", "Die Engine spielt in voller Stärke"),
+    ("toast.endgame_held", "{0}: Tablebase-Ergebnis erreicht"),
+    ("toast.endgame_missed", "{0}: Ergebnis schlechter als laut Tablebase, versuch es noch einmal"),
+    ("tournament.heading", "Engine-Turnier"),
+    ("tournament.levels", "Spielstärken:"),
+    ("tournament.second_engine_hint", "Pfad einer zweiten Engine (optional)"),
+    ("tournament.double_round", "Jede Paarung mit beiden Farben spielen"),
+    ("tournament.movetime", "Zeit pro Zug:"),
+    ("tournament.start", "▶ Turnier starten"),
+    ("tournament.stop", "⏹ Anhalten"),
+    ("tournament.need_two", "Mindestens zwei Teilnehmer wählen"),
+    ("tournament.export", "📄 Partien exportieren"),
+    ("tournament.progress", "Partie {0} / {1}"),
+    ("tournament.failed", "Turnier abgebrochen: {0}"),
+    ("tournament.score", "Punkte"),
+    ("tournament.elo", "Elo"),
+    ("tournament.elo_hint", "Geschätzter Wertungsabstand zum durchschnittlichen Gegner, aus den Punkten"),
+    ("tournament.engine_gone", "Eine Engine hat sich während des Turniers beendet"),
+    ("tournament.no_engine", "Keine Engine für das Turnier gefunden"),
+    ("toast.tournament_finished", "Turnier nach {0} Partien beendet"),
+    ("plan.heading", "Trainingsplan"),
+    ("plan.puzzles", "Aufgaben"),
+    ("plan.puzzles_hint", "Gelöste Aufgaben, ohne die Lösung anzuzeigen"),
+    ("plan.lines", "Repertoire-Varianten"),
+    ("plan.lines_hint", "Studienvarianten bis zum letzten Zug durchgegangen"),
+    ("plan.reviews", "Partieanalysen"),
+    ("plan.reviews_hint", "Vollständig durchgelaufene Engine-Analysen einer Partie"),
+    ("plan.done_today", "✔ Die heutigen Ziele sind erreicht"),
+    ("plan.streak", "Serie: {0} Tage (Bestwert {1})"),
+    ("toast.plan_met", "Die heutigen Trainingsziele sind erreicht"),
+    ("speech.pawn", "Bauer"),
+    ("coach.hanging", "Deine Figur auf {1} ({0}) ist angegriffen und ungedeckt"),
+    ("coach.attacked_by_lesser", "Deine Figur auf {1} ({0}) wird angegriffen von: {2}"),
+    ("coach.castle", "Die Engine will rochieren"),
+    ("coach.promote", "Die Engine will auf {0} umwandeln"),
+    ("coach.capture", "Die Engine will auf {1} schlagen ({0})"),
+    ("coach.check", "Die Engine will Schach geben ({0})"),
+    ("coach.open_file", "Die Engine will die {0}-Linie öffnen"),
+    ("coach.piece_move", "Die Engine will nach {1} ziehen ({0})"),
+    ("coach.pawn_push", "Die Engine will den Bauern nach {0} ziehen"),
+    ("settings.coach", "Trainer:"),
+    ("settings.coach_hints", "Bewertung erklären"),
+    ("settings.coach_hints_hint", "Hinweise in Worten unter dem Bewertungsbalken zeigen, etwa bedrohte Figuren und worum es beim besten Zug der Engine geht"),
+    ("settings.promotion", "Umwandlung:"),
+    ("settings.auto_queen", "Immer in eine Dame umwandeln"),
+    ("settings.auto_queen_hint", "Ohne Figurenauswahl umwandeln. N, R oder B gedrückt halten, während der Bauer abgelegt wird, um in Springer, Turm oder Läufer umzuwandeln"),
+    ("premove.heading", "Vorauszüge"),
+    ("premove.heading_hint", "Züge, die sofort nach der Antwort der Engine für dich gespielt werden. Wähle einen auf dem Brett, während die Engine rechnet, oder tippe eine Folge erwarteter Antworten und deiner Züge."),
+    ("premove.line_hint", "z. B. Nxe4 Nxe4 Bxe4 Qxe4"),
+    ("premove.set", "Vormerken"),
+    ("premove.empty", "Nichts vorgemerkt"),
+    ("premove.conditional", "wenn {0} → {1}"),
+    ("premove.any_reply", "nach jeder Antwort → {0}"),
+    ("premove.clear", "Vorauszüge löschen"),
+    ("toast.premove_illegal", "Vorauszug nicht mehr legal, Liste geleert"),
+    ("toast.premove_cancelled", "Die Engine hat anders gespielt, Vorauszüge gelöscht"),
+    ("toast.premove_invalid", "Diese Folge lässt sich nicht vormerken: {0}"),
+    ("settings.variety", "Eröffnungsvielfalt"),
+    ("settings.variety_enabled", "Erste Züge der Engine variieren"),
+    ("settings.variety_hint", "Die Engine wählt ihre ersten Züge zufällig unter den Varianten, die fast so gut bewertet sind wie die beste, damit Partien auf derselben Stufe nicht alle gleich beginnen"),
+    ("settings.variety_moves", "Züge"),
+    ("settings.variety_margin", "Innerhalb"),
+    ("settings.variety_margin_hint", "Um wie viele Centipawns ein Zug schlechter als die beste Variante sein darf, um noch gewählt zu werden"),
+    ("controls.undo_left", "↩ Zug zurücknehmen (noch {0})"),
+    ("controls.takebacks", "Zugrücknahmen"),
+    ("controls.takebacks_locked", "Zugrücknahmen lassen sich vor dem ersten Zug oder nach der Partie ändern"),
+    ("takeback.unlimited", "Unbegrenzt"),
+    ("takeback.per_game", "{0} pro Partie"),
+    ("takeback.off", "Keine"),
+    ("resign.confirm", "Diese Partie aufgeben?"),
+    ("resign.lost", "Deine Stellung sieht verloren aus"),
+    ("resign.lost_detail", "Die Engine bewertet sie seit mehreren Zügen mit {0}. Starke Spieler geben hier auf."),
+    ("resign.resign", "Aufgeben"),
+    ("resign.play_on", "Weiterspielen"),
+    ("settings.auto_resign", "Automatisch aufgeben"),
+    ("settings.auto_resign_enabled", "Aufgabe verlorener Partien anbieten"),
+    ("settings.auto_resign_hint", "Fragt, ob du aufgeben willst, sobald die Bewertung der Engine mehrere ihrer Züge lang unter der Schwelle bleibt – zur Übung darin, verlorene Partien zu erkennen"),
+    ("settings.auto_resign_threshold", "Rückstand"),
+    ("settings.auto_resign_moves", "Über Züge"),
+    ("settings.engine_roles", "Engines pro Modus"),
+    ("settings.engine_roles_hint", "Im Spielmodus, bei der Analyse und bei Partiebewertungen unterschiedliche Engines nutzen und die Threads jeder Engine begrenzen. Jede Engine läuft weiter, während ein anderer Modus genutzt wird. Leere Pfade nutzen die Engine darüber, bei Bewertungen die gerade genutzte Engine."),
+    ("settings.engine_role_game", "Partie"),
+    ("settings.engine_role_analysis", "Analyse"),
+    ("settings.engine_role_default", "Engine darüber"),
+    ("settings.engine_role_review", "Partieauswertung"),
+    ("settings.engine_role_review_hint", "Geladene Engine"),
+    ("settings.engine_threads_any", "Alle Threads"),
+    ("settings.engine_threads", "Bis zu {0} Threads"),
+    ("settings.uci_console", "UCI-Konsole"),
+    ("settings.uci_console_hint", "UCI-Befehle direkt an die laufende Engine senden"),
+    ("console.title", "UCI-Konsole"),
+    ("console.hint", "↑/↓ holt frühere Befehle zurück"),
+    ("console.input_hint", "UCI-Befehl, z. B. d oder eval"),
+    ("console.warn_search", "Eine Suche hier zu starten oder anzuhalten kann laufende Partien und Analysen stören"),
+    ("console.warn_position", "Ändert die Stellung der Engine ohne Wissen der App bis zu ihrer nächsten Suche"),
+    ("console.warn_handshake", "Die App hat den Handshake schon erledigt; die Antwort kann mit anderer Ausgabe verwechselt werden"),
+    ("console.warn_quit", "Beendet die Engine; starte sie in den Einstellungen neu"),
+    ("console.warn_option", "Die App setzt diese Option selbst und bemerkt die Änderung nicht"),
+    ("analysis.exclude", "Diesen Zug ausschließen und neu suchen, um den nächstbesten Plan zu sehen"),
+    ("analysis.excluded", "Ausgeschlossen:"),
+    ("analysis.restore_move", "Diesen Zug wieder mitsuchen"),
+    ("settings.check_moves", "Züge vor dem Ausführen prüfen"),
+    ("settings.check_moves_hint", "Im Spielmodus wartet ein auf dem Brett gemachter Zug, bis die Engine berechnet hat, wie viel er gegenüber dem besten Zug verliert"),
+    ("staged.move", "Dein Zug: {}"),
+    ("staged.checking", "Wird geprüft…"),
+    ("staged.no_loss", "So gut wie die Wahl der Engine"),
+    ("staged.cost", "Verliert {} Bauern gegenüber dem besten Zug"),
+    ("staged.play", "Ausführen"),
+    ("staged.cancel", "Anderen wählen"),
+    ("phase.opening", "Eröffnung"),
+    ("phase.middlegame", "Mittelspiel"),
+    ("phase.endgame", "Endspiel"),
+    ("report.by_phase", "Durchschnittlicher Verlust nach Phase (cp)"),
+    ("quality.by_phase", "Durchschnittlicher Verlust nach Spielphase, in Centipawns"),
+    ("coverage.open", "Repertoire-Abdeckung…"),
+    ("coverage.open_hint", "Deine Partien mit dem Repertoire in deinen Studien vergleichen"),
+    ("coverage.title", "Repertoire-Abdeckung"),
+    ("coverage.empty", "Deine Partien bleiben in deinem Repertoire. Studien aus Sicht von Weiß oder Schwarz zählen als Repertoire dieser Seite."),
+    ("coverage.suggestions", "Als Nächstes lernen"),
+    ("coverage.misplayed", "Falsch gespielte Varianten"),
+    ("coverage.uncovered", "Antworten, die dein Repertoire nicht abdeckt"),
+    ("coverage.none", "Keine"),
+    ("coverage.open_line", "Auf dem Brett zeigen"),
+    ("coverage.games", "{0} Partien, {1} verloren"),
+    ("coverage.prepared", "vorbereitet: {0}"),
+    ("compare.open", "⚖ Vergleichen"),
+    ("compare.open_hint", "Die Stellung neben dem Ende der Engine-Variante oder einer angehefteten Stellung zeigen"),
+    ("compare.title", "Stellungen vergleichen"),
+    ("compare.current", "Brett"),
+    ("compare.engine_line", "Nach der Engine-Variante"),
+    ("compare.pinned", "Angeheftete Stellung"),
+    ("compare.pin", "📌 Stellung anheften"),
+    ("compare.pin_hint", "Spätere Stellungen mit dieser vergleichen"),
+    ("compare.unpin", "Lösen"),
+    ("compare.pawns_only", "Nur Bauern"),
+    ("compare.pawns_only_hint", "Nur Felder markieren, auf denen sich die Bauernstruktur unterscheidet"),
+    ("compare.no_target", "Starte die Analyse oder hefte eine Stellung zum Vergleichen an."),
+    ("compare.differences", "{0} Felder unterscheiden sich"),
+    ("share.copy_link", "Stellungslink kopieren"),
+    ("share.copy_link_hint", "Einen Link zum Lichess-Analysebrett mit der Stellung auf dem Brett kopieren"),
+    ("share.copy_pgn", "Kommentierte PGN kopieren"),
+    ("share.copy_pgn_hint", "Die Partie als PGN kopieren, mit den aktuellen Varianten der Engine als Kommentar bei der Stellung auf dem Brett"),
+    ("toast.link_copied", "Link in die Zwischenablage kopiert"),
+    ("print.after", "Nach {0}"),
+    ("study.print", "Kapitel drucken…"),
+    ("study.print_hint", "Das Kapitel als druckfertige Seite speichern, die sich im Browser drucken oder als PDF sichern lässt, mit Diagrammen, Varianten und Kommentaren"),
+    ("study.print_every", "Diagramm alle"),
+    ("study.print_moves", "Züge"),
+    ("share.latex_diagram", "LaTeX-Diagramm kopieren"),
+    ("share.latex_diagram_hint", "Die Stellung als Snippet des chessboard-Pakets für LaTeX-Dokumente kopieren"),
+    ("share.latex_line", "LaTeX-Zugfolge kopieren"),
+    ("share.latex_line_hint", "Die Züge bis zur Stellung auf dem Brett als xskak-Snippet kopieren, gefolgt von einem Diagramm"),
+    ("toast.latex_copied", "LaTeX in die Zwischenablage kopiert"),
+    ("import.summary_archive", "{0} ist ein Archiv; die Partien werden beim Import gelesen."),
+    ("toast.duplicate_line", "Diese Variante steht bereits in Kapitel \"{0}\""),
+    ("toast.transposed_line", "Kapitel \"{0}\" erreicht diese Stellung bereits mit einer anderen Zugfolge"),
+    ("toast.go_to_duplicate", "Stattdessen dorthin"),
+    ("study.drill", "Kapitel trainieren"),
+    ("study.drill_hint", "Die Varianten des Kapitels von seiner Seite spielen; die Antworten des Gegners werden zufällig gewählt"),
+    ("study.drill_stop", "Training beenden"),
+    ("study.weak_spots", "Schwachstellen"),
+    ("study.weak_spots_none", "Stellungen, die du im Training verfehlst, erscheinen hier"),
+    ("study.weak_spot_recall", "{0}/{1} gewusst"),
+    ("drill.wrong", "Nicht dein Repertoirezug, versuch es noch einmal (noch {0} Versuche)"),
+    ("drill.finished", "Ende der Variante"),
+    ("drill.nothing", "Dieses Kapitel hat keine Züge seiner Seite zum Trainieren"),
+    ("drill.time_out", "Zeit für diesen Zug abgelaufen"),
+    ("drill.failed", "Zu viele Fehler, nächste Variante"),
+    ("drill.hint", "Repertoirezug: {0}"),
+    ("drill.summary_title", "Trainingsergebnis"),
+    ("drill.summary_score", "Punktzahl: {0}%"),
+    ("drill.summary_passed", "Varianten geschafft"),
+    ("drill.summary_failed", "Varianten verfehlt"),
+    ("drill.summary_first_try", "Züge im ersten Versuch gefunden"),
+    ("drill.summary_positions", "Gefragte Züge"),
+    ("drill.summary_mistakes", "Fehler"),
+    ("drill.summary_timeouts", "Zeit abgelaufen"),
+    ("drill.summary_hints", "Genutzte Hinweise"),
+    ("study.drill_mistakes", "Fehler in dieser Variante: {0}/{1}"),
+    ("study.drill_time", "noch {0} s"),
+    ("study.drill_hint_button", "Hinweis"),
+    ("settings.drill", "Repertoiretraining"),
+    ("settings.drill_move_seconds", "Zeit pro Zug"),
+    ("settings.drill_move_seconds_hint", "0 für kein Zeitlimit"),
+    ("settings.drill_mistakes", "Erlaubte Fehler pro Variante"),
+    ("settings.drill_hints", "Hinweise erlauben"),
+    ("audit.move_loses", "Engine: {0} cp schlechter als ihr bester Zug"),
+    ("audit.bad_ending", "Engine: die Variante endet bei {0} für deine Seite"),
+    ("audit.done", "Engine-Prüfung fertig, {0} Warnung(en)"),
+    ("audit.no_engine", "Die Engine ist nicht bereit, das Kapitel zu prüfen"),
+    ("audit.busy", "Warte, bis die Engine ihre laufende Auswertung beendet hat"),
+    ("audit.progress", "Kapitel wird mit der Engine geprüft… {0}/{1}"),
+    ("audit.cancel", "Abbrechen"),
+    ("audit.cancelled", "Engine-Prüfung abgebrochen"),
+    ("audit.stale", "Die Studie hat sich während der Engine-Prüfung geändert, die Ergebnisse wurden verworfen"),
+    ("annotate.progress", "Kommentiere: {0}/{1} Stellungen"),
+    ("annotate.done", "Kommentierung fertig: {0} Fehler markiert"),
+    ("annotate.cancelled", "Kommentierung abgebrochen"),
+    ("annotate.nothing", "Das Kapitel hat keine Züge zum Kommentieren"),
+    ("annotate.better", "Besser war {0}"),
+    ("study.audit", "Mit Engine prüfen"),
+    ("study.audit_hint", "Züge deiner Seite markieren, die mehr als den Schwellenwert gegenüber dem besten Engine-Zug verlieren, und Varianten, die in schlechten Stellungen enden"),
+    ("study.audit_threshold", "Schwellenwert"),
+    ("study.lock_hint", "Studie sperren: Sie kann weiter durchgesehen und trainiert, aber nicht bearbeitet werden"),
+    ("study.locked", "Schreibgeschützte Studie"),
+    ("study.locked_title", "Gesperrte Studie"),
+    ("study.locked_prompt", "Diese Studie ist gesperrt. Eine eigene Kopie zum Bearbeiten anlegen?"),
+    ("study.make_copy", "Kopie anlegen"),
+    ("study.copy_name", "{0} (Kopie)"),
+    ("study.paste_pgn", "📋 PGN als Kapitel einfügen"),
+    ("study.paste_pgn_hint", "Die Partie aus der Zwischenablage mit Varianten und Kommentaren als neues Kapitel hinzufügen"),
+    ("study.headers", "PGN-Kopfzeilen"),
+    ("study.header_name", "Tag"),
+    ("study.header_value", "Wert"),
+    ("study.header_remove", "Diesen Tag entfernen"),
+    ("study.annotate", "Partie kommentieren"),
+    ("study.annotate_hint", "Die Engine jeden Zug der Hauptvariante bewerten lassen und Fehler mit Symbol und besserer Fortsetzung markieren"),
+    ("settings.clicks", "Klicks"),
+    ("settings.own_piece", "Klick auf eine andere eigene Figur:"),
+    ("settings.own_piece_switch", "Wählt sie aus"),
+    ("settings.own_piece_move", "Versucht den Zug"),
+    ("settings.deselect_selected", "Klick auf die ausgewählte Figur hebt die Auswahl auf"),
+    ("settings.confirm_destination", "Züge mit zweitem Klick bestätigen"),
+    ("settings.confirm_destination_hint", "Der erste Klick auf ein Zielfeld markiert es, der zweite führt den Zug aus"),
+    ("settings.engine_moves", "Engine-Züge"),
+    ("settings.engine_move_highlight", "Züge der Engine in eigener Farbe hervorheben"),
+    ("settings.engine_move_sound", "Ton abspielen, wenn die Engine zieht"),
+];
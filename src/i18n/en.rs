@@ -0,0 +1,726 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    // Formatting
+    ("format.move_number", "{0}."),
+    // Mode and navigation
+    ("mode.label", "Mode:"),
+    ("mode.game", "Play against the engine"),
+    ("mode.analysis", "Analysis"),
+    ("mode.study", "Study"),
+    ("nav.label", "Navigation:"),
+    ("nav.start", "Go to start"),
+    ("nav.previous", "Previous move"),
+    ("nav.next", "Next move"),
+    ("nav.end", "Go to end"),
+    ("nav.move_counter", "Move: {0} / {1}"),
+    // Game controls
+    ("controls.engine_thinking", "Engine thinking..."),
+    ("controls.new_game", "New Game"),
+    ("controls.flip_board", "Flip Board"),
+    ("controls.play_as", "Play as:"),
+    ("controls.white", "White"),
+    ("controls.black", "Black"),
+    ("controls.difficulty", "Difficulty:"),
+    ("controls.personality", "Personality:"),
+    ("controls.theme", "Theme:"),
+    ("controls.language", "Language:"),
+    ("controls.resign", "🏳 Resign"),
+    ("controls.offer_draw", "🤝 Offer Draw"),
+    ("controls.undo", "↩ Undo Move"),
+    ("controls.export_pgn", "📄 Export PGN"),
+    ("controls.save_to_study", "📚 Save to Study"),
+    // Outcomes
+    ("outcome.white_checkmate", "White wins by checkmate!"),
+    ("outcome.black_checkmate", "Black wins by checkmate!"),
+    ("outcome.stalemate", "Draw by stalemate"),
+    ("outcome.insufficient_material", "Draw by insufficient material"),
+    ("outcome.threefold", "Draw by threefold repetition"),
+    ("outcome.fifty_move", "Draw by fifty-move rule"),
+    ("outcome.white_resignation", "White wins by resignation!"),
+    ("outcome.black_resignation", "Black wins by resignation!"),
+    ("outcome.agreement", "Draw by agreement"),
+    // Difficulty levels
+    ("difficulty.novice", "Novice (~1100)"),
+    ("difficulty.beginner", "Beginner (~1350)"),
+    ("difficulty.casual", "Casual (~1500)"),
+    ("difficulty.intermediate", "Intermediate (~1800)"),
+    ("difficulty.advanced", "Advanced (~2100)"),
+    ("difficulty.expert", "Expert (~2500)"),
+    ("difficulty.maximum", "Maximum Strength"),
+    // Engine personalities
+    ("personality.standard", "Standard"),
+    ("personality.aggressive", "Attacker"),
+    ("personality.positional", "Strategist"),
+    ("personality.grinder", "Endgame Grinder"),
+    ("personality.standard_description", "Plays the engine's best move."),
+    ("personality.aggressive_description", "Goes for checks, captures and sharp openings, and avoids draws."),
+    ("personality.positional_description", "Prefers quiet moves, early castling and closed openings."),
+    ("personality.grinder_description", "Trades pieces, marches the king up and grinds out long endgames."),
+    // Themes
+    ("theme.classic", "Classic"),
+    ("theme.dark", "Dark"),
+    // Analysis panel
+    ("analysis.heading", "Analysis"),
+    ("analysis.analyze", "▶ Analyze"),
+    ("analysis.stop", "⏹ Stop"),
+    ("analysis.analyzing", "Analyzing..."),
+    ("analysis.paused", "⏸ Paused"),
+    ("analysis.lines", "Lines:"),
+    ("analysis.eval_bar", "Evaluation bar"),
+    ("analysis.calculating", "/ {0} calculating"),
+    ("analysis.empty", "No analysis yet..."),
+    // Move list
+    ("moves.heading", "Moves"),
+    // Study panel
+    ("study.heading", "Study"),
+    ("study.untitled", "Untitled Study"),
+    ("study.game_name", "Game {0}"),
+    ("study.name", "Name:"),
+    ("study.chapter", "Chapter:"),
+    ("study.chapter_n", "Chapter {0}"),
+    ("study.comments", "Comments:"),
+    ("study.no_comments", "No comments yet..."),
+    ("study.add", "Add"),
+    ("study.variations", "Variations:"),
+    ("study.save", "💾 Save"),
+    ("study.load", "📂 Load"),
+    ("study.new", "🆕 New"),
+    ("study.export_pgn", "📄 Export PGN"),
+    ("study.new_study", "New Study"),
+    ("study.study_name", "Study name:"),
+    ("study.create", "Create"),
+    ("study.cancel", "Cancel"),
+    ("study.load_study", "Load Study"),
+    ("study.none_saved", "No saved studies found."),
+    ("study.close", "Close"),
+    ("study.start", "Start"),
+    ("study.alternatives", "Alternatives:"),
+    // Autoplay
+    ("nav.autoplay", "Play / pause autoplay (pauses on comments)"),
+    ("nav.autoplay_speed", "Speed:"),
+    // Status bar
+    ("status.no_engine", "No engine"),
+    ("status.offline", "Offline"),
+    ("status.idle", "Idle"),
+    ("status.thinking", "Thinking"),
+    ("status.analyzing", "Analyzing"),
+    ("status.difficulty", "Difficulty: {0}"),
+    // Settings
+    ("settings.title", "Settings"),
+    ("settings.notation", "Notation:"),
+    ("notation.san", "Algebraic (SAN)"),
+    ("notation.figurine", "Figurine"),
+    ("notation.long", "Long algebraic"),
+    ("notation.uci", "UCI"),
+    // Toasts
+    ("toast.dismiss", "Dismiss"),
+    ("toast.open_settings", "Open settings"),
+    ("toast.engine_error", "Engine error: {0}"),
+    ("error.engine_not_found", "No engine found at {0}"),
+    ("error.engine_not_found_fix", "Install Stockfish or choose the engine's path in the settings."),
+    ("error.engine_start", "Could not start the engine {0}: {1}"),
+    ("error.engine_start_fix", "Check that the file is a UCI engine built for this computer and that it may be run."),
+    ("error.engine_timeout", "The engine did not answer '{0}' within {1} s and is being restarted"),
+    ("error.engine_timeout_fix", "Lower the engine's threads or hash if the computer is busy, or raise the timeouts in config.toml."),
+    ("error.engine_closed", "The engine quit unexpectedly"),
+    ("error.engine_closed_fix", "The engine log may tell why; a build of the engine for an older processor can help."),
+    ("error.engine_reported", "The engine reported: {0}"),
+    ("error.engine_reported_fix", "Check the files the engine needs, such as its network file, next to the binary."),
+    ("error.engine_fix", "Open the engine log for details."),
+    ("error.study_read", "Could not read study {0}: {1}"),
+    ("error.study_read_fix", "Check that the studies folder in the data directory can be read."),
+    ("error.study_damaged", "Study {0} is damaged: {1}"),
+    ("error.study_damaged_fix", "Restore the study from a backup of your data."),
+    ("error.study_write", "Could not save study {0}: {1}"),
+    ("error.study_write_fix", "Check the free disk space and that the data directory can be written to."),
+    ("error.file_write", "Could not write {0}: {1}"),
+    ("error.file_write_fix", "Pick another folder, or check that files may be written there."),
+    ("error.pgn", "The PGN could not be read: {0}"),
+    ("error.pgn_fix", "Check the moves around the one named; variations have to be legal from where they branch off."),
+    ("error.no_pgn", "The clipboard holds no PGN game"),
+    ("error.no_pgn_fix", "Copy a game in PGN format, then paste again."),
+    ("toast.engine_terminated", "The engine has stopped"),
+    ("toast.engine_move_failed", "Could not play engine move {0}: {1}"),
+    ("toast.pgn_copied", "PGN copied to clipboard"),
+    // Engine setup
+    ("settings.engine_path", "Engine:"),
+    ("settings.apply", "Apply"),
+    ("settings.engine_missing", "No file at this path"),
+    ("onboarding.title", "Set up Stockfish"),
+    ("onboarding.intro", "Stockfish is a free, open-source chess engine. This app uses it to play against you and to analyze positions, but it is not bundled and could not be found on this computer."),
+    ("onboarding.step_download", "1. Download Stockfish"),
+    ("onboarding.download", "⬇ Download for this computer"),
+    ("onboarding.all_downloads", "All downloads"),
+    ("onboarding.step_locate", "2. Extract it and enter the path to the stockfish binary"),
+    ("onboarding.path_hint", "e.g. ~/Downloads/stockfish/stockfish-ubuntu-x86-64-avx2"),
+    ("onboarding.browse", "Browse…"),
+    ("onboarding.use_engine", "Use this engine"),
+    ("onboarding.not_found", "No file found at {0}"),
+    ("onboarding.skip", "Continue without an engine"),
+    // Move entry
+    ("entry.label", "Play:"),
+    ("entry.hint", "e4, Nf3, g1f3…"),
+    // Name plates
+    ("plate.you", "You"),
+    ("plate.white", "White"),
+    ("plate.black", "Black"),
+    // Search progress
+    ("controls.search_depth", "Depth {0}"),
+    // Line pinning
+    ("analysis.pin", "Pin this line"),
+    ("analysis.unpin", "Unpin"),
+    // Eval gap
+    ("analysis.eval_gap", "Δ {0} to 2nd line"),
+    ("analysis.eval_gap_hint", "Difference in pawns between the best and second-best move"),
+    ("analysis.only_move", "⚠ Only move"),
+    ("analysis.only_move_hint", "Every other move loses significantly"),
+    // Scratch board
+    ("scratch.start", "🔬 What if?"),
+    ("scratch.start_hint", "Try moves on a throwaway copy of this position"),
+    ("scratch.keep", "✔ Keep line"),
+    ("scratch.keep_hint", "Add the explored moves up to the current one to the game"),
+    ("scratch.discard", "✖ Discard"),
+    ("scratch.active", "Scratch board: moves are not saved"),
+    // Board orientation
+    ("study.orientation", "Side:"),
+    ("settings.board", "Board:"),
+    ("settings.orient_to_player", "Orient board to my color"),
+    ("settings.orient_to_player_hint", "Flip automatically when the player color or study chapter changes; the flip button still works"),
+    // Analysis refresh
+    ("settings.analysis_refresh", "Analysis refresh:"),
+    ("settings.analysis_refresh_hint", "How often engine lines are redrawn while analyzing; longer intervals use less CPU"),
+    // Time control and low-time warnings
+    ("outcome.white_timeout", "White wins on time!"),
+    ("outcome.black_timeout", "Black wins on time!"),
+    ("controls.time_control", "Time control:"),
+    ("controls.untimed", "Untimed"),
+    ("settings.clock", "Clock:"),
+    ("settings.clock_flash", "Flash clock when low on time"),
+    ("settings.clock_warning_sound", "Beep at 30 and 10 seconds"),
+    ("settings.clock_flag_sound", "Sound when a flag falls"),
+    // Touch mode
+    ("settings.touch", "Touch:"),
+    ("settings.touch_mode", "Touch-friendly layout"),
+    ("settings.touch_mode_hint", "Larger buttons and spacing. Drag pieces with a finger and pinch the board to resize it."),
+    // Board vision trainer
+    ("mode.training", "Training"),
+    ("vision.heading", "Board vision"),
+    ("vision.knight_path", "Knight routes"),
+    ("vision.attacks", "Attacked squares"),
+    ("vision.knight_path_prompt", "Take the knight from {0} to {1} by the shortest route"),
+    ("vision.attacks_prompt", "Mark every square the {0} on {1} attacks"),
+    ("vision.moves_so_far", "Moves so far: {0}"),
+    ("vision.check", "Check"),
+    ("vision.clear", "Clear"),
+    ("vision.reveal", "Show answer"),
+    ("vision.next", "Next ▶"),
+    ("vision.correct", "✔ Correct!"),
+    ("vision.wrong", "✖ Not quite, the answer is circled"),
+    ("vision.streak", "Streak: {0}"),
+    ("vision.stats", "{0}: {1}/{2} correct, {3} s average"),
+    ("vision.pawn", "pawn"),
+    ("vision.knight", "knight"),
+    ("vision.bishop", "bishop"),
+    ("vision.rook", "rook"),
+    ("vision.queen", "queen"),
+    ("vision.king", "king"),
+    ("heatmap.label", "Heatmap:"),
+    ("heatmap.off", "Off"),
+    ("heatmap.occupancy", "Occupied squares"),
+    ("heatmap.attacks", "Attacked squares"),
+    ("heatmap.hint", "Shade squares by how often they were occupied or attacked during the whole game"),
+    ("eval_graph.heading", "Evaluation"),
+    ("eval_graph.tooltip", "{0}. {1} — {2}"),
+    ("move_time.heading", "Time per move"),
+    ("move_time.tooltip", "{0}. {1} — {2}"),
+    ("move_time.long", "Spent much longer than usual"),
+    ("move_time.instant_blunder", "Played instantly into a blunder"),
+    ("settings.export", "PGN export"),
+    ("settings.export_mistakes", "Mark mistakes found by the engine (?, ??)"),
+    ("settings.export_figurine", "Figurine notation (♘f3)"),
+    ("settings.export_glyphs", "Annotation symbols (?!) instead of $ codes"),
+    ("settings.export_hint", "Leave both off for standard PGN that any program can read"),
+    ("result.won", "You won!"),
+    ("result.lost", "You lost"),
+    ("result.draw", "Draw"),
+    ("result.average_loss", "Average centipawn loss: {0}"),
+    ("result.rematch", "Rematch"),
+    ("result.analyze", "Analyze"),
+    ("result.export", "Copy PGN"),
+    ("result.close", "Close"),
+    ("toast.pgn_empty", "No games found"),
+    ("toast.open_failed", "Could not open {0}: {1}"),
+    ("import.title", "Import PGN"),
+    ("import.summary", "{0} contains {1} game(s)."),
+    ("import.game", "Open in Analysis"),
+    ("import.first_game", "Open the first game in Analysis"),
+    ("import.study", "Add to the current study as chapters"),
+    ("import.cancel", "Cancel"),
+    ("import.drop_hint", "Drop a PGN file (also .pgn.gz or .zip) or a FEN file to open it"),
+    ("toast.not_pgn_or_fen", "{0} is not a PGN file or a FEN position"),
+    ("toast.import_skipped", "{0} game(s) could not be imported"),
+    ("toast.importing", "Importing games from {0}…"),
+    ("toast.import_busy", "Wait for the current import to finish"),
+    ("toast.keep_line_locked", "The study is locked; make a personal copy to keep this line"),
+    ("toast.line_added_as_chapter", "The line was added as a variation of the game in chapter {0}"),
+    ("toast.config_invalid", "Ignoring {0}: {1}"),
+    ("toast.config_save_failed", "Could not save config.toml: {0}"),
+    ("toast.orphans_killed", "Stopped {0} engine process(es) left running by an earlier session"),
+    ("explorer.heading", "Opening explorer"),
+    ("explorer.masters", "Masters"),
+    ("explorer.lichess", "Lichess"),
+    ("explorer.loading", "Loading…"),
+    ("explorer.retry", "Retry"),
+    ("explorer.no_games", "No games reached this position"),
+    ("explorer.score", "White {0}% · Draw {1}% · Black {2}%"),
+    ("explorer.total", "{0} games"),
+    ("explorer.unauthorized", "Lichess requires a login for the explorer: add a token under [lichess] in config.toml"),
+    ("import.my_games", "Add to my games"),
+    ("import.my_games_hint", "Keep these games for the personal opening tree in the explorer"),
+    ("explorer.personal", "My games"),
+    ("explorer.personal_empty", "Drop a PGN of your games and choose \"Add to my games\", then enter your usernames in Settings"),
+    ("explorer.personal_total", "{0} of your games ({1} skipped)"),
+    ("explorer.personal_score", "+{0} ={1} -{2} · {3}%"),
+    ("explorer.faced", "Played by your opponents"),
+    ("explorer.deviation", "Leaves your repertoire, which plays {0}"),
+    ("settings.usernames", "Your usernames"),
+    ("settings.usernames_hint", "Comma-separated names you play under, used to find your side in imported games"),
+    ("toast.my_games_added", "Added {0} new games, skipped {1} already imported; opening tree now built from {2} of your games"),
+    ("toast.my_games_none_new", "All {0} games were already imported"),
+    ("puzzle.daily", "Daily puzzle"),
+    ("puzzle.source_lichess", "Lichess (offline set as fallback)"),
+    ("puzzle.source_bundled", "Offline set"),
+    ("puzzle.source_off", "Off"),
+    ("puzzle.fetch_failed", "Could not reach Lichess"),
+    ("puzzle.white_to_move", "White to move"),
+    ("puzzle.black_to_move", "Black to move"),
+    ("puzzle.rating", "Rating {0}"),
+    ("puzzle.offline", "Offline puzzle, Lichess is unreachable"),
+    ("puzzle.loading", "Fetching today's puzzle…"),
+    ("puzzle.streak", "Streak: {0} days"),
+    ("puzzle.streak_best", "Streak: {0} days (best {1})"),
+    ("puzzle.solve", "Solve"),
+    ("puzzle.later", "Later"),
+    ("puzzle.solved", "Solved!"),
+    ("puzzle.solved_revealed", "Solved with help, the streak is unchanged"),
+    ("puzzle.find_best", "Find the best move"),
+    ("puzzle.mistakes", "Wrong tries: {0}"),
+    ("puzzle.open_lichess", "Open on Lichess"),
+    ("puzzle.show_move", "Show move"),
+    ("puzzle.show_move_hint", "Plays the next move of the solution; today's puzzle then no longer counts for the streak"),
+    ("puzzle.close", "Close"),
+    ("puzzle.wrong", "Not the move, try again"),
+    ("settings.daily_puzzle", "Daily puzzle"),
+    ("settings.daily_puzzle_hint", "Offered on startup until solved; changes apply on the next start"),
+    ("broadcast.heading", "Follow broadcast"),
+    ("broadcast.url_hint", "PGN URL or file path"),
+    ("broadcast.interval", "Check every"),
+    ("broadcast.live_analysis", "Analyze new moves with the engine"),
+    ("broadcast.follow", "Follow"),
+    ("broadcast.stop", "Stop following"),
+    ("broadcast.game", "Game {0}"),
+    ("broadcast.error", "Update failed: {0}"),
+    ("broadcast.updated", "Updated {0} s ago"),
+    ("broadcast.waiting", "Waiting for the first update…"),
+    ("mode.online", "Online"),
+    ("online.player_engine", "Engine"),
+    ("online.player_me", "Me"),
+    ("online.player", "Moves by"),
+    ("online.unauthorized", "Lichess rejected the token; it needs the board:play or bot:play scope"),
+    ("online.no_token", "Add a Lichess API token with the board:play (or bot:play) scope to config.toml under [lichess] token to play online"),
+    ("online.bot_account", "bot"),
+    ("online.board_account", "player"),
+    ("online.connected", "Connected as {0} ({1})"),
+    ("online.connecting", "Connecting to Lichess…"),
+    ("online.reconnect", "Reconnect"),
+    ("online.disconnected", "Connection lost: {0}. Retrying…"),
+    ("online.stream_closed", "Lichess closed the connection"),
+    ("online.move_rejected", "Lichess refused the move: {0}"),
+    ("online.playing", "Playing {0}"),
+    ("online.your_turn", "Your move"),
+    ("online.their_turn", "Waiting for the opponent"),
+    ("online.resign", "Resign"),
+    ("online.finished", "Last game: {0}"),
+    ("online.seek", "Seek"),
+    ("online.seek_hint", "Lichess pairs seeks from the board only for rapid and classical games (8 minutes or more)"),
+    ("online.seeking", "Looking for an opponent…"),
+    ("online.challenges", "Challenges"),
+    ("online.no_challenges", "No open challenges"),
+    ("online.rated", "rated"),
+    ("online.casual", "casual"),
+    ("online.accept", "Accept"),
+    ("online.decline", "Decline"),
+    ("online.variant_unsupported", "Only standard chess can be played here"),
+    ("online.challenged", "{0} challenges you"),
+    ("online.game_over", "The online game is over"),
+    ("online.error", "Lichess: {0}"),
+    ("settings.dgt", "Electronic board"),
+    ("settings.dgt_no_port", "Choose port"),
+    ("settings.dgt_connect", "Connect"),
+    ("settings.dgt_disconnect", "Disconnect"),
+    ("settings.dgt_connected", "Board connected on {0}"),
+    ("settings.dgt_waiting", "Waiting for the board…"),
+    ("settings.dgt_rotated", "Black sits at the cable end"),
+    ("settings.announce_moves", "Read out moves to make on the board"),
+    ("settings.announce_moves_hint", "Uses the system's speech synthesizer (say, spd-say or espeak)"),
+    ("dgt.make_move", "Make {0} on the board"),
+    ("dgt.out_of_sync", "The board doesn't match the position on screen"),
+    ("speech.king", "king"),
+    ("speech.queen", "queen"),
+    ("speech.rook", "rook"),
+    ("speech.bishop", "bishop"),
+    ("speech.knight", "knight"),
+    ("speech.takes", "takes"),
+    ("speech.promotes", "promotes"),
+    ("speech.check", "check"),
+    ("speech.mate", "checkmate"),
+    ("speech.castle_short", "castles kingside"),
+    ("speech.castle_long", "castles queenside"),
+    ("report.score", "Game"),
+    ("report.evaluation", "Evaluation"),
+    ("report.accuracy", "Accuracy"),
+    ("report.average_loss", "Average loss (cp)"),
+    ("report.inaccuracies", "Inaccuracies"),
+    ("report.mistakes", "Mistakes"),
+    ("report.blunders", "Blunders"),
+    ("report.critical", "Critical moments"),
+    ("report.no_critical", "No mistakes or blunders were found."),
+    ("report.loss", "Lost {0} pawns"),
+    ("controls.export_report", "Save report…"),
+    ("toast.report_saved", "Report saved to {0}"),
+    ("toast.report_failed", "Could not save the report: {0}"),
+    ("settings.backup", "Backup"),
+    ("settings.backup_create", "Back up…"),
+    ("settings.backup_restore", "Restore…"),
+    ("settings.backup_hint", "Saves studies, imported games, the config file and settings in one ZIP file"),
+    ("toast.backup_saved", "Backup saved to {0}"),
+    ("toast.backup_failed", "Could not save the backup: {0}"),
+    ("toast.restore_failed", "Could not restore the backup: {0}"),
+    ("toast.backup_restored", "Backup restored"),
+    ("analysis.open_lichess", "Open in Lichess"),
+    ("analysis.open_lichess_hint", "Opens the game on the Lichess analysis board in your browser, for cloud analysis"),
+    ("engine_log.title", "Engine log"),
+    ("engine_log.copy", "Copy"),
+    ("engine_log.clear", "Clear"),
+    ("engine_log.empty", "The engine hasn't reported any errors or stderr output."),
+    ("settings.engine_log", "Log"),
+    ("toast.show_engine_log", "Show log"),
+    ("settings.power", "Power saving"),
+    ("settings.power_pause_unfocused", "Pause analysis in the background"),
+    ("settings.power_pause_on_battery", "Pause analysis on battery"),
+    ("settings.power_cpu_limit", "CPU limit"),
+    ("settings.power_cpu_limit_hint", "Share of the CPU cores the engine uses while the window is in the background or on battery"),
+    ("analysis.power_paused", "Paused to save power, resumes when the window has focus or the computer is charging"),
+    ("settings.study_format", "Study files"),
+    ("settings.study_format_hint", "Compact files are much smaller and faster for large repertoires; studies in either format can always be opened"),
+    ("study.format_json", "JSON (readable)"),
+    ("study.format_compact", "Compact (binary)"),
+    ("controls.review_game", "🔍 Review game"),
+    ("controls.review_game_hint", "Let the engine evaluate every move that has no evaluation yet"),
+    ("controls.review_progress", "Reviewing {0}/{1}"),
+    ("controls.review_cancel", "Cancel"),
+    ("toast.review_done", "Game review finished"),
+    ("settings.engine_hash", "Engine hash"),
+    ("settings.keep_hash", "Keep between positions of the same game"),
+    ("settings.keep_hash_hint", "The engine reuses what it found for nearby positions, so stepping through a game evaluates faster; other positions still start fresh"),
+    ("analysis.clear_hash", "Clear hash"),
+    ("analysis.clear_hash_hint", "Forget earlier searches and evaluate this position from scratch"),
+    ("games.heading", "My games"),
+    ("games.empty", "No games imported yet"),
+    ("games.untitled", "Untitled game"),
+    ("games.open_hint", "Open in Analysis"),
+    ("games.more", "{0} more games, narrow the filter to see them"),
+    ("export.heading", "Export games"),
+    ("export.all_games", "📄 Export all games"),
+    ("export.include_my_games", "Include my imported games"),
+    ("export.include_my_games_hint", "Add the imported games whose player, event, date or other tag contains the filter"),
+    ("export.filter_hint", "Filter, e.g. a player or 2024"),
+    ("toast.no_games_to_export", "No games to export yet"),
+    ("toast.games_exported", "Exported {0} games to {1}"),
+    ("toast.games_export_failed", "Could not export the games: {0}"),
+    ("board.move_slider_hint", "Drag to scrub through the game move by move"),
+    ("analysis.stable", "stable"),
+    ("analysis.settling", "settling"),
+    ("analysis.swinging", "swinging"),
+    ("analysis.stability_hint", "How much the evaluation changed over the last depths"),
+    ("critical.heading", "Critical moments"),
+    ("critical.missed_win", "missed win"),
+    ("critical.swing", "turning point"),
+    ("critical.jump_hint", "Go to the position before this move"),
+    ("study.snippet_idea", "with the idea of…"),
+    ("study.snippet_only_move", "the only move"),
+    ("study.snippet_better_was", "better was…"),
+    ("study.snippet_threatening", "threatening…"),
+    ("study.snippet_plan", "the plan is…"),
+    ("glyph.good", "Good move"),
+    ("glyph.mistake", "Mistake"),
+    ("glyph.brilliant", "Brilliant move"),
+    ("glyph.blunder", "Blunder"),
+    ("glyph.interesting", "Interesting move"),
+    ("glyph.dubious", "Dubious move"),
+    ("glyph.equal", "Equal position"),
+    ("glyph.unclear", "Unclear position"),
+    ("glyph.white_slightly", "White is slightly better"),
+    ("glyph.black_slightly", "Black is slightly better"),
+    ("glyph.white_better", "White is better"),
+    ("glyph.black_better", "Black is better"),
+    ("glyph.white_winning", "White is winning"),
+    ("glyph.black_winning", "Black is winning"),
+    ("glyph.only_move", "Only move"),
+    ("glyph.zugzwang", "Zugzwang"),
+    ("glyph.development", "Development advantage"),
+    ("glyph.initiative", "Initiative"),
+    ("glyph.attack", "Attack"),
+    ("glyph.compensation", "Compensation for the material"),
+    ("glyph.counterplay", "Counterplay"),
+    ("glyph.time_trouble", "Time trouble"),
+    ("glyph.with_idea", "With the idea"),
+    ("glyph.novelty", "Novelty"),
+    ("moves.book_exit", "First move out of opening theory"),
+    ("quality.brilliant", "Brilliant: sacrifices that kept the evaluation"),
+    ("quality.good", "Good moves"),
+    ("quality.inaccuracies", "Inaccuracies"),
+    ("quality.mistakes", "Mistakes"),
+    ("quality.blunders", "Blunders"),
+    ("endgame.heading", "Endgame practice"),
+    ("endgame.play_hint", "Play this position against the engine at full strength"),
+    ("endgame.kq_k", "Queen against king"),
+    ("endgame.kr_k", "Rook against king"),
+    ("endgame.kp_k", "King and pawn against king"),
+    ("endgame.lucena", "Lucena position"),
+    ("endgame.philidor", "Philidor defence"),
+    ("endgame.krb_kr", "Rook and bishop against rook (defend)"),
+    ("endgame.kbn_k", "Bishop and knight mate"),
+    ("endgame.goal", "Tablebase: {0}"),
+    ("endgame.goal_win", "win it"),
+    ("endgame.goal_draw", "hold the draw"),
+    ("endgame.goal_loss", "lost with best play, make it hard"),
+    ("endgame.looking_up", "Looking up the tablebase..."),
+    ("endgame.fetch_failed", "Tablebase unreachable, the result can't be checked"),
+    ("endgame.full_strength", "The engine plays at full strength"),
+    ("toast.endgame_held", "{0}: you achieved the tablebase result"),
+    ("toast.endgame_missed", "{0}: the result falls short of the tablebase, try again"),
+    ("tournament.heading", "Engine tournament"),
+    ("tournament.levels", "Difficulty levels:"),
+    ("tournament.second_engine_hint", "Second engine path (optional)"),
+    ("tournament.double_round", "Play each pairing with both colors"),
+    ("tournament.movetime", "Time per move:"),
+    ("tournament.start", "▶ Start tournament"),
+    ("tournament.stop", "⏹ Stop"),
+    ("tournament.need_two", "Choose at least two players"),
+    ("tournament.export", "📄 Export games"),
+    ("tournament.progress", "Game {0} / {1}"),
+    ("tournament.failed", "Tournament stopped: {0}"),
+    ("tournament.score", "Score"),
+    ("tournament.elo", "Elo"),
+    ("tournament.elo_hint", "Estimated rating difference to the average opponent, from the score"),
+    ("tournament.engine_gone", "An engine quit during the tournament"),
+    ("tournament.no_engine", "No engine found to play the tournament with"),
+    ("toast.tournament_finished", "Tournament finished after {0} games"),
+    ("plan.heading", "Training plan"),
+    ("plan.puzzles", "Puzzles"),
+    ("plan.puzzles_hint", "Puzzles solved without revealing the solution"),
+    ("plan.lines", "Repertoire lines"),
+    ("plan.lines_hint", "Study lines stepped through to their last move"),
+    ("plan.reviews", "Game reviews"),
+    ("plan.reviews_hint", "Engine reviews of a game run to the end"),
+    ("plan.done_today", "✔ Today's goals are met"),
+    ("plan.streak", "Streak: {0} days (best {1})"),
+    ("toast.plan_met", "Today's training goals are met"),
+    ("speech.pawn", "pawn"),
+    ("coach.hanging", "Your {0} on {1} is attacked and undefended"),
+    ("coach.attacked_by_lesser", "Your {0} on {1} is attacked by a {2}"),
+    ("coach.castle", "The engine wants to castle"),
+    ("coach.promote", "The engine wants to promote on {0}"),
+    ("coach.capture", "The engine wants to take the {0} on {1}"),
+    ("coach.check", "The engine wants to give check with the {0}"),
+    ("coach.open_file", "The engine wants to open the {0}-file"),
+    ("coach.piece_move", "The engine wants to bring the {0} to {1}"),
+    ("coach.pawn_push", "The engine wants to push the pawn to {0}"),
+    ("settings.coach", "Coach:"),
+    ("settings.coach_hints", "Explain the evaluation"),
+    ("settings.coach_hints_hint", "Show hints in words under the evaluation bar, such as pieces in danger and what the engine's best move is about"),
+    ("settings.promotion", "Promotion:"),
+    ("settings.auto_queen", "Always promote to a queen"),
+    ("settings.auto_queen_hint", "Skip the promotion picker. Hold N, R or B while dropping the pawn to under-promote"),
+    ("premove.heading", "Premoves"),
+    ("premove.heading_hint", "Moves played for you as soon as the engine replies. Pick one on the board while the engine thinks, or type a line of its expected replies and your answers."),
+    ("premove.line_hint", "e.g. Nxe4 Nxe4 Bxe4 Qxe4"),
+    ("premove.set", "Queue"),
+    ("premove.empty", "Nothing queued"),
+    ("premove.conditional", "if {0} → {1}"),
+    ("premove.any_reply", "after any reply → {0}"),
+    ("premove.clear", "Clear premoves"),
+    ("toast.premove_illegal", "Premove no longer legal, queue cleared"),
+    ("toast.premove_cancelled", "The engine played something else, premoves cleared"),
+    ("toast.premove_invalid", "Can't queue that line: {0}"),
+    ("settings.variety", "Opening variety"),
+    ("settings.variety_enabled", "Vary the engine's first moves"),
+    ("settings.variety_hint", "The engine picks its first moves at random among the lines that score nearly as well as its best one, so games at the same level don't all start alike"),
+    ("settings.variety_moves", "Moves"),
+    ("settings.variety_margin", "Within"),
+    ("settings.variety_margin_hint", "How much worse than the best line, in centipawns, a move may be and still be picked"),
+    ("controls.undo_left", "↩ Undo Move ({0} left)"),
+    ("controls.takebacks", "Takebacks"),
+    ("controls.takebacks_locked", "Takebacks can be changed before the first move or after the game"),
+    ("takeback.unlimited", "Unlimited"),
+    ("takeback.per_game", "{0} per game"),
+    ("takeback.off", "None"),
+    ("resign.confirm", "Resign this game?"),
+    ("resign.lost", "Your position looks lost"),
+    ("resign.lost_detail", "The engine has rated it {0} for several moves. Strong players resign here."),
+    ("resign.resign", "Resign"),
+    ("resign.play_on", "Play on"),
+    ("settings.auto_resign", "Auto-resign"),
+    ("settings.auto_resign_enabled", "Offer to resign lost games"),
+    ("settings.auto_resign_hint", "Asks whether to resign once the engine's evaluation has stayed below the threshold for several of its moves, as practice in knowing when a game is lost"),
+    ("settings.auto_resign_threshold", "Behind by"),
+    ("settings.auto_resign_moves", "For moves"),
+    ("settings.engine_roles", "Engines per mode"),
+    ("settings.engine_roles_hint", "Play Game mode, analyze and review games with different engines, and cap the threads each may use. Each engine keeps running while another mode is in use. Empty paths use the engine above, or for reviews the engine in use."),
+    ("settings.engine_role_game", "Game"),
+    ("settings.engine_role_analysis", "Analysis"),
+    ("settings.engine_role_default", "Engine above"),
+    ("settings.engine_role_review", "Game review"),
+    ("settings.engine_role_review_hint", "Loaded engine"),
+    ("settings.engine_threads_any", "All threads"),
+    ("settings.engine_threads", "Up to {0} threads"),
+    ("settings.uci_console", "UCI console"),
+    ("settings.uci_console_hint", "Type raw UCI commands to the running engine"),
+    ("console.title", "UCI Console"),
+    ("console.hint", "↑/↓ recall earlier commands"),
+    ("console.input_hint", "UCI command, e.g. d or eval"),
+    ("console.warn_search", "Starting or stopping a search here can confuse games and analysis in progress"),
+    ("console.warn_position", "Changes the engine's position behind the app's back until its next search"),
+    ("console.warn_handshake", "The app already did the handshake; the answer may be mistaken for other output"),
+    ("console.warn_quit", "Shuts the engine down; restart it from the settings"),
+    ("console.warn_option", "The app sets this option itself and won't know it changed"),
+    ("analysis.exclude", "Exclude this move and search again, to see the next best plan"),
+    ("analysis.excluded", "Excluded:"),
+    ("analysis.restore_move", "Search this move again"),
+    ("settings.check_moves", "Check my moves before playing them"),
+    ("settings.check_moves_hint", "In Game mode, a move made on the board waits while the engine works out how much it loses compared with the best move"),
+    ("staged.move", "Your move: {}"),
+    ("staged.checking", "Checking…"),
+    ("staged.no_loss", "As good as the engine's choice"),
+    ("staged.cost", "Loses {} pawns to the best move"),
+    ("staged.play", "Play it"),
+    ("staged.cancel", "Pick another"),
+    ("phase.opening", "Opening"),
+    ("phase.middlegame", "Middlegame"),
+    ("phase.endgame", "Endgame"),
+    ("report.by_phase", "Average loss by phase (cp)"),
+    ("quality.by_phase", "Average loss by phase, in centipawns"),
+    ("coverage.open", "Repertoire coverage…"),
+    ("coverage.open_hint", "Compare your games with the repertoire in your studies"),
+    ("coverage.title", "Repertoire coverage"),
+    ("coverage.empty", "Your games stay within your repertoire. Studies played from White or Black count as that side's repertoire."),
+    ("coverage.suggestions", "Learn next"),
+    ("coverage.misplayed", "Lines you misplayed"),
+    ("coverage.uncovered", "Replies your repertoire doesn't cover"),
+    ("coverage.none", "None"),
+    ("coverage.open_line", "Show on the board"),
+    ("coverage.games", "{0} games, {1} lost"),
+    ("coverage.prepared", "prepared: {0}"),
+    ("compare.open", "⚖ Compare"),
+    ("compare.open_hint", "Show the position next to the end of the engine's line or a pinned position"),
+    ("compare.title", "Compare positions"),
+    ("compare.current", "Board"),
+    ("compare.engine_line", "After the engine's line"),
+    ("compare.pinned", "Pinned position"),
+    ("compare.pin", "📌 Pin this position"),
+    ("compare.pin_hint", "Compare later positions against this one"),
+    ("compare.unpin", "Unpin"),
+    ("compare.pawns_only", "Pawns only"),
+    ("compare.pawns_only_hint", "Outline only squares where the pawn structure differs"),
+    ("compare.no_target", "Start the analysis or pin a position to compare with."),
+    ("compare.differences", "{0} squares differ"),
+    ("share.copy_link", "Copy position link"),
+    ("share.copy_link_hint", "Copy a Lichess analysis board link to the position on the board"),
+    ("share.copy_pgn", "Copy annotated PGN"),
+    ("share.copy_pgn_hint", "Copy the game as PGN, with the engine's current lines as a comment at the position on the board"),
+    ("toast.link_copied", "Link copied to the clipboard"),
+    ("print.after", "After {0}"),
+    ("study.print", "Print chapter…"),
+    ("study.print_hint", "Save the chapter as a page ready to print or save as PDF from the browser, with diagrams, variations and comments"),
+    ("study.print_every", "Diagram every"),
+    ("study.print_moves", "moves"),
+    ("share.latex_diagram", "Copy LaTeX diagram"),
+    ("share.latex_diagram_hint", "Copy the position as a chessboard package snippet for LaTeX documents"),
+    ("share.latex_line", "Copy LaTeX line"),
+    ("share.latex_line_hint", "Copy the moves up to the position on the board as an xskak snippet, followed by a diagram"),
+    ("toast.latex_copied", "LaTeX copied to the clipboard"),
+    ("import.summary_archive", "{0} is an archive; its games are read as they are imported."),
+    ("toast.duplicate_line", "This line is already in chapter \"{0}\""),
+    ("toast.transposed_line", "Chapter \"{0}\" already reaches this position by another move order"),
+    ("toast.go_to_duplicate", "Go there instead"),
+    ("study.drill", "Drill this chapter"),
+    ("study.drill_hint", "Play the chapter's lines from its side; the opponent's replies are picked at random"),
+    ("study.drill_stop", "Stop drill"),
+    ("study.weak_spots", "Weak spots"),
+    ("study.weak_spots_none", "Positions you miss in drills show up here"),
+    ("study.weak_spot_recall", "{0}/{1} recalled"),
+    ("drill.wrong", "Not your repertoire move, try again ({0} tries left)"),
+    ("drill.finished", "End of the line"),
+    ("drill.nothing", "This chapter has no moves for its side to drill"),
+    ("drill.time_out", "Out of time for this move"),
+    ("drill.failed", "Too many mistakes, starting another line"),
+    ("drill.hint", "Repertoire move: {0}"),
+    ("drill.summary_title", "Drill results"),
+    ("drill.summary_score", "Score: {0}%"),
+    ("drill.summary_passed", "Lines completed"),
+    ("drill.summary_failed", "Lines failed"),
+    ("drill.summary_first_try", "Moves found at the first try"),
+    ("drill.summary_positions", "Moves asked"),
+    ("drill.summary_mistakes", "Mistakes"),
+    ("drill.summary_timeouts", "Out of time"),
+    ("drill.summary_hints", "Hints used"),
+    ("study.drill_mistakes", "Mistakes in this line: {0}/{1}"),
+    ("study.drill_time", "{0} s left"),
+    ("study.drill_hint_button", "Hint"),
+    ("settings.drill", "Repertoire drills"),
+    ("settings.drill_move_seconds", "Time per move"),
+    ("settings.drill_move_seconds_hint", "0 for no time limit"),
+    ("settings.drill_mistakes", "Mistakes allowed per line"),
+    ("settings.drill_hints", "Allow hints"),
+    ("audit.move_loses", "Engine: {0} cp worse than its best move"),
+    ("audit.bad_ending", "Engine: the line ends at {0} for your side"),
+    ("audit.done", "Engine audit done, {0} warning(s)"),
+    ("audit.no_engine", "The engine isn't ready to audit the chapter"),
+    ("audit.busy", "Wait for the engine to finish its current review"),
+    ("audit.progress", "Checking the chapter with the engine… {0}/{1}"),
+    ("audit.cancel", "Cancel"),
+    ("audit.cancelled", "Engine audit cancelled"),
+    ("audit.stale", "The study changed while the engine was checking it, so the results were dropped"),
+    ("annotate.progress", "Annotating: {0}/{1} positions"),
+    ("annotate.done", "Annotation finished: {0} mistakes marked"),
+    ("annotate.cancelled", "Annotation cancelled"),
+    ("annotate.nothing", "The chapter has no moves to annotate"),
+    ("annotate.better", "Better was {0}"),
+    ("study.audit", "Check with engine"),
+    ("study.audit_hint", "Flag moves of your side losing more than the threshold against the engine's best, and lines ending in bad positions"),
+    ("study.audit_threshold", "Threshold"),
+    ("study.lock_hint", "Lock the study: it can still be browsed and trained, but not edited"),
+    ("study.locked", "Read-only study"),
+    ("study.locked_title", "Locked study"),
+    ("study.locked_prompt", "This study is locked. Make a personal copy to edit it?"),
+    ("study.make_copy", "Make a copy"),
+    ("study.copy_name", "{0} (copy)"),
+    ("study.paste_pgn", "📋 Paste PGN as chapter"),
+    ("study.paste_pgn_hint", "Add the game copied to the clipboard, with its variations and comments, as a new chapter"),
+    ("study.headers", "PGN headers"),
+    ("study.header_name", "Tag"),
+    ("study.header_value", "Value"),
+    ("study.header_remove", "Remove this tag"),
+    ("study.annotate", "Annotate game"),
+    ("study.annotate_hint", "Have the engine evaluate every move of the main line, marking mistakes with a glyph and the better line"),
+    ("settings.clicks", "Clicks"),
+    ("settings.own_piece", "Clicking another of my pieces:"),
+    ("settings.own_piece_switch", "Selects it"),
+    ("settings.own_piece_move", "Tries the move"),
+    ("settings.deselect_selected", "Clicking the selected piece deselects it"),
+    ("settings.confirm_destination", "Confirm moves with a second click"),
+    ("settings.confirm_destination_hint", "The first click on a destination marks it, the second makes the move"),
+    ("settings.engine_moves", "Engine moves"),
+    ("settings.engine_move_highlight", "Highlight the engine's moves in their own color"),
+    ("settings.engine_move_sound", "Play a sound when the engine moves"),
+];
@@ -0,0 +1,726 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    // Formatting
+    ("format.move_number", "{0}."),
+    // Mode and navigation
+    ("mode.label", "Modo:"),
+    ("mode.game", "Jugar contra el motor"),
+    ("mode.analysis", "Análisis"),
+    ("mode.study", "Estudio"),
+    ("nav.label", "Navegación:"),
+    ("nav.start", "Ir al inicio"),
+    ("nav.previous", "Jugada anterior"),
+    ("nav.next", "Jugada siguiente"),
+    ("nav.end", "Ir al final"),
+    ("nav.move_counter", "Jugada: {0} / {1}"),
+    // Game controls
+    ("controls.engine_thinking", "El motor está pensando..."),
+    ("controls.new_game", "Nueva partida"),
+    ("controls.flip_board", "Girar tablero"),
+    ("controls.play_as", "Jugar con:"),
+    ("controls.white", "Blancas"),
+    ("controls.black", "Negras"),
+    ("controls.difficulty", "Dificultad:"),
+    ("controls.personality", "Personalidad:"),
+    ("controls.theme", "Tema:"),
+    ("controls.language", "Idioma:"),
+    ("controls.resign", "🏳 Abandonar"),
+    ("controls.offer_draw", "🤝 Ofrecer tablas"),
+    ("controls.undo", "↩ Deshacer jugada"),
+    ("controls.export_pgn", "📄 Exportar PGN"),
+    ("controls.save_to_study", "📚 Guardar en estudio"),
+    // Outcomes
+    ("outcome.white_checkmate", "¡Ganan las blancas por jaque mate!"),
+    ("outcome.black_checkmate", "¡Ganan las negras por jaque mate!"),
+    ("outcome.stalemate", "Tablas por ahogado"),
+    ("outcome.insufficient_material", "Tablas por material insuficiente"),
+    ("outcome.threefold", "Tablas por triple repetición"),
+    ("outcome.fifty_move", "Tablas por la regla de los cincuenta movimientos"),
+    ("outcome.white_resignation", "¡Ganan las blancas por abandono!"),
+    ("outcome.black_resignation", "¡Ganan las negras por abandono!"),
+    ("outcome.agreement", "Tablas por acuerdo"),
+    // Difficulty levels
+    ("difficulty.novice", "Novato (~1100)"),
+    ("difficulty.beginner", "Principiante (~1350)"),
+    ("difficulty.casual", "Aficionado (~1500)"),
+    ("difficulty.intermediate", "Intermedio (~1800)"),
+    ("difficulty.advanced", "Avanzado (~2100)"),
+    ("difficulty.expert", "Experto (~2500)"),
+    ("difficulty.maximum", "Fuerza máxima"),
+    // Engine personalities
+    ("personality.standard", "Estándar"),
+    ("personality.aggressive", "Atacante"),
+    ("personality.positional", "Estratega"),
+    ("personality.grinder", "Especialista en finales"),
+    ("personality.standard_description", "Juega la mejor jugada del motor."),
+    ("personality.aggressive_description", "Busca jaques, capturas y aperturas agudas, y evita las tablas."),
+    ("personality.positional_description", "Prefiere jugadas tranquilas, enroque temprano y aperturas cerradas."),
+    ("personality.grinder_description", "Cambia piezas, activa el rey y exprime los finales largos."),
+    // Themes
+    ("theme.classic", "Clásico"),
+    ("theme.dark", "Oscuro"),
+    // Analysis panel
+    ("analysis.heading", "Análisis"),
+    ("analysis.analyze", "▶ Analizar"),
+    ("analysis.stop", "⏹ Detener"),
+    ("analysis.analyzing", "Analizando..."),
+    ("analysis.paused", "⏸ En pausa"),
+    ("analysis.lines", "Líneas:"),
+    ("analysis.eval_bar", "Barra de evaluación"),
+    ("analysis.calculating", "/ {0} calculando"),
+    ("analysis.empty", "Todavía no hay análisis..."),
+    // Move list
+    ("moves.heading", "Jugadas"),
+    // Study panel
+    ("study.heading", "Estudio"),
+    ("study.untitled", "Estudio sin título"),
+    ("study.game_name", "Partida {0}"),
+    ("study.name", "Nombre:"),
+    ("study.chapter", "Capítulo:"),
+    ("study.chapter_n", "Capítulo {0}"),
+    ("study.comments", "Comentarios:"),
+    ("study.no_comments", "Todavía no hay comentarios..."),
+    ("study.add", "Añadir"),
+    ("study.variations", "Variantes:"),
+    ("study.save", "💾 Guardar"),
+    ("study.load", "📂 Abrir"),
+    ("study.new", "🆕 Nuevo"),
+    ("study.export_pgn", "📄 Exportar PGN"),
+    ("study.new_study", "Nuevo estudio"),
+    ("study.study_name", "Nombre del estudio:"),
+    ("study.create", "Crear"),
+    ("study.cancel", "Cancelar"),
+    ("study.load_study", "Abrir estudio"),
+    ("study.none_saved", "No hay estudios guardados."),
+    ("study.close", "Cerrar"),
+    ("study.start", "Inicio"),
+    ("study.alternatives", "Alternativas:"),
+    // Autoplay
+    ("nav.autoplay", "Reproducir / pausar (se detiene en comentarios)"),
+    ("nav.autoplay_speed", "Velocidad:"),
+    // Status bar
+    ("status.no_engine", "Sin motor"),
+    ("status.offline", "Desconectado"),
+    ("status.idle", "Inactivo"),
+    ("status.thinking", "Pensando"),
+    ("status.analyzing", "Analizando"),
+    ("status.difficulty", "Dificultad: {0}"),
+    // Settings
+    ("settings.title", "Ajustes"),
+    ("settings.notation", "Notación:"),
+    ("notation.san", "Algebraica (SAN)"),
+    ("notation.figurine", "Figurines"),
+    ("notation.long", "Algebraica larga"),
+    ("notation.uci", "UCI"),
+    // Toasts
+    ("toast.dismiss", "Cerrar"),
+    ("toast.open_settings", "Abrir ajustes"),
+    ("toast.engine_error", "Error del motor: {0}"),
+    ("error.engine_not_found", "No se encontró ningún motor en {0}"),
+    ("error.engine_not_found_fix", "Instala Stockfish o elige la ruta del motor en los ajustes."),
+    ("error.engine_start", "No se pudo iniciar el motor {0}: {1}"),
+    ("error.engine_start_fix", "Comprueba que el archivo sea un motor UCI compilado para este ordenador y que pueda ejecutarse."),
+    ("error.engine_timeout", "El motor no respondió a '{0}' en {1} s y se está reiniciando"),
+    ("error.engine_timeout_fix", "Reduce los hilos o el hash del motor si el ordenador está ocupado, o aumenta los tiempos límite en config.toml."),
+    ("error.engine_closed", "El motor se cerró inesperadamente"),
+    ("error.engine_closed_fix", "El registro del motor puede indicar la causa; una versión del motor para procesadores antiguos puede ayudar."),
+    ("error.engine_reported", "El motor informó: {0}"),
+    ("error.engine_reported_fix", "Comprueba los archivos que necesita el motor, como su archivo de red, junto al ejecutable."),
+    ("error.engine_fix", "Abre el registro del motor para ver los detalles."),
+    ("error.study_read", "No se pudo leer el estudio {0}: {1}"),
+    ("error.study_read_fix", "Comprueba que la carpeta de estudios del directorio de datos se pueda leer."),
+    ("error.study_damaged", "El estudio {0} está dañado: {1}"),
+    ("error.study_damaged_fix", "Restaura el estudio desde una copia de seguridad de tus datos."),
+    ("error.study_write", "No se pudo guardar el estudio {0}: {1}"),
+    ("error.study_write_fix", "Comprueba el espacio libre en disco y que se pueda escribir en el directorio de datos."),
+    ("error.file_write", "No se pudo escribir {0}: {1}"),
+    ("error.file_write_fix", "Elige otra carpeta o comprueba que se puedan escribir archivos en ella."),
+    ("error.pgn", "No se pudo leer el PGN: {0}"),
+    ("error.pgn_fix", "Revisa las jugadas cercanas a la indicada; las variantes deben ser legales desde donde se ramifican."),
+    ("error.no_pgn", "El portapapeles no contiene ninguna partida PGN"),
+    ("error.no_pgn_fix", "Copia una partida en formato PGN y vuelve a pegarla."),
+    ("toast.engine_terminated", "El motor se ha detenido"),
+    ("toast.engine_move_failed", "No se pudo jugar la jugada del motor {0}: {1}"),
+    ("toast.pgn_copied", "PGN copiado al portapapeles"),
+    // Engine setup
+    ("settings.engine_path", "Motor:"),
+    ("settings.apply", "Aplicar"),
+    ("settings.engine_missing", "No hay ningún archivo en esta ruta"),
+    ("onboarding.title", "Configurar Stockfish"),
+    ("onboarding.intro", "Stockfish es un motor de ajedrez libre y de código abierto. Esta aplicación lo usa para jugar contra ti y analizar posiciones, pero no está incluido y no se ha encontrado en este equipo."),
+    ("onboarding.step_download", "1. Descarga Stockfish"),
+    ("onboarding.download", "⬇ Descargar para este equipo"),
+    ("onboarding.all_downloads", "Todas las descargas"),
+    ("onboarding.step_locate", "2. Descomprímelo e indica la ruta del ejecutable de stockfish"),
+    ("onboarding.path_hint", "p. ej. ~/Descargas/stockfish/stockfish-ubuntu-x86-64-avx2"),
+    ("onboarding.browse", "Examinar…"),
+    ("onboarding.use_engine", "Usar este motor"),
+    ("onboarding.not_found", "No se encontró ningún archivo en {0}"),
+    ("onboarding.skip", "Continuar sin motor"),
+    // Move entry
+    ("entry.label", "Jugar:"),
+    ("entry.hint", "e4, Nf3, g1f3…"),
+    // Name plates
+    ("plate.you", "Tú"),
+    ("plate.white", "Blancas"),
+    ("plate.black", "Negras"),
+    // Search progress
+    ("controls.search_depth", "Profundidad {0}"),
+    // Line pinning
+    ("analysis.pin", "Fijar esta línea"),
+    ("analysis.unpin", "Desfijar"),
+    // Eval gap
+    ("analysis.eval_gap", "Δ {0} con la 2.ª línea"),
+    ("analysis.eval_gap_hint", "Diferencia en peones entre la mejor y la segunda mejor jugada"),
+    ("analysis.only_move", "⚠ Única jugada"),
+    ("analysis.only_move_hint", "Cualquier otra jugada pierde claramente"),
+    // Scratch board
+    ("scratch.start", "🔬 ¿Y si…?"),
+    ("scratch.start_hint", "Prueba jugadas en una copia desechable de esta posición"),
+    ("scratch.keep", "✔ Conservar línea"),
+    ("scratch.keep_hint", "Añade a la partida las jugadas exploradas hasta la actual"),
+    ("scratch.discard", "✖ Descartar"),
+    ("scratch.active", "Tablero de pruebas: las jugadas no se guardan"),
+    // Board orientation
+    ("study.orientation", "Bando:"),
+    ("settings.board", "Tablero:"),
+    ("settings.orient_to_player", "Orientar el tablero a mi color"),
+    ("settings.orient_to_player_hint", "Girar automáticamente al cambiar el color o el capítulo; el botón de girar sigue funcionando"),
+    // Analysis refresh
+    ("settings.analysis_refresh", "Refresco del análisis:"),
+    ("settings.analysis_refresh_hint", "Frecuencia con la que se redibujan las líneas durante el análisis; intervalos más largos usan menos CPU"),
+    // Time control and low-time warnings
+    ("outcome.white_timeout", "¡Las blancas ganan por tiempo!"),
+    ("outcome.black_timeout", "¡Las negras ganan por tiempo!"),
+    ("controls.time_control", "Control de tiempo:"),
+    ("controls.untimed", "Sin reloj"),
+    ("settings.clock", "Reloj:"),
+    ("settings.clock_flash", "Hacer parpadear el reloj con poco tiempo"),
+    ("settings.clock_warning_sound", "Pitido a los 30 y 10 segundos"),
+    ("settings.clock_flag_sound", "Sonido al caer la bandera"),
+    // Touch mode
+    ("settings.touch", "Táctil:"),
+    ("settings.touch_mode", "Diseño para pantallas táctiles"),
+    ("settings.touch_mode_hint", "Botones y espacios más grandes. Arrastra piezas con el dedo y pellizca el tablero para cambiar su tamaño."),
+    // Board vision trainer
+    ("mode.training", "Entrenamiento"),
+    ("vision.heading", "Visión del tablero"),
+    ("vision.knight_path", "Rutas de caballo"),
+    ("vision.attacks", "Casillas atacadas"),
+    ("vision.knight_path_prompt", "Lleva el caballo de {0} a {1} por la ruta más corta"),
+    ("vision.attacks_prompt", "Marca todas las casillas que ataca la pieza ({0}) de {1}"),
+    ("vision.moves_so_far", "Jugadas hasta ahora: {0}"),
+    ("vision.check", "Comprobar"),
+    ("vision.clear", "Borrar"),
+    ("vision.reveal", "Ver solución"),
+    ("vision.next", "Siguiente ▶"),
+    ("vision.correct", "✔ ¡Correcto!"),
+    ("vision.wrong", "✖ No exactamente, la solución está marcada"),
+    ("vision.streak", "Racha: {0}"),
+    ("vision.stats", "{0}: {1}/{2} correctas, media {3} s"),
+    ("vision.pawn", "peón"),
+    ("vision.knight", "caballo"),
+    ("vision.bishop", "alfil"),
+    ("vision.rook", "torre"),
+    ("vision.queen", "dama"),
+    ("vision.king", "rey"),
+    ("heatmap.label", "Mapa de calor:"),
+    ("heatmap.off", "Desactivado"),
+    ("heatmap.occupancy", "Casillas ocupadas"),
+    ("heatmap.attacks", "Casillas atacadas"),
+    ("heatmap.hint", "Sombrea las casillas según la frecuencia con que estuvieron ocupadas o atacadas durante toda la partida"),
+    ("eval_graph.heading", "Evaluación"),
+    ("eval_graph.tooltip", "{0}. {1} — {2}"),
+    ("move_time.heading", "Tiempo por jugada"),
+    ("move_time.tooltip", "{0}. {1} — {2}"),
+    ("move_time.long", "Mucho más tiempo de lo habitual"),
+    ("move_time.instant_blunder", "Jugada instantánea que fue un error grave"),
+    ("settings.export", "Exportar PGN"),
+    ("settings.export_mistakes", "Marcar los errores encontrados por el motor (?, ??)"),
+    ("settings.export_figurine", "Notación figurativa (♘f3)"),
+    ("settings.export_glyphs", "Símbolos de anotación (?!) en lugar de códigos $"),
+    ("settings.export_hint", "Desactiva ambas para un PGN estándar legible por cualquier programa"),
+    ("result.won", "¡Has ganado!"),
+    ("result.lost", "Has perdido"),
+    ("result.draw", "Tablas"),
+    ("result.average_loss", "Pérdida media en centipeones: {0}"),
+    ("result.rematch", "Revancha"),
+    ("result.analyze", "Analizar"),
+    ("result.export", "Copiar PGN"),
+    ("result.close", "Cerrar"),
+    ("toast.pgn_empty", "No se encontraron partidas"),
+    ("toast.open_failed", "No se pudo abrir {0}: {1}"),
+    ("import.title", "Importar PGN"),
+    ("import.summary", "{0} contiene {1} partida(s)."),
+    ("import.game", "Abrir en Análisis"),
+    ("import.first_game", "Abrir la primera partida en Análisis"),
+    ("import.study", "Añadir al estudio actual como capítulos"),
+    ("import.cancel", "Cancelar"),
+    ("import.drop_hint", "Suelta un archivo PGN (también .pgn.gz o .zip) o FEN para abrirlo"),
+    ("toast.not_pgn_or_fen", "{0} no es un archivo PGN ni una posición FEN"),
+    ("toast.import_skipped", "No se pudieron importar {0} partida(s)"),
+    ("toast.importing", "Importando partidas de {0}…"),
+    ("toast.import_busy", "Espera a que termine la importación en curso"),
+    ("toast.keep_line_locked", "El estudio está bloqueado; haz una copia personal para conservar esta línea"),
+    ("toast.line_added_as_chapter", "La línea se añadió como variante de la partida en el capítulo {0}"),
+    ("toast.config_invalid", "Se ignora {0}: {1}"),
+    ("toast.config_save_failed", "No se pudo guardar config.toml: {0}"),
+    ("toast.orphans_killed", "Se detuvieron {0} procesos del motor de una sesión anterior"),
+    ("explorer.heading", "Explorador de aperturas"),
+    ("explorer.masters", "Maestros"),
+    ("explorer.lichess", "Lichess"),
+    ("explorer.loading", "Cargando…"),
+    ("explorer.retry", "Reintentar"),
+    ("explorer.no_games", "Ninguna partida llegó a esta posición"),
+    ("explorer.score", "Blancas {0}% · Tablas {1}% · Negras {2}%"),
+    ("explorer.total", "{0} partidas"),
+    ("explorer.unauthorized", "Lichess exige iniciar sesión para el explorador: añade un token en [lichess] en config.toml"),
+    ("import.my_games", "Añadir a mis partidas"),
+    ("import.my_games_hint", "Guarda estas partidas para el árbol de aperturas personal del explorador"),
+    ("explorer.personal", "Mis partidas"),
+    ("explorer.personal_empty", "Suelta un PGN con tus partidas y elige «Añadir a mis partidas»; luego indica tus nombres de usuario en Ajustes"),
+    ("explorer.personal_total", "{0} partidas tuyas ({1} omitidas)"),
+    ("explorer.personal_score", "+{0} ={1} -{2} · {3}%"),
+    ("explorer.faced", "Jugada por tus rivales"),
+    ("explorer.deviation", "Se sale de tu repertorio, que juega {0}"),
+    ("settings.usernames", "Tus nombres de usuario"),
+    ("settings.usernames_hint", "Nombres con los que juegas, separados por comas, para saber tu bando en las partidas importadas"),
+    ("toast.my_games_added", "{0} partidas nuevas añadidas, {1} ya importadas omitidas; el árbol de aperturas usa ahora {2} partidas tuyas"),
+    ("toast.my_games_none_new", "Las {0} partidas ya estaban importadas"),
+    ("puzzle.daily", "Problema del día"),
+    ("puzzle.source_lichess", "Lichess (colección local si no hay conexión)"),
+    ("puzzle.source_bundled", "Colección local"),
+    ("puzzle.source_off", "Desactivado"),
+    ("puzzle.fetch_failed", "No se pudo conectar con Lichess"),
+    ("puzzle.white_to_move", "Juegan las blancas"),
+    ("puzzle.black_to_move", "Juegan las negras"),
+    ("puzzle.rating", "Elo {0}"),
+    ("puzzle.offline", "Problema local, Lichess no responde"),
+    ("puzzle.loading", "Descargando el problema de hoy…"),
+    ("puzzle.streak", "Racha: {0} días"),
+    ("puzzle.streak_best", "Racha: {0} días (mejor {1})"),
+    ("puzzle.solve", "Resolver"),
+    ("puzzle.later", "Más tarde"),
+    ("puzzle.solved", "¡Resuelto!"),
+    ("puzzle.solved_revealed", "Resuelto con ayuda, la racha no cambia"),
+    ("puzzle.find_best", "Encuentra la mejor jugada"),
+    ("puzzle.mistakes", "Intentos fallidos: {0}"),
+    ("puzzle.open_lichess", "Abrir en Lichess"),
+    ("puzzle.show_move", "Mostrar jugada"),
+    ("puzzle.show_move_hint", "Juega la siguiente jugada de la solución; el problema de hoy ya no cuenta para la racha"),
+    ("puzzle.close", "Cerrar"),
+    ("puzzle.wrong", "No es la jugada, inténtalo de nuevo"),
+    ("settings.daily_puzzle", "Problema del día"),
+    ("settings.daily_puzzle_hint", "Se ofrece al iniciar hasta resolverlo; los cambios se aplican en el próximo inicio"),
+    ("broadcast.heading", "Seguir retransmisión"),
+    ("broadcast.url_hint", "URL o ruta de un PGN"),
+    ("broadcast.interval", "Comprobar cada"),
+    ("broadcast.live_analysis", "Analizar las nuevas jugadas con el motor"),
+    ("broadcast.follow", "Seguir"),
+    ("broadcast.stop", "Dejar de seguir"),
+    ("broadcast.game", "Partida {0}"),
+    ("broadcast.error", "Error al actualizar: {0}"),
+    ("broadcast.updated", "Actualizado hace {0} s"),
+    ("broadcast.waiting", "Esperando la primera actualización…"),
+    ("mode.online", "En línea"),
+    ("online.player_engine", "Motor"),
+    ("online.player_me", "Yo"),
+    ("online.player", "Juega"),
+    ("online.unauthorized", "Lichess rechazó el token; necesita el permiso board:play o bot:play"),
+    ("online.no_token", "Añade un token de la API de Lichess con el permiso board:play (o bot:play) en config.toml, en [lichess] token, para jugar en línea"),
+    ("online.bot_account", "bot"),
+    ("online.board_account", "jugador"),
+    ("online.connected", "Conectado como {0} ({1})"),
+    ("online.connecting", "Conectando con Lichess…"),
+    ("online.reconnect", "Reconectar"),
+    ("online.disconnected", "Conexión perdida: {0}. Reintentando…"),
+    ("online.stream_closed", "Lichess cerró la conexión"),
+    ("online.move_rejected", "Lichess rechazó la jugada: {0}"),
+    ("online.playing", "Jugando contra {0}"),
+    ("online.your_turn", "Te toca"),
+    ("online.their_turn", "Esperando al rival"),
+    ("online.resign", "Abandonar"),
+    ("online.finished", "Última partida: {0}"),
+    ("online.seek", "Buscar rival"),
+    ("online.seek_hint", "Lichess solo empareja búsquedas desde el tablero en partidas rápidas y clásicas (8 minutos o más)"),
+    ("online.seeking", "Buscando rival…"),
+    ("online.challenges", "Desafíos"),
+    ("online.no_challenges", "No hay desafíos pendientes"),
+    ("online.rated", "por puntos"),
+    ("online.casual", "amistosa"),
+    ("online.accept", "Aceptar"),
+    ("online.decline", "Rechazar"),
+    ("online.variant_unsupported", "Aquí solo se puede jugar ajedrez estándar"),
+    ("online.challenged", "{0} te desafía"),
+    ("online.game_over", "La partida en línea ha terminado"),
+    ("online.error", "Lichess: {0}"),
+    ("settings.dgt", "Tablero electrónico"),
+    ("settings.dgt_no_port", "Elegir puerto"),
+    ("settings.dgt_connect", "Conectar"),
+    ("settings.dgt_disconnect", "Desconectar"),
+    ("settings.dgt_connected", "Tablero conectado en {0}"),
+    ("settings.dgt_waiting", "Esperando al tablero…"),
+    ("settings.dgt_rotated", "Las negras están en el lado del cable"),
+    ("settings.announce_moves", "Leer en voz alta las jugadas que hay que hacer en el tablero"),
+    ("settings.announce_moves_hint", "Usa el sintetizador de voz del sistema (say, spd-say o espeak)"),
+    ("dgt.make_move", "Haz {0} en el tablero"),
+    ("dgt.out_of_sync", "El tablero no coincide con la posición en pantalla"),
+    ("speech.king", "rey"),
+    ("speech.queen", "dama"),
+    ("speech.rook", "torre"),
+    ("speech.bishop", "alfil"),
+    ("speech.knight", "caballo"),
+    ("speech.takes", "captura"),
+    ("speech.promotes", "corona"),
+    ("speech.check", "jaque"),
+    ("speech.mate", "jaque mate"),
+    ("speech.castle_short", "enroque corto"),
+    ("speech.castle_long", "enroque largo"),
+    ("report.score", "Partida"),
+    ("report.evaluation", "Evaluación"),
+    ("report.accuracy", "Precisión"),
+    ("report.average_loss", "Pérdida media (cp)"),
+    ("report.inaccuracies", "Imprecisiones"),
+    ("report.mistakes", "Errores"),
+    ("report.blunders", "Errores graves"),
+    ("report.critical", "Momentos críticos"),
+    ("report.no_critical", "No se encontraron errores."),
+    ("report.loss", "Perdió {0} peones"),
+    ("controls.export_report", "Guardar informe…"),
+    ("toast.report_saved", "Informe guardado en {0}"),
+    ("toast.report_failed", "No se pudo guardar el informe: {0}"),
+    ("settings.backup", "Copia de seguridad"),
+    ("settings.backup_create", "Crear copia…"),
+    ("settings.backup_restore", "Restaurar…"),
+    ("settings.backup_hint", "Guarda estudios, partidas importadas, el archivo de configuración y los ajustes en un archivo ZIP"),
+    ("toast.backup_saved", "Copia guardada en {0}"),
+    ("toast.backup_failed", "No se pudo guardar la copia: {0}"),
+    ("toast.restore_failed", "No se pudo restaurar la copia: {0}"),
+    ("toast.backup_restored", "Copia restaurada"),
+    ("analysis.open_lichess", "Abrir en Lichess"),
+    ("analysis.open_lichess_hint", "Abre la partida en el tablero de análisis de Lichess en el navegador, para el análisis en la nube"),
+    ("engine_log.title", "Registro del motor"),
+    ("engine_log.copy", "Copiar"),
+    ("engine_log.clear", "Borrar"),
+    ("engine_log.empty", "El motor no ha informado de errores ni de salida en stderr."),
+    ("settings.engine_log", "Registro"),
+    ("toast.show_engine_log", "Ver registro"),
+    ("settings.power", "Ahorro de energía"),
+    ("settings.power_pause_unfocused", "Pausar el análisis en segundo plano"),
+    ("settings.power_pause_on_battery", "Pausar el análisis con batería"),
+    ("settings.power_cpu_limit", "Límite de CPU"),
+    ("settings.power_cpu_limit_hint", "Parte de los núcleos que usa el motor con la ventana en segundo plano o con batería"),
+    ("analysis.power_paused", "En pausa para ahorrar energía; se reanuda con la ventana activa o al cargar"),
+    ("settings.study_format", "Archivos de estudio"),
+    ("settings.study_format_hint", "Los archivos compactos son mucho más pequeños y rápidos con repertorios grandes; los estudios en cualquiera de los dos formatos se pueden abrir siempre"),
+    ("study.format_json", "JSON (legible)"),
+    ("study.format_compact", "Compacto (binario)"),
+    ("controls.review_game", "🔍 Revisar partida"),
+    ("controls.review_game_hint", "El motor evalúa cada jugada que aún no tiene evaluación"),
+    ("controls.review_progress", "Revisando {0}/{1}"),
+    ("controls.review_cancel", "Cancelar"),
+    ("toast.review_done", "Revisión de la partida terminada"),
+    ("settings.engine_hash", "Hash del motor"),
+    ("settings.keep_hash", "Conservar entre posiciones de la misma partida"),
+    ("settings.keep_hash_hint", "El motor reutiliza lo que encontró en posiciones cercanas, así recorrer una partida evalúa más rápido; otras posiciones empiezan de cero"),
+    ("analysis.clear_hash", "Vaciar hash"),
+    ("analysis.clear_hash_hint", "Olvidar las búsquedas anteriores y evaluar esta posición desde cero"),
+    ("games.heading", "Mis partidas"),
+    ("games.empty", "Aún no hay partidas importadas"),
+    ("games.untitled", "Partida sin título"),
+    ("games.open_hint", "Abrir en Análisis"),
+    ("games.more", "{0} partidas más, acota el filtro para verlas"),
+    ("export.heading", "Exportar partidas"),
+    ("export.all_games", "📄 Exportar todas las partidas"),
+    ("export.include_my_games", "Incluir mis partidas importadas"),
+    ("export.include_my_games_hint", "Añade las partidas importadas cuyo jugador, evento, fecha u otra etiqueta contenga el filtro"),
+    ("export.filter_hint", "Filtro, p. ej. un jugador o 2024"),
+    ("toast.no_games_to_export", "Aún no hay partidas para exportar"),
+    ("toast.games_exported", "{0} partidas exportadas a {1}"),
+    ("toast.games_export_failed", "No se pudieron exportar las partidas: {0}"),
+    ("board.move_slider_hint", "Arrastra para recorrer la partida jugada a jugada"),
+    ("analysis.stable", "estable"),
+    ("analysis.settling", "asentándose"),
+    ("analysis.swinging", "oscilante"),
+    ("analysis.stability_hint", "Cuánto cambió la evaluación en las últimas profundidades"),
+    ("critical.heading", "Momentos críticos"),
+    ("critical.missed_win", "victoria perdida"),
+    ("critical.swing", "punto de inflexión"),
+    ("critical.jump_hint", "Ir a la posición anterior a esta jugada"),
+    ("study.snippet_idea", "con la idea de…"),
+    ("study.snippet_only_move", "la única jugada"),
+    ("study.snippet_better_was", "era mejor…"),
+    ("study.snippet_threatening", "amenazando…"),
+    ("study.snippet_plan", "el plan es…"),
+    ("glyph.good", "Buena jugada"),
+    ("glyph.mistake", "Error"),
+    ("glyph.brilliant", "Jugada brillante"),
+    ("glyph.blunder", "Error grave"),
+    ("glyph.interesting", "Jugada interesante"),
+    ("glyph.dubious", "Jugada dudosa"),
+    ("glyph.equal", "Posición igualada"),
+    ("glyph.unclear", "Posición poco clara"),
+    ("glyph.white_slightly", "Las blancas están algo mejor"),
+    ("glyph.black_slightly", "Las negras están algo mejor"),
+    ("glyph.white_better", "Las blancas están mejor"),
+    ("glyph.black_better", "Las negras están mejor"),
+    ("glyph.white_winning", "Las blancas ganan"),
+    ("glyph.black_winning", "Las negras ganan"),
+    ("glyph.only_move", "Única jugada"),
+    ("glyph.zugzwang", "Zugzwang"),
+    ("glyph.development", "Ventaja de desarrollo"),
+    ("glyph.initiative", "Iniciativa"),
+    ("glyph.attack", "Ataque"),
+    ("glyph.compensation", "Compensación por el material"),
+    ("glyph.counterplay", "Contrajuego"),
+    ("glyph.time_trouble", "Apuros de tiempo"),
+    ("glyph.with_idea", "Con la idea"),
+    ("glyph.novelty", "Novedad"),
+    ("moves.book_exit", "Primera jugada fuera de la teoría de aperturas"),
+    ("quality.brilliant", "Brillantes: sacrificios que mantuvieron la evaluación"),
+    ("quality.good", "Buenas jugadas"),
+    ("quality.inaccuracies", "Imprecisiones"),
+    ("quality.mistakes", "Errores"),
+    ("quality.blunders", "Errores graves"),
+    ("endgame.heading", "Práctica de finales"),
+    ("endgame.play_hint", "Jugar esta posición contra el motor a máxima fuerza"),
+    ("endgame.kq_k", "Dama contra rey"),
+    ("endgame.kr_k", "Torre contra rey"),
+    ("endgame.kp_k", "Rey y peón contra rey"),
+    ("endgame.lucena", "Posición de Lucena"),
+    ("endgame.philidor", "Defensa de Philidor"),
+    ("endgame.krb_kr", "Torre y alfil contra torre (defender)"),
+    ("endgame.kbn_k", "Mate de alfil y caballo"),
+    ("endgame.goal", "Tablebase: {0}"),
+    ("endgame.goal_win", "ganar"),
+    ("endgame.goal_draw", "aguantar las tablas"),
+    ("endgame.goal_loss", "perdida con el mejor juego, resiste lo más posible"),
+    ("endgame.looking_up", "Consultando la tablebase..."),
+    ("endgame.fetch_failed", "Tablebase no disponible, no se puede comprobar el resultado"),
+    ("endgame.full_strength", "El motor juega a máxima fuerza"),
+    ("toast.endgame_held", "{0}: has logrado el resultado de la tablebase"),
+    ("toast.endgame_missed", "{0}: el resultado no alcanza el de la tablebase, inténtalo de nuevo"),
+    ("tournament.heading", "Torneo de motores"),
+    ("tournament.levels", "Niveles de dificultad:"),
+    ("tournament.second_engine_hint", "Ruta de un segundo motor (opcional)"),
+    ("tournament.double_round", "Jugar cada emparejamiento con ambos colores"),
+    ("tournament.movetime", "Tiempo por jugada:"),
+    ("tournament.start", "▶ Empezar torneo"),
+    ("tournament.stop", "⏹ Detener"),
+    ("tournament.need_two", "Elige al menos dos jugadores"),
+    ("tournament.export", "📄 Exportar partidas"),
+    ("tournament.progress", "Partida {0} / {1}"),
+    ("tournament.failed", "Torneo detenido: {0}"),
+    ("tournament.score", "Puntos"),
+    ("tournament.elo", "Elo"),
+    ("tournament.elo_hint", "Diferencia de rating estimada con el rival medio, según la puntuación"),
+    ("tournament.engine_gone", "Un motor se cerró durante el torneo"),
+    ("tournament.no_engine", "No se encontró ningún motor para el torneo"),
+    ("toast.tournament_finished", "Torneo terminado tras {0} partidas"),
+    ("plan.heading", "Plan de entrenamiento"),
+    ("plan.puzzles", "Problemas"),
+    ("plan.puzzles_hint", "Problemas resueltos sin mostrar la solución"),
+    ("plan.lines", "Líneas de repertorio"),
+    ("plan.lines_hint", "Líneas de estudio recorridas hasta su última jugada"),
+    ("plan.reviews", "Revisiones de partidas"),
+    ("plan.reviews_hint", "Revisiones de una partida con el motor completadas"),
+    ("plan.done_today", "✔ Objetivos de hoy cumplidos"),
+    ("plan.streak", "Racha: {0} días (mejor {1})"),
+    ("toast.plan_met", "Has cumplido los objetivos de entrenamiento de hoy"),
+    ("speech.pawn", "peón"),
+    ("coach.hanging", "Tu pieza en {1} ({0}) está atacada y sin defensa"),
+    ("coach.attacked_by_lesser", "Tu pieza en {1} ({0}) está atacada por: {2}"),
+    ("coach.castle", "El motor quiere enrocar"),
+    ("coach.promote", "El motor quiere coronar en {0}"),
+    ("coach.capture", "El motor quiere capturar en {1} ({0})"),
+    ("coach.check", "El motor quiere dar jaque ({0})"),
+    ("coach.open_file", "El motor quiere abrir la columna {0}"),
+    ("coach.piece_move", "El motor quiere jugar a {1} ({0})"),
+    ("coach.pawn_push", "El motor quiere avanzar el peón a {0}"),
+    ("settings.coach", "Entrenador:"),
+    ("settings.coach_hints", "Explicar la evaluación"),
+    ("settings.coach_hints_hint", "Mostrar pistas en palabras bajo la barra de evaluación, como piezas en peligro y el sentido de la mejor jugada del motor"),
+    ("settings.promotion", "Coronación:"),
+    ("settings.auto_queen", "Coronar siempre en dama"),
+    ("settings.auto_queen_hint", "Omitir el selector de pieza. Mantén N, R o B al soltar el peón para coronar en caballo, torre o alfil"),
+    ("premove.heading", "Premovimientos"),
+    ("premove.heading_hint", "Jugadas que se hacen por ti en cuanto responde el motor. Elige una en el tablero mientras el motor piensa, o escribe una línea con sus respuestas esperadas y las tuyas."),
+    ("premove.line_hint", "p. ej. Nxe4 Nxe4 Bxe4 Qxe4"),
+    ("premove.set", "Encolar"),
+    ("premove.empty", "Nada en cola"),
+    ("premove.conditional", "si {0} → {1}"),
+    ("premove.any_reply", "tras cualquier respuesta → {0}"),
+    ("premove.clear", "Borrar premovimientos"),
+    ("toast.premove_illegal", "El premovimiento ya no es legal, cola vaciada"),
+    ("toast.premove_cancelled", "El motor jugó otra cosa, premovimientos borrados"),
+    ("toast.premove_invalid", "No se puede encolar esa línea: {0}"),
+    ("settings.variety", "Variedad de aperturas"),
+    ("settings.variety_enabled", "Variar las primeras jugadas del motor"),
+    ("settings.variety_hint", "El motor elige sus primeras jugadas al azar entre las líneas casi tan buenas como la mejor, para que las partidas del mismo nivel no empiecen todas igual"),
+    ("settings.variety_moves", "Jugadas"),
+    ("settings.variety_margin", "Margen"),
+    ("settings.variety_margin_hint", "Cuántos centipeones peor que la mejor línea puede ser una jugada para poder elegirse"),
+    ("controls.undo_left", "↩ Deshacer jugada (quedan {0})"),
+    ("controls.takebacks", "Retrocesos"),
+    ("controls.takebacks_locked", "Los retrocesos se pueden cambiar antes del primer movimiento o al terminar la partida"),
+    ("takeback.unlimited", "Ilimitados"),
+    ("takeback.per_game", "{0} por partida"),
+    ("takeback.off", "Ninguno"),
+    ("resign.confirm", "¿Abandonar esta partida?"),
+    ("resign.lost", "Tu posición parece perdida"),
+    ("resign.lost_detail", "El motor la valora en {0} desde hace varias jugadas. Los jugadores fuertes abandonan aquí."),
+    ("resign.resign", "Abandonar"),
+    ("resign.play_on", "Seguir jugando"),
+    ("settings.auto_resign", "Abandono automático"),
+    ("settings.auto_resign_enabled", "Ofrecer abandonar partidas perdidas"),
+    ("settings.auto_resign_hint", "Pregunta si quieres abandonar cuando la evaluación del motor se mantiene por debajo del umbral durante varias de sus jugadas, para practicar a reconocer partidas perdidas"),
+    ("settings.auto_resign_threshold", "Desventaja"),
+    ("settings.auto_resign_moves", "Durante jugadas"),
+    ("settings.engine_roles", "Motores por modo"),
+    ("settings.engine_roles_hint", "Usa motores distintos para jugar en el modo Partida, analizar y revisar partidas, y limita los hilos de cada uno. Cada motor sigue en marcha mientras se usa otro modo. Las rutas vacías usan el motor de arriba, o en las revisiones el motor en uso."),
+    ("settings.engine_role_game", "Partida"),
+    ("settings.engine_role_analysis", "Análisis"),
+    ("settings.engine_role_default", "Motor de arriba"),
+    ("settings.engine_role_review", "Revisión de partida"),
+    ("settings.engine_role_review_hint", "Motor cargado"),
+    ("settings.engine_threads_any", "Todos los hilos"),
+    ("settings.engine_threads", "Hasta {0} hilos"),
+    ("settings.uci_console", "Consola UCI"),
+    ("settings.uci_console_hint", "Escribe comandos UCI directamente al motor en marcha"),
+    ("console.title", "Consola UCI"),
+    ("console.hint", "↑/↓ recuperan comandos anteriores"),
+    ("console.input_hint", "Comando UCI, p. ej. d o eval"),
+    ("console.warn_search", "Iniciar o detener una búsqueda aquí puede confundir partidas y análisis en curso"),
+    ("console.warn_position", "Cambia la posición del motor sin que la aplicación lo sepa hasta su próxima búsqueda"),
+    ("console.warn_handshake", "La aplicación ya hizo el saludo inicial; la respuesta puede confundirse con otra salida"),
+    ("console.warn_quit", "Cierra el motor; reinícialo desde los ajustes"),
+    ("console.warn_option", "La aplicación fija esta opción ella misma y no sabrá que cambió"),
+    ("analysis.exclude", "Excluir esta jugada y buscar de nuevo, para ver el siguiente mejor plan"),
+    ("analysis.excluded", "Excluidas:"),
+    ("analysis.restore_move", "Volver a buscar esta jugada"),
+    ("settings.check_moves", "Comprobar mis jugadas antes de jugarlas"),
+    ("settings.check_moves_hint", "En el modo Partida, una jugada hecha en el tablero espera mientras el motor calcula cuánto pierde frente a la mejor jugada"),
+    ("staged.move", "Tu jugada: {}"),
+    ("staged.checking", "Comprobando…"),
+    ("staged.no_loss", "Tan buena como la elección del motor"),
+    ("staged.cost", "Pierde {} peones frente a la mejor jugada"),
+    ("staged.play", "Jugarla"),
+    ("staged.cancel", "Elegir otra"),
+    ("phase.opening", "Apertura"),
+    ("phase.middlegame", "Medio juego"),
+    ("phase.endgame", "Final"),
+    ("report.by_phase", "Pérdida media por fase (cp)"),
+    ("quality.by_phase", "Pérdida media por fase de la partida, en centipeones"),
+    ("coverage.open", "Cobertura del repertorio…"),
+    ("coverage.open_hint", "Comparar tus partidas con el repertorio de tus estudios"),
+    ("coverage.title", "Cobertura del repertorio"),
+    ("coverage.empty", "Tus partidas no salen de tu repertorio. Los estudios vistos desde blancas o negras cuentan como repertorio de ese bando."),
+    ("coverage.suggestions", "Aprender a continuación"),
+    ("coverage.misplayed", "Líneas que jugaste mal"),
+    ("coverage.uncovered", "Respuestas que tu repertorio no cubre"),
+    ("coverage.none", "Ninguna"),
+    ("coverage.open_line", "Mostrar en el tablero"),
+    ("coverage.games", "{0} partidas, {1} perdidas"),
+    ("coverage.prepared", "preparado: {0}"),
+    ("compare.open", "⚖ Comparar"),
+    ("compare.open_hint", "Mostrar la posición junto al final de la línea del motor o una posición fijada"),
+    ("compare.title", "Comparar posiciones"),
+    ("compare.current", "Tablero"),
+    ("compare.engine_line", "Tras la línea del motor"),
+    ("compare.pinned", "Posición fijada"),
+    ("compare.pin", "📌 Fijar esta posición"),
+    ("compare.pin_hint", "Comparar las posiciones siguientes con esta"),
+    ("compare.unpin", "Soltar"),
+    ("compare.pawns_only", "Solo peones"),
+    ("compare.pawns_only_hint", "Marcar solo las casillas donde difiere la estructura de peones"),
+    ("compare.no_target", "Inicia el análisis o fija una posición para comparar."),
+    ("compare.differences", "{0} casillas difieren"),
+    ("share.copy_link", "Copiar enlace a la posición"),
+    ("share.copy_link_hint", "Copiar un enlace al tablero de análisis de Lichess con la posición del tablero"),
+    ("share.copy_pgn", "Copiar PGN comentado"),
+    ("share.copy_pgn_hint", "Copiar la partida en PGN, con las líneas actuales del motor como comentario en la posición del tablero"),
+    ("toast.link_copied", "Enlace copiado al portapapeles"),
+    ("print.after", "Tras {0}"),
+    ("study.print", "Imprimir capítulo…"),
+    ("study.print_hint", "Guardar el capítulo como página lista para imprimir o guardar como PDF desde el navegador, con diagramas, variantes y comentarios"),
+    ("study.print_every", "Diagrama cada"),
+    ("study.print_moves", "jugadas"),
+    ("share.latex_diagram", "Copiar diagrama LaTeX"),
+    ("share.latex_diagram_hint", "Copiar la posición como fragmento del paquete chessboard para documentos LaTeX"),
+    ("share.latex_line", "Copiar línea LaTeX"),
+    ("share.latex_line_hint", "Copiar las jugadas hasta la posición del tablero como fragmento de xskak, seguido de un diagrama"),
+    ("toast.latex_copied", "LaTeX copiado al portapapeles"),
+    ("import.summary_archive", "{0} es un archivo comprimido; sus partidas se leen al importarlas."),
+    ("toast.duplicate_line", "Esta línea ya está en el capítulo \"{0}\""),
+    ("toast.transposed_line", "El capítulo \"{0}\" ya llega a esta posición con otro orden de jugadas"),
+    ("toast.go_to_duplicate", "Ir allí en su lugar"),
+    ("study.drill", "Entrenar este capítulo"),
+    ("study.drill_hint", "Juega las líneas del capítulo desde su lado; las respuestas del rival se eligen al azar"),
+    ("study.drill_stop", "Detener entrenamiento"),
+    ("study.weak_spots", "Puntos débiles"),
+    ("study.weak_spots_none", "Aquí aparecen las posiciones que fallas al entrenar"),
+    ("study.weak_spot_recall", "{0}/{1} recordadas"),
+    ("drill.wrong", "No es la jugada de tu repertorio, inténtalo de nuevo (quedan {0} intentos)"),
+    ("drill.finished", "Fin de la línea"),
+    ("drill.nothing", "Este capítulo no tiene jugadas de su lado para entrenar"),
+    ("drill.time_out", "Se acabó el tiempo para esta jugada"),
+    ("drill.failed", "Demasiados errores, empieza otra línea"),
+    ("drill.hint", "Jugada del repertorio: {0}"),
+    ("drill.summary_title", "Resultados del entrenamiento"),
+    ("drill.summary_score", "Puntuación: {0}%"),
+    ("drill.summary_passed", "Líneas completadas"),
+    ("drill.summary_failed", "Líneas falladas"),
+    ("drill.summary_first_try", "Jugadas acertadas al primer intento"),
+    ("drill.summary_positions", "Jugadas preguntadas"),
+    ("drill.summary_mistakes", "Errores"),
+    ("drill.summary_timeouts", "Sin tiempo"),
+    ("drill.summary_hints", "Pistas usadas"),
+    ("study.drill_mistakes", "Errores en esta línea: {0}/{1}"),
+    ("study.drill_time", "quedan {0} s"),
+    ("study.drill_hint_button", "Pista"),
+    ("settings.drill", "Entrenamiento de repertorio"),
+    ("settings.drill_move_seconds", "Tiempo por jugada"),
+    ("settings.drill_move_seconds_hint", "0 para no limitar el tiempo"),
+    ("settings.drill_mistakes", "Errores permitidos por línea"),
+    ("settings.drill_hints", "Permitir pistas"),
+    ("audit.move_loses", "Motor: {0} cp peor que su mejor jugada"),
+    ("audit.bad_ending", "Motor: la línea termina en {0} para tu lado"),
+    ("audit.done", "Revisión con el motor terminada, {0} aviso(s)"),
+    ("audit.no_engine", "El motor no está listo para revisar el capítulo"),
+    ("audit.busy", "Espera a que el motor termine su revisión actual"),
+    ("audit.progress", "Revisando el capítulo con el motor… {0}/{1}"),
+    ("audit.cancel", "Cancelar"),
+    ("audit.cancelled", "Revisión con el motor cancelada"),
+    ("audit.stale", "El estudio cambió mientras el motor lo revisaba, así que se descartaron los resultados"),
+    ("annotate.progress", "Anotando: {0}/{1} posiciones"),
+    ("annotate.done", "Anotación terminada: {0} errores marcados"),
+    ("annotate.cancelled", "Anotación cancelada"),
+    ("annotate.nothing", "El capítulo no tiene jugadas que anotar"),
+    ("annotate.better", "Mejor era {0}"),
+    ("study.audit", "Revisar con el motor"),
+    ("study.audit_hint", "Marca las jugadas de tu lado que pierden más que el umbral frente a la mejor del motor, y las líneas que terminan en malas posiciones"),
+    ("study.audit_threshold", "Umbral"),
+    ("study.lock_hint", "Bloquear el estudio: se puede seguir recorriendo y entrenando, pero no editar"),
+    ("study.locked", "Estudio de solo lectura"),
+    ("study.locked_title", "Estudio bloqueado"),
+    ("study.locked_prompt", "Este estudio está bloqueado. ¿Crear una copia personal para editarlo?"),
+    ("study.make_copy", "Crear copia"),
+    ("study.copy_name", "{0} (copia)"),
+    ("study.paste_pgn", "📋 Pegar PGN como capítulo"),
+    ("study.paste_pgn_hint", "Añadir la partida del portapapeles, con sus variantes y comentarios, como capítulo nuevo"),
+    ("study.headers", "Cabeceras PGN"),
+    ("study.header_name", "Etiqueta"),
+    ("study.header_value", "Valor"),
+    ("study.header_remove", "Quitar esta etiqueta"),
+    ("study.annotate", "Anotar partida"),
+    ("study.annotate_hint", "Hacer que el motor evalúe cada jugada de la línea principal, marcando los errores con un símbolo y la línea mejor"),
+    ("settings.clicks", "Clics"),
+    ("settings.own_piece", "Clic en otra pieza propia:"),
+    ("settings.own_piece_switch", "La selecciona"),
+    ("settings.own_piece_move", "Intenta la jugada"),
+    ("settings.deselect_selected", "Clic en la pieza seleccionada la deselecciona"),
+    ("settings.confirm_destination", "Confirmar jugadas con un segundo clic"),
+    ("settings.confirm_destination_hint", "El primer clic en una casilla de destino la marca, el segundo hace la jugada"),
+    ("settings.engine_moves", "Jugadas del motor"),
+    ("settings.engine_move_highlight", "Resaltar las jugadas del motor con su propio color"),
+    ("settings.engine_move_sound", "Reproducir un sonido cuando el motor juega"),
+];
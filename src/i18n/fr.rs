@@ -0,0 +1,726 @@
+pub const STRINGS: &[(&str, &str)] = &[
+    // Formatting
+    ("format.move_number", "{0}."),
+    // Mode and navigation
+    ("mode.label", "Mode :"),
+    ("mode.game", "Jouer contre le moteur"),
+    ("mode.analysis", "Analyse"),
+    ("mode.study", "Étude"),
+    ("nav.label", "Navigation :"),
+    ("nav.start", "Aller au début"),
+    ("nav.previous", "Coup précédent"),
+    ("nav.next", "Coup suivant"),
+    ("nav.end", "Aller à la fin"),
+    ("nav.move_counter", "Coup : {0} / {1}"),
+    // Game controls
+    ("controls.engine_thinking", "Le moteur réfléchit..."),
+    ("controls.new_game", "Nouvelle partie"),
+    ("controls.flip_board", "Retourner l'échiquier"),
+    ("controls.play_as", "Jouer avec :"),
+    ("controls.white", "Blancs"),
+    ("controls.black", "Noirs"),
+    ("controls.difficulty", "Difficulté :"),
+    ("controls.personality", "Personnalité :"),
+    ("controls.theme", "Thème :"),
+    ("controls.language", "Langue :"),
+    ("controls.resign", "🏳 Abandonner"),
+    ("controls.offer_draw", "🤝 Proposer nulle"),
+    ("controls.undo", "↩ Annuler le coup"),
+    ("controls.export_pgn", "📄 Exporter en PGN"),
+    ("controls.save_to_study", "📚 Enregistrer dans une étude"),
+    // Outcomes
+    ("outcome.white_checkmate", "Les Blancs gagnent par échec et mat !"),
+    ("outcome.black_checkmate", "Les Noirs gagnent par échec et mat !"),
+    ("outcome.stalemate", "Nulle par pat"),
+    ("outcome.insufficient_material", "Nulle par matériel insuffisant"),
+    ("outcome.threefold", "Nulle par triple répétition"),
+    ("outcome.fifty_move", "Nulle par la règle des cinquante coups"),
+    ("outcome.white_resignation", "Les Blancs gagnent par abandon !"),
+    ("outcome.black_resignation", "Les Noirs gagnent par abandon !"),
+    ("outcome.agreement", "Nulle par accord mutuel"),
+    // Difficulty levels
+    ("difficulty.novice", "Novice (~1100)"),
+    ("difficulty.beginner", "Débutant (~1350)"),
+    ("difficulty.casual", "Amateur (~1500)"),
+    ("difficulty.intermediate", "Intermédiaire (~1800)"),
+    ("difficulty.advanced", "Avancé (~2100)"),
+    ("difficulty.expert", "Expert (~2500)"),
+    ("difficulty.maximum", "Force maximale"),
+    // Engine personalities
+    ("personality.standard", "Standard"),
+    ("personality.aggressive", "Attaquant"),
+    ("personality.positional", "Stratège"),
+    ("personality.grinder", "Spécialiste des finales"),
+    ("personality.standard_description", "Joue le meilleur coup du moteur."),
+    ("personality.aggressive_description", "Cherche les échecs, les prises et les ouvertures tranchantes, et évite la nulle."),
+    ("personality.positional_description", "Préfère les coups calmes, le roque rapide et les ouvertures fermées."),
+    ("personality.grinder_description", "Échange les pièces, active le roi et gagne les longues finales."),
+    // Themes
+    ("theme.classic", "Classique"),
+    ("theme.dark", "Sombre"),
+    // Analysis panel
+    ("analysis.heading", "Analyse"),
+    ("analysis.analyze", "▶ Analyser"),
+    ("analysis.stop", "⏹ Arrêter"),
+    ("analysis.analyzing", "Analyse en cours..."),
+    ("analysis.paused", "⏸ En pause"),
+    ("analysis.lines", "Lignes :"),
+    ("analysis.eval_bar", "Barre d'évaluation"),
+    ("analysis.calculating", "/ {0} en calcul"),
+    ("analysis.empty", "Pas encore d'analyse..."),
+    // Move list
+    ("moves.heading", "Coups"),
+    // Study panel
+    ("study.heading", "Étude"),
+    ("study.untitled", "Étude sans titre"),
+    ("study.game_name", "Partie {0}"),
+    ("study.name", "Nom :"),
+    ("study.chapter", "Chapitre :"),
+    ("study.chapter_n", "Chapitre {0}"),
+    ("study.comments", "Commentaires :"),
+    ("study.no_comments", "Pas encore de commentaires..."),
+    ("study.add", "Ajouter"),
+    ("study.variations", "Variantes :"),
+    ("study.save", "💾 Enregistrer"),
+    ("study.load", "📂 Ouvrir"),
+    ("study.new", "🆕 Nouvelle"),
+    ("study.export_pgn", "📄 Exporter en PGN"),
+    ("study.new_study", "Nouvelle étude"),
+    ("study.study_name", "Nom de l'étude :"),
+    ("study.create", "Créer"),
+    ("study.cancel", "Annuler"),
+    ("study.load_study", "Ouvrir une étude"),
+    ("study.none_saved", "Aucune étude enregistrée."),
+    ("study.close", "Fermer"),
+    ("study.start", "Début"),
+    ("study.alternatives", "Alternatives :"),
+    // Autoplay
+    ("nav.autoplay", "Lecture automatique / pause (s'arrête sur les commentaires)"),
+    ("nav.autoplay_speed", "Vitesse :"),
+    // Status bar
+    ("status.no_engine", "Aucun moteur"),
+    ("status.offline", "Hors ligne"),
+    ("status.idle", "Inactif"),
+    ("status.thinking", "Réflexion"),
+    ("status.analyzing", "Analyse"),
+    ("status.difficulty", "Difficulté : {0}"),
+    // Settings
+    ("settings.title", "Paramètres"),
+    ("settings.notation", "Notation :"),
+    ("notation.san", "Algébrique (SAN)"),
+    ("notation.figurine", "Figurines"),
+    ("notation.long", "Algébrique longue"),
+    ("notation.uci", "UCI"),
+    // Toasts
+    ("toast.dismiss", "Fermer"),
+    ("toast.open_settings", "Ouvrir les paramètres"),
+    ("toast.engine_error", "Erreur du moteur : {0}"),
+    ("error.engine_not_found", "Aucun moteur trouvé à {0}"),
+    ("error.engine_not_found_fix", "Installez Stockfish ou choisissez le chemin du moteur dans les paramètres."),
+    ("error.engine_start", "Impossible de démarrer le moteur {0} : {1}"),
+    ("error.engine_start_fix", "Vérifiez que le fichier est un moteur UCI compilé pour cet ordinateur et qu'il peut être exécuté."),
+    ("error.engine_timeout", "Le moteur n'a pas répondu à '{0}' en {1} s et redémarre"),
+    ("error.engine_timeout_fix", "Réduisez les threads ou le hash du moteur si l'ordinateur est occupé, ou augmentez les délais dans config.toml."),
+    ("error.engine_closed", "Le moteur s'est arrêté de façon inattendue"),
+    ("error.engine_closed_fix", "Le journal du moteur peut en donner la raison ; une version du moteur pour processeurs plus anciens peut aider."),
+    ("error.engine_reported", "Le moteur signale : {0}"),
+    ("error.engine_reported_fix", "Vérifiez les fichiers dont le moteur a besoin, comme son fichier réseau, à côté de l'exécutable."),
+    ("error.engine_fix", "Ouvrez le journal du moteur pour les détails."),
+    ("error.study_read", "Impossible de lire l'étude {0} : {1}"),
+    ("error.study_read_fix", "Vérifiez que le dossier des études du répertoire de données est lisible."),
+    ("error.study_damaged", "L'étude {0} est endommagée : {1}"),
+    ("error.study_damaged_fix", "Restaurez l'étude depuis une sauvegarde de vos données."),
+    ("error.study_write", "Impossible d'enregistrer l'étude {0} : {1}"),
+    ("error.study_write_fix", "Vérifiez l'espace disque libre et que le répertoire de données est accessible en écriture."),
+    ("error.file_write", "Impossible d'écrire {0} : {1}"),
+    ("error.file_write_fix", "Choisissez un autre dossier ou vérifiez que des fichiers peuvent y être écrits."),
+    ("error.pgn", "Impossible de lire le PGN : {0}"),
+    ("error.pgn_fix", "Vérifiez les coups autour de celui indiqué ; les variantes doivent être légales depuis leur embranchement."),
+    ("error.no_pgn", "Le presse-papiers ne contient aucune partie PGN"),
+    ("error.no_pgn_fix", "Copiez une partie au format PGN, puis collez à nouveau."),
+    ("toast.engine_terminated", "Le moteur s'est arrêté"),
+    ("toast.engine_move_failed", "Impossible de jouer le coup du moteur {0} : {1}"),
+    ("toast.pgn_copied", "PGN copié dans le presse-papiers"),
+    // Engine setup
+    ("settings.engine_path", "Moteur :"),
+    ("settings.apply", "Appliquer"),
+    ("settings.engine_missing", "Aucun fichier à cet emplacement"),
+    ("onboarding.title", "Configurer Stockfish"),
+    ("onboarding.intro", "Stockfish est un moteur d'échecs libre et open source. Cette application l'utilise pour jouer contre vous et analyser les positions, mais il n'est pas inclus et n'a pas été trouvé sur cet ordinateur."),
+    ("onboarding.step_download", "1. Téléchargez Stockfish"),
+    ("onboarding.download", "⬇ Télécharger pour cet ordinateur"),
+    ("onboarding.all_downloads", "Tous les téléchargements"),
+    ("onboarding.step_locate", "2. Décompressez-le et indiquez le chemin de l'exécutable stockfish"),
+    ("onboarding.path_hint", "ex. ~/Téléchargements/stockfish/stockfish-ubuntu-x86-64-avx2"),
+    ("onboarding.browse", "Parcourir…"),
+    ("onboarding.use_engine", "Utiliser ce moteur"),
+    ("onboarding.not_found", "Aucun fichier trouvé à {0}"),
+    ("onboarding.skip", "Continuer sans moteur"),
+    // Move entry
+    ("entry.label", "Jouer :"),
+    ("entry.hint", "e4, Nf3, g1f3…"),
+    // Name plates
+    ("plate.you", "Vous"),
+    ("plate.white", "Blancs"),
+    ("plate.black", "Noirs"),
+    // Search progress
+    ("controls.search_depth", "Profondeur {0}"),
+    // Line pinning
+    ("analysis.pin", "Épingler cette ligne"),
+    ("analysis.unpin", "Désépingler"),
+    // Eval gap
+    ("analysis.eval_gap", "Δ {0} avec la 2e ligne"),
+    ("analysis.eval_gap_hint", "Écart en pions entre le meilleur et le deuxième meilleur coup"),
+    ("analysis.only_move", "⚠ Coup unique"),
+    ("analysis.only_move_hint", "Tout autre coup perd nettement"),
+    // Scratch board
+    ("scratch.start", "🔬 Et si… ?"),
+    ("scratch.start_hint", "Essayer des coups sur une copie jetable de cette position"),
+    ("scratch.keep", "✔ Garder la ligne"),
+    ("scratch.keep_hint", "Ajouter à la partie les coups explorés jusqu'au coup actuel"),
+    ("scratch.discard", "✖ Abandonner"),
+    ("scratch.active", "Échiquier d'essai : les coups ne sont pas enregistrés"),
+    // Board orientation
+    ("study.orientation", "Camp :"),
+    ("settings.board", "Échiquier :"),
+    ("settings.orient_to_player", "Orienter l'échiquier selon ma couleur"),
+    ("settings.orient_to_player_hint", "Retourner automatiquement au changement de couleur ou de chapitre ; le bouton de retournement reste disponible"),
+    // Analysis refresh
+    ("settings.analysis_refresh", "Rafraîchissement de l'analyse :"),
+    ("settings.analysis_refresh_hint", "Fréquence de rafraîchissement des lignes pendant l'analyse ; un intervalle plus long consomme moins de CPU"),
+    // Time control and low-time warnings
+    ("outcome.white_timeout", "Les blancs gagnent au temps !"),
+    ("outcome.black_timeout", "Les noirs gagnent au temps !"),
+    ("controls.time_control", "Cadence :"),
+    ("controls.untimed", "Sans pendule"),
+    ("settings.clock", "Pendule :"),
+    ("settings.clock_flash", "Faire clignoter la pendule en cas de manque de temps"),
+    ("settings.clock_warning_sound", "Bip à 30 et 10 secondes"),
+    ("settings.clock_flag_sound", "Son à la chute du drapeau"),
+    // Touch mode
+    ("settings.touch", "Tactile :"),
+    ("settings.touch_mode", "Disposition adaptée au tactile"),
+    ("settings.touch_mode_hint", "Boutons et espacements plus grands. Faites glisser les pièces du doigt et pincez l'échiquier pour le redimensionner."),
+    // Board vision trainer
+    ("mode.training", "Entraînement"),
+    ("vision.heading", "Vision de l'échiquier"),
+    ("vision.knight_path", "Parcours du cavalier"),
+    ("vision.attacks", "Cases attaquées"),
+    ("vision.knight_path_prompt", "Menez le cavalier de {0} à {1} par le chemin le plus court"),
+    ("vision.attacks_prompt", "Marquez toutes les cases attaquées par la pièce ({0}) en {1}"),
+    ("vision.moves_so_far", "Coups joués : {0}"),
+    ("vision.check", "Vérifier"),
+    ("vision.clear", "Effacer"),
+    ("vision.reveal", "Voir la solution"),
+    ("vision.next", "Suivant ▶"),
+    ("vision.correct", "✔ Correct !"),
+    ("vision.wrong", "✖ Pas tout à fait, la solution est entourée"),
+    ("vision.streak", "Série : {0}"),
+    ("vision.stats", "{0} : {1}/{2} justes, moyenne {3} s"),
+    ("vision.pawn", "pion"),
+    ("vision.knight", "cavalier"),
+    ("vision.bishop", "fou"),
+    ("vision.rook", "tour"),
+    ("vision.queen", "dame"),
+    ("vision.king", "roi"),
+    ("heatmap.label", "Carte de chaleur :"),
+    ("heatmap.off", "Désactivée"),
+    ("heatmap.occupancy", "Cases occupées"),
+    ("heatmap.attacks", "Cases attaquées"),
+    ("heatmap.hint", "Colore les cases selon la fréquence à laquelle elles ont été occupées ou attaquées pendant toute la partie"),
+    ("eval_graph.heading", "Évaluation"),
+    ("eval_graph.tooltip", "{0}. {1} — {2}"),
+    ("move_time.heading", "Temps par coup"),
+    ("move_time.tooltip", "{0}. {1} — {2}"),
+    ("move_time.long", "Beaucoup plus long que d'habitude"),
+    ("move_time.instant_blunder", "Joué instantanément : une gaffe"),
+    ("settings.export", "Export PGN"),
+    ("settings.export_mistakes", "Marquer les erreurs trouvées par le moteur (?, ??)"),
+    ("settings.export_figurine", "Notation figurine (♘f3)"),
+    ("settings.export_glyphs", "Symboles d'annotation (?!) au lieu des codes $"),
+    ("settings.export_hint", "Laissez les deux désactivées pour un PGN standard lisible par tout logiciel"),
+    ("result.won", "Vous avez gagné !"),
+    ("result.lost", "Vous avez perdu"),
+    ("result.draw", "Nulle"),
+    ("result.average_loss", "Perte moyenne en centipions : {0}"),
+    ("result.rematch", "Revanche"),
+    ("result.analyze", "Analyser"),
+    ("result.export", "Copier le PGN"),
+    ("result.close", "Fermer"),
+    ("toast.pgn_empty", "Aucune partie trouvée"),
+    ("toast.open_failed", "Impossible d'ouvrir {0} : {1}"),
+    ("import.title", "Importer un PGN"),
+    ("import.summary", "{0} contient {1} partie(s)."),
+    ("import.game", "Ouvrir dans l'analyse"),
+    ("import.first_game", "Ouvrir la première partie dans l'analyse"),
+    ("import.study", "Ajouter à l'étude actuelle comme chapitres"),
+    ("import.cancel", "Annuler"),
+    ("import.drop_hint", "Déposez un fichier PGN (aussi .pgn.gz ou .zip) ou FEN pour l'ouvrir"),
+    ("toast.not_pgn_or_fen", "{0} n'est ni un fichier PGN ni une position FEN"),
+    ("toast.import_skipped", "{0} partie(s) n'ont pas pu être importée(s)"),
+    ("toast.importing", "Import des parties de {0}…"),
+    ("toast.import_busy", "Attendez la fin de l'import en cours"),
+    ("toast.keep_line_locked", "L'étude est verrouillée ; faites-en une copie personnelle pour garder cette ligne"),
+    ("toast.line_added_as_chapter", "La ligne a été ajoutée comme variante de la partie au chapitre {0}"),
+    ("toast.config_invalid", "{0} ignoré : {1}"),
+    ("toast.config_save_failed", "Impossible d'enregistrer config.toml : {0}"),
+    ("toast.orphans_killed", "{0} processus du moteur laissés par une session précédente ont été arrêtés"),
+    ("explorer.heading", "Explorateur d'ouvertures"),
+    ("explorer.masters", "Maîtres"),
+    ("explorer.lichess", "Lichess"),
+    ("explorer.loading", "Chargement…"),
+    ("explorer.retry", "Réessayer"),
+    ("explorer.no_games", "Aucune partie n'a atteint cette position"),
+    ("explorer.score", "Blancs {0}% · Nulle {1}% · Noirs {2}%"),
+    ("explorer.total", "{0} parties"),
+    ("explorer.unauthorized", "Lichess exige une connexion pour l'explorateur : ajoutez un jeton sous [lichess] dans config.toml"),
+    ("import.my_games", "Ajouter à mes parties"),
+    ("import.my_games_hint", "Conserver ces parties pour l'arbre d'ouvertures personnel de l'explorateur"),
+    ("explorer.personal", "Mes parties"),
+    ("explorer.personal_empty", "Déposez un PGN de vos parties, choisissez « Ajouter à mes parties », puis indiquez vos pseudos dans les paramètres"),
+    ("explorer.personal_total", "{0} de vos parties ({1} ignorées)"),
+    ("explorer.personal_score", "+{0} ={1} -{2} · {3}%"),
+    ("explorer.faced", "Joué par vos adversaires"),
+    ("explorer.deviation", "Sort de votre répertoire, qui joue {0}"),
+    ("settings.usernames", "Vos pseudos"),
+    ("settings.usernames_hint", "Noms sous lesquels vous jouez, séparés par des virgules, pour trouver votre camp dans les parties importées"),
+    ("toast.my_games_added", "{0} nouvelles parties ajoutées, {1} déjà importées ignorées ; l'arbre d'ouvertures compte maintenant {2} de vos parties"),
+    ("toast.my_games_none_new", "Les {0} parties étaient déjà importées"),
+    ("puzzle.daily", "Problème du jour"),
+    ("puzzle.source_lichess", "Lichess (série locale en secours)"),
+    ("puzzle.source_bundled", "Série locale"),
+    ("puzzle.source_off", "Désactivé"),
+    ("puzzle.fetch_failed", "Impossible de joindre Lichess"),
+    ("puzzle.white_to_move", "Les blancs jouent"),
+    ("puzzle.black_to_move", "Les noirs jouent"),
+    ("puzzle.rating", "Classement {0}"),
+    ("puzzle.offline", "Problème local, Lichess injoignable"),
+    ("puzzle.loading", "Chargement du problème du jour…"),
+    ("puzzle.streak", "Série : {0} jours"),
+    ("puzzle.streak_best", "Série : {0} jours (record {1})"),
+    ("puzzle.solve", "Résoudre"),
+    ("puzzle.later", "Plus tard"),
+    ("puzzle.solved", "Résolu !"),
+    ("puzzle.solved_revealed", "Résolu avec aide, la série ne change pas"),
+    ("puzzle.find_best", "Trouvez le meilleur coup"),
+    ("puzzle.mistakes", "Essais ratés : {0}"),
+    ("puzzle.open_lichess", "Ouvrir sur Lichess"),
+    ("puzzle.show_move", "Montrer le coup"),
+    ("puzzle.show_move_hint", "Joue le coup suivant de la solution ; le problème du jour ne compte alors plus pour la série"),
+    ("puzzle.close", "Fermer"),
+    ("puzzle.wrong", "Ce n'est pas le bon coup, réessayez"),
+    ("settings.daily_puzzle", "Problème du jour"),
+    ("settings.daily_puzzle_hint", "Proposé au démarrage jusqu'à sa résolution ; les changements s'appliquent au prochain démarrage"),
+    ("broadcast.heading", "Suivre une retransmission"),
+    ("broadcast.url_hint", "URL ou chemin d'un PGN"),
+    ("broadcast.interval", "Vérifier toutes les"),
+    ("broadcast.live_analysis", "Analyser les nouveaux coups avec le moteur"),
+    ("broadcast.follow", "Suivre"),
+    ("broadcast.stop", "Arrêter de suivre"),
+    ("broadcast.game", "Partie {0}"),
+    ("broadcast.error", "Échec de la mise à jour : {0}"),
+    ("broadcast.updated", "Mis à jour il y a {0} s"),
+    ("broadcast.waiting", "En attente de la première mise à jour…"),
+    ("mode.online", "En ligne"),
+    ("online.player_engine", "Moteur"),
+    ("online.player_me", "Moi"),
+    ("online.player", "Coups joués par"),
+    ("online.unauthorized", "Lichess a refusé le jeton ; il lui faut la portée board:play ou bot:play"),
+    ("online.no_token", "Ajoutez un jeton d'API Lichess avec la portée board:play (ou bot:play) dans config.toml sous [lichess] token pour jouer en ligne"),
+    ("online.bot_account", "bot"),
+    ("online.board_account", "joueur"),
+    ("online.connected", "Connecté en tant que {0} ({1})"),
+    ("online.connecting", "Connexion à Lichess…"),
+    ("online.reconnect", "Se reconnecter"),
+    ("online.disconnected", "Connexion perdue : {0}. Nouvelle tentative…"),
+    ("online.stream_closed", "Lichess a fermé la connexion"),
+    ("online.move_rejected", "Lichess a refusé le coup : {0}"),
+    ("online.playing", "Partie contre {0}"),
+    ("online.your_turn", "À vous de jouer"),
+    ("online.their_turn", "En attente de l'adversaire"),
+    ("online.resign", "Abandonner"),
+    ("online.finished", "Dernière partie : {0}"),
+    ("online.seek", "Chercher"),
+    ("online.seek_hint", "Lichess n'apparie les recherches depuis l'échiquier que pour les parties rapides et classiques (8 minutes ou plus)"),
+    ("online.seeking", "Recherche d'un adversaire…"),
+    ("online.challenges", "Défis"),
+    ("online.no_challenges", "Aucun défi en attente"),
+    ("online.rated", "classée"),
+    ("online.casual", "amicale"),
+    ("online.accept", "Accepter"),
+    ("online.decline", "Refuser"),
+    ("online.variant_unsupported", "Seuls les échecs standard sont jouables ici"),
+    ("online.challenged", "{0} vous défie"),
+    ("online.game_over", "La partie en ligne est terminée"),
+    ("online.error", "Lichess : {0}"),
+    ("settings.dgt", "Échiquier électronique"),
+    ("settings.dgt_no_port", "Choisir le port"),
+    ("settings.dgt_connect", "Connecter"),
+    ("settings.dgt_disconnect", "Déconnecter"),
+    ("settings.dgt_connected", "Échiquier connecté sur {0}"),
+    ("settings.dgt_waiting", "En attente de l'échiquier…"),
+    ("settings.dgt_rotated", "Les noirs sont du côté du câble"),
+    ("settings.announce_moves", "Lire à voix haute les coups à jouer sur l'échiquier"),
+    ("settings.announce_moves_hint", "Utilise la synthèse vocale du système (say, spd-say ou espeak)"),
+    ("dgt.make_move", "Jouez {0} sur l'échiquier"),
+    ("dgt.out_of_sync", "L'échiquier ne correspond pas à la position à l'écran"),
+    ("speech.king", "roi"),
+    ("speech.queen", "dame"),
+    ("speech.rook", "tour"),
+    ("speech.bishop", "fou"),
+    ("speech.knight", "cavalier"),
+    ("speech.takes", "prend"),
+    ("speech.promotes", "promeut en"),
+    ("speech.check", "échec"),
+    ("speech.mate", "échec et mat"),
+    ("speech.castle_short", "petit roque"),
+    ("speech.castle_long", "grand roque"),
+    ("report.score", "Partie"),
+    ("report.evaluation", "Évaluation"),
+    ("report.accuracy", "Précision"),
+    ("report.average_loss", "Perte moyenne (cp)"),
+    ("report.inaccuracies", "Imprécisions"),
+    ("report.mistakes", "Erreurs"),
+    ("report.blunders", "Gaffes"),
+    ("report.critical", "Moments critiques"),
+    ("report.no_critical", "Aucune erreur n'a été trouvée."),
+    ("report.loss", "A perdu {0} pions"),
+    ("controls.export_report", "Enregistrer le rapport…"),
+    ("toast.report_saved", "Rapport enregistré dans {0}"),
+    ("toast.report_failed", "Impossible d'enregistrer le rapport : {0}"),
+    ("settings.backup", "Sauvegarde"),
+    ("settings.backup_create", "Sauvegarder…"),
+    ("settings.backup_restore", "Restaurer…"),
+    ("settings.backup_hint", "Enregistre les études, les parties importées, le fichier de configuration et les réglages dans un fichier ZIP"),
+    ("toast.backup_saved", "Sauvegarde enregistrée dans {0}"),
+    ("toast.backup_failed", "Impossible d'enregistrer la sauvegarde : {0}"),
+    ("toast.restore_failed", "Impossible de restaurer la sauvegarde : {0}"),
+    ("toast.backup_restored", "Sauvegarde restaurée"),
+    ("analysis.open_lichess", "Ouvrir dans Lichess"),
+    ("analysis.open_lichess_hint", "Ouvre la partie sur l'échiquier d'analyse de Lichess dans le navigateur, pour l'analyse dans le cloud"),
+    ("engine_log.title", "Journal du moteur"),
+    ("engine_log.copy", "Copier"),
+    ("engine_log.clear", "Effacer"),
+    ("engine_log.empty", "Le moteur n'a signalé aucune erreur ni sortie sur stderr."),
+    ("settings.engine_log", "Journal"),
+    ("toast.show_engine_log", "Voir le journal"),
+    ("settings.power", "Économie d'énergie"),
+    ("settings.power_pause_unfocused", "Suspendre l'analyse en arrière-plan"),
+    ("settings.power_pause_on_battery", "Suspendre l'analyse sur batterie"),
+    ("settings.power_cpu_limit", "Limite CPU"),
+    ("settings.power_cpu_limit_hint", "Part des cœurs utilisée par le moteur en arrière-plan ou sur batterie"),
+    ("analysis.power_paused", "Suspendue pour économiser l'énergie, reprend quand la fenêtre est active ou l'ordinateur en charge"),
+    ("settings.study_format", "Fichiers d'étude"),
+    ("settings.study_format_hint", "Les fichiers compacts sont bien plus petits et rapides pour les grands répertoires ; les études dans l'un ou l'autre format peuvent toujours être ouvertes"),
+    ("study.format_json", "JSON (lisible)"),
+    ("study.format_compact", "Compact (binaire)"),
+    ("controls.review_game", "🔍 Analyser la partie"),
+    ("controls.review_game_hint", "Le moteur évalue chaque coup qui n'a pas encore d'évaluation"),
+    ("controls.review_progress", "Analyse {0}/{1}"),
+    ("controls.review_cancel", "Annuler"),
+    ("toast.review_done", "Analyse de la partie terminée"),
+    ("settings.engine_hash", "Hash du moteur"),
+    ("settings.keep_hash", "Conserver entre les positions d'une même partie"),
+    ("settings.keep_hash_hint", "Le moteur réutilise ce qu'il a trouvé pour les positions proches, le parcours d'une partie est donc plus rapide ; les autres positions repartent de zéro"),
+    ("analysis.clear_hash", "Vider le hash"),
+    ("analysis.clear_hash_hint", "Oublier les recherches précédentes et évaluer cette position de zéro"),
+    ("games.heading", "Mes parties"),
+    ("games.empty", "Aucune partie importée"),
+    ("games.untitled", "Partie sans titre"),
+    ("games.open_hint", "Ouvrir dans l'analyse"),
+    ("games.more", "{0} autres parties, affinez le filtre pour les voir"),
+    ("export.heading", "Exporter les parties"),
+    ("export.all_games", "📄 Exporter toutes les parties"),
+    ("export.include_my_games", "Inclure mes parties importées"),
+    ("export.include_my_games_hint", "Ajouter les parties importées dont le joueur, l'événement, la date ou une autre balise contient le filtre"),
+    ("export.filter_hint", "Filtre, par ex. un joueur ou 2024"),
+    ("toast.no_games_to_export", "Aucune partie à exporter pour l'instant"),
+    ("toast.games_exported", "{0} parties exportées dans {1}"),
+    ("toast.games_export_failed", "Impossible d'exporter les parties : {0}"),
+    ("board.move_slider_hint", "Faire glisser pour parcourir la partie coup par coup"),
+    ("analysis.stable", "stable"),
+    ("analysis.settling", "se stabilise"),
+    ("analysis.swinging", "fluctue"),
+    ("analysis.stability_hint", "À quel point l'évaluation a changé sur les dernières profondeurs"),
+    ("critical.heading", "Moments critiques"),
+    ("critical.missed_win", "gain manqué"),
+    ("critical.swing", "tournant"),
+    ("critical.jump_hint", "Aller à la position avant ce coup"),
+    ("study.snippet_idea", "avec l'idée de…"),
+    ("study.snippet_only_move", "le seul coup"),
+    ("study.snippet_better_was", "mieux valait…"),
+    ("study.snippet_threatening", "menaçant…"),
+    ("study.snippet_plan", "le plan est…"),
+    ("glyph.good", "Bon coup"),
+    ("glyph.mistake", "Erreur"),
+    ("glyph.brilliant", "Coup brillant"),
+    ("glyph.blunder", "Gaffe"),
+    ("glyph.interesting", "Coup intéressant"),
+    ("glyph.dubious", "Coup douteux"),
+    ("glyph.equal", "Position égale"),
+    ("glyph.unclear", "Position peu claire"),
+    ("glyph.white_slightly", "Les Blancs sont légèrement mieux"),
+    ("glyph.black_slightly", "Les Noirs sont légèrement mieux"),
+    ("glyph.white_better", "Les Blancs sont mieux"),
+    ("glyph.black_better", "Les Noirs sont mieux"),
+    ("glyph.white_winning", "Les Blancs gagnent"),
+    ("glyph.black_winning", "Les Noirs gagnent"),
+    ("glyph.only_move", "Coup unique"),
+    ("glyph.zugzwang", "Zugzwang"),
+    ("glyph.development", "Avance de développement"),
+    ("glyph.initiative", "Initiative"),
+    ("glyph.attack", "Attaque"),
+    ("glyph.compensation", "Compensation pour le matériel"),
+    ("glyph.counterplay", "Contre-jeu"),
+    ("glyph.time_trouble", "Zeitnot"),
+    ("glyph.with_idea", "Avec l'idée"),
+    ("glyph.novelty", "Nouveauté"),
+    ("moves.book_exit", "Premier coup hors de la théorie des ouvertures"),
+    ("quality.brilliant", "Brillants : sacrifices qui ont gardé l'évaluation"),
+    ("quality.good", "Bons coups"),
+    ("quality.inaccuracies", "Imprécisions"),
+    ("quality.mistakes", "Erreurs"),
+    ("quality.blunders", "Gaffes"),
+    ("endgame.heading", "Entraînement aux finales"),
+    ("endgame.play_hint", "Jouer cette position contre le moteur à pleine puissance"),
+    ("endgame.kq_k", "Dame contre roi"),
+    ("endgame.kr_k", "Tour contre roi"),
+    ("endgame.kp_k", "Roi et pion contre roi"),
+    ("endgame.lucena", "Position de Lucena"),
+    ("endgame.philidor", "Défense Philidor"),
+    ("endgame.krb_kr", "Tour et fou contre tour (défendre)"),
+    ("endgame.kbn_k", "Mat du fou et du cavalier"),
+    ("endgame.goal", "Tablebase : {0}"),
+    ("endgame.goal_win", "gagner"),
+    ("endgame.goal_draw", "tenir la nulle"),
+    ("endgame.goal_loss", "perdu avec le meilleur jeu, résistez au maximum"),
+    ("endgame.looking_up", "Consultation de la tablebase..."),
+    ("endgame.fetch_failed", "Tablebase injoignable, le résultat ne peut pas être vérifié"),
+    ("endgame.full_strength", "Le moteur joue à pleine puissance"),
+    ("toast.endgame_held", "{0} : résultat de la tablebase atteint"),
+    ("toast.endgame_missed", "{0} : résultat inférieur à celui de la tablebase, réessayez"),
+    ("tournament.heading", "Tournoi de moteurs"),
+    ("tournament.levels", "Niveaux de difficulté :"),
+    ("tournament.second_engine_hint", "Chemin d'un second moteur (facultatif)"),
+    ("tournament.double_round", "Jouer chaque appariement avec les deux couleurs"),
+    ("tournament.movetime", "Temps par coup :"),
+    ("tournament.start", "▶ Lancer le tournoi"),
+    ("tournament.stop", "⏹ Arrêter"),
+    ("tournament.need_two", "Choisissez au moins deux joueurs"),
+    ("tournament.export", "📄 Exporter les parties"),
+    ("tournament.progress", "Partie {0} / {1}"),
+    ("tournament.failed", "Tournoi interrompu : {0}"),
+    ("tournament.score", "Points"),
+    ("tournament.elo", "Elo"),
+    ("tournament.elo_hint", "Écart de classement estimé avec l'adversaire moyen, d'après le score"),
+    ("tournament.engine_gone", "Un moteur s'est arrêté pendant le tournoi"),
+    ("tournament.no_engine", "Aucun moteur trouvé pour le tournoi"),
+    ("toast.tournament_finished", "Tournoi terminé après {0} parties"),
+    ("plan.heading", "Plan d'entraînement"),
+    ("plan.puzzles", "Problèmes"),
+    ("plan.puzzles_hint", "Problèmes résolus sans afficher la solution"),
+    ("plan.lines", "Lignes de répertoire"),
+    ("plan.lines_hint", "Lignes d'étude parcourues jusqu'au dernier coup"),
+    ("plan.reviews", "Analyses de parties"),
+    ("plan.reviews_hint", "Analyses d'une partie par le moteur menées à terme"),
+    ("plan.done_today", "✔ Objectifs du jour atteints"),
+    ("plan.streak", "Série : {0} jours (record {1})"),
+    ("toast.plan_met", "Objectifs d'entraînement du jour atteints"),
+    ("speech.pawn", "pion"),
+    ("coach.hanging", "Votre pièce en {1} ({0}) est attaquée et non défendue"),
+    ("coach.attacked_by_lesser", "Votre pièce en {1} ({0}) est attaquée par : {2}"),
+    ("coach.castle", "Le moteur veut roquer"),
+    ("coach.promote", "Le moteur veut promouvoir en {0}"),
+    ("coach.capture", "Le moteur veut prendre en {1} ({0})"),
+    ("coach.check", "Le moteur veut donner échec ({0})"),
+    ("coach.open_file", "Le moteur veut ouvrir la colonne {0}"),
+    ("coach.piece_move", "Le moteur veut jouer en {1} ({0})"),
+    ("coach.pawn_push", "Le moteur veut pousser le pion en {0}"),
+    ("settings.coach", "Entraîneur :"),
+    ("settings.coach_hints", "Expliquer l'évaluation"),
+    ("settings.coach_hints_hint", "Afficher des indications en mots sous la barre d'évaluation, comme les pièces en danger et l'idée du meilleur coup du moteur"),
+    ("settings.promotion", "Promotion :"),
+    ("settings.auto_queen", "Toujours promouvoir en dame"),
+    ("settings.auto_queen_hint", "Passer le choix de la pièce. Maintenez N, R ou B en lâchant le pion pour promouvoir en cavalier, tour ou fou"),
+    ("premove.heading", "Précoups"),
+    ("premove.heading_hint", "Coups joués pour vous dès que le moteur répond. Choisissez-en un sur l'échiquier pendant que le moteur réfléchit, ou saisissez une ligne de ses réponses attendues et des vôtres."),
+    ("premove.line_hint", "p. ex. Nxe4 Nxe4 Bxe4 Qxe4"),
+    ("premove.set", "Mettre en file"),
+    ("premove.empty", "Rien en file"),
+    ("premove.conditional", "si {0} → {1}"),
+    ("premove.any_reply", "après toute réponse → {0}"),
+    ("premove.clear", "Effacer les précoups"),
+    ("toast.premove_illegal", "Précoup devenu illégal, file vidée"),
+    ("toast.premove_cancelled", "Le moteur a joué autre chose, précoups effacés"),
+    ("toast.premove_invalid", "Impossible de mettre cette ligne en file : {0}"),
+    ("settings.variety", "Variété d'ouvertures"),
+    ("settings.variety_enabled", "Varier les premiers coups du moteur"),
+    ("settings.variety_hint", "Le moteur choisit ses premiers coups au hasard parmi les lignes presque aussi bonnes que la meilleure, pour que les parties au même niveau ne commencent pas toutes pareil"),
+    ("settings.variety_moves", "Coups"),
+    ("settings.variety_margin", "Écart"),
+    ("settings.variety_margin_hint", "De combien de centipions un coup peut être moins bon que la meilleure ligne pour être choisi"),
+    ("controls.undo_left", "↩ Annuler le coup (reste {0})"),
+    ("controls.takebacks", "Reprises de coups"),
+    ("controls.takebacks_locked", "Les reprises de coups se modifient avant le premier coup ou après la partie"),
+    ("takeback.unlimited", "Illimitées"),
+    ("takeback.per_game", "{0} par partie"),
+    ("takeback.off", "Aucune"),
+    ("resign.confirm", "Abandonner cette partie ?"),
+    ("resign.lost", "Votre position semble perdue"),
+    ("resign.lost_detail", "Le moteur l'évalue à {0} depuis plusieurs coups. Les joueurs forts abandonnent ici."),
+    ("resign.resign", "Abandonner"),
+    ("resign.play_on", "Continuer"),
+    ("settings.auto_resign", "Abandon automatique"),
+    ("settings.auto_resign_enabled", "Proposer d'abandonner les parties perdues"),
+    ("settings.auto_resign_hint", "Demande s'il faut abandonner quand l'évaluation du moteur reste sous le seuil pendant plusieurs de ses coups, pour apprendre à reconnaître une partie perdue"),
+    ("settings.auto_resign_threshold", "Retard de"),
+    ("settings.auto_resign_moves", "Pendant coups"),
+    ("settings.engine_roles", "Moteurs par mode"),
+    ("settings.engine_roles_hint", "Utiliser des moteurs différents pour jouer en mode Partie, analyser et revoir les parties, et limiter les threads de chacun. Chaque moteur reste lancé pendant qu'un autre mode est utilisé. Les chemins vides utilisent le moteur ci-dessus, ou pour les revues le moteur en cours."),
+    ("settings.engine_role_game", "Partie"),
+    ("settings.engine_role_analysis", "Analyse"),
+    ("settings.engine_role_default", "Moteur ci-dessus"),
+    ("settings.engine_role_review", "Revue de partie"),
+    ("settings.engine_role_review_hint", "Moteur chargé"),
+    ("settings.engine_threads_any", "Tous les threads"),
+    ("settings.engine_threads", "Jusqu'à {0} threads"),
+    ("settings.uci_console", "Console UCI"),
+    ("settings.uci_console_hint", "Envoyer des commandes UCI brutes au moteur en cours"),
+    ("console.title", "Console UCI"),
+    ("console.hint", "↑/↓ rappellent les commandes précédentes"),
+    ("console.input_hint", "Commande UCI, p. ex. d ou eval"),
+    ("console.warn_search", "Lancer ou arrêter une recherche ici peut perturber les parties et analyses en cours"),
+    ("console.warn_position", "Change la position du moteur à l'insu de l'application jusqu'à sa prochaine recherche"),
+    ("console.warn_handshake", "L'application a déjà fait l'initialisation ; la réponse peut être confondue avec d'autres sorties"),
+    ("console.warn_quit", "Arrête le moteur ; relancez-le depuis les réglages"),
+    ("console.warn_option", "L'application règle cette option elle-même et ne saura pas qu'elle a changé"),
+    ("analysis.exclude", "Exclure ce coup et relancer la recherche, pour voir le meilleur plan suivant"),
+    ("analysis.excluded", "Exclus :"),
+    ("analysis.restore_move", "Rechercher de nouveau ce coup"),
+    ("settings.check_moves", "Vérifier mes coups avant de les jouer"),
+    ("settings.check_moves_hint", "En mode Partie, un coup joué sur l'échiquier attend que le moteur calcule combien il perd par rapport au meilleur coup"),
+    ("staged.move", "Votre coup : {}"),
+    ("staged.checking", "Vérification…"),
+    ("staged.no_loss", "Aussi bon que le choix du moteur"),
+    ("staged.cost", "Perd {} pions par rapport au meilleur coup"),
+    ("staged.play", "Le jouer"),
+    ("staged.cancel", "En choisir un autre"),
+    ("phase.opening", "Ouverture"),
+    ("phase.middlegame", "Milieu de jeu"),
+    ("phase.endgame", "Finale"),
+    ("report.by_phase", "Perte moyenne par phase (cp)"),
+    ("quality.by_phase", "Perte moyenne par phase de la partie, en centipions"),
+    ("coverage.open", "Couverture du répertoire…"),
+    ("coverage.open_hint", "Comparer vos parties avec le répertoire de vos études"),
+    ("coverage.title", "Couverture du répertoire"),
+    ("coverage.empty", "Vos parties restent dans votre répertoire. Les études vues des Blancs ou des Noirs comptent comme répertoire de ce camp."),
+    ("coverage.suggestions", "À apprendre ensuite"),
+    ("coverage.misplayed", "Lignes mal jouées"),
+    ("coverage.uncovered", "Réponses que votre répertoire ne couvre pas"),
+    ("coverage.none", "Aucune"),
+    ("coverage.open_line", "Afficher sur l'échiquier"),
+    ("coverage.games", "{0} parties, {1} perdues"),
+    ("coverage.prepared", "préparé : {0}"),
+    ("compare.open", "⚖ Comparer"),
+    ("compare.open_hint", "Afficher la position à côté de la fin de la ligne du moteur ou d'une position épinglée"),
+    ("compare.title", "Comparer des positions"),
+    ("compare.current", "Échiquier"),
+    ("compare.engine_line", "Après la ligne du moteur"),
+    ("compare.pinned", "Position épinglée"),
+    ("compare.pin", "📌 Épingler cette position"),
+    ("compare.pin_hint", "Comparer les positions suivantes à celle-ci"),
+    ("compare.unpin", "Désépingler"),
+    ("compare.pawns_only", "Pions seulement"),
+    ("compare.pawns_only_hint", "N'encadrer que les cases où la structure de pions diffère"),
+    ("compare.no_target", "Lancez l'analyse ou épinglez une position à comparer."),
+    ("compare.differences", "{0} cases diffèrent"),
+    ("share.copy_link", "Copier le lien de la position"),
+    ("share.copy_link_hint", "Copier un lien vers l'échiquier d'analyse Lichess avec la position affichée"),
+    ("share.copy_pgn", "Copier la PGN annotée"),
+    ("share.copy_pgn_hint", "Copier la partie en PGN, avec les lignes actuelles du moteur en commentaire à la position affichée"),
+    ("toast.link_copied", "Lien copié dans le presse-papiers"),
+    ("print.after", "Après {0}"),
+    ("study.print", "Imprimer le chapitre…"),
+    ("study.print_hint", "Enregistrer le chapitre en page prête à imprimer ou à enregistrer en PDF depuis le navigateur, avec diagrammes, variantes et commentaires"),
+    ("study.print_every", "Diagramme tous les"),
+    ("study.print_moves", "coups"),
+    ("share.latex_diagram", "Copier le diagramme LaTeX"),
+    ("share.latex_diagram_hint", "Copier la position comme extrait du paquet chessboard pour documents LaTeX"),
+    ("share.latex_line", "Copier la ligne LaTeX"),
+    ("share.latex_line_hint", "Copier les coups jusqu'à la position affichée comme extrait xskak, suivi d'un diagramme"),
+    ("toast.latex_copied", "LaTeX copié dans le presse-papiers"),
+    ("import.summary_archive", "{0} est une archive ; ses parties sont lues pendant l'import."),
+    ("toast.duplicate_line", "Cette ligne est déjà dans le chapitre « {0} »"),
+    ("toast.transposed_line", "Le chapitre « {0} » atteint déjà cette position par une autre interversion"),
+    ("toast.go_to_duplicate", "Y aller plutôt"),
+    ("study.drill", "Entraîner ce chapitre"),
+    ("study.drill_hint", "Jouer les lignes du chapitre de son côté ; les réponses de l'adversaire sont tirées au hasard"),
+    ("study.drill_stop", "Arrêter l'entraînement"),
+    ("study.weak_spots", "Points faibles"),
+    ("study.weak_spots_none", "Les positions ratées à l'entraînement apparaissent ici"),
+    ("study.weak_spot_recall", "{0}/{1} retrouvés"),
+    ("drill.wrong", "Ce n'est pas le coup de votre répertoire, réessayez (encore {0} essais)"),
+    ("drill.finished", "Fin de la ligne"),
+    ("drill.nothing", "Ce chapitre n'a aucun coup de son côté à entraîner"),
+    ("drill.time_out", "Temps écoulé pour ce coup"),
+    ("drill.failed", "Trop d'erreurs, nouvelle ligne"),
+    ("drill.hint", "Coup du répertoire : {0}"),
+    ("drill.summary_title", "Résultats de l'entraînement"),
+    ("drill.summary_score", "Score : {0} %"),
+    ("drill.summary_passed", "Lignes réussies"),
+    ("drill.summary_failed", "Lignes échouées"),
+    ("drill.summary_first_try", "Coups trouvés du premier coup"),
+    ("drill.summary_positions", "Coups demandés"),
+    ("drill.summary_mistakes", "Erreurs"),
+    ("drill.summary_timeouts", "Temps écoulé"),
+    ("drill.summary_hints", "Indices utilisés"),
+    ("study.drill_mistakes", "Erreurs dans cette ligne : {0}/{1}"),
+    ("study.drill_time", "encore {0} s"),
+    ("study.drill_hint_button", "Indice"),
+    ("settings.drill", "Entraînement du répertoire"),
+    ("settings.drill_move_seconds", "Temps par coup"),
+    ("settings.drill_move_seconds_hint", "0 pour aucune limite"),
+    ("settings.drill_mistakes", "Erreurs permises par ligne"),
+    ("settings.drill_hints", "Autoriser les indices"),
+    ("audit.move_loses", "Moteur : {0} cp de moins que son meilleur coup"),
+    ("audit.bad_ending", "Moteur : la ligne finit à {0} pour votre camp"),
+    ("audit.done", "Vérification par le moteur terminée, {0} avertissement(s)"),
+    ("audit.no_engine", "Le moteur n'est pas prêt à vérifier le chapitre"),
+    ("audit.busy", "Attendez que le moteur termine son analyse en cours"),
+    ("audit.progress", "Vérification du chapitre par le moteur… {0}/{1}"),
+    ("audit.cancel", "Annuler"),
+    ("audit.cancelled", "Vérification par le moteur annulée"),
+    ("audit.stale", "L'étude a changé pendant la vérification du moteur, les résultats ont été abandonnés"),
+    ("annotate.progress", "Annotation : {0}/{1} positions"),
+    ("annotate.done", "Annotation terminée : {0} erreurs marquées"),
+    ("annotate.cancelled", "Annotation annulée"),
+    ("annotate.nothing", "Le chapitre n'a aucun coup à annoter"),
+    ("annotate.better", "Mieux valait {0}"),
+    ("study.audit", "Vérifier avec le moteur"),
+    ("study.audit_hint", "Signale les coups de votre camp qui perdent plus que le seuil face au meilleur coup du moteur, et les lignes qui finissent dans de mauvaises positions"),
+    ("study.audit_threshold", "Seuil"),
+    ("study.lock_hint", "Verrouiller l'étude : elle peut toujours être parcourue et travaillée, mais pas modifiée"),
+    ("study.locked", "Étude en lecture seule"),
+    ("study.locked_title", "Étude verrouillée"),
+    ("study.locked_prompt", "Cette étude est verrouillée. Créer une copie personnelle pour la modifier ?"),
+    ("study.make_copy", "Créer une copie"),
+    ("study.copy_name", "{0} (copie)"),
+    ("study.paste_pgn", "📋 Coller le PGN comme chapitre"),
+    ("study.paste_pgn_hint", "Ajouter la partie du presse-papiers, avec ses variantes et commentaires, comme nouveau chapitre"),
+    ("study.headers", "En-têtes PGN"),
+    ("study.header_name", "Balise"),
+    ("study.header_value", "Valeur"),
+    ("study.header_remove", "Retirer cette balise"),
+    ("study.annotate", "Annoter la partie"),
+    ("study.annotate_hint", "Faire évaluer chaque coup de la ligne principale par le moteur, en marquant les erreurs d'un symbole et de la meilleure suite"),
+    ("settings.clicks", "Clics"),
+    ("settings.own_piece", "Clic sur une autre de mes pièces :"),
+    ("settings.own_piece_switch", "La sélectionne"),
+    ("settings.own_piece_move", "Tente le coup"),
+    ("settings.deselect_selected", "Cliquer sur la pièce sélectionnée la désélectionne"),
+    ("settings.confirm_destination", "Confirmer les coups par un second clic"),
+    ("settings.confirm_destination_hint", "Le premier clic sur une case d'arrivée la marque, le second joue le coup"),
+    ("settings.engine_moves", "Coups du moteur"),
+    ("settings.engine_move_highlight", "Surligner les coups du moteur dans leur propre couleur"),
+    ("settings.engine_move_sound", "Jouer un son quand le moteur joue"),
+];
@@ -0,0 +1,149 @@
+mod de;
+mod en;
+mod es;
+mod fr;
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Languages the UI can be displayed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+impl Language {
+    pub fn all() -> &'static [Language] {
+        &[
+            Language::English,
+            Language::Spanish,
+            Language::German,
+            Language::French,
+        ]
+    }
+
+    /// Name of the language in that language, for the selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        }
+    }
+
+    fn bundle(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => en::STRINGS,
+            Language::Spanish => es::STRINGS,
+            Language::German => de::STRINGS,
+            Language::French => fr::STRINGS,
+        }
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self {
+            Language::English => '.',
+            Language::Spanish | Language::German | Language::French => ',',
+        }
+    }
+
+    fn date_format(&self) -> &'static str {
+        match self {
+            Language::English => "%Y-%m-%d",
+            Language::Spanish | Language::French => "%d/%m/%Y",
+            Language::German => "%d.%m.%Y",
+        }
+    }
+}
+
+/// The active UI language, shared by every widget
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    let idx = Language::all().iter().position(|l| *l == language).unwrap_or(0);
+    CURRENT.store(idx as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::all()
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Look up a UI string in the active language, falling back to English and then the key
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(language(), key)
+        .or_else(|| lookup(Language::English, key))
+        .unwrap_or(key)
+}
+
+/// Look up a UI string and substitute `{0}`, `{1}`, ... with the given arguments
+pub fn tr_args(key: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut text = tr(key).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    text
+}
+
+fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    language
+        .bundle()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| *v)
+}
+
+/// Format a move number ("12.") for display
+pub fn format_move_number(number: usize) -> String {
+    tr_args("format.move_number", &[&number])
+}
+
+/// Format a decimal number with the active locale's separator
+pub fn format_decimal(value: f32, precision: usize) -> String {
+    let text = format!("{:.*}", precision, value);
+    let separator = language().decimal_separator();
+    if separator == '.' {
+        text
+    } else {
+        text.replace('.', &separator.to_string())
+    }
+}
+
+/// Format a date for display in the active locale
+pub fn format_date<Tz: chrono::TimeZone>(date: &chrono::DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    date.format(language().date_format()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundles_cover_english_keys() {
+        for language in Language::all() {
+            for (key, _) in en::STRINGS {
+                assert!(
+                    lookup(*language, key).is_some(),
+                    "{:?} is missing '{}'",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_key_falls_back_to_key() {
+        assert_eq!(tr("no.such.key"), "no.such.key");
+    }
+}
@@ -1,13 +1,38 @@
 mod app;
+mod backup;
+mod broadcast;
+mod cli;
+mod config;
+mod dgt;
 mod engine;
+mod error;
+mod events;
+mod explorer;
 mod game;
+mod i18n;
+mod online;
+mod power;
 mod study;
+mod tournament;
+mod training;
 mod ui;
 
 use anyhow::Result;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn main() -> Result<()> {
+    let launch = match cli::LaunchOptions::parse(std::env::args().skip(1)) {
+        Ok(launch) if launch.help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(launch) => launch,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
     // Initialize tracing
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer())
@@ -16,18 +41,24 @@ fn main() -> Result<()> {
 
     tracing::info!("Starting Stockfish Chess");
 
+    // Set before anything reads or writes the data directory
+    if let Some(dir) = launch.data_dir.clone().or_else(|| config::portable_dir(launch.portable)) {
+        config::set_data_dir(dir);
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([900.0, 700.0])
             .with_min_inner_size([600.0, 500.0])
             .with_title("Stockfish Chess"),
+        persistence_path: config::settings_file(),
         ..Default::default()
     };
 
     eframe::run_native(
         "Stockfish Chess",
         native_options,
-        Box::new(|cc| Ok(Box::new(app::ChessApp::new(cc)))),
+        Box::new(|cc| Ok(Box::new(app::ChessApp::new(cc, launch)))),
     )
     .map_err(|e| anyhow::anyhow!("eframe error: {}", e))
 }
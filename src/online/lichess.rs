@@ -0,0 +1,271 @@
+use crate::config::USER_AGENT;
+use crate::game::PlayerColor;
+use crate::i18n::tr;
+use crate::online::{Challenge, OnlineEvent, RemotePlayer, RemoteState};
+use serde::Deserialize;
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const API: &str = "https://lichess.org/api";
+
+/// Send an authorized request, turning a 401 into a hint about the token
+fn call(request: ureq::Request, token: &str) -> Result<ureq::Response, String> {
+    match request
+        .set("User-Agent", USER_AGENT)
+        .set("Authorization", &format!("Bearer {}", token))
+        .call()
+    {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(401, _)) => Err(tr("online.unauthorized").to_string()),
+        Err(ureq::Error::Status(_, response)) => Err(response.into_string().unwrap_or_default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// POST without a body, e.g. to accept a challenge or play a move
+pub fn post(path: &str, token: &str) -> Result<(), String> {
+    call(ureq::post(&format!("{}/{}", API, path)), token).map(|_| ())
+}
+
+/// Look for an opponent with the Board API. Lichess keeps the seek open until the
+/// request ends, so this blocks until a game starts.
+pub fn seek(token: &str, minutes: u32, increment: u32) -> Result<(), String> {
+    ureq::post(&format!("{}/board/seek", API))
+        .set("User-Agent", USER_AGENT)
+        .set("Authorization", &format!("Bearer {}", token))
+        .send_form(&[
+            ("time", &minutes.to_string()),
+            ("increment", &increment.to_string()),
+            ("rated", "false"),
+        ])
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Deserialize)]
+struct Account {
+    username: String,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+/// The user's name, and whether the account is a bot
+pub fn account(token: &str) -> Result<(String, bool), String> {
+    let account: Account = call(ureq::get(&format!("{}/account", API)), token)?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    Ok((account.username, account.title.as_deref() == Some("BOT")))
+}
+
+/// Read a newline-delimited JSON stream, handing each parsed line to `forward` until the
+/// stream goes away, `forward` returns false or `stop` is set. Empty lines are keep-alives,
+/// which Lichess sends every few seconds, so a stop is noticed soon.
+pub fn stream(
+    path: &str,
+    token: &str,
+    parse: fn(&str) -> Option<OnlineEvent>,
+    stop: &AtomicBool,
+    forward: &mut dyn FnMut(OnlineEvent) -> bool,
+) -> Result<(), String> {
+    let response = call(ureq::get(&format!("{}/{}", API, path)), token)?;
+    for line in std::io::BufReader::new(response.into_reader()).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(event) = parse(&line) {
+            if !forward(event) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum IncomingEvent {
+    Challenge { challenge: ChallengeJson },
+    ChallengeCanceled { challenge: ChallengeJson },
+    ChallengeDeclined { challenge: ChallengeJson },
+    GameStart { game: GameRef },
+    GameFinish { game: GameRef },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct ChallengeJson {
+    id: String,
+    #[serde(default)]
+    challenger: Option<PlayerJson>,
+    #[serde(default)]
+    rated: bool,
+    #[serde(default)]
+    variant: VariantJson,
+    #[serde(rename = "timeControl", default)]
+    time_control: TimeControlJson,
+}
+
+#[derive(Deserialize, Default)]
+struct VariantJson {
+    key: String,
+}
+
+#[derive(Deserialize, Default)]
+struct TimeControlJson {
+    /// "5+3", absent for unlimited games
+    show: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GameRef {
+    #[serde(rename = "gameId")]
+    game_id: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PlayerJson {
+    id: Option<String>,
+    name: Option<String>,
+    rating: Option<u32>,
+    #[serde(rename = "aiLevel")]
+    ai_level: Option<u32>,
+}
+
+impl From<PlayerJson> for RemotePlayer {
+    fn from(player: PlayerJson) -> Self {
+        let name = match (player.name, player.ai_level) {
+            (Some(name), _) => name,
+            (None, Some(level)) => format!("Stockfish {}", level),
+            (None, None) => "?".to_string(),
+        };
+        Self {
+            id: player.id.unwrap_or_default(),
+            name,
+            rating: player.rating,
+        }
+    }
+}
+
+/// A line of the account event stream
+pub fn parse_event(line: &str) -> Option<OnlineEvent> {
+    let event = match serde_json::from_str(line) {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::warn!("Unreadable Lichess event {}: {}", line, e);
+            return None;
+        }
+    };
+    Some(match event {
+        IncomingEvent::Challenge { challenge } => OnlineEvent::Challenge(Challenge {
+            id: challenge.id,
+            challenger: challenge.challenger.map(RemotePlayer::from).unwrap_or_default(),
+            rated: challenge.rated,
+            variant: challenge.variant.key,
+            time_control: challenge.time_control.show.unwrap_or_else(|| "∞".to_string()),
+        }),
+        IncomingEvent::ChallengeCanceled { challenge } | IncomingEvent::ChallengeDeclined { challenge } => {
+            OnlineEvent::ChallengeGone(challenge.id)
+        }
+        IncomingEvent::GameStart { game } => OnlineEvent::GameStart(game.game_id),
+        IncomingEvent::GameFinish { game } => OnlineEvent::GameFinish(game.game_id),
+        IncomingEvent::Other => return None,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum GameStreamLine {
+    GameFull {
+        id: String,
+        white: PlayerJson,
+        black: PlayerJson,
+        #[serde(rename = "initialFen")]
+        initial_fen: String,
+        state: StateJson,
+    },
+    GameState(StateJson),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StateJson {
+    /// Moves so far in UCI, separated by spaces
+    moves: String,
+    /// Remaining time in milliseconds
+    wtime: u64,
+    btime: u64,
+    status: String,
+    winner: Option<String>,
+}
+
+impl From<StateJson> for RemoteState {
+    fn from(state: StateJson) -> Self {
+        Self {
+            moves: state.moves.split_whitespace().map(str::to_string).collect(),
+            white_ms: state.wtime,
+            black_ms: state.btime,
+            status: state.status,
+            winner: match state.winner.as_deref() {
+                Some("white") => Some(PlayerColor::White),
+                Some("black") => Some(PlayerColor::Black),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A line of a game stream
+pub fn parse_game_line(line: &str) -> Option<OnlineEvent> {
+    match serde_json::from_str(line) {
+        Ok(GameStreamLine::GameFull { id, white, black, initial_fen, state }) => Some(OnlineEvent::GameFull {
+            id,
+            white: white.into(),
+            black: black.into(),
+            initial_fen: (initial_fen != "startpos").then_some(initial_fen),
+            state: state.into(),
+        }),
+        Ok(GameStreamLine::GameState(state)) => Some(OnlineEvent::GameState(state.into())),
+        Ok(GameStreamLine::Other) => None,
+        Err(e) => {
+            tracing::warn!("Unreadable Lichess game line {}: {}", line, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_lines() {
+        let challenge = r#"{"type":"challenge","challenge":{"id":"abc","challenger":{"id":"bob","name":"Bob","rating":1800},"rated":true,"variant":{"key":"standard"},"speed":"blitz","timeControl":{"type":"clock","limit":300,"increment":3,"show":"5+3"}}}"#;
+        let Some(OnlineEvent::Challenge(challenge)) = parse_event(challenge) else {
+            panic!("not a challenge");
+        };
+        assert_eq!((challenge.challenger.name.as_str(), challenge.time_control.as_str()), ("Bob", "5+3"));
+        assert!(challenge.is_standard());
+        assert!(parse_event(r#"{"type":"somethingNew"}"#).is_none());
+
+        let full = r#"{"type":"gameFull","id":"g1","white":{"id":"me","name":"Me"},"black":{"aiLevel":3},"initialFen":"startpos","state":{"type":"gameState","moves":"e2e4 e7e5","wtime":299000,"btime":300000,"status":"started"}}"#;
+        let Some(OnlineEvent::GameFull { black, initial_fen, state, .. }) = parse_game_line(full) else {
+            panic!("not a full game");
+        };
+        assert_eq!((black.name.as_str(), initial_fen), ("Stockfish 3", None));
+        assert_eq!(state.moves, ["e2e4", "e7e5"]);
+
+        let over = r#"{"type":"gameState","moves":"f2f3 e7e5 g2g4 d8h4","wtime":1,"btime":2,"status":"mate","winner":"black"}"#;
+        let Some(OnlineEvent::GameState(state)) = parse_game_line(over) else {
+            panic!("not a game state");
+        };
+        assert_eq!((state.status.as_str(), state.winner), ("mate", Some(PlayerColor::Black)));
+    }
+}
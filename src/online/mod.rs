@@ -0,0 +1,492 @@
+mod lichess;
+
+use crate::game::{GameError, GameState, PgnGame, PlayerColor};
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+const ANALYSIS_URL: &str = "https://lichess.org/analysis";
+/// Wait before reconnecting a dropped stream, doubled after each failed attempt up to the maximum
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+const STANDARD_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Percent-encode everything but unreserved characters, for use in a URL path
+fn url_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The Lichess analysis board with `game` loaded, opened at the move being viewed and seen
+/// from `orientation`. A position without moves is passed as FEN, anything else as PGN.
+pub fn analysis_url(game: &GameState, orientation: PlayerColor) -> String {
+    let color = match orientation {
+        PlayerColor::White => "white",
+        PlayerColor::Black => "black",
+    };
+    let start = game.starting_fen();
+    if game.move_history().is_empty() {
+        return format!("{}/standard/{}?color={}", ANALYSIS_URL, url_encode(&start).replace("%20", "_"), color);
+    }
+
+    let mut pgn = PgnGame {
+        headers: Vec::new(),
+        moves: game.move_history().iter().map(|m| m.san.clone()).collect(),
+        ..Default::default()
+    };
+    if start != STANDARD_FEN {
+        pgn.headers.push(("FEN".to_string(), start.clone()));
+        pgn.headers.push(("SetUp".to_string(), "1".to_string()));
+    }
+    // Lichess counts plies from the start of the game, not of the position
+    let fullmoves: usize = start.split_whitespace().nth(5).and_then(|n| n.parse().ok()).unwrap_or(1);
+    let ply = (fullmoves - 1) * 2 + usize::from(game.starting_turn() == PlayerColor::Black) + game.current_index();
+    format!("{}/pgn/{}?color={}#{}", ANALYSIS_URL, url_encode(&pgn.to_pgn()), color, ply)
+}
+
+/// Who makes our moves in online games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnlinePlayer {
+    /// The local engine plays, as a bot
+    Engine,
+    /// The user plays on the board
+    Me,
+}
+
+impl OnlinePlayer {
+    pub fn all() -> &'static [OnlinePlayer] {
+        &[OnlinePlayer::Engine, OnlinePlayer::Me]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OnlinePlayer::Engine => tr("online.player_engine"),
+            OnlinePlayer::Me => tr("online.player_me"),
+        }
+    }
+}
+
+/// Online play options, remembered between sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnlineSettings {
+    pub player: OnlinePlayer,
+    /// Time control for seeks, in minutes plus increment seconds
+    pub seek_minutes: u32,
+    pub seek_increment: u32,
+}
+
+impl Default for OnlineSettings {
+    fn default() -> Self {
+        Self {
+            player: OnlinePlayer::Me,
+            seek_minutes: 10,
+            seek_increment: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemotePlayer {
+    /// Lowercase user id, empty for the Lichess AI
+    pub id: String,
+    pub name: String,
+    pub rating: Option<u32>,
+}
+
+/// An incoming challenge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub id: String,
+    pub challenger: RemotePlayer,
+    pub rated: bool,
+    pub variant: String,
+    /// "5+3", or "∞" for games without a clock
+    pub time_control: String,
+}
+
+impl Challenge {
+    /// Only standard chess can be played here
+    pub fn is_standard(&self) -> bool {
+        matches!(self.variant.as_str(), "standard" | "fromPosition")
+    }
+}
+
+/// The server's view of a game
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteState {
+    /// All moves so far in UCI
+    pub moves: Vec<String>,
+    pub white_ms: u64,
+    pub black_ms: u64,
+    /// "started" while the game is on, otherwise how it ended ("mate", "resign", ...)
+    pub status: String,
+    pub winner: Option<PlayerColor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnlineEvent {
+    Connected { username: String, bot: bool },
+    Challenge(Challenge),
+    /// A challenge was withdrawn or declined
+    ChallengeGone(String),
+    GameStart(String),
+    GameFinish(String),
+    /// First line of a game stream
+    GameFull {
+        id: String,
+        white: RemotePlayer,
+        black: RemotePlayer,
+        initial_fen: Option<String>,
+        state: RemoteState,
+    },
+    GameState(RemoteState),
+    /// The account event stream dropped; it is reconnected after a pause
+    Disconnected(String),
+    /// Lichess refused a move, so the board no longer matches the server's
+    MoveRejected { game_id: String, reason: String },
+    /// A request failed
+    Error(String),
+}
+
+/// Connection to Lichess as a Bot or Board API client. Streams and requests run on
+/// background threads and report back through `poll`; dropping the client ends its streams.
+pub struct LichessClient {
+    token: String,
+    event_tx: mpsc::Sender<OnlineEvent>,
+    event_rx: mpsc::Receiver<OnlineEvent>,
+    /// Set when the client is dropped, to end its stream threads
+    stop: Arc<AtomicBool>,
+    pub username: Option<String>,
+    /// Bot accounts use the Bot API, everyone else the Board API
+    pub bot: bool,
+    /// Why the account stream is down, until it reconnects
+    pub error: Option<String>,
+    /// A seek is waiting for an opponent
+    pub seeking: bool,
+}
+
+impl LichessClient {
+    /// Log in with `token` and start listening for challenges and games
+    pub fn connect(token: String) -> Self {
+        let (event_tx, event_rx) = mpsc::channel();
+        let client = Self {
+            token,
+            event_tx,
+            event_rx,
+            stop: Arc::new(AtomicBool::new(false)),
+            username: None,
+            bot: false,
+            error: None,
+            seeking: false,
+        };
+        let (token, events, stop) = (client.token.clone(), client.event_tx.clone(), client.stop.clone());
+        std::thread::spawn(move || {
+            let connect = |forward: &mut dyn FnMut(OnlineEvent) -> bool| {
+                let (username, bot) = lichess::account(&token)?;
+                forward(OnlineEvent::Connected { username, bot });
+                lichess::stream("stream/event", &token, lichess::parse_event, &stop, forward)
+            };
+            follow(&stop, |_| false, connect, &events, OnlineEvent::Disconnected);
+        });
+        client
+    }
+
+    /// Run a request on its own thread, reporting failure as an `Error` event
+    fn spawn(&self, request: impl FnOnce(&str, &mpsc::Sender<OnlineEvent>) -> Result<(), String> + Send + 'static) {
+        let token = self.token.clone();
+        let events = self.event_tx.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = request(&token, &events) {
+                let _ = events.send(OnlineEvent::Error(e));
+            }
+        });
+    }
+
+    fn api(&self) -> &'static str {
+        if self.bot {
+            "bot"
+        } else {
+            "board"
+        }
+    }
+
+    pub fn accept(&self, challenge_id: &str) {
+        let path = format!("challenge/{}/accept", challenge_id);
+        self.spawn(move |token, _| lichess::post(&path, token));
+    }
+
+    pub fn decline(&self, challenge_id: &str) {
+        let path = format!("challenge/{}/decline", challenge_id);
+        self.spawn(move |token, _| lichess::post(&path, token));
+    }
+
+    /// Look for a casual opponent; bots can't seek
+    pub fn seek(&mut self, minutes: u32, increment: u32) {
+        self.seeking = true;
+        self.spawn(move |token, _| lichess::seek(token, minutes, increment));
+    }
+
+    /// Follow a started game's moves and clocks until it ends
+    pub fn stream_game(&self, game_id: &str) {
+        let path = format!("{}/game/stream/{}", self.api(), game_id);
+        let (token, events, stop) = (self.token.clone(), self.event_tx.clone(), self.stop.clone());
+        std::thread::spawn(move || {
+            let connect = |forward: &mut dyn FnMut(OnlineEvent) -> bool| {
+                lichess::stream(&path, &token, lichess::parse_game_line, &stop, forward)
+            };
+            follow(&stop, game_ended, connect, &events, OnlineEvent::Error);
+        });
+    }
+
+    pub fn play_move(&self, game_id: &str, uci: &str) {
+        let path = format!("{}/game/{}/move/{}", self.api(), game_id, uci);
+        let game_id = game_id.to_string();
+        self.spawn(move |token, events| {
+            if let Err(reason) = lichess::post(&path, token) {
+                let _ = events.send(OnlineEvent::MoveRejected { game_id, reason });
+            }
+            Ok(())
+        });
+    }
+
+    pub fn resign(&self, game_id: &str) {
+        let path = format!("{}/game/{}/resign", self.api(), game_id);
+        self.spawn(move |token, _| lichess::post(&path, token));
+    }
+
+    /// Events since the last call; connection details are taken in here
+    pub fn poll(&mut self) -> Vec<OnlineEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = self.event_rx.try_recv() {
+            match &event {
+                OnlineEvent::Connected { username, bot } => {
+                    self.username = Some(username.clone());
+                    self.bot = *bot;
+                    self.error = None;
+                }
+                OnlineEvent::GameStart(_) => self.seeking = false,
+                OnlineEvent::Disconnected(e) => {
+                    self.error = Some(e.clone());
+                    self.seeking = false;
+                }
+                OnlineEvent::Error(_) => self.seeking = false,
+                _ => {}
+            }
+            events.push(event);
+        }
+        events
+    }
+}
+
+impl Drop for LichessClient {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Whether a game stream has nothing more to say
+fn game_ended(event: &OnlineEvent) -> bool {
+    match event {
+        OnlineEvent::GameFull { state, .. } | OnlineEvent::GameState(state) => {
+            !matches!(state.status.as_str(), "created" | "started")
+        }
+        _ => false,
+    }
+}
+
+/// Run `connect`, a stream handing its events to the callback it is given until it ends, again
+/// after a growing pause each time it drops. Stops once `stop` is set, nobody listens, or an
+/// event is one `ends` says is the last. Why the stream dropped is sent with `dropped`.
+fn follow(
+    stop: &AtomicBool,
+    ends: fn(&OnlineEvent) -> bool,
+    mut connect: impl FnMut(&mut dyn FnMut(OnlineEvent) -> bool) -> Result<(), String>,
+    events: &mpsc::Sender<OnlineEvent>,
+    dropped: fn(String) -> OnlineEvent,
+) {
+    let mut delay = RECONNECT_MIN;
+    loop {
+        let started = Instant::now();
+        let (mut ended, mut listening) = (false, true);
+        let result = connect(&mut |event| {
+            if ended {
+                return false;
+            }
+            ended = ends(&event);
+            listening = events.send(event).is_ok();
+            listening && !ended
+        });
+        if ended || !listening || stop.load(Ordering::Relaxed) {
+            return;
+        }
+        // A stream that stayed up a while was fine; start the backoff over
+        if started.elapsed() > RECONNECT_MAX {
+            delay = RECONNECT_MIN;
+        }
+        let reason = result.err().unwrap_or_else(|| tr("online.stream_closed").to_string());
+        tracing::warn!("Lichess stream dropped ({}); reconnecting in {:?}", reason, delay);
+        if events.send(dropped(reason)).is_err() {
+            return;
+        }
+        let resume = Instant::now() + delay;
+        while Instant::now() < resume {
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        delay = (delay * 2).min(RECONNECT_MAX);
+    }
+}
+
+/// The online game being played
+#[derive(Debug, Clone)]
+pub struct OnlineGame {
+    pub id: String,
+    pub color: PlayerColor,
+    pub white: RemotePlayer,
+    pub black: RemotePlayer,
+    initial_fen: Option<String>,
+    pub state: RemoteState,
+    /// When `state` arrived, to run the clock of the side to move
+    received: Instant,
+    to_move: PlayerColor,
+}
+
+impl OnlineGame {
+    /// `username` decides which side is ours
+    pub fn new(
+        id: String,
+        username: &str,
+        (white, black): (RemotePlayer, RemotePlayer),
+        initial_fen: Option<String>,
+        state: RemoteState,
+    ) -> Self {
+        let color = if white.id.eq_ignore_ascii_case(username) {
+            PlayerColor::White
+        } else {
+            PlayerColor::Black
+        };
+        let mut game = Self {
+            id,
+            color,
+            white,
+            black,
+            initial_fen,
+            state: RemoteState::default(),
+            received: Instant::now(),
+            to_move: PlayerColor::White,
+        };
+        game.update(state);
+        game
+    }
+
+    pub fn update(&mut self, state: RemoteState) {
+        self.received = Instant::now();
+        if let Ok(game) = self.to_game_with(&state) {
+            self.to_move = game.turn();
+        }
+        self.state = state;
+    }
+
+    fn to_game_with(&self, state: &RemoteState) -> Result<GameState, GameError> {
+        let mut game = match &self.initial_fen {
+            Some(fen) => GameState::from_fen(fen)?,
+            None => GameState::new(),
+        };
+        for uci in &state.moves {
+            game.make_move_uci(uci)?;
+        }
+        Ok(game)
+    }
+
+    /// The game as the server has it
+    pub fn to_game(&self) -> Result<GameState, GameError> {
+        self.to_game_with(&self.state)
+    }
+
+    pub fn is_over(&self) -> bool {
+        !matches!(self.state.status.as_str(), "created" | "started")
+    }
+
+    pub fn our_turn(&self) -> bool {
+        !self.is_over() && self.to_move == self.color
+    }
+
+    pub fn player(&self, side: PlayerColor) -> &RemotePlayer {
+        match side {
+            PlayerColor::White => &self.white,
+            PlayerColor::Black => &self.black,
+        }
+    }
+
+    /// Lichess starts the clocks once both sides have moved
+    pub fn clock_running(&self, side: PlayerColor) -> bool {
+        !self.is_over() && self.state.moves.len() >= 2 && self.to_move == side
+    }
+
+    pub fn remaining(&self, side: PlayerColor) -> Duration {
+        let ms = match side {
+            PlayerColor::White => self.state.white_ms,
+            PlayerColor::Black => self.state.black_ms,
+        };
+        let remaining = Duration::from_millis(ms);
+        if self.clock_running(side) {
+            remaining.saturating_sub(self.received.elapsed())
+        } else {
+            remaining
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analysis_url() {
+        let mut game = GameState::new();
+        assert_eq!(
+            analysis_url(&game, PlayerColor::Black),
+            "https://lichess.org/analysis/standard/rnbqkbnr%2Fpppppppp%2F8%2F8%2F8%2F8%2FPPPPPPPP%2FRNBQKBNR_w_KQkq_-_0_1?color=black"
+        );
+        game.make_move_san("e4").unwrap();
+        game.make_move_san("e5").unwrap();
+        game.go_back().unwrap();
+        assert_eq!(
+            analysis_url(&game, PlayerColor::White),
+            "https://lichess.org/analysis/pgn/%0A1.%20e4%20e5%20%2A%0A?color=white#1"
+        );
+    }
+
+    #[test]
+    fn test_follow_reconnects() {
+        let (tx, rx) = mpsc::channel();
+        let stop = AtomicBool::new(false);
+        let mut attempts = 0;
+        let over = RemoteState { status: "mate".to_string(), ..Default::default() };
+        follow(&stop, game_ended, |forward| {
+            attempts += 1;
+            if attempts == 1 {
+                return Err("connection reset".to_string());
+            }
+            forward(OnlineEvent::GameState(RemoteState { status: "started".to_string(), ..Default::default() }));
+            forward(OnlineEvent::GameState(over.clone()));
+            // Nothing after the game ended is passed on
+            forward(OnlineEvent::Error("late".to_string()));
+            Ok(())
+        }, &tx, OnlineEvent::Error);
+        assert_eq!(attempts, 2);
+        let events: Vec<_> = rx.try_iter().collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], OnlineEvent::Error("connection reset".to_string()));
+        assert_eq!(events[2], OnlineEvent::GameState(over));
+    }
+}
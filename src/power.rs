@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+/// How often the power source is checked
+const BATTERY_POLL: Duration = Duration::from_secs(30);
+
+/// How often the watcher wakes up to notice the computer was asleep
+const SLEEP_POLL: Duration = Duration::from_secs(5);
+
+/// Wall-clock time beyond a poll's interval that means the computer was suspended in between
+const SLEEP_GAP: Duration = Duration::from_secs(20);
+
+/// When infinite analysis pauses or slows down to save power
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    /// Stop analyzing while the window is in the background
+    pub pause_unfocused: bool,
+    /// Stop analyzing while the computer runs on battery
+    pub pause_on_battery: bool,
+    /// Share of the CPU cores the engine may use in the background or on battery, in percent
+    pub cpu_limit: u8,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            pause_unfocused: false,
+            pause_on_battery: false,
+            cpu_limit: 50,
+        }
+    }
+}
+
+impl PowerSettings {
+    /// Engine threads out of `cores` while saving power, None when the engine keeps its own
+    /// setting
+    pub fn threads(&self, cores: u32, saving: bool) -> Option<u32> {
+        (saving && self.cpu_limit < 100).then(|| limited_threads(cores, self.cpu_limit))
+    }
+}
+
+fn limited_threads(cores: u32, percent: u8) -> u32 {
+    (cores * u32::from(percent.min(100)) / 100).max(1)
+}
+
+/// What the watcher thread reports
+#[derive(Default)]
+struct PowerFlags {
+    on_battery: AtomicBool,
+    /// The computer woke from sleep since this was last taken
+    resumed: AtomicBool,
+}
+
+/// Watches the power source, and for the computer waking from sleep, on a background thread,
+/// which ends once the monitor is dropped
+pub struct PowerMonitor {
+    flags: Arc<PowerFlags>,
+    cores: u32,
+}
+
+impl PowerMonitor {
+    pub fn start() -> Self {
+        let flags = Arc::new(PowerFlags::default());
+        let weak = Arc::downgrade(&flags);
+        std::thread::spawn(move || watch_power(weak));
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as u32;
+        Self { flags, cores }
+    }
+
+    /// CPU cores of the computer, looked up once at startup
+    pub fn cores(&self) -> u32 {
+        self.cores
+    }
+
+    pub fn on_battery(&self) -> bool {
+        self.flags.on_battery.load(Ordering::Relaxed)
+    }
+
+    /// Whether the computer woke from sleep since the last call
+    pub fn take_resumed(&self) -> bool {
+        self.flags.resumed.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Whether the wall clock moved on by `elapsed` over a poll of `interval` because the
+/// computer slept, as no thread runs while it is suspended
+fn slept(interval: Duration, elapsed: Duration) -> bool {
+    elapsed > interval + SLEEP_GAP
+}
+
+fn watch_power(flags: Weak<PowerFlags>) {
+    let polls_per_check = (BATTERY_POLL.as_secs() / SLEEP_POLL.as_secs()).max(1);
+    let mut poll = 0;
+    let mut resumed = false;
+    while let Some(strong) = flags.upgrade() {
+        if resumed {
+            strong.resumed.store(true, Ordering::Relaxed);
+        }
+        if poll % polls_per_check == 0 {
+            strong.on_battery.store(running_on_battery(), Ordering::Relaxed);
+        }
+        drop(strong);
+        poll += 1;
+
+        let before = SystemTime::now();
+        std::thread::sleep(SLEEP_POLL);
+        // A clock set backwards shows no gap
+        let elapsed = SystemTime::now().duration_since(before).unwrap_or_default();
+        resumed = slept(SLEEP_POLL, elapsed);
+        if resumed {
+            tracing::info!("Computer woke from sleep after {} s", elapsed.as_secs());
+            // Chargers are often plugged in or pulled while the computer sleeps
+            poll = 0;
+        }
+    }
+}
+
+/// Whether the computer runs on battery right now, false when it can't be told
+#[cfg(target_os = "linux")]
+fn running_on_battery() -> bool {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).map(|s| s.trim().to_string()).unwrap_or_default();
+    let mut has_battery = false;
+    for supply in supplies.flatten() {
+        let path = supply.path();
+        match read(path.join("type")).as_str() {
+            // Any connected charger means full power
+            "Mains" | "USB" if read(path.join("online")) == "1" => return false,
+            "Battery" => has_battery |= read(path.join("status")) == "Discharging",
+            _ => {}
+        }
+    }
+    has_battery
+}
+
+#[cfg(target_os = "macos")]
+fn running_on_battery() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .map_or(false, |out| String::from_utf8_lossy(&out.stdout).contains("'Battery Power'"))
+}
+
+#[cfg(target_os = "windows")]
+fn running_on_battery() -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    // BatteryStatus 1 is "discharging"
+    std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", "(Get-CimInstance Win32_Battery).BatteryStatus"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_or(false, |out| String::from_utf8_lossy(&out.stdout).lines().any(|l| l.trim() == "1"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn running_on_battery() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limited_threads() {
+        assert_eq!(limited_threads(8, 100), 8);
+        assert_eq!(limited_threads(8, 50), 4);
+        assert_eq!(limited_threads(8, 25), 2);
+        assert_eq!(limited_threads(2, 25), 1);
+        assert_eq!(limited_threads(1, 0), 1);
+    }
+
+    #[test]
+    fn test_threads_only_while_saving() {
+        let power = PowerSettings::default();
+        assert_eq!(power.threads(8, false), None);
+        assert_eq!(power.threads(8, true), Some(4));
+        let unlimited = PowerSettings { cpu_limit: 100, ..power };
+        assert_eq!(unlimited.threads(8, true), None);
+    }
+
+    #[test]
+    fn test_slept() {
+        assert!(!slept(SLEEP_POLL, SLEEP_POLL));
+        assert!(!slept(SLEEP_POLL, SLEEP_POLL + Duration::from_secs(2)));
+        assert!(slept(SLEEP_POLL, Duration::from_secs(600)));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::game::{fen_ply, numbered_line, GameState, Nag, MISTAKE_CP};
+use crate::i18n::tr_args;
+use crate::study::{glyph_group, GlyphGroup, NodeId, ReviewedNodes, Study, StudyChapter, ROOT};
+
+/// Start of the comment an annotation leaves on every move, so annotating again replaces it
+const EVAL_PREFIX: &str = "[%eval ";
+/// Moves of the engine's line quoted as the improvement on a mistake
+const BETTER_LINE_PLIES: usize = 4;
+
+/// What the engine found in one position of the main line
+#[derive(Debug, Clone)]
+struct Evaluation {
+    /// Side-to-move score, mates as ±10000
+    score: i32,
+    /// Moves to mate for the side to move, negative when it gets mated
+    mate: Option<i32>,
+    depth: Option<u32>,
+    pv: Vec<String>,
+}
+
+/// Engine annotation of a chapter's main line: every position is evaluated, each move gets the
+/// evaluation as a `[%eval]` comment, and moves losing enough get a glyph and, from mistakes
+/// on, the engine's better line.
+#[derive(Debug, Clone)]
+pub struct GameAnnotation {
+    /// Where the results go, checked before they are written
+    target: ReviewedNodes,
+    /// Main line nodes, the root first, by review index
+    nodes: Vec<NodeId>,
+    evaluations: Vec<Option<Evaluation>>,
+    /// Positions evaluated so far
+    pub done: usize,
+}
+
+impl GameAnnotation {
+    /// The annotation of the study's current chapter and the positions to review, as (index, FEN)
+    pub fn new(study: &Study) -> (Self, Vec<(usize, String)>) {
+        let chapter = study.current_chapter();
+        let nodes: Vec<NodeId> = std::iter::once(ROOT).chain(chapter.main_line_ids()).collect();
+        let positions = nodes.iter().map(|&id| chapter.node(id).fen.clone()).enumerate().collect();
+        let annotation = Self {
+            target: ReviewedNodes::new(study, &nodes),
+            evaluations: vec![None; nodes.len()],
+            nodes,
+            done: 0,
+        };
+        (annotation, positions)
+    }
+
+    pub fn total(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Result of the search of the position at review `index`, scores for the side to move
+    pub fn record(&mut self, index: usize, score_cp: Option<i32>, score_mate: Option<i32>, depth: Option<u32>, pv: Vec<String>) {
+        let Some(slot) = self.evaluations.get_mut(index) else {
+            return;
+        };
+        self.done += 1;
+        let score = score_mate.map(|mate| if mate > 0 { 10000 } else { -10000 }).or(score_cp);
+        *slot = score.map(|score| Evaluation { score, mate: score_mate, depth, pv });
+    }
+
+    /// The annotated chapter in `study`, `None` if the study was switched or the chapter's
+    /// nodes changed since the annotation started
+    pub fn chapter_in<'a>(&self, study: &'a mut Study) -> Option<&'a mut StudyChapter> {
+        self.target.chapter_in(study)
+    }
+
+    /// Lowest depth the engine reached over the evaluated positions
+    fn depth(&self) -> Option<u32> {
+        self.evaluations.iter().flatten().filter_map(|e| e.depth).min()
+    }
+
+    /// Write the evaluations, glyphs and better lines onto the chapter's main line, replacing
+    /// those of an earlier annotation, and name the engine as its annotator. Returns how many
+    /// moves were marked as mistakes or worse.
+    pub fn apply(&self, chapter: &mut StudyChapter, engine: &str) -> usize {
+        let mut marked = 0;
+        for (i, pair) in self.nodes.windows(2).enumerate() {
+            let (from, played) = (pair[0], pair[1]);
+            chapter.node_mut(played).comments.retain(|c| !c.starts_with(EVAL_PREFIX));
+            let Some(after) = &self.evaluations[i + 1] else {
+                continue;
+            };
+            let white_to_move = chapter.node(played).fen.split_whitespace().nth(1) != Some("b");
+            let mut comment = format!("{}{}]", EVAL_PREFIX, eval_text(after, white_to_move));
+
+            if let Some(before) = &self.evaluations[i] {
+                // `after` is from the opponent's side
+                let loss = before.score + after.score;
+                if let Some(nag) = Nag::from_loss(loss) {
+                    let nags = &mut chapter.node_mut(played).nags;
+                    nags.retain(|&n| glyph_group(n) != Some(GlyphGroup::Move));
+                    nags.push(nag.code());
+                    nags.sort_unstable();
+                }
+                let played_uci = chapter.node(played).move_record.as_ref().map(|m| m.uci.as_str());
+                if loss >= MISTAKE_CP && before.pv.first().map(String::as_str) != played_uci {
+                    marked += 1;
+                    if let Some(line) = san_line(&chapter.node(from).fen, &before.pv) {
+                        comment.push(' ');
+                        comment.push_str(&tr_args("annotate.better", &[&line]));
+                    }
+                }
+            }
+            chapter.node_mut(played).comments.insert(0, comment);
+        }
+
+        let annotator = match self.depth() {
+            Some(depth) => format!("{}, depth {}", engine, depth),
+            None => engine.to_string(),
+        };
+        chapter.headers.retain(|(key, _)| key != "Annotator");
+        chapter.headers.push(("Annotator".to_string(), annotator));
+        marked
+    }
+}
+
+/// Evaluation in `[%eval]` form: pawns or `#n` mates, from White's side
+fn eval_text(evaluation: &Evaluation, white_to_move: bool) -> String {
+    let sign = if white_to_move { 1 } else { -1 };
+    match evaluation.mate {
+        Some(mate) => format!("#{}", mate * sign),
+        None => format!("{:.2}", (evaluation.score * sign) as f32 / 100.0),
+    }
+}
+
+/// The first moves of the UCI line `pv` from `fen` in numbered SAN
+fn san_line(fen: &str, pv: &[String]) -> Option<String> {
+    let mut game = GameState::from_fen(fen).ok()?;
+    let mut sans = Vec::new();
+    for uci in pv.iter().take(BETTER_LINE_PLIES) {
+        sans.push(game.make_move_uci(uci).ok()?.san);
+    }
+    (!sans.is_empty()).then(|| numbered_line(fen_ply(fen), &sans))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::study::Study;
+
+    #[test]
+    fn test_annotation_marks_mistakes() {
+        let mut study = Study::new("Games".to_string());
+        let chapter = study.current_chapter_mut();
+        let mut game = GameState::new();
+        for san in ["e4", "e5", "Qh5"] {
+            let record = game.make_move_san(san).unwrap();
+            chapter.add_move(record, game.fen());
+        }
+        chapter.go_to_start();
+
+        let (mut annotation, positions) = GameAnnotation::new(&study);
+        assert_eq!(positions.len(), 4);
+        let pv = |line: &[&str]| line.iter().map(|m| m.to_string()).collect();
+        annotation.record(0, Some(30), None, Some(20), pv(&["e2e4"]));
+        annotation.record(1, Some(-30), None, Some(18), pv(&["e7e5"]));
+        annotation.record(2, Some(30), None, Some(19), pv(&["g1f3", "b8c6"]));
+        annotation.record(3, Some(120), None, Some(21), pv(&["b8c6"]));
+        assert_eq!(annotation.done, annotation.total());
+
+        let chapter = annotation.chapter_in(&mut study).unwrap();
+        assert_eq!(annotation.apply(chapter, "Stockfish"), 1);
+        let node = |path: &[usize]| chapter.node(chapter.node_at(path)).clone();
+        assert_eq!(node(&[0]).comments, ["[%eval 0.30]"]);
+        assert!(node(&[0]).nags.is_empty());
+        let qh5 = node(&[0, 0, 0]);
+        assert_eq!(qh5.nags, [Nag::Mistake.code()]);
+        assert!(qh5.comments[0].starts_with("[%eval -1.20]"));
+        assert!(qh5.comments[0].contains("2. Nf3 Nc6"));
+        assert_eq!(chapter.header("Annotator"), Some("Stockfish, depth 18"));
+
+        // Annotating again replaces the earlier comments
+        annotation.apply(chapter, "Stockfish");
+        assert_eq!(chapter.node(chapter.node_at(&[0, 0, 0])).comments.len(), 1);
+        assert_eq!(chapter.headers.len(), 1);
+
+        // Not applied once the line was cut back
+        let newest = chapter.node_at(&[0, 0, 0]);
+        assert!(chapter.remove_newest_node(newest));
+        assert!(annotation.chapter_in(&mut study).is_none());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::i18n::tr_args;
+use crate::study::{NodeId, ReviewedNodes, Study, StudyChapter, ROOT};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Lines ending this many centipawns down for the chapter's side are flagged
+const BAD_ENDING_CP: i32 = 150;
+
+/// What an engine audit found wrong with a node, kept as numbers and worded when shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditWarning {
+    /// The move lost this many centipawns against the engine's best
+    MoveLoses { loss_cp: i32 },
+    /// The line ends at this score for the chapter's side
+    BadEnding { score_cp: i32 },
+}
+
+impl std::fmt::Display for AuditWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            AuditWarning::MoveLoses { loss_cp } => tr_args("audit.move_loses", &[loss_cp]),
+            AuditWarning::BadEnding { score_cp } => {
+                tr_args("audit.bad_ending", &[&format!("{:+.2}", *score_cp as f32 / 100.0)])
+            }
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Engine check of the moves a chapter gives its side: every move ending a line or left
+/// without comments or glyphs is compared with the engine's best, and every line's final
+/// position is scored. Results are written onto the nodes as warnings.
+#[derive(Debug, Clone)]
+pub struct RepertoireAudit {
+    /// Where the results go, checked before they are written
+    target: ReviewedNodes,
+    /// Centipawns a move may lose against the engine's best before it is flagged
+    threshold_cp: i32,
+    /// Node of each position sent to the engine, by review index
+    nodes: Vec<NodeId>,
+    /// Side-to-move score of each position, by review index
+    scores: Vec<Option<i32>>,
+    /// Moves checked, as (position played from, move)
+    moves: Vec<(NodeId, NodeId)>,
+    leaves: Vec<NodeId>,
+    /// Positions evaluated so far
+    pub done: usize,
+}
+
+impl RepertoireAudit {
+    /// The audit of the study's current chapter and the positions to review, as (index, FEN)
+    pub fn new(study: &Study, threshold_cp: i32) -> (Self, Vec<(usize, String)>) {
+        let chapter = study.current_chapter();
+        let mut audit = Self {
+            target: ReviewedNodes::default(),
+            threshold_cp,
+            nodes: Vec::new(),
+            scores: Vec::new(),
+            moves: Vec::new(),
+            leaves: Vec::new(),
+            done: 0,
+        };
+        let mut index_of = HashMap::new();
+        let mut positions = Vec::new();
+        let mut add = |id: NodeId, audit: &mut Self| {
+            index_of.entry(id).or_insert_with(|| {
+                audit.nodes.push(id);
+                positions.push((audit.nodes.len() - 1, chapter.node(id).fen.clone()));
+                audit.nodes.len() - 1
+            });
+        };
+
+        for (id, node) in chapter.nodes().enumerate() {
+            if node.children.is_empty() {
+                if id != ROOT {
+                    add(id, &mut audit);
+                    audit.leaves.push(id);
+                }
+                continue;
+            }
+            if !chapter.side_to_move_is_mine(&node.fen) {
+                continue;
+            }
+            for &child in &node.children {
+                let played = chapter.node(child);
+                if played.children.is_empty() || (played.comments.is_empty() && played.nags.is_empty()) {
+                    add(id, &mut audit);
+                    add(child, &mut audit);
+                    audit.moves.push((id, child));
+                }
+            }
+        }
+        audit.scores = vec![None; audit.nodes.len()];
+        audit.target = ReviewedNodes::new(study, &audit.nodes);
+        (audit, positions)
+    }
+
+    pub fn total(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Side-to-move score of the position at review `index`, mates as ±10000
+    pub fn record(&mut self, index: usize, score: Option<i32>) {
+        if let Some(slot) = self.scores.get_mut(index) {
+            *slot = score;
+            self.done += 1;
+        }
+    }
+
+    fn score(&self, id: NodeId) -> Option<i32> {
+        let index = self.nodes.iter().position(|&n| n == id)?;
+        self.scores[index]
+    }
+
+    /// The audited chapter in `study`, `None` if the study was switched or the chapter's
+    /// nodes changed since the audit started
+    pub fn chapter_in<'a>(&self, study: &'a mut Study) -> Option<&'a mut StudyChapter> {
+        self.target.chapter_in(study)
+    }
+
+    /// Replace the chapter's warnings with this audit's, returning how many nodes were flagged
+    pub fn apply(&self, chapter: &mut StudyChapter) -> usize {
+        let ids: Vec<NodeId> = (0..chapter.nodes().count()).collect();
+        for &id in &ids {
+            chapter.node_mut(id).audit = None;
+        }
+
+        let mut flagged = 0;
+        for &(from, played) in &self.moves {
+            let (Some(best), Some(after)) = (self.score(from), self.score(played)) else {
+                continue;
+            };
+            // `after` is from the opponent's side
+            let loss = best + after;
+            if loss > self.threshold_cp {
+                chapter.node_mut(played).audit = Some(AuditWarning::MoveLoses { loss_cp: loss });
+                flagged += 1;
+            }
+        }
+        for &leaf in &self.leaves {
+            let Some(score) = self.score(leaf) else {
+                continue;
+            };
+            let mine = if chapter.side_to_move_is_mine(&chapter.node(leaf).fen) { score } else { -score };
+            if mine <= -BAD_ENDING_CP && chapter.node(leaf).audit.is_none() {
+                chapter.node_mut(leaf).audit = Some(AuditWarning::BadEnding { score_cp: mine });
+                flagged += 1;
+            }
+        }
+        flagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+    use crate::study::Study;
+
+    #[test]
+    fn test_audit_flags_losing_moves() {
+        let mut study = Study::new("Repertoire".to_string());
+        let chapter = study.current_chapter_mut();
+        let mut game = GameState::new();
+        for san in ["e4", "e5", "Qh5"] {
+            let record = game.make_move_san(san).unwrap();
+            chapter.add_move(record, game.fen());
+        }
+        // 1. e4 is commented, so only checked as the way into the line
+        chapter.current_path = vec![0];
+        chapter.add_comment("Best by test".to_string());
+
+        let (mut audit, positions) = RepertoireAudit::new(&study, 50);
+        let chapter = study.current_chapter_mut();
+        let fens: Vec<_> = positions.iter().map(|(_, fen)| fen.as_str()).collect();
+        // After 1... e5 and after 2. Qh5, the leaf
+        assert_eq!(fens, [chapter.node(chapter.node_at(&[0, 0])).fen.as_str(), chapter.node(chapter.node_at(&[0, 0, 0])).fen.as_str()]);
+        audit.record(0, Some(40));
+        audit.record(1, Some(200));
+        assert_eq!(audit.done, audit.total());
+
+        assert_eq!(audit.apply(chapter), 1);
+        let qh5 = chapter.node(chapter.node_at(&[0, 0, 0]));
+        assert_eq!(qh5.audit, Some(AuditWarning::MoveLoses { loss_cp: 240 }));
+        assert!(qh5.audit.unwrap().to_string().contains("240"));
+
+        // Older files saved the worded warning, which reads back as none
+        let mut json = serde_json::to_value(qh5).unwrap();
+        json["audit"] = serde_json::json!("Engine: 240 cp worse than its best move");
+        assert_eq!(serde_json::from_value::<crate::study::StudyNode>(json).unwrap().audit, None);
+    }
+
+    #[test]
+    fn test_audit_skips_changed_study() {
+        let mut study = Study::new("Repertoire".to_string());
+        let mut game = GameState::new();
+        for san in ["e4", "e5"] {
+            let record = game.make_move_san(san).unwrap();
+            study.current_chapter_mut().add_move(record, game.fen());
+        }
+        let (audit, _) = RepertoireAudit::new(&study, 50);
+        assert!(audit.chapter_in(&mut study).is_some());
+
+        // The last move taken back and another played in its place
+        let chapter = study.current_chapter_mut();
+        assert!(chapter.remove_newest_node(chapter.current_id()));
+        let mut game = GameState::from_fen(chapter.current_fen()).unwrap();
+        let record = game.make_move_san("c5").unwrap();
+        chapter.add_move(record, game.fen());
+        assert!(audit.chapter_in(&mut study).is_none());
+
+        let mut other = Study::new("Repertoire".to_string());
+        assert!(audit.chapter_in(&mut other).is_none());
+    }
+}
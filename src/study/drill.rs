@@ -0,0 +1,327 @@
+use crate::game::{fen_ply, numbered_line, PlayerColor};
+use crate::study::{NodeId, Study, StudyChapter, ROOT};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How repertoire drills are timed and scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrillSettings {
+    /// Seconds to find each move, unlimited when zero
+    pub move_seconds: u32,
+    /// Wrong moves allowed in a line before it fails
+    pub allowed_mistakes: u32,
+    /// Whether the repertoire move can be asked for
+    pub hints: bool,
+}
+
+impl Default for DrillSettings {
+    fn default() -> Self {
+        Self {
+            move_seconds: 0,
+            allowed_mistakes: 2,
+            hints: true,
+        }
+    }
+}
+
+/// Score of a drill session, shown when it ends
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrillSession {
+    pub lines_passed: u32,
+    pub lines_failed: u32,
+    /// Positions the user had to find a move in
+    pub positions: u32,
+    /// Positions whose move was found at the first try
+    pub first_try: u32,
+    pub mistakes: u32,
+    /// Moves not found in time, also counted as mistakes
+    pub timeouts: u32,
+    pub hints: u32,
+}
+
+impl DrillSession {
+    /// Percentage of positions whose move was found at the first try
+    pub fn score(&self) -> u32 {
+        (self.first_try * 100).checked_div(self.positions).unwrap_or(0)
+    }
+}
+
+/// How well the move from a position was recalled in repertoire drills, counting the first
+/// try at each visit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrillStats {
+    pub correct: u32,
+    pub wrong: u32,
+}
+
+impl DrillStats {
+    pub fn attempts(&self) -> u32 {
+        self.correct + self.wrong
+    }
+
+    /// Share of tries recalled, None before the first
+    pub fn recall(&self) -> Option<f32> {
+        (self.attempts() > 0).then(|| self.correct as f32 / self.attempts() as f32)
+    }
+}
+
+/// Result of a move played in a drill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrillStep {
+    /// One of the chapter's moves; the opponent answers with the move leading to the node, if
+    /// the line goes on
+    Correct(Option<NodeId>),
+    /// Not a move of the chapter, to be tried again
+    Wrong,
+    /// One mistake more than the settings allow; the line is over
+    Failed,
+}
+
+/// Playing a chapter's lines from the side it is studied from: the user has to find the
+/// chapter's move in each of their positions while the opponent's replies are picked at random
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepertoireDrill {
+    /// Index of the chapter in the study
+    pub chapter: usize,
+    /// Position reached
+    pub node: NodeId,
+    pub settings: DrillSettings,
+    pub session: DrillSession,
+    /// Mistakes in the line being played
+    pub line_mistakes: u32,
+    /// Whether the user already tried a move from `node`, or asked for it
+    tried: bool,
+    /// When the user's current move was asked for
+    move_started: Instant,
+}
+
+impl RepertoireDrill {
+    /// Whether the chapter has a move for its side to drill
+    pub fn can_drill(chapter: &StudyChapter) -> bool {
+        chapter.nodes().any(|node| chapter.side_to_move_is_mine(&node.fen) && !node.children.is_empty())
+    }
+
+    /// Start from the chapter's root, returning the opponent's first move when they start
+    pub fn start(chapter_idx: usize, chapter: &StudyChapter, settings: DrillSettings) -> (Self, Option<NodeId>) {
+        let mut drill = Self {
+            chapter: chapter_idx,
+            node: ROOT,
+            settings,
+            session: DrillSession::default(),
+            line_mistakes: 0,
+            tried: false,
+            move_started: Instant::now(),
+        };
+        let reply = drill.opponent_reply(chapter);
+        (drill, reply)
+    }
+
+    /// Go back to the chapter's root for another line, returning the opponent's first move
+    pub fn next_line(&mut self, chapter: &StudyChapter) -> Option<NodeId> {
+        self.node = ROOT;
+        self.line_mistakes = 0;
+        self.tried = false;
+        self.move_started = Instant::now();
+        self.opponent_reply(chapter)
+    }
+
+    /// Whether the line played has come to its end, counting it as passed
+    pub fn finished(&mut self, chapter: &StudyChapter) -> bool {
+        let finished = chapter.node(self.node).children.is_empty();
+        if finished {
+            self.session.lines_passed += 1;
+        }
+        finished
+    }
+
+    /// Time left to find the current move, None without a time limit
+    pub fn time_left(&self) -> Option<Duration> {
+        (self.settings.move_seconds > 0)
+            .then(|| Duration::from_secs(self.settings.move_seconds.into()).saturating_sub(self.move_started.elapsed()))
+    }
+
+    /// Count running out of time as a mistake, restarting the clock for another try
+    pub fn time_out(&mut self, chapter: &mut StudyChapter) -> DrillStep {
+        self.session.timeouts += 1;
+        self.record_try(chapter, false);
+        self.mistake()
+    }
+
+    /// The chapter's moves from the current position, if hints are allowed. The position
+    /// counts as not recalled.
+    pub fn hint(&mut self, chapter: &mut StudyChapter) -> Option<Vec<String>> {
+        if !self.settings.hints {
+            return None;
+        }
+        self.session.hints += 1;
+        self.record_try(chapter, false);
+        let node = chapter.node(self.node);
+        Some(node.children.iter().filter_map(|&c| chapter.node(c).move_record.as_ref().map(|m| m.san.clone())).collect())
+    }
+
+    /// Check the user's move `uci`, recording the first try at the position in the chapter
+    pub fn play(&mut self, chapter: &mut StudyChapter, uci: &str) -> DrillStep {
+        let found = chapter
+            .node(self.node)
+            .children
+            .iter()
+            .copied()
+            .find(|&child| chapter.node(child).move_record.as_ref().is_some_and(|m| m.uci == uci));
+        self.record_try(chapter, found.is_some());
+        let Some(child) = found else {
+            return self.mistake();
+        };
+        self.node = child;
+        self.tried = false;
+        self.move_started = Instant::now();
+        DrillStep::Correct(self.opponent_reply(chapter))
+    }
+
+    /// Count the first try at the current position in the chapter and the session
+    fn record_try(&mut self, chapter: &mut StudyChapter, correct: bool) {
+        if std::mem::replace(&mut self.tried, true) {
+            return;
+        }
+        self.session.positions += 1;
+        let stats = &mut chapter.node_mut(self.node).drill;
+        if correct {
+            self.session.first_try += 1;
+            stats.correct += 1;
+        } else {
+            stats.wrong += 1;
+        }
+    }
+
+    fn mistake(&mut self) -> DrillStep {
+        self.session.mistakes += 1;
+        self.line_mistakes += 1;
+        self.move_started = Instant::now();
+        if self.line_mistakes > self.settings.allowed_mistakes {
+            self.session.lines_failed += 1;
+            DrillStep::Failed
+        } else {
+            DrillStep::Wrong
+        }
+    }
+
+    /// Move on by one of the chapter's moves when the opponent is to move, preferring
+    /// replies the line goes on after
+    fn opponent_reply(&mut self, chapter: &StudyChapter) -> Option<NodeId> {
+        let node = chapter.node(self.node);
+        if chapter.side_to_move_is_mine(&node.fen) || node.children.is_empty() {
+            return None;
+        }
+        let going_on: Vec<NodeId> = node.children.iter().copied().filter(|&c| !chapter.node(c).children.is_empty()).collect();
+        let choices = if going_on.is_empty() { &node.children } else { &going_on };
+        self.node = *fastrand::choice(choices)?;
+        self.move_started = Instant::now();
+        Some(self.node)
+    }
+}
+
+
+/// A position of the study the user often fails to find their move in
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeakSpot {
+    pub chapter: usize,
+    /// Child indices to the position in its chapter
+    pub path: Vec<usize>,
+    /// SAN moves leading to it
+    pub line: Vec<String>,
+    /// Ply of the game the chapter starts at, which the first move of `line` is
+    pub start_ply: usize,
+    pub stats: DrillStats,
+}
+
+impl WeakSpot {
+    /// The line with move numbers, like "1. e4 c5 2. Nf3"
+    pub fn line_text(&self) -> String {
+        numbered_line(self.start_ply, &self.line)
+    }
+}
+
+impl Study {
+    /// Drilled positions recalled less than every time, the worst first
+    pub fn weak_spots(&self) -> Vec<WeakSpot> {
+        let mut spots = Vec::new();
+        for (idx, chapter) in self.chapters.iter().enumerate() {
+            for (id, node) in chapter.nodes().enumerate() {
+                if node.drill.wrong == 0 {
+                    continue;
+                }
+                let path = chapter.path_to(id);
+                let mut line = Vec::new();
+                let mut at = ROOT;
+                for &i in &path {
+                    at = chapter.node(at).children[i];
+                    line.extend(chapter.node(at).move_record.as_ref().map(|m| m.san.clone()));
+                }
+                spots.push(WeakSpot {
+                    chapter: idx,
+                    path,
+                    line,
+                    start_ply: fen_ply(&chapter.root().fen),
+                    stats: node.drill,
+                });
+            }
+        }
+        spots.sort_by(|a, b| {
+            let recall = |s: &WeakSpot| s.stats.recall().unwrap_or(1.0);
+            recall(a).total_cmp(&recall(b)).then(b.stats.attempts().cmp(&a.stats.attempts()))
+        });
+        spots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    #[test]
+    fn test_drill_records_recall() {
+        let mut study = Study::new("Repertoire".to_string());
+        let chapter = study.current_chapter_mut();
+        chapter.orientation = PlayerColor::Black;
+        let mut game = GameState::new();
+        for san in ["e4", "c5", "Nf3", "d6"] {
+            let record = game.make_move_san(san).unwrap();
+            chapter.add_move(record, game.fen());
+        }
+
+        assert!(RepertoireDrill::can_drill(study.current_chapter()));
+
+        // White starts, so the drill opens with the opponent's 1. e4
+        let settings = DrillSettings::default();
+        let (mut drill, reply) = RepertoireDrill::start(0, study.current_chapter(), settings);
+        assert_eq!(reply, Some(study.current_chapter().node_at(&[0])));
+        assert_eq!(drill.time_left(), None);
+        let chapter = study.current_chapter_mut();
+        assert_eq!(drill.play(chapter, "e7e5"), DrillStep::Wrong);
+        assert_eq!(drill.play(chapter, "e7e5"), DrillStep::Wrong);
+        assert_eq!(drill.play(chapter, "c7c5"), DrillStep::Correct(Some(chapter.node_at(&[0, 0, 0]))));
+        assert_eq!(drill.hint(chapter), Some(vec!["d6".to_string()]));
+        assert_eq!(drill.play(chapter, "d7d6"), DrillStep::Correct(None));
+        assert!(drill.finished(chapter));
+        assert_eq!(drill.session.score(), 0);
+
+        // The third mistake in a line fails it with two allowed
+        drill.next_line(chapter);
+        assert_eq!(drill.play(chapter, "c7c5"), DrillStep::Correct(Some(chapter.node_at(&[0, 0, 0]))));
+        assert_eq!(drill.time_out(chapter), DrillStep::Wrong);
+        assert_eq!(drill.play(chapter, "e7e5"), DrillStep::Wrong);
+        assert_eq!(drill.play(chapter, "e7e5"), DrillStep::Failed);
+        let session = drill.session;
+        assert_eq!((session.lines_passed, session.lines_failed, session.mistakes, session.timeouts), (1, 1, 5, 1));
+        assert_eq!((session.positions, session.first_try, session.score()), (4, 1, 25));
+
+        let after_e4 = chapter.node(chapter.node_at(&[0])).drill;
+        assert_eq!(after_e4, DrillStats { correct: 1, wrong: 1 });
+        let spots = study.weak_spots();
+        assert_eq!(spots.len(), 2);
+        assert_eq!(spots[0].line_text(), "1. e4 c5 2. Nf3");
+        assert_eq!(spots[0].stats.recall(), Some(0.0));
+        assert_eq!(spots[1].stats.recall(), Some(0.5));
+    }
+}
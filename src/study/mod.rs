@@ -1,95 +1,213 @@
-use crate::game::MoveRecord;
+use crate::error::AppError;
+use crate::game::{position_key, MoveRecord, PgnStyle, PlayerColor};
+use crate::i18n::{tr, tr_args};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+mod annotate;
+mod audit;
+mod drill;
+mod movetext;
+mod print;
+
+pub use annotate::GameAnnotation;
+pub use audit::{AuditWarning, RepertoireAudit};
+pub use drill::{DrillSession, DrillSettings, DrillStats, DrillStep, RepertoireDrill};
+
+/// Index of a node in its chapter's arena
+pub type NodeId = usize;
+
+/// The root node of every chapter
+pub const ROOT: NodeId = 0;
+
+const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Kinds of annotation glyph; a node carries at most one move and one evaluation glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphGroup {
+    /// Judgement of the move that led to the node
+    Move,
+    /// Assessment of the position
+    Evaluation,
+    /// Informant-style symbols, any number of which can be combined
+    Symbol,
+}
+
+/// Annotation glyphs offered in studies: PGN NAG number, symbol, group and the key of its description
+pub const GLYPHS: &[(u8, &str, GlyphGroup, &str)] = &[
+    (1, "!", GlyphGroup::Move, "glyph.good"),
+    (2, "?", GlyphGroup::Move, "glyph.mistake"),
+    (3, "!!", GlyphGroup::Move, "glyph.brilliant"),
+    (4, "??", GlyphGroup::Move, "glyph.blunder"),
+    (5, "!?", GlyphGroup::Move, "glyph.interesting"),
+    (6, "?!", GlyphGroup::Move, "glyph.dubious"),
+    (10, "=", GlyphGroup::Evaluation, "glyph.equal"),
+    (13, "∞", GlyphGroup::Evaluation, "glyph.unclear"),
+    (14, "+=", GlyphGroup::Evaluation, "glyph.white_slightly"),
+    (15, "=+", GlyphGroup::Evaluation, "glyph.black_slightly"),
+    (16, "±", GlyphGroup::Evaluation, "glyph.white_better"),
+    (17, "∓", GlyphGroup::Evaluation, "glyph.black_better"),
+    (18, "+−", GlyphGroup::Evaluation, "glyph.white_winning"),
+    (19, "−+", GlyphGroup::Evaluation, "glyph.black_winning"),
+    (7, "□", GlyphGroup::Symbol, "glyph.only_move"),
+    (22, "⨀", GlyphGroup::Symbol, "glyph.zugzwang"),
+    (32, "⟳", GlyphGroup::Symbol, "glyph.development"),
+    (36, "↑", GlyphGroup::Symbol, "glyph.initiative"),
+    (40, "→", GlyphGroup::Symbol, "glyph.attack"),
+    (44, "=∞", GlyphGroup::Symbol, "glyph.compensation"),
+    (132, "⇆", GlyphGroup::Symbol, "glyph.counterplay"),
+    (138, "⊕", GlyphGroup::Symbol, "glyph.time_trouble"),
+    (140, "∆", GlyphGroup::Symbol, "glyph.with_idea"),
+    (146, "N", GlyphGroup::Symbol, "glyph.novelty"),
+];
+
+fn glyph_group(nag: u8) -> Option<GlyphGroup> {
+    GLYPHS.iter().find(|g| g.0 == nag).map(|g| g.2)
+}
+
+/// Glyphs as written after their move: move glyphs attached, the others spaced, unknown ones as `$n`
+pub fn glyph_text(nags: &[u8]) -> String {
+    let mut text = String::new();
+    for &nag in nags {
+        match GLYPHS.iter().find(|g| g.0 == nag) {
+            Some(&(_, symbol, GlyphGroup::Move, _)) => text.push_str(symbol),
+            Some(&(_, symbol, _, _)) => text.push_str(&format!(" {}", symbol)),
+            None => text.push_str(&format!(" ${}", nag)),
+        }
+    }
+    text
+}
 
 /// A node in the study tree - represents a position with comments and child variations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StudyNode {
-    /// Index into the parent's children (for identification)
-    pub id: usize,
     /// The move that leads to this position (None for root)
     pub move_record: Option<MoveRecord>,
     /// Position in FEN
     pub fen: String,
     /// User comments on this position
     pub comments: Vec<String>,
-    /// Child variations from this position
-    pub children: Vec<StudyNode>,
+    /// PGN annotation glyphs (NAG numbers) on the move and position, in ascending order
+    #[serde(default)]
+    pub nags: Vec<u8>,
+    /// The node this move was played from (None for root)
+    pub parent: Option<NodeId>,
+    /// Recall of the move to play from this position in repertoire drills
+    #[serde(default)]
+    pub drill: DrillStats,
+    /// Warning left by the last engine audit of the chapter
+    #[serde(default, deserialize_with = "audit_warning")]
+    pub audit: Option<AuditWarning>,
+    /// Child variations from this position, main line first
+    pub children: Vec<NodeId>,
 }
 
 impl StudyNode {
-    pub fn new_root(fen: String) -> Self {
+    fn new(parent: Option<NodeId>, move_record: Option<MoveRecord>, fen: String) -> Self {
         Self {
-            id: 0,
-            move_record: None,
+            move_record,
             fen,
             comments: Vec::new(),
+            nags: Vec::new(),
+            parent,
+            drill: DrillStats::default(),
+            audit: None,
             children: Vec::new(),
         }
     }
+}
 
-    pub fn new_child(id: usize, move_record: MoveRecord, fen: String) -> Self {
-        Self {
-            id,
-            move_record: Some(move_record),
-            fen,
-            comments: Vec::new(),
-            children: Vec::new(),
-        }
-    }
+/// An audit warning, dropping the already worded text older versions saved in its place
+fn audit_warning<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<AuditWarning>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
+}
 
-    /// Add a child node
-    pub fn add_child(&mut self, move_record: MoveRecord, fen: String) -> usize {
-        let id = self.children.len();
-        self.children.push(StudyNode::new_child(id, move_record, fen));
-        id
-    }
+/// Node of the nested tree studies were saved as before chapters kept their nodes in an arena
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LegacyNode {
+    move_record: Option<MoveRecord>,
+    fen: String,
+    comments: Vec<String>,
+    children: Vec<LegacyNode>,
+}
 
-    /// Get all lines (sequences of moves) from this node
-    pub fn get_lines(&self) -> Vec<Vec<String>> {
-        let mut lines = Vec::new();
-        
-        for child in &self.children {
-            let child_lines = child.get_lines_recursive();
-            for mut line in child_lines {
-                line.insert(0, child.move_record.as_ref().unwrap().san.clone());
-                lines.push(line);
-            }
-        }
-        
-        if lines.is_empty() {
-            lines.push(Vec::new());
+/// A study chapter as saved: the node arena, or the nested `root` tree of older files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredChapter {
+    id: usize,
+    name: String,
+    #[serde(default)]
+    nodes: Vec<StudyNode>,
+    #[serde(default)]
+    root: Option<LegacyNode>,
+    current_path: Vec<usize>,
+    #[serde(default)]
+    orientation: PlayerColor,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+}
+
+impl From<StudyChapter> for StoredChapter {
+    fn from(chapter: StudyChapter) -> Self {
+        Self {
+            id: chapter.id,
+            name: chapter.name,
+            nodes: chapter.nodes,
+            root: None,
+            current_path: chapter.current_path,
+            orientation: chapter.orientation,
+            headers: chapter.headers,
         }
-        
-        lines
     }
+}
 
-    fn get_lines_recursive(&self) -> Vec<Vec<String>> {
-        let mut lines = Vec::new();
-        
-        for child in &self.children {
-            let child_lines = child.get_lines_recursive();
-            for mut line in child_lines {
-                line.insert(0, child.move_record.as_ref().unwrap().san.clone());
-                lines.push(line);
+impl TryFrom<StoredChapter> for StudyChapter {
+    type Error = String;
+
+    fn try_from(stored: StoredChapter) -> Result<Self, String> {
+        let mut chapter = Self {
+            id: stored.id,
+            name: stored.name,
+            nodes: stored.nodes,
+            current_path: stored.current_path,
+            orientation: stored.orientation,
+            headers: stored.headers,
+        };
+        if let Some(root) = stored.root.filter(|_| chapter.nodes.is_empty()) {
+            chapter.nodes.push(StudyNode::new(None, None, root.fen));
+            chapter.nodes[ROOT].comments = root.comments;
+            // Flatten breadth-first so each node's children are added in order
+            let mut pending: std::collections::VecDeque<_> = root.children.into_iter().map(|c| (ROOT, c)).collect();
+            while let Some((parent, node)) = pending.pop_front() {
+                let id = chapter.push_node(parent, node.move_record, node.fen);
+                chapter.nodes[id].comments = node.comments;
+                pending.extend(node.children.into_iter().map(|c| (id, c)));
             }
         }
-        
-        if lines.is_empty() {
-            lines.push(Vec::new());
+        if chapter.nodes.is_empty() {
+            chapter.nodes.push(StudyNode::new(None, None, STARTING_FEN.to_string()));
         }
-        
-        lines
+        chapter.check_links()?;
+        Ok(chapter)
     }
 }
 
-/// A study chapter - contains a tree of positions
+/// A study chapter - contains a tree of positions, stored as an arena of nodes linked by id
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "StoredChapter", into = "StoredChapter")]
 pub struct StudyChapter {
     pub id: usize,
     pub name: String,
-    pub root: StudyNode,
+    /// Every node of the tree, the root first
+    nodes: Vec<StudyNode>,
     /// Current position in the tree (path of child indices)
     pub current_path: Vec<usize>,
+    /// Side the chapter is studied from, used to orient the board
+    pub orientation: PlayerColor,
+    /// PGN tag pairs of the chapter's game (White, Black, Event, Annotator...), in file order.
+    /// The start position is the root's FEN rather than a tag.
+    pub headers: Vec<(String, String)>,
 }
 
 impl StudyChapter {
@@ -97,35 +215,137 @@ impl StudyChapter {
         Self {
             id,
             name,
-            root: StudyNode::new_root("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string()),
+            nodes: vec![StudyNode::new(None, None, STARTING_FEN.to_string())],
             current_path: Vec::new(),
+            orientation: PlayerColor::White,
+            headers: Vec::new(),
         }
     }
 
-    /// Get the current node based on current_path
-    pub fn current_node(&self) -> &StudyNode {
-        let mut node = &self.root;
-        for &idx in &self.current_path {
-            if idx < node.children.len() {
-                node = &node.children[idx];
-            } else {
-                break;
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Take the tags of an imported game, leaving out the start position the root already has
+    fn set_pgn_headers(&mut self, headers: &[(String, String)]) {
+        self.headers = headers
+            .iter()
+            .filter(|(key, _)| key != "FEN" && key != "SetUp")
+            .cloned()
+            .collect();
+    }
+
+    /// The chapter's tags as written to PGN: the seven tag roster first, filled in from the
+    /// study where the chapter has none, then its other tags and the start position if it
+    /// isn't the standard one
+    fn pgn_headers(&self, study: &Study) -> Vec<(String, String)> {
+        let date = study.created_at.get(..10).unwrap_or_default().replace('-', ".");
+        let defaults = [
+            ("Event", format!("{}: {}", study.name, self.name)),
+            ("Site", "?".to_string()),
+            ("Date", date),
+            ("Round", "?".to_string()),
+            ("White", "?".to_string()),
+            ("Black", "?".to_string()),
+            ("Result", "*".to_string()),
+        ];
+        let mut headers: Vec<(String, String)> = defaults
+            .iter()
+            .map(|(key, default)| (key.to_string(), self.header(key).map_or_else(|| default.clone(), str::to_string)))
+            .collect();
+        headers.extend(
+            self.headers
+                .iter()
+                .filter(|(key, _)| !defaults.iter().any(|(roster, _)| roster == key))
+                .cloned(),
+        );
+        if self.root().fen != STARTING_FEN {
+            headers.push(("SetUp".to_string(), "1".to_string()));
+            headers.push(("FEN".to_string(), self.root().fen.clone()));
+        }
+        headers
+    }
+
+    pub fn node(&self, id: NodeId) -> &StudyNode {
+        &self.nodes[id]
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut StudyNode {
+        &mut self.nodes[id]
+    }
+
+    /// Whether the side the chapter is studied from is to move in `fen`
+    fn side_to_move_is_mine(&self, fen: &str) -> bool {
+        let black_to_move = fen.split_whitespace().nth(1) == Some("b");
+        (self.orientation == PlayerColor::Black) == black_to_move
+    }
+
+    pub fn root(&self) -> &StudyNode {
+        &self.nodes[ROOT]
+    }
+
+    /// All nodes, in the order they were added
+    pub fn nodes(&self) -> impl Iterator<Item = &StudyNode> {
+        self.nodes.iter()
+    }
+
+    /// Start the chapter from another position
+    pub fn set_root_fen(&mut self, fen: String) {
+        self.nodes[ROOT].fen = fen;
+    }
+
+    fn push_node(&mut self, parent: NodeId, move_record: Option<MoveRecord>, fen: String) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(StudyNode::new(Some(parent), move_record, fen));
+        self.nodes[parent].children.push(id);
+        id
+    }
+
+    /// Check that the saved arena is one tree: the root first, and every other node reached
+    /// exactly once as a child of the parent it names
+    fn check_links(&self) -> Result<(), String> {
+        if self.nodes[ROOT].parent.is_some() {
+            return Err(format!("chapter {}: the root has a parent", self.id));
+        }
+        let mut reached = vec![false; self.nodes.len()];
+        reached[ROOT] = true;
+        for (id, node) in self.nodes.iter().enumerate() {
+            for &child in &node.children {
+                let linked = self.nodes.get(child).is_some_and(|c| c.parent == Some(id));
+                if !linked || std::mem::replace(&mut reached[child], true) {
+                    return Err(format!("chapter {}: node {id} has a broken child link to {child}", self.id));
+                }
             }
         }
-        node
+        match reached.iter().position(|&r| !r) {
+            Some(id) => Err(format!("chapter {}: node {id} is not in the tree", self.id)),
+            None => Ok(()),
+        }
     }
 
-    /// Get the current node mutably
-    fn current_node_mut(&mut self) -> &mut StudyNode {
-        let mut node = &mut self.root;
-        for &idx in &self.current_path {
-            if idx < node.children.len() {
-                node = &mut node.children[idx];
-            } else {
-                break;
+    /// Node reached by following `path` from the root, stopping at the first invalid index
+    pub fn node_at(&self, path: &[usize]) -> NodeId {
+        let mut id = ROOT;
+        for &idx in path {
+            match self.nodes[id].children.get(idx) {
+                Some(&child) => id = child,
+                None => break,
             }
         }
-        node
+        id
+    }
+
+    /// Get the id of the current node based on current_path
+    pub fn current_id(&self) -> NodeId {
+        self.node_at(&self.current_path)
+    }
+
+    /// Get the current node based on current_path
+    pub fn current_node(&self) -> &StudyNode {
+        &self.nodes[self.current_id()]
     }
 
     /// Navigate to parent
@@ -152,30 +372,103 @@ impl StudyChapter {
     /// Add a move at current position
     /// Returns true if move was added, false if move already exists (navigates to it)
     pub fn add_move(&mut self, move_record: MoveRecord, fen: String) -> bool {
-        let current = self.current_node_mut();
-        
+        let current = self.current_id();
+
         // Check if this move already exists as a child
-        for (idx, child) in current.children.iter().enumerate() {
-            if let Some(ref child_move) = child.move_record {
-                if child_move.uci == move_record.uci {
-                    // Move exists, navigate to it
-                    self.current_path.push(idx);
-                    return false;
-                }
-            }
+        if let Some(idx) = self.child_index(&move_record.uci) {
+            // Move exists, navigate to it
+            self.current_path.push(idx);
+            return false;
         }
-        
+
         // Add new child
-        let child_id = current.children.len();
-        current.children.push(StudyNode::new_child(child_id, move_record, fen));
-        self.current_path.push(child_id);
+        self.push_node(current, Some(move_record), fen);
+        self.current_path.push(self.nodes[current].children.len() - 1);
+        true
+    }
+
+    /// Index of the current node's child reached by the UCI move `uci`, if the tree has it
+    pub fn child_index(&self, uci: &str) -> Option<usize> {
+        self.nodes[self.current_id()]
+            .children
+            .iter()
+            .position(|&child| self.nodes[child].move_record.as_ref().is_some_and(|m| m.uci == uci))
+    }
+
+    /// Undo adding node `id`, if it is still the newest node and nothing was added after it.
+    /// The current path moves to its parent.
+    pub fn remove_newest_node(&mut self, id: NodeId) -> bool {
+        if id == ROOT || id + 1 != self.nodes.len() || !self.nodes[id].children.is_empty() {
+            return false;
+        }
+        let parent = self.nodes[id].parent.unwrap_or(ROOT);
+        self.nodes.pop();
+        self.nodes[parent].children.retain(|&child| child != id);
+        self.current_path = self.path_to(parent);
         true
     }
 
+    /// Child indices leading from the root to `id`
+    pub fn path_to(&self, id: NodeId) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut id = id;
+        while let Some(parent) = self.nodes[id].parent {
+            path.push(self.nodes[parent].children.iter().position(|&c| c == id).unwrap_or(0));
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Hash of the positions from the root to each node, by node id
+    fn position_path_hashes(&self) -> Vec<u64> {
+        use std::hash::{Hash, Hasher};
+
+        // Parents are always added before their children
+        let mut hashes: Vec<u64> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            node.parent.map(|p| hashes[p]).hash(&mut hasher);
+            position_key(&node.fen).hash(&mut hasher);
+            hashes.push(hasher.finish());
+        }
+        hashes
+    }
+
     /// Add a comment to current position
     pub fn add_comment(&mut self, comment: String) {
-        let current = self.current_node_mut();
-        current.comments.push(comment);
+        let current = self.current_id();
+        self.nodes[current].comments.push(comment);
+    }
+
+    /// Add the glyph `nag` to the current node, replacing another of its group, or remove it if present
+    pub fn toggle_nag(&mut self, nag: u8) {
+        let current = self.current_id();
+        let nags = &mut self.nodes[current].nags;
+        if let Some(i) = nags.iter().position(|&n| n == nag) {
+            nags.remove(i);
+            return;
+        }
+        let group = glyph_group(nag);
+        if group != Some(GlyphGroup::Symbol) {
+            nags.retain(|&n| glyph_group(n) != group);
+        }
+        nags.push(nag);
+        nags.sort_unstable();
+    }
+
+    /// UCI moves from the root to the current node
+    pub fn current_line_uci(&self) -> Vec<String> {
+        let mut id = ROOT;
+        let mut moves = Vec::new();
+        for &idx in &self.current_path {
+            let Some(&child) = self.nodes[id].children.get(idx) else {
+                break;
+            };
+            id = child;
+            moves.extend(self.nodes[id].move_record.as_ref().map(|m| m.uci.clone()));
+        }
+        moves
     }
 
     /// Get current FEN
@@ -183,14 +476,55 @@ impl StudyChapter {
         &self.current_node().fen
     }
 
-    /// Get the main line (longest variation)
-    pub fn get_main_line(&self) -> Vec<String> {
-        self.root.get_lines()
+    /// Nodes of every line from the root to a leaf, the root left out
+    fn line_ids(&self) -> Vec<Vec<NodeId>> {
+        let mut lines = Vec::new();
+        let mut pending = vec![(ROOT, Vec::new())];
+        while let Some((id, line)) = pending.pop() {
+            let node = &self.nodes[id];
+            if node.children.is_empty() {
+                lines.push(line);
+                continue;
+            }
+            // Reversed so the main line comes out first
+            for &child in node.children.iter().rev() {
+                let mut line = line.clone();
+                line.push(child);
+                pending.push((child, line));
+            }
+        }
+        lines
+    }
+
+    /// SAN moves of every line from the root to a leaf
+    pub fn get_lines(&self) -> Vec<Vec<String>> {
+        self.line_ids()
+            .into_iter()
+            .map(|line| {
+                line.into_iter()
+                    .filter_map(|id| self.nodes[id].move_record.as_ref().map(|r| r.san.clone()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Nodes of the main line (longest variation)
+    fn main_line_ids(&self) -> Vec<NodeId> {
+        self.line_ids()
             .into_iter()
             .max_by_key(|line| line.len())
             .unwrap_or_default()
     }
 
+    /// FEN at the end of the main line (following the first child at each node)
+    pub fn main_line_end_fen(&self) -> &str {
+        let mut id = ROOT;
+        while let Some(&child) = self.nodes[id].children.first() {
+            id = child;
+        }
+        &self.nodes[id].fen
+    }
+
     /// Go to start
     pub fn go_to_start(&mut self) {
         self.current_path.clear();
@@ -207,6 +541,16 @@ impl StudyChapter {
     }
 }
 
+/// Another chapter already holding a line of the current one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMatch {
+    pub chapter: usize,
+    /// Child indices to the matching position in that chapter
+    pub path: Vec<usize>,
+    /// Reached through the same positions, rather than by another move order
+    pub exact: bool,
+}
+
 /// A complete study with multiple chapters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Study {
@@ -216,6 +560,10 @@ pub struct Study {
     pub current_chapter: usize,
     pub created_at: String,
     pub updated_at: String,
+    /// Read-only, e.g. a study handed out by a coach: it can be browsed and trained, but edits
+    /// need a personal copy
+    #[serde(default)]
+    pub locked: bool,
 }
 
 impl Study {
@@ -228,8 +576,9 @@ impl Study {
             current_chapter: 0,
             created_at: now.clone(),
             updated_at: now,
+            locked: false,
         };
-        study.add_chapter("Chapter 1".to_string());
+        study.add_chapter(tr_args("study.chapter_n", &[&1]));
         study
     }
 
@@ -240,6 +589,33 @@ impl Study {
         id
     }
 
+    /// SAN moves the study gives from each position, across all chapters, by position key
+    pub fn moves_by_position(&self) -> HashMap<String, Vec<String>> {
+        Self::collect_moves(self.chapters.iter())
+    }
+
+    /// Like `moves_by_position`, over the chapters studied from `side` only
+    pub fn repertoire(&self, side: PlayerColor) -> HashMap<String, Vec<String>> {
+        Self::collect_moves(self.chapters.iter().filter(|c| c.orientation == side))
+    }
+
+    fn collect_moves<'a>(chapters: impl Iterator<Item = &'a StudyChapter>) -> HashMap<String, Vec<String>> {
+        let mut moves: HashMap<String, Vec<String>> = HashMap::new();
+        for chapter in chapters {
+            for node in chapter.nodes() {
+                let known = moves.entry(position_key(&node.fen)).or_default();
+                for &child in &node.children {
+                    if let Some(record) = &chapter.node(child).move_record {
+                        if !known.contains(&record.san) {
+                            known.push(record.san.clone());
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
     pub fn current_chapter(&self) -> &StudyChapter {
         &self.chapters[self.current_chapter]
     }
@@ -248,6 +624,38 @@ impl Study {
         &mut self.chapters[self.current_chapter]
     }
 
+    /// Another chapter with the line up to the current position, or failing that with the
+    /// position reached by a transposition
+    pub fn find_duplicate_line(&self) -> Option<LineMatch> {
+        let chapter = self.current_chapter();
+        let current = chapter.current_id();
+        if current == ROOT {
+            return None;
+        }
+        let hash = chapter.position_path_hashes()[current];
+        let key = position_key(chapter.current_fen());
+
+        let mut transposition = None;
+        for (idx, other) in self.chapters.iter().enumerate().filter(|(idx, _)| *idx != self.current_chapter) {
+            let hashes = other.position_path_hashes();
+            if let Some(id) = hashes.iter().position(|&h| h == hash) {
+                return Some(LineMatch {
+                    chapter: idx,
+                    path: other.path_to(id),
+                    exact: true,
+                });
+            }
+            if transposition.is_none() {
+                transposition = other.nodes.iter().position(|n| position_key(&n.fen) == key).map(|id| LineMatch {
+                    chapter: idx,
+                    path: other.path_to(id),
+                    exact: false,
+                });
+            }
+        }
+        transposition
+    }
+
     pub fn switch_chapter(&mut self, idx: usize) -> bool {
         if idx < self.chapters.len() {
             self.current_chapter = idx;
@@ -257,41 +665,68 @@ impl Study {
         }
     }
 
+    /// An unlocked copy of the study, saved under its own id, for editing a locked one
+    pub fn personal_copy(&self) -> Study {
+        let now = chrono::Local::now();
+        Study {
+            id: format!("study_{}", now.timestamp_millis()),
+            name: tr_args("study.copy_name", &[&self.name]),
+            created_at: now.to_rfc3339(),
+            updated_at: now.to_rfc3339(),
+            locked: false,
+            ..self.clone()
+        }
+    }
+
     pub fn update_timestamp(&mut self) {
         self.updated_at = chrono::Local::now().to_rfc3339();
     }
 
     /// Export to PGN
-    pub fn to_pgn(&self) -> String {
+    pub fn to_pgn(&self, style: PgnStyle) -> String {
         let mut pgn = String::new();
         
-        pgn.push_str(&format!("[Event \"{}\"]\n", self.name));
-        pgn.push_str("[Site \"Stockfish Chess\"]\n");
-        pgn.push_str(&format!("[Date \"{}\"]\n", &self.created_at[..10]));
-        
-        for chapter in &self.chapters {
+        for (i, chapter) in self.chapters.iter().enumerate() {
+            if i > 0 {
+                pgn.push('\n');
+            }
+            let headers = chapter.pgn_headers(self);
+            for (name, value) in &headers {
+                pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+            }
             pgn.push('\n');
-            pgn.push_str(&format!("[Chapter \"{}\"]\n", chapter.name));
             
             // Add comments for starting position
-            if !chapter.root.comments.is_empty() {
-                for comment in &chapter.root.comments {
+            if !chapter.root().comments.is_empty() {
+                for comment in &chapter.root().comments {
                     pgn.push_str(&format!("{{ {} }} ", comment));
                 }
                 pgn.push('\n');
             }
             
-            // Export main line
-            let line = chapter.get_main_line();
-            for (i, san) in line.iter().enumerate() {
+            // Export main line with its glyphs
+            let line = chapter.main_line_ids();
+            for (i, &id) in line.iter().enumerate() {
+                let node = chapter.node(id);
+                let Some(record) = &node.move_record else {
+                    continue;
+                };
                 if i % 2 == 0 {
                     pgn.push_str(&format!("{}. ", i / 2 + 1));
                 }
-                pgn.push_str(san);
+                pgn.push_str(&style.move_text(&record.san, None));
+                if style.nag_glyphs {
+                    pgn.push_str(&glyph_text(&node.nags));
+                } else {
+                    for nag in &node.nags {
+                        pgn.push_str(&format!(" ${}", nag));
+                    }
+                }
                 pgn.push(' ');
             }
             
-            pgn.push_str("*\n");
+            pgn.push_str(chapter.header("Result").unwrap_or("*"));
+            pgn.push('\n');
         }
         
         pgn
@@ -300,8 +735,147 @@ impl Study {
 
 impl Default for Study {
     fn default() -> Self {
-        Self::new("Untitled Study".to_string())
+        Self::new(tr("study.untitled").to_string())
+    }
+}
+
+/// The nodes of a chapter an engine review was started on, to tell whether its results still
+/// fit when it finishes: the study may have been switched, or nodes removed and others added
+/// under the same ids
+#[derive(Debug, Clone, Default)]
+struct ReviewedNodes {
+    study_id: String,
+    chapter: usize,
+    /// Each reviewed node with the position it held
+    nodes: Vec<(NodeId, String)>,
+}
+
+impl ReviewedNodes {
+    /// `ids` of the study's current chapter
+    fn new(study: &Study, ids: &[NodeId]) -> Self {
+        let chapter = study.current_chapter();
+        Self {
+            study_id: study.id.clone(),
+            chapter: study.current_chapter,
+            nodes: ids.iter().map(|&id| (id, chapter.node(id).fen.clone())).collect(),
+        }
+    }
+
+    fn chapter_in<'a>(&self, study: &'a mut Study) -> Option<&'a mut StudyChapter> {
+        if study.id != self.study_id {
+            return None;
+        }
+        let chapter = study.chapters.get_mut(self.chapter)?;
+        let unchanged = self.nodes.iter().all(|(id, fen)| chapter.nodes.get(*id).is_some_and(|n| &n.fen == fen));
+        unchanged.then_some(chapter)
+    }
+}
+
+/// Lightweight description of a saved study for the load dialog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudySummary {
+    pub id: String,
+    pub name: String,
+    /// (chapter name, FEN at the end of the chapter's main line)
+    pub chapters: Vec<(String, String)>,
+    pub updated_at: String,
+}
+
+impl From<&Study> for StudySummary {
+    fn from(study: &Study) -> Self {
+        Self {
+            id: study.id.clone(),
+            name: study.name.clone(),
+            chapters: study
+                .chapters
+                .iter()
+                .map(|c| (c.name.clone(), c.main_line_end_fen().to_string()))
+                .collect(),
+            updated_at: study.updated_at.clone(),
+        }
+    }
+}
+
+/// How studies are written to disk. Both formats are always readable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StudyFormat {
+    /// Pretty-printed JSON that can be read and edited by hand
+    #[default]
+    Json,
+    /// Compact JSON compressed with zstd behind a version header, far smaller for big
+    /// repertoire trees
+    Compact,
+}
+
+impl StudyFormat {
+    pub fn all() -> &'static [StudyFormat] {
+        &[StudyFormat::Json, StudyFormat::Compact]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StudyFormat::Json => tr("study.format_json"),
+            StudyFormat::Compact => tr("study.format_compact"),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            StudyFormat::Json => "json",
+            StudyFormat::Compact => "study",
+        }
+    }
+
+    fn from_extension(extension: &std::ffi::OsStr) -> Option<Self> {
+        Self::all().iter().copied().find(|f| extension == f.extension())
+    }
+
+    fn encode(&self, study: &Study) -> Result<Vec<u8>, std::io::Error> {
+        match self {
+            StudyFormat::Json => Ok(serde_json::to_vec_pretty(study)?),
+            StudyFormat::Compact => {
+                let mut bytes = COMPACT_MAGIC.to_vec();
+                bytes.push(COMPACT_VERSION);
+                bytes.extend(zstd::encode_all(serde_json::to_vec(study)?.as_slice(), 3)?);
+                Ok(bytes)
+            }
+        }
     }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Study, std::io::Error> {
+        match self {
+            StudyFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            StudyFormat::Compact => match bytes.strip_prefix(COMPACT_MAGIC) {
+                Some([COMPACT_VERSION, body @ ..]) => Ok(serde_json::from_slice(&zstd::decode_all(body)?)?),
+                Some(_) => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "study written by a newer version of the app",
+                )),
+                None => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a compact study file")),
+            },
+        }
+    }
+}
+
+/// Start of a compact study file. The body is self-describing so fields added later default
+/// when older files are read.
+const COMPACT_MAGIC: &[u8] = b"SFCSTUDY";
+/// Version of the compact body after the magic; bump it when the body's encoding changes
+const COMPACT_VERSION: u8 = 1;
+
+/// Index file kept next to the studies so listing them doesn't parse every full tree
+const INDEX_FILE: &str = "index.meta";
+
+/// Extension added to a study file for the copy kept from before its last save
+const BACKUP_EXTENSION: &str = "bak";
+
+/// Cached summary of one study file, valid while the file's size and mtime are unchanged.
+/// A file that could not be read has none, so it isn't parsed again until it changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    len: u64,
+    modified: std::time::SystemTime,
+    summary: Option<StudySummary>,
 }
 
 /// Manager for studies (save/load)
@@ -311,49 +885,138 @@ pub struct StudyManager {
 
 impl StudyManager {
     pub fn new() -> Self {
-        let studies_dir = dirs::data_dir()
-            .unwrap_or_else(|| std::env::current_dir().unwrap())
-            .join("Stockfish-Chess")
-            .join("studies");
-        
+        Self::in_dir(crate::config::data_dir().join("studies"))
+    }
+
+    fn in_dir(studies_dir: std::path::PathBuf) -> Self {
         std::fs::create_dir_all(&studies_dir).ok();
-        
         Self { studies_dir }
     }
 
-    pub fn save_study(&self, study: &Study) -> Result<(), std::io::Error> {
-        let path = self.studies_dir.join(format!("{}.json", study.id));
-        let json = serde_json::to_string_pretty(study)?;
-        std::fs::write(path, json)
+    fn study_path(&self, id: &str, format: StudyFormat) -> std::path::PathBuf {
+        self.studies_dir.join(format!("{}.{}", id, format.extension()))
     }
 
-    pub fn load_study(&self, id: &str) -> Result<Study, Box<dyn std::error::Error>> {
-        let path = self.studies_dir.join(format!("{}.json", id));
-        let json = std::fs::read_to_string(path)?;
-        let study = serde_json::from_str(&json)?;
-        Ok(study)
+    /// The study as it was before its last save
+    fn backup_path(&self, id: &str, format: StudyFormat) -> std::path::PathBuf {
+        self.studies_dir.join(format!("{}.{}.{}", id, format.extension(), BACKUP_EXTENSION))
     }
 
-    pub fn list_studies(&self) -> Result<Vec<(String, String)>, std::io::Error> {
-        let mut studies = Vec::new();
-        
+    /// Save in `format`, keeping the previous save as a backup and removing any copy of the
+    /// study in the other format
+    pub fn save_study(&self, study: &Study, format: StudyFormat) -> Result<(), AppError> {
+        self.write_study(study, format)
+            .map_err(|e| AppError::StudyWrite { name: study.name.clone(), reason: e.to_string() })
+    }
+
+    fn write_study(&self, study: &Study, format: StudyFormat) -> Result<(), std::io::Error> {
+        let path = self.study_path(&study.id, format);
+        if path.exists() {
+            std::fs::copy(&path, self.backup_path(&study.id, format))?;
+        }
+        crate::config::write_atomic(&path, format.encode(study)?)?;
+        for other in StudyFormat::all().iter().filter(|f| **f != format) {
+            for path in [self.study_path(&study.id, *other), self.backup_path(&study.id, *other)] {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a study, from its backup if the file itself is missing or damaged
+    pub fn load_study(&self, id: &str) -> Result<Study, AppError> {
+        let format = StudyFormat::all()
+            .iter()
+            .copied()
+            .find(|f| self.study_path(id, *f).exists() || self.backup_path(id, *f).exists())
+            .unwrap_or_default();
+        let read = |path: std::path::PathBuf| {
+            let bytes = std::fs::read(path)
+                .map_err(|e| AppError::StudyRead { id: id.to_string(), reason: e.to_string() })?;
+            format
+                .decode(&bytes)
+                .map_err(|e| AppError::StudyDamaged { id: id.to_string(), reason: e.to_string() })
+        };
+        read(self.study_path(id, format)).or_else(|e| {
+            let backup = self.backup_path(id, format);
+            if !backup.exists() {
+                return Err(e);
+            }
+            tracing::warn!("Study {} could not be read ({}); loading its backup", id, e);
+            read(backup)
+        })
+    }
+
+    /// Summaries of every saved study, parsing only files that changed since the index was written
+    pub fn list_studies(&self) -> Result<Vec<StudySummary>, std::io::Error> {
+        let index_path = self.studies_dir.join(INDEX_FILE);
+        let old_index: HashMap<String, IndexEntry> = std::fs::read_to_string(&index_path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        let mut index = HashMap::new();
+        let mut changed = false;
+
         for entry in std::fs::read_dir(&self.studies_dir)? {
             let entry = entry?;
-            if entry.path().extension().map_or(false, |e| e == "json") {
-                if let Ok(json) = std::fs::read_to_string(entry.path()) {
-                    if let Ok(study) = serde_json::from_str::<Study>(&json) {
-                        studies.push((study.id, study.name));
+            let path = entry.path();
+            let Some(format) = path.extension().and_then(StudyFormat::from_extension) else {
+                continue;
+            };
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let len = metadata.len();
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+
+            match old_index.get(&file_name) {
+                Some(cached) if cached.len == len && cached.modified == modified => {
+                    index.insert(file_name, cached.clone());
+                }
+                _ => {
+                    changed = true;
+                    let summary = std::fs::read(&path)
+                        .ok()
+                        .and_then(|bytes| format.decode(&bytes).ok())
+                        .map(|study| StudySummary::from(&study));
+                    if summary.is_none() {
+                        tracing::warn!("Study file {} could not be read", path.display());
                     }
+                    index.insert(file_name, IndexEntry { len, modified, summary });
                 }
             }
         }
-        
+
+        if changed || index.len() != old_index.len() {
+            if let Ok(json) = serde_json::to_string(&index) {
+                if let Err(e) = crate::config::write_atomic(&index_path, json) {
+                    tracing::warn!("Failed to write study index: {}", e);
+                }
+            }
+        }
+
+        let mut studies: Vec<StudySummary> = index.into_values().filter_map(|entry| entry.summary).collect();
+        studies.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
         Ok(studies)
     }
 
-    pub fn delete_study(&self, id: &str) -> Result<(), std::io::Error> {
-        let path = self.studies_dir.join(format!("{}.json", id));
-        std::fs::remove_file(path)
+    pub fn delete_study(&self, id: &str) -> Result<(), AppError> {
+        for format in StudyFormat::all() {
+            for path in [self.study_path(id, *format), self.backup_path(id, *format)] {
+                if path.exists() {
+                    std::fs::remove_file(path)
+                        .map_err(|e| AppError::StudyWrite { name: id.to_string(), reason: e.to_string() })?;
+                }
+            }
+        }
+        Ok(())
     }
 }
 
@@ -362,3 +1025,278 @@ impl Default for StudyManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    #[test]
+    fn test_formats_round_trip() {
+        let mut study = Study::new("Repertoire".to_string());
+        study.current_chapter_mut().add_comment("Main line".to_string());
+        for format in StudyFormat::all() {
+            let bytes = format.encode(&study).unwrap();
+            let loaded = format.decode(&bytes).unwrap();
+            assert_eq!(loaded.name, "Repertoire");
+            assert_eq!(loaded.current_chapter().root().comments, vec!["Main line".to_string()]);
+        }
+    }
+
+    #[test]
+    fn test_compact_reads_older_files() {
+        let mut study = Study::new("Repertoire".to_string());
+        let record = GameState::new().make_move_san("e4").unwrap();
+        study.current_chapter_mut().add_move(record.clone(), record.resulting_fen.clone());
+
+        // A field the file doesn't have yet takes its default
+        let mut json = serde_json::to_value(&study).unwrap();
+        json.as_object_mut().unwrap().remove("locked");
+        let mut bytes = COMPACT_MAGIC.to_vec();
+        bytes.push(COMPACT_VERSION);
+        bytes.extend(zstd::encode_all(serde_json::to_vec(&json).unwrap().as_slice(), 3).unwrap());
+        assert!(!StudyFormat::Compact.decode(&bytes).unwrap().locked);
+
+        bytes[COMPACT_MAGIC.len()] = COMPACT_VERSION + 1;
+        assert!(StudyFormat::Compact.decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_compact_rejects_headerless() {
+        let study = Study::new("Repertoire".to_string());
+        let body = zstd::encode_all(serde_json::to_vec(&study).unwrap().as_slice(), 3).unwrap();
+        assert!(StudyFormat::Compact.decode(&body).is_err());
+        assert!(StudyFormat::Compact.decode(b"").is_err());
+    }
+
+    #[test]
+    fn test_save_keeps_backup() {
+        let dir = std::env::temp_dir().join(format!("stockfish-chess-studies-{}", std::process::id()));
+        let manager = StudyManager::in_dir(dir.clone());
+        let mut study = Study::new("Repertoire".to_string());
+        manager.save_study(&study, StudyFormat::Json).unwrap();
+        study.name = "Renamed".to_string();
+        manager.save_study(&study, StudyFormat::Json).unwrap();
+        assert_eq!(manager.list_studies().unwrap().len(), 1);
+
+        // A save cut short falls back to the one before
+        std::fs::write(manager.study_path(&study.id, StudyFormat::Json), "{\"chap").unwrap();
+        assert_eq!(manager.load_study(&study.id).unwrap().name, "Repertoire");
+
+        manager.save_study(&study, StudyFormat::Compact).unwrap();
+        assert!(!manager.backup_path(&study.id, StudyFormat::Json).exists());
+        manager.delete_study(&study.id).unwrap();
+        assert!(!manager.backup_path(&study.id, StudyFormat::Compact).exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_study_index() {
+        let dir = std::env::temp_dir().join(format!("stockfish-chess-index-{}", std::process::id()));
+        let manager = StudyManager::in_dir(dir.clone());
+        let index_path = dir.join(INDEX_FILE);
+        let mut study = Study::new("Repertoire".to_string());
+        manager.save_study(&study, StudyFormat::Json).unwrap();
+        assert_eq!(manager.list_studies().unwrap()[0].name, "Repertoire");
+
+        // An unchanged file is listed from the index without being read
+        let index = std::fs::read_to_string(&index_path).unwrap();
+        std::fs::write(&index_path, index.replace("\"Repertoire\"", "\"Cached\"")).unwrap();
+        assert_eq!(manager.list_studies().unwrap()[0].name, "Cached");
+
+        // A saved change is read again
+        study.name = "Renamed".to_string();
+        manager.save_study(&study, StudyFormat::Json).unwrap();
+        assert_eq!(manager.list_studies().unwrap()[0].name, "Renamed");
+
+        // A damaged file is left out, and remembered so the index isn't written again
+        std::fs::write(dir.join("broken.json"), "{\"chap").unwrap();
+        assert_eq!(manager.list_studies().unwrap().len(), 1);
+        let index = std::fs::read_to_string(&index_path).unwrap();
+        assert!(index.contains("broken.json"));
+        let tampered = index.replace("\"Renamed\"", "\"Cached\"");
+        std::fs::write(&index_path, &tampered).unwrap();
+        manager.list_studies().unwrap();
+        assert_eq!(std::fs::read_to_string(&index_path).unwrap(), tampered);
+
+        // Removed files leave the index
+        manager.delete_study(&study.id).unwrap();
+        std::fs::remove_file(dir.join("broken.json")).unwrap();
+        assert!(manager.list_studies().unwrap().is_empty());
+        assert!(!std::fs::read_to_string(&index_path).unwrap().contains("broken.json"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_toggle_nag() {
+        let mut study = Study::new("Glyphs".to_string());
+        let record = GameState::new().make_move_san("e4").unwrap();
+        let chapter = study.current_chapter_mut();
+        chapter.add_move(record.clone(), record.resulting_fen.clone());
+        for nag in [1, 2, 16, 40, 7] {
+            chapter.toggle_nag(nag);
+        }
+        assert_eq!(chapter.current_node().nags, [2, 7, 16, 40]);
+        chapter.toggle_nag(7);
+        assert_eq!(chapter.current_node().nags, [2, 16, 40]);
+
+        let pgn = study.to_pgn(PgnStyle { figurine: false, nag_glyphs: true });
+        assert!(pgn.contains("1. e4? ± → *"));
+        let pgn = study.to_pgn(PgnStyle { figurine: false, nag_glyphs: false });
+        assert!(pgn.contains("1. e4 $2 $16 $40 *"));
+    }
+
+    #[test]
+    fn test_legacy_nested_chapter() {
+        let json = r#"{
+            "id": 0,
+            "name": "Sicilian",
+            "root": {
+                "id": 0, "move_record": null, "fen": "start", "comments": ["Intro"],
+                "children": [
+                    {"id": 0, "move_record": {"san": "e4", "uci": "e2e4", "resulting_fen": "a"}, "fen": "a", "comments": [],
+                     "children": [{"id": 0, "move_record": {"san": "c5", "uci": "c7c5", "resulting_fen": "b"}, "fen": "b", "comments": ["Main"], "children": []}]},
+                    {"id": 1, "move_record": {"san": "d4", "uci": "d2d4", "resulting_fen": "c"}, "fen": "c", "comments": [], "children": []}
+                ]
+            },
+            "current_path": [0, 0]
+        }"#;
+        let chapter: StudyChapter = serde_json::from_str(json).unwrap();
+        assert_eq!(chapter.root().comments, vec!["Intro".to_string()]);
+        assert_eq!(chapter.current_fen(), "b");
+        assert_eq!(chapter.current_node().comments, vec!["Main".to_string()]);
+        assert_eq!(chapter.get_lines(), vec![vec!["e4".to_string(), "c5".to_string()], vec!["d4".to_string()]]);
+
+        // Parent links lead back to the root
+        let current = chapter.current_node();
+        assert_eq!(chapter.node(current.parent.unwrap()).fen, "a");
+
+        // Saved again in the arena layout, it reads back the same
+        let saved = serde_json::to_string(&chapter).unwrap();
+        let reloaded: StudyChapter = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.get_lines(), chapter.get_lines());
+        assert_eq!(reloaded.current_fen(), "b");
+    }
+
+    #[test]
+    fn test_damaged_chapter_rejected() {
+        let mut chapter = StudyChapter::new(0, "Test".to_string());
+        chapter.push_node(ROOT, None, "a".to_string());
+        let saved = serde_json::to_value(&chapter).unwrap();
+        let reloaded: StudyChapter = serde_json::from_value(saved.clone()).unwrap();
+        assert_eq!(reloaded.nodes.len(), 2);
+
+        // A child id past the end of the arena
+        let mut damaged = saved.clone();
+        damaged["nodes"][0]["children"] = serde_json::json!([5]);
+        assert!(serde_json::from_value::<StudyChapter>(damaged).is_err());
+
+        // A child that names another parent
+        let mut damaged = saved.clone();
+        damaged["nodes"][1]["parent"] = serde_json::json!(1);
+        assert!(serde_json::from_value::<StudyChapter>(damaged).is_err());
+
+        // A node no parent links to
+        let mut damaged = saved;
+        damaged["nodes"][0]["children"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<StudyChapter>(damaged).is_err());
+    }
+
+    #[test]
+    fn test_find_duplicate_line() {
+        let mut study = Study::new("Repertoire".to_string());
+        let play = |study: &mut Study, sans: &[&str]| {
+            let chapter = study.current_chapter_mut();
+            chapter.go_to_start();
+            let mut game = GameState::new();
+            for san in sans {
+                let record = game.make_move_san(san).unwrap();
+                chapter.add_move(record, game.fen());
+            }
+        };
+        play(&mut study, &["e4", "c5", "Nf3", "d6"]);
+        study.add_chapter("Second".to_string());
+        play(&mut study, &["d4", "Nf6"]);
+        assert_eq!(study.find_duplicate_line(), None);
+
+        play(&mut study, &["e4", "c5", "Nf3"]);
+        let found = study.find_duplicate_line().unwrap();
+        assert_eq!((found.chapter, found.exact), (0, true));
+        assert_eq!(study.chapters[0].node_at(&found.path), study.chapters[0].node_at(&[0, 0, 0]));
+
+        // 1. Nf3 c5 2. e4 transposes
+        play(&mut study, &["Nf3", "c5", "e4"]);
+        let found = study.find_duplicate_line().unwrap();
+        assert!(!found.exact);
+        assert_eq!(found.path, [0, 0, 0]);
+
+        let chapter = study.current_chapter_mut();
+        let newest = chapter.current_id();
+        assert!(chapter.remove_newest_node(newest));
+        assert_eq!(chapter.current_path, [2, 0]);
+        assert!(!chapter.remove_newest_node(chapter.node_at(&[0, 0])));
+    }
+
+    #[test]
+    fn test_pgn_headers_round_trip() {
+        let text = r#"[Event "Club Championship"]
+[Site "Berlin"]
+[Date "2024.03.01"]
+[Round "4"]
+[White "Coach"]
+[Black "Student"]
+[Result "1-0"]
+[Annotator "Coach"]
+[ECO "C50"]
+
+1. e4 e5 1-0
+"#;
+        let mut study = Study::new("Games".to_string());
+        let game = crate::game::parse_pgn(text).remove(0);
+        study.add_pgn_chapter(&game).unwrap();
+        study.chapters[1].headers.push(("PlyCount".to_string(), "2".to_string()));
+
+        let exported = crate::game::parse_pgn(&study.to_pgn(PgnStyle { figurine: false, nag_glyphs: false }));
+        assert_eq!(exported.len(), 2);
+        let mut expected = game.headers.clone();
+        expected.push(("PlyCount".to_string(), "2".to_string()));
+        assert_eq!(exported[1].headers, expected);
+        assert_eq!(exported[1].moves, ["e4", "e5"]);
+
+        // A chapter without tags gets the seven tag roster from the study
+        let first = &exported[0];
+        assert_eq!(first.header("Event"), Some(format!("Games: {}", study.chapters[0].name).as_str()));
+        assert_eq!(first.header("Date"), Some(study.created_at[..10].replace('-', ".").as_str()));
+        assert_eq!(first.header("Result"), Some("*"));
+        assert_eq!(first.header("Chapter"), None);
+
+        // A chapter from a set-up position exports it as a FEN tag
+        let fen = "8/8/8/4k3/8/8/4P3/4K3 w - - 0 1";
+        study.chapters[0].set_root_fen(fen.to_string());
+        let exported = crate::game::parse_pgn(&study.to_pgn(PgnStyle { figurine: false, nag_glyphs: false }));
+        assert_eq!(exported[0].header("FEN"), Some(fen));
+        assert_eq!(exported[0].header("SetUp"), Some("1"));
+    }
+
+    #[test]
+    fn test_personal_copy() {
+        let mut study = Study::new("Coach".to_string());
+        let record = GameState::new().make_move_san("d4").unwrap();
+        study.current_chapter_mut().add_move(record.clone(), record.resulting_fen.clone());
+        study.locked = true;
+
+        // Older files without the flag open unlocked
+        let mut json = serde_json::to_value(&study).unwrap();
+        json.as_object_mut().unwrap().remove("locked");
+        assert!(!serde_json::from_value::<Study>(json).unwrap().locked);
+
+        let mut copy = study.personal_copy();
+        assert!(!copy.locked);
+        assert_ne!(copy.name, study.name);
+        assert_eq!(copy.current_chapter().get_lines(), study.current_chapter().get_lines());
+        let chapter = copy.current_chapter_mut();
+        assert_eq!(chapter.child_index("d2d4"), None);
+        chapter.go_to_start();
+        assert_eq!(chapter.child_index("d2d4"), Some(0));
+    }
+}
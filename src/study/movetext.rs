@@ -0,0 +1,230 @@
+use crate::game::{parse_pgn, GameError, GameState, PgnGame};
+use crate::i18n::tr_args;
+use crate::study::{NodeId, Study, StudyChapter, ROOT};
+
+/// Tokens that end a game's movetext
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// Glyphs written straight after a move and their NAG numbers, longest first
+const SUFFIX_NAGS: [(&str, u8); 6] = [("!!", 3), ("??", 4), ("!?", 5), ("?!", 6), ("!", 1), ("?", 2)];
+
+/// Piece of PGN movetext
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Move(String),
+    Comment(String),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+    Result,
+}
+
+/// Split movetext into tokens, dropping move numbers and rest-of-line comments
+fn tokenize(movetext: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut chars = movetext.chars();
+    let finish = |word: &mut String, tokens: &mut Vec<Token>| {
+        let text = std::mem::take(word);
+        if RESULTS.contains(&text.as_str()) {
+            tokens.push(Token::Result);
+        } else if let Some(nag) = text.strip_prefix('$') {
+            tokens.extend(nag.parse().ok().map(Token::Nag));
+        } else {
+            // Move numbers may be glued to the move ("12.Nf3", "12...Nf3")
+            let san = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+            if !san.is_empty() {
+                tokens.push(Token::Move(san.to_string()));
+            }
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                finish(&mut word, &mut tokens);
+                let comment: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let comment = comment.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !comment.is_empty() {
+                    tokens.push(Token::Comment(comment));
+                }
+            }
+            ';' => {
+                finish(&mut word, &mut tokens);
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '(' | ')' => {
+                finish(&mut word, &mut tokens);
+                tokens.push(if c == '(' { Token::VariationStart } else { Token::VariationEnd });
+            }
+            c if c.is_whitespace() => finish(&mut word, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    finish(&mut word, &mut tokens);
+    tokens
+}
+
+impl StudyChapter {
+    /// Add the moves of PGN movetext to the tree below the root, with their variations,
+    /// comments and glyphs
+    fn add_movetext(&mut self, movetext: &str) -> Result<(), GameError> {
+        // The node the last move led to and the one it was played from, saved at each variation
+        let mut current = ROOT;
+        let mut before = ROOT;
+        let mut variations: Vec<(NodeId, NodeId)> = Vec::new();
+        for token in tokenize(movetext) {
+            match token {
+                Token::Move(text) => {
+                    let (san, nag) = match SUFFIX_NAGS.iter().find(|(suffix, _)| text.ends_with(suffix)) {
+                        Some((suffix, nag)) => (&text[..text.len() - suffix.len()], Some(*nag)),
+                        None => (text.as_str(), None),
+                    };
+                    let record = GameState::from_fen(&self.node(current).fen)?.make_move_san(san)?;
+                    let existing = self.node(current).children.iter().copied().find(|&child| {
+                        self.node(child).move_record.as_ref().is_some_and(|m| m.uci == record.uci)
+                    });
+                    before = current;
+                    current = match existing {
+                        Some(child) => child,
+                        None => {
+                            let fen = record.resulting_fen.clone();
+                            self.push_node(before, Some(record), fen)
+                        }
+                    };
+                    if let Some(nag) = nag {
+                        self.add_nag(current, nag);
+                    }
+                }
+                Token::Comment(comment) => self.node_mut(current).comments.push(comment),
+                Token::Nag(nag) => self.add_nag(current, nag),
+                // A variation replaces the last move
+                Token::VariationStart => {
+                    variations.push((current, before));
+                    current = before;
+                    before = self.node(current).parent.unwrap_or(ROOT);
+                }
+                Token::VariationEnd => {
+                    if let Some((saved_current, saved_before)) = variations.pop() {
+                        current = saved_current;
+                        before = saved_before;
+                    }
+                }
+                Token::Result => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn add_nag(&mut self, id: NodeId, nag: u8) {
+        let nags = &mut self.node_mut(id).nags;
+        if !nags.contains(&nag) {
+            nags.push(nag);
+            nags.sort_unstable();
+        }
+    }
+}
+
+impl Study {
+    /// Add a game, variations, comments and glyphs included, as a new chapter positioned at
+    /// its start
+    pub fn add_pgn_chapter(&mut self, pgn: &PgnGame) -> Result<usize, GameError> {
+        let id = self.chapters.len();
+        let name = pgn.title().unwrap_or_else(|| tr_args("study.chapter_n", &[&(id + 1)]));
+        let mut chapter = StudyChapter::new(id, name);
+        chapter.set_pgn_headers(&pgn.headers);
+        if let Some(fen) = pgn.header("FEN") {
+            GameState::from_fen(fen)?;
+            chapter.set_root_fen(fen.to_string());
+        }
+        // Games made in the app have their main line only
+        if pgn.movetext.is_empty() {
+            chapter.add_movetext(&pgn.moves.join(" "))?;
+        } else {
+            chapter.add_movetext(&pgn.movetext)?;
+        }
+        self.chapters.push(chapter);
+        self.current_chapter = id;
+        Ok(id)
+    }
+
+    /// Add the first game of PGN text as a new chapter, like `add_pgn_chapter`. `None` when the
+    /// text holds no game.
+    pub fn add_pgn_text_chapter(&mut self, text: &str) -> Result<Option<usize>, GameError> {
+        match parse_pgn(text).first() {
+            Some(game) => self.add_pgn_chapter(game).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("1.e4 {Best by\n test} e5 (1...c5!? $14) 2. Nf3 ; rest\n Nc6 *");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Move("e4".to_string()),
+                Token::Comment("Best by test".to_string()),
+                Token::Move("e5".to_string()),
+                Token::VariationStart,
+                Token::Move("c5!?".to_string()),
+                Token::Nag(14),
+                Token::VariationEnd,
+                Token::Move("Nf3".to_string()),
+                Token::Move("Nc6".to_string()),
+                Token::Result,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_pgn_text_chapter() {
+        let text = r#"[White "Coach"]
+[Black "Student"]
+
+1. e4 e5 (1... c5 2. Nf3 (2. c3) 2... d6) 2. Nf3 {Main line} Nc6?! $16 *
+
+[Event "Ignored"]
+1. d4 *
+"#;
+        let mut study = Study::new("Openings".to_string());
+        let id = study.add_pgn_text_chapter(text).unwrap().unwrap();
+        assert_eq!(study.current_chapter, id);
+        let chapter = study.current_chapter();
+        assert_eq!(chapter.name, "Coach - Student");
+        assert!(chapter.current_path.is_empty());
+        let san = |lines: Vec<Vec<String>>| lines.into_iter().map(|line| line.join(" ")).collect::<Vec<_>>();
+        assert_eq!(san(chapter.get_lines()), ["e4 e5 Nf3 Nc6", "e4 c5 Nf3 d6", "e4 c5 c3"]);
+
+        let nc6 = chapter.node_at(&[0, 0, 0, 0]);
+        assert_eq!(chapter.node(nc6).nags, [6, 16]);
+        assert_eq!(chapter.node(chapter.node_at(&[0, 0, 0])).comments, ["Main line"]);
+
+        assert_eq!(study.add_pgn_text_chapter("\n").unwrap(), None);
+        assert!(study.add_pgn_text_chapter("1. e4 e5 2. Ke3 *").is_err());
+        assert_eq!(study.chapters.len(), 2);
+    }
+
+    #[test]
+    fn test_add_pgn_chapter_from_file() {
+        let text = "[Event \"First\"]\n\n1. e4 {Open} e5 (1... c5) *\n\n[Event \"Second\"]\n\n1. d4 d5 2. c4 *\n";
+        let games: Vec<PgnGame> = crate::game::PgnReader::new(text.as_bytes()).collect();
+        let mut study = Study::new("Games".to_string());
+        for game in &games {
+            study.add_pgn_chapter(game).unwrap();
+        }
+        let san = |chapter: &StudyChapter| chapter.get_lines().into_iter().map(|line| line.join(" ")).collect::<Vec<_>>();
+        assert_eq!(san(&study.chapters[1]), ["e4 e5", "e4 c5"]);
+        assert_eq!(study.chapters[1].node(study.chapters[1].node_at(&[0])).comments, ["Open"]);
+        assert_eq!(san(&study.chapters[2]), ["d4 d5 c4"]);
+
+        // Games made in the app have no movetext
+        let made = PgnGame { moves: vec!["Nf3".to_string(), "d5".to_string()], ..Default::default() };
+        let id = study.add_pgn_chapter(&made).unwrap();
+        assert_eq!(san(&study.chapters[id]), ["Nf3 d5"]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::game::{escape_html, fen_ply, move_number, svg_diagram, GameState, PlayerColor};
+use crate::i18n::tr_args;
+use crate::study::{glyph_text, NodeId, StudyChapter, ROOT};
+use shakmaty::Position;
+use std::fmt::Write;
+
+/// Page setup for printing; diagrams and the heading are never split across pages
+const PRINT_STYLE: &str = "@page { size: A4; margin: 2cm; }\n\
+body { font-family: Georgia, serif; font-size: 11pt; line-height: 1.5; max-width: 17cm; margin: 0 auto; }\n\
+h1, h2 { font-family: sans-serif; break-after: avoid; }\n\
+.moves { text-align: justify; }\n\
+.move { font-weight: bold; }\n\
+.variation { color: #444; }\n\
+.comment { font-style: italic; }\n\
+figure { break-inside: avoid; page-break-inside: avoid; text-align: center; margin: 1em 0; }\n\
+figcaption { font-size: 10pt; }\n";
+
+/// Writes a chapter's moves as HTML, numbering moves where the reader needs it
+struct PrintWriter<'a> {
+    chapter: &'a StudyChapter,
+    /// Main line plies between diagrams, none when zero
+    diagram_plies: usize,
+    html: String,
+    /// Main line plies written so far
+    plies: usize,
+}
+
+impl PrintWriter<'_> {
+    /// "12." for a White move, "12..." for Black, from the position the move was played in
+    fn move_number(&self, id: NodeId) -> String {
+        let parent = self.chapter.node(id).parent.unwrap_or(ROOT);
+        move_number(fen_ply(&self.chapter.node(parent).fen), 0).unwrap_or_default()
+    }
+
+    /// The move leading to `id` with its glyphs
+    fn move_text(&self, id: NodeId) -> String {
+        let node = self.chapter.node(id);
+        let san = node.move_record.as_ref().map_or("", |r| r.san.as_str());
+        format!("{}{}", san, glyph_text(&node.nags))
+    }
+
+    fn write_comments(&mut self, comments: &[String]) {
+        for comment in comments {
+            let _ = write!(self.html, "<span class=\"comment\">{}</span> ", escape_html(comment));
+        }
+    }
+
+    /// Moves from `first` to the end of its line, with the alternatives to each move in brackets.
+    /// On the main line a diagram follows every `diagram_plies` moves.
+    fn write_line(&mut self, first: NodeId, main: bool) {
+        let mut id = first;
+        let mut numbered = true;
+        loop {
+            let node = self.chapter.node(id);
+            let white = !self.move_number(id).ends_with("...");
+            let number = if numbered || white { format!("{} ", self.move_number(id)) } else { String::new() };
+            let class = if main { "move" } else { "" };
+            let _ = write!(self.html, "<span class=\"{}\">{}{}</span> ", class, number, escape_html(&self.move_text(id)));
+            numbered = !node.comments.is_empty();
+            self.write_comments(&node.comments);
+
+            let siblings = &self.chapter.node(node.parent.unwrap_or(ROOT)).children;
+            if siblings.first() == Some(&id) {
+                for &alternative in &siblings[1..] {
+                    self.html.push_str("<span class=\"variation\">(");
+                    self.write_line(alternative, false);
+                    self.html.push_str(")</span> ");
+                    numbered = true;
+                }
+            }
+
+            if main {
+                self.plies += 1;
+                if self.diagram_plies > 0 && self.plies % self.diagram_plies == 0 && !node.children.is_empty() {
+                    self.write_diagram(id);
+                    numbered = true;
+                }
+            }
+            match node.children.first() {
+                Some(&next) => id = next,
+                None => break,
+            }
+        }
+    }
+
+    /// Diagram of the position after the move leading to `id`, between paragraphs of moves
+    fn write_diagram(&mut self, id: NodeId) {
+        let node = self.chapter.node(id);
+        let Ok(game) = GameState::from_fen(&node.fen) else {
+            return;
+        };
+        let squares = node.move_record.as_ref().and_then(|r| Some((r.uci.get(0..2)?.parse().ok()?, r.uci.get(2..4)?.parse().ok()?)));
+        let flipped = self.chapter.orientation == PlayerColor::Black;
+        let caption = tr_args("print.after", &[&format!("{} {}", self.move_number(id), self.move_text(id))]);
+        let _ = write!(
+            self.html,
+            "</p>\n<figure>\n{}<figcaption>{}</figcaption>\n</figure>\n<p class=\"moves\">",
+            svg_diagram(game.current_position().board(), squares, flipped),
+            escape_html(&caption)
+        );
+    }
+}
+
+impl StudyChapter {
+    /// Print-ready HTML of the chapter: the starting diagram, the moves with variations and
+    /// comments, and a diagram after every `diagram_every` moves of the main line
+    pub fn to_print_html(&self, study_name: &str, diagram_every: usize) -> String {
+        let mut writer = PrintWriter {
+            chapter: self,
+            diagram_plies: diagram_every * 2,
+            html: String::new(),
+            plies: 0,
+        };
+        let title = escape_html(&format!("{} – {}", study_name, self.name));
+        let _ = write!(
+            writer.html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n\
+             <h1>{}</h1>\n<h2>{}</h2>\n",
+            title,
+            PRINT_STYLE,
+            escape_html(study_name),
+            escape_html(&self.name)
+        );
+
+        let root = self.node(ROOT);
+        if let Ok(game) = GameState::from_fen(&root.fen) {
+            let flipped = self.orientation == PlayerColor::Black;
+            let _ = writeln!(writer.html, "<figure>\n{}</figure>", svg_diagram(game.current_position().board(), None, flipped));
+        }
+        writer.html.push_str("<p class=\"moves\">");
+        writer.write_comments(&root.comments);
+        if let Some(&first) = root.children.first() {
+            writer.write_line(first, true);
+        }
+        writer.html.push_str("</p>\n</body>\n</html>\n");
+        writer.html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::study::Study;
+
+    #[test]
+    fn test_print_html() {
+        let mut study = Study::new("Club <night>".to_string());
+        let chapter = study.current_chapter_mut();
+        let mut game = GameState::new();
+        for san in ["e4", "e5", "Nf3", "Nc6", "Bb5"] {
+            let record = game.make_move_san(san).unwrap();
+            chapter.add_move(record, game.fen());
+        }
+        chapter.add_comment("The Spanish".to_string());
+        // 2... d6 instead of 2... Nc6
+        chapter.current_path.truncate(3);
+        let mut alternative = GameState::from_fen(chapter.current_fen()).unwrap();
+        let record = alternative.make_move_san("d6").unwrap();
+        chapter.add_move(record, alternative.fen());
+
+        let html = study.current_chapter().to_print_html("Club <night>", 1);
+        assert!(html.contains("<h1>Club &lt;night&gt;</h1>"));
+        assert!(html.contains("2... d6"));
+        assert!(html.contains("<span class=\"comment\">The Spanish</span>"));
+        // Starting position plus one after each full move but the last
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.find("Nc6").unwrap() < html.find("d6").unwrap());
+    }
+}
@@ -0,0 +1,342 @@
+use crate::engine::{DifficultyLevel, EngineActor, EngineCommand, EngineEvent, EngineTimeouts};
+use crate::game::{GameOutcome, GameState, PgnGame, PlayerColor};
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Games still running after this many plies are adjudicated as draws
+const MAX_PLIES: usize = 400;
+
+/// How long past the move time a `bestmove` may take before the engine is given up on
+const BESTMOVE_GRACE: Duration = Duration::from_secs(15);
+
+/// Which levels play each other and how, remembered between sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TournamentSettings {
+    pub levels: Vec<DifficultyLevel>,
+    /// Another UCI engine that joins at full strength, none when empty
+    pub second_engine: String,
+    /// Every pairing plays twice, once with each color
+    pub double_round: bool,
+    pub movetime_ms: u64,
+}
+
+impl Default for TournamentSettings {
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                DifficultyLevel::Beginner,
+                DifficultyLevel::Casual,
+                DifficultyLevel::Intermediate,
+                DifficultyLevel::Advanced,
+            ],
+            second_engine: String::new(),
+            double_round: true,
+            movetime_ms: 200,
+        }
+    }
+}
+
+/// One player of the tournament: an engine at a difficulty level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entrant {
+    pub name: String,
+    pub path: String,
+    pub level: DifficultyLevel,
+}
+
+impl Entrant {
+    /// The configured engine at each chosen level, then the second engine if there is one
+    pub fn field(settings: &TournamentSettings, engine_path: &str) -> Vec<Entrant> {
+        let mut entrants: Vec<Entrant> = DifficultyLevel::all()
+            .iter()
+            .filter(|level| settings.levels.contains(level))
+            .map(|&level| Entrant {
+                name: level.label().to_string(),
+                path: engine_path.to_string(),
+                level,
+            })
+            .collect();
+        let second = shellexpand::tilde(settings.second_engine.trim()).to_string();
+        if !second.is_empty() {
+            let name = std::path::Path::new(&second)
+                .file_name()
+                .map_or_else(|| second.clone(), |name| name.to_string_lossy().to_string());
+            entrants.push(Entrant {
+                name,
+                path: second,
+                level: DifficultyLevel::Maximum,
+            });
+        }
+        entrants
+    }
+}
+
+/// (white, black) entrant indices for every game of a round robin among `count` players,
+/// each pairing once, or twice with colors reversed
+pub fn pairings(count: usize, double_round: bool) -> Vec<(usize, usize)> {
+    let mut games: Vec<(usize, usize)> =
+        (0..count).flat_map(|a| (a + 1..count).map(move |b| (a, b))).collect();
+    if double_round {
+        let returns: Vec<(usize, usize)> = games.iter().map(|&(a, b)| (b, a)).collect();
+        games.extend(returns);
+    }
+    games
+}
+
+/// Rating difference to the average opponent implied by scoring `points` out of `games`.
+/// Half a point is added to each side so perfect and zero scores stay finite.
+pub fn elo_gap(points: f32, games: usize) -> Option<f32> {
+    if games == 0 {
+        return None;
+    }
+    let fraction = (points + 0.5) / (games as f32 + 1.0);
+    Some(400.0 * (fraction / (1.0 - fraction)).log10())
+}
+
+/// A game the tournament finished
+#[derive(Debug, Clone)]
+pub struct TournamentGame {
+    pub white: usize,
+    pub black: usize,
+    pub outcome: GameOutcome,
+    pub pgn: PgnGame,
+}
+
+impl TournamentGame {
+    /// Points `entrant` took from this game, if they played in it
+    pub fn points(&self, entrant: usize) -> Option<f32> {
+        let color = if entrant == self.white {
+            PlayerColor::White
+        } else if entrant == self.black {
+            PlayerColor::Black
+        } else {
+            return None;
+        };
+        Some(match self.outcome.winner() {
+            Some(winner) if winner == color => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        })
+    }
+}
+
+enum TournamentEvent {
+    Game(TournamentGame),
+    Failed(String),
+    Done,
+}
+
+/// A round robin played on a background thread until it is over or dropped
+pub struct Tournament {
+    /// Dropping the sender stops the tournament after the current move
+    stop_tx: Option<mpsc::Sender<()>>,
+    event_rx: mpsc::Receiver<TournamentEvent>,
+    pub entrants: Vec<Entrant>,
+    pub games: Vec<TournamentGame>,
+    pub total_games: usize,
+    pub error: Option<String>,
+    pub finished: bool,
+}
+
+impl Tournament {
+    pub fn start(entrants: Vec<Entrant>, double_round: bool, movetime_ms: u64, timeouts: EngineTimeouts) -> Self {
+        let schedule = pairings(entrants.len(), double_round);
+        let total_games = schedule.len();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (event_tx, event_rx) = mpsc::channel();
+        let players = entrants.clone();
+        std::thread::spawn(move || {
+            let event = match run(&players, &schedule, movetime_ms, timeouts, &stop_rx, &event_tx) {
+                Ok(()) => TournamentEvent::Done,
+                Err(e) => TournamentEvent::Failed(e),
+            };
+            let _ = event_tx.send(event);
+        });
+        Self {
+            stop_tx: Some(stop_tx),
+            event_rx,
+            entrants,
+            games: Vec::new(),
+            total_games,
+            error: None,
+            finished: false,
+        }
+    }
+
+    /// Take finished games; true when there was news
+    pub fn poll(&mut self) -> bool {
+        let mut updated = false;
+        while let Ok(event) = self.event_rx.try_recv() {
+            match event {
+                TournamentEvent::Game(game) => self.games.push(game),
+                TournamentEvent::Failed(e) => {
+                    self.error = Some(e);
+                    self.finished = true;
+                }
+                TournamentEvent::Done => self.finished = true,
+            }
+            updated = true;
+        }
+        updated
+    }
+
+    /// End the tournament after the move being searched; finished games are kept
+    pub fn stop(&mut self) {
+        self.stop_tx = None;
+        self.finished = true;
+    }
+
+    /// Points and games played by `entrant`
+    pub fn score(&self, entrant: usize) -> (f32, usize) {
+        self.games
+            .iter()
+            .filter_map(|game| game.points(entrant))
+            .fold((0.0, 0), |(points, games), p| (points + p, games + 1))
+    }
+
+    /// Points `entrant` scored against `opponent`, if they have played
+    pub fn head_to_head(&self, entrant: usize, opponent: usize) -> Option<f32> {
+        self.games
+            .iter()
+            .filter(|game| game.points(opponent).is_some())
+            .filter_map(|game| game.points(entrant))
+            .reduce(|a, b| a + b)
+    }
+
+    pub fn pgn(&self) -> String {
+        self.games.iter().map(|game| game.pgn.to_pgn()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// An engine process taking part, with its own channels
+struct Player {
+    cmd_tx: mpsc::Sender<EngineCommand>,
+    event_rx: mpsc::Receiver<EngineEvent>,
+}
+
+impl Player {
+    fn spawn(entrant: &Entrant, timeouts: EngineTimeouts) -> Result<Self, String> {
+        let (cmd_tx, event_rx) = EngineActor::spawn(Some(entrant.path.clone()), timeouts);
+        // Applied by Init, which only reports Ready afterwards
+        let _ = cmd_tx.send(EngineCommand::SetDifficulty(entrant.level));
+        let _ = cmd_tx.send(EngineCommand::Init);
+        loop {
+            match event_rx.recv() {
+                Ok(EngineEvent::Ready) => return Ok(Self { cmd_tx, event_rx }),
+                Ok(EngineEvent::Error(e)) => return Err(format!("{}: {}", entrant.name, e)),
+                Ok(EngineEvent::Terminated) | Err(_) => return Err(tr("tournament.engine_gone").to_string()),
+                Ok(_) => {}
+            }
+        }
+    }
+
+    fn best_move(&self, search_id: u64, game: &GameState, movetime_ms: u64) -> Result<String, String> {
+        let _ = self.cmd_tx.send(EngineCommand::Go {
+            search_id,
+            fen: game.starting_fen(),
+            moves: game.uci_moves(),
+            movetime_ms: Some(movetime_ms),
+        });
+        let timeout = Duration::from_millis(movetime_ms) + BESTMOVE_GRACE;
+        loop {
+            match self.event_rx.recv_timeout(timeout) {
+                Ok(EngineEvent::BestMove { search_id: id, best_move, .. }) if id == search_id => return Ok(best_move),
+                Ok(EngineEvent::Error(e)) => return Err(e.to_string()),
+                Ok(EngineEvent::Terminated) | Err(_) => return Err(tr("tournament.engine_gone").to_string()),
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        let _ = self.cmd_tx.send(EngineCommand::Quit);
+    }
+}
+
+fn run(
+    entrants: &[Entrant],
+    schedule: &[(usize, usize)],
+    movetime_ms: u64,
+    timeouts: EngineTimeouts,
+    stop_rx: &mpsc::Receiver<()>,
+    event_tx: &mpsc::Sender<TournamentEvent>,
+) -> Result<(), String> {
+    let players = entrants
+        .iter()
+        .map(|entrant| Player::spawn(entrant, timeouts))
+        .collect::<Result<Vec<_>, _>>()?;
+    let date = chrono::Local::now().format("%Y.%m.%d").to_string();
+    let mut search_id = 0;
+
+    for (round, &(white, black)) in schedule.iter().enumerate() {
+        let _ = players[white].cmd_tx.send(EngineCommand::NewGame);
+        let _ = players[black].cmd_tx.send(EngineCommand::NewGame);
+        let mut game = GameState::new();
+        while game.outcome() == GameOutcome::InProgress {
+            if !matches!(stop_rx.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                return Ok(());
+            }
+            if game.move_history().len() >= MAX_PLIES {
+                game.agree_to_draw();
+                break;
+            }
+            let player = match game.turn() {
+                PlayerColor::White => &players[white],
+                PlayerColor::Black => &players[black],
+            };
+            search_id += 1;
+            let uci = player.best_move(search_id, &game, movetime_ms)?;
+            game.make_move_uci(&uci).map_err(|e| format!("{}: {}", uci, e))?;
+        }
+
+        let outcome = game.outcome();
+        let headers = vec![
+            ("Event".to_string(), "Stockfish Chess Tournament".to_string()),
+            ("Site".to_string(), "Local".to_string()),
+            ("Date".to_string(), date.clone()),
+            ("Round".to_string(), (round + 1).to_string()),
+            ("White".to_string(), entrants[white].name.clone()),
+            ("Black".to_string(), entrants[black].name.clone()),
+            ("Result".to_string(), outcome.result().to_string()),
+        ];
+        let moves = game.move_history().iter().map(|record| record.san.clone()).collect();
+        let finished = TournamentGame {
+            white,
+            black,
+            outcome,
+            pgn: PgnGame { headers, moves, ..Default::default() },
+        };
+        if event_tx.send(TournamentEvent::Game(finished)).is_err() {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairings() {
+        assert_eq!(pairings(3, false), vec![(0, 1), (0, 2), (1, 2)]);
+        let double = pairings(3, true);
+        assert_eq!(double.len(), 6);
+        assert!(double.contains(&(2, 1)));
+        assert!(pairings(1, true).is_empty());
+    }
+
+    #[test]
+    fn test_elo_gap() {
+        assert_eq!(elo_gap(0.0, 0), None);
+        assert!(elo_gap(2.0, 4).unwrap().abs() < 0.01);
+        let strong = elo_gap(4.0, 4).unwrap();
+        assert!(strong > 0.0 && strong.is_finite());
+        assert!((elo_gap(0.0, 4).unwrap() + strong).abs() < 0.01);
+    }
+}
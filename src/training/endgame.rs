@@ -0,0 +1,169 @@
+use crate::config::USER_AGENT;
+use crate::game::{GameOutcome, PlayerColor};
+use crate::i18n::tr;
+use serde::Deserialize;
+use std::sync::mpsc;
+
+const TABLEBASE_URL: &str = "https://tablebase.lichess.ovh/standard";
+
+/// Theoretical endgames to practise against the engine: (id, FEN, name key).
+/// The side to move is the side the player takes.
+pub const ENDGAMES: &[(&str, &str, &str)] = &[
+    ("kq-k", "8/8/8/4k3/8/8/8/3QK3 w - - 0 1", "endgame.kq_k"),
+    ("kr-k", "8/8/8/4k3/8/8/8/R3K3 w - - 0 1", "endgame.kr_k"),
+    ("kp-k", "3k4/8/3K4/3P4/8/8/8/8 w - - 0 1", "endgame.kp_k"),
+    ("lucena", "1K6/1P1k4/8/8/8/8/r7/2R5 w - - 0 1", "endgame.lucena"),
+    ("philidor", "3k4/R7/r7/3PK3/8/8/8/8 b - - 0 1", "endgame.philidor"),
+    ("krb-kr", "8/8/8/3k4/8/3KB3/7R/r7 b - - 0 1", "endgame.krb_kr"),
+    ("kbn-k", "8/8/8/4k3/8/8/8/2B1KN2 w - - 0 1", "endgame.kbn_k"),
+];
+
+/// What perfect play from the starting position leads to, for the side to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TablebaseOutcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl TablebaseOutcome {
+    /// Lichess tablebase category; wins and losses spoiled by the fifty-move rule count as draws
+    fn from_category(category: &str) -> Option<Self> {
+        match category {
+            "win" => Some(TablebaseOutcome::Win),
+            "draw" | "cursed-win" | "blessed-loss" => Some(TablebaseOutcome::Draw),
+            "loss" => Some(TablebaseOutcome::Loss),
+            _ => None,
+        }
+    }
+
+    /// How a finished game went for `player`, if it is over
+    pub fn of_game(outcome: GameOutcome, player: PlayerColor) -> Option<Self> {
+        if outcome == GameOutcome::InProgress {
+            return None;
+        }
+        Some(match outcome.winner() {
+            Some(winner) if winner == player => TablebaseOutcome::Win,
+            Some(_) => TablebaseOutcome::Loss,
+            None => TablebaseOutcome::Draw,
+        })
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TablebaseOutcome::Win => tr("endgame.goal_win"),
+            TablebaseOutcome::Draw => tr("endgame.goal_draw"),
+            TablebaseOutcome::Loss => tr("endgame.goal_loss"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TablebaseResponse {
+    category: String,
+}
+
+fn fetch_outcome(fen: &str) -> Result<TablebaseOutcome, String> {
+    let response: TablebaseResponse = ureq::get(TABLEBASE_URL)
+        .set("User-Agent", USER_AGENT)
+        .query("fen", fen)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    TablebaseOutcome::from_category(&response.category).ok_or(response.category)
+}
+
+/// An endgame being played out, with the tablebase verdict it is held to
+pub struct EndgameSession {
+    pub index: usize,
+    pub player: PlayerColor,
+    /// The color chosen for regular games, given back when practice ends
+    pub previous_color: PlayerColor,
+    receiver: Option<mpsc::Receiver<Result<TablebaseOutcome, String>>>,
+    expected: Option<TablebaseOutcome>,
+    pub fetch_error: Option<String>,
+    /// Set once the result has been reported, so it is only reported once
+    pub judged: bool,
+}
+
+impl EndgameSession {
+    /// Start practising `ENDGAMES[index]`, looking its outcome up in the background
+    pub fn start(index: usize, player: PlayerColor, previous_color: PlayerColor) -> Self {
+        let fen = ENDGAMES[index].1.to_string();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(fetch_outcome(&fen));
+        });
+        Self {
+            index,
+            player,
+            previous_color,
+            receiver: Some(rx),
+            expected: None,
+            fetch_error: None,
+            judged: false,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        tr(ENDGAMES[self.index].2)
+    }
+
+    /// The tablebase outcome once it has arrived
+    pub fn expected(&mut self) -> Option<TablebaseOutcome> {
+        if let Some(receiver) = &self.receiver {
+            let result = match receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => Err(tr("endgame.fetch_failed").to_string()),
+            };
+            self.receiver = None;
+            match result {
+                Ok(outcome) => self.expected = Some(outcome),
+                Err(e) => {
+                    tracing::warn!("Tablebase lookup failed: {}", e);
+                    self.fetch_error = Some(e);
+                }
+            }
+        }
+        self.expected
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Whether the game ended at least as well as the tablebase says it should,
+    /// or None while the game or the lookup is still running
+    pub fn verdict(&mut self, outcome: GameOutcome) -> Option<bool> {
+        let achieved = TablebaseOutcome::of_game(outcome, self.player)?;
+        Some(achieved >= self.expected()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endgames_are_legal() {
+        for (id, fen, _) in ENDGAMES {
+            assert!(crate::game::GameState::from_fen(fen).is_ok(), "{} does not load", id);
+        }
+    }
+
+    #[test]
+    fn test_outcome_for_player() {
+        let mate = GameOutcome::Checkmate(PlayerColor::White);
+        assert_eq!(TablebaseOutcome::of_game(mate, PlayerColor::White), Some(TablebaseOutcome::Win));
+        assert_eq!(TablebaseOutcome::of_game(mate, PlayerColor::Black), Some(TablebaseOutcome::Loss));
+        assert_eq!(
+            TablebaseOutcome::of_game(GameOutcome::FiftyMoveRule, PlayerColor::Black),
+            Some(TablebaseOutcome::Draw)
+        );
+        assert_eq!(TablebaseOutcome::of_game(GameOutcome::InProgress, PlayerColor::White), None);
+        assert_eq!(TablebaseOutcome::from_category("cursed-win"), Some(TablebaseOutcome::Draw));
+        assert!(TablebaseOutcome::Win > TablebaseOutcome::Draw);
+    }
+}
@@ -0,0 +1,9 @@
+mod endgame;
+mod plan;
+mod puzzle;
+mod vision;
+
+pub use endgame::{EndgameSession, ENDGAMES};
+pub use plan::{Activity, TrainingPlan};
+pub use vision::{VisionDrill, VisionExercise, VisionHistory};
+pub use puzzle::{today, DailyPuzzle, PuzzleSession, PuzzleSource, PuzzleStep, PuzzleStreak};
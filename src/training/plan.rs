@@ -0,0 +1,129 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Something done in the app that counts toward the daily goals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    PuzzleSolved,
+    /// A study line was stepped through to its end
+    LineReviewed,
+    GameReviewed,
+}
+
+/// How much of each activity makes a day's training; zero leaves it out of the plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyGoals {
+    pub puzzles: u32,
+    pub lines: u32,
+    pub reviews: u32,
+}
+
+impl Default for DailyGoals {
+    fn default() -> Self {
+        Self {
+            puzzles: 3,
+            lines: 5,
+            reviews: 1,
+        }
+    }
+}
+
+/// Activity counted on one day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DayProgress {
+    pub puzzles: u32,
+    pub lines: u32,
+    pub reviews: u32,
+}
+
+impl DayProgress {
+    /// Whether every goal is met; a plan without goals is never met
+    pub fn meets(&self, goals: &DailyGoals) -> bool {
+        let any = goals.puzzles + goals.lines + goals.reviews > 0;
+        any && self.puzzles >= goals.puzzles && self.lines >= goals.lines && self.reviews >= goals.reviews
+    }
+}
+
+/// Daily goals, today's progress toward them and the streak of days they were met
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrainingPlan {
+    pub goals: DailyGoals,
+    day: Option<NaiveDate>,
+    progress: DayProgress,
+    streak: u32,
+    pub best: u32,
+    last_met: Option<NaiveDate>,
+}
+
+impl TrainingPlan {
+    /// Activity counted on `day`, nothing yet if the last activity was on another day
+    pub fn progress(&self, day: NaiveDate) -> DayProgress {
+        if self.day == Some(day) {
+            self.progress
+        } else {
+            DayProgress::default()
+        }
+    }
+
+    /// Consecutive days the goals were met, zero once a day has been missed
+    pub fn streak(&self, day: NaiveDate) -> u32 {
+        match self.last_met {
+            Some(last) if last == day || Some(last) == day.checked_sub_days(Days::new(1)) => self.streak,
+            _ => 0,
+        }
+    }
+
+    /// Count `activity` on `day`; true when it completes the day's goals
+    pub fn record(&mut self, activity: Activity, day: NaiveDate) -> bool {
+        self.progress = self.progress(day);
+        self.day = Some(day);
+        match activity {
+            Activity::PuzzleSolved => self.progress.puzzles += 1,
+            Activity::LineReviewed => self.progress.lines += 1,
+            Activity::GameReviewed => self.progress.reviews += 1,
+        }
+        if self.last_met == Some(day) || !self.progress.meets(&self.goals) {
+            return false;
+        }
+        self.streak = self.streak(day) + 1;
+        self.best = self.best.max(self.streak);
+        self.last_met = Some(day);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_streak() {
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let next = day.checked_add_days(Days::new(1)).unwrap();
+        let mut plan = TrainingPlan {
+            goals: DailyGoals {
+                puzzles: 2,
+                lines: 0,
+                reviews: 1,
+            },
+            ..Default::default()
+        };
+
+        assert!(!plan.record(Activity::PuzzleSolved, day));
+        assert!(!plan.record(Activity::GameReviewed, day));
+        assert!(plan.record(Activity::PuzzleSolved, day));
+        // Only the first completion of a day counts
+        assert!(!plan.record(Activity::PuzzleSolved, day));
+        assert_eq!(plan.progress(day).puzzles, 3);
+        assert_eq!(plan.progress(next), DayProgress::default());
+
+        plan.record(Activity::GameReviewed, next);
+        plan.record(Activity::PuzzleSolved, next);
+        assert!(plan.record(Activity::PuzzleSolved, next));
+        assert_eq!((plan.streak(next), plan.best), (2, 2));
+        assert_eq!(plan.streak(next.checked_add_days(Days::new(2)).unwrap()), 0);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::USER_AGENT;
+use crate::game::{parse_pgn, GameState};
+use crate::i18n::tr;
+use chrono::{Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+
+const DAILY_PUZZLE_URL: &str = "https://lichess.org/api/puzzle/daily";
+
+/// Puzzles used when Lichess is unreachable or the user prefers to stay offline:
+/// (id, FEN, solution in UCI)
+const BUNDLED: &[(&str, &str, &[&str])] = &[
+    ("back-rank", "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1", &["d1d8"]),
+    ("smothered", "6rk/6pp/8/6N1/8/8/8/6K1 w - - 0 1", &["g5f7"]),
+    ("arabian", "7k/R7/5N2/8/8/8/8/6K1 w - - 0 1", &["a7h7"]),
+    (
+        "scholars-mate",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+        &["f3f7"],
+    ),
+    ("queen-decoy", "r5k1/5ppp/8/8/3Q4/8/5PPP/3R2K1 w - - 0 1", &["d4d8", "a8d8", "d1d8"]),
+    ("philidors-legacy", "r6k/6pp/7N/3Q4/8/8/8/6K1 w - - 0 1", &["d5g8", "a8g8", "h6f7"]),
+];
+
+/// Where the daily puzzle comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PuzzleSource {
+    /// The Lichess puzzle of the day, falling back to the bundled set when offline
+    Lichess,
+    /// Only the puzzles shipped with the app
+    Bundled,
+    /// No daily puzzle card on startup
+    Off,
+}
+
+impl PuzzleSource {
+    pub fn all() -> &'static [PuzzleSource] {
+        &[PuzzleSource::Lichess, PuzzleSource::Bundled, PuzzleSource::Off]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PuzzleSource::Lichess => tr("puzzle.source_lichess"),
+            PuzzleSource::Bundled => tr("puzzle.source_bundled"),
+            PuzzleSource::Off => tr("puzzle.source_off"),
+        }
+    }
+}
+
+/// A position with a forced line. The side to move is the solver; solution moves alternate
+/// between the solver and the opponent's replies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub id: String,
+    pub fen: String,
+    pub solution: Vec<String>,
+    pub rating: Option<u32>,
+    /// Whether the puzzle came from Lichess rather than the bundled set
+    pub online: bool,
+}
+
+impl Puzzle {
+    /// The bundled puzzle for a day, cycling through the set
+    pub fn bundled(day: NaiveDate) -> Self {
+        let index = day.signed_duration_since(NaiveDate::default()).num_days().rem_euclid(BUNDLED.len() as i64);
+        let (id, fen, solution) = BUNDLED[index as usize];
+        Self {
+            id: id.to_string(),
+            fen: fen.to_string(),
+            solution: solution.iter().map(|m| m.to_string()).collect(),
+            rating: None,
+            online: false,
+        }
+    }
+
+    pub fn url(&self) -> Option<String> {
+        self.online.then(|| format!("https://lichess.org/training/{}", self.id))
+    }
+}
+
+/// The part of the Lichess daily puzzle response we use
+#[derive(Deserialize)]
+struct LichessDaily {
+    game: LichessGame,
+    puzzle: LichessPuzzle,
+}
+
+#[derive(Deserialize)]
+struct LichessGame {
+    /// Moves of the game up to the puzzle position, in SAN
+    pgn: String,
+}
+
+#[derive(Deserialize)]
+struct LichessPuzzle {
+    id: String,
+    rating: u32,
+    solution: Vec<String>,
+}
+
+fn fetch_lichess() -> Result<Puzzle, String> {
+    let daily: LichessDaily = ureq::get(DAILY_PUZZLE_URL)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    let game = parse_pgn(&daily.game.pgn)
+        .first()
+        .ok_or_else(|| tr("toast.pgn_empty").to_string())?
+        .to_game()
+        .map_err(|e| e.to_string())?;
+    Ok(Puzzle {
+        id: daily.puzzle.id,
+        fen: game.fen(),
+        solution: daily.puzzle.solution,
+        rating: Some(daily.puzzle.rating),
+        online: true,
+    })
+}
+
+/// Today's puzzle, fetched in the background when it comes from Lichess
+pub struct DailyPuzzle {
+    receiver: Option<mpsc::Receiver<Result<Puzzle, String>>>,
+    puzzle: Option<Puzzle>,
+    /// Why the bundled puzzle is shown instead of the Lichess one
+    pub fetch_error: Option<String>,
+}
+
+impl DailyPuzzle {
+    pub fn load(source: PuzzleSource) -> Self {
+        let mut daily = Self {
+            receiver: None,
+            puzzle: None,
+            fetch_error: None,
+        };
+        match source {
+            PuzzleSource::Lichess => {
+                let (tx, rx) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let _ = tx.send(fetch_lichess());
+                });
+                daily.receiver = Some(rx);
+            }
+            PuzzleSource::Bundled => daily.puzzle = Some(Puzzle::bundled(today())),
+            PuzzleSource::Off => {}
+        }
+        daily
+    }
+
+    /// The puzzle once it is available
+    pub fn poll(&mut self) -> Option<&Puzzle> {
+        if let Some(receiver) = &self.receiver {
+            let result = match receiver.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => Err(tr("puzzle.fetch_failed").to_string()),
+            };
+            self.receiver = None;
+            self.puzzle = Some(result.unwrap_or_else(|e| {
+                tracing::warn!("Daily puzzle fetch failed: {}", e);
+                self.fetch_error = Some(e);
+                Puzzle::bundled(today())
+            }));
+        }
+        self.puzzle.as_ref()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.receiver.is_some()
+    }
+}
+
+/// The current day in UTC, when Lichess switches its daily puzzle
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+/// Consecutive days with a solved daily puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PuzzleStreak {
+    current: u32,
+    pub best: u32,
+    last_solved: Option<NaiveDate>,
+}
+
+impl PuzzleStreak {
+    pub fn solved_on(&self, day: NaiveDate) -> bool {
+        self.last_solved == Some(day)
+    }
+
+    /// The running streak, zero once a day has been missed
+    pub fn current(&self, day: NaiveDate) -> u32 {
+        match self.last_solved {
+            Some(last) if last == day || Some(last) == day.checked_sub_days(Days::new(1)) => self.current,
+            _ => 0,
+        }
+    }
+
+    pub fn record_solved(&mut self, day: NaiveDate) {
+        if self.solved_on(day) {
+            return;
+        }
+        self.current = self.current(day) + 1;
+        self.best = self.best.max(self.current);
+        self.last_solved = Some(day);
+    }
+}
+
+/// What a move did to the puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleStep {
+    /// Correct; the opponent answers with this UCI move
+    Continue(String),
+    Solved,
+    /// Not the solution; the move should be taken back
+    Wrong,
+}
+
+/// Progress through a puzzle's solution
+#[derive(Debug, Clone)]
+pub struct PuzzleSession {
+    pub puzzle: Puzzle,
+    /// Index of the next solution move
+    progress: usize,
+    pub mistakes: u32,
+    /// The solution was shown, so solving no longer counts
+    pub revealed: bool,
+    pub solved: bool,
+}
+
+impl PuzzleSession {
+    pub fn new(puzzle: Puzzle) -> Self {
+        Self {
+            puzzle,
+            progress: 0,
+            mistakes: 0,
+            revealed: false,
+            solved: false,
+        }
+    }
+
+    /// Check the solver's move, played in `game` as `uci`. Any checkmate counts as correct.
+    pub fn play(&mut self, uci: &str, game: &GameState) -> PuzzleStep {
+        let expected = self.puzzle.solution.get(self.progress).map(String::as_str);
+        let mates = matches!(game.outcome(), crate::game::GameOutcome::Checkmate(_));
+        if expected != Some(uci) && !mates {
+            self.mistakes += 1;
+            return PuzzleStep::Wrong;
+        }
+        self.progress += 1;
+        match self.puzzle.solution.get(self.progress) {
+            Some(reply) if !mates => {
+                self.progress += 1;
+                if self.progress >= self.puzzle.solution.len() {
+                    self.solved = true;
+                }
+                PuzzleStep::Continue(reply.clone())
+            }
+            _ => {
+                self.solved = true;
+                PuzzleStep::Solved
+            }
+        }
+    }
+
+    /// The solver's next move, for hints and revealing the solution
+    pub fn next_move(&self) -> Option<&str> {
+        self.puzzle.solution.get(self.progress).map(String::as_str)
+    }
+
+    /// Whether solving should extend the streak
+    pub fn counts(&self) -> bool {
+        self.solved && !self.revealed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameOutcome;
+
+    #[test]
+    fn test_bundled_puzzles_end_in_mate() {
+        for (id, fen, solution) in BUNDLED {
+            let mut game = GameState::from_fen(fen).unwrap();
+            for uci in *solution {
+                game.make_move_uci(uci).unwrap_or_else(|_| panic!("{}: illegal {}", id, uci));
+            }
+            assert!(matches!(game.outcome(), GameOutcome::Checkmate(_)), "{} is not mate", id);
+        }
+    }
+
+    #[test]
+    fn test_session_and_streak() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let puzzle = (0..BUNDLED.len() as u64)
+            .map(|offset| Puzzle::bundled(day.checked_add_days(Days::new(offset)).unwrap()))
+            .find(|puzzle| puzzle.id == "queen-decoy")
+            .unwrap();
+        let mut game = GameState::from_fen(&puzzle.fen).unwrap();
+        let mut session = PuzzleSession::new(puzzle);
+
+        game.make_move_uci("d4g7").unwrap();
+        assert_eq!(session.play("d4g7", &game), PuzzleStep::Wrong);
+        game.go_back().unwrap();
+
+        game.make_move_uci("d4d8").unwrap();
+        assert_eq!(session.play("d4d8", &game), PuzzleStep::Continue("a8d8".to_string()));
+        game.make_move_uci("a8d8").unwrap();
+        game.make_move_uci("d1d8").unwrap();
+        assert_eq!(session.play("d1d8", &game), PuzzleStep::Solved);
+        assert!(session.counts());
+
+        let mut streak = PuzzleStreak::default();
+        streak.record_solved(day);
+        streak.record_solved(day);
+        streak.record_solved(day.checked_add_days(Days::new(1)).unwrap());
+        assert_eq!(streak.current(day.checked_add_days(Days::new(1)).unwrap()), 2);
+        assert_eq!(streak.current(day.checked_add_days(Days::new(3)).unwrap()), 0);
+        streak.record_solved(day.checked_add_days(Days::new(3)).unwrap());
+        assert_eq!((streak.current(day.checked_add_days(Days::new(3)).unwrap()), streak.best), (1, 2));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::i18n::{tr, tr_args};
+use serde::{Deserialize, Serialize};
+use shakmaty::{attacks, Bitboard, Color, Piece, Role, Square};
+use std::collections::VecDeque;
+
+/// Number of results kept in the persisted history
+const HISTORY_LEN: usize = 200;
+
+/// Kinds of board vision exercises
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VisionDrill {
+    /// Find a shortest knight route between two squares
+    KnightPath,
+    /// Mark every square a piece attacks
+    Attacks,
+}
+
+impl VisionDrill {
+    pub fn all() -> &'static [VisionDrill] {
+        &[VisionDrill::KnightPath, VisionDrill::Attacks]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VisionDrill::KnightPath => tr("vision.knight_path"),
+            VisionDrill::Attacks => tr("vision.attacks"),
+        }
+    }
+}
+
+/// A procedurally generated board vision question
+#[derive(Debug, Clone)]
+pub enum VisionExercise {
+    KnightPath {
+        from: Square,
+        to: Square,
+        /// Number of knight moves in a shortest route
+        shortest: usize,
+    },
+    Attacks {
+        piece: Piece,
+        square: Square,
+        /// Pawns that block sliding pieces
+        blockers: Bitboard,
+        attacked: Bitboard,
+    },
+}
+
+impl VisionExercise {
+    pub fn generate(drill: VisionDrill) -> Self {
+        match drill {
+            VisionDrill::KnightPath => {
+                let from = random_square();
+                // Routes of a single move are too easy to be worth asking
+                let to = loop {
+                    let to = random_square();
+                    if to != from && !attacks::knight_attacks(from).contains(to) {
+                        break to;
+                    }
+                };
+                let shortest = knight_path(from, to).len() - 1;
+                VisionExercise::KnightPath { from, to, shortest }
+            }
+            VisionDrill::Attacks => {
+                let role = *fastrand::choice(&[Role::Knight, Role::Bishop, Role::Rook, Role::Queen, Role::King])
+                    .expect("non-empty role list");
+                let color = if fastrand::bool() { Color::White } else { Color::Black };
+                let piece = Piece { color, role };
+                let square = random_square();
+                let mut blockers = Bitboard::EMPTY;
+                if matches!(role, Role::Bishop | Role::Rook | Role::Queen) {
+                    for _ in 0..fastrand::usize(1..=3) {
+                        let blocker = random_square();
+                        if blocker != square {
+                            blockers.add(blocker);
+                        }
+                    }
+                }
+                let attacked = attacks::attacks(square, piece, blockers);
+                VisionExercise::Attacks {
+                    piece,
+                    square,
+                    blockers,
+                    attacked,
+                }
+            }
+        }
+    }
+
+    pub fn drill(&self) -> VisionDrill {
+        match self {
+            VisionExercise::KnightPath { .. } => VisionDrill::KnightPath,
+            VisionExercise::Attacks { .. } => VisionDrill::Attacks,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match self {
+            VisionExercise::KnightPath { from, to, .. } => {
+                tr_args("vision.knight_path_prompt", &[from, to])
+            }
+            VisionExercise::Attacks { piece, square, .. } => {
+                tr_args("vision.attacks_prompt", &[&piece_name(piece.role), square])
+            }
+        }
+    }
+
+    /// Pieces to draw on the otherwise empty board
+    pub fn pieces(&self) -> Vec<(Square, Piece)> {
+        match self {
+            VisionExercise::KnightPath { from, .. } => vec![(*from, Role::Knight.of(Color::White))],
+            VisionExercise::Attacks {
+                piece,
+                square,
+                blockers,
+                ..
+            } => {
+                let blocker = Role::Pawn.of(!piece.color);
+                std::iter::once((*square, *piece))
+                    .chain(blockers.into_iter().map(|sq| (sq, blocker)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Squares the user may click as part of an answer
+    pub fn accepts(&self, square: Square) -> bool {
+        match self {
+            VisionExercise::KnightPath { from, .. } => square != *from,
+            VisionExercise::Attacks { square: origin, .. } => square != *origin,
+        }
+    }
+
+    /// Whether the clicked squares answer the exercise. Knight routes are checked in click order,
+    /// attacked squares as a set.
+    pub fn check(&self, answer: &[Square]) -> bool {
+        match self {
+            VisionExercise::KnightPath { from, to, shortest } => {
+                answer.len() == *shortest
+                    && answer.last() == Some(to)
+                    && std::iter::once(from)
+                        .chain(answer.iter())
+                        .zip(answer.iter())
+                        .all(|(a, b)| attacks::knight_attacks(*a).contains(*b))
+            }
+            VisionExercise::Attacks { attacked, .. } => {
+                let chosen: Bitboard = answer.iter().copied().collect();
+                chosen == *attacked
+            }
+        }
+    }
+
+    /// A correct answer, shown after a miss
+    pub fn solution(&self) -> Vec<Square> {
+        match self {
+            VisionExercise::KnightPath { from, to, .. } => knight_path(*from, *to).into_iter().skip(1).collect(),
+            VisionExercise::Attacks { attacked, .. } => attacked.into_iter().collect(),
+        }
+    }
+}
+
+fn random_square() -> Square {
+    Square::new(fastrand::u32(0..64))
+}
+
+fn piece_name(role: Role) -> &'static str {
+    match role {
+        Role::Pawn => tr("vision.pawn"),
+        Role::Knight => tr("vision.knight"),
+        Role::Bishop => tr("vision.bishop"),
+        Role::Rook => tr("vision.rook"),
+        Role::Queen => tr("vision.queen"),
+        Role::King => tr("vision.king"),
+    }
+}
+
+/// A shortest knight route from `from` to `to`, including both ends
+fn knight_path(from: Square, to: Square) -> Vec<Square> {
+    let mut previous: [Option<Square>; 64] = [None; 64];
+    let mut queue = VecDeque::from([from]);
+    let mut seen = Bitboard::from_square(from);
+    while let Some(square) = queue.pop_front() {
+        if square == to {
+            break;
+        }
+        for next in attacks::knight_attacks(square) {
+            if !seen.contains(next) {
+                seen.add(next);
+                previous[usize::from(next)] = Some(square);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut path = vec![to];
+    let mut current = to;
+    while let Some(prev) = previous[usize::from(current)] {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+/// Outcome of one answered exercise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisionResult {
+    pub drill: VisionDrill,
+    pub correct: bool,
+    pub seconds: f32,
+    pub timestamp: String,
+}
+
+/// Answered exercises, newest last, persisted between sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VisionHistory {
+    pub results: Vec<VisionResult>,
+}
+
+impl VisionHistory {
+    pub fn record(&mut self, drill: VisionDrill, correct: bool, seconds: f32) {
+        self.results.push(VisionResult {
+            drill,
+            correct,
+            seconds,
+            timestamp: chrono::Local::now().to_rfc3339(),
+        });
+        if self.results.len() > HISTORY_LEN {
+            self.results.remove(0);
+        }
+    }
+
+    /// Consecutive correct answers ending with the latest one
+    pub fn streak(&self) -> usize {
+        self.results.iter().rev().take_while(|r| r.correct).count()
+    }
+
+    /// Correct answers, total answers and average solve time for a drill
+    pub fn stats(&self, drill: VisionDrill) -> (usize, usize, f32) {
+        let results: Vec<&VisionResult> = self.results.iter().filter(|r| r.drill == drill).collect();
+        let correct = results.iter().filter(|r| r.correct).count();
+        let average = if results.is_empty() {
+            0.0
+        } else {
+            results.iter().map(|r| r.seconds).sum::<f32>() / results.len() as f32
+        };
+        (correct, results.len(), average)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knight_path() {
+        // a1 to h8 takes six knight moves
+        let path = knight_path(Square::A1, Square::H8);
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&Square::A1));
+        assert_eq!(path.last(), Some(&Square::H8));
+
+        let exercise = VisionExercise::KnightPath {
+            from: Square::A1,
+            to: Square::H8,
+            shortest: 6,
+        };
+        assert!(exercise.check(&exercise.solution()));
+        assert!(!exercise.check(&[Square::B3, Square::H8]));
+    }
+
+    #[test]
+    fn test_attacks_check_ignores_order() {
+        let exercise = VisionExercise::Attacks {
+            piece: Role::Knight.of(Color::White),
+            square: Square::A1,
+            blockers: Bitboard::EMPTY,
+            attacked: attacks::knight_attacks(Square::A1),
+        };
+        assert!(exercise.check(&[Square::C2, Square::B3]));
+        assert!(!exercise.check(&[Square::C2]));
+    }
+}
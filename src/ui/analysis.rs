@@ -1,4 +1,8 @@
+use crate::game::{coach_hints, move_number, GameState, MoveRecord, Notation, PlayerColor};
+use crate::i18n::{format_decimal, format_move_number, tr, tr_args};
+use crate::ui::{BoardThumbnail, PieceRenderer, Theme};
 use egui::{Color32, CornerRadius, Pos2, Rect, Stroke, Ui, Vec2};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default)]
 pub struct EngineLine {
@@ -7,26 +11,123 @@ pub struct EngineLine {
     pub score_mate: Option<i32>,
     pub depth: u32,
     pub pv: Vec<String>,
+    /// The PV replayed from the analyzed position, for display in any notation
+    pub records: Vec<MoveRecord>,
+    /// Full move number of the analyzed position
+    pub start_move_number: u32,
+    /// Whether the first PV move is played by White
+    pub white_first: bool,
+    /// Evaluations of the line as the search deepened
+    pub history: Vec<EvalSample>,
 }
 
 impl EngineLine {
+    /// Remember the current evaluation if the search reached a new depth
+    fn record_sample(&mut self) {
+        if self.history.last().is_some_and(|s| s.depth >= self.depth) {
+            return;
+        }
+        self.history.push(EvalSample {
+            depth: self.depth,
+            score_cp: self.score_cp,
+            score_mate: self.score_mate,
+        });
+    }
+
+    /// How much the evaluation still moves over the last few depths
+    fn stability(&self) -> Option<Stability> {
+        if self.history.len() < STABILITY_DEPTHS {
+            return None;
+        }
+        let recent = &self.history[self.history.len() - STABILITY_DEPTHS..];
+        let (min, max) = recent.iter().map(EvalSample::pawns).fold((f32::MAX, f32::MIN), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+        Some(if max - min <= STABLE_SPREAD {
+            Stability::Stable
+        } else if max - min <= SETTLING_SPREAD {
+            Stability::Settling
+        } else {
+            Stability::Swinging
+        })
+    }
+
+    /// Store a new UCI principal variation, replaying it from `base_fen` for display
+    fn set_pv(&mut self, base_fen: Option<&str>, pv: Vec<String>) {
+        self.records.clear();
+        if let Some(mut game) = base_fen.and_then(|fen| GameState::from_fen(fen).ok()) {
+            self.start_move_number = game.fullmove_number();
+            self.white_first = game.turn() == PlayerColor::White;
+            self.records = pv
+                .iter()
+                .map_while(|uci| game.make_move_uci(uci).ok())
+                .collect();
+        }
+        self.pv = pv;
+    }
+
+    /// Whether every PV move could be replayed, so SAN and move numbers are available
+    fn has_records(&self) -> bool {
+        !self.records.is_empty() && self.records.len() == self.pv.len()
+    }
+
+    /// Ply of the game the PV's first move is
+    fn first_ply(&self) -> usize {
+        (self.start_move_number as usize).saturating_sub(1) * 2 + usize::from(!self.white_first)
+    }
+
+    /// Move number to show before the PV move at `index` ("12." or "12..." for Black)
+    fn move_number_prefix(&self, index: usize) -> Option<String> {
+        if !self.has_records() {
+            return None;
+        }
+        let ply = self.first_ply() + index;
+        let number = ply / 2 + 1;
+        if ply % 2 == 0 {
+            Some(format_move_number(number))
+        } else if index == 0 {
+            Some(format!("{}..", format_move_number(number)))
+        } else {
+            None
+        }
+    }
+
+    /// FEN after the PV move at `index`, if the line could be replayed
+    fn fen_after(&self, index: usize) -> Option<&str> {
+        if !self.has_records() {
+            return None;
+        }
+        self.records.get(index).map(|r| r.resulting_fen.as_str())
+    }
+
+    /// Text for the PV move at `index` in the given notation, falling back to UCI
+    fn move_text(&self, index: usize, notation: Notation) -> String {
+        match self.records.get(index) {
+            Some(record) if self.has_records() => notation.format(record),
+            _ => self.pv[index].clone(),
+        }
+    }
+
     pub fn format_score(&self) -> String {
-        if let Some(mate) = self.score_mate {
-            if mate > 0 {
-                format!("+M{}", mate)
-            } else {
-                format!("-M{}", mate.abs())
-            }
-        } else if let Some(cp) = self.score_cp {
-            let pawns = cp as f32 / 100.0;
-            if pawns >= 0.0 {
-                format!("+{:.2}", pawns)
-            } else {
-                format!("{:.2}", pawns)
+        format_score(self.score_cp, self.score_mate)
+    }
+
+    /// Score and moves in SAN for a PGN comment, without the locale's number formatting
+    fn pgn_text(&self) -> String {
+        let mut text = match (self.score_mate, self.score_cp) {
+            (Some(mate), _) => format!("#{}", mate),
+            (None, Some(cp)) => format!("{:+.2}", cp as f32 / 100.0),
+            (None, None) => "--".to_string(),
+        };
+        for i in 0..self.pv.len() {
+            if let Some(number) = move_number(self.first_ply(), i).filter(|_| self.has_records()) {
+                text.push(' ');
+                text.push_str(&number);
             }
-        } else {
-            "--".to_string()
+            text.push(' ');
+            text.push_str(&self.move_text(i, Notation::San));
         }
+        text
     }
 
     pub fn score_for_sorting(&self) -> f32 {
@@ -55,52 +156,213 @@ impl EngineLine {
     }
 }
 
+fn format_score(score_cp: Option<i32>, score_mate: Option<i32>) -> String {
+    if let Some(mate) = score_mate {
+        if mate > 0 {
+            format!("+M{}", mate)
+        } else {
+            format!("-M{}", mate.abs())
+        }
+    } else if let Some(cp) = score_cp {
+        let pawns = cp as f32 / 100.0;
+        if pawns >= 0.0 {
+            format!("+{}", format_decimal(pawns, 2))
+        } else {
+            format_decimal(pawns, 2)
+        }
+    } else {
+        "--".to_string()
+    }
+}
+
+/// Evaluation of a line at one depth
+#[derive(Debug, Clone, Copy)]
+pub struct EvalSample {
+    pub depth: u32,
+    pub score_cp: Option<i32>,
+    pub score_mate: Option<i32>,
+}
+
+impl EvalSample {
+    /// Evaluation in pawns, capped at ten with mates at the cap
+    fn pawns(&self) -> f32 {
+        match (self.score_mate, self.score_cp) {
+            (Some(mate), _) => 10.0f32.copysign(mate as f32),
+            (None, Some(cp)) => (cp as f32 / 100.0).clamp(-10.0, 10.0),
+            (None, None) => 0.0,
+        }
+    }
+}
+
+/// Whether a line's evaluation has settled as the search deepened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stability {
+    Stable,
+    Settling,
+    Swinging,
+}
+
+impl Stability {
+    fn label(self) -> &'static str {
+        match self {
+            Stability::Stable => tr("analysis.stable"),
+            Stability::Settling => tr("analysis.settling"),
+            Stability::Swinging => tr("analysis.swinging"),
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Stability::Stable => Color32::from_rgb(90, 170, 90),
+            Stability::Settling => Color32::from_rgb(200, 170, 60),
+            Stability::Swinging => Color32::from_rgb(220, 90, 60),
+        }
+    }
+}
+
+/// An engine line the user pinned, tracked by its first move regardless of MultiPV rank
+#[derive(Debug, Clone)]
+pub struct PinnedLine {
+    pub first_move: String,
+    pub line: EngineLine,
+}
+
+impl PinnedLine {
+    fn new(line: &EngineLine) -> Option<Self> {
+        Some(Self {
+            first_move: line.pv.first()?.clone(),
+            line: line.clone(),
+        })
+    }
+}
+
+/// What the user did with an engine line
+enum LineAction {
+    /// Play the PV up to and including the clicked move
+    Play(Vec<String>),
+    TogglePin,
+    /// Search again without the line's first move
+    Exclude,
+}
+
+/// What the user did in the analysis panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalysisAction {
+    /// Play `path` (UCI) from `base_fen`, the position the analysis started from
+    Play { base_fen: String, path: Vec<String> },
+    /// Moves were excluded from or restored to the search, which has to restart
+    Exclusions,
+}
+
+/// Edge length of the hover preview board
+const PREVIEW_BOARD_SIZE: f32 = 160.0;
+
+/// Number of past evaluations shown under a pinned line
+const PINNED_HISTORY_LEN: usize = 6;
+
+/// Depths drawn in a line's evaluation sparkline
+const SPARKLINE_DEPTHS: usize = 12;
+
+/// Size of the evaluation sparkline next to a line's score
+const SPARKLINE_SIZE: Vec2 = Vec2::new(40.0, 14.0);
+
+/// Latest depths over which a line's stability is judged
+const STABILITY_DEPTHS: usize = 5;
+
+/// Largest swing in pawns over those depths for an evaluation to count as settled or settling
+const STABLE_SPREAD: f32 = 0.2;
+const SETTLING_SPREAD: f32 = 0.6;
+
+/// Drop in pawns from the best to the second-best line that makes the best move an "only move"
+const ONLY_MOVE_GAP: f32 = 1.5;
+
+/// Above this evaluation the second-best move still wins, so the best one isn't forced
+const STILL_WINNING: f32 = 2.0;
+
+/// How the analysis panel is laid out, remembered between sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisSettings {
+    /// Number of lines to display (1-5)
+    pub display_lines: u32,
+    pub show_eval_bar: bool,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        Self {
+            display_lines: 3,
+            show_eval_bar: true,
+        }
+    }
+}
+
 pub struct AnalysisPanel {
     /// All lines received from engine (up to 5)
     pub all_lines: Vec<EngineLine>,
-    /// Number of lines to display (1-5)
-    pub display_lines: u32,
+    pub settings: AnalysisSettings,
     /// Maximum lines the engine is calculating
     pub max_calculated: u32,
     pub is_analyzing: bool,
+    /// Analysis is on hold until the window has focus or the computer is charging again
+    pub power_paused: bool,
     pub total_nodes: u64,
     pub current_depth: u32,
     /// The FEN position where analysis started - all lines are relative to this
     pub base_fen: Option<String>,
+    /// Line kept in a fixed slot above the others
+    pub pinned: Option<PinnedLine>,
+    /// First moves (UCI) left out of the search of the base position
+    pub excluded: Vec<String>,
+    /// Moves (UCI) of the line under the pointer, drawn on the board until the pointer leaves
+    pub hovered_pv: Option<Vec<String>>,
 }
 
 impl Default for AnalysisPanel {
     fn default() -> Self {
         Self {
             all_lines: Vec::new(),
-            display_lines: 3,
+            settings: AnalysisSettings::default(),
             max_calculated: 5,
             is_analyzing: false,
+            power_paused: false,
             total_nodes: 0,
             current_depth: 0,
             base_fen: None,
+            pinned: None,
+            excluded: Vec::new(),
+            hovered_pv: None,
         }
     }
 }
 
 impl AnalysisPanel {
-    /// Returns clicked moves if user clicked on PV moves
-    /// Returns (base_fen, move_path) - the FEN where analysis started, and the full move sequence to play
-    /// This allows the app to reset to the base position and apply moves from there
-    pub fn show(&mut self, ui: &mut Ui) -> Option<(String, Vec<String>)> {
-        let mut result: Option<(String, Vec<String>)> = None;
+    /// Returns the moves to play when the user clicked a move of a line, from the FEN where
+    /// analysis started so the app can reset to it first, or that the excluded moves changed
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        notation: Notation,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+        coach: bool,
+    ) -> Option<AnalysisAction> {
+        let mut result = None;
+        self.hovered_pv = None;
         
         ui.vertical(|ui| {
-            ui.heading("Analysis");
+            ui.heading(tr("analysis.heading"));
             ui.separator();
 
             // Status and controls
             ui.horizontal(|ui| {
                 if self.is_analyzing {
                     ui.spinner();
-                    ui.label("Analyzing...");
+                    ui.label(tr("analysis.analyzing"));
+                } else if self.power_paused {
+                    ui.label(tr("analysis.paused")).on_hover_text(tr("analysis.power_paused"));
                 } else {
-                    ui.label("⏸ Paused");
+                    ui.label(tr("analysis.paused"));
                 }
                 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -111,45 +373,90 @@ impl AnalysisPanel {
             ui.add_space(8.0);
 
             // Evaluation bar (from best line)
-            if let Some(best) = self.all_lines.first() {
+            if let Some(best) = self.all_lines.first().filter(|_| self.settings.show_eval_bar) {
                 self.show_eval_bar(ui, best);
             }
 
+            self.show_eval_gap(ui);
+
+            if coach {
+                self.show_coach_hints(ui);
+            }
+
             ui.add_space(8.0);
 
             // Number of lines dropdown
             ui.horizontal(|ui| {
-                ui.label("Lines:");
+                ui.label(tr("analysis.lines"));
                 egui::ComboBox::from_id_salt("lines_dropdown")
                     .width(60.0)
-                    .selected_text(format!("{}", self.display_lines))
+                    .selected_text(format!("{}", self.settings.display_lines))
                     .show_ui(ui, |ui| {
                         for n in 1..=5 {
-                            ui.selectable_value(&mut self.display_lines, n, format!("{}", n));
+                            ui.selectable_value(&mut self.settings.display_lines, n, format!("{}", n));
                         }
                     });
-                ui.label(format!("/ {} calculating", self.max_calculated));
+                ui.label(tr_args("analysis.calculating", &[&self.max_calculated]));
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.settings.show_eval_bar, tr("analysis.eval_bar"));
             });
 
             ui.add_space(8.0);
             ui.separator();
 
+            let mut toggled_pin = None;
+
+            // Pinned line stays in the first slot whatever its current rank
+            if let Some(pinned) = self.pinned.clone() {
+                match self.show_engine_line(ui, &pinned.line, true, notation, theme, piece_renderer) {
+                    Some(LineAction::Play(path)) => {
+                        let base_fen = self.base_fen.clone().unwrap_or_default();
+                        result = Some(AnalysisAction::Play { base_fen, path });
+                    }
+                    Some(LineAction::TogglePin) => toggled_pin = Some(pinned.line.clone()),
+                    Some(LineAction::Exclude) | None => {}
+                }
+                Self::show_pinned_history(ui, &pinned);
+                ui.separator();
+            }
+
             // Engine lines - only show display_lines
+            let pinned_move = self.pinned.as_ref().map(|p| p.first_move.clone());
             let lines_to_show: Vec<_> = self.all_lines.iter()
-                .take(self.display_lines as usize)
+                .filter(|l| pinned_move.is_none() || l.pv.first() != pinned_move.as_ref())
+                .take(self.settings.display_lines as usize)
                 .cloned()
                 .collect();
                 
             for line in &lines_to_show {
-                if let Some(path) = self.show_engine_line(ui, line) {
-                    // Include base_fen so app can reset to correct position
-                    let base_fen = self.base_fen.clone().unwrap_or_default();
-                    result = Some((base_fen, path));
+                match self.show_engine_line(ui, line, false, notation, theme, piece_renderer) {
+                    Some(LineAction::Play(path)) => {
+                        // Include base_fen so app can reset to correct position
+                        let base_fen = self.base_fen.clone().unwrap_or_default();
+                        result = Some(AnalysisAction::Play { base_fen, path });
+                    }
+                    Some(LineAction::TogglePin) => toggled_pin = Some(line.clone()),
+                    Some(LineAction::Exclude) => {
+                        if let Some(first) = line.pv.first() {
+                            self.excluded.push(first.clone());
+                            result = Some(AnalysisAction::Exclusions);
+                        }
+                    }
+                    None => {}
                 }
             }
 
+            if let Some(line) = toggled_pin {
+                self.toggle_pin(&line);
+            }
+
+            if self.show_excluded(ui, notation) {
+                result = Some(AnalysisAction::Exclusions);
+            }
+
             if self.all_lines.is_empty() {
-                ui.label("No analysis yet...");
+                ui.label(tr("analysis.empty"));
             }
         });
         
@@ -215,14 +522,123 @@ impl AnalysisPanel {
         }
     }
 
+    /// What the position and the engine's best move are about, in words
+    fn show_coach_hints(&self, ui: &mut Ui) {
+        let Some(fen) = &self.base_fen else {
+            return;
+        };
+        let pv = self.all_lines.iter().find(|l| l.id == 1).map_or(&[][..], |l| &l.pv[..]);
+        for hint in coach_hints(fen, pv) {
+            ui.label(egui::RichText::new(format!("💡 {}", hint.text())).small());
+        }
+    }
+
+    /// Best and second-best lines by engine rank
+    fn top_two_lines(&self) -> Option<(&EngineLine, &EngineLine)> {
+        let best = self.all_lines.iter().find(|l| l.id == 1)?;
+        let second = self.all_lines.iter().find(|l| l.id == 2)?;
+        Some((best, second))
+    }
+
+    /// Gap between the best and second-best line, flagging forced "only moves"
+    fn show_eval_gap(&self, ui: &mut Ui) {
+        let Some((best, second)) = self.top_two_lines() else {
+            return;
+        };
+        let gap = best.score_for_sorting() - second.score_for_sorting();
+
+        ui.horizontal(|ui| {
+            // Mate scores have no meaningful centipawn gap
+            if best.score_cp.is_some() && second.score_cp.is_some() {
+                ui.label(tr_args("analysis.eval_gap", &[&format_decimal(gap, 2)]))
+                    .on_hover_text(tr("analysis.eval_gap_hint"));
+            }
+            if gap >= ONLY_MOVE_GAP && second.score_for_sorting() < STILL_WINNING {
+                ui.colored_label(Color32::from_rgb(255, 170, 0), tr("analysis.only_move"))
+                    .on_hover_text(tr("analysis.only_move_hint"));
+            }
+        });
+    }
+
+    /// Evaluations of the pinned line at previous depths, newest last
+    fn show_pinned_history(ui: &mut Ui, pinned: &PinnedLine) {
+        let start = pinned.line.history.len().saturating_sub(PINNED_HISTORY_LEN);
+        let history: Vec<String> = pinned.line.history[start..]
+            .iter()
+            .map(|s| format!("d{} {}", s.depth, format_score(s.score_cp, s.score_mate)))
+            .collect();
+        if !history.is_empty() {
+            ui.weak(history.join(" → "));
+        }
+    }
+
+    /// Sparkline of the line's evaluation by depth and a badge saying whether it has settled
+    fn show_stability(ui: &mut Ui, line: &EngineLine) {
+        let start = line.history.len().saturating_sub(SPARKLINE_DEPTHS);
+        let samples = &line.history[start..];
+        if samples.len() < 2 {
+            return;
+        }
+
+        let (rect, response) = ui.allocate_exact_size(SPARKLINE_SIZE, egui::Sense::hover());
+        let (min, max) = samples.iter().map(EvalSample::pawns).fold((f32::MAX, f32::MIN), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+        // Keep small wobbles small rather than stretching them over the full height
+        let mid = (min + max) / 2.0;
+        let half_range = ((max - min) / 2.0).max(0.5);
+        let step = rect.width() / (samples.len() - 1) as f32;
+        let points: Vec<Pos2> = samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let y = rect.center().y - (s.pawns() - mid) / half_range * rect.height() / 2.0;
+                Pos2::new(rect.min.x + i as f32 * step, y)
+            })
+            .collect();
+        let stability = line.stability();
+        let color = stability.map_or(ui.visuals().weak_text_color(), Stability::color);
+        ui.painter().add(egui::Shape::line(points, Stroke::new(1.5, color)));
+        response.on_hover_text(
+            samples
+                .iter()
+                .map(|s| format!("d{} {}", s.depth, format_score(s.score_cp, s.score_mate)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+
+        if let Some(stability) = stability {
+            ui.colored_label(stability.color(), stability.label())
+                .on_hover_text(tr("analysis.stability_hint"));
+        }
+    }
+
     /// Shows an engine line
-    /// Returns Vec<move_uci> - the full path up to and including the clicked move
-    fn show_engine_line(&self, ui: &mut Ui, line: &EngineLine) -> Option<Vec<String>> {
-        let mut clicked_path: Option<Vec<String>> = None;
+    /// Returns the full path up to and including a clicked move, or a pin toggle
+    fn show_engine_line(
+        &mut self,
+        ui: &mut Ui,
+        line: &EngineLine,
+        is_pinned: bool,
+        notation: Notation,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+    ) -> Option<LineAction> {
+        let mut action = None;
         
-        ui.horizontal_wrapped(|ui| {
+        let row = ui.horizontal_wrapped(|ui| {
+            let pin_hint = if is_pinned { tr("analysis.unpin") } else { tr("analysis.pin") };
+            if ui.selectable_label(is_pinned, "📌").on_hover_text(pin_hint).clicked() {
+                action = Some(LineAction::TogglePin);
+            }
+
             // Line number and score
-            ui.label(format!("{}.", line.id));
+            if !is_pinned {
+                if ui.small_button("⊘").on_hover_text(tr("analysis.exclude")).clicked() {
+                    action = Some(LineAction::Exclude);
+                }
+                ui.label(format!("{}.", line.id));
+            }
             
             let score_text = line.format_score();
             let color = if line.score_cp.unwrap_or(0) > 0 || line.score_mate.unwrap_or(0) > 0 {
@@ -233,12 +649,17 @@ impl AnalysisPanel {
                 ui.visuals().text_color()
             };
             ui.colored_label(color, score_text);
+            Self::show_stability(ui, line);
             
             // PV moves as clickable hyperlinks (ALL of them)
             if !line.pv.is_empty() {
-                for (i, mv) in line.pv.iter().enumerate() {
+                for i in 0..line.pv.len() {
+                    if let Some(prefix) = line.move_number_prefix(i) {
+                        ui.label(prefix);
+                    }
+
                     // All moves are clickable - use Button for proper pointer cursor
-                    let text = egui::RichText::new(mv)
+                    let text = egui::RichText::new(line.move_text(i, notation))
                         .color(ui.visuals().hyperlink_color)
                         .underline();
                     
@@ -246,17 +667,83 @@ impl AnalysisPanel {
                         .fill(egui::Color32::TRANSPARENT)
                         .stroke(egui::Stroke::NONE)
                         .sense(egui::Sense::click()));
+
+                    // Preview the position after this move without touching the main board
+                    let response = match line.fen_after(i) {
+                        Some(fen) => response.on_hover_ui(|ui| {
+                            BoardThumbnail::show(ui, fen, theme, piece_renderer, PREVIEW_BOARD_SIZE);
+                        }),
+                        None => response,
+                    };
                     
                     if response.clicked() {
                         // Return all moves from start up to and including clicked move
-                        clicked_path = Some(line.pv[..=i].to_vec());
+                        action = Some(LineAction::Play(line.pv[..=i].to_vec()));
                     }
                     ui.label(" ");
                 }
             }
         });
+        if row.response.contains_pointer() {
+            self.hovered_pv = Some(line.pv.clone());
+        }
         
-        clicked_path
+        action
+    }
+
+    /// Moves left out of the search, each with a button to search it again; true when one was restored
+    fn show_excluded(&mut self, ui: &mut Ui, notation: Notation) -> bool {
+        if self.excluded.is_empty() {
+            return false;
+        }
+        let base_fen = self.base_fen.clone().unwrap_or_default();
+        let mut restored = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.weak(tr("analysis.excluded"));
+            for (i, uci) in self.excluded.iter().enumerate() {
+                let name = GameState::from_fen(&base_fen)
+                    .and_then(|mut game| game.make_move_uci(uci))
+                    .map_or_else(|_| uci.clone(), |record| notation.format(&record));
+                let text = format!("{} ✖", name);
+                if ui.small_button(text).on_hover_text(tr("analysis.restore_move")).clicked() {
+                    restored = Some(i);
+                }
+            }
+        });
+        if let Some(i) = restored {
+            self.excluded.remove(i);
+        }
+        restored.is_some()
+    }
+
+    fn toggle_pin(&mut self, line: &EngineLine) {
+        let already_pinned = self
+            .pinned
+            .as_ref()
+            .is_some_and(|p| line.pv.first() == Some(&p.first_move));
+        self.pinned = if already_pinned { None } else { PinnedLine::new(line) };
+    }
+
+    /// Reset for analysis of `fen`, keeping the pin if the position hasn't changed
+    pub fn start(&mut self, fen: String) {
+        self.clear();
+        if self.base_fen.as_deref() != Some(fen.as_str()) {
+            self.pinned = None;
+            self.excluded.clear();
+        }
+        self.base_fen = Some(fen);
+    }
+
+    /// The displayed lines as one PGN comment, like "Stockfish 17 depth 24: +0.35 1. e4 e5; +0.20 1. d4"
+    pub fn pgn_comment(&self, engine: &str) -> Option<String> {
+        let lines: Vec<String> = self
+            .all_lines
+            .iter()
+            .filter(|l| !l.pv.is_empty())
+            .take(self.settings.display_lines as usize)
+            .map(EngineLine::pgn_text)
+            .collect();
+        (!lines.is_empty()).then(|| format!("{} depth {}: {}", engine, self.current_depth, lines.join("; ")))
     }
 
     /// Update a line from engine output (always store up to 5)
@@ -275,16 +762,20 @@ impl AnalysisPanel {
                 line.depth = d;
             }
             if !pv.is_empty() {
-                line.pv = pv;
+                line.set_pv(self.base_fen.as_deref(), pv.clone());
             }
+            line.record_sample();
         } else {
-            self.all_lines.push(EngineLine {
+            let mut line = EngineLine {
                 id,
                 score_cp,
                 score_mate,
                 depth: depth.unwrap_or(0),
-                pv,
-            });
+                ..Default::default()
+            };
+            line.set_pv(self.base_fen.as_deref(), pv.clone());
+            line.record_sample();
+            self.all_lines.push(line);
             // Sort by score (best first)
             self.all_lines.sort_by(|a, b| {
                 b.score_for_sorting().partial_cmp(&a.score_for_sorting()).unwrap()
@@ -295,6 +786,17 @@ impl AnalysisPanel {
             }
         }
         
+        // Follow the pinned line by its first move, whichever rank it has now
+        if let Some(pinned) = self.pinned.as_mut().filter(|p| pv.first() == Some(&p.first_move)) {
+            pinned.line.score_cp = score_cp;
+            pinned.line.score_mate = score_mate;
+            if let Some(d) = depth {
+                pinned.line.depth = d;
+            }
+            pinned.line.set_pv(self.base_fen.as_deref(), pv);
+            pinned.line.record_sample();
+        }
+
         // Track max calculated
         self.max_calculated = self.max_calculated.max(id);
     }
@@ -307,10 +809,20 @@ impl AnalysisPanel {
     }
 
     pub fn get_display_lines(&self) -> u32 {
-        self.display_lines
+        self.settings.display_lines
     }
     
     pub fn set_display_lines(&mut self, n: u32) {
-        self.display_lines = n.clamp(1, 5);
+        self.settings.display_lines = n.clamp(1, 5);
+    }
+
+    /// The panel laid out with settings from an earlier session
+    pub fn with_settings(settings: AnalysisSettings) -> Self {
+        let mut panel = Self {
+            settings,
+            ..Self::default()
+        };
+        panel.set_display_lines(panel.settings.display_lines);
+        panel
     }
 }
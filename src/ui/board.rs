@@ -1,20 +1,58 @@
-use crate::game::{GameState};
+use crate::game::{GameState, Heatmap};
+use crate::ui::settings::{ClickSettings, OwnPieceClick};
 use crate::ui::{PieceRenderer, Theme};
 use egui::{
-    pos2, vec2, Color32, Id, Rect, Response, Sense, Stroke, Ui,
+    pos2, vec2, Color32, Id, Pos2, Rect, Response, Sense, Stroke, Ui,
 };
-use shakmaty::{File, Move, Rank, Square};
+use shakmaty::{Color, File, Move, Rank, Role, Square};
+
+/// Opacity of the heatmap tint on the busiest square
+const HEATMAP_MAX_ALPHA: f32 = 170.0;
+
+/// Colors of arrows for moves of the side to move and of the other side
+const ARROW_COLORS: [Color32; 2] = [
+    Color32::from_rgba_premultiplied(200, 130, 0, 200),
+    Color32::from_rgba_premultiplied(40, 100, 180, 200),
+];
+
+/// Pieces offered by the promotion picker, nearest the promotion square first
+const PROMOTION_CHOICES: [Role; 4] = [Role::Queen, Role::Knight, Role::Rook, Role::Bishop];
+
+/// Keys that pick the promotion piece when held while a pawn is dropped on the last rank
+const PROMOTION_KEYS: [(egui::Key, Role); 4] = [
+    (egui::Key::Q, Role::Queen),
+    (egui::Key::N, Role::Knight),
+    (egui::Key::R, Role::Rook),
+    (egui::Key::B, Role::Bishop),
+];
 
 pub struct ChessBoard<'a> {
     game: &'a GameState,
     theme: Theme,
     flipped: bool,
     piece_renderer: &'a mut PieceRenderer,
+    /// Square intensities from 0 to 1 tinted over the board
+    heatmap: Option<[f32; 64]>,
+    /// Promote to a queen without asking
+    auto_queen: bool,
+    /// Moves drawn as numbered arrows over the pieces, in the order they would be played
+    arrows: Vec<(Square, Square)>,
+    clicks: ClickSettings,
+    /// Side the engine plays, whose last move gets its own highlight
+    engine_side: Option<Color>,
+    /// Side whose pieces can be picked up, the side to move unless premoving
+    movable_side: Color,
 }
 
 pub struct BoardResponse {
     pub move_made: Option<Move>,
     pub square_clicked: Option<Square>,
+    /// A click dropped the selection
+    pub deselected: bool,
+    /// Vertical mouse wheel movement while the pointer is over the board
+    pub scroll_delta: f32,
+    /// Pinch (or ctrl+wheel) zoom factor while the pointer is over the board, 1.0 when unchanged
+    pub zoom_delta: f32,
 }
 
 impl<'a> ChessBoard<'a> {
@@ -29,9 +67,45 @@ impl<'a> ChessBoard<'a> {
             theme,
             flipped,
             piece_renderer,
+            heatmap: None,
+            auto_queen: false,
+            arrows: Vec::new(),
+            clicks: ClickSettings::default(),
+            engine_side: None,
+            movable_side: game.turn().into(),
         }
     }
 
+    pub fn with_movable_side(mut self, movable_side: Color) -> Self {
+        self.movable_side = movable_side;
+        self
+    }
+
+    pub fn with_engine_side(mut self, engine_side: Option<Color>) -> Self {
+        self.engine_side = engine_side;
+        self
+    }
+
+    pub fn with_clicks(mut self, clicks: ClickSettings) -> Self {
+        self.clicks = clicks;
+        self
+    }
+
+    pub fn with_arrows(mut self, arrows: Vec<(Square, Square)>) -> Self {
+        self.arrows = arrows;
+        self
+    }
+
+    pub fn with_auto_queen(mut self, auto_queen: bool) -> Self {
+        self.auto_queen = auto_queen;
+        self
+    }
+
+    pub fn with_heatmap(mut self, heatmap: Option<&Heatmap>) -> Self {
+        self.heatmap = heatmap.map(|h| h.intensities());
+        self
+    }
+
     pub fn show(
         &mut self,
         ui: &mut Ui,
@@ -41,23 +115,52 @@ impl<'a> ChessBoard<'a> {
         let mut response = BoardResponse {
             move_made: None,
             square_clicked: None,
+            deselected: false,
+            scroll_delta: 0.0,
+            zoom_delta: 1.0,
         };
+        // Square a piece is being dragged from, kept across frames
+        let drag_id = Id::new("chess_board_drag");
+        let mut dragging_from: Option<Square> = ui.data(|d| d.get_temp(drag_id));
+        // Pawn move waiting for the promotion piece to be picked, dropped once it's no longer legal
+        let promotion_id = Id::new("chess_board_promotion");
+        let mut pending_promotion: Option<(Square, Square)> = ui
+            .data(|d| d.get_temp(promotion_id))
+            .filter(|&(from, to)| legal_moves_for_selected.iter().any(|m| m.from() == Some(from) && m.to() == to));
+
+        // Destination clicked once, waiting for the confirming click
+        let destination_id = Id::new("chess_board_destination");
+        let mut marked_destination: Option<Square> = ui
+            .data(|d| d.get_temp(destination_id))
+            .filter(|&to| legal_moves_for_selected.iter().any(|m| m.to() == to));
 
         let available_size = ui.available_size();
         let board_size = available_size.x.min(available_size.y);
         let square_size = board_size / 8.0;
 
+        let origin = ui.cursor().min;
+
         // Use a scope to isolate board interactions
         ui.scope(|ui| {
             // Allocate the board area
-            let board_rect = ui
-                .allocate_rect(
-                    egui::Rect::from_min_size(ui.cursor().min, vec2(board_size, board_size)),
-                    Sense::hover(),
-                )
-                .rect;
+            let board_response = ui.allocate_rect(
+                egui::Rect::from_min_size(origin, vec2(board_size, board_size)),
+                Sense::hover(),
+            );
+            let board_rect = board_response.rect;
+
+            if board_response.contains_pointer() {
+                response.scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
+                response.zoom_delta = ui.input(|i| i.zoom_delta());
+            }
 
         let last_move_squares = self.game.last_move_squares();
+        let last_mover: Color = self.game.turn().opposite().into();
+        let last_move_color = if self.engine_side == Some(last_mover) {
+            self.theme.engine_move_highlight()
+        } else {
+            self.theme.last_move_highlight()
+        };
 
         let king_in_check = if self.game.is_check() {
             self.game.king_square(self.game.turn())
@@ -85,7 +188,7 @@ impl<'a> ChessBoard<'a> {
 
                 // Determine square color
                 let is_light = (file_idx + rank_idx) % 2 == 1;
-                let is_selected = *selected_square == Some(square);
+                let is_selected = *selected_square == Some(square) || marked_destination == Some(square);
                 let is_last_move = last_move_squares
                     .map(|(from, to)| square == from || square == to)
                     .unwrap_or(false);
@@ -96,7 +199,7 @@ impl<'a> ChessBoard<'a> {
                 } else if is_selected {
                     self.theme.selected_square()
                 } else if is_last_move {
-                    self.theme.last_move_highlight()
+                    last_move_color
                 } else if is_light {
                     self.theme.light_square()
                 } else {
@@ -105,6 +208,12 @@ impl<'a> ChessBoard<'a> {
 
                 // Draw square background using painter
                 ui.painter().rect_filled(rect, 0.0, bg_color);
+                if let Some(intensity) = self.heatmap.map(|h| h[usize::from(square)]) {
+                    if intensity > 0.0 {
+                        let alpha = (intensity * HEATMAP_MAX_ALPHA) as u8;
+                        ui.painter().rect_filled(rect, 0.0, Color32::from_rgba_unmultiplied(230, 60, 30, alpha));
+                    }
+                }
 
                 // Draw legal move indicator
                 let is_legal_destination = legal_moves_for_selected
@@ -130,22 +239,10 @@ impl<'a> ChessBoard<'a> {
                     }
                 }
 
-                // Draw piece
+                // Draw piece (a dragged piece follows the pointer instead)
                 if let Some((role, color)) = self.game.piece_at(square) {
-                    let piece_size = (square_size * 0.9) as u32;
-                    if piece_size > 0 {
-                        if let Some(texture) = self.piece_renderer.get_texture(ui.ctx(), role, color, piece_size) {
-                            let piece_rect = Rect::from_center_size(
-                                rect.center(),
-                                vec2(square_size * 0.9, square_size * 0.9),
-                            );
-                            ui.painter().image(
-                                texture.id(),
-                                piece_rect,
-                                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
-                                Color32::WHITE,
-                            );
-                        }
+                    if dragging_from != Some(square) {
+                        self.draw_piece(ui, role, color, rect.center(), square_size);
                     }
                 }
 
@@ -189,27 +286,241 @@ impl<'a> ChessBoard<'a> {
                     );
                 }
 
-                // Handle click interaction
+                // Handle click and drag interaction
                 let square_id = Id::new(("chess_square", file_idx, rank_idx));
-                let square_response = ui.interact(rect, square_id, Sense::click());
+                let square_response = ui.interact(rect, square_id, Sense::click_and_drag());
+
+                let movable = self.game.piece_at(square).is_some_and(|(_, color)| color == self.movable_side);
+                if square_response.drag_started() && movable {
+                    // Picking a piece up selects it, so its legal moves are known on release; an
+                    // opponent's piece can't be picked up, the drop on its square decides the move
+                    dragging_from = Some(square);
+                    marked_destination = None;
+                    response.square_clicked = Some(square);
+                }
+
+                if square_response.drag_stopped() && dragging_from == Some(square) {
+                    dragging_from = None;
+                    let target = square_response
+                        .interact_pointer_pos()
+                        .or_else(|| ui.ctx().pointer_latest_pos())
+                        .and_then(|pos| self.square_at(board_rect, square_size, pos));
+                    if let Some(to) = target {
+                        response.move_made =
+                            self.pick_move(ui, legal_moves_for_selected, square, to, &mut pending_promotion);
+                    }
+                }
                 
                 if square_response.clicked() {
                     tracing::info!("Square CLICKED: {:?} (file_idx={}, rank_idx={})", square, file_idx, rank_idx);
-                    response.square_clicked = Some(square);
+                    let selected = *selected_square;
+                    let own_piece = selected.is_some_and(|from| {
+                        let color = |sq| self.game.piece_at(sq).map(|(_, color)| color);
+                        color(square).is_some() && color(square) == color(from)
+                    });
+                    let destination =
+                        legal_moves_for_selected.iter().find(|m| m.to() == square).and_then(|m| m.from());
 
-                    // Check if clicking on a legal destination
-                    if let Some(m) = legal_moves_for_selected
-                        .iter()
-                        .find(|m| m.to() == square)
-                    {
-                        tracing::info!("Move made: {:?}", m);
-                        response.move_made = Some(m.clone());
+                    if let Some(from) = destination {
+                        if self.clicks.confirm_destination && marked_destination != Some(square) {
+                            marked_destination = Some(square);
+                        } else {
+                            marked_destination = None;
+                            response.square_clicked = Some(square);
+                            response.move_made =
+                                self.pick_move(ui, legal_moves_for_selected, from, square, &mut pending_promotion);
+                            tracing::info!("Move made: {:?}", response.move_made);
+                        }
+                    } else {
+                        marked_destination = None;
+                        let deselect = if selected == Some(square) {
+                            self.clicks.deselect_selected
+                        } else {
+                            own_piece && self.clicks.own_piece == OwnPieceClick::Move
+                        };
+                        if deselect {
+                            response.deselected = true;
+                        } else {
+                            response.square_clicked = Some(square);
+                        }
                     }
                 }
             }
         }
+
+        for (i, &(from, to)) in self.arrows.iter().enumerate() {
+            self.draw_arrow(ui, board_rect, square_size, from, to, i);
+        }
+
+        // Draw the dragged piece on top of the board, under the pointer
+        if let Some(from) = dragging_from {
+            match (self.game.piece_at(from), ui.ctx().pointer_latest_pos()) {
+                (Some((role, color)), Some(pos)) => {
+                    self.draw_piece(ui, role, color, pos, square_size);
+                    ui.ctx().request_repaint();
+                }
+                _ => dragging_from = None,
+            }
+        }
+        });
+
+        if let Some((from, to)) = pending_promotion {
+            let board_rect = Rect::from_min_size(origin, vec2(board_size, board_size));
+            match self.show_promotion_picker(ui, board_rect, square_size, from, to) {
+                Some(Some(role)) => {
+                    response.move_made = legal_moves_for_selected
+                        .iter()
+                        .find(|m| m.from() == Some(from) && m.to() == to && m.promotion() == Some(role))
+                        .copied();
+                    pending_promotion = None;
+                }
+                Some(None) => pending_promotion = None,
+                None => {}
+            }
+        }
+
+        ui.data_mut(|d| match dragging_from {
+            Some(square) => d.insert_temp(drag_id, square),
+            None => d.remove::<Square>(drag_id),
+        });
+        ui.data_mut(|d| match marked_destination {
+            Some(square) => d.insert_temp(destination_id, square),
+            None => d.remove::<Square>(destination_id),
+        });
+        ui.data_mut(|d| match pending_promotion {
+            Some(squares) => d.insert_temp(promotion_id, squares),
+            None => d.remove::<(Square, Square)>(promotion_id),
         });
 
         response
     }
+
+    /// The legal move from `from` to `to`. A promotion takes the piece whose key is held,
+    /// or a queen when auto-queening, and otherwise waits for the picker.
+    fn pick_move(
+        &self,
+        ui: &Ui,
+        legal_moves: &[Move],
+        from: Square,
+        to: Square,
+        pending_promotion: &mut Option<(Square, Square)>,
+    ) -> Option<Move> {
+        let mut candidates = legal_moves.iter().filter(|m| m.from() == Some(from) && m.to() == to);
+        let first = candidates.next()?;
+        if first.promotion().is_none() {
+            return Some(*first);
+        }
+        let held = ui.input(|i| PROMOTION_KEYS.iter().find(|(key, _)| i.key_down(*key)).map(|(_, role)| *role));
+        let Some(role) = held.or(self.auto_queen.then_some(Role::Queen)) else {
+            *pending_promotion = Some((from, to));
+            return None;
+        };
+        std::iter::once(first).chain(candidates).find(|m| m.promotion() == Some(role)).copied()
+    }
+
+    /// Screen rectangle of a square, taking orientation into account
+    fn square_rect(&self, board_rect: Rect, square_size: f32, square: Square) -> Rect {
+        let (file_idx, rank_idx) = (u32::from(square.file()) as f32, u32::from(square.rank()) as f32);
+        let (display_file, display_rank) = if self.flipped {
+            (7.0 - file_idx, rank_idx)
+        } else {
+            (file_idx, 7.0 - rank_idx)
+        };
+        Rect::from_min_size(
+            board_rect.min + vec2(display_file * square_size, display_rank * square_size),
+            vec2(square_size, square_size),
+        )
+    }
+
+    /// Arrow for the `index`th move of a line, numbered at its middle
+    fn draw_arrow(&self, ui: &Ui, board_rect: Rect, square_size: f32, from: Square, to: Square, index: usize) {
+        let start = self.square_rect(board_rect, square_size, from).center();
+        let end = self.square_rect(board_rect, square_size, to).center();
+        if from == to {
+            return;
+        }
+        let direction = (end - start).normalized();
+        let color = ARROW_COLORS[index % 2];
+        let head_length = square_size * 0.35;
+        let tip = end - direction * square_size * 0.1;
+        let head_base = tip - direction * head_length;
+        let side = direction.rot90() * head_length * 0.6;
+        let painter = ui.painter();
+        painter.line_segment([start, head_base], Stroke::new(square_size * 0.14, color));
+        painter.add(egui::Shape::convex_polygon(vec![tip, head_base + side, head_base - side], color, Stroke::NONE));
+
+        let label = start + (head_base - start) * 0.5;
+        painter.circle_filled(label, square_size * 0.16, color);
+        painter.text(
+            label,
+            egui::Align2::CENTER_CENTER,
+            (index + 1).to_string(),
+            egui::FontId::proportional(square_size * 0.2),
+            Color32::WHITE,
+        );
+    }
+
+    /// Column of promotion pieces running from the promotion square toward the board's middle.
+    /// Returns Some(Some(role)) once a piece is picked, Some(None) when the picker is dismissed.
+    fn show_promotion_picker(
+        &mut self,
+        ui: &mut Ui,
+        board_rect: Rect,
+        square_size: f32,
+        from: Square,
+        to: Square,
+    ) -> Option<Option<Role>> {
+        // The pawn's own color, which differs from the side to move for a premove
+        let color = self.game.piece_at(from).map_or_else(|| self.game.turn().into(), |(_, color)| color);
+        let target = self.square_rect(board_rect, square_size, to);
+        let step = if target.center().y < board_rect.center().y { square_size } else { -square_size };
+
+        ui.painter().rect_filled(board_rect, 0.0, Color32::from_black_alpha(120));
+        let mut picked = None;
+        let mut over_choice = false;
+        for (i, role) in PROMOTION_CHOICES.into_iter().enumerate() {
+            let rect = target.translate(vec2(0.0, step * i as f32));
+            let choice = ui.interact(rect, Id::new(("promotion_choice", i)), Sense::click());
+            over_choice |= choice.hovered();
+            let fill = if choice.hovered() { self.theme.selected_square() } else { self.theme.light_square() };
+            ui.painter().circle_filled(rect.center(), square_size * 0.48, fill);
+            self.draw_piece(ui, role, color, rect.center(), square_size);
+            if choice.clicked() {
+                picked = Some(Some(role));
+            }
+        }
+        let dismissed = ui.input(|i| i.key_pressed(egui::Key::Escape) || (i.pointer.any_pressed() && !over_choice));
+        picked.or(dismissed.then_some(None))
+    }
+
+    fn draw_piece(&mut self, ui: &Ui, role: Role, color: Color, center: Pos2, square_size: f32) {
+        let piece_size = (square_size * 0.9) as u32;
+        if piece_size == 0 {
+            return;
+        }
+        if let Some(texture) = self.piece_renderer.get_texture(ui.ctx(), role, color, piece_size) {
+            let piece_rect = Rect::from_center_size(center, vec2(square_size * 0.9, square_size * 0.9));
+            ui.painter().image(
+                texture.id(),
+                piece_rect,
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        }
+    }
+
+    /// Board square under a screen position, taking orientation into account
+    fn square_at(&self, board_rect: Rect, square_size: f32, pos: Pos2) -> Option<Square> {
+        if !board_rect.contains(pos) || square_size <= 0.0 {
+            return None;
+        }
+        let display_file = (((pos.x - board_rect.min.x) / square_size) as u32).min(7);
+        let display_rank = (((pos.y - board_rect.min.y) / square_size) as u32).min(7);
+        let (file_idx, rank_idx) = if self.flipped {
+            (7 - display_file, display_rank)
+        } else {
+            (display_file, 7 - display_rank)
+        };
+        Some(Square::from_coords(File::new(file_idx), Rank::new(rank_idx)))
+    }
 }
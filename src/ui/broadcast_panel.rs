@@ -0,0 +1,70 @@
+use crate::broadcast::{Broadcast, BroadcastSettings, MIN_INTERVAL_SECS};
+use crate::i18n::{tr, tr_args};
+use egui::{Color32, Ui};
+
+/// What the user did in the broadcast panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastAction {
+    Follow,
+    Stop,
+    /// Another game of the event was picked
+    SelectGame(usize),
+}
+
+/// Settings and status for following a live PGN
+pub struct BroadcastPanel;
+
+impl BroadcastPanel {
+    pub fn show(ui: &mut Ui, settings: &mut BroadcastSettings, broadcast: Option<&Broadcast>) -> Option<BroadcastAction> {
+        let mut action = None;
+        let following = broadcast.is_some();
+
+        ui.add_enabled(
+            !following,
+            egui::TextEdit::singleline(&mut settings.url).hint_text(tr("broadcast.url_hint")),
+        );
+        ui.horizontal(|ui| {
+            ui.label(tr("broadcast.interval"));
+            ui.add(egui::DragValue::new(&mut settings.interval_secs).range(MIN_INTERVAL_SECS..=600).suffix(" s"));
+        });
+        ui.checkbox(&mut settings.live_analysis, tr("broadcast.live_analysis"));
+
+        match broadcast {
+            None => {
+                if ui
+                    .add_enabled(!settings.url.trim().is_empty(), egui::Button::new(tr("broadcast.follow")))
+                    .clicked()
+                {
+                    action = Some(BroadcastAction::Follow);
+                }
+            }
+            Some(broadcast) => {
+                if ui.button(tr("broadcast.stop")).clicked() {
+                    action = Some(BroadcastAction::Stop);
+                }
+                if broadcast.games.len() > 1 {
+                    let title = |i: usize| {
+                        broadcast.games[i].title().unwrap_or_else(|| tr_args("broadcast.game", &[&(i + 1)]))
+                    };
+                    egui::ComboBox::from_id_salt("broadcast_game")
+                        .selected_text(title(broadcast.selected))
+                        .show_ui(ui, |ui| {
+                            for i in 0..broadcast.games.len() {
+                                if ui.selectable_label(i == broadcast.selected, title(i)).clicked() {
+                                    action = Some(BroadcastAction::SelectGame(i));
+                                }
+                            }
+                        });
+                }
+                if let Some(error) = &broadcast.last_error {
+                    ui.colored_label(Color32::from_rgb(230, 80, 80), tr_args("broadcast.error", &[error]));
+                } else if let Some(updated) = broadcast.last_update {
+                    ui.weak(tr_args("broadcast.updated", &[&updated.elapsed().as_secs()]));
+                } else {
+                    ui.weak(tr("broadcast.waiting"));
+                }
+            }
+        }
+        action
+    }
+}
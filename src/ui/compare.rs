@@ -0,0 +1,120 @@
+use crate::i18n::{tr, tr_args};
+use crate::ui::{BoardThumbnail, PieceRenderer, Theme};
+use egui::{vec2, Color32, Context, Rect, Stroke, StrokeKind};
+use shakmaty::{fen::Fen, Board, Role, Square};
+
+/// Edge length of each board in the comparison window
+const COMPARE_BOARD_SIZE: f32 = 240.0;
+
+/// Outline of the squares that differ between the two boards
+const DIFFERENCE_COLOR: Color32 = Color32::from_rgb(230, 80, 60);
+
+/// Two positions side by side, the squares that differ outlined: the board's position against
+/// the end of the engine's best line, or against a position pinned earlier
+#[derive(Default)]
+pub struct CompareWindow {
+    pub open: bool,
+    /// Only pawns count as differences, to compare pawn structures
+    pub pawns_only: bool,
+    /// Position the board is compared against instead of the engine's line
+    pub pinned: Option<String>,
+}
+
+impl CompareWindow {
+    /// `fen` is the board's position and `engine_fen` the one at the end of the engine's best line
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        fen: &str,
+        engine_fen: Option<&str>,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+    ) {
+        let mut open = self.open;
+        egui::Window::new(tr("compare.title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("compare.pin")).on_hover_text(tr("compare.pin_hint")).clicked() {
+                        self.pinned = Some(fen.to_string());
+                    }
+                    if self.pinned.is_some() && ui.button(tr("compare.unpin")).clicked() {
+                        self.pinned = None;
+                    }
+                    ui.checkbox(&mut self.pawns_only, tr("compare.pawns_only"))
+                        .on_hover_text(tr("compare.pawns_only_hint"));
+                });
+                ui.separator();
+
+                let (target, target_label) = match (&self.pinned, engine_fen) {
+                    (Some(pinned), _) => (Some(pinned.as_str()), tr("compare.pinned")),
+                    (None, Some(engine_fen)) => (Some(engine_fen), tr("compare.engine_line")),
+                    (None, None) => (None, tr("compare.engine_line")),
+                };
+                let differences = target.map(|target| differing_squares(fen, target, self.pawns_only)).unwrap_or_default();
+
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label(tr("compare.current"));
+                        show_board(ui, fen, &differences, theme, piece_renderer);
+                    });
+                    ui.vertical(|ui| {
+                        ui.label(target_label);
+                        match target {
+                            Some(target) => show_board(ui, target, &differences, theme, piece_renderer),
+                            None => {
+                                ui.allocate_ui(vec2(COMPARE_BOARD_SIZE, COMPARE_BOARD_SIZE), |ui| {
+                                    ui.weak(tr("compare.no_target"));
+                                });
+                            }
+                        }
+                    });
+                });
+                if target.is_some() {
+                    ui.label(tr_args("compare.differences", &[&differences.len()]));
+                }
+            });
+        self.open = open;
+    }
+}
+
+fn show_board(ui: &mut egui::Ui, fen: &str, differences: &[Square], theme: Theme, piece_renderer: &mut PieceRenderer) {
+    let rect = BoardThumbnail::show(ui, fen, theme, piece_renderer, COMPARE_BOARD_SIZE).rect;
+    let square_size = COMPARE_BOARD_SIZE / 8.0;
+    for square in differences {
+        // White at the bottom, as the thumbnail draws it
+        let min = rect.min + vec2(
+            u32::from(square.file()) as f32 * square_size,
+            (7 - u32::from(square.rank())) as f32 * square_size,
+        );
+        let square_rect = Rect::from_min_size(min, vec2(square_size, square_size)).shrink(1.0);
+        ui.painter().rect_stroke(square_rect, 0.0, Stroke::new(2.0, DIFFERENCE_COLOR), StrokeKind::Inside);
+    }
+}
+
+/// Squares holding different pieces in the two FEN positions, or with `pawns_only` different
+/// pawns, as pawn structures are compared
+pub fn differing_squares(a: &str, b: &str, pawns_only: bool) -> Vec<Square> {
+    let board = |fen: &str| fen.parse::<Fen>().map(|f| f.into_setup().board).unwrap_or_else(|_| Board::empty());
+    let (a, b) = (board(a), board(b));
+    let piece = |board: &Board, square| board.piece_at(square).filter(|p| !pawns_only || p.role == Role::Pawn);
+    Square::ALL.into_iter().filter(|&square| piece(&a, square) != piece(&b, square)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differing_squares() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let after = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+        assert!(differing_squares(start, start, false).is_empty());
+        assert_eq!(
+            differing_squares(start, after, false),
+            [Square::G1, Square::E2, Square::F3, Square::E4, Square::E5, Square::E7]
+        );
+        assert_eq!(differing_squares(start, after, true), [Square::E2, Square::E4, Square::E5, Square::E7]);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::engine::DifficultyLevel;
-use crate::game::{GameOutcome, PlayerColor};
+use crate::engine::{DifficultyLevel, Personality};
+use crate::game::{GameOutcome, PlayerColor, TakebackPolicy, TimeControl};
+use crate::i18n::{tr, tr_args};
 use crate::ui::Theme;
 use egui::Ui;
 
@@ -10,8 +11,10 @@ pub enum ControlAction {
     NewGame,
     FlipBoard,
     SetDifficulty(DifficultyLevel),
+    SetPersonality(Personality),
     SetTheme(Theme),
     SetPlayerColor(PlayerColor),
+    SetTimeControl(Option<TimeControl>),
     Resign,
     OfferDraw,
     Undo,
@@ -23,8 +26,10 @@ impl ControlPanel {
         difficulty: &mut DifficultyLevel,
         theme: &mut Theme,
         player_color: &mut PlayerColor,
+        time_control: &mut Option<TimeControl>,
         outcome: GameOutcome,
-        is_engine_thinking: bool,
+        // Search progress while the engine is thinking (empty until the first depth arrives)
+        engine_thinking: Option<&str>,
     ) -> Option<ControlAction> {
         let mut action = None;
 
@@ -33,55 +38,32 @@ impl ControlPanel {
             ui.separator();
 
             // Game status
-            match outcome {
-                GameOutcome::InProgress => {
-                    if is_engine_thinking {
-                        ui.horizontal(|ui| {
-                            ui.spinner();
-                            ui.label("Engine thinking...");
-                        });
-                    }
-                }
-                GameOutcome::Checkmate(winner) => {
-                    let text = match winner {
-                        PlayerColor::White => "White wins by checkmate!",
-                        PlayerColor::Black => "Black wins by checkmate!",
-                    };
-                    ui.colored_label(egui::Color32::GREEN, text);
-                }
-                GameOutcome::Stalemate => {
-                    ui.colored_label(egui::Color32::YELLOW, "Draw by stalemate");
-                }
-                GameOutcome::InsufficientMaterial => {
-                    ui.colored_label(egui::Color32::YELLOW, "Draw by insufficient material");
-                }
-                GameOutcome::ThreefoldRepetition => {
-                    ui.colored_label(egui::Color32::YELLOW, "Draw by threefold repetition");
-                }
-                GameOutcome::FiftyMoveRule => {
-                    ui.colored_label(egui::Color32::YELLOW, "Draw by fifty-move rule");
-                }
-                GameOutcome::Resignation(winner) => {
-                    let text = match winner {
-                        PlayerColor::White => "White wins by resignation!",
-                        PlayerColor::Black => "Black wins by resignation!",
-                    };
-                    ui.colored_label(egui::Color32::GREEN, text);
-                }
-                GameOutcome::DrawByAgreement => {
-                    ui.colored_label(egui::Color32::YELLOW, "Draw by agreement");
+            if let Some(message) = outcome.message() {
+                let color = if outcome.winner().is_some() {
+                    egui::Color32::GREEN
+                } else {
+                    egui::Color32::YELLOW
+                };
+                ui.colored_label(color, message);
+            } else if let Some(progress) = engine_thinking {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("controls.engine_thinking"));
+                });
+                if !progress.is_empty() {
+                    ui.weak(progress);
                 }
             }
 
             ui.add_space(10.0);
 
             // New Game button
-            if ui.button("New Game").clicked() {
+            if ui.button(tr("controls.new_game")).clicked() {
                 action = Some(ControlAction::NewGame);
             }
 
             // Flip Board button
-            if ui.button("Flip Board").clicked() {
+            if ui.button(tr("controls.flip_board")).clicked() {
                 action = Some(ControlAction::FlipBoard);
             }
 
@@ -89,13 +71,13 @@ impl ControlPanel {
             ui.separator();
 
             // Play as
-            ui.label("Play as:");
+            ui.label(tr("controls.play_as"));
             ui.horizontal(|ui| {
-                if ui.selectable_label(*player_color == PlayerColor::White, "White").clicked() {
+                if ui.selectable_label(*player_color == PlayerColor::White, tr("controls.white")).clicked() {
                     *player_color = PlayerColor::White;
                     action = Some(ControlAction::SetPlayerColor(PlayerColor::White));
                 }
-                if ui.selectable_label(*player_color == PlayerColor::Black, "Black").clicked() {
+                if ui.selectable_label(*player_color == PlayerColor::Black, tr("controls.black")).clicked() {
                     *player_color = PlayerColor::Black;
                     action = Some(ControlAction::SetPlayerColor(PlayerColor::Black));
                 }
@@ -103,8 +85,26 @@ impl ControlPanel {
 
             ui.add_space(10.0);
 
+            // Time control, starts a new game when changed
+            ui.label(tr("controls.time_control"));
+            let selected = time_control.map_or_else(|| tr("controls.untimed").to_string(), |tc| tc.label());
+            egui::ComboBox::from_id_salt("time_control")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_value(time_control, None, tr("controls.untimed")).clicked() {
+                        action = Some(ControlAction::SetTimeControl(None));
+                    }
+                    for tc in TimeControl::presets() {
+                        if ui.selectable_value(time_control, Some(*tc), tc.label()).clicked() {
+                            action = Some(ControlAction::SetTimeControl(Some(*tc)));
+                        }
+                    }
+                });
+
+            ui.add_space(10.0);
+
             // Difficulty selection
-            ui.label("Difficulty:");
+            ui.label(tr("controls.difficulty"));
             egui::ComboBox::from_id_salt("difficulty")
                 .selected_text(difficulty.label())
                 .show_ui(ui, |ui| {
@@ -118,7 +118,7 @@ impl ControlPanel {
             ui.add_space(10.0);
 
             // Theme selection
-            ui.label("Theme:");
+            ui.label(tr("controls.theme"));
             egui::ComboBox::from_id_salt("theme")
                 .selected_text(theme.label())
                 .show_ui(ui, |ui| {
@@ -135,20 +135,88 @@ impl ControlPanel {
                 ui.separator();
                 
                 ui.horizontal(|ui| {
-                    if ui.button("🏳 Resign").clicked() {
+                    if ui.button(tr("controls.resign")).clicked() {
                         action = Some(ControlAction::Resign);
                     }
-                    if ui.button("🤝 Offer Draw").clicked() {
+                    if ui.button(tr("controls.offer_draw")).clicked() {
                         action = Some(ControlAction::OfferDraw);
                     }
                 });
-                
-                if ui.button("↩ Undo Move").clicked() {
-                    action = Some(ControlAction::Undo);
-                }
             }
         });
 
         action
     }
+
+    /// Engine personality picker, with a short description of the chosen style
+    pub fn show_personality(ui: &mut Ui, personality: &mut Personality) -> Option<ControlAction> {
+        let mut action = None;
+
+        ui.add_space(10.0);
+        ui.label(tr("controls.personality"));
+        egui::ComboBox::from_id_salt("personality")
+            .selected_text(personality.to_string())
+            .show_ui(ui, |ui| {
+                for p in Personality::all() {
+                    if ui
+                        .selectable_value(personality, *p, p.to_string())
+                        .on_hover_text(p.description())
+                        .clicked()
+                    {
+                        action = Some(ControlAction::SetPersonality(*p));
+                    }
+                }
+            });
+        ui.weak(personality.description());
+
+        action
+    }
+
+    /// Undo button with the takebacks left in this game, and the takeback policy.
+    /// `can_undo` is whether the player has a move on the board to take back;
+    /// `started` is whether any move has been played, which locks the policy
+    /// until the game ends.
+    pub fn show_takebacks(
+        ui: &mut Ui,
+        policy: &mut TakebackPolicy,
+        used: u32,
+        outcome: GameOutcome,
+        can_undo: bool,
+        started: bool,
+    ) -> Option<ControlAction> {
+        let mut action = None;
+
+        if outcome == GameOutcome::InProgress {
+            let remaining = policy.remaining(used);
+            let label = match remaining {
+                Some(left) => tr_args("controls.undo_left", &[&left]),
+                None => tr("controls.undo").to_string(),
+            };
+            if ui
+                .add_enabled(can_undo && policy.allows(used), egui::Button::new(label))
+                .clicked()
+            {
+                action = Some(ControlAction::Undo);
+            }
+        }
+
+        ui.add_space(10.0);
+
+        // Chosen before the first move and kept until the game ends
+        let locked = started && outcome == GameOutcome::InProgress;
+        ui.label(tr("controls.takebacks"));
+        ui.add_enabled_ui(!locked, |ui| {
+            egui::ComboBox::from_id_salt("takebacks")
+                .selected_text(policy.label())
+                .show_ui(ui, |ui| {
+                    for preset in TakebackPolicy::presets() {
+                        ui.selectable_value(policy, *preset, preset.label());
+                    }
+                });
+        })
+        .response
+        .on_disabled_hover_text(tr("controls.takebacks_locked"));
+
+        action
+    }
 }
@@ -0,0 +1,61 @@
+use crate::explorer::{CoverageGap, CoverageReport};
+use crate::i18n::tr;
+use egui::{Context, Ui};
+
+/// Where the user's games left their repertoire, and which lines to learn next
+pub struct CoverageWindow;
+
+impl CoverageWindow {
+    /// Returns the SAN moves of a clicked line
+    pub fn show(ctx: &Context, open: &mut bool, report: &CoverageReport) -> Option<Vec<String>> {
+        let mut clicked = None;
+        egui::Window::new(tr("coverage.title"))
+            .open(open)
+            .default_size([480.0, 420.0])
+            .show(ctx, |ui| {
+                if ui.button(tr("engine_log.copy")).clicked() {
+                    ui.ctx().copy_text(report.to_text());
+                }
+                ui.separator();
+                if report.is_empty() {
+                    ui.weak(tr("coverage.empty"));
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let sections = [
+                        ("coverage.suggestions", report.suggestions()),
+                        ("coverage.misplayed", report.misplayed.iter().collect()),
+                        ("coverage.uncovered", report.uncovered.iter().collect()),
+                    ];
+                    for (heading, gaps) in sections {
+                        egui::CollapsingHeader::new(tr(heading))
+                            .default_open(heading == "coverage.suggestions")
+                            .show(ui, |ui| {
+                                if let Some(line) = Self::show_gaps(ui, heading, &gaps) {
+                                    clicked = Some(line);
+                                }
+                            });
+                    }
+                });
+            });
+        clicked
+    }
+
+    fn show_gaps(ui: &mut Ui, id: &str, gaps: &[&CoverageGap]) -> Option<Vec<String>> {
+        let mut clicked = None;
+        if gaps.is_empty() {
+            ui.weak(tr("coverage.none"));
+            return None;
+        }
+        egui::Grid::new(id).num_columns(2).striped(true).show(ui, |ui| {
+            for gap in gaps {
+                if ui.link(gap.line_text()).on_hover_text(tr("coverage.open_line")).clicked() {
+                    clicked = Some(gap.line.clone());
+                }
+                ui.weak(gap.detail());
+                ui.end_row();
+            }
+        });
+        clicked
+    }
+}
@@ -0,0 +1,58 @@
+use crate::game::{CriticalKind, CriticalMoment, GameState, Nag, Notation, PlayerColor};
+use crate::i18n::{format_decimal, format_move_number, tr};
+use egui::{Color32, Ui};
+
+const MISSED_WIN_COLOR: Color32 = Color32::from_rgb(255, 170, 0);
+const SWING_COLOR: Color32 = Color32::from_rgb(230, 80, 80);
+
+/// The turning points of a reviewed game, each a link to the position before the move
+pub struct CriticalMomentsPanel<'a> {
+    pub moments: &'a [CriticalMoment],
+    pub game: &'a GameState,
+    pub notation: Notation,
+}
+
+impl CriticalMomentsPanel<'_> {
+    /// Lists the moments and returns the index of the position to jump to when one is clicked
+    pub fn show(&self, ui: &mut Ui) -> Option<usize> {
+        let first_mover = self.game.starting_turn();
+        let first_number = self
+            .game
+            .starting_fen()
+            .split_whitespace()
+            .nth(5)
+            .and_then(|n| n.parse::<usize>().ok())
+            .unwrap_or(1);
+        let offset = usize::from(first_mover == PlayerColor::Black);
+
+        let mut clicked = None;
+        ui.label(tr("critical.heading"));
+        for moment in self.moments {
+            let Some(record) = self.game.move_history().get(moment.ply) else {
+                continue;
+            };
+            let number = format_move_number(first_number + (moment.ply + offset) / 2);
+            let number = if (moment.ply + offset) % 2 == 0 { number } else { format!("{}..", number) };
+            let glyph = Nag::from_loss(moment.loss).map_or("", Nag::glyph);
+            let (kind, color) = match moment.kind {
+                CriticalKind::MissedWin => (tr("critical.missed_win"), MISSED_WIN_COLOR),
+                CriticalKind::Swing => (tr("critical.swing"), SWING_COLOR),
+            };
+
+            ui.horizontal(|ui| {
+                let text = format!("{} {}{}", number, self.notation.format(record), glyph);
+                let selected = self.game.current_index() == moment.ply;
+                if ui
+                    .selectable_label(selected, text)
+                    .on_hover_text(tr("critical.jump_hint"))
+                    .clicked()
+                {
+                    clicked = Some(moment.ply);
+                }
+                ui.colored_label(color, kind);
+                ui.weak(format!("-{}", format_decimal(moment.loss as f32 / 100.0, 2)));
+            });
+        }
+        clicked
+    }
+}
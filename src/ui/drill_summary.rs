@@ -0,0 +1,35 @@
+use crate::i18n::{tr, tr_args};
+use crate::study::DrillSession;
+use egui::Context;
+
+/// Score of a repertoire drill session, shown when it ends
+pub struct DrillSummaryWindow;
+
+impl DrillSummaryWindow {
+    pub fn show(ctx: &Context, open: &mut bool, session: &DrillSession) {
+        egui::Window::new(tr("drill.summary_title"))
+            .open(open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(tr_args("drill.summary_score", &[&session.score()]));
+                ui.separator();
+                egui::Grid::new("drill_summary").num_columns(2).show(ui, |ui| {
+                    let rows = [
+                        ("drill.summary_passed", session.lines_passed),
+                        ("drill.summary_failed", session.lines_failed),
+                        ("drill.summary_first_try", session.first_try),
+                        ("drill.summary_positions", session.positions),
+                        ("drill.summary_mistakes", session.mistakes),
+                        ("drill.summary_timeouts", session.timeouts),
+                        ("drill.summary_hints", session.hints),
+                    ];
+                    for (label, value) in rows {
+                        ui.label(tr(label));
+                        ui.label(value.to_string());
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
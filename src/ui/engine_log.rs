@@ -0,0 +1,82 @@
+use crate::i18n::tr;
+use chrono::{DateTime, Local};
+use egui::{Color32, Context, RichText};
+use std::collections::VecDeque;
+
+/// Entries kept before the oldest are dropped
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    /// Ordinary stderr output
+    Output,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: LogLevel,
+    pub text: String,
+}
+
+/// Recent engine errors and stderr output, to diagnose engines that fail to start or crash
+#[derive(Debug, Default)]
+pub struct EngineLog {
+    entries: VecDeque<LogEntry>,
+}
+
+impl EngineLog {
+    pub fn push(&mut self, level: LogLevel, text: impl Into<String>) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            time: Local::now(),
+            level,
+            text: text.into(),
+        });
+    }
+
+    fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|e| format!("{} {}\n", e.time.format("%H:%M:%S"), e.text))
+            .collect()
+    }
+}
+
+/// Window listing the engine log, with buttons to copy or clear it
+pub struct EngineLogWindow;
+
+impl EngineLogWindow {
+    pub fn show(ctx: &Context, open: &mut bool, log: &mut EngineLog) {
+        egui::Window::new(tr("engine_log.title"))
+            .open(open)
+            .default_size([520.0, 320.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("engine_log.copy")).clicked() {
+                        ui.ctx().copy_text(log.to_text());
+                    }
+                    if ui.button(tr("engine_log.clear")).clicked() {
+                        log.entries.clear();
+                    }
+                });
+                ui.separator();
+                if log.entries.is_empty() {
+                    ui.weak(tr("engine_log.empty"));
+                    return;
+                }
+                egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                    for entry in &log.entries {
+                        let text = RichText::new(format!("{} {}", entry.time.format("%H:%M:%S"), entry.text)).monospace();
+                        match entry.level {
+                            LogLevel::Output => ui.label(text),
+                            LogLevel::Error => ui.label(text.color(Color32::from_rgb(230, 80, 80))),
+                        };
+                    }
+                });
+            });
+    }
+}
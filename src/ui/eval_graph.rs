@@ -0,0 +1,87 @@
+use crate::game::MoveRecord;
+use crate::i18n::{tr, tr_args};
+use crate::ui::TimeFlag;
+use egui::{pos2, vec2, Color32, Sense, Stroke, Ui};
+
+const GRAPH_HEIGHT: f32 = 80.0;
+/// Evaluations beyond this many centipawns are drawn at the edge
+const CLAMP_CP: i32 = 1000;
+const LINE_COLOR: Color32 = Color32::from_rgb(58, 123, 213);
+pub const BOOK_EXIT_COLOR: Color32 = Color32::from_rgb(90, 150, 220);
+
+/// Evaluation over the game as a line, White's advantage upwards, with the moves whose
+/// thinking time stands out and the first move out of opening theory marked on it
+pub struct EvalGraph<'a> {
+    pub moves: &'a [MoveRecord],
+    /// White-relative centipawn evaluation of each position by index, where known
+    pub evals: &'a [Option<i32>],
+    /// Timing of each move, empty without clock data
+    pub flags: &'a [Option<TimeFlag>],
+    /// Index of the position on the board
+    pub current_index: usize,
+    /// Ply of the first move out of opening theory
+    pub book_exit: Option<usize>,
+}
+
+impl EvalGraph<'_> {
+    /// Draws the graph and returns the index of a clicked position
+    pub fn show(&self, ui: &mut Ui) -> Option<usize> {
+        let positions = self.moves.len() + 1;
+        if !self.evals.iter().take(positions).any(Option::is_some) {
+            return None;
+        }
+        let width = ui.available_width();
+        let (rect, response) = ui.allocate_exact_size(vec2(width, GRAPH_HEIGHT), Sense::click());
+        let painter = ui.painter_at(rect);
+        let (top, bottom) = rect.split_top_bottom_at_y(rect.center().y);
+        painter.rect_filled(top, 0.0, Color32::from_gray(220));
+        painter.rect_filled(bottom, 0.0, Color32::from_gray(60));
+
+        let step = width / (positions.max(2) - 1) as f32;
+        let x = |index: usize| rect.left() + index as f32 * step;
+        let y = |cp: i32| rect.center().y - cp.clamp(-CLAMP_CP, CLAMP_CP) as f32 / CLAMP_CP as f32 * (GRAPH_HEIGHT / 2.0 - 2.0);
+        let points: Vec<_> = self
+            .evals
+            .iter()
+            .enumerate()
+            .take(positions)
+            .filter_map(|(index, eval)| eval.map(|cp| pos2(x(index), y(cp))))
+            .collect();
+        painter.line(points, Stroke::new(2.0, LINE_COLOR));
+
+        // Each move is marked at the position it led to
+        if let Some(ply) = self.book_exit.filter(|ply| *ply < self.moves.len()) {
+            let exit = x(ply + 1);
+            painter.line_segment([pos2(exit, rect.top()), pos2(exit, rect.bottom())], Stroke::new(1.5, BOOK_EXIT_COLOR));
+        }
+        for (ply, flag) in self.flags.iter().enumerate() {
+            if let (Some(flag), Some(cp)) = (flag, self.evals.get(ply + 1).copied().flatten()) {
+                painter.circle_filled(pos2(x(ply + 1), y(cp)), 3.5, flag.color());
+            }
+        }
+        if self.current_index < positions {
+            let stroke = Stroke::new(1.0, ui.visuals().selection.stroke.color);
+            let current = x(self.current_index);
+            painter.line_segment([pos2(current, rect.top()), pos2(current, rect.bottom())], stroke);
+        }
+
+        let pointer = response.hover_pos()?;
+        let index = (((pointer.x - rect.left()) / step).round().max(0.0) as usize).min(positions - 1);
+        let response = response.on_hover_ui_at_pointer(|ui| {
+            if let Some(m) = index.checked_sub(1).and_then(|ply| self.moves.get(ply)) {
+                let eval = match self.evals.get(index).copied().flatten() {
+                    Some(cp) => format!("{:+.2}", cp as f32 / 100.0),
+                    None => "--".to_string(),
+                };
+                ui.label(tr_args("eval_graph.tooltip", &[&((index - 1) / 2 + 1), &m.san, &eval]));
+            }
+            if let Some(flag) = index.checked_sub(1).and_then(|ply| self.flags.get(ply).copied().flatten()) {
+                ui.colored_label(flag.color(), flag.label());
+            }
+            if self.book_exit.is_some_and(|ply| ply + 1 == index) {
+                ui.colored_label(BOOK_EXIT_COLOR, tr("moves.book_exit"));
+            }
+        });
+        response.clicked().then_some(index)
+    }
+}
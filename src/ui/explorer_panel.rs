@@ -0,0 +1,210 @@
+use crate::explorer::{ExplorerClient, ExplorerDatabase, ExplorerResult, OpeningTree, TreeMove};
+use crate::game::position_key;
+use crate::i18n::{tr, tr_args};
+use crate::study::Study;
+use egui::{vec2, Color32, Rect, Sense, Ui};
+
+const BAR_WIDTH: f32 = 90.0;
+const BAR_HEIGHT: f32 = 12.0;
+
+const DEVIATION_COLOR: Color32 = Color32::from_rgb(255, 170, 0);
+
+/// Candidate moves for the position on the board from the Lichess opening explorer or the
+/// user's own imported games
+pub struct ExplorerPanel {
+    database: ExplorerDatabase,
+    /// Show the user's own games instead of a Lichess database
+    personal: bool,
+    /// The repertoire coverage report was asked for
+    pub open_coverage: bool,
+    prepared: PreparedMoves,
+}
+
+/// Moves the study gives from the last position shown, looked up again only once the position
+/// or the study changes
+#[derive(Default)]
+struct PreparedMoves {
+    study_id: String,
+    updated_at: String,
+    key: String,
+    moves: Vec<String>,
+}
+
+impl PreparedMoves {
+    fn get(&mut self, study: &Study, fen: &str) -> &[String] {
+        let key = position_key(fen);
+        if self.key != key || self.study_id != study.id || self.updated_at != study.updated_at {
+            self.moves = study.moves_by_position().remove(&key).unwrap_or_default();
+            self.key = key;
+            self.study_id = study.id.clone();
+            self.updated_at = study.updated_at.clone();
+        }
+        &self.moves
+    }
+}
+
+impl Default for ExplorerPanel {
+    fn default() -> Self {
+        Self {
+            database: ExplorerDatabase::Masters,
+            personal: false,
+            open_coverage: false,
+            prepared: PreparedMoves::default(),
+        }
+    }
+}
+
+impl ExplorerPanel {
+    /// Returns the UCI of a clicked move. The study is the repertoire personal moves are checked against.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        client: &mut ExplorerClient,
+        tree: &OpeningTree,
+        study: &Study,
+        fen: &str,
+    ) -> Option<String> {
+        let mut clicked = None;
+
+        ui.horizontal(|ui| {
+            for database in ExplorerDatabase::all() {
+                if ui.selectable_label(!self.personal && self.database == *database, database.label()).clicked() {
+                    self.database = *database;
+                    self.personal = false;
+                }
+            }
+            ui.selectable_value(&mut self.personal, true, tr("explorer.personal"));
+        });
+
+        if self.personal {
+            let prepared = self.prepared.get(study, fen);
+            return Self::show_personal(ui, tree, prepared, fen, &mut self.open_coverage);
+        }
+
+        match client.lookup(self.database, fen) {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("explorer.loading"));
+                });
+                // Poll until the background query answers
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::from_rgb(230, 80, 80), e.as_str());
+                if ui.button(tr("explorer.retry")).clicked() {
+                    client.retry(self.database, fen);
+                }
+            }
+            Some(Ok(result)) if result.moves.is_empty() => {
+                ui.weak(tr("explorer.no_games"));
+            }
+            Some(Ok(result)) => {
+                clicked = Self::show_moves(ui, result);
+            }
+        }
+
+        clicked
+    }
+
+    /// `prepared` are the moves the study gives from the position
+    fn show_personal(ui: &mut Ui, tree: &OpeningTree, prepared: &[String], fen: &str, open_coverage: &mut bool) -> Option<String> {
+        if tree.games == 0 {
+            ui.weak(tr("explorer.personal_empty"));
+            return None;
+        }
+        let moves = tree.moves(fen);
+        if moves.is_empty() {
+            ui.weak(tr("explorer.no_games"));
+        }
+
+        let prepared = Some(prepared).filter(|m| !m.is_empty());
+        let mut clicked = None;
+        egui::Grid::new("personal_moves")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for m in moves {
+                    if ui.link(&m.san).clicked() {
+                        clicked = Some(m.uci.clone());
+                    }
+                    ui.label(m.games.to_string());
+                    let segments = [
+                        (u64::from(m.wins), Color32::from_rgb(80, 200, 120)),
+                        (u64::from(m.draws), Color32::from_gray(150)),
+                        (u64::from(m.losses), Color32::from_rgb(230, 80, 80)),
+                    ];
+                    score_bar(ui, segments).on_hover_text(Self::personal_score(m));
+                    match prepared {
+                        Some(prepared) if m.mine && !prepared.contains(&m.san) => {
+                            ui.colored_label(DEVIATION_COLOR, "⚠")
+                                .on_hover_text(tr_args("explorer.deviation", &[&prepared.join(", ")]));
+                        }
+                        _ => {
+                            ui.label("");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.weak(tr_args("explorer.personal_total", &[&tree.games, &tree.skipped]));
+        if ui.button(tr("coverage.open")).on_hover_text(tr("coverage.open_hint")).clicked() {
+            *open_coverage = true;
+        }
+        clicked
+    }
+
+    fn personal_score(m: &TreeMove) -> String {
+        let text = tr_args("explorer.personal_score", &[&m.wins, &m.draws, &m.losses, &m.score()]);
+        if m.mine {
+            text
+        } else {
+            format!("{}\n{}", text, tr("explorer.faced"))
+        }
+    }
+
+    fn show_moves(ui: &mut Ui, result: &ExplorerResult) -> Option<String> {
+        let mut clicked = None;
+        egui::Grid::new("explorer_moves")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for m in &result.moves {
+                    if ui.link(&m.san).clicked() {
+                        clicked = Some(m.uci.clone());
+                    }
+                    ui.label(m.games().to_string());
+                    let bar = score_bar(
+                        ui,
+                        [
+                            (m.white, Color32::from_gray(235)),
+                            (m.draws, Color32::from_gray(150)),
+                            (m.black, Color32::from_gray(40)),
+                        ],
+                    );
+                    let percent = |n: u64| n * 100 / m.games().max(1);
+                    bar.on_hover_text(tr_args(
+                        "explorer.score",
+                        &[&percent(m.white), &percent(m.draws), &percent(m.black)],
+                    ));
+                    ui.end_row();
+                }
+            });
+        ui.weak(tr_args("explorer.total", &[&(result.white + result.draws + result.black)]));
+        clicked
+    }
+}
+
+/// Share of the games of each result as a stacked bar
+fn score_bar(ui: &mut Ui, segments: [(u64, Color32); 3]) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(vec2(BAR_WIDTH, BAR_HEIGHT), Sense::hover());
+    let total = segments.iter().map(|(count, _)| count).sum::<u64>().max(1) as f32;
+    let mut left = rect.left();
+    for (count, color) in segments {
+        let width = rect.width() * count as f32 / total;
+        let segment = Rect::from_min_size(egui::pos2(left, rect.top()), vec2(width, rect.height()));
+        ui.painter().rect_filled(segment, 0.0, color);
+        left += width;
+    }
+    response
+}
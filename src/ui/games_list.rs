@@ -0,0 +1,82 @@
+use crate::game::{PgnGame, PlayerColor, QualitySummary};
+use crate::i18n::{tr, tr_args};
+use crate::ui::QualityStrip;
+use egui::Ui;
+use std::collections::HashMap;
+
+/// Most games listed at once; the filter narrows down larger databases
+const MAX_LISTED: usize = 200;
+
+/// The games database, newest first, with move quality strips for the games that were
+/// exported with evaluations
+#[derive(Default)]
+pub struct GamesList {
+    filter: String,
+    /// White's and Black's move quality by game fingerprint, worked out once per game
+    summaries: HashMap<u64, [Option<QualitySummary>; 2]>,
+}
+
+impl GamesList {
+    /// Shows the games matching the filter; returns the index of a game to open
+    pub fn show(&mut self, ui: &mut Ui, games: &[PgnGame]) -> Option<usize> {
+        if games.is_empty() {
+            ui.weak(tr("games.empty"));
+            return None;
+        }
+        let mut open = None;
+        ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text(tr("export.filter_hint")));
+        let matching: Vec<usize> = (0..games.len()).rev().filter(|&i| games[i].matches(&self.filter)).collect();
+        egui::ScrollArea::vertical().id_salt("games_list").max_height(300.0).show(ui, |ui| {
+            for &index in matching.iter().take(MAX_LISTED) {
+                let game = &games[index];
+                let title = game.title().unwrap_or_else(|| tr("games.untitled").to_string());
+                let text = format!("{}  {}", title, game.header("Result").unwrap_or("*"));
+                if ui.link(text).on_hover_text(tr("games.open_hint")).clicked() {
+                    open = Some(index);
+                }
+                let summaries = self.summaries.entry(game.fingerprint()).or_insert_with(|| quality(game));
+                for (side, summary) in [("White", summaries[0]), ("Black", summaries[1])] {
+                    if let Some(summary) = summary {
+                        let name = game.header(side).unwrap_or(side);
+                        QualityStrip { name, summary: &summary }.show(ui);
+                    }
+                }
+            }
+        });
+        if matching.len() > MAX_LISTED {
+            ui.weak(tr_args("games.more", &[&(matching.len() - MAX_LISTED)]));
+        }
+        open
+    }
+}
+
+/// Move quality of White and Black from the evaluations the game was exported with
+fn quality(game: &PgnGame) -> [Option<QualitySummary>; 2] {
+    let Some(state) = (!game.evals.is_empty()).then(|| game.to_game().ok()).flatten() else {
+        return [None, None];
+    };
+    let mut evals = game.evals.clone();
+    evals.resize(game.moves.len() + 1, None);
+    // Analysed games carry no evaluation of the standard start, which is level
+    if game.header("FEN").is_none() {
+        evals[0].get_or_insert(0);
+    }
+    [PlayerColor::White, PlayerColor::Black].map(|side| QualitySummary::of_game(&state, &evals, side))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_pgn;
+
+    #[test]
+    fn test_quality_from_evals() {
+        let analysed = "[White \"A\"]\n[Black \"B\"]\n\n1. e4 { [%eval 0.3] } 1... f6 { [%eval 0.6] } 2. d4 { [%eval 0.5] } 2... g5 { [%eval #1] } 0-1\n";
+        let [white, black] = quality(&parse_pgn(analysed)[0]);
+        assert_eq!(white.unwrap().blunders, 0);
+        assert_eq!(black.unwrap().blunders, 1);
+
+        let plain = "1. e4 e5 *\n";
+        assert_eq!(quality(&parse_pgn(plain)[0]), [None, None]);
+    }
+}
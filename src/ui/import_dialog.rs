@@ -0,0 +1,53 @@
+use crate::i18n::{tr, tr_args};
+use egui::Context;
+
+/// Where an imported PGN goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    /// Open the first game on the board in Analysis mode
+    Game,
+    /// Add every game to the current study as chapters
+    Study,
+    /// Keep the games for the personal opening tree
+    MyGames,
+    Cancel,
+}
+
+/// Asks where a dropped PGN file should be opened
+pub struct ImportDialog;
+
+impl ImportDialog {
+    /// `game_count` is None for an archive, which isn't read until the user picks
+    pub fn show(ctx: &Context, file_name: &str, game_count: Option<usize>) -> Option<ImportAction> {
+        let mut action = None;
+        egui::Window::new(tr("import.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                match game_count {
+                    Some(count) => ui.label(tr_args("import.summary", &[&file_name, &count])),
+                    None => ui.label(tr_args("import.summary_archive", &[&file_name])),
+                };
+                ui.add_space(8.0);
+                let game_label = if game_count == Some(1) { tr("import.game") } else { tr("import.first_game") };
+                if ui.button(game_label).clicked() {
+                    action = Some(ImportAction::Game);
+                }
+                if ui.button(tr("import.study")).clicked() {
+                    action = Some(ImportAction::Study);
+                }
+                if ui
+                    .button(tr("import.my_games"))
+                    .on_hover_text(tr("import.my_games_hint"))
+                    .clicked()
+                {
+                    action = Some(ImportAction::MyGames);
+                }
+                if ui.button(tr("import.cancel")).clicked() {
+                    action = Some(ImportAction::Cancel);
+                }
+            });
+        action
+    }
+}
@@ -1,15 +1,71 @@
 mod board;
+mod broadcast_panel;
+mod compare;
 mod pieces;
 mod controls;
+mod coverage_window;
+mod drill_summary;
+mod critical_moments;
+mod engine_log;
+mod eval_graph;
+mod explorer_panel;
+mod games_list;
+mod import_dialog;
+mod move_entry;
 mod move_list;
+mod move_time_graph;
 mod theme;
 mod analysis;
+mod onboarding;
+mod online_panel;
+mod player_plate;
+mod puzzle_panel;
+mod quality_strip;
+mod resign_dialog;
+mod result_dialog;
+mod settings;
+mod sound;
 mod study_panel;
+mod status_bar;
+mod thumbnail;
+mod toasts;
+mod training_plan_panel;
+mod tournament_panel;
+mod uci_console;
+mod vision_trainer;
 
 pub use board::ChessBoard;
+pub use broadcast_panel::{BroadcastAction, BroadcastPanel};
+pub use compare::CompareWindow;
 pub use pieces::PieceRenderer;
 pub use controls::{ControlPanel, ControlAction};
+pub use coverage_window::CoverageWindow;
+pub use drill_summary::DrillSummaryWindow;
+pub use critical_moments::CriticalMomentsPanel;
+pub use engine_log::{EngineLog, EngineLogWindow, LogLevel};
+pub use eval_graph::{EvalGraph, BOOK_EXIT_COLOR};
+pub use explorer_panel::ExplorerPanel;
+pub use games_list::GamesList;
+pub use import_dialog::{ImportAction, ImportDialog};
+pub use move_entry::MoveEntry;
 pub use move_list::MoveList;
+pub use move_time_graph::{MoveTimeGraph, TimeFlag};
 pub use theme::Theme;
-pub use analysis::AnalysisPanel;
+pub use analysis::{AnalysisAction, AnalysisPanel, AnalysisSettings};
 pub use study_panel::{StudyPanel, StudyNavAction};
+pub use onboarding::{EngineOnboarding, OnboardingAction};
+pub use online_panel::{OnlineAction, OnlinePanel};
+pub use player_plate::{PlayerPlate, PLATE_HEIGHT};
+pub use puzzle_panel::{DailyPuzzleCard, PuzzleAction, PuzzleCardAction, PuzzlePanel};
+pub use quality_strip::{PhaseStrip, QualityStrip};
+pub use resign_dialog::{ResignDialog, ResignPrompt};
+pub use result_dialog::{ResultAction, ResultDialog};
+pub use settings::{Preferences, SettingsAction, SettingsWindow};
+pub use sound::{speak, Sound};
+pub use status_bar::{EngineActivity, StatusBar};
+pub use thumbnail::BoardThumbnail;
+pub use toasts::{ToastAction, ToastLevel, Toasts};
+pub use tournament_panel::{TournamentAction, TournamentPanel};
+pub use uci_console::{UciConsole, UciConsoleWindow};
+pub use training_plan_panel::TrainingPlanPanel;
+pub use vision_trainer::VisionTrainer;
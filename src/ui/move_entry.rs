@@ -0,0 +1,88 @@
+use crate::game::GameState;
+use crate::i18n::tr;
+use egui::{Key, Ui};
+use shakmaty::Move;
+
+/// Maximum number of autocomplete suggestions shown under the input
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Text box for typing moves in SAN or UCI, with legal-move autocomplete
+#[derive(Default)]
+pub struct MoveEntry {
+    input: String,
+    error: bool,
+}
+
+/// Strip decorations so "Nxf3+" matches a typed "nf3"
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, 'x' | '+' | '#' | '=' | '-'))
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+impl MoveEntry {
+    /// Shows the entry box and returns a legal move when the user submits one
+    pub fn show(&mut self, ui: &mut Ui, game: &GameState, enabled: bool) -> Option<Move> {
+        let mut played = None;
+
+        let suggestions: Vec<(String, Move)> = if self.input.trim().is_empty() || !enabled {
+            Vec::new()
+        } else {
+            let typed = normalize(self.input.trim());
+            game.legal_moves_san()
+                .into_iter()
+                .filter(|(san, m)| {
+                    let uci = m.to_uci(shakmaty::CastlingMode::Standard).to_string();
+                    normalize(san).starts_with(&typed) || uci.starts_with(&typed)
+                })
+                .take(MAX_SUGGESTIONS)
+                .collect()
+        };
+
+        ui.horizontal(|ui| {
+            let label = ui.label(tr("entry.label"));
+            let text_color = if self.error {
+                Some(egui::Color32::from_rgb(255, 100, 100))
+            } else {
+                None
+            };
+            let response = ui.add_enabled(
+                enabled,
+                egui::TextEdit::singleline(&mut self.input)
+                    .hint_text(tr("entry.hint"))
+                    .text_color_opt(text_color)
+                    .desired_width(120.0),
+            )
+            .labelled_by(label.id);
+
+            if response.changed() {
+                self.error = false;
+            }
+
+            if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                // An exact parse wins; otherwise accept a single unambiguous suggestion
+                let parsed = game.parse_move(&self.input).ok().or_else(|| {
+                    (suggestions.len() == 1).then(|| suggestions[0].1)
+                });
+                match parsed {
+                    Some(m) => {
+                        played = Some(m);
+                        self.input.clear();
+                    }
+                    None => self.error = true,
+                }
+                response.request_focus();
+            }
+
+            for (san, m) in &suggestions {
+                if ui.small_button(san).clicked() {
+                    played = Some(*m);
+                    self.input.clear();
+                }
+            }
+        });
+
+        played
+    }
+}
@@ -1,35 +1,33 @@
-use crate::game::MoveRecord;
+use crate::game::{format_clock, MoveRecord, Notation};
+use crate::i18n::{format_move_number, tr};
+use crate::ui::BOOK_EXIT_COLOR;
 use egui::{ScrollArea, Ui};
 
 pub struct MoveList;
 
 impl MoveList {
-    pub fn show(ui: &mut Ui, moves: &[MoveRecord]) {
+    /// `book_exit` is the ply of the first move out of opening theory, marked in the list
+    pub fn show(ui: &mut Ui, moves: &[MoveRecord], notation: Notation, book_exit: Option<usize>) {
         ui.vertical(|ui| {
-            ui.heading("Moves");
+            ui.heading(tr("moves.heading"));
             ui.separator();
 
             ScrollArea::vertical()
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
                     // Display moves in pairs (white, black)
-                    let mut move_pairs: Vec<(usize, &str, Option<&str>)> = Vec::new();
-
-                    for (i, record) in moves.iter().enumerate() {
-                        let move_number = i / 2 + 1;
-                        if i % 2 == 0 {
-                            // White's move
-                            let black_move = moves.get(i + 1).map(|r| r.san.as_str());
-                            move_pairs.push((move_number, &record.san, black_move));
-                        }
-                    }
-
-                    for (num, white_move, black_move) in move_pairs {
+                    for (i, pair) in moves.chunks(2).enumerate() {
                         ui.horizontal(|ui| {
-                            ui.label(format!("{}.", num));
-                            ui.monospace(white_move);
-                            if let Some(black) = black_move {
-                                ui.monospace(black);
+                            ui.label(format_move_number(i + 1));
+                            for (j, record) in pair.iter().enumerate() {
+                                if book_exit == Some(i * 2 + j) {
+                                    ui.colored_label(BOOK_EXIT_COLOR, "📖").on_hover_text(tr("moves.book_exit"));
+                                }
+                                ui.monospace(notation.format(record));
+                                // Thinking time of moves played against the clock
+                                if let Some(spent) = record.time_spent {
+                                    ui.weak(format_clock(spent));
+                                }
                             }
                         });
                     }
@@ -0,0 +1,157 @@
+use crate::game::{eval_loss, format_clock, MoveRecord, Nag, PlayerColor};
+use crate::i18n::{tr, tr_args};
+use egui::{pos2, vec2, Color32, Rect, Sense, Stroke, Ui};
+use std::time::Duration;
+
+const GRAPH_HEIGHT: f32 = 80.0;
+
+/// A move took longer than this many times the side's average...
+const LONG_MOVE_FACTOR: f32 = 2.5;
+/// ...and at least this long
+const LONG_MOVE_MIN: Duration = Duration::from_secs(5);
+/// Moves made faster than this count as instant
+const INSTANT_MOVE: Duration = Duration::from_secs(2);
+
+const LONG_COLOR: Color32 = Color32::from_rgb(255, 170, 0);
+const BLUNDER_COLOR: Color32 = Color32::from_rgb(230, 80, 80);
+
+/// How the time spent on a move stands out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFlag {
+    /// Much longer than the side usually takes
+    Long,
+    /// Played instantly into a blunder
+    InstantBlunder,
+}
+
+impl TimeFlag {
+    pub fn color(self) -> Color32 {
+        match self {
+            TimeFlag::Long => LONG_COLOR,
+            TimeFlag::InstantBlunder => BLUNDER_COLOR,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeFlag::Long => tr("move_time.long"),
+            TimeFlag::InstantBlunder => tr("move_time.instant_blunder"),
+        }
+    }
+}
+
+/// Thinking time per move as bars, White above the axis and Black below
+pub struct MoveTimeGraph<'a> {
+    pub moves: &'a [MoveRecord],
+    pub times: &'a [Duration],
+    /// White-relative centipawn evaluation of each position by index, where known
+    pub evals: &'a [Option<i32>],
+    pub first_mover: PlayerColor,
+    /// Index of the position on the board
+    pub current_index: usize,
+}
+
+impl MoveTimeGraph<'_> {
+    fn mover(&self, ply: usize) -> PlayerColor {
+        if ply % 2 == 0 {
+            self.first_mover
+        } else {
+            self.first_mover.opposite()
+        }
+    }
+
+    fn average(&self, side: PlayerColor) -> Duration {
+        let times: Vec<Duration> = (0..self.times.len())
+            .filter(|ply| self.mover(*ply) == side)
+            .map(|ply| self.times[ply])
+            .collect();
+        if times.is_empty() {
+            Duration::ZERO
+        } else {
+            times.iter().sum::<Duration>() / times.len() as u32
+        }
+    }
+
+    fn is_blunder(&self, ply: usize) -> bool {
+        eval_loss(self.evals, ply, self.mover(ply)).and_then(Nag::from_loss) == Some(Nag::Blunder)
+    }
+
+    /// How the time of each move with a recorded time stands out, if it does
+    pub fn flags(&self) -> Vec<Option<TimeFlag>> {
+        let averages = [self.average(PlayerColor::White), self.average(PlayerColor::Black)];
+        self.times
+            .iter()
+            .enumerate()
+            .map(|(ply, time)| {
+                let average = averages[if self.mover(ply) == PlayerColor::White { 0 } else { 1 }];
+                if *time < INSTANT_MOVE && self.is_blunder(ply) {
+                    Some(TimeFlag::InstantBlunder)
+                } else if *time >= LONG_MOVE_MIN && time.as_secs_f32() > average.as_secs_f32() * LONG_MOVE_FACTOR {
+                    Some(TimeFlag::Long)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Draws the graph and returns the index of the position after a clicked move
+    pub fn show(&self, ui: &mut Ui) -> Option<usize> {
+        if self.times.is_empty() {
+            return None;
+        }
+        let width = ui.available_width();
+        let (rect, response) = ui.allocate_exact_size(vec2(width, GRAPH_HEIGHT), Sense::click());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        let axis = rect.center().y;
+        painter.line_segment(
+            [pos2(rect.left(), axis), pos2(rect.right(), axis)],
+            Stroke::new(1.0, ui.visuals().weak_text_color()),
+        );
+
+        let longest = self.times.iter().max().copied().unwrap_or_default().as_secs_f32().max(1.0);
+        let flags = self.flags();
+        let bar_width = width / self.times.len() as f32;
+        let mut hovered = None;
+
+        for (ply, time) in self.times.iter().enumerate() {
+            let side = self.mover(ply);
+
+            let height = (time.as_secs_f32() / longest * (GRAPH_HEIGHT / 2.0 - 2.0)).max(1.0);
+            let left = rect.left() + ply as f32 * bar_width;
+            let (top, bottom) = if side == PlayerColor::White {
+                (axis - height, axis)
+            } else {
+                (axis, axis + height)
+            };
+            let bar = Rect::from_min_max(pos2(left, top), pos2(left + (bar_width - 1.0).max(1.0), bottom));
+
+            let color = match flags[ply] {
+                Some(flag) => flag.color(),
+                None if side == PlayerColor::White => Color32::from_gray(220),
+                None => Color32::from_gray(90),
+            };
+            painter.rect_filled(bar, 0.0, color);
+            if ply + 1 == self.current_index {
+                let stroke = Stroke::new(1.0, ui.visuals().selection.stroke.color);
+                painter.rect_stroke(bar.expand(1.0), 0.0, stroke, egui::StrokeKind::Outside);
+            }
+
+            let column = Rect::from_x_y_ranges(left..=left + bar_width, rect.y_range());
+            if response.hover_pos().is_some_and(|p| column.contains(p)) {
+                hovered = Some(ply);
+            }
+        }
+
+        let ply = hovered?;
+        let san = self.moves.get(ply).map_or("", |m| m.san.as_str());
+        let response = response.on_hover_ui_at_pointer(|ui| {
+            ui.label(tr_args("move_time.tooltip", &[&(ply / 2 + 1), &san, &format_clock(self.times[ply])]));
+            if let Some(flag) = flags[ply] {
+                ui.colored_label(flag.color(), flag.label());
+            }
+        });
+        response.clicked().then_some(ply + 1)
+    }
+}
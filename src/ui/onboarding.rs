@@ -0,0 +1,92 @@
+use crate::i18n::{tr, tr_args};
+use egui::Context;
+use std::path::Path;
+
+/// Official Stockfish release build for the current platform
+fn platform_download_url() -> &'static str {
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        "https://github.com/official-stockfish/Stockfish/releases/latest/download/stockfish-macos-m1-apple-silicon.tar"
+    } else if cfg!(target_os = "macos") {
+        "https://github.com/official-stockfish/Stockfish/releases/latest/download/stockfish-macos-x86-64-avx2.tar"
+    } else if cfg!(target_os = "windows") {
+        "https://github.com/official-stockfish/Stockfish/releases/latest/download/stockfish-windows-x86-64-avx2.zip"
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        "https://github.com/official-stockfish/Stockfish/releases/latest/download/stockfish-ubuntu-x86-64-avx2.tar"
+    } else {
+        "https://stockfishchess.org/download/"
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum OnboardingAction {
+    /// Use the engine binary at this path
+    UseEnginePath(String),
+    /// Close the dialog and continue without an engine
+    Dismiss,
+}
+
+/// First-run dialog shown when no Stockfish binary could be found
+#[derive(Default)]
+pub struct EngineOnboarding {
+    path_input: String,
+    error: Option<String>,
+}
+
+impl EngineOnboarding {
+    pub fn show(&mut self, ctx: &Context) -> Option<OnboardingAction> {
+        let mut action = None;
+
+        egui::Window::new(tr("onboarding.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.set_max_width(420.0);
+                ui.label(tr("onboarding.intro"));
+                ui.add_space(8.0);
+
+                // Step 1: download
+                ui.strong(tr("onboarding.step_download"));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("onboarding.download")).clicked() {
+                        ctx.open_url(egui::OpenUrl::new_tab(platform_download_url()));
+                    }
+                    ui.hyperlink_to(tr("onboarding.all_downloads"), "https://stockfishchess.org/download/");
+                });
+                ui.add_space(8.0);
+
+                // Step 2: locate the binary
+                ui.strong(tr("onboarding.step_locate"));
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.path_input)
+                        .on_hover_text(tr("onboarding.path_hint"));
+                    if ui.button(tr("onboarding.browse")).clicked() {
+                        if let Some(path) = rfd::FileDialog::new().set_title(tr("onboarding.title")).pick_file() {
+                            self.path_input = path.display().to_string();
+                            self.error = None;
+                        }
+                    }
+                    if ui.button(tr("onboarding.use_engine")).clicked() {
+                        let expanded = shellexpand::tilde(self.path_input.trim()).to_string();
+                        if Path::new(&expanded).is_file() {
+                            self.error = None;
+                            action = Some(OnboardingAction::UseEnginePath(expanded));
+                        } else {
+                            self.error = Some(tr_args("onboarding.not_found", &[&expanded]));
+                        }
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error);
+                }
+
+                ui.add_space(8.0);
+                ui.separator();
+                if ui.button(tr("onboarding.skip")).clicked() {
+                    action = Some(OnboardingAction::Dismiss);
+                }
+            });
+
+        action
+    }
+}
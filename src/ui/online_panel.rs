@@ -0,0 +1,120 @@
+use crate::i18n::{tr, tr_args};
+use crate::online::{Challenge, LichessClient, OnlineGame, OnlinePlayer, OnlineSettings};
+use egui::{Color32, Ui};
+
+/// What the user did in the online panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnlineAction {
+    Connect,
+    Accept(String),
+    Decline(String),
+    Seek,
+    Resign,
+}
+
+/// Lichess connection, challenges and the running online game
+pub struct OnlinePanel;
+
+impl OnlinePanel {
+    pub fn show(
+        ui: &mut Ui,
+        settings: &mut OnlineSettings,
+        client: Option<&LichessClient>,
+        challenges: &[Challenge],
+        game: Option<&OnlineGame>,
+    ) -> Option<OnlineAction> {
+        let mut action = None;
+
+        let Some(client) = client else {
+            ui.label(tr("online.no_token"));
+            return None;
+        };
+        match &client.username {
+            Some(name) => {
+                let account = if client.bot { tr("online.bot_account") } else { tr("online.board_account") };
+                ui.label(tr_args("online.connected", &[name, &account]));
+            }
+            None if client.error.is_none() => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("online.connecting"));
+                });
+            }
+            None => {}
+        }
+        if let Some(error) = &client.error {
+            ui.colored_label(Color32::from_rgb(230, 80, 80), tr_args("online.disconnected", &[error]));
+            if ui.button(tr("online.reconnect")).clicked() {
+                action = Some(OnlineAction::Connect);
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr("online.player"));
+            for player in OnlinePlayer::all() {
+                ui.selectable_value(&mut settings.player, *player, player.label());
+            }
+        });
+        ui.separator();
+
+        if let Some(game) = game.filter(|g| !g.is_over()) {
+            let opponent = game.player(game.color.opposite());
+            ui.label(tr_args("online.playing", &[&opponent.name]));
+            ui.label(if game.our_turn() { tr("online.your_turn") } else { tr("online.their_turn") });
+            if ui.button(tr("online.resign")).clicked() {
+                action = Some(OnlineAction::Resign);
+            }
+            return action;
+        }
+        if let Some(game) = game {
+            ui.label(tr_args("online.finished", &[&game.state.status]));
+        }
+
+        // Seeking is only open to Board API accounts
+        if client.username.is_some() && !client.bot {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.seek_minutes).range(1..=180).suffix(" min"));
+                ui.label("+");
+                ui.add(egui::DragValue::new(&mut settings.seek_increment).range(0..=180).suffix(" s"));
+                // Lichess only pairs Board API seeks of rapid length or longer
+                let allowed = settings.seek_minutes >= 8;
+                if ui
+                    .add_enabled(allowed && !client.seeking, egui::Button::new(tr("online.seek")))
+                    .on_disabled_hover_text(tr("online.seek_hint"))
+                    .clicked()
+                {
+                    action = Some(OnlineAction::Seek);
+                }
+            });
+            if client.seeking {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("online.seeking"));
+                });
+            }
+        }
+
+        ui.label(tr("online.challenges"));
+        if challenges.is_empty() {
+            ui.weak(tr("online.no_challenges"));
+        }
+        for challenge in challenges {
+            ui.horizontal(|ui| {
+                let rating = challenge.challenger.rating.map(|r| format!(" ({})", r)).unwrap_or_default();
+                let kind = if challenge.rated { tr("online.rated") } else { tr("online.casual") };
+                ui.label(format!("{}{} · {} · {}", challenge.challenger.name, rating, challenge.time_control, kind));
+                if ui
+                    .add_enabled(challenge.is_standard(), egui::Button::new(tr("online.accept")))
+                    .on_disabled_hover_text(tr("online.variant_unsupported"))
+                    .clicked()
+                {
+                    action = Some(OnlineAction::Accept(challenge.id.clone()));
+                }
+                if ui.button(tr("online.decline")).clicked() {
+                    action = Some(OnlineAction::Decline(challenge.id.clone()));
+                }
+            });
+        }
+        action
+    }
+}
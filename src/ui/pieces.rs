@@ -1,6 +1,7 @@
-use egui::{vec2, Color32, ColorImage, Context, TextureHandle, TextureOptions};
+use egui::{vec2, Color32, ColorImage, Context, TextureFilter, TextureHandle, TextureOptions};
 use shakmaty::{Color, Role};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 // Embedded SVG piece data
 const PIECE_SVGS: &[(&str, &str)] = &[
@@ -35,26 +36,44 @@ fn piece_key(role: Role, color: Color) -> &'static str {
     }
 }
 
+/// Sizes pieces are rasterized at; anything in between is drawn from the next size up and
+/// scaled by the GPU, so resizing the window doesn't render new textures every frame
+const SIZE_BUCKETS: [u32; 12] = [16, 24, 32, 48, 64, 96, 128, 192, 256, 384, 512, 768];
+
+/// Sizes not drawn for this long are dropped from the cache
+const STALE_AFTER: Duration = Duration::from_secs(10);
+
+/// The rasterized bucket at or above `size`, the largest one for anything bigger
+fn bucket_for(size: u32) -> u32 {
+    SIZE_BUCKETS
+        .iter()
+        .copied()
+        .find(|&bucket| bucket >= size)
+        .unwrap_or(SIZE_BUCKETS[SIZE_BUCKETS.len() - 1])
+}
+
+/// Every piece rendered at one size bucket
+struct PieceSet {
+    textures: HashMap<&'static str, TextureHandle>,
+    last_used: Instant,
+}
+
 pub struct PieceRenderer {
-    textures: HashMap<(String, u32), TextureHandle>,
-    svg_data: HashMap<String, String>,
-    current_size: u32,
+    sets: HashMap<u32, PieceSet>,
+    svg_data: HashMap<&'static str, &'static str>,
 }
 
 impl PieceRenderer {
     pub fn new() -> Self {
-        let mut svg_data = HashMap::new();
-        for (key, data) in PIECE_SVGS {
-            svg_data.insert(key.to_string(), data.to_string());
-        }
-
         Self {
-            textures: HashMap::new(),
-            svg_data,
-            current_size: 0,
+            sets: HashMap::new(),
+            svg_data: PIECE_SVGS.iter().copied().collect(),
         }
     }
 
+    /// Texture for a piece drawn `size` points wide. It is rasterized in physical pixels,
+    /// possibly a little larger, so paint it over its full UV range into a rect of the
+    /// size wanted rather than its own.
     pub fn get_texture(
         &mut self,
         ctx: &Context,
@@ -62,23 +81,36 @@ impl PieceRenderer {
         color: Color,
         size: u32,
     ) -> Option<&TextureHandle> {
-        let key = piece_key(role, color).to_string();
-        let cache_key = (key.clone(), size);
-
-        if !self.textures.contains_key(&cache_key) {
-            if let Some(svg_str) = self.svg_data.get(&key) {
-                if let Some(image) = self.render_svg(svg_str, size) {
-                    let texture = ctx.load_texture(
-                        format!("piece_{}_{}", key, size),
-                        image,
-                        TextureOptions::LINEAR,
-                    );
-                    self.textures.insert(cache_key.clone(), texture);
-                }
-            }
+        let pixels = (size as f32 * ctx.pixels_per_point()).round() as u32;
+        let bucket = bucket_for(pixels);
+        let now = Instant::now();
+
+        if !self.sets.contains_key(&bucket) {
+            self.sets.retain(|_, set| now.duration_since(set.last_used) < STALE_AFTER);
+            let set = self.render_set(ctx, bucket, now);
+            self.sets.insert(bucket, set);
         }
 
-        self.textures.get(&cache_key)
+        let set = self.sets.get_mut(&bucket)?;
+        set.last_used = now;
+        set.textures.get(piece_key(role, color))
+    }
+
+    /// Rasterize all twelve pieces at once so a new size costs one hitch, not twelve
+    fn render_set(&self, ctx: &Context, size: u32, now: Instant) -> PieceSet {
+        let options = TextureOptions {
+            mipmap_mode: Some(TextureFilter::Linear),
+            ..TextureOptions::LINEAR
+        };
+        let textures = self
+            .svg_data
+            .iter()
+            .filter_map(|(&key, svg_str)| {
+                let image = self.render_svg(svg_str, size)?;
+                Some((key, ctx.load_texture(format!("piece_{}_{}", key, size), image, options)))
+            })
+            .collect();
+        PieceSet { textures, last_used: now }
     }
 
     fn render_svg(&self, svg_str: &str, size: u32) -> Option<ColorImage> {
@@ -107,13 +139,7 @@ impl PieceRenderer {
     }
 
     pub fn invalidate_cache(&mut self) {
-        self.textures.clear();
-    }
-
-    pub fn set_size(&mut self, size: u32) {
-        if self.current_size != size {
-            self.current_size = size;
-        }
+        self.sets.clear();
     }
 }
 
@@ -122,3 +148,17 @@ impl Default for PieceRenderer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_for() {
+        assert_eq!(bucket_for(1), 16);
+        assert_eq!(bucket_for(48), 48);
+        assert_eq!(bucket_for(49), 64);
+        assert_eq!(bucket_for(700), 768);
+        assert_eq!(bucket_for(1000), 768);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::game::format_clock;
+use egui::{Color32, RichText, Ui};
+use shakmaty::{Color, Role};
+use std::time::Duration;
+
+/// Height reserved for a name plate above or below the board
+pub const PLATE_HEIGHT: f32 = 28.0;
+
+/// Name, rating, clock and captured material for one side of the board
+pub struct PlayerPlate<'a> {
+    pub name: &'a str,
+    pub elo: Option<u32>,
+    pub clock: Option<Duration>,
+    pub clock_running: bool,
+    /// Flash the clock red to warn about low time
+    pub low_time: bool,
+    /// Opponent pieces this player has captured
+    pub captured: &'a [Role],
+    /// Color of the captured pieces
+    pub captured_color: Color,
+    /// Material lead in pawns, shown when positive
+    pub advantage: i32,
+}
+
+fn captured_symbol(role: Role, color: Color) -> char {
+    match (color, role) {
+        (Color::White, Role::Pawn) => '♙',
+        (Color::White, Role::Knight) => '♘',
+        (Color::White, Role::Bishop) => '♗',
+        (Color::White, Role::Rook) => '♖',
+        (Color::White, Role::Queen) => '♕',
+        (Color::White, Role::King) => '♔',
+        (Color::Black, Role::Pawn) => '♟',
+        (Color::Black, Role::Knight) => '♞',
+        (Color::Black, Role::Bishop) => '♝',
+        (Color::Black, Role::Rook) => '♜',
+        (Color::Black, Role::Queen) => '♛',
+        (Color::Black, Role::King) => '♚',
+    }
+}
+
+impl PlayerPlate<'_> {
+    pub fn show(&self, ui: &mut Ui) {
+        ui.horizontal_centered(|ui| {
+            ui.strong(self.name);
+            if let Some(elo) = self.elo {
+                ui.weak(format!("({})", elo));
+            }
+
+            let captured: String = self
+                .captured
+                .iter()
+                .map(|role| captured_symbol(*role, self.captured_color))
+                .collect();
+            if !captured.is_empty() {
+                ui.label(RichText::new(captured).size(16.0));
+            }
+            if self.advantage > 0 {
+                ui.weak(format!("+{}", self.advantage));
+            }
+
+            if let Some(clock) = self.clock {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let text = RichText::new(format_clock(clock)).monospace().size(16.0);
+                    let text = if self.low_time {
+                        // Pulse between dark and bright red once per second
+                        let phase = (ui.input(|i| i.time) * std::f64::consts::TAU).sin() as f32 * 0.5 + 0.5;
+                        let red = Color32::from_rgb(120, 20, 20).lerp_to_gamma(Color32::from_rgb(220, 40, 40), phase);
+                        ui.ctx().request_repaint();
+                        text.strong().color(Color32::WHITE).background_color(red)
+                    } else if self.clock_running {
+                        text.strong().color(Color32::WHITE).background_color(Color32::from_rgb(60, 110, 60))
+                    } else {
+                        text
+                    };
+                    ui.label(text);
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,120 @@
+use crate::game::{GameState, PlayerColor};
+use crate::i18n::{tr, tr_args};
+use crate::training::{DailyPuzzle, PuzzleSession};
+use crate::ui::{BoardThumbnail, PieceRenderer, Theme};
+use egui::{Color32, Context, RichText, Ui};
+
+const THUMBNAIL_SIZE: f32 = 200.0;
+
+/// What the user chose on the daily puzzle card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleCardAction {
+    Solve,
+    Later,
+}
+
+/// Startup card offering the puzzle of the day
+pub struct DailyPuzzleCard;
+
+impl DailyPuzzleCard {
+    pub fn show(
+        ctx: &Context,
+        daily: &mut DailyPuzzle,
+        streak: u32,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+    ) -> Option<PuzzleCardAction> {
+        let mut action = None;
+        egui::Window::new(tr("puzzle.daily"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    let fetch_error = daily.fetch_error.clone();
+                    match daily.poll() {
+                        Some(puzzle) => {
+                            BoardThumbnail::show(ui, &puzzle.fen, theme, piece_renderer, THUMBNAIL_SIZE);
+                            if let Ok(game) = GameState::from_fen(&puzzle.fen) {
+                                ui.label(match game.turn() {
+                                    PlayerColor::White => tr("puzzle.white_to_move"),
+                                    PlayerColor::Black => tr("puzzle.black_to_move"),
+                                });
+                            }
+                            if let Some(rating) = puzzle.rating {
+                                ui.weak(tr_args("puzzle.rating", &[&rating]));
+                            }
+                            if let Some(error) = fetch_error {
+                                ui.weak(tr("puzzle.offline")).on_hover_text(error);
+                            }
+                        }
+                        None => {
+                            ui.spinner();
+                            ui.label(tr("puzzle.loading"));
+                        }
+                    }
+                    ui.label(tr_args("puzzle.streak", &[&streak]));
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!daily.is_loading(), egui::Button::new(tr("puzzle.solve")))
+                            .clicked()
+                        {
+                            action = Some(PuzzleCardAction::Solve);
+                        }
+                        if ui.button(tr("puzzle.later")).clicked() {
+                            action = Some(PuzzleCardAction::Later);
+                        }
+                    });
+                });
+            });
+        action
+    }
+}
+
+/// What the user chose in the puzzle sidebar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleAction {
+    /// Play the next solution move, giving up the streak for today
+    ShowMove,
+    Close,
+}
+
+/// Sidebar shown while solving a puzzle, in place of the analysis tools
+pub struct PuzzlePanel;
+
+impl PuzzlePanel {
+    pub fn show(ui: &mut Ui, session: &PuzzleSession, streak: u32, best: u32) -> Option<PuzzleAction> {
+        let mut action = None;
+        ui.heading(tr("puzzle.daily"));
+        if session.solved {
+            let message = if session.counts() { tr("puzzle.solved") } else { tr("puzzle.solved_revealed") };
+            ui.colored_label(Color32::from_rgb(100, 200, 100), RichText::new(message).strong());
+        } else {
+            ui.label(tr("puzzle.find_best"));
+        }
+        if session.mistakes > 0 {
+            ui.weak(tr_args("puzzle.mistakes", &[&session.mistakes]));
+        }
+        ui.label(tr_args("puzzle.streak_best", &[&streak, &best]));
+        if let Some(url) = session.puzzle.url() {
+            ui.hyperlink_to(tr("puzzle.open_lichess"), url);
+        }
+
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            if !session.solved
+                && ui
+                    .button(tr("puzzle.show_move"))
+                    .on_hover_text(tr("puzzle.show_move_hint"))
+                    .clicked()
+            {
+                action = Some(PuzzleAction::ShowMove);
+            }
+            if ui.button(tr("puzzle.close")).clicked() {
+                action = Some(PuzzleAction::Close);
+            }
+        });
+        action
+    }
+}
@@ -0,0 +1,56 @@
+use crate::game::{GamePhase, QualitySummary};
+use crate::i18n::tr;
+use egui::{Color32, RichText, Ui};
+
+const BRILLIANT_COLOR: Color32 = Color32::from_rgb(40, 190, 190);
+const GOOD_COLOR: Color32 = Color32::from_rgb(90, 170, 90);
+const INACCURACY_COLOR: Color32 = Color32::from_rgb(230, 200, 60);
+const MISTAKE_COLOR: Color32 = Color32::from_rgb(255, 150, 40);
+const BLUNDER_COLOR: Color32 = Color32::from_rgb(230, 80, 80);
+
+/// One player's move quality counts on a single line, like the summaries of online review tools
+pub struct QualityStrip<'a> {
+    pub name: &'a str,
+    pub summary: &'a QualitySummary,
+}
+
+impl QualityStrip<'_> {
+    pub fn show(&self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(self.name).strong());
+            let counts = [
+                ("!!", self.summary.brilliant, BRILLIANT_COLOR, "quality.brilliant"),
+                ("✔", self.summary.good, GOOD_COLOR, "quality.good"),
+                ("?!", self.summary.inaccuracies, INACCURACY_COLOR, "quality.inaccuracies"),
+                ("?", self.summary.mistakes, MISTAKE_COLOR, "quality.mistakes"),
+                ("??", self.summary.blunders, BLUNDER_COLOR, "quality.blunders"),
+            ];
+            for (symbol, count, color, key) in counts {
+                let text = RichText::new(format!("{} {}", symbol, count)).color(color).monospace();
+                ui.label(text).on_hover_text(tr(key));
+            }
+        });
+    }
+}
+
+/// One player's average loss in each phase of the game, the weakest phase highlighted
+pub struct PhaseStrip<'a> {
+    pub phases: &'a [(GamePhase, QualitySummary)],
+}
+
+impl PhaseStrip<'_> {
+    pub fn show(&self, ui: &mut Ui) {
+        let worst = self.phases.iter().max_by_key(|(_, s)| s.average_loss).map(|(phase, _)| *phase);
+        ui.horizontal(|ui| {
+            for (phase, summary) in self.phases {
+                let text = RichText::new(format!("{} {}", phase.label(), summary.average_loss)).small();
+                let text = if self.phases.len() > 1 && Some(*phase) == worst {
+                    text.color(MISTAKE_COLOR)
+                } else {
+                    text.weak()
+                };
+                ui.label(text).on_hover_text(tr("quality.by_phase"));
+            }
+        });
+    }
+}
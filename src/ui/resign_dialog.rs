@@ -0,0 +1,63 @@
+use crate::i18n::{tr, tr_args};
+use egui::{Align2, Color32, Context, Id, Key, Rect};
+
+/// Why the player is asked whether to resign
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResignPrompt {
+    /// The resign button was pressed
+    Confirm,
+    /// The engine's evaluation stayed below the auto-resign threshold; `score` is the latest,
+    /// in centipawns from the player's side
+    Lost { score: i32 },
+}
+
+/// Asks before resigning, drawn over the board
+pub struct ResignDialog {
+    pub prompt: ResignPrompt,
+}
+
+impl ResignDialog {
+    /// Some(true) to resign, Some(false) to play on; Escape plays on
+    pub fn show(&self, ctx: &Context, board_rect: Rect) -> Option<bool> {
+        let mut choice = None;
+
+        ctx.layer_painter(egui::LayerId::new(egui::Order::Middle, Id::new("resign_dim")))
+            .rect_filled(board_rect, 0.0, Color32::from_black_alpha(120));
+
+        egui::Area::new(Id::new("resign_dialog"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(board_rect.center())
+            .pivot(Align2::CENTER_CENTER)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).inner_margin(16.0).show(ui, |ui| {
+                    ui.set_max_width(board_rect.width() * 0.8);
+                    ui.vertical_centered(|ui| {
+                        match self.prompt {
+                            ResignPrompt::Confirm => {
+                                ui.heading(tr("resign.confirm"));
+                            }
+                            ResignPrompt::Lost { score } => {
+                                ui.heading(tr("resign.lost"));
+                                let pawns = format!("{:+.1}", score as f32 / 100.0);
+                                ui.label(tr_args("resign.lost_detail", &[&pawns]));
+                            }
+                        }
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("resign.resign")).clicked() {
+                                choice = Some(true);
+                            }
+                            if ui.button(tr("resign.play_on")).clicked() {
+                                choice = Some(false);
+                            }
+                        });
+                    });
+                });
+            });
+
+        if ctx.input(|i| i.key_pressed(Key::Escape)) {
+            choice = Some(false);
+        }
+        choice
+    }
+}
@@ -0,0 +1,82 @@
+use crate::game::{GameOutcome, PlayerColor, QualitySummary};
+use crate::i18n::{tr, tr_args};
+use crate::ui::QualityStrip;
+use egui::{Align2, Color32, Context, Id, Rect, RichText};
+
+/// What the user chose in the result dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultAction {
+    Rematch,
+    Analyze,
+    Export,
+    Close,
+}
+
+/// Game-over summary drawn over the board
+pub struct ResultDialog<'a> {
+    pub outcome: GameOutcome,
+    pub player_color: PlayerColor,
+    /// The player's move quality, when the engine evaluated the game
+    pub summary: Option<&'a QualitySummary>,
+    pub opponent: &'a str,
+    pub opponent_summary: Option<&'a QualitySummary>,
+}
+
+impl ResultDialog<'_> {
+    pub fn show(&self, ctx: &Context, board_rect: Rect) -> Option<ResultAction> {
+        let mut action = None;
+
+        // Dim the board behind the dialog
+        ctx.layer_painter(egui::LayerId::new(egui::Order::Middle, Id::new("result_dim")))
+            .rect_filled(board_rect, 0.0, Color32::from_black_alpha(120));
+
+        egui::Area::new(Id::new("result_dialog"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(board_rect.center())
+            .pivot(Align2::CENTER_CENTER)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).inner_margin(16.0).show(ui, |ui| {
+                    ui.set_max_width(board_rect.width() * 0.8);
+                    ui.vertical_centered(|ui| {
+                        let heading = match self.outcome.winner() {
+                            Some(winner) if winner == self.player_color => tr("result.won"),
+                            Some(_) => tr("result.lost"),
+                            None => tr("result.draw"),
+                        };
+                        ui.heading(heading);
+                        ui.label(RichText::new(self.outcome.result()).size(28.0).strong());
+                        if let Some(message) = self.outcome.message() {
+                            ui.label(message);
+                        }
+
+                        if let Some(summary) = self.summary {
+                            ui.separator();
+                            ui.label(tr_args("result.average_loss", &[&summary.average_loss]));
+                            QualityStrip { name: tr("plate.you"), summary }.show(ui);
+                            if let Some(summary) = self.opponent_summary {
+                                QualityStrip { name: self.opponent, summary }.show(ui);
+                            }
+                        }
+
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("result.rematch")).clicked() {
+                                action = Some(ResultAction::Rematch);
+                            }
+                            if ui.button(tr("result.analyze")).clicked() {
+                                action = Some(ResultAction::Analyze);
+                            }
+                            if ui.button(tr("result.export")).clicked() {
+                                action = Some(ResultAction::Export);
+                            }
+                            if ui.button(tr("result.close")).clicked() {
+                                action = Some(ResultAction::Close);
+                            }
+                        });
+                    });
+                });
+            });
+
+        action
+    }
+}
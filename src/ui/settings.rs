@@ -0,0 +1,628 @@
+use crate::game::{Notation, PgnStyle};
+use crate::i18n::{tr, tr_args, Language};
+use crate::dgt::{available_ports, DgtConnection};
+use crate::engine::{EngineRoles, VarietySettings};
+use crate::power::PowerSettings;
+use crate::study::{DrillSettings, StudyFormat};
+use crate::training::PuzzleSource;
+use egui::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Low-time warnings for timed games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockSettings {
+    /// Pulse the clock red when low on time
+    pub flash: bool,
+    /// Beep when crossing the low-time thresholds
+    pub warning_sound: bool,
+    /// Play a sound when a clock runs out
+    pub flag_sound: bool,
+}
+
+impl Default for ClockSettings {
+    fn default() -> Self {
+        Self {
+            flash: true,
+            warning_sound: true,
+            flag_sound: true,
+        }
+    }
+}
+
+/// Offering resignation once the engine rates the player's game as lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoResignSettings {
+    pub enabled: bool,
+    /// Centipawns the player must be behind
+    pub threshold_cp: i32,
+    /// Consecutive engine moves the player must stay behind for
+    pub moves: usize,
+}
+
+impl Default for AutoResignSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_cp: 500,
+            moves: 3,
+        }
+    }
+}
+
+impl AutoResignSettings {
+    /// The latest score when the engine's last searches, newest first and scored from the
+    /// player's side, all put the player at least the threshold behind
+    pub fn lost(&self, scores: impl Iterator<Item = Option<i32>>) -> Option<i32> {
+        if !self.enabled || self.moves == 0 {
+            return None;
+        }
+        let recent: Vec<Option<i32>> = scores.take(self.moves).collect();
+        let lost = recent.len() == self.moves && recent.iter().all(|s| s.is_some_and(|s| s <= -self.threshold_cp));
+        if lost {
+            recent[0]
+        } else {
+            None
+        }
+    }
+}
+
+/// What clicking one of the moving side's pieces does while another of them is selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OwnPieceClick {
+    /// Select the clicked piece instead
+    #[default]
+    Switch,
+    /// Try to move the selected piece there, dropping the selection when that isn't legal
+    Move,
+}
+
+impl OwnPieceClick {
+    pub fn all() -> &'static [OwnPieceClick] {
+        &[OwnPieceClick::Switch, OwnPieceClick::Move]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OwnPieceClick::Switch => tr("settings.own_piece_switch"),
+            OwnPieceClick::Move => tr("settings.own_piece_move"),
+        }
+    }
+}
+
+/// How clicks on the board pick pieces up and move them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClickSettings {
+    pub own_piece: OwnPieceClick,
+    /// Clicking the selected piece again drops the selection
+    pub deselect_selected: bool,
+    /// The first click on a destination only marks it; a second click makes the move
+    pub confirm_destination: bool,
+}
+
+/// User preferences edited in the settings window, persisted with the app state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub language: Language,
+    pub notation: Notation,
+    /// Flip the board to the player's color (Game) or the chapter's side (Study) automatically
+    pub orient_to_player: bool,
+    /// How often the analysis panel refreshes while the engine is analyzing, in milliseconds
+    pub analysis_refresh_ms: u64,
+    /// Keep the engine's hash between analyses of positions from the same game or study line
+    pub keep_hash: bool,
+    /// Engines and thread limits for playing, analysis and game reviews
+    pub engine_roles: EngineRoles,
+    /// Randomized opening moves of the engine in Game mode
+    pub variety: VarietySettings,
+    /// Plain-language hints about the position under the evaluation bar
+    pub coach_hints: bool,
+    /// In Game mode, hold each move back until the engine has said how much it costs
+    pub check_moves: bool,
+    pub clock: ClockSettings,
+    pub auto_resign: AutoResignSettings,
+    /// Larger buttons and spacing for touch screens
+    pub touch_mode: bool,
+    /// Promote pawns to a queen without opening the picker
+    pub auto_queen: bool,
+    pub clicks: ClickSettings,
+    /// Mark the engine's last move in Game mode with its own color
+    pub engine_move_highlight: bool,
+    /// Play a sound when the engine moves in Game mode
+    pub engine_move_sound: bool,
+    /// Move and annotation symbols in exported PGN
+    pub pgn_style: PgnStyle,
+    /// Mark the mistakes the engine found in exported games with annotation glyphs
+    pub export_mistakes: bool,
+    /// The user's names on Lichess, Chess.com etc., comma separated, to find their side in imported games
+    pub usernames: String,
+    /// Where the daily puzzle offered on startup comes from
+    pub daily_puzzle: PuzzleSource,
+    /// Serial port of the electronic board last connected
+    pub dgt_port: String,
+    /// The electronic board is set up with Black at the cable end
+    pub dgt_rotated: bool,
+    /// Read out moves that have to be made on the electronic board
+    pub announce_moves: bool,
+    /// Pausing or slowing down analysis to save power
+    pub power: PowerSettings,
+    /// File format new saves of a study are written in
+    pub study_format: StudyFormat,
+    pub drill: DrillSettings,
+}
+
+impl Preferences {
+    pub fn usernames(&self) -> Vec<String> {
+        self.usernames
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            language: Language::English,
+            notation: Notation::San,
+            orient_to_player: true,
+            analysis_refresh_ms: 250,
+            keep_hash: true,
+            engine_roles: EngineRoles::default(),
+            variety: VarietySettings::default(),
+            coach_hints: false,
+            check_moves: false,
+            clock: ClockSettings::default(),
+            auto_resign: AutoResignSettings::default(),
+            touch_mode: false,
+            auto_queen: false,
+            clicks: ClickSettings::default(),
+            engine_move_highlight: true,
+            engine_move_sound: false,
+            pgn_style: PgnStyle::default(),
+            export_mistakes: false,
+            usernames: String::new(),
+            daily_puzzle: PuzzleSource::Lichess,
+            dgt_port: String::new(),
+            dgt_rotated: false,
+            announce_moves: true,
+            power: PowerSettings::default(),
+            study_format: StudyFormat::default(),
+            drill: DrillSettings::default(),
+        }
+    }
+}
+
+/// Settings window for display preferences that apply to every mode
+pub struct SettingsWindow;
+
+#[derive(Debug, Clone)]
+pub enum SettingsAction {
+    Language(Language),
+    Notation(Notation),
+    OrientToPlayer(bool),
+    TouchMode(bool),
+    /// The user's names were edited
+    Usernames,
+    EnginePath(String),
+    /// An engine assigned to a mode was changed
+    EngineRoles,
+    ShowEngineLog,
+    ShowUciConsole,
+    /// Connect the electronic board on this port
+    DgtConnect(String),
+    DgtDisconnect,
+    /// Save studies, games and settings to a backup file
+    Backup,
+    /// Replace them from a backup file
+    Restore,
+}
+
+impl SettingsWindow {
+    pub fn show(
+        ctx: &Context,
+        open: &mut bool,
+        preferences: &mut Preferences,
+        engine_path: &mut String,
+        dgt: Option<&DgtConnection>,
+    ) -> Option<SettingsAction> {
+        let mut action = None;
+        let Preferences {
+            language,
+            notation,
+            orient_to_player,
+            analysis_refresh_ms,
+            keep_hash,
+            engine_roles,
+            variety,
+            coach_hints,
+            check_moves,
+            clock,
+            auto_resign,
+            touch_mode,
+            auto_queen,
+            clicks,
+            engine_move_highlight,
+            engine_move_sound,
+            pgn_style,
+            export_mistakes,
+            usernames,
+            daily_puzzle,
+            dgt_port,
+            dgt_rotated,
+            announce_moves,
+            power,
+            study_format,
+            drill,
+        } = preferences;
+
+        egui::Window::new(tr("settings.title"))
+            .open(open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 8.0])
+                    .show(ui, |ui| {
+                        // Language selection
+                        ui.label(tr("controls.language"));
+                        egui::ComboBox::from_id_salt("language")
+                            .selected_text(language.label())
+                            .show_ui(ui, |ui| {
+                                for l in Language::all() {
+                                    if ui.selectable_value(language, *l, l.label()).clicked() {
+                                        action = Some(SettingsAction::Language(*l));
+                                    }
+                                }
+                            });
+                        ui.end_row();
+
+                        // Move notation
+                        ui.label(tr("settings.notation"));
+                        egui::ComboBox::from_id_salt("notation")
+                            .selected_text(notation.label())
+                            .show_ui(ui, |ui| {
+                                for n in Notation::all() {
+                                    if ui.selectable_value(notation, *n, n.label()).clicked() {
+                                        action = Some(SettingsAction::Notation(*n));
+                                    }
+                                }
+                            });
+                        ui.end_row();
+
+                        // Board orientation
+                        ui.label(tr("settings.board"));
+                        if ui
+                            .checkbox(orient_to_player, tr("settings.orient_to_player"))
+                            .on_hover_text(tr("settings.orient_to_player_hint"))
+                            .changed()
+                        {
+                            action = Some(SettingsAction::OrientToPlayer(*orient_to_player));
+                        }
+                        ui.end_row();
+
+                        // Touch screens
+                        ui.label(tr("settings.touch"));
+                        if ui
+                            .checkbox(touch_mode, tr("settings.touch_mode"))
+                            .on_hover_text(tr("settings.touch_mode_hint"))
+                            .changed()
+                        {
+                            action = Some(SettingsAction::TouchMode(*touch_mode));
+                        }
+                        ui.end_row();
+
+                        // Holding N, R or B while dropping a pawn still under-promotes
+                        ui.label(tr("settings.promotion"));
+                        ui.checkbox(auto_queen, tr("settings.auto_queen"))
+                            .on_hover_text(tr("settings.auto_queen_hint"));
+                        ui.end_row();
+
+                        // Click habits differ between chess programs
+                        ui.label(tr("settings.clicks"));
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tr("settings.own_piece"));
+                                egui::ComboBox::from_id_salt("own_piece_click")
+                                    .selected_text(clicks.own_piece.label())
+                                    .show_ui(ui, |ui| {
+                                        for choice in OwnPieceClick::all() {
+                                            ui.selectable_value(&mut clicks.own_piece, *choice, choice.label());
+                                        }
+                                    });
+                            });
+                            ui.checkbox(&mut clicks.deselect_selected, tr("settings.deselect_selected"));
+                            ui.checkbox(&mut clicks.confirm_destination, tr("settings.confirm_destination"))
+                                .on_hover_text(tr("settings.confirm_destination_hint"));
+                        });
+                        ui.end_row();
+
+                        // Which side just moved, at a glance or from another window
+                        ui.label(tr("settings.engine_moves"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(engine_move_highlight, tr("settings.engine_move_highlight"));
+                            ui.checkbox(engine_move_sound, tr("settings.engine_move_sound"));
+                        });
+                        ui.end_row();
+
+                        // Analysis refresh rate, lower values cost more CPU
+                        ui.label(tr("settings.analysis_refresh"));
+                        ui.add(
+                            egui::Slider::new(analysis_refresh_ms, 50..=1000)
+                                .suffix(" ms")
+                                .logarithmic(true),
+                        )
+                        .on_hover_text(tr("settings.analysis_refresh_hint"));
+                        ui.end_row();
+
+                        // Reusing the hash speeds up stepping through a game
+                        ui.label(tr("settings.engine_hash"));
+                        ui.checkbox(keep_hash, tr("settings.keep_hash"))
+                            .on_hover_text(tr("settings.keep_hash_hint"));
+                        ui.end_row();
+
+                        // Games without an opening book still start differently
+                        ui.label(tr("settings.variety"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut variety.enabled, tr("settings.variety_enabled"))
+                                .on_hover_text(tr("settings.variety_hint"));
+                            ui.add_enabled_ui(variety.enabled, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("settings.variety_moves"));
+                                    ui.add(egui::Slider::new(&mut variety.moves, 2..=6));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("settings.variety_margin"));
+                                    ui.add(egui::Slider::new(&mut variety.margin_cp, 5..=100).suffix(" cp"))
+                                        .on_hover_text(tr("settings.variety_margin_hint"));
+                                });
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("settings.coach"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(coach_hints, tr("settings.coach_hints"))
+                                .on_hover_text(tr("settings.coach_hints_hint"));
+                            ui.checkbox(check_moves, tr("settings.check_moves"))
+                                .on_hover_text(tr("settings.check_moves_hint"));
+                        });
+                        ui.end_row();
+
+                        // Power saving while analyzing
+                        ui.label(tr("settings.power"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut power.pause_unfocused, tr("settings.power_pause_unfocused"));
+                            ui.checkbox(&mut power.pause_on_battery, tr("settings.power_pause_on_battery"));
+                            ui.horizontal(|ui| {
+                                ui.label(tr("settings.power_cpu_limit"));
+                                ui.add(egui::Slider::new(&mut power.cpu_limit, 10..=100).suffix("%"))
+                                    .on_hover_text(tr("settings.power_cpu_limit_hint"));
+                            });
+                        });
+                        ui.end_row();
+
+                        // Low-time warnings
+                        ui.label(tr("settings.clock"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut clock.flash, tr("settings.clock_flash"));
+                            ui.checkbox(&mut clock.warning_sound, tr("settings.clock_warning_sound"));
+                            ui.checkbox(&mut clock.flag_sound, tr("settings.clock_flag_sound"));
+                        });
+                        ui.end_row();
+
+                        // Practice in recognizing lost games
+                        ui.label(tr("settings.auto_resign"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut auto_resign.enabled, tr("settings.auto_resign_enabled"))
+                                .on_hover_text(tr("settings.auto_resign_hint"));
+                            ui.add_enabled_ui(auto_resign.enabled, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("settings.auto_resign_threshold"));
+                                    ui.add(egui::Slider::new(&mut auto_resign.threshold_cp, 200..=1500).suffix(" cp"));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("settings.auto_resign_moves"));
+                                    ui.add(egui::Slider::new(&mut auto_resign.moves, 1..=10));
+                                });
+                            });
+                        });
+                        ui.end_row();
+
+                        // PGN export, standard SAN unless the user opts into symbols
+                        ui.label(tr("settings.export"));
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut pgn_style.figurine, tr("settings.export_figurine"));
+                            ui.checkbox(&mut pgn_style.nag_glyphs, tr("settings.export_glyphs"));
+                            ui.weak(tr("settings.export_hint"));
+                            ui.checkbox(export_mistakes, tr("settings.export_mistakes"));
+                        });
+                        ui.end_row();
+
+                        // Study file format, applies from the next save
+                        ui.label(tr("settings.study_format"));
+                        egui::ComboBox::from_id_salt("study_format")
+                            .selected_text(study_format.label())
+                            .show_ui(ui, |ui| {
+                                for format in StudyFormat::all() {
+                                    ui.selectable_value(study_format, *format, format.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr("settings.study_format_hint"));
+                        ui.end_row();
+
+                        // Repertoire drills, from the next drill started
+                        ui.label(tr("settings.drill"));
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tr("settings.drill_move_seconds"));
+                                ui.add(egui::DragValue::new(&mut drill.move_seconds).range(0..=120).suffix(" s"))
+                                    .on_hover_text(tr("settings.drill_move_seconds_hint"));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(tr("settings.drill_mistakes"));
+                                ui.add(egui::DragValue::new(&mut drill.allowed_mistakes).range(0..=10));
+                            });
+                            ui.checkbox(&mut drill.hints, tr("settings.drill_hints"));
+                        });
+                        ui.end_row();
+
+                        // Names used to find the user's side in imported games
+                        ui.label(tr("settings.usernames"));
+                        if ui
+                            .text_edit_singleline(usernames)
+                            .on_hover_text(tr("settings.usernames_hint"))
+                            .lost_focus()
+                        {
+                            action = Some(SettingsAction::Usernames);
+                        }
+                        ui.end_row();
+
+                        // Daily puzzle, takes effect on the next start
+                        ui.label(tr("settings.daily_puzzle"));
+                        egui::ComboBox::from_id_salt("daily_puzzle")
+                            .selected_text(daily_puzzle.label())
+                            .show_ui(ui, |ui| {
+                                for source in PuzzleSource::all() {
+                                    ui.selectable_value(daily_puzzle, *source, source.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr("settings.daily_puzzle_hint"));
+                        ui.end_row();
+
+                        // Electronic board
+                        ui.label(tr("settings.dgt"));
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_salt("dgt_port")
+                                    .selected_text(if dgt_port.is_empty() { tr("settings.dgt_no_port") } else { dgt_port.as_str() })
+                                    .show_ui(ui, |ui| {
+                                        for port in available_ports() {
+                                            let label = port.clone();
+                                            ui.selectable_value(dgt_port, port, label);
+                                        }
+                                    });
+                                if dgt.is_some() {
+                                    if ui.button(tr("settings.dgt_disconnect")).clicked() {
+                                        action = Some(SettingsAction::DgtDisconnect);
+                                    }
+                                } else if ui
+                                    .add_enabled(!dgt_port.is_empty(), egui::Button::new(tr("settings.dgt_connect")))
+                                    .clicked()
+                                {
+                                    action = Some(SettingsAction::DgtConnect(dgt_port.clone()));
+                                }
+                            });
+                            match dgt {
+                                Some(dgt) if dgt.error.is_some() => {
+                                    ui.colored_label(egui::Color32::from_rgb(230, 80, 80), dgt.error.as_deref().unwrap_or_default());
+                                }
+                                Some(dgt) if dgt.is_connected() => {
+                                    ui.weak(tr_args("settings.dgt_connected", &[&dgt.port]));
+                                }
+                                Some(_) => {
+                                    ui.weak(tr("settings.dgt_waiting"));
+                                }
+                                None => {}
+                            }
+                            ui.checkbox(dgt_rotated, tr("settings.dgt_rotated"));
+                            ui.checkbox(announce_moves, tr("settings.announce_moves"))
+                                .on_hover_text(tr("settings.announce_moves_hint"));
+                        });
+                        ui.end_row();
+
+                        // Engine binary
+                        ui.label(tr("settings.engine_path"));
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(engine_path);
+                            let expanded = shellexpand::tilde(engine_path.trim()).to_string();
+                            let valid = Path::new(&expanded).is_file();
+                            if ui
+                                .add_enabled(valid, egui::Button::new(tr("settings.apply")))
+                                .on_disabled_hover_text(tr("settings.engine_missing"))
+                                .clicked()
+                            {
+                                action = Some(SettingsAction::EnginePath(expanded));
+                            }
+                            if ui.button(tr("settings.engine_log")).clicked() {
+                                action = Some(SettingsAction::ShowEngineLog);
+                            }
+                            if ui.button(tr("settings.uci_console")).on_hover_text(tr("settings.uci_console_hint")).clicked() {
+                                action = Some(SettingsAction::ShowUciConsole);
+                            }
+                        });
+                        ui.end_row();
+
+                        // Engines per mode, each falling back to the one above
+                        ui.label(tr("settings.engine_roles")).on_hover_text(tr("settings.engine_roles_hint"));
+                        egui::Grid::new("engine_roles").num_columns(3).show(ui, |ui| {
+                            let roles = [
+                                (tr("settings.engine_role_game"), &mut engine_roles.game, tr("settings.engine_role_default")),
+                                (tr("settings.engine_role_analysis"), &mut engine_roles.analysis, tr("settings.engine_role_default")),
+                                (tr("settings.engine_role_review"), &mut engine_roles.review, tr("settings.engine_role_review_hint")),
+                            ];
+                            for (label, role, hint) in roles {
+                                ui.label(label);
+                                let field = egui::TextEdit::singleline(&mut role.path).hint_text(hint).desired_width(160.0);
+                                if ui.add(field).lost_focus() {
+                                    action = Some(SettingsAction::EngineRoles);
+                                }
+                                ui.add(thread_limit(&mut role.max_threads));
+                                ui.end_row();
+                            }
+                        });
+                        ui.end_row();
+
+                        // Backup of studies, games and settings
+                        ui.label(tr("settings.backup"));
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("settings.backup_create")).on_hover_text(tr("settings.backup_hint")).clicked() {
+                                action = Some(SettingsAction::Backup);
+                            }
+                            if ui.button(tr("settings.backup_restore")).clicked() {
+                                action = Some(SettingsAction::Restore);
+                            }
+                        });
+                        ui.end_row();
+                    });
+            });
+
+        action
+    }
+}
+
+/// Thread limit for an engine role, zero meaning no limit
+fn thread_limit(threads: &mut u32) -> egui::DragValue<'_> {
+    egui::DragValue::new(threads)
+        .range(0..=256)
+        .custom_formatter(|n, _| if n == 0.0 { tr("settings.engine_threads_any").to_string() } else { tr_args("settings.engine_threads", &[&n]) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_resign() {
+        let settings = AutoResignSettings {
+            enabled: true,
+            threshold_cp: 500,
+            moves: 2,
+        };
+        assert_eq!(settings.lost([Some(-700), Some(-520), Some(100)].into_iter()), Some(-700));
+        assert_eq!(settings.lost([Some(-700), Some(-300)].into_iter()), None);
+        // Unevaluated moves and short games don't count
+        assert_eq!(settings.lost([Some(-700), None].into_iter()), None);
+        assert_eq!(settings.lost([Some(-700)].into_iter()), None);
+        assert_eq!(AutoResignSettings::default().lost([Some(-900); 5].into_iter()), None);
+    }
+}
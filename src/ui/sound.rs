@@ -0,0 +1,177 @@
+use crate::config::{data_dir, write_atomic};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, OnceLock};
+
+const SAMPLE_RATE: u32 = 22_050;
+/// Directory under the data dir holding the synthesized sounds
+const SOUND_DIR: &str = "sounds";
+
+/// Queue of the thread playing sounds one after another, started on the first sound
+static PLAYER: OnceLock<mpsc::Sender<Sound>> = OnceLock::new();
+
+/// Short UI sounds, synthesized on first use and played with the platform's audio player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    /// Clock dropped below a low-time threshold
+    LowTime,
+    /// A clock ran out
+    Flag,
+    /// The engine replied in a game
+    EngineMove,
+}
+
+impl Sound {
+    fn file_name(&self) -> &'static str {
+        match self {
+            Sound::LowTime => "low-time.wav",
+            Sound::Flag => "flag.wav",
+            Sound::EngineMove => "engine-move.wav",
+        }
+    }
+
+    /// Sequence of (frequency in Hz, duration in ms) tones
+    fn tones(&self) -> &'static [(f32, u32)] {
+        match self {
+            Sound::LowTime => &[(880.0, 90), (0.0, 60), (880.0, 90)],
+            Sound::Flag => &[(660.0, 150), (440.0, 150), (330.0, 300)],
+            Sound::EngineMove => &[(520.0, 50), (780.0, 70)],
+        }
+    }
+
+    /// Play without blocking; failures are only logged since sound is optional
+    pub fn play(self) {
+        let player = PLAYER.get_or_init(|| {
+            let (tx, rx) = mpsc::channel::<Sound>();
+            std::thread::spawn(move || {
+                for sound in rx {
+                    if let Err(e) = sound.play_blocking() {
+                        tracing::debug!("Could not play {:?}: {}", sound, e);
+                    }
+                }
+            });
+            tx
+        });
+        let _ = player.send(self);
+    }
+
+    fn play_blocking(&self) -> std::io::Result<()> {
+        let path = data_dir().join(SOUND_DIR).join(self.file_name());
+        if !path.exists() {
+            write_atomic(&path, wav_bytes(self.tones()))?;
+        }
+        for mut command in player_commands(path) {
+            let status = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+            if status.is_ok_and(|s| s.success()) {
+                return Ok(());
+            }
+        }
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no audio player available"))
+    }
+}
+
+/// Read `text` aloud with the platform's speech synthesizer, without blocking
+pub fn speak(text: &str) {
+    let text = text.to_string();
+    std::thread::spawn(move || {
+        for mut command in speech_commands(&text) {
+            let status = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+            if status.is_ok_and(|s| s.success()) {
+                return;
+            }
+        }
+        tracing::debug!("No speech synthesizer available to say {:?}", text);
+    });
+}
+
+/// Speech synthesizers to try, in order, for the current platform
+fn speech_commands(text: &str) -> Vec<Command> {
+    let mut commands = Vec::new();
+    if cfg!(target_os = "macos") {
+        let mut say = Command::new("say");
+        say.arg(text);
+        commands.push(say);
+    } else if cfg!(target_os = "windows") {
+        let mut powershell = Command::new("powershell");
+        powershell.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+                text.replace('\'', "''")
+            ),
+        ]);
+        commands.push(powershell);
+    } else {
+        let mut spd_say = Command::new("spd-say");
+        spd_say.arg("--wait").arg(text);
+        commands.push(spd_say);
+        let mut espeak = Command::new("espeak");
+        espeak.arg(text);
+        commands.push(espeak);
+    }
+    commands
+}
+
+/// Audio players to try, in order, for the current platform
+fn player_commands(path: PathBuf) -> Vec<Command> {
+    let mut commands = Vec::new();
+    if cfg!(target_os = "macos") {
+        let mut afplay = Command::new("afplay");
+        afplay.arg(&path);
+        commands.push(afplay);
+    } else if cfg!(target_os = "windows") {
+        let mut powershell = Command::new("powershell");
+        powershell.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "(New-Object Media.SoundPlayer '{}').PlaySync()",
+                path.display().to_string().replace('\'', "''")
+            ),
+        ]);
+        commands.push(powershell);
+    } else {
+        let mut paplay = Command::new("paplay");
+        paplay.arg(&path);
+        commands.push(paplay);
+        let mut aplay = Command::new("aplay");
+        aplay.arg("-q").arg(&path);
+        commands.push(aplay);
+    }
+    commands
+}
+
+/// 16-bit mono PCM WAV file containing the given tones (a frequency of 0 is silence)
+fn wav_bytes(tones: &[(f32, u32)]) -> Vec<u8> {
+    let mut samples: Vec<i16> = Vec::new();
+    for &(freq, ms) in tones {
+        let count = SAMPLE_RATE * ms / 1000;
+        for n in 0..count {
+            let t = n as f32 / SAMPLE_RATE as f32;
+            // Short linear fade in/out to avoid clicks
+            let fade = (n.min(count - n) as f32 / (SAMPLE_RATE as f32 * 0.005)).min(1.0);
+            let value = (2.0 * std::f32::consts::PI * freq * t).sin() * fade * 0.4;
+            samples.push((value * i16::MAX as f32) as i16);
+        }
+    }
+
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
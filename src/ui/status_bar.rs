@@ -0,0 +1,71 @@
+use crate::engine::DifficultyLevel;
+use crate::i18n::{format_decimal, tr, tr_args};
+use egui::{Color32, Ui};
+
+/// What the engine is currently doing, as shown in the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineActivity {
+    Offline,
+    Idle,
+    Thinking,
+    Analyzing,
+}
+
+impl EngineActivity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EngineActivity::Offline => tr("status.offline"),
+            EngineActivity::Idle => tr("status.idle"),
+            EngineActivity::Thinking => tr("status.thinking"),
+            EngineActivity::Analyzing => tr("status.analyzing"),
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self {
+            EngineActivity::Offline => Color32::RED,
+            EngineActivity::Idle => Color32::GRAY,
+            EngineActivity::Thinking | EngineActivity::Analyzing => Color32::GREEN,
+        }
+    }
+}
+
+pub struct StatusBar;
+
+impl StatusBar {
+    pub fn show(
+        ui: &mut Ui,
+        engine_name: Option<&str>,
+        activity: EngineActivity,
+        nps: Option<u64>,
+        difficulty: DifficultyLevel,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(engine_name.unwrap_or(tr("status.no_engine")));
+            ui.separator();
+            ui.colored_label(activity.color(), format!("● {}", activity.label()));
+
+            if matches!(activity, EngineActivity::Thinking | EngineActivity::Analyzing) {
+                if let Some(nps) = nps {
+                    ui.separator();
+                    ui.label(format_nps(nps));
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(tr_args("status.difficulty", &[&difficulty.label()]));
+            });
+        });
+    }
+}
+
+/// Format nodes per second with a k/M suffix
+fn format_nps(nps: u64) -> String {
+    if nps >= 1_000_000 {
+        format!("{} Mn/s", format_decimal(nps as f32 / 1_000_000.0, 1))
+    } else if nps >= 1_000 {
+        format!("{} kn/s", nps / 1_000)
+    } else {
+        format!("{} n/s", nps)
+    }
+}
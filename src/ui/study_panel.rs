@@ -1,21 +1,68 @@
-use crate::study::{Study, StudyManager};
+use crate::error::AppError;
+use crate::game::{Notation, PlayerColor};
+use crate::i18n::{tr, tr_args};
+use crate::study::{glyph_text, GlyphGroup, RepertoireDrill, Study, StudyManager, StudySummary, GLYPHS};
+use crate::ui::{BoardThumbnail, PieceRenderer, Preferences, Theme};
 use egui::Ui;
 
+/// Edge length of the board previews in the chapter selector
+const CHAPTER_THUMBNAIL_SIZE: f32 = 32.0;
+/// Edge length of the board previews in the load dialog
+const LOAD_THUMBNAIL_SIZE: f32 = 56.0;
+/// Most positions listed under weak spots
+const MAX_WEAK_SPOTS: usize = 10;
+/// Frames to wait for the clipboard's text after asking for it; an empty clipboard sends none
+const PASTE_FRAMES: u64 = 3;
+
+/// Phrases the annotation toolbar adds to the comment being written
+const SNIPPETS: [&str; 5] = [
+    "study.snippet_idea",
+    "study.snippet_only_move",
+    "study.snippet_better_was",
+    "study.snippet_threatening",
+    "study.snippet_plan",
+];
+
 /// Navigation action from study panel
 #[derive(Debug, Clone)]
 pub enum StudyNavAction {
     /// Navigate to a specific position by path of child indices
     GoToPosition(Vec<usize>),
+    /// A different chapter or study was opened
+    ChapterChanged,
+    /// The side the current chapter is studied from was changed
+    OrientationChanged,
+    /// Drill the current chapter's lines from its side
+    StartDrill,
+    StopDrill,
+    /// Show the repertoire move of the drilled position
+    DrillHint,
+    /// Have the engine check the current chapter, flagging moves losing more centipawns than this
+    Audit(i32),
+    /// Have the engine comment on every move of the current chapter's main line
+    Annotate,
 }
 
 pub struct StudyPanel {
     study_manager: StudyManager,
-    available_studies: Vec<(String, String)>, // (id, name)
+    available_studies: Vec<StudySummary>,
     show_new_study_dialog: bool,
     new_study_name: String,
     current_comment: String,
     show_load_dialog: bool,
+    /// Errors raised while saving/loading, drained by the app into toasts
+    errors: Vec<AppError>,
     export_pgn: bool,
+    /// Frame at which the clipboard's text was asked for, to be added as a chapter
+    paste_requested: Option<u64>,
+    /// Full moves between diagrams in printed chapters, none when zero
+    print_every: usize,
+    /// Centipawns a repertoire move may lose in engine audits
+    audit_threshold: i32,
+    /// An edit of a locked study was attempted; offer a personal copy
+    show_copy_prompt: bool,
+    /// Name and value of the PGN tag being added to the chapter
+    new_header: (String, String),
 }
 
 impl Default for StudyPanel {
@@ -30,31 +77,145 @@ impl Default for StudyPanel {
             new_study_name: String::new(),
             current_comment: String::new(),
             show_load_dialog: false,
+            errors: Vec::new(),
             export_pgn: false,
+            paste_requested: None,
+            print_every: 8,
+            audit_threshold: 50,
+            show_copy_prompt: false,
+            new_header: (String::new(), String::new()),
         }
     }
 }
 
 impl StudyPanel {
+    /// Read the saved studies again after their files were replaced, and `study` from its saved
+    /// copy. A study that isn't saved there is kept as it is.
+    pub fn reload(&mut self, study: &mut Study) -> Option<StudyNavAction> {
+        self.available_studies = self.study_manager.list_studies().unwrap_or_default();
+        if !self.available_studies.iter().any(|summary| summary.id == study.id) {
+            return None;
+        }
+        match self.study_manager.load_study(&study.id) {
+            Ok(loaded) => {
+                *study = loaded;
+                Some(StudyNavAction::ChapterChanged)
+            }
+            Err(e) => {
+                self.errors.push(e);
+                None
+            }
+        }
+    }
+
+    /// Save the current chapter as printable HTML and open it in the browser to print
+    fn print_chapter(&mut self, ctx: &egui::Context, study: &Study) {
+        let chapter = study.current_chapter();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("HTML", &["html", "htm"])
+            .set_file_name(format!("{}.html", chapter.name))
+            .save_file()
+        else {
+            return;
+        };
+        match std::fs::write(&path, chapter.to_print_html(&study.name, self.print_every)) {
+            Ok(()) => {
+                // The dialog gives an absolute path, which always makes a file URL
+                if let Ok(url) = url::Url::from_file_path(&path) {
+                    ctx.open_url(egui::OpenUrl::new_tab(url));
+                }
+            }
+            Err(e) => self.errors.push(AppError::FileWrite { path: path.display().to_string(), reason: e.to_string() }),
+        }
+    }
+
+    /// Offer to make an editable copy of the locked study after an edit was refused
+    pub fn prompt_copy(&mut self) {
+        self.show_copy_prompt = true;
+    }
+
+    /// Add PGN text from the clipboard as a new chapter
+    fn paste_chapter(&mut self, study: &mut Study, text: &str) -> Option<StudyNavAction> {
+        match study.add_pgn_text_chapter(text) {
+            Ok(Some(_)) => {
+                study.update_timestamp();
+                Some(StudyNavAction::ChapterChanged)
+            }
+            Ok(None) => {
+                self.errors.push(AppError::NoPgn);
+                None
+            }
+            Err(e) => {
+                self.errors.push(e.into());
+                None
+            }
+        }
+    }
+
+    /// Take any errors raised since the last call
+    pub fn take_errors(&mut self) -> Vec<AppError> {
+        std::mem::take(&mut self.errors)
+    }
+
     /// Shows the study panel and returns any navigation action
-    pub fn show(&mut self, ui: &mut Ui, study: &mut Study) -> Option<StudyNavAction> {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        study: &mut Study,
+        theme: Theme,
+        preferences: &Preferences,
+        piece_renderer: &mut PieceRenderer,
+        drill: Option<&RepertoireDrill>,
+    ) -> Option<StudyNavAction> {
         let mut nav_action = None;
         
         // Handle export PGN
         if self.export_pgn {
-            let pgn = study.to_pgn();
+            let pgn = study.to_pgn(preferences.pgn_style);
             ui.ctx().copy_text(pgn);
             self.export_pgn = false;
         }
 
-        ui.heading("Study");
+        // The clipboard's text comes back as a paste event in a later frame
+        if let Some(requested) = self.paste_requested {
+            let pasted = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(text) = pasted {
+                self.paste_requested = None;
+                nav_action = self.paste_chapter(study, &text);
+            } else if ui.ctx().cumulative_frame_nr() > requested + PASTE_FRAMES {
+                self.paste_requested = None;
+                self.errors.push(AppError::NoPgn);
+            } else {
+                ui.ctx().request_repaint();
+            }
+        }
+
+        ui.heading(tr("study.heading"));
         ui.separator();
 
-        // Study name
+        // Study name, and the lock that keeps the study from being edited
+        let locked = study.locked;
         ui.horizontal(|ui| {
-            ui.label("Name:");
-            ui.text_edit_singleline(&mut study.name);
+            ui.label(tr("study.name"));
+            if locked {
+                ui.strong(&study.name);
+            } else {
+                ui.text_edit_singleline(&mut study.name);
+            }
+            let lock = if locked { "🔒" } else { "🔓" };
+            if ui.selectable_label(locked, lock).on_hover_text(tr("study.lock_hint")).clicked() {
+                study.locked = !locked;
+                study.update_timestamp();
+            }
         });
+        if locked {
+            ui.weak(tr("study.locked"));
+        }
 
         // Chapter selector
         let current_chapter_name = study.current_chapter().name.clone();
@@ -62,38 +223,98 @@ impl StudyPanel {
         let chapter_count = study.chapters.len();
         let mut switch_to: Option<usize> = None;
         ui.horizontal(|ui| {
-            ui.label("Chapter:");
+            ui.label(tr("study.chapter"));
             egui::ComboBox::from_id_salt("chapter_select")
                 .selected_text(&current_chapter_name)
                 .show_ui(ui, |ui| {
                     for (idx, chapter) in study.chapters.iter().enumerate() {
-                        if ui.selectable_label(
-                            current_chapter == idx,
-                            &chapter.name
-                        ).clicked() {
-                            switch_to = Some(idx);
-                        }
+                        ui.horizontal(|ui| {
+                            let thumbnail = BoardThumbnail::show(
+                                ui,
+                                chapter.main_line_end_fen(),
+                                theme,
+                                piece_renderer,
+                                CHAPTER_THUMBNAIL_SIZE,
+                            );
+                            let label = ui.selectable_label(current_chapter == idx, &chapter.name);
+                            if thumbnail.clicked() || label.clicked() {
+                                switch_to = Some(idx);
+                            }
+                        });
                     }
                 });
             
             if ui.button("+").clicked() {
-                let chapter_num = chapter_count + 1;
-                study.add_chapter(format!("Chapter {}", chapter_num));
+                if locked {
+                    self.show_copy_prompt = true;
+                } else {
+                    let chapter_num = chapter_count + 1;
+                    study.add_chapter(tr_args("study.chapter_n", &[&chapter_num]));
+                }
             }
         });
         if let Some(idx) = switch_to {
-            study.switch_chapter(idx);
+            if study.switch_chapter(idx) {
+                nav_action = Some(StudyNavAction::ChapterChanged);
+            }
+        }
+
+        // Side the chapter is trained from
+        ui.horizontal(|ui| {
+            ui.label(tr("study.orientation"));
+            let orientation = study.current_chapter().orientation;
+            for (side, key) in [(PlayerColor::White, "controls.white"), (PlayerColor::Black, "controls.black")] {
+                if ui.selectable_label(orientation == side, tr(key)).clicked() && orientation != side {
+                    if locked {
+                        self.show_copy_prompt = true;
+                    } else {
+                        study.current_chapter_mut().orientation = side;
+                        nav_action = Some(StudyNavAction::OrientationChanged);
+                    }
+                }
+            }
+        });
+
+        self.show_headers(ui, study);
+
+        if let Some(drill) = drill {
+            if let Some(action) = Self::show_drill(ui, drill) {
+                nav_action = Some(action);
+            }
+        } else if ui.button(tr("study.drill")).on_hover_text(tr("study.drill_hint")).clicked() {
+            nav_action = Some(StudyNavAction::StartDrill);
+        }
+        if let Some(action) = Self::show_weak_spots(ui, study) {
+            nav_action = Some(action);
+        }
+        ui.horizontal(|ui| {
+            if ui.button(tr("study.audit")).on_hover_text(tr("study.audit_hint")).clicked() {
+                nav_action = Some(StudyNavAction::Audit(self.audit_threshold));
+            }
+            ui.label(tr("study.audit_threshold"));
+            ui.add(egui::DragValue::new(&mut self.audit_threshold).range(10..=300).suffix(" cp"));
+        });
+        if ui.button(tr("study.annotate")).on_hover_text(tr("study.annotate_hint")).clicked() {
+            if locked {
+                self.show_copy_prompt = true;
+            } else {
+                nav_action = Some(StudyNavAction::Annotate);
+            }
         }
 
         ui.separator();
 
         // Comments section
-        ui.label("Comments:");
+        ui.label(tr("study.comments"));
         
+        if let Some(warning) = &study.current_chapter().current_node().audit {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", warning));
+        }
+
         // Show existing comments
         let comments: Vec<String> = study.current_chapter().current_node().comments.clone();
         if comments.is_empty() {
-            ui.label("No comments yet...");
+            ui.label(tr("study.no_comments"));
         } else {
             for (i, comment) in comments.iter().enumerate() {
                 ui.horizontal(|ui| {
@@ -103,10 +324,16 @@ impl StudyPanel {
             }
         }
 
+        self.show_annotation_toolbar(ui, study);
+
         // Add comment input
         ui.horizontal(|ui| {
             ui.text_edit_singleline(&mut self.current_comment);
-            if ui.button("Add").clicked() && !self.current_comment.is_empty() {
+            if ui.button(tr("study.add")).clicked() && !self.current_comment.is_empty() {
+                if locked {
+                    self.show_copy_prompt = true;
+                    return;
+                }
                 study.current_chapter_mut().add_comment(self.current_comment.clone());
                 self.current_comment.clear();
                 study.update_timestamp();
@@ -116,8 +343,8 @@ impl StudyPanel {
         ui.separator();
 
         // Variations tree
-        ui.label("Variations:");
-        if let Some(action) = self.show_variation_tree(ui, study) {
+        ui.label(tr("study.variations"));
+        if let Some(action) = self.show_variation_tree(ui, study, preferences.notation) {
             nav_action = Some(action);
         }
 
@@ -125,43 +352,85 @@ impl StudyPanel {
 
         // Save/Load buttons
         ui.horizontal(|ui| {
-            if ui.button("💾 Save").clicked() {
-                if let Err(e) = self.study_manager.save_study(study) {
+            if ui.button(tr("study.save")).clicked() {
+                if let Err(e) = self.study_manager.save_study(study, preferences.study_format) {
                     tracing::error!("Failed to save study: {}", e);
+                    self.errors.push(e);
                 } else {
                     self.available_studies = self.study_manager.list_studies().unwrap_or_default();
                 }
             }
             
-            if ui.button("📂 Load").clicked() {
+            if ui.button(tr("study.load")).clicked() {
                 self.show_load_dialog = true;
             }
             
-            if ui.button("🆕 New").clicked() {
+            if ui.button(tr("study.new")).clicked() {
                 self.show_new_study_dialog = true;
             }
         });
 
-        // Export PGN
-        if ui.button("📄 Export PGN").clicked() {
-            self.export_pgn = true;
+        // Export PGN, or add a game copied elsewhere as a chapter
+        ui.horizontal(|ui| {
+            if ui.button(tr("study.export_pgn")).clicked() {
+                self.export_pgn = true;
+            }
+            if ui.button(tr("study.paste_pgn")).on_hover_text(tr("study.paste_pgn_hint")).clicked() {
+                if study.locked {
+                    self.show_copy_prompt = true;
+                } else {
+                    ui.ctx().send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+                    self.paste_requested = Some(ui.ctx().cumulative_frame_nr());
+                    ui.ctx().request_repaint();
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("study.print")).on_hover_text(tr("study.print_hint")).clicked() {
+                self.print_chapter(ui.ctx(), study);
+            }
+            ui.label(tr("study.print_every"));
+            ui.add(egui::DragValue::new(&mut self.print_every).range(0..=40));
+            ui.label(tr("study.print_moves"));
+        });
+
+        // Edits of a locked study go to a personal copy
+        if self.show_copy_prompt {
+            egui::Window::new(tr("study.locked_title"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ui.ctx(), |ui| {
+                    ui.label(tr("study.locked_prompt"));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("study.make_copy")).clicked() {
+                            *study = study.personal_copy();
+                            self.show_copy_prompt = false;
+                            nav_action = Some(StudyNavAction::ChapterChanged);
+                        }
+                        if ui.button(tr("study.cancel")).clicked() {
+                            self.show_copy_prompt = false;
+                        }
+                    });
+                });
         }
 
         // New study dialog
         if self.show_new_study_dialog {
-            egui::Window::new("New Study")
+            egui::Window::new(tr("study.new_study"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ui.ctx(), |ui| {
-                    ui.label("Study name:");
+                    ui.label(tr("study.study_name"));
                     ui.text_edit_singleline(&mut self.new_study_name);
                     ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() && !self.new_study_name.is_empty() {
+                        if ui.button(tr("study.create")).clicked() && !self.new_study_name.is_empty() {
                             *study = Study::new(self.new_study_name.clone());
                             self.new_study_name.clear();
                             self.show_new_study_dialog = false;
+                            nav_action = Some(StudyNavAction::ChapterChanged);
                         }
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(tr("study.cancel")).clicked() {
                             self.new_study_name.clear();
                             self.show_new_study_dialog = false;
                         }
@@ -171,24 +440,53 @@ impl StudyPanel {
 
         // Load study dialog
         if self.show_load_dialog {
-            egui::Window::new("Load Study")
+            egui::Window::new(tr("study.load_study"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ui.ctx(), |ui| {
                     if self.available_studies.is_empty() {
-                        ui.label("No saved studies found.");
+                        ui.label(tr("study.none_saved"));
                     } else {
-                        for (id, name) in self.available_studies.clone().iter() {
-                            if ui.button(name).clicked() {
-                                if let Ok(loaded) = self.study_manager.load_study(id) {
+                        let mut to_load: Option<(String, usize)> = None;
+                        egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            for summary in &self.available_studies {
+                                if ui.button(&summary.name).clicked() {
+                                    to_load = Some((summary.id.clone(), 0));
+                                }
+                                ui.horizontal_wrapped(|ui| {
+                                    for (idx, (chapter_name, fen)) in summary.chapters.iter().enumerate() {
+                                        let thumbnail = BoardThumbnail::show(
+                                            ui,
+                                            fen,
+                                            theme,
+                                            piece_renderer,
+                                            LOAD_THUMBNAIL_SIZE,
+                                        );
+                                        if thumbnail.on_hover_text(chapter_name).clicked() {
+                                            to_load = Some((summary.id.clone(), idx));
+                                        }
+                                    }
+                                });
+                                ui.add_space(4.0);
+                            }
+                        });
+                        if let Some((id, chapter)) = to_load {
+                            match self.study_manager.load_study(&id) {
+                                Ok(loaded) => {
                                     *study = loaded;
+                                    study.switch_chapter(chapter);
+                                    nav_action = Some(StudyNavAction::ChapterChanged);
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to load study: {}", e);
+                                    self.errors.push(e);
                                 }
-                                self.show_load_dialog = false;
                             }
+                            self.show_load_dialog = false;
                         }
                     }
                     ui.separator();
-                    if ui.button("Close").clicked() {
+                    if ui.button(tr("study.close")).clicked() {
                         self.show_load_dialog = false;
                     }
                 });
@@ -197,14 +495,148 @@ impl StudyPanel {
         nav_action
     }
 
-    fn show_variation_tree(&self, ui: &mut Ui, study: &Study) -> Option<StudyNavAction> {
+    /// Mistakes and time left in the line being drilled
+    fn show_drill(ui: &mut Ui, drill: &RepertoireDrill) -> Option<StudyNavAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            let allowed = drill.settings.allowed_mistakes;
+            ui.label(tr_args("study.drill_mistakes", &[&drill.line_mistakes, &allowed]));
+            if let Some(left) = drill.time_left() {
+                ui.label(tr_args("study.drill_time", &[&left.as_secs_f32().ceil()]));
+            }
+        });
+        ui.horizontal(|ui| {
+            if drill.settings.hints && ui.button(tr("study.drill_hint_button")).clicked() {
+                action = Some(StudyNavAction::DrillHint);
+            }
+            if ui.button(tr("study.drill_stop")).clicked() {
+                action = Some(StudyNavAction::StopDrill);
+            }
+        });
+        action
+    }
+
+    /// The chapter's PGN tags, editable unless the study is locked
+    fn show_headers(&mut self, ui: &mut Ui, study: &mut Study) {
+        let locked = study.locked;
+        let mut changed = false;
+        egui::CollapsingHeader::new(tr("study.headers")).show(ui, |ui| {
+            let headers = &mut study.current_chapter_mut().headers;
+            let mut remove = None;
+            egui::Grid::new("chapter_headers").num_columns(3).show(ui, |ui| {
+                for (i, (name, value)) in headers.iter_mut().enumerate() {
+                    ui.label(name.as_str());
+                    if locked {
+                        ui.label(value.as_str());
+                    } else {
+                        changed |= ui.text_edit_singleline(value).changed();
+                        if ui.small_button("✖").on_hover_text(tr("study.header_remove")).clicked() {
+                            remove = Some(i);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(i) = remove {
+                headers.remove(i);
+                changed = true;
+            }
+            if locked {
+                return;
+            }
+            ui.horizontal(|ui| {
+                let (name, value) = &mut self.new_header;
+                ui.add(egui::TextEdit::singleline(name).hint_text(tr("study.header_name")).desired_width(70.0));
+                ui.add(egui::TextEdit::singleline(value).hint_text(tr("study.header_value")).desired_width(100.0));
+                // Tag names are single words and appear once
+                let valid = !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !headers.iter().any(|(key, _)| key == name);
+                if ui.add_enabled(valid, egui::Button::new("+")).clicked() {
+                    headers.push(std::mem::take(&mut self.new_header));
+                    changed = true;
+                }
+            });
+        });
+        if changed {
+            study.update_timestamp();
+        }
+    }
+
+    /// Drilled positions with the worst recall, each opening its chapter at the position
+    fn show_weak_spots(ui: &mut Ui, study: &mut Study) -> Option<StudyNavAction> {
+        let mut clicked = None;
+        egui::CollapsingHeader::new(tr("study.weak_spots")).show(ui, |ui| {
+            let spots = study.weak_spots();
+            if spots.is_empty() {
+                ui.weak(tr("study.weak_spots_none"));
+                return;
+            }
+            egui::Grid::new("weak_spots").num_columns(2).striped(true).show(ui, |ui| {
+                for spot in spots.into_iter().take(MAX_WEAK_SPOTS) {
+                    let chapter = &study.chapters[spot.chapter].name;
+                    let text = if spot.line.is_empty() { chapter.clone() } else { format!("{} · {}", chapter, spot.line_text()) };
+                    if ui.link(text).clicked() {
+                        clicked = Some(spot.clone());
+                    }
+                    ui.weak(tr_args("study.weak_spot_recall", &[&spot.stats.correct, &spot.stats.attempts()]));
+                    ui.end_row();
+                }
+            });
+        });
+        let spot = clicked?;
+        study.switch_chapter(spot.chapter);
+        study.current_chapter_mut().current_path = spot.path;
+        Some(StudyNavAction::ChapterChanged)
+    }
+
+    /// One-click glyphs for the current node and phrases for the comment being written
+    fn show_annotation_toolbar(&mut self, ui: &mut Ui, study: &mut Study) {
+        let node = study.current_chapter().current_node();
+        let has_move = node.move_record.is_some();
+        let nags = node.nags.clone();
+        let mut toggled = None;
+
+        for group in [GlyphGroup::Move, GlyphGroup::Evaluation, GlyphGroup::Symbol] {
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 2.0;
+                for &(nag, symbol, _, key) in GLYPHS.iter().filter(|g| g.2 == group) {
+                    // Move glyphs judge the move that led here, so the start position has none
+                    let enabled = has_move || group != GlyphGroup::Move;
+                    let button = egui::Button::selectable(nags.contains(&nag), symbol);
+                    if ui.add_enabled(enabled, button).on_hover_text(tr(key)).clicked() {
+                        toggled = Some(nag);
+                    }
+                }
+            });
+        }
+        if toggled.is_some() && study.locked {
+            self.show_copy_prompt = true;
+        } else if let Some(nag) = toggled {
+            study.current_chapter_mut().toggle_nag(nag);
+            study.update_timestamp();
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for key in SNIPPETS {
+                if ui.small_button(tr(key)).clicked() {
+                    if !self.current_comment.is_empty() && !self.current_comment.ends_with(' ') {
+                        self.current_comment.push(' ');
+                    }
+                    self.current_comment.push_str(tr(key));
+                }
+            }
+        });
+    }
+
+    fn show_variation_tree(&self, ui: &mut Ui, study: &Study, notation: Notation) -> Option<StudyNavAction> {
         let chapter = study.current_chapter();
         let mut nav_action = None;
 
         // Show path to current position as clickable moves
         ui.horizontal_wrapped(|ui| {
             // Start button - goes to root
-            let start_text = egui::RichText::new("Start")
+            let start_text = egui::RichText::new(tr("study.start"))
                 .color(ui.visuals().hyperlink_color);
             let start_btn = ui.add(egui::Button::new(start_text)
                 .fill(egui::Color32::TRANSPARENT)
@@ -215,24 +647,25 @@ impl StudyPanel {
                 nav_action = Some(StudyNavAction::GoToPosition(Vec::new()));
             }
             
-            let mut node = &chapter.root;
+            let mut node = chapter.root();
             let mut current_path = Vec::new();
             
             for (depth, &idx) in chapter.current_path.iter().enumerate() {
-                if idx < node.children.len() {
-                    let child = &node.children[idx];
+                if let Some(&child_id) = node.children.get(idx) {
+                    let child = chapter.node(child_id);
                     current_path.push(idx);
                     
                     if let Some(ref mv) = child.move_record {
                         // Highlight if this is on our current path
                         let is_current = depth == chapter.current_path.len() - 1;
                         
+                        let label = format!("{}{}", notation.format(mv), glyph_text(&child.nags));
                         let text = if is_current {
-                            egui::RichText::new(&mv.san)
+                            egui::RichText::new(label)
                                 .color(ui.visuals().selection.stroke.color)
                                 .strong()
                         } else {
-                            egui::RichText::new(&mv.san)
+                            egui::RichText::new(label)
                                 .color(ui.visuals().hyperlink_color)
                                 .underline()
                         };
@@ -255,21 +688,27 @@ impl StudyPanel {
         // Show alternatives at current position as clickable moves
         let current_node = chapter.current_node();
         if !current_node.children.is_empty() {
-            ui.label("Alternatives:");
-            for (idx, child) in current_node.children.iter().enumerate() {
+            ui.label(tr("study.alternatives"));
+            for (idx, &child) in current_node.children.iter().enumerate() {
+                let child = chapter.node(child);
                 if let Some(ref mv) = child.move_record {
                     ui.horizontal(|ui| {
                         ui.label(format!("{}.", idx + 1));
                         
                         // Make the move SAN a clickable hyperlink
-                        let text = egui::RichText::new(&mv.san)
+                        let label = format!("{}{}", notation.format(mv), glyph_text(&child.nags));
+                        let text = egui::RichText::new(label)
                             .color(ui.visuals().hyperlink_color)
                             .underline();
                         
-                        let btn = ui.add(egui::Button::new(text)
+                        let mut btn = ui.add(egui::Button::new(text)
                             .fill(egui::Color32::TRANSPARENT)
                             .stroke(egui::Stroke::NONE)
                             .sense(egui::Sense::click()));
+                        if let Some(warning) = &child.audit {
+                            btn = btn.on_hover_text(warning.to_string());
+                            ui.colored_label(ui.visuals().warn_fg_color, "⚠");
+                        }
                         
                         if btn.clicked() {
                             // Build path: current path + this child index
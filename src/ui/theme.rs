@@ -1,3 +1,4 @@
+use crate::i18n::tr;
 use egui::Color32;
 use serde::{Deserialize, Serialize};
 
@@ -17,10 +18,10 @@ impl Theme {
 
     pub fn label(&self) -> &'static str {
         match self {
-            Theme::Classic => "Classic",
+            Theme::Classic => tr("theme.classic"),
             Theme::Lichess => "Lichess",
             Theme::ChessCom => "Chess.com",
-            Theme::Dark => "Dark",
+            Theme::Dark => tr("theme.dark"),
         }
     }
 
@@ -60,6 +61,16 @@ impl Theme {
         }
     }
 
+    /// Last move when the engine made it, so its replies stand out from the player's moves
+    pub fn engine_move_highlight(&self) -> Color32 {
+        match self {
+            Theme::Classic => Color32::from_rgb(130, 180, 210),
+            Theme::Lichess => Color32::from_rgb(130, 180, 210),
+            Theme::ChessCom => Color32::from_rgb(120, 190, 230),
+            Theme::Dark => Color32::from_rgb(70, 120, 160),
+        }
+    }
+
     pub fn legal_move_dot(&self) -> Color32 {
         Color32::from_rgba_unmultiplied(0, 0, 0, 40)
     }
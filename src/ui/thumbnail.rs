@@ -0,0 +1,61 @@
+use crate::ui::{PieceRenderer, Theme};
+use egui::{pos2, vec2, Color32, Rect, Response, Sense, Ui};
+use shakmaty::{fen::Fen, Board, File, Rank, Square};
+
+/// A small, static, non-interactive board preview of a FEN position
+pub struct BoardThumbnail;
+
+impl BoardThumbnail {
+    pub fn show(
+        ui: &mut Ui,
+        fen: &str,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+        size: f32,
+    ) -> Response {
+        let (rect, response) = ui.allocate_exact_size(vec2(size, size), Sense::click());
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        let board = fen
+            .parse::<Fen>()
+            .map(|f| f.into_setup().board)
+            .unwrap_or_else(|_| Board::empty());
+        let square_size = size / 8.0;
+        let piece_size = square_size.round() as u32;
+
+        for rank_idx in 0u8..8 {
+            for file_idx in 0u8..8 {
+                let square = Square::from_coords(File::new(file_idx as u32), Rank::new(rank_idx as u32));
+                let square_rect = Rect::from_min_size(
+                    rect.min + vec2(file_idx as f32 * square_size, (7 - rank_idx) as f32 * square_size),
+                    vec2(square_size, square_size),
+                );
+
+                let is_light = (file_idx + rank_idx) % 2 == 1;
+                let bg_color = if is_light {
+                    theme.light_square()
+                } else {
+                    theme.dark_square()
+                };
+                ui.painter().rect_filled(square_rect, 0.0, bg_color);
+
+                if let Some(piece) = board.piece_at(square) {
+                    if piece_size > 0 {
+                        if let Some(texture) = piece_renderer.get_texture(ui.ctx(), piece.role, piece.color, piece_size) {
+                            ui.painter().image(
+                                texture.id(),
+                                square_rect,
+                                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                                Color32::WHITE,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,174 @@
+use crate::error::AppError;
+use crate::i18n::tr;
+use egui::{Align2, Color32, Context, Id, RichText};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn color(&self) -> Color32 {
+        match self {
+            ToastLevel::Info => Color32::LIGHT_BLUE,
+            ToastLevel::Warning => Color32::YELLOW,
+            ToastLevel::Error => Color32::from_rgb(255, 100, 100),
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            ToastLevel::Info => "ℹ",
+            ToastLevel::Warning => "⚠",
+            ToastLevel::Error => "❌",
+        }
+    }
+
+    /// How long a toast of this level stays on screen
+    fn lifetime(&self) -> Duration {
+        match self {
+            ToastLevel::Info => Duration::from_secs(4),
+            ToastLevel::Warning => Duration::from_secs(6),
+            ToastLevel::Error => Duration::from_secs(10),
+        }
+    }
+}
+
+/// Follow-up the user can trigger from a toast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastAction {
+    OpenSettings,
+    ShowEngineLog,
+    /// Go to the chapter already holding the line just added to a study
+    GoToDuplicateLine,
+}
+
+impl ToastAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ToastAction::OpenSettings => tr("toast.open_settings"),
+            ToastAction::ShowEngineLog => tr("toast.show_engine_log"),
+            ToastAction::GoToDuplicateLine => tr("toast.go_to_duplicate"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    /// What the user can do about it, under the message
+    pub hint: Option<String>,
+    pub action: Option<ToastAction>,
+    created_at: Instant,
+}
+
+/// Non-blocking notifications stacked in the bottom-right corner
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, message: impl Into<String>, action: Option<ToastAction>) {
+        let message = message.into();
+        // Don't stack identical messages (e.g. repeated engine errors)
+        self.toasts.retain(|t| t.message != message);
+        self.toasts.push(Toast {
+            level,
+            message,
+            hint: None,
+            action,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Show `error` with how to fix it, offering `action`
+    pub fn report(&mut self, error: &AppError, action: Option<ToastAction>) {
+        self.report_as(ToastLevel::Error, error, action);
+    }
+
+    /// Like `report`, for problems that didn't stop anything
+    pub fn report_warning(&mut self, error: &AppError, action: Option<ToastAction>) {
+        self.report_as(ToastLevel::Warning, error, action);
+    }
+
+    fn report_as(&mut self, level: ToastLevel, error: &AppError, action: Option<ToastAction>) {
+        self.push(level, error.to_string(), action);
+        if let Some(toast) = self.toasts.last_mut() {
+            toast.hint = Some(error.remediation().to_string());
+        }
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Info, message, None);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Warning, message, None);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Error, message, None);
+    }
+
+    /// Shows all active toasts and returns an action if the user clicked one
+    pub fn show(&mut self, ctx: &Context) -> Option<ToastAction> {
+        self.toasts.retain(|t| t.created_at.elapsed() < t.level.lifetime());
+        if self.toasts.is_empty() {
+            return None;
+        }
+
+        let mut action = None;
+        let mut dismissed = None;
+
+        egui::Area::new(Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -36.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                for (idx, toast) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(toast.level.icon()).color(toast.level.color()));
+                            ui.label(&toast.message);
+                        });
+                        if let Some(hint) = &toast.hint {
+                            ui.weak(hint);
+                        }
+                        ui.horizontal(|ui| {
+                            if let Some(toast_action) = toast.action {
+                                if ui.button(toast_action.label()).clicked() {
+                                    action = Some(toast_action);
+                                    dismissed = Some(idx);
+                                }
+                            }
+                            if ui.small_button(tr("toast.dismiss")).clicked() {
+                                dismissed = Some(idx);
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(idx) = dismissed {
+            self.toasts.remove(idx);
+        }
+
+        // Wake up again when the next toast expires
+        if let Some(remaining) = self
+            .toasts
+            .iter()
+            .map(|t| t.level.lifetime().saturating_sub(t.created_at.elapsed()))
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+
+        action
+    }
+}
@@ -0,0 +1,115 @@
+use crate::engine::DifficultyLevel;
+use crate::i18n::{format_decimal, tr, tr_args};
+use crate::tournament::{elo_gap, Tournament, TournamentSettings};
+use egui::{Color32, RichText, Ui};
+
+/// What the user did in the tournament panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentAction {
+    Start,
+    Stop,
+    ExportPgn,
+}
+
+/// Settings, progress and crosstable of an engine round robin
+pub struct TournamentPanel;
+
+impl TournamentPanel {
+    pub fn show(ui: &mut Ui, settings: &mut TournamentSettings, tournament: Option<&Tournament>) -> Option<TournamentAction> {
+        let mut action = None;
+        let running = tournament.is_some_and(|t| !t.finished);
+
+        ui.add_enabled_ui(!running, |ui| {
+            ui.label(tr("tournament.levels"));
+            ui.horizontal_wrapped(|ui| {
+                for &level in DifficultyLevel::all() {
+                    let mut chosen = settings.levels.contains(&level);
+                    if ui.checkbox(&mut chosen, level.label()).changed() {
+                        if chosen {
+                            settings.levels.push(level);
+                        } else {
+                            settings.levels.retain(|l| *l != level);
+                        }
+                    }
+                }
+            });
+            ui.add(
+                egui::TextEdit::singleline(&mut settings.second_engine).hint_text(tr("tournament.second_engine_hint")),
+            );
+            ui.checkbox(&mut settings.double_round, tr("tournament.double_round"));
+            ui.horizontal(|ui| {
+                ui.label(tr("tournament.movetime"));
+                ui.add(egui::DragValue::new(&mut settings.movetime_ms).range(50..=5000).suffix(" ms"));
+            });
+        });
+
+        let entrants = settings.levels.len() + usize::from(!settings.second_engine.trim().is_empty());
+        ui.horizontal(|ui| {
+            if running {
+                if ui.button(tr("tournament.stop")).clicked() {
+                    action = Some(TournamentAction::Stop);
+                }
+            } else if ui
+                .add_enabled(entrants >= 2, egui::Button::new(tr("tournament.start")))
+                .on_disabled_hover_text(tr("tournament.need_two"))
+                .clicked()
+            {
+                action = Some(TournamentAction::Start);
+            }
+            if tournament.is_some_and(|t| !t.games.is_empty()) && ui.button(tr("tournament.export")).clicked() {
+                action = Some(TournamentAction::ExportPgn);
+            }
+        });
+
+        let Some(tournament) = tournament else {
+            return action;
+        };
+        if running {
+            let done = tournament.games.len();
+            ui.add(
+                egui::ProgressBar::new(done as f32 / tournament.total_games.max(1) as f32)
+                    .text(tr_args("tournament.progress", &[&(done + 1), &tournament.total_games])),
+            );
+        }
+        if let Some(error) = &tournament.error {
+            ui.colored_label(Color32::from_rgb(230, 80, 80), tr_args("tournament.failed", &[error]));
+        }
+        Self::show_crosstable(ui, tournament);
+        action
+    }
+
+    fn show_crosstable(ui: &mut Ui, tournament: &Tournament) {
+        let count = tournament.entrants.len();
+        egui::Grid::new("tournament_crosstable").striped(true).show(ui, |ui| {
+            ui.label("");
+            ui.label("");
+            for opponent in 0..count {
+                ui.label(RichText::new((opponent + 1).to_string()).strong());
+            }
+            ui.label(RichText::new(tr("tournament.score")).strong());
+            ui.label(RichText::new(tr("tournament.elo")).strong())
+                .on_hover_text(tr("tournament.elo_hint"));
+            ui.end_row();
+
+            for (index, entrant) in tournament.entrants.iter().enumerate() {
+                ui.label(RichText::new((index + 1).to_string()).strong());
+                ui.label(&entrant.name);
+                for opponent in 0..count {
+                    let cell = match tournament.head_to_head(index, opponent) {
+                        _ if opponent == index => "—".to_string(),
+                        Some(points) => format_decimal(points, 1),
+                        None => "·".to_string(),
+                    };
+                    ui.monospace(cell);
+                }
+                let (points, games) = tournament.score(index);
+                ui.monospace(format!("{}/{}", format_decimal(points, 1), games));
+                match elo_gap(points, games) {
+                    Some(gap) => ui.monospace(format!("{:+.0}", gap)),
+                    None => ui.weak("–"),
+                };
+                ui.end_row();
+            }
+        });
+    }
+}
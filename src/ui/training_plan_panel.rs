@@ -0,0 +1,41 @@
+use crate::i18n::{tr, tr_args};
+use crate::training::TrainingPlan;
+use chrono::NaiveDate;
+use egui::{Color32, RichText, Ui};
+
+const MET_COLOR: Color32 = Color32::from_rgb(90, 170, 90);
+
+/// Daily goals with today's progress toward each and the streak of days they were met
+pub struct TrainingPlanPanel;
+
+impl TrainingPlanPanel {
+    pub fn show(ui: &mut Ui, plan: &mut TrainingPlan, today: NaiveDate) {
+        let progress = plan.progress(today);
+        let rows = [
+            (&mut plan.goals.puzzles, progress.puzzles, "plan.puzzles", "plan.puzzles_hint"),
+            (&mut plan.goals.lines, progress.lines, "plan.lines", "plan.lines_hint"),
+            (&mut plan.goals.reviews, progress.reviews, "plan.reviews", "plan.reviews_hint"),
+        ];
+        egui::Grid::new("training_plan").num_columns(3).show(ui, |ui| {
+            for (goal, done, label, hint) in rows {
+                ui.label(tr(label)).on_hover_text(tr(hint));
+                ui.add(egui::DragValue::new(goal).range(0..=50));
+                if *goal == 0 {
+                    ui.weak("–");
+                } else {
+                    let fraction = (done as f32 / *goal as f32).min(1.0);
+                    let bar = egui::ProgressBar::new(fraction)
+                        .desired_width(90.0)
+                        .text(format!("{} / {}", done.min(*goal), goal));
+                    ui.add(if done >= *goal { bar.fill(MET_COLOR) } else { bar });
+                }
+                ui.end_row();
+            }
+        });
+
+        if progress.meets(&plan.goals) {
+            ui.label(RichText::new(tr("plan.done_today")).color(MET_COLOR));
+        }
+        ui.label(tr_args("plan.streak", &[&plan.streak(today), &plan.best]));
+    }
+}
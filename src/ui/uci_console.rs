@@ -0,0 +1,185 @@
+use crate::i18n::tr;
+use egui::{Color32, Context, Key, RichText};
+use std::collections::VecDeque;
+
+/// Lines kept before the oldest are dropped
+const MAX_LINES: usize = 1000;
+
+/// Commands remembered for recall with the arrow keys
+const MAX_HISTORY: usize = 100;
+
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 170, 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConsoleLine {
+    Sent(String),
+    Received(String),
+}
+
+/// What a console command does to the app's own use of the engine, as an i18n key
+pub fn console_warning(command: &str) -> Option<&'static str> {
+    let mut words = command.split_whitespace();
+    let key = match words.next()? {
+        "go" | "stop" | "ponderhit" => "console.warn_search",
+        "position" | "ucinewgame" => "console.warn_position",
+        "uci" | "isready" => "console.warn_handshake",
+        "quit" => "console.warn_quit",
+        "setoption" => {
+            let option = words.skip_while(|w| *w != "name").skip(1).take_while(|w| *w != "value");
+            let name = option.collect::<Vec<_>>().join(" ").to_ascii_lowercase();
+            match name.as_str() {
+                "multipv" | "threads" | "uci_limitstrength" | "uci_elo" | "skill level" => "console.warn_option",
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Raw UCI commands typed by the user and the engine's answers
+#[derive(Debug, Default)]
+pub struct UciConsole {
+    lines: VecDeque<ConsoleLine>,
+    input: String,
+    history: Vec<String>,
+    /// Position in `history` while recalling with the arrow keys
+    recalled: Option<usize>,
+}
+
+impl UciConsole {
+    pub fn push_output(&mut self, line: String) {
+        self.push(ConsoleLine::Received(line));
+    }
+
+    fn push(&mut self, line: ConsoleLine) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Move through earlier commands, `back` toward the oldest
+    fn recall(&mut self, back: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.recalled = match (self.recalled, back) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.input = self.recalled.map(|i| self.history[i].clone()).unwrap_or_default();
+    }
+
+    /// Take the typed command, remembering it
+    fn submit(&mut self) -> Option<String> {
+        let command = self.input.trim().to_string();
+        self.input.clear();
+        self.recalled = None;
+        if command.is_empty() {
+            return None;
+        }
+        if self.history.last() != Some(&command) {
+            self.history.push(command.clone());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.push(ConsoleLine::Sent(command.clone()));
+        Some(command)
+    }
+}
+
+/// Window for typing UCI commands to the running engine
+pub struct UciConsoleWindow;
+
+impl UciConsoleWindow {
+    /// Shows the console; returns a command to send to the engine
+    pub fn show(ctx: &Context, open: &mut bool, console: &mut UciConsole) -> Option<String> {
+        let mut command = None;
+        egui::Window::new(tr("console.title"))
+            .open(open)
+            .default_size([520.0, 360.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("engine_log.clear")).clicked() {
+                        console.lines.clear();
+                    }
+                    ui.weak(tr("console.hint"));
+                });
+                ui.separator();
+
+                let input_height = ui.spacing().interact_size.y * 3.0;
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .max_height((ui.available_height() - input_height).max(60.0))
+                    .show(ui, |ui| {
+                        for line in &console.lines {
+                            match line {
+                                ConsoleLine::Sent(text) => ui.label(RichText::new(format!("> {}", text)).monospace().strong()),
+                                ConsoleLine::Received(text) => ui.label(RichText::new(text).monospace()),
+                            };
+                        }
+                    });
+                ui.separator();
+
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut console.input)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text(tr("console.input_hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                if field.has_focus() {
+                    if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
+                        console.recall(true);
+                    } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
+                        console.recall(false);
+                    }
+                }
+                if field.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                    command = console.submit();
+                    field.request_focus();
+                }
+                if let Some(warning) = console_warning(&console.input) {
+                    ui.label(RichText::new(format!("⚠ {}", tr(warning))).color(WARNING_COLOR));
+                }
+            });
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_warning() {
+        assert_eq!(console_warning("go depth 10"), Some("console.warn_search"));
+        assert_eq!(console_warning("  position startpos"), Some("console.warn_position"));
+        assert_eq!(console_warning("setoption name Skill Level value 3"), Some("console.warn_option"));
+        assert_eq!(console_warning("setoption name Hash value 64"), None);
+        assert_eq!(console_warning("eval"), None);
+        assert_eq!(console_warning(""), None);
+    }
+
+    #[test]
+    fn test_console_history() {
+        let mut console = UciConsole::default();
+        for command in ["d", "eval", "eval"] {
+            console.input = command.to_string();
+            console.submit();
+        }
+        assert_eq!(console.history, vec!["d", "eval"]);
+        console.recall(true);
+        console.recall(true);
+        assert_eq!(console.input, "d");
+        console.recall(false);
+        assert_eq!(console.input, "eval");
+        console.recall(false);
+        assert_eq!(console.input, "");
+    }
+}
@@ -0,0 +1,233 @@
+use crate::i18n::{format_decimal, tr, tr_args};
+use crate::training::{VisionDrill, VisionExercise, VisionHistory};
+use crate::ui::{PieceRenderer, Theme};
+use egui::{pos2, vec2, Color32, Id, Rect, RichText, Sense, Stroke, Ui};
+use shakmaty::{File, Rank, Square};
+use std::time::Instant;
+
+/// Number of recent results shown as ✔/✖ marks
+const RECENT_RESULTS: usize = 12;
+
+const CORRECT_COLOR: Color32 = Color32::from_rgb(80, 200, 120);
+const WRONG_COLOR: Color32 = Color32::from_rgb(230, 80, 80);
+
+/// Knight-route and attacked-square drills played on an empty board
+pub struct VisionTrainer {
+    exercise: VisionExercise,
+    /// Squares clicked so far, in order
+    answer: Vec<Square>,
+    started: Instant,
+    /// Whether the current exercise was answered correctly, once answered
+    result: Option<bool>,
+}
+
+impl Default for VisionTrainer {
+    fn default() -> Self {
+        Self::new(VisionDrill::KnightPath)
+    }
+}
+
+impl VisionTrainer {
+    fn new(drill: VisionDrill) -> Self {
+        Self {
+            exercise: VisionExercise::generate(drill),
+            answer: Vec::new(),
+            started: Instant::now(),
+            result: None,
+        }
+    }
+
+    fn next(&mut self, drill: VisionDrill) {
+        *self = Self::new(drill);
+    }
+
+    fn finish(&mut self, correct: bool, history: &mut VisionHistory) {
+        if self.result.is_none() {
+            self.result = Some(correct);
+            history.record(self.exercise.drill(), correct, self.started.elapsed().as_secs_f32());
+        }
+    }
+
+    /// Sidebar with the question, answer controls and score history
+    pub fn show_panel(&mut self, ui: &mut Ui, history: &mut VisionHistory) {
+        ui.heading(tr("vision.heading"));
+        ui.separator();
+
+        let drill = self.exercise.drill();
+        ui.horizontal(|ui| {
+            for d in VisionDrill::all() {
+                if ui.selectable_label(drill == *d, d.label()).clicked() && drill != *d {
+                    self.next(*d);
+                }
+            }
+        });
+        ui.add_space(8.0);
+
+        ui.label(RichText::new(self.exercise.prompt()).strong());
+        if let VisionExercise::KnightPath { .. } = self.exercise {
+            ui.weak(tr_args("vision.moves_so_far", &[&self.answer.len()]));
+        }
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            if self.result.is_none() {
+                if matches!(self.exercise, VisionExercise::Attacks { .. }) && ui.button(tr("vision.check")).clicked() {
+                    let correct = self.exercise.check(&self.answer);
+                    self.finish(correct, history);
+                }
+                if ui.button(tr("vision.clear")).clicked() {
+                    self.answer.clear();
+                }
+                if ui.button(tr("vision.reveal")).clicked() {
+                    self.finish(false, history);
+                }
+            } else if ui.button(tr("vision.next")).clicked() {
+                self.next(drill);
+            }
+        });
+
+        match self.result {
+            Some(true) => {
+                ui.colored_label(CORRECT_COLOR, tr("vision.correct"));
+            }
+            Some(false) => {
+                ui.colored_label(WRONG_COLOR, tr("vision.wrong"));
+            }
+            None => {}
+        }
+
+        ui.separator();
+
+        // Score and history
+        ui.label(tr_args("vision.streak", &[&history.streak()]));
+        for d in VisionDrill::all() {
+            let (correct, total, average) = history.stats(*d);
+            if total > 0 {
+                ui.label(tr_args(
+                    "vision.stats",
+                    &[&d.label(), &correct, &total, &format_decimal(average, 1)],
+                ));
+            }
+        }
+        let recent: String = history
+            .results
+            .iter()
+            .rev()
+            .take(RECENT_RESULTS)
+            .map(|r| if r.correct { '✔' } else { '✖' })
+            .collect();
+        if !recent.is_empty() {
+            ui.weak(recent);
+        }
+    }
+
+    /// The exercise board; clicks build the answer
+    pub fn show_board(
+        &mut self,
+        ui: &mut Ui,
+        theme: Theme,
+        piece_renderer: &mut PieceRenderer,
+        flipped: bool,
+        history: &mut VisionHistory,
+    ) {
+        let size = ui.available_width().min(ui.available_height()).max(0.0);
+        let (board_rect, _) = ui.allocate_exact_size(vec2(size, size), Sense::hover());
+        let square_size = size / 8.0;
+        let pieces = self.exercise.pieces();
+        let solution = self.result.map(|_| self.exercise.solution()).unwrap_or_default();
+
+        for rank_idx in 0u32..8 {
+            for file_idx in 0u32..8 {
+                let square = Square::from_coords(File::new(file_idx), Rank::new(rank_idx));
+                let (display_file, display_rank) = if flipped {
+                    (7 - file_idx, rank_idx)
+                } else {
+                    (file_idx, 7 - rank_idx)
+                };
+                let rect = Rect::from_min_size(
+                    board_rect.min + vec2(display_file as f32 * square_size, display_rank as f32 * square_size),
+                    vec2(square_size, square_size),
+                );
+
+                let is_light = (file_idx + rank_idx) % 2 == 1;
+                let chosen = self.answer.iter().position(|s| *s == square);
+                let is_target = matches!(self.exercise, VisionExercise::KnightPath { to, .. } if to == square);
+                let bg = if chosen.is_some() {
+                    theme.selected_square()
+                } else if is_target {
+                    theme.last_move_highlight()
+                } else if is_light {
+                    theme.light_square()
+                } else {
+                    theme.dark_square()
+                };
+                ui.painter().rect_filled(rect, 0.0, bg);
+
+                if let Some((_, piece)) = pieces.iter().find(|(s, _)| *s == square) {
+                    let piece_size = (square_size * 0.9) as u32;
+                    if let Some(texture) = piece_renderer.get_texture(ui.ctx(), piece.role, piece.color, piece_size) {
+                        ui.painter().image(
+                            texture.id(),
+                            Rect::from_center_size(rect.center(), vec2(square_size * 0.9, square_size * 0.9)),
+                            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                            Color32::WHITE,
+                        );
+                    }
+                }
+
+                // Number the steps of a knight route
+                if let (Some(idx), VisionExercise::KnightPath { .. }) = (chosen, &self.exercise) {
+                    ui.painter().text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        (idx + 1).to_string(),
+                        egui::FontId::proportional(square_size * 0.4),
+                        Color32::BLACK,
+                    );
+                }
+
+                // After answering, ring the expected squares and mark wrong picks
+                if self.result.is_some() {
+                    if solution.contains(&square) {
+                        ui.painter().circle_stroke(
+                            rect.center(),
+                            square_size * 0.4,
+                            Stroke::new(square_size * 0.06, CORRECT_COLOR),
+                        );
+                    } else if chosen.is_some() && matches!(self.exercise, VisionExercise::Attacks { .. }) {
+                        ui.painter().circle_filled(rect.center(), square_size * 0.12, WRONG_COLOR);
+                    }
+                }
+
+                let response = ui.interact(rect, Id::new(("vision_square", file_idx, rank_idx)), Sense::click());
+                if response.clicked() && self.result.is_none() && self.exercise.accepts(square) {
+                    self.click(square, history);
+                }
+            }
+        }
+    }
+
+    fn click(&mut self, square: Square, history: &mut VisionHistory) {
+        match &self.exercise {
+            VisionExercise::KnightPath { to, .. } => {
+                // Clicking the last step again takes it back
+                if self.answer.last() == Some(&square) {
+                    self.answer.pop();
+                    return;
+                }
+                self.answer.push(square);
+                if square == *to {
+                    let correct = self.exercise.check(&self.answer);
+                    self.finish(correct, history);
+                }
+            }
+            VisionExercise::Attacks { .. } => {
+                if let Some(idx) = self.answer.iter().position(|s| *s == square) {
+                    self.answer.remove(idx);
+                } else {
+                    self.answer.push(square);
+                }
+            }
+        }
+    }
+}